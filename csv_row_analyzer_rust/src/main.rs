@@ -11,11 +11,15 @@
 //!
 //! # With custom output directory
 //! $ cargo run --release -- path/to/large_file.csv custom/output/dir
+//!
+//! # With house recommendation rules from a config file
+//! $ cargo run --release -- path/to/large_file.csv --config analyzer.conf
+//!
+//! # Read the input with tokio async I/O
+//! $ cargo run --release --features async -- path/to/large_file.csv --async
 //! ```
 
-// Import the analyzer module
-mod csv_row_analyzer;
-use csv_row_analyzer::csv_row_analyzer_main;
+use csv_tools_core::csv_row_analyzer_main;
 
 
 /// call from module
@@ -0,0 +1,637 @@
+//! Command-line parsing, help, and completion scripts.
+//!
+//! A flag that needs a value must not swallow the next flag, paths that start with `--`
+//! must be reachable after `--`, help and completions must list the registered flags, and
+//! `--porcelain` must print one line and nothing else. `selftest` must pass on every
+//! platform the tests run on, `bench-compare` must compare two engines, `translate-rows`
+//! must map lines to `--csv-mode` records and back, and `--layout dated` must file reports
+//! by date and basename. `check` must print `file:line:column` problems and fail on them.
+//! `--recursive` and `--glob` must name the reports of subdirectory files by their relative path,
+//! and a directory run must end with a summary of every file, listing the skipped and
+//! unreadable ones and exiting with 6 when a file could not be read. `--threads` must not
+//! change the output of a directory run.
+//! An input of `-` must be read from stdin, with only the summary
+//! on stdout under `--stdout-summary`. The list of generated reports at the end of a run
+//! must name exactly the files in the report directory.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use csv_tools_core::test_support::scratch_dir;
+
+/// Creates a scratch directory holding a small CSV, unique to this test run.
+fn fixture_dir(name: &str) -> PathBuf {
+    let dir = scratch_dir(name);
+    fs::write(dir.join("data.csv"), "id,name\n1,Ana\n2,Bartholomew\n").expect("write fixture");
+    dir
+}
+
+/// Runs the analyzer in `dir` with the given arguments.
+fn run_analyzer(dir: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_csv_row_analyzer_rust"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("run analyzer")
+}
+
+#[test]
+fn value_flag_does_not_swallow_the_next_flag() {
+    let dir = fixture_dir("swallow");
+    let output = run_analyzer(&dir, &["data.csv", "out", "--history", "--entropy"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--history requires a <path> argument"), "{}", stderr);
+    assert!(!dir.join("--entropy").exists());
+}
+
+#[test]
+fn inline_values_and_separator_allow_dashed_names() {
+    let dir = fixture_dir("dashed");
+    let output = run_analyzer(&dir, &["--history=--runs.csv", "data.csv", "--", "--out"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.join("--runs.csv").is_file());
+    assert!(dir.join("--out").is_dir());
+}
+
+#[test]
+fn extra_positional_arguments_are_rejected() {
+    let dir = fixture_dir("extra");
+    let output = run_analyzer(&dir, &["data.csv", "out", "leftover"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unexpected argument: leftover"));
+
+    let output = run_analyzer(&dir, &["--directory", ".", "data.csv", "out"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn help_and_completions_list_registered_flags() {
+    let dir = fixture_dir("help");
+    let help = run_analyzer(&dir, &["--help"]);
+    assert!(help.status.success());
+    let help = String::from_utf8_lossy(&help.stdout);
+    for flag in ["--directory <path>", "--rule <rule>", "--entropy", "preview-row"] {
+        assert!(help.contains(flag), "help is missing {}", flag);
+    }
+
+    for shell in ["bash", "zsh", "fish"] {
+        let script = run_analyzer(&dir, &["completions", shell]);
+        assert!(script.status.success(), "{}", String::from_utf8_lossy(&script.stderr));
+        let script = String::from_utf8_lossy(&script.stdout);
+        assert!(script.contains("length-buckets") && script.contains("validate-encoding"), "{} script incomplete", shell);
+    }
+}
+
+#[test]
+fn porcelain_prints_exactly_one_line() {
+    let dir = fixture_dir("porcelain");
+    let output = run_analyzer(&dir, &["data.csv", "out", "--porcelain"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "{}", stdout);
+    let fields: Vec<&str> = lines[0].split('\t').collect();
+    assert_eq!(fields.len(), 13);
+    assert_eq!(&fields[..5], ["6", "ok", "data.csv", "3", "0"]);
+
+    let output = run_analyzer(&dir, &["missing.csv", "out", "--porcelain"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("6\tfailed\tmissing.csv\t"), "{}", stdout);
+    assert_eq!(stdout.lines().count(), 1);
+}
+
+#[test]
+fn selftest_passes_its_corpus() {
+    let dir = fixture_dir("selftest");
+    let output = run_analyzer(&dir, &["selftest"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("7 of 7 cases passed"), "{}", stdout);
+
+    let output = run_analyzer(&dir, &["selftest", "--case", "no_such_case"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn bench_compare_checks_both_engines() {
+    let dir = fixture_dir("bench_compare");
+    let output = run_analyzer(&dir, &["bench-compare", "data.csv"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Results: identical (3 rows, 3 distinct lengths)"), "{}", stdout);
+
+    let output = run_analyzer(&dir, &["bench-compare", "no_such_file.csv"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn dated_layout_files_reports_by_date_and_basename() {
+    let dir = fixture_dir("layout");
+    let output = run_analyzer(&dir, &["data.csv", "out", "--layout", "dated"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let dates: Vec<PathBuf> = fs::read_dir(dir.join("out")).expect("output dir").map(|entry| entry.expect("entry").path()).collect();
+    assert_eq!(dates.len(), 1);
+    let date = dates[0].file_name().and_then(|name| name.to_str()).expect("date dir");
+    assert!(date.len() == 10 && date.as_bytes()[4] == b'-' && date.as_bytes()[7] == b'-', "{}", date);
+    let reports = fs::read_dir(dates[0].join("data")).expect("basename dir").count();
+    assert_eq!(reports, 5);
+}
+
+#[test]
+fn no_reports_prints_the_summary_and_writes_nothing() {
+    let dir = fixture_dir("no_reports");
+    let output = run_analyzer(&dir, &["data.csv", "out", "--no-reports"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Summary of data (no reports written):\n  Rows: 3 (0 unreadable), 25 characters"), "{}", stdout);
+    assert!(!stdout.contains("Report status:"), "{}", stdout);
+
+    let output = run_analyzer(&dir, &["data.csv", "out", "--no-reports", "--format", "json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = &stdout[stdout.find("{\n").expect("JSON document")..];
+    assert!(json.contains("\"total_rows\": 3,") && json.ends_with("}\n"), "{}", stdout);
+
+    let entries: Vec<_> = fs::read_dir(&dir).expect("scratch dir").map(|entry| entry.expect("entry").file_name()).collect();
+    assert_eq!(entries, ["data.csv"]);
+
+    let output = run_analyzer(&dir, &["data.csv", "out", "--no-reports", "--history", "history.csv"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--history writes to disk"));
+}
+
+#[test]
+fn stdin_input_prints_only_the_summary_with_stdout_summary() {
+    let dir = fixture_dir("stdin");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_csv_row_analyzer_rust"))
+        .current_dir(&dir)
+        .args(["-", "--stdout-summary"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run analyzer");
+    child.stdin.take().expect("stdin").write_all(b"id,name\n1,Ana\n2,Bartholomew\n").expect("write stdin");
+    let output = child.wait_with_output().expect("wait for analyzer");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Summary of stdin (no reports written):\n  Rows: 3 (0 unreadable), 25 characters"), "{}", stdout);
+    let entries: Vec<_> = fs::read_dir(&dir).expect("scratch dir").map(|entry| entry.expect("entry").file_name()).collect();
+    assert_eq!(entries, ["data.csv"]);
+
+    let output = run_analyzer(&dir, &["-", "out", "--extract-outliers"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--extract-outliers reads the input again"));
+}
+
+#[test]
+fn check_prints_problems_as_file_line_column() {
+    let dir = fixture_dir("check");
+    fs::write(dir.join("broken.csv"), b"id,name\n1,Ana \n2,\"a\nb\",x\n3,Jos\xe9\n").expect("write fixture");
+    let output = run_analyzer(&dir, &["check", "data.csv", "broken.csv"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8_lossy(&output.stdout),
+               "broken.csv:2:6: trailing whitespace\nbroken.csv:3:1: record has 3 fields, the header has 2\nbroken.csv:5:6: invalid UTF-8 byte 0xE9\n");
+    assert_eq!(fs::read_dir(&dir).expect("scratch dir").count(), 2);
+
+    let output = run_analyzer(&dir, &["check", "data.csv"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert!(output.stdout.is_empty());
+
+    // Fields are split at the file's own delimiter
+    fs::write(dir.join("names.psv"), "id|name\n1|Ana, Bo\n2|Cy\n").expect("write fixture");
+    let output = run_analyzer(&dir, &["check", "names.psv"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    let output = run_analyzer(&dir, &["check", "names.psv", "--delimiter", "comma"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "names.psv:2:1: record has 2 fields, the header has 1\n");
+}
+
+#[test]
+fn recursive_directory_names_reports_by_relative_path() {
+    let dir = fixture_dir("recursive");
+    for month in ["01", "02"] {
+        fs::create_dir_all(dir.join("export").join(month)).expect("create subdirectory");
+        fs::write(dir.join("export").join(month).join("orders.csv"), "id,total\n1,9.50\n").expect("write fixture");
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(dir.join("export"), dir.join("export").join("01").join("loop")).expect("symlink loop");
+
+    let output = run_analyzer(&dir, &["--directory", "export", "out", "--recursive"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let mut reports: Vec<String> = fs::read_dir(dir.join("out")).expect("output dir")
+        .map(|entry| entry.expect("entry").file_name().to_string_lossy().into_owned())
+        .filter(|name| name.contains("_char_counts_report_"))
+        .collect();
+    reports.sort();
+    assert_eq!(reports.len(), 2, "{:?}", reports);
+    assert!(reports[0].starts_with("01__orders_char_counts_report_") && reports[1].starts_with("02__orders_char_counts_report_"), "{:?}", reports);
+
+    let output = run_analyzer(&dir, &["--directory", "export", "shallow", "--recursive", "--max-depth", "0"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!dir.join("shallow").exists());
+
+    let output = run_analyzer(&dir, &["data.csv", "out", "--recursive"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--recursive applies to --directory runs"));
+}
+
+#[test]
+fn glob_analyzes_the_matching_files_of_every_subdirectory() {
+    let dir = fixture_dir("glob");
+    for (subdirectory, name) in [("2023", "orders_2023.csv"), ("2024/q1", "orders_2024.csv"), ("2024/q1", "orders_2024.tsv"), ("", "totals_2024.csv")] {
+        fs::create_dir_all(dir.join("exports").join(subdirectory)).expect("create subdirectory");
+        fs::write(dir.join("exports").join(subdirectory).join(name), "id,total\n1,9.50\n").expect("write fixture");
+    }
+
+    let output = run_analyzer(&dir, &["--glob", "exports/**/*_2024*.csv", "out", "--reports", "char_counts"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let mut reports: Vec<String> = fs::read_dir(dir.join("out")).expect("output dir")
+        .map(|entry| entry.expect("entry").file_name().to_string_lossy().into_owned())
+        .filter(|name| name.contains("_char_counts_report_"))
+        .collect();
+    reports.sort();
+    assert_eq!(reports.len(), 2, "{:?}", reports);
+    assert!(reports[0].starts_with("2024__q1__orders_2024_char_counts_report_") && reports[1].starts_with("totals_2024_char_counts_report_"), "{:?}", reports);
+
+    let output = run_analyzer(&dir, &["--glob", "exports/*.csv", "--directory", "exports"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--directory and --glob both select the input files"));
+}
+
+#[test]
+fn directory_run_writes_a_summary_of_every_file() {
+    let dir = fixture_dir("roll_up");
+    fs::write(dir.join("wide.csv"), "id,note\n1,a much longer note\n").expect("write fixture");
+    fs::write(dir.join(".hidden.csv"), "id\n1\n").expect("write fixture");
+    fs::write(dir.join("empty.csv"), "").expect("write fixture");
+    let output = run_analyzer(&dir, &["--directory", ".", "out", "--reports", "value_counts", "--skip-hidden", "--min-size", "1"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(read_directory_summary(&dir.join("out")), "\
+file,status,total_rows,error_rows,total_chars,max_length,mean_length,median_length,p99_length,outlier_rows,detail,report_schema_version
+data.csv,analyzed,3,0,25,13,8.33,7,13,0,,6
+wide.csv,analyzed,2,0,27,20,13.50,13,20,0,,6
+.hidden.csv,skipped,,,,,,,,,hidden file,6
+empty.csv,skipped,,,,,,,,,smaller than --min-size (0 of 1 bytes),6
+(all files),aggregate,5,0,52,20,10.40,7,20,0,,6
+");
+
+    // A file that cannot be opened is listed, the others are analyzed, and the run exits with 6
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let locked = dir.join("locked.csv");
+        fs::write(&locked, "id\n1\n").expect("write fixture");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).expect("lock fixture");
+        // Permissions do not stop the superuser
+        if fs::File::open(&locked).is_err() {
+            let output = run_analyzer(&dir, &["--directory", ".", "locked_out", "--reports", "value_counts", "--skip-hidden"]);
+            assert_eq!(output.status.code(), Some(6), "{}", String::from_utf8_lossy(&output.stderr));
+            assert!(String::from_utf8_lossy(&output.stderr).contains("locked.csv: could not be read"));
+            let summary = read_directory_summary(&dir.join("locked_out"));
+            assert!(summary.contains("\nlocked.csv,unreadable,,,,,,,,,permission denied,5\n"), "{}", summary);
+            assert!(summary.contains("\nwide.csv,analyzed,"), "{}", summary);
+        }
+    }
+}
+
+#[test]
+fn threads_keep_the_output_and_summary_of_a_serial_directory_run() {
+    let dir = fixture_dir("threads");
+    for i in 1..=8 {
+        let rows: String = (0..i * 300).map(|row| format!("{},{}\n", row, "x".repeat((row * 7 + i) % 40))).collect();
+        fs::write(dir.join(format!("feed_{}.csv", i)), format!("id,value\n{}", rows)).expect("write fixture");
+    }
+    let serial = run_analyzer(&dir, &["--directory", ".", "serial", "--porcelain", "--history", "serial/history.csv"]);
+    assert!(serial.status.success(), "{}", String::from_utf8_lossy(&serial.stderr));
+    let threaded = run_analyzer(&dir, &["--directory", ".", "threaded", "--porcelain", "--history", "threaded/history.csv", "--threads", "4"]);
+    assert!(threaded.status.success(), "{}", String::from_utf8_lossy(&threaded.stderr));
+
+    // The porcelain lines come in file order, and every file is summarized the same way
+    assert_eq!(String::from_utf8_lossy(&serial.stdout).lines().count(), 9);
+    assert_eq!(String::from_utf8_lossy(&threaded.stdout), String::from_utf8_lossy(&serial.stdout));
+    assert_eq!(read_directory_summary(&dir.join("threaded")), read_directory_summary(&dir.join("serial")));
+    // Every file appended one whole line to the shared history file, after a single header
+    let history = fs::read_to_string(dir.join("threaded").join("history.csv")).expect("read history");
+    assert_eq!(history.lines().count(), 10, "{}", history);
+    assert_eq!(history.lines().filter(|line| line.starts_with("run_timestamp,")).count(), 1);
+}
+
+#[test]
+fn directory_run_reads_tsv_files_at_their_own_delimiter() {
+    let dir = fixture_dir("tsv_directory");
+    fs::write(dir.join("orders.csv"), "id;total\n1;9,50\n2;12,00\n").expect("write fixture");
+    fs::write(dir.join("cities.tsv"), "city\tcountry\nLima, Peru\tPE\nOslo, Norway\tNO\n").expect("write fixture");
+
+    let output = run_analyzer(&dir, &["--directory", ".", "csv_only", "--reports", "outliers_md"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let summary = read_directory_summary(&dir.join("csv_only"));
+    assert!(summary.contains("\ncities.tsv,skipped,,,,,,,,,extension not selected (add tsv to --extensions),6\n"), "{}", summary);
+
+    let output = run_analyzer(&dir, &["--directory", ".", "out", "--extensions", "csv,tsv", "--reports", "outliers_md", "--field-counts"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let markdown = |basename: &str| {
+        let path = fs::read_dir(dir.join("out")).expect("read reports")
+            .map(|entry| entry.expect("dir entry").path())
+            .find(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(&format!("{}_md_outliers_report_", basename))))
+            .expect("markdown report");
+        fs::read_to_string(path).expect("read markdown report")
+    };
+    let cities = markdown("cities");
+    assert!(cities.contains("*Delimiter: tab (file extension)*"), "{}", cities);
+    assert!(cities.contains("- **Header Columns**: 2"), "{}", cities);
+    let orders = markdown("orders");
+    assert!(orders.contains("*Delimiter: semicolon (detected)*"), "{}", orders);
+    assert!(orders.contains("- **Header Columns**: 2"), "{}", orders);
+}
+
+/// Reads the CSV directory summary written to `output_dir`, checking its name.
+fn read_directory_summary(output_dir: &PathBuf) -> String {
+    let summaries: Vec<PathBuf> = fs::read_dir(output_dir).expect("output dir")
+        .map(|entry| entry.expect("entry").path())
+        .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().contains("_directory_summary_")))
+        .collect();
+    assert_eq!(summaries.len(), 2, "{:?}", summaries);
+    let csv = summaries.iter().find(|path| path.extension().is_some_and(|extension| extension == "csv")).expect("CSV summary");
+    let name = csv.file_name().and_then(|name| name.to_str()).expect("summary name");
+    let directory = output_dir.parent().and_then(|dir| dir.file_name()).expect("run directory").to_string_lossy();
+    assert!(name.starts_with(&format!("{}_directory_summary_", directory)), "{}", name);
+    fs::read_to_string(csv).expect("read summary")
+}
+
+#[test]
+fn translate_rows_maps_lines_and_records() {
+    let dir = fixture_dir("translate_rows");
+    fs::write(dir.join("notes.csv"), "id,note\n1,\"first\nsecond\nthird\"\n2,plain\n").expect("write fixture");
+    let output = run_analyzer(&dir, &["notes.csv", "out", "--csv-mode"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let report = fs::read_dir(dir.join("out")).expect("read reports")
+        .map(|entry| entry.expect("dir entry").path())
+        .find(|path| path.to_string_lossy().contains("_char_counts_report_"))
+        .expect("char_counts report");
+    let report = report.to_str().expect("report path");
+
+    let output = run_analyzer(&dir, &["translate-rows", report, "3,5"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout),
+               "line 3 -> row_index 1 (data_index 0), lines 2-4\nline 5 -> row_index 2 (data_index 1), lines 5-5\n");
+
+    let output = run_analyzer(&dir, &["translate-rows", report, "1", "--from", "record"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "row_index 1 (data_index 0) -> lines 2-4\n");
+
+    let output = run_analyzer(&dir, &["translate-rows", report, "6"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 6 is not in"));
+}
+
+#[test]
+fn query_answers_from_a_saved_state() {
+    let dir = fixture_dir("query_state");
+    // More rows of one length than the reports keep examples of
+    let rows: String = (1..=7).map(|id| format!("{},same\n", id)).collect();
+    fs::write(dir.join("notes.csv"), format!("id,description\n{}8,a much longer description\n9,\n", rows)).expect("write fixture");
+    let output = run_analyzer(&dir, &["notes.csv", "out", "--save-state", "--profile-columns"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let state = fs::read_dir(dir.join("out")).expect("read reports")
+        .map(|entry| entry.expect("dir entry").path())
+        .find(|path| path.extension().is_some_and(|extension| extension == "bin"))
+        .expect("state file");
+    let state = state.to_str().expect("state path");
+
+    let output = run_analyzer(&dir, &["query", state, "--rows-with-length-over", "5"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let same: String = (1..=7).map(|row| format!("{},6\n", row)).collect();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), format!("row_index,length\n0,14\n{}8,27\n", same));
+
+    let output = run_analyzer(&dir, &["query", state, "--column", "description", "--percentile", "100"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "description p100: 25 chars (8 non-empty values)\n");
+
+    // Nearest rank, as the reports take their p99: rank 8 of 10 rows, then rank 10
+    let output = run_analyzer(&dir, &["query", state, "--percentile", "80"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "rows p80: 6 chars (10 rows)\n");
+    let output = run_analyzer(&dir, &["query", state, "--percentile", "99"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "rows p99: 27 chars (10 rows)\n");
+
+    let output = run_analyzer(&dir, &["query", state, "--column", "missing", "--percentile", "50"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no column 'missing'"));
+}
+
+#[test]
+fn analyze_extract_and_split_subcommands() {
+    let dir = fixture_dir("subcommands");
+    fs::write(dir.join("rows.csv"), "id,text\n1,a\n2,a longer text\n3,bb\n4,the longest text of all\n").expect("write fixture");
+
+    let output = run_analyzer(&dir, &["analyze", "rows.csv", "out", "--reports", "char_counts"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(fs::read_dir(dir.join("out")).expect("read reports").count() > 0);
+
+    let output = run_analyzer(&dir, &["extract", "rows.csv", "--length-over", "4"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "id,text\n2,a longer text\n4,the longest text of all\n");
+
+    let output = run_analyzer(&dir, &["extract", "rows.csv", "--rows", "2..4", "--length-under", "10"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "id,text\n3,bb\n");
+
+    let output = run_analyzer(&dir, &["split", "rows.csv", "parts", "--lengths", "5,20"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let part = |name: &str| fs::read_to_string(dir.join("parts").join(name)).expect("read split file");
+    assert_eq!(part("rows_rows_under_5.csv"), "id,text\n1,a\n3,bb\n");
+    assert_eq!(part("rows_rows_5_20.csv"), "id,text\n2,a longer text\n");
+    assert_eq!(part("rows_rows_over_20.csv"), "id,text\n4,the longest text of all\n");
+}
+
+#[test]
+fn compare_reports_the_shift_between_two_files() {
+    let dir = fixture_dir("compare");
+    let rows: String = (10..30).map(|id| format!("{},abcd\n", id)).collect();
+    fs::write(dir.join("week_1.csv"), format!("id,text\n{}", rows)).expect("write baseline");
+    fs::write(dir.join("week_2.csv"), format!("id,text\n{}99,{}\n", rows, "x".repeat(40))).expect("write current");
+
+    let output = run_analyzer(&dir, &["compare", "week_1.csv", "week_2.csv", "out"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Rows: 21 -> 22 (+1)"), "{}", stdout);
+    assert!(stdout.contains("New outlier lengths: 1 (1 rows)"), "{}", stdout);
+
+    let report = |extension: &str| {
+        let path = fs::read_dir(dir.join("out")).expect("read reports")
+            .map(|entry| entry.expect("dir entry").path())
+            .find(|path| path.extension().is_some_and(|found| found == extension))
+            .expect("compare report");
+        assert!(path.file_name().unwrap().to_string_lossy().starts_with("week_1_vs_week_2_compare_"));
+        fs::read_to_string(path).expect("read compare report")
+    };
+    let csv = report("csv");
+    assert!(csv.starts_with("metric,baseline,current,delta,report_schema_version\nrows,21,22,1,"), "{}", csv);
+    assert!(csv.contains("\nmax,7,43,36,"), "{}", csv);
+    assert!(csv.contains("\nnew_outlier_lengths,,43,1,"), "{}", csv);
+    assert!(report("md").contains("| 43 | 1 |"));
+}
+
+/// File names listed under "Generated these report files" on standard output.
+fn listed_reports(stdout: &str) -> BTreeSet<String> {
+    stdout.lines()
+        .skip_while(|line| !line.starts_with("Generated these report files"))
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .filter(|line| !line.starts_with("  With --row-flags:"))
+        .map(|line| {
+            // "  name - description", or "  With --flag: name - description"
+            let entry = line.trim_start();
+            let entry = entry.strip_prefix("With --").and_then(|rest| rest.split_once(": ")).map_or(entry, |(_, entry)| entry);
+            let file_name = entry.split_once(" - ").map_or(entry, |(file_name, _)| file_name);
+            file_name.trim_end_matches("/rows_*.txt").to_string()
+        })
+        .collect()
+}
+
+/// File names in a report directory.
+fn report_files(dir: &Path) -> BTreeSet<String> {
+    fs::read_dir(dir).expect("read reports")
+        .map(|entry| entry.expect("dir entry").file_name().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn generated_reports_are_listed_by_their_file_names() {
+    let dir = fixture_dir("generated_reports");
+    fs::write(dir.join("t.csv"), "id,name\n1,Ana\n2,Bo\n3,Bartholomew\n").expect("write fixture");
+
+    let output = run_analyzer(&dir, &[
+        "t.csv", "--html-report", "--length-buckets", "5", "--column-totals", "--format", "json", "--save-state",
+    ]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Generated these report files with prefix 't_':"), "{}", stdout);
+    let listed = listed_reports(&stdout);
+    assert_eq!(listed, report_files(&dir.join("reports")), "{}", stdout);
+    assert!(listed.iter().any(|name| name.starts_with("t_md_outliers_report_") && name.ends_with(".md")), "{:?}", listed);
+
+    // A report that is not written is not listed
+    let output = run_analyzer(&dir, &["t.csv", "lengths", "--lengths-only"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let listed = listed_reports(&stdout);
+    assert_eq!(listed.len(), 2, "{}", stdout);
+    assert_eq!(listed, report_files(&dir.join("lengths")), "{}", stdout);
+}
+
+/// Reads the CSV report of a `compare` run.
+fn compare_csv_report(dir: &Path) -> String {
+    let path = fs::read_dir(dir).expect("read reports")
+        .map(|entry| entry.expect("dir entry").path())
+        .find(|path| path.extension().is_some_and(|found| found == "csv"))
+        .expect("compare report");
+    fs::read_to_string(path).expect("read compare report")
+}
+
+/// Writes a baseline with row lengths 4 to 7 and a current file with one more row.
+fn write_compare_inputs(dir: &Path, extra_row: &str) {
+    let rows: String = (10..30).map(|id| format!("{},{}\n", id, "a".repeat(1 + id % 4))).collect();
+    fs::write(dir.join("week_1.csv"), format!("id,text\n{}", rows)).expect("write baseline");
+    fs::write(dir.join("week_2.csv"), format!("id,text\n{}{}\n", rows, extra_row)).expect("write current");
+}
+
+#[test]
+fn compare_counts_rows_in_the_count_mode() {
+    let dir = fixture_dir("compare_bytes");
+    // Seven characters, eleven UTF-8 bytes
+    write_compare_inputs(&dir, "99,éééé");
+
+    let output = run_analyzer(&dir, &["compare", "week_1.csv", "week_2.csv", "chars"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(compare_csv_report(&dir.join("chars")).contains("\nmax,7,7,0,"));
+
+    let output = run_analyzer(&dir, &["compare", "week_1.csv", "week_2.csv", "bytes", "--count-mode", "bytes"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Mean: 5.57 -> 5.82 bytes (+0.25)"), "{}", stdout);
+    let csv = compare_csv_report(&dir.join("bytes"));
+    assert!(csv.contains("\nmax,7,11,4,"), "{}", csv);
+    assert!(csv.contains("\nnew_outlier_lengths,,11,1,"), "{}", csv);
+}
+
+#[test]
+fn compare_fences_outliers_with_the_outlier_factor() {
+    let dir = fixture_dir("compare_factor");
+    write_compare_inputs(&dir, "99,xxxxxxxx");
+
+    // Q3 is 7 and the IQR 2: the row of 11 is above 1.5 × IQR (10) but not 3 × IQR (13)
+    let output = run_analyzer(&dir, &["compare", "week_1.csv", "week_2.csv", "default"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let csv = compare_csv_report(&dir.join("default"));
+    assert!(csv.contains("\nupper_fence,10,10,0,"), "{}", csv);
+    assert!(csv.contains("\nnew_outlier_lengths,,11,1,"), "{}", csv);
+
+    let output = run_analyzer(&dir, &["compare", "week_1.csv", "week_2.csv", "wide", "--outlier-factor", "3"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("New outlier lengths: 0 (0 rows)"));
+    let csv = compare_csv_report(&dir.join("wide"));
+    assert!(csv.contains("\nupper_fence,13,13,0,"), "{}", csv);
+    assert!(csv.contains("\noutlier_rows,0,0,0,"), "{}", csv);
+    let md_path = fs::read_dir(dir.join("wide")).expect("read reports")
+        .map(|entry| entry.expect("dir entry").path())
+        .find(|path| path.extension().is_some_and(|found| found == "md"))
+        .expect("markdown report");
+    assert!(fs::read_to_string(md_path).expect("read markdown report").contains("outliers by the 3 × IQR rule"));
+}
+
+#[test]
+fn check_baseline_fails_a_regressed_file_with_status_2() {
+    let dir = fixture_dir("baseline");
+    let rows: String = (10..30).map(|id| format!("{},abcd\n", id)).collect();
+    fs::write(dir.join("good.csv"), format!("id,text\n{}", rows)).expect("write good file");
+    fs::write(dir.join("drop.csv"), format!("id,text\n{}98,a,b\n99,{}\n", rows, "x".repeat(40))).expect("write new drop");
+
+    let output = run_analyzer(&dir, &["good.csv", "out", "--save-baseline", "baseline.json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let baseline = fs::read_to_string(dir.join("baseline.json")).expect("read baseline");
+    assert!(baseline.contains("\"max_length\": 7,"), "{}", baseline);
+    assert!(baseline.contains("\"field_count_match_pct\": 100\n"), "{}", baseline);
+
+    let output = run_analyzer(&dir, &["good.csv", "out", "--check-baseline", "baseline.json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let output = run_analyzer(&dir, &["drop.csv", "out", "--check-baseline", "baseline.json"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("outlier rows rose from 0.00% to 4.35%"), "{}", stderr);
+    assert!(stderr.contains("the longest row grew from 7 to 43"), "{}", stderr);
+    assert!(stderr.contains("records with the header's field count fell from 100.00% to 95.45%"), "{}", stderr);
+
+    fs::write(dir.join("tolerant.conf"), "[baseline]\noutlier_pct = 10\nmax_length_pct = 600\nfield_count_pct = 5\n").expect("write config");
+    let output = run_analyzer(&dir, &["drop.csv", "out", "--check-baseline", "baseline.json", "--config", "tolerant.conf"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn regression_takes_precedence_over_truncation() {
+    let dir = fixture_dir("baseline_truncated");
+    let rows: String = (10..30).map(|id| format!("{},abcd\n", id)).collect();
+    fs::write(dir.join("good.csv"), format!("id,text\n{}", rows)).expect("write good file");
+    // A long row, then a last row cut off before its second field
+    fs::write(dir.join("drop.csv"), format!("id,text\n{}99,{}\n100", rows, "x".repeat(40))).expect("write new drop");
+    fs::create_dir_all(dir.join("drops")).expect("create drop directory");
+    fs::copy(dir.join("drop.csv"), dir.join("drops/drop.csv")).expect("copy new drop");
+
+    let output = run_analyzer(&dir, &["good.csv", "out", "--save-baseline", "baseline.json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let output = run_analyzer(&dir, &["drop.csv", "out", "--check-baseline", "baseline.json"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("file appears truncated at row 22"), "{}", stderr);
+    assert!(stderr.contains("the longest row grew from 7 to 43"), "{}", stderr);
+
+    // Without the baseline the truncation alone decides
+    let output = run_analyzer(&dir, &["drop.csv", "out"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = run_analyzer(&dir, &["--directory", "drops", "out", "--check-baseline", "baseline.json"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,211 @@
+//! Line ending policy and path handling across platforms.
+//!
+//! Files written on Windows (CRLF), Unix (LF), or a mix of both must report the same
+//! row lengths by default, and `--include-line-endings` must count each terminator.
+//! Rows far longer than the read buffer must be measured the same whatever its size, and
+//! the reports must not change with the size of the buffer they are written through.
+//! Input and output paths with spaces and non-ASCII characters must work unchanged.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use csv_tools_core::test_support::scratch_dir;
+
+const ROWS: [&str; 4] = ["id,name,notes", "1,Ana,short", "2,Bartholomew,a longer note", "3,Zoë,ünïcödé"];
+
+/// Writes `ROWS` with the given terminator after each row (`None` leaves the last row unterminated).
+fn write_rows(path: &Path, terminators: &[&str]) {
+    let content: String = ROWS.iter()
+        .zip(terminators)
+        .map(|(row, terminator)| format!("{}{}", row, terminator))
+        .collect();
+    fs::write(path, content).expect("write fixture");
+}
+
+/// Runs the analyzer on `input`, writing reports to `out`.
+fn run_analyzer(input: &Path, out: &Path, extra_args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_csv_row_analyzer_rust"))
+        .arg(input)
+        .arg(out)
+        .args(extra_args)
+        .output()
+        .expect("run analyzer");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+/// Reads the single report in `dir` whose name contains `marker`.
+fn read_report(dir: &Path, marker: &str) -> String {
+    let path = fs::read_dir(dir).expect("read output dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .find(|path| path.file_name().unwrap().to_string_lossy().contains(marker))
+        .unwrap_or_else(|| panic!("no report containing '{}' in {}", marker, dir.display()));
+    fs::read_to_string(path).expect("read report")
+}
+
+/// Returns the lengths column of a char_counts report.
+fn row_lengths(dir: &Path) -> Vec<usize> {
+    read_report(dir, "_char_counts_report_").lines()
+        .skip(1)
+        .map(|line| line.split(',').nth(3).unwrap().parse().expect("length"))
+        .collect()
+}
+
+/// Returns the "Line Endings:" line of the text report.
+fn line_endings_line(dir: &Path) -> String {
+    read_report(dir, "_txt_outliers_report_").lines()
+        .find(|line| line.starts_with("Line Endings:"))
+        .expect("line endings line")
+        .to_string()
+}
+
+#[test]
+fn crlf_and_lf_files_report_the_same_lengths_by_default() {
+    let dir = scratch_dir("default");
+    write_rows(&dir.join("unix.csv"), &["\n"; 4]);
+    write_rows(&dir.join("windows.csv"), &["\r\n"; 4]);
+    write_rows(&dir.join("mixed.csv"), &["\r\n", "\n", "\r\n", ""]);
+
+    let expected: Vec<usize> = ROWS.iter().map(|row| row.chars().count()).collect();
+    for name in ["unix", "windows", "mixed"] {
+        let out = dir.join(format!("out_{}", name));
+        run_analyzer(&dir.join(format!("{}.csv", name)), &out, &[]);
+        assert_eq!(row_lengths(&out), expected, "{}", name);
+        assert!(read_report(&out, "_txt_outliers_report_").contains("Row lengths exclude line endings"));
+    }
+
+    assert!(line_endings_line(&dir.join("out_unix")).contains("LF 4, CRLF 0, none 0"));
+    assert!(line_endings_line(&dir.join("out_windows")).contains("LF 0, CRLF 4, none 0"));
+    let mixed = line_endings_line(&dir.join("out_mixed"));
+    assert!(mixed.contains("LF 1, CRLF 2, none 1") && mixed.contains("(mixed)"), "{}", mixed);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn include_line_endings_counts_each_terminator() {
+    let dir = scratch_dir("include");
+    let input = dir.join("mixed.csv");
+    write_rows(&input, &["\r\n", "\n", "\r\n", ""]);
+    let out = dir.join("out");
+
+    run_analyzer(&input, &out, &["--include-line-endings"]);
+
+    let expected: Vec<usize> = ROWS.iter()
+        .zip([2, 1, 2, 0])
+        .map(|(row, terminator_length)| row.chars().count() + terminator_length)
+        .collect();
+    assert_eq!(row_lengths(&out), expected);
+    assert!(read_report(&out, "_md_outliers_report_").contains("Row lengths include line endings"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lone_carriage_return_is_row_content() {
+    let dir = scratch_dir("lone_cr");
+    let input = dir.join("classic_mac.csv");
+    fs::write(&input, "id,name\r1,Ana\r2,Bo\n").expect("write fixture");
+    let out = dir.join("out");
+
+    run_analyzer(&input, &out, &[]);
+
+    // Only \n ends a row, so the whole file is one row that keeps its \r characters
+    assert_eq!(row_lengths(&out), vec!["id,name\r1,Ana\r2,Bo".len()]);
+    assert!(line_endings_line(&out).contains("LF 1, CRLF 0, none 0"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn long_rows_read_the_same_with_any_read_buffer() {
+    let dir = scratch_dir("read_buffer");
+    let input = dir.join("long_rows.csv");
+    // Rows from a few bytes to 1 MiB, so the adaptive buffer grows several times and
+    // CRLF terminators and multi-byte characters straddle buffer refills
+    let mut content = String::from("id,blob\r\n");
+    let mut expected = vec!["id,blob".len()];
+    for (i, length) in [10, 70_000, 3, 300_000, 1 << 20, 65_535, 42].into_iter().enumerate() {
+        let row = format!("{},{}", i, "é".repeat(length));
+        expected.push(row.chars().count());
+        content.push_str(&row);
+        content.push_str(if i % 2 == 0 { "\r\n" } else { "\n" });
+    }
+    fs::write(&input, content).expect("write fixture");
+
+    for (name, extra_args) in [("adaptive", &[][..]), ("fixed", &["--read-buffer-size", "4K"][..])] {
+        let out = dir.join(name);
+        run_analyzer(&input, &out, extra_args);
+        assert_eq!(row_lengths(&out), expected, "{}", name);
+        assert!(line_endings_line(&out).contains("LF 3, CRLF 5, none 0"), "{}", name);
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn reports_are_the_same_with_any_report_buffer() {
+    let dir = scratch_dir("report_buffer");
+    let input = dir.join("many_rows.csv");
+    let content: String = (0..5000).map(|i| format!("{},{}\n", i, "x".repeat(i % 37))).collect();
+    fs::write(&input, content).expect("write fixture");
+
+    // Unbuffered, a buffer shorter than one line, and the default
+    let reports: Vec<(String, String)> = [&["--report-buffer-size", "0"][..], &["--report-buffer-size", "1"][..], &[][..]]
+        .into_iter()
+        .enumerate()
+        .map(|(i, extra_args)| {
+            let out = dir.join(i.to_string());
+            run_analyzer(&input, &out, extra_args);
+            (read_report(&out, "_char_counts_report_"), read_report(&out, "_value_counts_report_"))
+        })
+        .collect();
+    assert_eq!(reports[0].0.lines().count(), 5001);
+    assert_eq!(reports[0], reports[1]);
+    assert_eq!(reports[0], reports[2]);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn paths_with_spaces_and_non_ascii_characters() {
+    let dir = scratch_dir("paths");
+    let input_dir = dir.join("input files").join("données");
+    fs::create_dir_all(&input_dir).expect("create input dir");
+    let input = input_dir.join("März export.csv");
+    write_rows(&input, &["\r\n"; 4]);
+    let out = dir.join("report output").join("nested ñ");
+
+    let output = run_analyzer(&input, &out, &[]);
+
+    assert!(String::from_utf8_lossy(&output.stdout).contains("März export.csv"));
+    let report_names: Vec<String> = fs::read_dir(&out).expect("read output dir")
+        .map(|entry| entry.expect("dir entry").file_name().to_string_lossy().to_string())
+        .collect();
+    assert!(report_names.iter().any(|name| name.starts_with("März export_char_counts_report_")), "{:?}", report_names);
+    assert_eq!(row_lengths(&out).len(), ROWS.len());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn directory_mode_accepts_paths_with_spaces() {
+    let dir = scratch_dir("directory");
+    let input_dir = dir.join("csv files");
+    fs::create_dir_all(&input_dir).expect("create input dir");
+    write_rows(&input_dir.join("a b.csv"), &["\n"; 4]);
+    write_rows(&input_dir.join("c.CSV"), &["\r\n"; 4]);
+    let out = dir.join("out dir");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_csv_row_analyzer_rust"))
+        .arg("--directory")
+        .arg(&input_dir)
+        .arg(&out)
+        .output()
+        .expect("run analyzer");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Successfully processed 2 CSV files"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
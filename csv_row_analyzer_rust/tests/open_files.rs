@@ -0,0 +1,77 @@
+//! Open file descriptors in large directory runs.
+//!
+//! A directory run writes several reports per file. It must work under a tiny open-files
+//! limit (`ulimit -n`), because it only ever holds the input and one report open, and
+//! closes both before the next file. With `--threads <n>`, each worker holds its input and
+//! reports take one of `n` writers, so the run fits a limit of the standard streams plus
+//! twice `n`.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use csv_tools_core::test_support::scratch_dir;
+
+/// Files in the directory, each producing the five base reports plus the optional ones
+const FILE_COUNT: usize = 60;
+/// Open-files limit: stdin, stdout, stderr, the input, and one report
+const OPEN_FILES_LIMIT: usize = 5;
+
+/// Files analyzed at once in the run on worker threads
+const THREADS: usize = 4;
+
+/// Creates a scratch directory with `FILE_COUNT` small CSV files.
+fn fixture_dir(name: &str) -> PathBuf {
+    let dir = scratch_dir(name);
+    fs::create_dir_all(dir.join("in")).expect("create input dir");
+    for i in 0..FILE_COUNT {
+        let rows: String = (0..20).map(|row| format!("{},{}\n", row, "x".repeat((row * 7 + i) % 40))).collect();
+        fs::write(dir.join("in").join(format!("feed_{:03}.csv", i)), format!("id,value\n{}", rows)).expect("write fixture");
+    }
+    dir
+}
+
+/// Runs the analyzer on the scratch directory under an open-files limit of `limit`.
+fn run_with_limit(name: &str, limit: usize, extra_args: &[&str]) -> PathBuf {
+    let dir = fixture_dir(name);
+    let script = format!("ulimit -n {} && exec \"$0\" \"$@\"", limit);
+    let output = Command::new("sh")
+        .current_dir(&dir)
+        .args(["-c", &script, env!("CARGO_BIN_EXE_csv_row_analyzer_rust")])
+        .args(["--directory", "in", "out", "--length-buckets", "10,20", "--history", "runs.csv", "--column-totals"])
+        .args(extra_args)
+        .output()
+        .expect("run analyzer");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(!stderr.contains("Too many open files"), "{}", stderr);
+    dir
+}
+
+/// Checks that every file got all of its reports and its history record.
+fn assert_complete_run(dir: &Path) {
+
+    // Every file got all of its reports: five base reports, totals, and the bucket directory,
+    // then the two directory summary reports
+    let reports = fs::read_dir(dir.join("out")).expect("read reports").count();
+    assert_eq!(reports, FILE_COUNT * 7 + 2);
+    let history = fs::read_to_string(dir.join("runs.csv")).expect("read history");
+    assert_eq!(history.lines().count(), FILE_COUNT + 1);
+}
+
+#[test]
+fn directory_run_fits_a_low_open_files_limit() {
+    let dir = run_with_limit("serial", OPEN_FILES_LIMIT, &[]);
+    assert_complete_run(&dir);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn threaded_run_fits_two_files_per_worker() {
+    // stdin, stdout, stderr, then an input and a report per worker
+    let dir = run_with_limit("threads", 3 + 2 * THREADS, &["--threads", &THREADS.to_string()]);
+    assert_complete_run(&dir);
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,136 @@
+//! `--parallel` and `--lengths-only` against a serial run.
+//!
+//! Unreadable rows land in different worker batches, including the very last row, so
+//! `--parallel` only matches the serial reports when every batch and its length tally is
+//! merged back in file order, with any number of `--threads`. Rows straddle the byte-range
+//! boundaries of `--lengths-only`, so its histogram only matches when every row is counted
+//! by exactly one worker.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use csv_tools_core::test_support::scratch_dir;
+
+/// Reads the single report in `dir` whose name contains `marker`.
+fn read_report(dir: &Path, marker: &str) -> String {
+    let path = fs::read_dir(dir).expect("read output dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .find(|path| path.file_name().unwrap().to_string_lossy().contains(marker))
+        .unwrap_or_else(|| panic!("no report containing '{}' in {}", marker, dir.display()));
+    fs::read_to_string(path).expect("read report")
+}
+
+/// Drops the report's generation time, the only line that differs between two runs.
+fn without_generation_time(report: &str) -> String {
+    report.lines().filter(|line| !line.contains("generated")).collect::<Vec<_>>().join("\n")
+}
+
+fn run_analyzer(input: &Path, out: &Path, extra: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_csv_row_analyzer_rust"))
+        .arg(input)
+        .arg(out)
+        .args(extra)
+        .output()
+        .expect("run analyzer");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn parallel_reports_match_serial_run() {
+    let dir = scratch_dir("parity");
+    let input = dir.join("mixed.csv");
+    // About 2.5 MiB, so the rows are spread over several batches
+    let mut bytes = b"id,name,notes\n".to_vec();
+    let mut bad_file_rows = Vec::new();
+    for i in 0..40_000 {
+        if i % 3_701 == 5 || i == 39_999 {
+            bytes.extend_from_slice(b"bad,\xff\xfe,row\n");
+            bad_file_rows.push(i + 2);
+        } else {
+            bytes.extend_from_slice(format!("{},name{},{}\n", i, i, "x".repeat(i % 97)).as_bytes());
+        }
+    }
+    fs::write(&input, bytes).expect("write fixture");
+
+    let serial_out = dir.join("serial");
+    let parallel_out = dir.join("parallel");
+    run_analyzer(&input, &serial_out, &[]);
+    run_analyzer(&input, &parallel_out, &["--parallel"]);
+
+    for marker in ["_char_counts_report_", "_value_counts_report_", "_pages_valuecounts_report_"] {
+        assert_eq!(read_report(&parallel_out, marker), read_report(&serial_out, marker), "{}", marker);
+    }
+
+    // The outlier reports list example rows from the workers' merged index maps, whatever
+    // the number of workers; only their generation time differs
+    for threads in ["1", "3"] {
+        let threaded_out = dir.join(format!("threads_{}", threads));
+        run_analyzer(&input, &threaded_out, &["--parallel", "--threads", threads]);
+        for marker in ["_value_counts_report_", "_txt_outliers_report_", "_md_outliers_report_"] {
+            assert_eq!(
+                without_generation_time(&read_report(&threaded_out, marker)),
+                without_generation_time(&read_report(&serial_out, marker)),
+                "{} with --threads {}",
+                marker,
+                threads
+            );
+        }
+    }
+
+    // Errors are reported at their own file rows, and do not shift the data indices
+    let char_counts = read_report(&parallel_out, "_char_counts_report_");
+    let mut error_rows = Vec::new();
+    for line in char_counts.lines().skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let (row_index, file_row, data_index): (i64, i64, i64) =
+            (fields[0].parse().unwrap(), fields[1].parse().unwrap(), fields[2].parse().unwrap());
+        assert_eq!((file_row, data_index), (row_index + 1, file_row - 2), "row: {}", line);
+        if fields[3] == "error_reading_line" {
+            error_rows.push(file_row as usize);
+        }
+    }
+    assert_eq!(error_rows, bad_file_rows);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lengths_only_histogram_matches_full_run() {
+    let dir = scratch_dir("lengths_only");
+    let input = dir.join("mixed.csv");
+    let mut bytes = b"id,name\n".to_vec();
+    for i in 0..500 {
+        let name = "é".repeat(i % 5) + &"x".repeat((i * 37) % 211);
+        bytes.extend_from_slice(format!("{},{}", i, name).as_bytes());
+        bytes.extend_from_slice(if i % 4 == 0 { b"\r\n" } else { b"\n" });
+        if i % 97 == 11 {
+            bytes.extend_from_slice(b"bad,\xff\xfe\n");
+        }
+    }
+    bytes.extend_from_slice(b"last,row");
+    fs::write(&input, bytes).expect("write fixture");
+
+    let full_out = dir.join("full");
+    run_analyzer(&input, &full_out, &[]);
+
+    // One byte range per thread, so every count but 1 puts boundaries inside rows
+    for threads in ["1", "2", "7", "64"] {
+        let fast_out = dir.join(format!("fast_{}", threads));
+        run_analyzer(&input, &fast_out, &["--lengths-only", "--threads", threads]);
+
+        assert_eq!(
+            read_report(&fast_out, "_value_counts_report_"),
+            read_report(&full_out, "_value_counts_report_"),
+            "--threads {}",
+            threads
+        );
+        let summary = read_report(&fast_out, "_lengths_summary_report_");
+        let values: Vec<&str> = summary.lines().nth(1).expect("summary line").split(',').collect();
+        // 1 header + 500 data rows + the last row are readable; 6 rows are not
+        assert_eq!(&values[..2], &["502", "6"], "--threads {}: {}", threads, summary);
+        assert_eq!(fs::read_dir(&fast_out).unwrap().count(), 2, "only two reports are written");
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,105 @@
+//! Truncated file detection.
+//!
+//! A file cut off inside a quoted field or in the middle of its last row must be reported
+//! with its row and byte position and fail the run with exit status 3, after all reports
+//! were written. Complete files, including ones with trailing blank lines or a quoted
+//! field spanning lines, must not be flagged.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use csv_tools_core::test_support::scratch_dir;
+
+/// Writes `content` as `data.csv` and runs the analyzer on it.
+fn analyze(name: &str, content: &str) -> (Output, PathBuf) {
+    analyze_as(name, "data.csv", content)
+}
+
+/// Writes `content` as `file_name` and runs the analyzer on it.
+fn analyze_as(name: &str, file_name: &str, content: &str) -> (Output, PathBuf) {
+    analyze_with(name, file_name, content, &[])
+}
+
+/// Writes `content` as `file_name` and runs the analyzer on it with extra arguments.
+fn analyze_with(name: &str, file_name: &str, content: &str, args: &[&str]) -> (Output, PathBuf) {
+    let dir = scratch_dir(name);
+    fs::write(dir.join(file_name), content).expect("write fixture");
+    let output = Command::new(env!("CARGO_BIN_EXE_csv_row_analyzer_rust"))
+        .current_dir(&dir)
+        .args([file_name, "out"])
+        .args(args)
+        .output()
+        .expect("run analyzer");
+    (output, dir)
+}
+
+/// Reads the Markdown outliers report of a run.
+fn markdown_report(dir: &Path) -> String {
+    let report = fs::read_dir(dir.join("out")).expect("read reports")
+        .map(|entry| entry.expect("report entry").path())
+        .find(|path| path.to_string_lossy().contains("_md_outliers_report_"))
+        .expect("markdown report");
+    fs::read_to_string(report).expect("read markdown report")
+}
+
+#[test]
+fn open_quoted_field_at_end_is_reported() {
+    let (output, dir) = analyze("open_quote", "id,note\n1,\"complete\"\n2,\"cut off\nin the mid");
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("file appears truncated at row 3 / byte 42"), "{}", stderr);
+    assert!(stderr.contains("quoted field opened on row 2"), "{}", stderr);
+    assert!(markdown_report(&dir).contains("**Warning**: file appears truncated at row 3"));
+}
+
+#[test]
+fn short_final_row_is_reported() {
+    let (output, _) = analyze("short_row", "id,name,notes\n1,Ana,short\n2,Bart");
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("file appears truncated at row 2 / byte 32: the last row has 2 of the header's 3 fields"),
+            "{}", stderr);
+}
+
+#[test]
+fn complete_files_are_not_flagged() {
+    let (output, dir) = analyze("complete", "id,note\n1,\"spans\ntwo lines\"\n2,plain\n\n");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!markdown_report(&dir).contains("truncated"));
+}
+
+#[test]
+fn short_final_row_is_reported_at_the_file_delimiter() {
+    let rows = "id;name;notes\n1;Ana;a\n2;Bo;b\n3;Cy;c\n4;Di;d\n5;Ed;e\n";
+    let (output, _) = analyze_as("short_row_semicolon", "data.csv", &format!("{}6;Flo", rows));
+    assert_eq!(output.status.code(), Some(3), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("the last row has 2 of the header's 3 fields"));
+    let (output, _) = analyze_as("complete_semicolon", "data.csv", rows);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // Too few rows for the short one to be outvoted; it is left out of the detection
+    let (output, dir) = analyze_as("short_row_few_rows", "data.csv", "a;b;c\n1;2;3\n4;5\n");
+    assert_eq!(output.status.code(), Some(3), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("the last row has 2 of the header's 3 fields"));
+    assert!(markdown_report(&dir).contains("*Delimiter: semicolon (detected)*"));
+
+    let (output, _) = analyze_as("short_row_tab", "data.tsv", "id\tname\tnotes\n1\tAna\tshort, with a comma\n2\tBart");
+    assert_eq!(output.status.code(), Some(3), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("the last row has 2 of the header's 3 fields"));
+}
+
+#[test]
+fn truncation_warning_follows_the_language() {
+    let (output, dir) = analyze_with("spanish", "data.csv", "id,name,notes\n1,Ana,short\n2,Bart", &["--lang", "es"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "Aviso: data: el archivo parece truncado en la fila 2 / byte 32: la última fila tiene 2 de los 3 campos del encabezado"
+    ), "{}", stderr);
+    assert!(!stderr.contains("Warning"), "{}", stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Estado de los informes:"), "{}", stdout);
+    // The report body stays in English
+    assert!(markdown_report(&dir).contains("**Warning**: file appears truncated at row 2"));
+}
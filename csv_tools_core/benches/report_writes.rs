@@ -0,0 +1,73 @@
+//! # Report Write Throughput
+//!
+//! Analyzes a generated file of short rows twice per round, once with the reports written
+//! unbuffered (`--report-buffer-size 0`, one system call per line) and once through the
+//! default buffer, and prints the rows per second of both and the speedup. The
+//! char_counts report has one line per row, so it dominates the writes.
+//!
+//! ```bash
+//! $ cargo bench --bench report_writes -- 1000000
+//! rows      unbuffered       buffered         speedup
+//! 1000000   0.57M rows/s     1.46M rows/s     2.54x
+//! ```
+//!
+//! The optional argument is the number of rows (default 1,000,000). The best of three
+//! rounds is shown, to keep other load on the machine out of the comparison.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use csv_tools_core::{analyze_csv_row_lengths, AnalysisOptions};
+
+const DEFAULT_ROWS: usize = 1_000_000;
+const ROUNDS: usize = 3;
+
+fn main() {
+    // `cargo bench` passes `--bench` to every bench target; only a number is the row count
+    let rows = env::args().skip(1).find_map(|arg| arg.parse().ok()).unwrap_or(DEFAULT_ROWS);
+    if let Err(e) = run(rows) {
+        eprintln!("report_writes: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(rows: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = env::temp_dir().join(format!("csv_report_writes_bench_{}", process::id()));
+    fs::create_dir_all(&dir)?;
+    let input = dir.join("rows.csv");
+    write_rows(&input, rows)?;
+
+    let unbuffered = AnalysisOptions::from_flags(&["--reports", "char_counts,value_counts", "--report-buffer-size", "0"])?
+        .with_quiet(true);
+    let buffered = AnalysisOptions::from_flags(&["--reports", "char_counts,value_counts"])?.with_quiet(true);
+
+    let mut best = [Duration::MAX; 2];
+    for _ in 0..ROUNDS {
+        for (slot, options) in [&unbuffered, &buffered].into_iter().enumerate() {
+            let started = Instant::now();
+            analyze_csv_row_lengths(&input, dir.join("reports"), options)?;
+            best[slot] = best[slot].min(started.elapsed());
+            fs::remove_dir_all(dir.join("reports"))?;
+        }
+    }
+    fs::remove_dir_all(&dir)?;
+
+    let rate = |elapsed: Duration| format!("{:.2}M rows/s", rows as f64 / elapsed.as_secs_f64() / 1e6);
+    println!("{:<9} {:<16} {:<16} speedup", "rows", "unbuffered", "buffered");
+    println!("{:<9} {:<16} {:<16} {:.2}x", rows, rate(best[0]), rate(best[1]),
+             best[0].as_secs_f64() / best[1].as_secs_f64());
+    Ok(())
+}
+
+/// Writes a header and `rows` short rows of varying length.
+fn write_rows(path: &Path, rows: usize) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "id,name,amount")?;
+    for row in 1..rows {
+        writeln!(file, "{},customer_{},{}.{:02}", row, row % 977, row % 10_000, row % 100)?;
+    }
+    file.flush()
+}
@@ -0,0 +1,85 @@
+//! # Batch Job
+//!
+//! Analyzes every CSV file of a directory on a pool of threads, as a nightly job would,
+//! and prints one line per file, the largest share of outlier rows first. The job fails
+//! when a file could not be analyzed.
+//!
+//! ```bash
+//! $ cargo run --release --example batch_job -- exports reports 8
+//! outliers  rows      longest  file
+//!    2.41%  120000    48211    exports/orders.csv
+//!    0.00%  300       95       exports/users.csv
+//! ```
+//!
+//! The files are analyzed on a rayon pool of the given size; the options are `Sync`, so
+//! one set serves every thread.
+//!
+//! `csv_row_analyzer --directory exports reports --threads 8` does the same from the
+//! command line, with a directory summary report at the end.
+
+use std::path::PathBuf;
+use std::{env, fs, io, process};
+
+use csv_tools_core::{analyze_csv_row_lengths, AnalysisOptions, AnalysisResult};
+use rayon::prelude::*;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(input_dir) = args.first() else {
+        eprintln!("Usage: batch_job <directory> [output_directory] [threads]");
+        process::exit(2);
+    };
+    let output_dir = args.get(1).map_or("reports", String::as_str);
+    let threads = args.get(2).and_then(|threads| threads.parse().ok()).unwrap_or(4);
+
+    let mut files: Vec<PathBuf> = match fs::read_dir(input_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("csv")))
+            .collect(),
+        Err(e) => {
+            eprintln!("Could not read {}: {}", input_dir, e);
+            process::exit(1);
+        },
+    };
+    files.sort();
+
+    let options = AnalysisOptions::from_flags(&["--reports", "value_counts,outliers_md"])
+        .expect("valid flags")
+        .with_quiet(true);
+
+    let pool = match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Could not start {} threads: {}", threads, e);
+            process::exit(1);
+        },
+    };
+    let results: Vec<(PathBuf, Result<AnalysisResult, io::Error>)> = pool.install(|| {
+        files.par_iter().map(|path| (path.clone(), analyze_csv_row_lengths(path, output_dir, &options))).collect()
+    });
+
+    let mut analyzed = Vec::new();
+    let mut failed = 0;
+    for (path, result) in results {
+        match result {
+            Ok(result) => analyzed.push((path, result)),
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                failed += 1;
+            },
+        }
+    }
+
+    let outlier_share = |result: &AnalysisResult| result.outlier_rows as f64 * 100.0 / result.total_rows.max(1) as f64;
+    analyzed.sort_by(|(a_path, a), (b_path, b)| outlier_share(b).total_cmp(&outlier_share(a)).then_with(|| a_path.cmp(b_path)));
+    println!("{:>8}  {:<8}  {:<7}  file", "outliers", "rows", "longest");
+    for (path, result) in &analyzed {
+        println!("{:>7.2}%  {:<8}  {:<7}  {}", outlier_share(result), result.total_rows, result.statistics.max, path.display());
+    }
+
+    if failed > 0 {
+        eprintln!("{} of {} files could not be analyzed", failed, files.len());
+        process::exit(1);
+    }
+}
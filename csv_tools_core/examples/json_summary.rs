@@ -0,0 +1,52 @@
+//! # Consuming the JSON Summary
+//!
+//! A pipeline step that runs after the analysis, possibly on another machine: it reads the
+//! `summary-json` reports an earlier run wrote, and fails the pipeline when a file has too
+//! many outlier rows or unreadable rows.
+//!
+//! ```bash
+//! $ csv_row_analyzer --directory exports reports --writer summary-json
+//! $ cargo run --example json_summary -- 1.5 reports/*_summary-json_report_*.json
+//! ok    orders: 120000 rows, 0.41% outliers, 0 unreadable
+//! FAIL  users: 300 rows, 2.33% outliers, 2 unreadable
+//! ```
+//!
+//! The summary is read back into an [`AnalysisResult`] with `AnalysisResult::from_json`,
+//! so the gate works with the same fields as code that calls the analyzer directly, and a
+//! summary of an unsupported `report_schema_version` is refused instead of misread.
+//! `AnalysisResult::to_json` writes the same object.
+
+use std::{env, fs, process};
+
+use csv_tools_core::AnalysisResult;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (Some(max_outlier_percent), summaries) = (args.first().and_then(|limit| limit.parse::<f64>().ok()), args.get(1..).unwrap_or_default()) else {
+        eprintln!("Usage: json_summary <max outlier percent> <summary.json>...");
+        process::exit(2);
+    };
+
+    let mut failures = 0;
+    for path in summaries {
+        let summary = match fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|text| AnalysisResult::from_json(&text)) {
+            Ok(summary) => summary,
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                failures += 1;
+                continue;
+            },
+        };
+        let outlier_percent = summary.outlier_rows as f64 * 100.0 / summary.total_rows.max(1) as f64;
+        let passed = outlier_percent <= max_outlier_percent && summary.error_rows == 0;
+        println!("{:<5} {}: {} rows, {:.2}% outliers, {} unreadable",
+                 if passed { "ok" } else { "FAIL" }, summary.basename, summary.total_rows, outlier_percent, summary.error_rows);
+        if !passed {
+            failures += 1;
+        }
+    }
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,110 @@
+//! # Upload Handler
+//!
+//! Embeds the analyzer in an HTTP service: a client posts a CSV file, the service
+//! analyzes it, keeps the reports of the upload on disk, and answers with the JSON summary.
+//!
+//! ```bash
+//! $ cargo run --release --example upload_handler -- 127.0.0.1:8080 uploads
+//! $ curl --data-binary @orders.csv 'http://127.0.0.1:8080/analyze?name=orders.csv'
+//! {"report_schema_version":6,"basename":"orders","timestamp":"1767225600","total_rows":1200,...}
+//! ```
+//!
+//! [`handle_upload`] is the whole integration and knows nothing of the server; the axum
+//! route runs it on a blocking thread, since the analysis reads and writes files. One set
+//! of [`AnalysisOptions`] serves every request; it is `Send + Sync`, and `with_quiet`
+//! keeps the analysis from printing to the service's standard output.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::{env, fs, process};
+
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Query, State};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use csv_tools_core::{analyze_csv_row_lengths, AnalysisOptions};
+
+/// Largest upload accepted, in bytes
+const MAX_UPLOAD_BYTES: usize = 256 << 20;
+
+/// Numbers the uploads, so each gets a directory of its own
+static NEXT_UPLOAD: AtomicU64 = AtomicU64::new(1);
+
+/// State shared by every request
+struct App {
+    upload_dir: PathBuf,
+    options: AnalysisOptions,
+}
+
+/// Analyzes one uploaded file and returns its summary as JSON.
+///
+/// # Arguments
+///
+/// * `body` - The uploaded CSV data
+/// * `name` - The client's file name, which names the reports (only its last part is used)
+/// * `upload_dir` - Directory that receives `[n]/[name]` and `[n]/reports/` for upload n
+/// * `options` - Analysis options shared by every upload
+///
+/// # Returns
+///
+/// * `Result<String, io::Error>` - The `summary-json` object, or an Error if the upload
+///   cannot be stored or analyzed
+fn handle_upload(body: &[u8], name: &str, upload_dir: &Path, options: &AnalysisOptions) -> Result<String, io::Error> {
+    // Never let the client's name reach outside the upload directory
+    let name = Path::new(name).file_name().map_or_else(|| PathBuf::from("upload.csv"), PathBuf::from);
+    let dir = upload_dir.join(NEXT_UPLOAD.fetch_add(1, Ordering::Relaxed).to_string());
+    fs::create_dir_all(&dir)?;
+    let input = dir.join(name);
+    fs::write(&input, body)?;
+
+    let result = analyze_csv_row_lengths(&input, dir.join("reports"), options)?;
+    Ok(result.to_json())
+}
+
+/// The `POST /analyze?name=<file name>` route.
+async fn analyze(State(app): State<Arc<App>>, Query(query): Query<HashMap<String, String>>, body: Bytes) -> (StatusCode, String) {
+    let name = query.get("name").cloned().unwrap_or_else(|| "upload.csv".to_string());
+    match tokio::task::spawn_blocking(move || handle_upload(&body, &name, &app.upload_dir, &app.options)).await {
+        Ok(Ok(summary)) => (StatusCode::OK, summary + "\n"),
+        Ok(Err(e)) => (StatusCode::UNPROCESSABLE_ENTITY, format!("could not analyze the upload: {}\n", e)),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("the analysis failed: {}\n", e)),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let address = args.first().map_or("127.0.0.1:8080", String::as_str);
+    let upload_dir = PathBuf::from(args.get(1).map_or("uploads", String::as_str));
+
+    // The reports every upload gets, as on the command line
+    let options = match AnalysisOptions::from_flags(&["--reports", "value_counts,outliers_md", "--column-totals"]) {
+        Ok(options) => options.with_quiet(true),
+        Err(e) => {
+            eprintln!("Invalid options: {}", e);
+            process::exit(2);
+        },
+    };
+    let listener = match tokio::net::TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Could not listen on {}: {}", address, e);
+            process::exit(1);
+        },
+    };
+    println!("Listening on {}; uploads are kept in {}", address, upload_dir.display());
+
+    // Larger uploads are answered with 413 Payload Too Large
+    let app = Router::new()
+        .route("/analyze", post(analyze))
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+        .with_state(Arc::new(App { upload_dir, options }));
+    if let Err(e) = axum::serve(listener, app).await {
+        eprintln!("Server failed: {}", e);
+        process::exit(1);
+    }
+}
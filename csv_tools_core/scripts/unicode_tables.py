@@ -0,0 +1,130 @@
+#!/usr/bin/env python3
+"""Generates src/grapheme_tables.rs from the Unicode Character Database.
+
+Usage: scripts/unicode_tables.py <ucd_dir>
+
+<ucd_dir> holds GraphemeBreakProperty.txt (from ucd/auxiliary/), emoji-data.txt (from
+ucd/emoji/), and DerivedCoreProperties.txt (from ucd/) of one Unicode version, as
+published under https://www.unicode.org/Public/<version>/ucd/. Run it from the crate
+directory when moving to a new Unicode version and commit the regenerated table.
+"""
+
+import os
+import re
+import sys
+
+OUTPUT = os.path.join(os.path.dirname(__file__), "..", "src", "grapheme_tables.rs")
+
+# Grapheme_Cluster_Break values and the Break variant each maps to
+BREAK_VARIANTS = {
+    "CR": "Cr",
+    "LF": "Lf",
+    "Control": "Control",
+    "Extend": "Extend",
+    "ZWJ": "Zwj",
+    "Regional_Indicator": "RegionalIndicator",
+    "Prepend": "Prepend",
+    "SpacingMark": "SpacingMark",
+    "L": "HangulL",
+    "V": "HangulV",
+    "T": "HangulT",
+    "LV": "HangulLv",
+    "LVT": "HangulLvt",
+}
+CONJUNCT_VARIANTS = {"Linker": "Linker", "Consonant": "Consonant", "Extend": "Extend"}
+
+
+def read_ranges(path, select):
+    """(start, end, value) of the data lines for which select(fields) returns a value."""
+    ranges = []
+    with open(path, encoding="utf-8") as data:
+        for line in data:
+            fields = [field.strip() for field in line.split("#", 1)[0].split(";")]
+            if len(fields) < 2:
+                continue
+            value = select(fields[1:])
+            if value is None:
+                continue
+            start, _, end = fields[0].partition("..")
+            ranges.append((int(start, 16), int(end or start, 16), value))
+    return merge(ranges)
+
+
+def merge(ranges):
+    """Sorts the ranges and joins adjacent ones with the same value."""
+    merged = []
+    for start, end, value in sorted(ranges):
+        if merged and merged[-1][2] == value and merged[-1][1] + 1 == start:
+            merged[-1] = (merged[-1][0], end, value)
+        else:
+            merged.append((start, end, value))
+    return merged
+
+
+def unicode_version(path):
+    with open(path, encoding="utf-8") as data:
+        match = re.search(r"-(\d+\.\d+\.\d+)\.txt", data.readline())
+    if not match:
+        sys.exit("{}: no version in the first line".format(path))
+    return match.group(1)
+
+
+def table(name, doc, element, ranges, per_line, enum=None):
+    entries = []
+    for start, end, value in ranges:
+        if enum:
+            entries.append("(0x{:04X}, 0x{:04X}, {}::{})".format(start, end, enum, value))
+        else:
+            entries.append("(0x{:04X}, 0x{:04X})".format(start, end))
+    lines = ["/// {}".format(doc), "pub const {}: &[{}] = &[".format(name, element)]
+    for index in range(0, len(entries), per_line):
+        lines.append("    " + ", ".join(entries[index:index + per_line]) + ",")
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit(__doc__)
+    ucd = sys.argv[1]
+    grapheme_path = os.path.join(ucd, "GraphemeBreakProperty.txt")
+    version = unicode_version(grapheme_path)
+    for other in ["emoji-data.txt", "DerivedCoreProperties.txt"]:
+        if unicode_version(os.path.join(ucd, other)) != version:
+            sys.exit("{} is not from Unicode {}".format(other, version))
+
+    breaks = read_ranges(grapheme_path, lambda fields: BREAK_VARIANTS.get(fields[0]))
+    pictographic = read_ranges(
+        os.path.join(ucd, "emoji-data.txt"),
+        lambda fields: True if fields[0] == "Extended_Pictographic" else None,
+    )
+    conjuncts = read_ranges(
+        os.path.join(ucd, "DerivedCoreProperties.txt"),
+        lambda fields: CONJUNCT_VARIANTS.get(fields[1]) if fields[0] == "InCB" and len(fields) > 1 else None,
+    )
+
+    source = "\n\n".join([
+        "\n".join([
+            "//! Unicode property tables of the `graphemes` module.",
+            "//!",
+            "//! Generated by `scripts/unicode_tables.py` from the Unicode {} character database".format(version),
+            "//! (GraphemeBreakProperty.txt, emoji-data.txt, DerivedCoreProperties.txt). Do not edit;",
+            "//! rerun the script instead.",
+            "",
+            "use crate::graphemes::{Break, Conjunct};",
+        ]),
+        table("GRAPHEME_BREAK", "Grapheme_Cluster_Break of the code points that are not Other",
+              "(u32, u32, Break)", breaks, 3, enum="Break"),
+        table("EXTENDED_PICTOGRAPHIC", "Code points with the Extended_Pictographic property",
+              "(u32, u32)", pictographic, 5),
+        table("INDIC_CONJUNCT_BREAK", "Indic_Conjunct_Break of the code points that are not None",
+              "(u32, u32, Conjunct)", conjuncts, 3, enum="Conjunct"),
+    ]) + "\n"
+    with open(OUTPUT, "w", encoding="utf-8") as output:
+        output.write(source)
+    print("{}: {} break, {} pictographic, {} conjunct ranges (Unicode {})".format(
+        os.path.normpath(OUTPUT), len(breaks), len(pictographic), len(conjuncts), version))
+
+
+if __name__ == "__main__":
+    main()
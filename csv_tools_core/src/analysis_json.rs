@@ -0,0 +1,147 @@
+//! # JSON Analysis Report (`--format json`)
+//!
+//! Writes the whole analysis of a file as one JSON document, next to the CSV, Markdown,
+//! and text reports, so CI pipelines can read the results without scraping the Markdown
+//! tables. With `--no-reports`, the document is printed to stdout instead:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --format json
+//! $ jq '.outliers[] | .file_row' reports/orders_analysis_report_1767225600.json
+//! $ csv_row_analyzer orders.csv --no-reports --format json | jq .outlier_rows
+//! ```
+//!
+//! ```json
+//! {
+//!   "report_schema_version": 5,
+//!   "basename": "orders",
+//!   "statistics": {"min": 4, "max": 13, "mean": 7.25, ...},
+//!   "outlier_thresholds": {"lower": -2.5, "upper": 14.5},
+//!   "length_distribution": [{"length": 13, "rows": 1, "percentage": 25}, ...],
+//!   "page_distribution": [{"pages": 1, "rows": 4, "percentage": 100}],
+//!   "outlier_rows": 0,
+//!   "outliers": []
+//! }
+//! ```
+//!
+//! The length distribution is sorted longest first, as in the value_counts report, and
+//! the page distribution by page count, as in the pages_valuecounts report. Every outlier
+//! is listed with its `row_index`, `file_row`, and `data_index`, as in the char_counts
+//! report, longest first and then in file order. `outlier_rows` is the exact count; with
+//! more outliers of one length than `--max-example-rows` keeps, `outliers` lists only the
+//! kept rows.
+
+use std::path::Path;
+
+use crate::csv_row_analyzer::AnalysisResult;
+use crate::db_sink::OutlierRow;
+use crate::json::{json_number, json_string};
+use crate::report_schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_COLUMN};
+
+/// Output formats selected with `--format`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ReportFormat {
+    /// The CSV, Markdown, and text reports only
+    #[default]
+    Text,
+    /// The JSON analysis report as well
+    Json,
+}
+
+impl ReportFormat {
+    /// Parses a `--format` value.
+    pub fn parse(name: &str) -> Result<ReportFormat, String> {
+        match name {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!("invalid --format value '{}' (use text or json)", name)),
+        }
+    }
+}
+
+/// Builds the JSON analysis report.
+///
+/// # Arguments
+///
+/// * `input_file_path` - The analyzed file
+/// * `result` - Summary of the analysis
+/// * `generated_at` - Human-readable time of the run
+/// * `chars_per_page` - Characters per page of the page distribution
+/// * `page_counts` - (pages, rows) pairs sorted by page count
+/// * `outliers` - Rows above the upper outlier threshold
+///
+/// # Returns
+///
+/// * `String` - The JSON document, ending in a line break
+pub fn analysis_json(
+    input_file_path: &Path,
+    result: &AnalysisResult,
+    generated_at: &str,
+    chars_per_page: usize,
+    page_counts: &[(usize, u64)],
+    outliers: &[OutlierRow],
+) -> String {
+    let stats = &result.statistics;
+    let percentage = |rows: u64| json_number(percent_of(rows, result.total_rows));
+    let mut members = vec![
+        (SCHEMA_VERSION_COLUMN, REPORT_SCHEMA_VERSION.to_string()),
+        ("basename", json_string(&result.basename)),
+        ("input_path", json_string(&input_file_path.display().to_string())),
+        ("timestamp", json_string(&result.timestamp)),
+        ("generated_at", json_string(generated_at)),
+        ("total_rows", result.total_rows.to_string()),
+        ("error_rows", result.error_rows.to_string()),
+        ("total_chars", result.total_chars.to_string()),
+        ("column_count", result.column_count.to_string()),
+        ("header_fingerprint", result.header_fingerprint.as_deref().map_or("null".to_string(), json_string)),
+        ("statistics", format!(
+            "{{\"min\": {}, \"max\": {}, \"mean\": {}, \"median\": {}, \"q1\": {}, \"q3\": {}, \"p99\": {}, \"std_dev\": {}}}",
+            stats.min, stats.max, json_number(round2(stats.mean)), stats.median, stats.q1, stats.q3, stats.p99, json_number(round2(stats.std_dev))
+        )),
+        ("outlier_thresholds", format!(
+            "{{\"lower\": {}, \"upper\": {}}}",
+            json_number(round2(result.outlier_threshold_lower)), json_number(round2(result.outlier_threshold_upper))
+        )),
+    ];
+
+    let lengths: Vec<String> = result.length_counts.iter()
+        .map(|&(length, rows)| format!("{{\"length\": {}, \"rows\": {}, \"percentage\": {}}}", length, rows, percentage(rows)))
+        .collect();
+    members.push(("length_distribution", json_list(&lengths)));
+
+    members.push(("chars_per_page", chars_per_page.to_string()));
+    let pages: Vec<String> = page_counts.iter()
+        .map(|&(pages, rows)| format!("{{\"pages\": {}, \"rows\": {}, \"percentage\": {}}}", pages, rows, percentage(rows)))
+        .collect();
+    members.push(("page_distribution", json_list(&pages)));
+
+    members.push(("outlier_rows", result.outlier_rows.to_string()));
+    let outlier_entries: Vec<String> = outliers.iter()
+        .map(|outlier| {
+            let std_devs = if stats.std_dev > 0.0 { (outlier.character_length as f64 - stats.mean) / stats.std_dev } else { 0.0 };
+            format!("{{\"row_index\": {}, \"file_row\": {}, \"data_index\": {}, \"character_length\": {}, \"std_devs_from_mean\": {}}}",
+                    outlier.row_index, outlier.file_row, outlier.row_index as i64 - 1,
+                    outlier.character_length, json_number(round2(std_devs)))
+        })
+        .collect();
+    members.push(("outliers", json_list(&outlier_entries)));
+
+    let body: Vec<String> = members.iter().map(|(key, value)| format!("  {}: {}", json_string(key), value)).collect();
+    format!("{{\n{}\n}}\n", body.join(",\n"))
+}
+
+/// Formats an array with one element per line.
+fn json_list(items: &[String]) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    format!("[\n    {}\n  ]", items.join(",\n    "))
+}
+
+fn percent_of(rows: u64, total_rows: u64) -> f64 {
+    if total_rows == 0 { 0.0 } else { round2(rows as f64 * 100.0 / total_rows as f64) }
+}
+
+/// Rounds to two decimals, as the other reports print them.
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -0,0 +1,350 @@
+//! # Saved Analysis State (`--save-state`, `query` subcommand)
+//!
+//! Questions come up after the reports are read (which rows are over 100,000 characters?
+//! how long are the longest values of the description column?), and answering them from
+//! the input means another pass, hours for a terabyte file. With `--save-state`, the pass
+//! also writes `[basename]_state_[timestamp].bin`, and the `query` subcommand answers from
+//! that file alone:
+//!
+//! ```bash
+//! $ csv_row_analyzer huge.csv reports --save-state --profile-columns
+//! $ csv_row_analyzer query reports/huge_state_1767225600.bin
+//! $ csv_row_analyzer query reports/huge_state_1767225600.bin --rows-with-length-over 100000
+//! $ csv_row_analyzer query reports/huge_state_1767225600.bin --column description --percentile 99
+//! ```
+//!
+//! Without a query flag, the rows, errors, and length statistics are printed.
+//! `--rows-with-length-over <n>` prints `row_index,length` for every row longer than `n`,
+//! in file order. `--percentile <p>` prints the nearest-rank percentile of the row
+//! lengths, or with `--column <name>` of the value lengths of that column (empty values
+//! left out).
+//!
+//! The state holds the length histogram, the index of every row under its length (so
+//! `--max-example-rows` does not cap it), and, when columns were profiled, the histogram
+//! of each column's value lengths. Numbers are LEB128 varints and row indices are
+//! delta-encoded, so most rows take one byte. The file starts with `CSVSTATE` and a
+//! format version; a state from a newer version is refused.
+//!
+//! `--save-state` keeps every row index, so it cannot be combined with `--low-memory`.
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::cli::{parse_command_line, QUERY_COMMAND};
+use crate::column_profile::ColumnProfiler;
+use crate::count_mode::CountMode;
+use crate::csv_row_analyzer::{statistics_from_length_counts, Statistics};
+use crate::sorted_lengths::SortedLengths;
+
+/// First bytes of a state file
+const STATE_MAGIC: &[u8; 8] = b"CSVSTATE";
+/// Version of the state layout, raised when it changes
+pub const STATE_FORMAT_VERSION: u64 = 1;
+
+/// Histogram of the value lengths of one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSketch {
+    pub name: String,
+    /// (length, count) pairs in ascending order of length
+    pub length_counts: Vec<(usize, u64)>,
+}
+
+/// Collects the value length histogram of every profiled column.
+pub fn collect_column_sketches(profiler: &ColumnProfiler) -> Vec<ColumnSketch> {
+    profiler.columns()
+        .into_iter()
+        .map(|(_, name, profile)| {
+            let mut length_counts: Vec<(usize, u64)> = profile.length_counts.iter().map(|(&length, &count)| (length, count)).collect();
+            length_counts.sort_unstable();
+            ColumnSketch { name, length_counts }
+        })
+        .collect()
+}
+
+/// Everything `query` answers from, as saved after one pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisState {
+    pub basename: String,
+    /// Unit of the lengths, as given to `--count-mode`
+    pub count_mode: CountMode,
+    /// Rows read successfully
+    pub total_rows: u64,
+    /// Rows that could not be read
+    pub error_rows: u64,
+    /// (length, row indices) in ascending order of length, the indices in file order
+    pub rows_by_length: Vec<(usize, Vec<usize>)>,
+    /// Value lengths of every column, when columns were profiled
+    pub columns: Option<Vec<ColumnSketch>>,
+}
+
+impl AnalysisState {
+    /// Writes the state in the binary layout described in the module docs.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(STATE_MAGIC)?;
+        write_varint(&mut out, STATE_FORMAT_VERSION)?;
+        write_text(&mut out, &self.basename)?;
+        write_text(&mut out, self.count_mode.name())?;
+        write_varint(&mut out, self.total_rows)?;
+        write_varint(&mut out, self.error_rows)?;
+
+        write_varint(&mut out, self.rows_by_length.len() as u64)?;
+        for (length, indices) in &self.rows_by_length {
+            write_varint(&mut out, *length as u64)?;
+            write_varint(&mut out, indices.len() as u64)?;
+            let mut previous = 0;
+            for &index in indices {
+                write_varint(&mut out, (index - previous) as u64)?;
+                previous = index;
+            }
+        }
+
+        match &self.columns {
+            None => write_varint(&mut out, 0)?,
+            Some(columns) => {
+                write_varint(&mut out, 1)?;
+                write_varint(&mut out, columns.len() as u64)?;
+                for column in columns {
+                    write_text(&mut out, &column.name)?;
+                    write_varint(&mut out, column.length_counts.len() as u64)?;
+                    for &(length, count) in &column.length_counts {
+                        write_varint(&mut out, length as u64)?;
+                        write_varint(&mut out, count)?;
+                    }
+                }
+            },
+        }
+        out.flush()
+    }
+
+    /// Reads a state written by [`AnalysisState::write`].
+    pub fn read(path: impl AsRef<Path>) -> io::Result<AnalysisState> {
+        let mut input = BufReader::new(File::open(path)?);
+        let mut magic = [0; 8];
+        input.read_exact(&mut magic).map_err(|_| invalid("not a state file written by --save-state".to_string()))?;
+        if &magic != STATE_MAGIC {
+            return Err(invalid("not a state file written by --save-state".to_string()));
+        }
+        let version = read_varint(&mut input)?;
+        if version > STATE_FORMAT_VERSION {
+            return Err(invalid(format!(
+                "state format version {} is newer than this analyzer reads ({})", version, STATE_FORMAT_VERSION
+            )));
+        }
+        let basename = read_text(&mut input)?;
+        let count_mode = CountMode::parse(&read_text(&mut input)?).map_err(invalid)?;
+        let total_rows = read_varint(&mut input)?;
+        let error_rows = read_varint(&mut input)?;
+
+        let lengths = read_varint(&mut input)?;
+        let mut rows_by_length = Vec::new();
+        for _ in 0..lengths {
+            let length = read_varint(&mut input)? as usize;
+            let rows = read_varint(&mut input)?;
+            let mut indices = Vec::new();
+            let mut index = 0;
+            for _ in 0..rows {
+                index += read_varint(&mut input)? as usize;
+                indices.push(index);
+            }
+            rows_by_length.push((length, indices));
+        }
+
+        let columns = match read_varint(&mut input)? {
+            0 => None,
+            _ => {
+                let count = read_varint(&mut input)?;
+                let mut columns = Vec::new();
+                for _ in 0..count {
+                    let name = read_text(&mut input)?;
+                    let entries = read_varint(&mut input)?;
+                    let mut length_counts = Vec::new();
+                    for _ in 0..entries {
+                        length_counts.push((read_varint(&mut input)? as usize, read_varint(&mut input)?));
+                    }
+                    columns.push(ColumnSketch { name, length_counts });
+                }
+                Some(columns)
+            },
+        };
+        Ok(AnalysisState { basename, count_mode, total_rows, error_rows, rows_by_length, columns })
+    }
+
+    /// (length, count) pairs of the row lengths.
+    pub fn length_counts(&self) -> Vec<(usize, u64)> {
+        self.rows_by_length.iter().map(|(length, indices)| (*length, indices.len() as u64)).collect()
+    }
+
+    /// Descriptive statistics of the row lengths.
+    pub fn statistics(&self) -> Statistics {
+        statistics_from_length_counts(&self.length_counts())
+    }
+
+    /// (row index, length) of every row longer than `length`, in file order.
+    pub fn rows_with_length_over(&self, length: usize) -> Vec<(usize, usize)> {
+        let mut rows: Vec<(usize, usize)> = self.rows_by_length.iter()
+            .filter(|(row_length, _)| *row_length > length)
+            .flat_map(|(row_length, indices)| indices.iter().map(move |&index| (index, *row_length)))
+            .collect();
+        rows.sort_unstable();
+        rows
+    }
+
+    /// The sketch of a column, found by name (ignoring case).
+    pub fn column(&self, name: &str) -> Result<&ColumnSketch, String> {
+        let columns = self.columns.as_ref()
+            .ok_or("the state has no column profiles; save it with --profile-columns")?;
+        columns.iter()
+            .find(|column| column.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!(
+                "no column '{}' in the state (columns: {})",
+                name, columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>().join(", ")
+            ))
+    }
+}
+
+/// Options of the `query` subcommand
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    pub state_path: String,
+    /// `--rows-with-length-over`
+    pub rows_with_length_over: Option<usize>,
+    /// `--column`
+    pub column: Option<String>,
+    /// `--percentile`
+    pub percentile: Option<f64>,
+}
+
+/// Parses the arguments that follow `query`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<QueryOptions, String>` - Parsed options or an error message
+pub fn parse_query_arguments(args: &[String]) -> Result<QueryOptions, String> {
+    let parsed = parse_command_line(&QUERY_COMMAND, args)?;
+    let mut options = QueryOptions { state_path: String::new(), rows_with_length_over: None, column: None, percentile: None };
+
+    for (flag, value) in &parsed.flags {
+        match *flag {
+            "--rows-with-length-over" => {
+                options.rows_with_length_over = Some(value.parse()
+                    .map_err(|_| format!("invalid --rows-with-length-over value '{}' (expected a length)", value))?);
+            },
+            "--column" => options.column = Some(value.clone()),
+            "--percentile" => {
+                options.percentile = match value.parse::<f64>() {
+                    Ok(percentile) if percentile > 0.0 && percentile <= 100.0 => Some(percentile),
+                    _ => return Err(format!("invalid --percentile value '{}' (expected a number above 0 and up to 100)", value)),
+                };
+            },
+            _ => {},
+        }
+    }
+
+    if options.column.is_some() && options.percentile.is_none() {
+        return Err("--column selects the column of --percentile; add --percentile".to_string());
+    }
+    if options.rows_with_length_over.is_some() && options.percentile.is_some() {
+        return Err("--rows-with-length-over and --percentile are separate queries; use one of them".to_string());
+    }
+    match parsed.positionals.as_slice() {
+        [state_path] => options.state_path = state_path.clone(),
+        _ => return Err("Expected a state file written by --save-state".to_string()),
+    }
+    Ok(options)
+}
+
+/// Runs the `query` subcommand and prints its answer.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - An Error if the state cannot be read or the column is unknown
+pub fn run_query(options: &QueryOptions) -> Result<(), io::Error> {
+    let state = AnalysisState::read(&options.state_path)?;
+    let unit = state.count_mode.name();
+
+    if let Some(length) = options.rows_with_length_over {
+        let mut out = io::stdout().lock();
+        writeln!(out, "row_index,length")?;
+        for (row_index, row_length) in state.rows_with_length_over(length) {
+            writeln!(out, "{},{}", row_index, row_length)?;
+        }
+        return Ok(());
+    }
+
+    if let Some(p) = options.percentile {
+        match &options.column {
+            Some(name) => {
+                let column = state.column(name).map_err(invalid)?;
+                let values: u64 = column.length_counts.iter().map(|&(_, count)| count).sum();
+                match SortedLengths::Counts(&column.length_counts).percentile(p) {
+                    Some(length) => println!("{} p{}: {} chars ({} non-empty values)", column.name, p, length, values),
+                    None => println!("{} p{}: no non-empty values", column.name, p),
+                }
+            },
+            None => match SortedLengths::Counts(&state.length_counts()).percentile(p) {
+                Some(length) => println!("rows p{}: {} {} ({} rows)", p, length, unit, state.total_rows),
+                None => println!("rows p{}: no rows", p),
+            },
+        }
+        return Ok(());
+    }
+
+    let stats = state.statistics();
+    println!("{}: {} rows ({} with errors), lengths in {}", state.basename, state.total_rows, state.error_rows, unit);
+    println!("min {}, q1 {}, median {}, q3 {}, p99 {}, max {}, mean {:.2}, std dev {:.2}",
+             stats.min, stats.q1, stats.median, stats.q3, stats.p99, stats.max, stats.mean, stats.std_dev);
+    if let Some(columns) = &state.columns {
+        println!("columns: {}", columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+    Ok(())
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_varint(out: &mut impl Write, mut value: u64) -> io::Result<()> {
+    while value >= 0x80 {
+        out.write_all(&[(value as u8 & 0x7f) | 0x80])?;
+        value >>= 7;
+    }
+    out.write_all(&[value as u8])
+}
+
+fn read_varint(input: &mut impl Read) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        input.read_exact(&mut byte).map_err(|_| invalid("the state file ends early".to_string()))?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid("the state file has a malformed number".to_string()))
+}
+
+fn write_text(out: &mut impl Write, text: &str) -> io::Result<()> {
+    write_varint(out, text.len() as u64)?;
+    out.write_all(text.as_bytes())
+}
+
+fn read_text(input: &mut impl Read) -> io::Result<String> {
+    let length = read_varint(input)?;
+    let mut bytes = Vec::new();
+    input.take(length).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != length {
+        return Err(invalid("the state file ends early".to_string()));
+    }
+    String::from_utf8(bytes).map_err(|_| invalid("the state file has a name that is not UTF-8".to_string()))
+}
@@ -0,0 +1,305 @@
+//! # Async Analysis Entry Points (feature `async`)
+//!
+//! Runs the same analysis as `analyze_csv_row_lengths`, but reads the input through
+//! tokio's async I/O so it can run on an existing tokio runtime (for example inside an
+//! ingestion service) without a blocking thread per request.
+//!
+//! Build with `cargo build --features async`. Two entry points are provided:
+//!
+//! * [`analyze_csv_file_async`] - reads a local file with `tokio::fs`
+//! * [`analyze_csv_reader_async`] - reads any `AsyncBufRead`, such as a socket or a
+//!   decoded request body
+//!
+//! The per-row report is written asynchronously while the input streams in. The
+//! summary reports (value counts, pages, outliers, history) and the optional database
+//! sink use ordinary file and process I/O, so they are written on tokio's blocking pool
+//! with `spawn_blocking` once the input is consumed, and never stall the runtime's workers.
+//! That is why the entry points take the options as an `Arc`: the blocking task outlives
+//! the borrow.
+//!
+//! Row ranges (`--rows`) are supported; byte ranges need a seekable file and are not.
+//! Gzip and zstd input is only decompressed by the blocking analyzer.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+
+use crate::compression::compression_format;
+use crate::csv_row_analyzer::{
+    char_counts_entry, char_counts_header, generate_timestamp, write_reports, AnalysisOptions, AnalysisResult,
+    FileAnalysis, RowAccumulator,
+};
+use crate::delimiter::Delimiter;
+use crate::fields::RecordJoiner;
+use crate::input_range::InputRange;
+use crate::line_reader::{decode_line, PhysicalLine};
+use crate::read_buffer::INITIAL_READ_BUFFER_SIZE;
+use crate::report_selection::StandardReport;
+use crate::messages::Message;
+use crate::report_status::ReportLog;
+
+/// Async counterpart of `LineReader`: yields physical lines with their positions
+pub struct AsyncLineReader<R: AsyncBufRead + Unpin> {
+    reader: R,
+    next_row_index: usize,
+    next_offset: u64,
+    end_row: Option<usize>,
+    finished: bool,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncLineReader<R> {
+    /// Creates a reader positioned at the start of the input.
+    pub fn new(reader: R) -> Self {
+        AsyncLineReader {
+            reader,
+            next_row_index: 0,
+            next_offset: 0,
+            end_row: None,
+            finished: false,
+        }
+    }
+
+    /// Stops iteration before the row with this index (exclusive end).
+    pub fn with_end_row(mut self, end_row: Option<usize>) -> Self {
+        self.end_row = end_row;
+        self
+    }
+
+    /// Reads the next line, or returns None at the end of the input or range.
+    ///
+    /// As with `LineReader`, an I/O error is returned once as the line's content and
+    /// ends the iteration, since the stream position is unknown afterwards.
+    pub async fn next_line(&mut self) -> Option<PhysicalLine> {
+        if self.finished || self.end_row.is_some_and(|end| self.next_row_index >= end) {
+            return None;
+        }
+
+        let row_index = self.next_row_index;
+        let byte_offset = self.next_offset;
+        let mut buffer = Vec::new();
+
+        let bytes_read = match self.reader.read_until(b'\n', &mut buffer).await {
+            Ok(0) => {
+                self.finished = true;
+                return None;
+            },
+            Ok(n) => n,
+            Err(e) => {
+                self.finished = true;
+                return Some(PhysicalLine { row_index, byte_offset, byte_length: 0, content: Err(e), terminator: "" });
+            }
+        };
+
+        self.next_row_index += 1;
+        self.next_offset += bytes_read as u64;
+
+        Some(decode_line(buffer, row_index, byte_offset, bytes_read))
+    }
+}
+
+/// Analyzes a local CSV file using async file reads.
+///
+/// # Arguments
+///
+/// * `input_file_path` - Path to the input CSV file to analyze
+/// * `output_directory_path` - Directory where report files will be saved (will be created if it doesn't exist)
+/// * `options` - Run options such as the recommendation rules, shared with the blocking report writes
+///
+/// # Returns
+///
+/// * `Result<AnalysisResult, io::Error>` - The file's summary, or an Error if file operations fail
+pub async fn analyze_csv_file_async(
+    input_file_path: impl AsRef<Path>,
+    output_directory_path: impl AsRef<Path>,
+    options: Arc<AnalysisOptions>,
+) -> Result<AnalysisResult, io::Error> {
+    let reader = open_file(input_file_path.as_ref(), &options).await?;
+    analyze_csv_reader_async(reader, input_file_path, output_directory_path, options).await
+}
+
+/// Opens a local file for the async analyzer, rejecting compressed input.
+async fn open_file(input_file_path: &Path, options: &AnalysisOptions) -> Result<BufReader<tokio::fs::File>, io::Error> {
+    let file = tokio::fs::File::open(input_file_path).await?;
+    // A tokio reader cannot grow its buffer, so it starts at the adaptive reader's initial size
+    let mut reader = BufReader::with_capacity(options.read_buffer_size.fixed_or(INITIAL_READ_BUFFER_SIZE), file);
+    if let Some(format) = compression_format(reader.fill_buf().await?) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} input is not supported by the async analyzer; run without --async", format),
+        ));
+    }
+    Ok(reader)
+}
+
+/// Analyzes CSV data from any async buffered reader.
+///
+/// # Arguments
+///
+/// * `reader` - Source of the CSV data (file, socket, request body)
+/// * `source_name` - Name or path of the source; its basename names the reports
+/// * `output_directory_path` - Directory where report files will be saved (will be created if it doesn't exist)
+/// * `options` - Run options such as the recommendation rules, shared with the blocking report writes
+///
+/// # Returns
+///
+/// * `Result<AnalysisResult, io::Error>` - The source's summary, or an Error if reading or writing fails
+pub async fn analyze_csv_reader_async<R: AsyncBufRead + Unpin>(
+    reader: R,
+    source_name: impl AsRef<Path>,
+    output_directory_path: impl AsRef<Path>,
+    options: Arc<AnalysisOptions>,
+) -> Result<AnalysisResult, io::Error> {
+    let pending = read_input(reader, source_name.as_ref(), output_directory_path.as_ref(), &options).await?;
+    tokio::task::spawn_blocking(move || pending.write(&options))
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// An analyzed input whose summary reports are still to be written
+struct PendingReports {
+    source_name: PathBuf,
+    timestamp: String,
+    analysis: FileAnalysis,
+    report_log: ReportLog,
+}
+
+impl PendingReports {
+    /// Writes the summary reports with blocking I/O.
+    fn write(self, options: &AnalysisOptions) -> Result<AnalysisResult, io::Error> {
+        write_reports(&self.source_name, &self.timestamp, &self.analysis, options, self.report_log)
+    }
+}
+
+/// Reads the input and streams the row report, leaving the summary reports to the caller.
+async fn read_input<R: AsyncBufRead + Unpin>(
+    reader: R,
+    source_name: &Path,
+    output_directory_path: &Path,
+    options: &AnalysisOptions,
+) -> Result<PendingReports, io::Error> {
+    let (start_row, end_row) = match &options.input_range {
+        None => (0, None),
+        Some(InputRange::Rows { start, end }) => (*start, *end),
+        Some(InputRange::Bytes { .. }) => return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "byte ranges are not supported by the async analyzer; use --rows",
+        )),
+    };
+
+    let input_basename = options.report_basename(source_name)?;
+    let timestamp = generate_timestamp(&options.clock)?;
+
+    // Each report is written independently, so a failed one does not lose the others
+    let report_dir = options.output_layout.unwrap_or_default().report_dir(output_directory_path, &input_basename, &timestamp);
+    let report_log = ReportLog::new(&report_dir, options.report_fallback_dir.as_deref(), options.report_retries)
+        .with_writes_disabled(options.no_reports)
+        .with_language(options.language);
+    // Row flags need the outlier fences, so that row report is written with the others
+    let (mut report_log, mut row_report_file) = if options.row_flags || !options.reports.includes(StandardReport::CharCounts) {
+        (report_log, None)
+    } else {
+        // Creating the report directory and file is blocking I/O
+        let file_name = format!("{}_char_counts_report_{}.csv", input_basename, timestamp);
+        let (report_log, file) = tokio::task::spawn_blocking(move || {
+            let mut report_log = report_log;
+            let file = report_log.create("char_counts", &file_name);
+            (report_log, file)
+        })
+        .await
+        .map_err(io::Error::other)?;
+        (report_log, file.map(|file| BufWriter::with_capacity(options.report_buffer_size, tokio::fs::File::from_std(file.into_file()))))
+    };
+    write_row_report_line(&mut row_report_file, &mut report_log, &char_counts_header(options.csv_mode)).await;
+
+    let mut lines = AsyncLineReader::new(reader).with_end_row(end_row);
+    // A stream is read once, so its delimiter is not sniffed
+    let delimiter = Delimiter::given(options.delimiter);
+    let mut accumulator = RowAccumulator::new(options, &source_name.to_string_lossy(), delimiter);
+
+    // A generic stream cannot be reopened, so keep the header while skipping to the range
+    let mut header = RecordJoiner::default();
+    let mut header_complete = false;
+    while lines.next_row_index < start_row {
+        match lines.next_line().await {
+            Some(line) => match line.content {
+                Ok(content) if !header_complete => header_complete = header.push_line(&content, line.terminator, delimiter.symbol),
+                Err(_) => header_complete = true,
+                Ok(_) => {},
+            },
+            None => break,
+        }
+    }
+    let header_line = (!header.is_empty()).then(|| header.into_record());
+
+    while let Some(physical_line) = lines.next_line().await {
+        if let Some(entry) = accumulator.add_line(physical_line) {
+            write_row_report_line(&mut row_report_file, &mut report_log, &char_counts_entry(entry)).await;
+        }
+    }
+    if let Some(entry) = accumulator.finish_records() {
+        write_row_report_line(&mut row_report_file, &mut report_log, &char_counts_entry(entry)).await;
+    }
+    if let Some(file) = row_report_file.as_mut()
+        && let Err(e) = file.shutdown().await
+    {
+        report_log.fail("char_counts", e);
+    }
+    // Close the streamed report before the summary reports are opened
+    drop(row_report_file);
+
+    if let Some(header) = &header_line {
+        accumulator.set_header_if_missing(header);
+    }
+    accumulator.check_column_limit(source_name, options.max_columns)?;
+
+    let analysis = accumulator.finish(input_basename, options.input_range.as_ref());
+    Ok(PendingReports { source_name: source_name.to_path_buf(), timestamp, analysis, report_log })
+}
+
+/// Writes one line of the streamed row report, giving up on the report after a failed write.
+async fn write_row_report_line(
+    row_report_file: &mut Option<BufWriter<tokio::fs::File>>,
+    report_log: &mut ReportLog,
+    line: &str,
+) {
+    if let Some(file) = row_report_file {
+        let result = match file.write_all(line.as_bytes()).await {
+            Ok(()) => file.write_all(b"\n").await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            eprintln!("{}", Message::RowReportFailed.text(report_log.language(), &[&e]));
+            *row_report_file = None;
+            report_log.fail("char_counts", e);
+        }
+    }
+}
+
+/// Reads a file on a new single-threaded runtime (used by `--async`).
+///
+/// The runtime serves only this file, so its summary reports are written on the calling
+/// thread after the runtime finishes, without moving the options to the blocking pool.
+///
+/// # Arguments
+///
+/// * `input_file_path` - Path to the input CSV file to analyze
+/// * `output_directory_path` - Directory where report files will be saved
+/// * `options` - Run options such as the recommendation rules
+///
+/// # Returns
+///
+/// * `Result<AnalysisResult, io::Error>` - The file's summary, or an Error if file operations fail
+pub(crate) fn run_file_analysis_on_runtime(
+    input_file_path: impl AsRef<Path>,
+    output_directory_path: impl AsRef<Path>,
+    options: &AnalysisOptions,
+) -> Result<AnalysisResult, io::Error> {
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    let pending = runtime.block_on(async {
+        let reader = open_file(input_file_path.as_ref(), options).await?;
+        read_input(reader, input_file_path.as_ref(), output_directory_path.as_ref(), options).await
+    })?;
+    pending.write(options)
+}
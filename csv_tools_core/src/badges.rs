@@ -0,0 +1,120 @@
+//! # Status Badges (`--badge-dir <path>`)
+//!
+//! Writes two tiny JSON files per analyzed feed in the shields.io endpoint format, so
+//! catalog pages can show live badges fed by a scheduled run:
+//!
+//! * `{basename}_quality_badge.json` - quality score, e.g. `data quality | 97%`
+//! * `{basename}_status_badge.json` - last-run status and date, e.g. `last run | ok 2026-10-16`
+//!
+//! The file names carry no timestamp, so each run overwrites the previous badges and a
+//! badge URL stays stable (`https://img.shields.io/endpoint?url=<url of the json>`).
+//!
+//! The quality score is the share of rows that are both readable and not length
+//! outliers: `100 × readable rows / all rows × (1 − outlier rows / readable rows)`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::clock::utc_date;
+use crate::json::json_string;
+
+/// Outcome of the latest run of a feed
+#[derive(Debug, Clone)]
+pub enum RunStatus {
+    /// Analysis completed and every row was readable
+    Ok,
+    /// Analysis completed but some rows could not be read
+    ReadErrors(u64),
+    /// Analysis did not complete
+    Failed,
+}
+
+/// Computes the quality score (0-100) from the row counts of a run.
+///
+/// # Arguments
+///
+/// * `total_rows` - Rows read successfully
+/// * `error_rows` - Rows that could not be read
+/// * `outlier_rows` - Readable rows above the outlier threshold
+pub fn quality_score(total_rows: u64, error_rows: u64, outlier_rows: u64) -> f64 {
+    let all_rows = total_rows + error_rows;
+    if all_rows == 0 || total_rows == 0 {
+        return 0.0;
+    }
+    let readable = total_rows as f64 / all_rows as f64;
+    let regular = 1.0 - outlier_rows as f64 / total_rows as f64;
+    100.0 * readable * regular
+}
+
+/// Writes the quality badge for a feed.
+///
+/// # Arguments
+///
+/// * `badge_dir` - Directory that holds the badge files
+/// * `basename` - Feed name used in the file name
+/// * `score` - Quality score from [`quality_score`]
+///
+/// # Returns
+///
+/// * `Result<PathBuf, io::Error>` - Path of the written badge
+pub fn write_quality_badge(badge_dir: impl AsRef<Path>, basename: &str, score: f64) -> Result<PathBuf, io::Error> {
+    let color = match score {
+        s if s >= 95.0 => "brightgreen",
+        s if s >= 85.0 => "green",
+        s if s >= 70.0 => "yellow",
+        s if s >= 50.0 => "orange",
+        _ => "red",
+    };
+    // Floor, so a score just below 100 never shows as a perfect 100%
+    let message = format!("{}%", score.floor() as u64);
+    write_badge(badge_dir.as_ref().join(format!("{}_quality_badge.json", basename)), "data quality", &message, color, false)
+}
+
+/// Writes the last-run status badge for a feed.
+///
+/// # Arguments
+///
+/// * `badge_dir` - Directory that holds the badge files
+/// * `basename` - Feed name used in the file name
+/// * `status` - Outcome of the run
+/// * `run_timestamp` - Unix timestamp of the run (seconds), shown as a UTC date
+///
+/// # Returns
+///
+/// * `Result<PathBuf, io::Error>` - Path of the written badge
+pub fn write_status_badge(
+    badge_dir: impl AsRef<Path>,
+    basename: &str,
+    status: &RunStatus,
+    run_timestamp: u64,
+) -> Result<PathBuf, io::Error> {
+    let date = utc_date(run_timestamp);
+    let (message, color, is_error) = match status {
+        RunStatus::Ok => (format!("ok {}", date), "brightgreen", false),
+        RunStatus::ReadErrors(count) => (format!("{} unreadable rows {}", count, date), "orange", false),
+        RunStatus::Failed => (format!("failed {}", date), "red", true),
+    };
+    write_badge(badge_dir.as_ref().join(format!("{}_status_badge.json", basename)), "last run", &message, color, is_error)
+}
+
+/// Writes one shields.io endpoint JSON document.
+fn write_badge(path: PathBuf, label: &str, message: &str, color: &str, is_error: bool) -> Result<PathBuf, io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut json = format!(
+        "{{\"schemaVersion\":1,\"label\":{},\"message\":{},\"color\":{}",
+        json_string(label), json_string(message), json_string(color)
+    );
+    if is_error {
+        json.push_str(",\"isError\":true");
+    }
+    json.push_str("}\n");
+
+    // Write then rename, so a page polling the badge never reads a half-written file
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json)?;
+    fs::rename(&temp_path, &path)?;
+    Ok(path)
+}
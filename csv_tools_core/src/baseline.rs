@@ -0,0 +1,278 @@
+//! # Baselines for CI (`--save-baseline`, `--check-baseline`)
+//!
+//! A pipeline that receives a new data drop every day can fail the drop instead of loading
+//! it when its rows look worse than a known-good one. `--save-baseline` keeps the metrics of
+//! a good file, and `--check-baseline` compares each later file with them:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders_2024-06-03.csv reports --save-baseline ci/orders_baseline.json
+//! $ csv_row_analyzer orders_2024-06-10.csv reports --check-baseline ci/orders_baseline.json || exit 1
+//! Warning: orders_2024-06-10: outlier rows rose from 0.42% to 3.10% (tolerance +1 point) against the baseline of orders_2024-06-03
+//! ```
+//!
+//! The baseline is a small JSON document:
+//!
+//! ```json
+//! {"baseline_version": 1, "source": "orders_2024-06-03", "count_mode": "chars", "include_line_endings": false,
+//!  "rows": 1200000, "outlier_pct": 0.42, "max_length": 1830, "field_count_match_pct": 99.98}
+//! ```
+//!
+//! Three metrics are checked, each against a tolerance:
+//!
+//! * Outlier percentage - the share of rows above the upper outlier fence may rise by at
+//!   most `outlier_pct` percentage points (default 1)
+//! * Max length - the longest row may grow by at most `max_length_pct` percent (default 10)
+//! * Field-count consistency - the share of data records with the header's field count may
+//!   drop by at most `field_count_pct` percentage points (default 0)
+//!
+//! Only a change for the worse is a regression: fewer outliers or shorter rows pass. Each
+//! regression is printed as a warning, and once every report is written the run ends with
+//! exit status [`EXIT_BASELINE_REGRESSION`], even when the file also appears truncated
+//! (which alone exits with 3); in a `--directory` run the file is listed as `regressed` in
+//! the directory summary. The tolerances are set in the config file:
+//!
+//! ```text
+//! [baseline]
+//! outlier_pct = 2           # allow up to 2 more percentage points of outlier rows
+//! max_length_pct = 25       # allow the longest row to grow by up to 25%
+//! field_count_pct = 0.1     # allow 0.1 points fewer records with the header's field count
+//! ```
+//!
+//! A baseline applies only to lengths measured the same way, so checking with a different
+//! `--count-mode` or `--include-line-endings` setting than the save is an error. Field
+//! counts are not kept with `--low-memory`, so such runs save no field-count consistency,
+//! and the metric is not checked when either side lacks it. In a `--directory` run every
+//! file saves to the same path, so the last file's baseline is the one kept.
+
+use std::fmt;
+use std::fs;
+use std::io;
+
+use crate::config::AnalyzerConfig;
+use crate::count_mode::CountMode;
+use crate::csv_row_analyzer::AnalysisResult;
+use crate::field_counts::FieldCounter;
+use crate::json::{json_number, json_string, parse_json, JsonValue};
+use crate::messages::{Language, Message};
+
+/// Exit status when a file regressed beyond the tolerances of its baseline
+pub const EXIT_BASELINE_REGRESSION: i32 = 2;
+/// Version of the baseline file layout
+pub const BASELINE_VERSION: u64 = 1;
+
+/// Limits beyond which a change from the baseline is a regression
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineTolerances {
+    /// Allowed rise of the outlier row share, in percentage points
+    pub outlier_pct: f64,
+    /// Allowed growth of the longest row, in percent
+    pub max_length_pct: f64,
+    /// Allowed drop of the share of records with the header's field count, in percentage points
+    pub field_count_pct: f64,
+}
+
+impl Default for BaselineTolerances {
+    fn default() -> Self {
+        BaselineTolerances { outlier_pct: 1.0, max_length_pct: 10.0, field_count_pct: 0.0 }
+    }
+}
+
+/// Reads the `[baseline]` tolerances from the config file, keeping the defaults for keys
+/// that are not set.
+///
+/// # Arguments
+///
+/// * `config` - Parsed config file, if any
+///
+/// # Returns
+///
+/// * `Result<BaselineTolerances, String>` - The tolerances, or an error naming the bad line
+pub fn load_baseline_tolerances(config: Option<&AnalyzerConfig>) -> Result<BaselineTolerances, String> {
+    let mut tolerances = BaselineTolerances::default();
+    let Some(config) = config else {
+        return Ok(tolerances);
+    };
+    for entry in config.section_entries("baseline") {
+        let value = entry.value.parse().ok().filter(|v: &f64| *v >= 0.0)
+            .ok_or_else(|| format!("line {}: invalid value '{}' for {}", entry.line_number, entry.value, entry.key))?;
+        match entry.key.as_str() {
+            "outlier_pct" => tolerances.outlier_pct = value,
+            "max_length_pct" => tolerances.max_length_pct = value,
+            "field_count_pct" => tolerances.field_count_pct = value,
+            key => return Err(format!("line {}: unknown [baseline] key '{}'", entry.line_number, key)),
+        }
+    }
+    Ok(tolerances)
+}
+
+/// Share of data records whose field count matches the header's, in percent.
+///
+/// # Returns
+///
+/// * `Option<f64>` - The share, or None without a header or data records
+pub fn field_count_match_pct(counter: &FieldCounter) -> Option<f64> {
+    counter.header_fields()?;
+    let records = counter.records();
+    if records.is_empty() {
+        return None;
+    }
+    let matching = records.iter().filter(|&&(_, fields)| counter.matches_header(fields)).count();
+    Some(matching as f64 / records.len() as f64 * 100.0)
+}
+
+/// Metrics of a known-good file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    /// Basename of the file the baseline was saved from
+    pub source: String,
+    pub count_mode: CountMode,
+    pub include_line_endings: bool,
+    pub rows: u64,
+    /// Share of rows above the upper outlier fence, in percent
+    pub outlier_pct: f64,
+    pub max_length: usize,
+    /// Share of data records with the header's field count, in percent
+    pub field_count_match_pct: Option<f64>,
+}
+
+impl Baseline {
+    /// Takes the metrics of an analyzed file.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - Summary of the analyzed file
+    /// * `field_count_match_pct` - Field-count consistency, if the fields were counted
+    /// * `count_mode` - Unit of the row lengths
+    /// * `include_line_endings` - Whether the lengths include the line terminators
+    pub fn from_run(result: &AnalysisResult, field_count_match_pct: Option<f64>, count_mode: CountMode, include_line_endings: bool) -> Self {
+        Baseline {
+            source: result.basename.clone(),
+            count_mode,
+            include_line_endings,
+            rows: result.total_rows,
+            outlier_pct: percent(result.outlier_rows, result.total_rows),
+            max_length: result.statistics.max,
+            field_count_match_pct,
+        }
+    }
+
+    /// Writes the baseline as a JSON document.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\n  \"baseline_version\": {},\n  \"source\": {},\n  \"count_mode\": {},\n  \"include_line_endings\": {},\n  \"rows\": {},\n  \"outlier_pct\": {},\n  \"max_length\": {},\n  \"field_count_match_pct\": {}\n}}\n",
+            BASELINE_VERSION,
+            json_string(&self.source),
+            json_string(self.count_mode.name()),
+            self.include_line_endings,
+            self.rows,
+            json_number(rounded(self.outlier_pct)),
+            self.max_length,
+            self.field_count_match_pct.map_or("null".to_string(), |pct| json_number(rounded(pct))),
+        )
+    }
+
+    /// Reads a baseline written by `--save-baseline`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Baseline, String>` - The baseline, or a message naming the file and problem
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Could not read baseline {}: {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("Invalid baseline {}: {}", path, e))
+    }
+
+    /// Parses the JSON text of a baseline.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let document = parse_json(text)?;
+        match document.get("baseline_version").and_then(JsonValue::as_f64) {
+            Some(version) if version == BASELINE_VERSION as f64 => {},
+            Some(version) => return Err(format!("unsupported baseline_version {} (expected {})", version, BASELINE_VERSION)),
+            None => return Err("missing baseline_version".to_string()),
+        }
+        let number = |key: &str| document.get(key).and_then(JsonValue::as_f64).ok_or(format!("missing {}", key));
+        Ok(Baseline {
+            source: document.get("source").and_then(JsonValue::as_str).unwrap_or("").to_string(),
+            count_mode: CountMode::parse(document.get("count_mode").and_then(JsonValue::as_str).unwrap_or("chars"))?,
+            include_line_endings: document.get("include_line_endings") == Some(&JsonValue::Bool(true)),
+            rows: number("rows")? as u64,
+            outlier_pct: number("outlier_pct")?,
+            max_length: number("max_length")? as usize,
+            field_count_match_pct: document.get("field_count_match_pct").and_then(JsonValue::as_f64),
+        })
+    }
+
+    /// Lists the metrics of a later file that regressed beyond the tolerances.
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - Metrics of the checked file
+    /// * `tolerances` - Allowed changes for the worse
+    /// * `language` - Language of the sentences (`--lang`)
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - One sentence per regressed metric, empty when the file passes
+    pub fn regressions(&self, current: &Baseline, tolerances: &BaselineTolerances, language: Language) -> Vec<String> {
+        let mut regressions = Vec::new();
+        if current.outlier_pct > self.outlier_pct + tolerances.outlier_pct {
+            regressions.push(Message::OutlierShareRose.text(language, &[
+                &format!("{:.2}", self.outlier_pct), &format!("{:.2}", current.outlier_pct),
+                &tolerances.outlier_pct, &points(tolerances.outlier_pct, language),
+            ]));
+        }
+        if current.max_length as f64 > self.max_length as f64 * (1.0 + tolerances.max_length_pct / 100.0) {
+            regressions.push(Message::LongestRowGrew.text(
+                language, &[&self.max_length, &current.max_length, &tolerances.max_length_pct],
+            ));
+        }
+        if let (Some(baseline), Some(now)) = (self.field_count_match_pct, current.field_count_match_pct)
+            && now < baseline - tolerances.field_count_pct
+        {
+            regressions.push(Message::FieldCountMatchFell.text(language, &[
+                &format!("{:.2}", baseline), &format!("{:.2}", now),
+                &tolerances.field_count_pct, &points(tolerances.field_count_pct, language),
+            ]));
+        }
+        regressions
+    }
+}
+
+fn percent(part: u64, whole: u64) -> f64 {
+    if whole == 0 { 0.0 } else { part as f64 / whole as f64 * 100.0 }
+}
+
+fn rounded(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+fn points(value: f64, language: Language) -> String {
+    let unit = if value == 1.0 { Message::PercentagePoint } else { Message::PercentagePoints };
+    unit.text(language, &[])
+}
+
+/// A file that regressed beyond its baseline, raised after every report was written
+#[derive(Debug)]
+pub struct BaselineRegressionError {
+    regressions: Vec<String>,
+}
+
+impl fmt::Display for BaselineRegressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.regressions.join("; "))
+    }
+}
+
+impl std::error::Error for BaselineRegressionError {}
+
+impl BaselineRegressionError {
+    /// Wraps the regressions of a file in an `io::Error`.
+    pub fn into_io_error(regressions: Vec<String>) -> io::Error {
+        io::Error::other(BaselineRegressionError { regressions })
+    }
+
+    /// Whether an error is a [`BaselineRegressionError`], i.e. the analysis and its reports
+    /// completed.
+    pub fn is_baseline_regression_error(error: &io::Error) -> bool {
+        error.get_ref().is_some_and(|inner| inner.is::<BaselineRegressionError>())
+    }
+}
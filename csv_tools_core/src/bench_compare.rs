@@ -0,0 +1,256 @@
+//! # Serial/Parallel Benchmark (`bench-compare` subcommand)
+//!
+//! Runs this analyzer on the same file without and with `--parallel`, checks that both
+//! measured the same rows, and prints their wall time, throughput, and peak memory side by
+//! side, so whether to use `--parallel` can be decided for the actual hardware and file
+//! profile:
+//!
+//! ```bash
+//! $ csv_row_analyzer bench-compare large_file.csv --runs 3
+//! Engine       Wall Time    Throughput     Peak Memory
+//! serial       4.812 s      212.4 MB/s     6.1 MB
+//! parallel     1.377 s      742.3 MB/s     1.9 GB
+//! parallel is 3.49x faster than serial
+//! Results: identical (25000000 rows, 412 distinct lengths)
+//! ```
+//!
+//! Each engine runs as its own process with its default reports, written to a scratch
+//! directory under the system temp directory that is removed afterwards. With `--runs`,
+//! each engine runs that many times and the fastest run is shown, with the highest peak
+//! memory of all runs.
+//!
+//! The results are compared through the `char_counts` report (the length of every row,
+//! in file order) and the `value_counts` report (rows per length). Peak memory is the
+//! resident set high-water mark, sampled from `/proc` while the engine runs; it is shown
+//! as `n/a` where `/proc` is not available. The exit code is 0 when the results are
+//! identical and [`EXIT_RESULTS_DIFFER`] when they are not.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cli::{parse_command_line, BENCH_COMPARE_COMMAND};
+
+/// Exit code when the two engines disagree
+pub const EXIT_RESULTS_DIFFER: i32 = 5;
+
+/// How often a running engine's memory is sampled
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Options of the `bench-compare` subcommand
+#[derive(Debug, Clone)]
+pub struct BenchCompareOptions {
+    pub input_path: String,
+    /// Runs per engine (`--runs`)
+    pub runs: usize,
+}
+
+/// Timing and memory of one engine, over all its runs
+struct EngineResult {
+    name: &'static str,
+    best_time: Duration,
+    peak_memory_kb: Option<u64>,
+    /// Report directory of the last run
+    report_dir: PathBuf,
+}
+
+/// Parses the arguments that follow `bench-compare`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<BenchCompareOptions, String>` - Parsed options or an error message
+pub fn parse_bench_compare_arguments(args: &[String]) -> Result<BenchCompareOptions, String> {
+    let parsed = parse_command_line(&BENCH_COMPARE_COMMAND, args)?;
+    let mut runs = 1;
+
+    for (flag, value) in &parsed.flags {
+        if *flag == "--runs" {
+            runs = value.parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| format!("invalid --runs value '{}'", value))?;
+        }
+    }
+
+    Ok(BenchCompareOptions {
+        input_path: parsed.positionals.first().cloned().ok_or("Missing input file path")?,
+        runs,
+    })
+}
+
+/// Runs the `bench-compare` subcommand and prints the comparison.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+///
+/// # Returns
+///
+/// * `Result<bool, io::Error>` - Whether both engines produced identical results, or an
+///   error when an engine could not be run
+pub fn run_bench_compare(options: &BenchCompareOptions) -> Result<bool, io::Error> {
+    let input_bytes = fs::metadata(&options.input_path)?.len();
+    let binary = env::current_exe()?;
+
+    let scratch_dir = env::temp_dir().join(format!("csv_bench_compare_{}", process::id()));
+    let result = (|| {
+        let serial = run_engine("serial", &binary, &[], options, &scratch_dir)?;
+        let parallel = run_engine("parallel", &binary, &["--parallel"], options, &scratch_dir)?;
+        print_comparison(&[&serial, &parallel], input_bytes);
+        compare_results(&serial.report_dir, &parallel.report_dir)
+    })();
+    let _ = fs::remove_dir_all(&scratch_dir);
+    result
+}
+
+/// Runs one engine `options.runs` times with its default reports.
+fn run_engine(
+    name: &'static str,
+    binary: &Path,
+    engine_flags: &[&str],
+    options: &BenchCompareOptions,
+    scratch_dir: &Path,
+) -> Result<EngineResult, io::Error> {
+    let mut best_time = Duration::MAX;
+    let mut peak_memory_kb = None;
+    let mut report_dir = PathBuf::new();
+
+    for run in 0..options.runs {
+        report_dir = scratch_dir.join(format!("{}_{}", name, run));
+        let started = Instant::now();
+        let mut child = Command::new(binary)
+            .arg(&options.input_path)
+            .arg(&report_dir)
+            .args(engine_flags)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| io::Error::new(e.kind(), format!("could not run the {} engine {}: {}", name, binary.display(), e)))?;
+        let status = loop {
+            if let Some(memory) = peak_memory_of(child.id()) {
+                peak_memory_kb = peak_memory_kb.max(Some(memory));
+            }
+            match child.try_wait()? {
+                Some(status) => break status,
+                None => thread::sleep(MEMORY_SAMPLE_INTERVAL),
+            }
+        };
+        let elapsed = started.elapsed();
+        if !status.success() {
+            return Err(io::Error::other(format!("the {} engine {} failed with {}", name, binary.display(), status)));
+        }
+        best_time = best_time.min(elapsed);
+    }
+
+    Ok(EngineResult { name, best_time, peak_memory_kb, report_dir })
+}
+
+/// Resident set high-water mark of a running process, in kB (Linux only).
+fn peak_memory_of(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status.lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+fn print_comparison(engines: &[&EngineResult], input_bytes: u64) {
+    println!("{:<12} {:<12} {:<14} {:<12}", "Engine", "Wall Time", "Throughput", "Peak Memory");
+    for engine in engines {
+        let seconds = engine.best_time.as_secs_f64();
+        let throughput = if seconds > 0.0 { input_bytes as f64 / seconds } else { 0.0 };
+        println!("{:<12} {:<12} {:<14} {:<12}",
+                 engine.name,
+                 format!("{:.3} s", seconds),
+                 format!("{}/s", format_bytes(throughput)),
+                 engine.peak_memory_kb.map_or("n/a".to_string(), |kb| format_bytes(kb as f64 * 1024.0)));
+    }
+    if let [serial, parallel] = engines {
+        let (faster, slower) = if parallel.best_time < serial.best_time { (parallel, serial) } else { (serial, parallel) };
+        if faster.best_time > Duration::ZERO {
+            println!("{} is {:.2}x faster than {}",
+                     faster.name, slower.best_time.as_secs_f64() / faster.best_time.as_secs_f64(), slower.name);
+        }
+    }
+}
+
+/// Formats a byte count with a binary unit (`212.4 MB`).
+fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Compares the row lengths and length counts of two report directories and prints the verdict.
+fn compare_results(serial_dir: &Path, parallel_dir: &Path) -> Result<bool, io::Error> {
+    let serial_lengths = read_column(&find_report(serial_dir, "_char_counts_report_")?, "character_length")?;
+    let parallel_lengths = read_column(&find_report(parallel_dir, "_char_counts_report_")?, "character_length")?;
+    let serial_counts = read_value_counts(&find_report(serial_dir, "_value_counts_report_")?)?;
+    let parallel_counts = read_value_counts(&find_report(parallel_dir, "_value_counts_report_")?)?;
+
+    let mut differences = Vec::new();
+    if serial_lengths.len() != parallel_lengths.len() {
+        differences.push(format!("serial measured {} rows, parallel {}", serial_lengths.len(), parallel_lengths.len()));
+    }
+    if let Some(row) = serial_lengths.iter().zip(&parallel_lengths).position(|(serial, parallel)| serial != parallel) {
+        differences.push(format!("first differing row length at row {}: serial '{}', parallel '{}'",
+                                 row, serial_lengths[row], parallel_lengths[row]));
+    }
+    if serial_counts != parallel_counts {
+        differences.push("the value_counts reports differ".to_string());
+    }
+
+    if differences.is_empty() {
+        println!("Results: identical ({} rows, {} distinct lengths)", serial_lengths.len(), serial_counts.len());
+        return Ok(true);
+    }
+    println!("Results: DIFFERENT");
+    for difference in differences {
+        println!("  {}", difference);
+    }
+    Ok(false)
+}
+
+/// The one report in `dir` whose name contains `marker`.
+fn find_report(dir: &Path, marker: &str) -> Result<PathBuf, io::Error> {
+    fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.contains(marker)))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no {} report in {}", marker.trim_matches('_'), dir.display())))
+}
+
+/// Values of the named column of a CSV report, in row order.
+fn read_column(path: &Path, column: &str) -> Result<Vec<String>, io::Error> {
+    let content = fs::read_to_string(path)?;
+    let mut lines = content.lines();
+    let index = lines.next()
+        .and_then(|header| header.split(',').position(|name| name == column))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("no {} column in {}", column, path.display())))?;
+    Ok(lines.map(|line| line.split(',').nth(index).unwrap_or("").to_string()).collect())
+}
+
+/// Rows per length from a value_counts report.
+fn read_value_counts(path: &Path) -> Result<BTreeMap<String, String>, io::Error> {
+    let content = fs::read_to_string(path)?;
+    Ok(content.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split(',');
+            Some((fields.next()?.to_string(), fields.next()?.to_string()))
+        })
+        .collect())
+}
@@ -0,0 +1,125 @@
+//! # Char Counts Reports as Input (`--input-kind char-report`)
+//!
+//! The char_counts report of a run lists the length of every row, which is everything the
+//! statistics, the outlier fences, and the distribution reports are computed from. Given
+//! that report instead of the data, the analyzer rebuilds them from the recorded lengths,
+//! so a new outlier method, page size, or language can be applied to a historical run
+//! whose original input has been purged:
+//!
+//! ```bash
+//! $ csv_row_analyzer reports/orders_char_counts_report_1767225600.csv rerun --input-kind char-report --outlier-method mad
+//! $ ls rerun/
+//! orders_md_outliers_report_1767312000.md  orders_txt_outliers_report_1767312000.txt
+//! orders_value_counts_report_1767312000.csv  orders_pages_valuecounts_report_1767312000.csv
+//! ```
+//!
+//! The columns are found by name, so reports of any schema version with `row_index` and
+//! `character_length` columns work, with or without `--row-flags`. The reports are named
+//! after the original input (`orders`), and no new char_counts report is written. A report
+//! of a `--csv-mode` run (with `physical_lines`) keeps its record line numbers. Rows
+//! recorded as `error_reading_line` count as unreadable.
+//!
+//! The recorded lengths are used as they are: whether they include line endings was
+//! decided by the original run. Everything that needs the row contents (column analyses,
+//! row flags, header fingerprint, previews of the largest rows) is not available, so the
+//! flags asking for it are refused.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::csv_row_analyzer::{
+    generate_timestamp, write_reports, AnalysisOptions, AnalysisResult, RowAccumulator,
+};
+use crate::delimiter::Delimiter;
+use crate::report_status::ReportLog;
+
+/// What the input file is
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum InputKind {
+    /// Delimited data (default)
+    #[default]
+    Data,
+    /// A char_counts report of an earlier run
+    CharReport,
+}
+
+impl InputKind {
+    /// Parses an input kind (`data` or `char-report`).
+    pub fn parse(text: &str) -> Result<InputKind, String> {
+        match text.trim().to_lowercase().as_str() {
+            "data" => Ok(InputKind::Data),
+            "char-report" => Ok(InputKind::CharReport),
+            _ => Err(format!("invalid input kind '{}' (expected data or char-report)", text)),
+        }
+    }
+}
+
+/// Recomputes the analysis of an earlier run from its char_counts report and writes the
+/// reports of this run.
+///
+/// # Arguments
+///
+/// * `input_file_path` - The char_counts report
+/// * `output_directory_path` - Directory where report files will be saved
+/// * `options` - Run options
+///
+/// # Returns
+///
+/// * `Result<AnalysisResult, io::Error>` - The recomputed summary, or an Error if the
+///   report cannot be read or is not a char_counts report
+pub fn analyze_char_report(
+    input_file_path: impl AsRef<Path>,
+    output_directory_path: impl AsRef<Path>,
+    options: &AnalysisOptions,
+) -> Result<AnalysisResult, io::Error> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let basename = original_basename(&options.report_basename(&input_file_path)?);
+    let timestamp = generate_timestamp(&options.clock)?;
+
+    let mut lines = BufReader::new(File::open(&input_file_path)?).lines();
+    let header = lines.next().transpose()?.unwrap_or_default();
+    let columns: Vec<&str> = header.split(',').collect();
+    let column = |name: &str| columns.iter().position(|&column| column == name);
+    let (Some(row_index_column), Some(length_column)) = (column("row_index"), column("character_length")) else {
+        return Err(invalid(format!("{} is not a char_counts report (no row_index and character_length columns)",
+                                   input_file_path.as_ref().display())));
+    };
+    let record_columns = column("file_row").zip(column("physical_lines"));
+
+    let mut accumulator = RowAccumulator::new(options, &input_file_path.as_ref().to_string_lossy(), Delimiter::default());
+    for (line_number, line) in lines.enumerate() {
+        let line = line?;
+        let fields: Vec<&str> = line.split(',').collect();
+        let number = |index: usize| fields.get(index).and_then(|value| value.parse::<usize>().ok());
+        let malformed = || invalid(format!("malformed line {} of the report: {}", line_number + 2, line));
+        let row_index = number(row_index_column).ok_or_else(malformed)?;
+        let char_count = match fields.get(length_column) {
+            Some(&"error_reading_line") => None,
+            _ => Some(number(length_column).ok_or_else(malformed)?),
+        };
+        let record_lines = match record_columns {
+            Some((file_row, physical_lines)) => Some((
+                number(file_row).filter(|&file_row| file_row > 0).ok_or_else(malformed)? - 1,
+                number(physical_lines).ok_or_else(malformed)?,
+            )),
+            None => None,
+        };
+        accumulator.add_measured_row(row_index, char_count, record_lines);
+    }
+
+    let analysis = accumulator.finish(basename.clone(), None);
+    let report_dir = options.output_layout.unwrap_or_default().report_dir(&output_directory_path, &basename, &timestamp);
+    let report_log = ReportLog::new(&report_dir, options.report_fallback_dir.as_deref(), options.report_retries)
+        .with_writes_disabled(options.no_reports)
+        .with_language(options.language);
+    write_reports(&input_file_path, &timestamp, &analysis, options, report_log)
+}
+
+/// The basename of the original input: `orders` for `orders_char_counts_report_1767225600`.
+fn original_basename(report_basename: &str) -> String {
+    match report_basename.rsplit_once("_char_counts_report_") {
+        Some((original, timestamp)) if !original.is_empty() && timestamp.bytes().all(|b| b.is_ascii_digit()) => original.to_string(),
+        _ => report_basename.to_string(),
+    }
+}
@@ -0,0 +1,134 @@
+//! # Chunk Checksums
+//!
+//! Each worker computes an Adler-32 checksum over the bytes of the rows it processed
+//! (content and line terminator). Adler-32 checksums of consecutive byte runs can be
+//! combined without the bytes themselves, so the chunk checksums merged in chunk order
+//! must equal the checksum of the input as read. That checksum is taken by a
+//! [`ChecksumReader`] from the read buffers themselves, before they are split into rows,
+//! so it does not depend on the row boundaries it checks. A row dropped, duplicated, or
+//! moved at a chunk boundary changes the combined checksum or byte count, and the
+//! analysis stops before writing statistics built from the wrong rows.
+
+use std::fmt;
+use std::io::{self, BufRead, Read};
+use std::ops::Range;
+
+/// Largest prime below 2^16, the Adler-32 modulus
+const MOD_ADLER: u64 = 65521;
+/// Bytes summed before the sums are reduced, zlib's `NMAX`: the most that keeps `b`
+/// below 2^32 when it starts just under the modulus and every byte is 255
+const NMAX: usize = 5552;
+
+/// Running Adler-32 checksum of a byte run, with the run's length
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adler32 {
+    a: u64,
+    b: u64,
+    /// Number of bytes checksummed
+    pub length: u64,
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Adler32 { a: 1, b: 0, length: 0 }
+    }
+}
+
+impl Adler32 {
+    /// Adds bytes to the end of the run.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for block in bytes.chunks(NMAX) {
+            for &byte in block {
+                self.a += byte as u64;
+                self.b += self.a;
+            }
+            self.a %= MOD_ADLER;
+            self.b %= MOD_ADLER;
+        }
+        self.length += bytes.len() as u64;
+    }
+
+    /// Appends the checksum of the run that directly follows this one.
+    ///
+    /// Follows zlib's `adler32_combine`: the result equals the checksum of both runs'
+    /// bytes concatenated.
+    pub fn combine(&mut self, next: &Adler32) {
+        let remainder = next.length % MOD_ADLER;
+        let a = (self.a + next.a + MOD_ADLER - 1) % MOD_ADLER;
+        let b = (remainder * self.a + self.b + next.b + MOD_ADLER - remainder) % MOD_ADLER;
+        self.a = a;
+        self.b = b;
+        self.length += next.length;
+    }
+
+    /// The 32-bit checksum value.
+    pub fn value(&self) -> u32 {
+        ((self.b << 16) | self.a) as u32
+    }
+}
+
+impl fmt::Display for Adler32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "adler32 {:08x} over {} bytes", self.value(), self.length)
+    }
+}
+
+/// Buffered reader that checksums the bytes consumed from it, as the reader's buffers
+/// hold them
+///
+/// Only the bytes at positions inside the window count; the position starts at the
+/// reader's offset in the file, so the window is given in file offsets.
+pub struct ChecksumReader<R> {
+    inner: R,
+    position: u64,
+    window: Range<u64>,
+    /// Checksum of the bytes consumed inside the window so far
+    pub checksum: Adler32,
+}
+
+impl<R: BufRead> ChecksumReader<R> {
+    /// Checksums every byte consumed from `inner`.
+    pub fn new(inner: R) -> Self {
+        ChecksumReader::with_window(inner, 0, 0..u64::MAX)
+    }
+
+    /// Checksums the bytes consumed from `inner`, which is positioned at file offset
+    /// `position`, that lie inside `window`.
+    pub fn with_window(inner: R, position: u64, window: Range<u64>) -> Self {
+        ChecksumReader { inner, position, window, checksum: Adler32::default() }
+    }
+}
+
+impl<R: BufRead> BufRead for ChecksumReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if amount == 0 {
+            return;
+        }
+        // The bytes being consumed are still at the front of the inner buffer, so this
+        // returns them without reading
+        if let Ok(buffer) = self.inner.fill_buf() {
+            let end = self.position + amount as u64;
+            let from = self.window.start.clamp(self.position, end);
+            let to = self.window.end.clamp(self.position, end);
+            if from < to {
+                self.checksum.update(&buffer[(from - self.position) as usize..(to - self.position) as usize]);
+            }
+        }
+        self.position += amount as u64;
+        self.inner.consume(amount);
+    }
+}
+
+impl<R: BufRead> Read for ChecksumReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let count = available.len().min(out.len());
+        out[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
@@ -0,0 +1,93 @@
+//! # Suggested Chunk Size
+//!
+//! A downstream system that reads rows into fixed buffers (a message queue, an embedding
+//! model's context, a database page) needs a size that fits nearly every row. The outlier
+//! reports suggest one from the row lengths instead of the fixed `--chars-per-page`:
+//!
+//! ```text
+//! ## Suggested Chunk Size
+//! - **95th Percentile Row Length**: 1790 chars
+//! - **Suggested Chunk Size**: 2048 chars (the 95th percentile rounded up to a power of two)
+//!
+//! | Chunk Size | Rows Above | Percentage |
+//! |------------|------------|------------|
+//! | 1024 | 310 | 12.40% |
+//! | 2048 | 118 | 4.72% |
+//! | 3000 | 41 | 1.64% |
+//! | 4096 | 6 | 0.24% |
+//! | 16384 | 0 | 0.00% |
+//! ```
+//!
+//! The suggestion is the [`SUGGESTION_PERCENTILE`] row length (nearest rank, see the
+//! `sorted_lengths` module) rounded up to the next power of two, so about one row in
+//! twenty is longer and has to be split or handled on its own. The table lists how many rows are longer than each candidate: the
+//! suggestion, the `--chars-per-page` size, and the powers of four from 1024 characters,
+//! up to the first candidate no row exceeds.
+
+use crate::sorted_lengths::SortedLengths;
+
+/// Percentile of the row lengths the suggested chunk size covers
+pub const SUGGESTION_PERCENTILE: u64 = 95;
+/// Smallest standard candidate size; the others are its powers of four
+const SMALLEST_CANDIDATE: usize = 1024;
+
+/// A suggested chunk size, and the rows longer than each candidate size
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkSizes {
+    /// Row length at [`SUGGESTION_PERCENTILE`]
+    pub percentile_length: usize,
+    /// The percentile length rounded up to a power of two
+    pub suggested: usize,
+    /// (chunk size, rows longer than it), smallest size first
+    pub candidates: Vec<(usize, u64)>,
+    /// Rows the counts are out of
+    pub total_rows: u64,
+}
+
+impl ChunkSizes {
+    /// Suggests a chunk size for rows with the given (length, count) pairs, or None
+    /// without rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `length_counts` - (row length, number of rows) pairs, in any order
+    /// * `chars_per_page` - The page size of the page estimates, listed as a candidate
+    pub fn suggest(length_counts: &[(usize, u64)], chars_per_page: usize) -> Option<ChunkSizes> {
+        let mut sorted = length_counts.to_vec();
+        sorted.sort_unstable();
+        let total_rows: u64 = sorted.iter().map(|&(_, count)| count).sum();
+        if total_rows == 0 {
+            return None;
+        }
+
+        let percentile_length = SortedLengths::Counts(&sorted).percentile(SUGGESTION_PERCENTILE as f64).unwrap_or(0);
+        let suggested = percentile_length.max(1).next_power_of_two();
+
+        let max_length = sorted.last().map_or(0, |&(length, _)| length);
+        let mut sizes = vec![suggested, chars_per_page];
+        let mut size = SMALLEST_CANDIDATE;
+        loop {
+            sizes.push(size);
+            if size >= max_length {
+                break;
+            }
+            size *= 4;
+        }
+        sizes.sort_unstable();
+        sizes.dedup();
+        // Sizes past the first one that fits every row add nothing
+        if let Some(fits_all) = sizes.iter().position(|&size| size >= max_length) {
+            sizes.truncate(fits_all + 1);
+        }
+
+        let candidates = sizes.into_iter()
+            .map(|size| (size, sorted.iter().filter(|&&(length, _)| length > size).map(|&(_, count)| count).sum()))
+            .collect();
+        Some(ChunkSizes { percentile_length, suggested, candidates, total_rows })
+    }
+
+    /// Share of the rows longer than a candidate size, in percent.
+    pub fn percentage(&self, rows_above: u64) -> f64 {
+        rows_above as f64 * 100.0 / self.total_rows as f64
+    }
+}
@@ -0,0 +1,673 @@
+//! # Command-Line Registry
+//!
+//! Every command, positional argument, and flag is declared once in [`MAIN_COMMAND`] and
+//! [`SUBCOMMANDS`]. The same tables drive argument parsing, `--help`, and the shell
+//! completion scripts:
+//!
+//! ```bash
+//! $ csv_row_analyzer --help
+//! $ csv_row_analyzer help preview-row
+//! $ source <(csv_row_analyzer completions bash)      # or zsh; fish:
+//! $ csv_row_analyzer completions fish > ~/.config/fish/completions/csv_row_analyzer.fish
+//! ```
+//!
+//! ## Parsing Rules
+//!
+//! * Flags take their value as the next argument (`--history runs.csv`) or inline
+//!   (`--history=runs.csv`), and may appear before or after the positional arguments
+//! * A value that starts with `--` must be given inline (`--rule=--x`): `--history --entropy`
+//!   is an error instead of a history file named `--entropy`
+//! * `--` ends the flags; everything after it is positional, so an input file or output
+//!   directory whose name starts with `--` can still be given
+//! * Unknown flags and extra positional arguments are errors
+
+use std::fmt::Write as _;
+
+use crate::selftest::SELFTEST_CASE_NAMES;
+
+/// What a flag or positional argument takes, which also picks its shell completion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueKind {
+    /// A flag without a value
+    Switch,
+    /// A file path
+    File,
+    /// A directory path
+    Directory,
+    /// Free text such as a number, range, or rule
+    Text,
+    /// One of a fixed set of words
+    Choice(&'static [&'static str]),
+}
+
+/// A flag such as `--history <path>`
+#[derive(Debug)]
+pub struct FlagSpec {
+    pub name: &'static str,
+    /// Placeholder shown in help, e.g. `<path>` (empty for switches)
+    pub placeholder: &'static str,
+    pub kind: ValueKind,
+    pub help: &'static str,
+}
+
+/// A positional argument
+#[derive(Debug)]
+pub struct PositionalSpec {
+    pub name: &'static str,
+    pub kind: ValueKind,
+    pub help: &'static str,
+    /// Whether the argument takes every remaining positional value (last positional only)
+    pub repeated: bool,
+}
+
+/// A command (the analyzer itself, or a subcommand) with its arguments
+#[derive(Debug)]
+pub struct CommandSpec {
+    /// Subcommand name, or empty for the main analyzer command
+    pub name: &'static str,
+    pub about: &'static str,
+    /// Usage lines, without the program name
+    pub usage: &'static [&'static str],
+    /// Positional arguments in order; later ones are optional
+    pub positionals: &'static [PositionalSpec],
+    pub flags: &'static [FlagSpec],
+}
+
+const fn flag(name: &'static str, placeholder: &'static str, kind: ValueKind, help: &'static str) -> FlagSpec {
+    FlagSpec { name, placeholder, kind, help }
+}
+
+const fn switch(name: &'static str, help: &'static str) -> FlagSpec {
+    FlagSpec { name, placeholder: "", kind: ValueKind::Switch, help }
+}
+
+const fn positional(name: &'static str, kind: ValueKind, help: &'static str) -> PositionalSpec {
+    PositionalSpec { name, kind, help, repeated: false }
+}
+
+const fn repeated_positional(name: &'static str, kind: ValueKind, help: &'static str) -> PositionalSpec {
+    PositionalSpec { name, kind, help, repeated: true }
+}
+
+/// Shells with completion scripts
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// The analyzer command
+pub const MAIN_COMMAND: CommandSpec = CommandSpec {
+    name: "",
+    about: "Analyze the character count of every row of a CSV file and report the distribution and outliers",
+    usage: &[
+        "<input_csv_path> [output_directory] [options]",
+        "--directory <path> [output_directory] [options]",
+        "<command> [arguments]",
+    ],
+    positionals: &[
+        positional("input_csv_path", ValueKind::File, "File to analyze (omitted with --directory)"),
+        positional("output_directory", ValueKind::Directory, "Directory for the reports (default: reports)"),
+    ],
+    flags: &[
+        flag("--directory", "<path>", ValueKind::Directory, "Analyze every selected file in a directory"),
+        flag("--glob", "<pattern>", ValueKind::Text, "Analyze every file matching a pattern such as 'exports/**/*.csv'"),
+        flag("--extensions", "<list>", ValueKind::Text, "Select directory files by extension, e.g. csv,tsv,txt,dat (default: csv)"),
+        switch("--all-files", "Select every directory file whose content looks delimited"),
+        switch("--recursive", "With --directory, also analyze the files of every subdirectory"),
+        switch("--skip-hidden", "Leave out files and directories whose name starts with a dot"),
+        flag("--min-size", "<size>", ValueKind::Text, "Leave out directory files smaller than this (e.g. 1K)"),
+        flag("--max-size", "<size>", ValueKind::Text, "Leave out directory files larger than this (e.g. 2G)"),
+        flag("--threads", "<n>", ValueKind::Text, "Files of a directory or glob run analyzed at once (default: 1, or one per core with --parallel), and the row workers of --parallel and --lengths-only (default: one per core)"),
+        flag("--max-depth", "<n>", ValueKind::Text, "Subdirectory levels read with --recursive (default: no limit)"),
+        flag("--config", "<path>", ValueKind::File, "Load an INI-style config file"),
+        flag("--history", "<path>", ValueKind::File, "Append a summary line per file to a history CSV and report changes since the last run"),
+        flag("--rows", "<start..end>", ValueKind::Text, "Analyze only these rows, e.g. 1000000..2000000"),
+        flag("--bytes", "<start..end>", ValueKind::Text, "Analyze only the rows starting in this byte range, e.g. 5G..6G"),
+        flag("--db-sink", "<url>", ValueKind::Text, "Insert the run summary and outliers into postgres://... or mysql://..."),
+        flag("--timezone", "<zone>", ValueKind::Text, "Time zone of the report times: UTC, local, +HH:MM, or a name like Europe/Berlin (default: UTC)"),
+        flag("--layout", "<name>", ValueKind::Choice(&["flat", "dated"]), "Put reports in <output>/<YYYY-MM-DD>/<basename>/ with dated (default: flat)"),
+        flag("--fallback-dir", "<path>", ValueKind::Directory, "Directory for reports that cannot be written to the output directory"),
+        flag("--report-retries", "<n>", ValueKind::Text, "Retry each failed report write up to n times"),
+        flag("--badge-dir", "<path>", ValueKind::Directory, "Write shields.io badge JSON per file"),
+        switch("--include-line-endings", "Count each row's \\n or \\r\\n toward its length"),
+        flag("--count-mode", "<mode>", ValueKind::Choice(&["chars", "bytes", "utf16", "graphemes"]), "Count row lengths in characters, UTF-8 bytes, UTF-16 code units, or graphemes (default: chars)"),
+        switch("--csv-mode", "Measure CSV records, joining the lines of quoted fields that contain line breaks"),
+        flag("--delimiter", "<delimiter>", ValueKind::Text, "Split fields at comma, semicolon, tab, pipe, or a character (default: detected per file)"),
+        switch("--profile-columns", "Profile each column and flag values that need text loading"),
+        switch("--redact-examples", "Mask the shortest and longest value of each column in the column format report"),
+        switch("--table-schema", "Write the inferred columns as a Frictionless Table Schema"),
+        switch("--loader-hints", "Write PostgreSQL, Redshift, and Snowflake COPY settings derived from the file"),
+        switch("--save-state", "Also save the length histogram, every row index, and the column value lengths for the query command"),
+        switch("--column-totals", "Sum each numeric column to check control totals"),
+        switch("--value-shapes", "Report columns of URL, email, UUID, or JSON values and their conformity"),
+        switch("--null-report", "Count the empty, whitespace-only, and null-token (NULL, NA, -) cells of each column"),
+        switch("--column-reports-json", "Write the column format, totals, value shapes, null counts, rules, sizes, and model scores reports as JSON"),
+        flag("--format", "<text|json>", ValueKind::Text, "With json, also write the whole analysis as one JSON report"),
+        switch("--html-report", "Also write a self-contained HTML page with the length and page charts and a sortable outlier table"),
+        switch("--no-reports", "Print the summary without writing anything to disk (JSON with --format json)"),
+        switch("--stdout-summary", "Print only the summary to stdout; without an output directory, write nothing"),
+        flag("--reports", "<list>", ValueKind::Text, "Write only these standard reports: char_counts, value_counts, pages, outliers_md, outliers_txt"),
+        flag("--writer", "<name>", ValueKind::Text, "Also write the report of a registered writer, e.g. summary-json or summary-csv (repeatable)"),
+        flag("--rule", "<rule>", ValueKind::Text, "Check a cross-column rule such as \"end_date >= start_date\" (repeatable)"),
+        flag("--exclude-columns-regex", "<pattern>", ValueKind::Text, "Leave columns whose names match the pattern out of the column analyses"),
+        flag("--max-columns", "<n>", ValueKind::Text, "Refuse files with more columns than this for the column analyses (default: 10000)"),
+        flag("--sample-columns", "<n>", ValueKind::Text, "Profile n evenly spaced columns of a wide file instead of every column"),
+        flag("--null-tokens", "<list>", ValueKind::Text, "Treat these comma-separated values (e.g. NA,NULL,\\N) as empty when profiling"),
+        flag("--verify-sample", "<n>", ValueKind::Text, "Re-read n random outlier rows by byte offset and check their recorded lengths"),
+        flag("--seed", "<n>", ValueKind::Text, "Seed of the random picks (default: the run timestamp)"),
+        flag("--sample-rows", "<n>", ValueKind::Text, "Show n redacted typical rows and n rows per outlier tier in the markdown report"),
+        switch("--aggregates-only", "Only aggregate distributions: no row indices or row content in any output"),
+        switch("--extract-outliers", "Copy the outlier rows, prefixed with their line numbers, into a CSV of their own"),
+        switch("--field-counts", "Count the fields of every record and report rows that differ from the header"),
+        switch("--entropy", "Measure each row's byte entropy and flag abnormal rows"),
+        flag("--length-buckets", "<bounds>", ValueKind::Text, "Write the line numbers of rows per length bucket, e.g. 100KB,1MB"),
+        flag("--input-kind", "<kind>", ValueKind::Choice(&["data", "char-report"]), "Read the input as data (default) or as an earlier run's char_counts report"),
+        flag("--outlier-method", "<method>", ValueKind::Choice(&["iqr", "zscore", "mad"]), "Derive the outlier fences from the IQR, the z-score, or the MAD (default: iqr)"),
+        flag("--outlier-factor", "<f>", ValueKind::Text, "Spreads from the center to a fence (default: 1.5 for iqr, 3 for zscore, 3.5 for mad)"),
+        flag("--length-scale", "<scale>", ValueKind::Text, "Compute the outlier fences on linear, log, or sqrt row lengths"),
+        flag("--flag-stream", "<path>", ValueKind::File, "Append flagged rows as JSON lines while the analysis runs, for a tailing process"),
+        switch("--read-ahead", "Read the next buffer on a background thread"),
+        flag("--read-buffer-size", "<size>", ValueKind::Text, "Fixed read buffer size such as 8M (default: 64K, growing with long rows)"),
+        flag("--read-retries", "<n>", ValueKind::Text, "Retry reads failing with a transient I/O error up to n times (default: 3)"),
+        flag("--read-retry-delay", "<ms>", ValueKind::Text, "Wait before the first read retry, doubled for each further one (default: 100)"),
+        flag("--report-buffer-size", "<size>", ValueKind::Text, "Report write buffer size such as 1M (default: 64K; 0 for unbuffered)"),
+        switch("--parallel", "Decode and count the rows on worker threads; the reports are the same"),
+        switch("--lengths-only", "Only the length histogram and summary statistics, read in parallel byte ranges"),
+        switch("--low-memory", "Keep no per-row lengths; memory stays constant however many rows the file has"),
+        switch("--porcelain", "Print only one tab-separated line of key metrics per file to stdout"),
+        flag("--hash-algorithm", "<name>", ValueKind::Choice(&["fnv1a", "xxh64", "sha256"]), "Hash of the header fingerprint (default: fnv1a)"),
+        flag("--hash-salt", "<salt>", ValueKind::Text, "Salt the row hashes; with sha256 they become HMAC-SHA256"),
+        flag("--chars-per-page", "<n>", ValueKind::Text, "Characters per page of the page-equivalent metrics (default: 3000)"),
+        flag("--min-row-chars", "<n>", ValueKind::Text, "Leave rows shorter than n characters out of the statistics, counting them separately"),
+        flag("--max-example-rows", "<n>", ValueKind::Text, "Row indices kept per row length for the report examples (default: 1000, at least 5)"),
+        switch("--row-flags", "Add a flags column (O,S,B,E,Q,F) to the char_counts report"),
+        switch("--column-sizes", "Report the characters, bytes, and share of the file of every column"),
+        switch("--parquet-sidecar", "Write each data record's length, field count, and flags as a Parquet sidecar"),
+        flag("--export-model", "<path>", ValueKind::File, "Write the row and column length distributions as a JSON model"),
+        flag("--score-against", "<path>", ValueKind::File, "Flag records whose lengths are unusual for an exported model"),
+        flag("--save-baseline", "<path>", ValueKind::File, "Save the outlier share, longest row, and field-count consistency as a JSON baseline"),
+        flag("--check-baseline", "<path>", ValueKind::File, "Exit with status 2 when the file regressed beyond the tolerances of a saved baseline (before status 3 for a truncated file)"),
+        flag("--lang", "<code>", ValueKind::Choice(&["en", "es", "pt"]), "Language of console messages and report headings (default: en)"),
+        switch("--async", "Read inputs with tokio async I/O (requires building with --features async)"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `analyze`: the analyzer command by name, the same run as without one
+pub const ANALYZE_COMMAND: CommandSpec = CommandSpec {
+    name: "analyze",
+    about: "Analyze the character count of every row of a CSV file (the default command)",
+    usage: &[
+        "<input_csv_path> [output_directory] [options]",
+        "--directory <path> [output_directory] [options]",
+    ],
+    positionals: MAIN_COMMAND.positionals,
+    flags: MAIN_COMMAND.flags,
+};
+
+/// `validate-encoding`: encoding check without reports (see the `encoding_check` module)
+pub const VALIDATE_ENCODING_COMMAND: CommandSpec = CommandSpec {
+    name: "validate-encoding",
+    about: "Only check the encoding of a file, without writing reports",
+    usage: &["<input_csv_path> [options]"],
+    positionals: &[positional("input_csv_path", ValueKind::File, "File to check")],
+    flags: &[
+        flag("--encoding", "<name>", ValueKind::Choice(&["utf-8", "ascii", "windows-1252"]), "Expected encoding (default: utf-8)"),
+        flag("--max-violations", "<n>", ValueKind::Text, "Stop listing violations after n (default: 20)"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `preview-row`: head and tail of one row (see the `row_preview` module)
+pub const PREVIEW_ROW_COMMAND: CommandSpec = CommandSpec {
+    name: "preview-row",
+    about: "Print the first and last bytes of one row",
+    usage: &["<input_csv_path> <row_index> [options]"],
+    positionals: &[
+        positional("input_csv_path", ValueKind::File, "File to read"),
+        positional("row_index", ValueKind::Text, "0-based row index, as in the reports"),
+    ],
+    flags: &[
+        flag("--bytes", "<n>", ValueKind::Text, "Bytes shown from each end of the row (default: 200)"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `check`: problems of small repository-tracked CSV files (see the `fixture_check` module)
+pub const CHECK_COMMAND: CommandSpec = CommandSpec {
+    name: "check",
+    about: "Check small CSV files for field-count mismatches, trailing whitespace, and invalid UTF-8",
+    usage: &["<input_csv_path>... [options]"],
+    positionals: &[repeated_positional("input_csv_path", ValueKind::File, "Files to check")],
+    flags: &[
+        flag("--format", "<format>", ValueKind::Choice(&["gnu", "github"]), "file:line:column lines (gnu, default) or GitHub Actions annotations"),
+        flag("--delimiter", "<delimiter>", ValueKind::Text, "Split fields at comma, semicolon, tab, pipe, or a character (default: detected per file)"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `translate-rows`: line numbers to records and back (see the `translate_rows` module)
+pub const TRANSLATE_ROWS_COMMAND: CommandSpec = CommandSpec {
+    name: "translate-rows",
+    about: "Translate line numbers to the records of a --csv-mode run, or records to line numbers",
+    usage: &["<char_counts_report> <numbers> [options]"],
+    positionals: &[
+        positional("char_counts_report", ValueKind::File, "char_counts report of a --csv-mode run"),
+        positional("numbers", ValueKind::Text, "Comma-separated 1-based line numbers, or row indices with --from record"),
+    ],
+    flags: &[
+        flag("--from", "<numbering>", ValueKind::Choice(&["line", "record"]), "Numbering of the given numbers (default: line)"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `query`: drill-down questions answered from a `--save-state` file (see the `analysis_state` module)
+pub const QUERY_COMMAND: CommandSpec = CommandSpec {
+    name: "query",
+    about: "Answer questions about an analyzed file from its --save-state file, without rereading the input",
+    usage: &["<state_file> [options]"],
+    positionals: &[positional("state_file", ValueKind::File, "State file written by --save-state")],
+    flags: &[
+        flag("--rows-with-length-over", "<n>", ValueKind::Text, "List the row index and length of every row longer than n"),
+        flag("--percentile", "<p>", ValueKind::Text, "Print the p-th percentile of the row lengths"),
+        flag("--column", "<name>", ValueKind::Text, "With --percentile, use the value lengths of this column"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `compare`: the row lengths of two files side by side (see the `length_compare` module)
+pub const COMPARE_COMMAND: CommandSpec = CommandSpec {
+    name: "compare",
+    about: "Compare the row-length distributions of two files and report the deltas, new outlier lengths, and KS distance",
+    usage: &["<baseline_csv_path> <current_csv_path> [output_directory] [options]"],
+    positionals: &[
+        positional("baseline_csv_path", ValueKind::File, "Earlier file to compare against"),
+        positional("current_csv_path", ValueKind::File, "Later file to compare"),
+        positional("output_directory", ValueKind::Directory, "Directory for the reports (default: reports)"),
+    ],
+    flags: &[
+        switch("--csv-mode", "Treat quoted line breaks as part of the record"),
+        flag("--count-mode", "<mode>", ValueKind::Choice(&["chars", "bytes", "utf16", "graphemes"]), "Count row lengths in characters, UTF-8 bytes, UTF-16 code units, or graphemes (default: chars)"),
+        switch("--include-line-endings", "Count each row's \\n or \\r\\n toward its length"),
+        flag("--outlier-method", "<method>", ValueKind::Choice(&["iqr", "zscore", "mad"]), "Derive the outlier fences from the IQR, the z-score, or the MAD (default: iqr)"),
+        flag("--outlier-factor", "<f>", ValueKind::Text, "Spreads from the center to a fence (default: 1.5 for iqr, 3 for zscore, 3.5 for mad)"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `extract`: rows by range or length, copied as they are (see the `extract_rows` module)
+pub const EXTRACT_COMMAND: CommandSpec = CommandSpec {
+    name: "extract",
+    about: "Copy the rows in a row range or a length range, after the header, to stdout or a file",
+    usage: &["<input_csv_path> [options]"],
+    positionals: &[positional("input_csv_path", ValueKind::File, "File to read")],
+    flags: &[
+        flag("--rows", "<a..b>", ValueKind::Text, "Copy the rows of this range of row indices"),
+        flag("--length-over", "<n>", ValueKind::Text, "Copy the rows longer than n characters"),
+        flag("--length-under", "<n>", ValueKind::Text, "Copy the rows shorter than n characters"),
+        switch("--csv-mode", "Treat quoted line breaks as part of the record"),
+        flag("--output", "<path>", ValueKind::File, "Write the rows to this file instead of stdout"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `split`: one file per row-length range (see the `split_rows` module)
+pub const SPLIT_COMMAND: CommandSpec = CommandSpec {
+    name: "split",
+    about: "Split a file into one CSV per row-length range, each starting with the header",
+    usage: &["<input_csv_path> <output_directory> --lengths <bounds> [options]"],
+    positionals: &[
+        positional("input_csv_path", ValueKind::File, "File to split"),
+        positional("output_directory", ValueKind::Directory, "Directory for the split files"),
+    ],
+    flags: &[
+        flag("--lengths", "<bounds>", ValueKind::Text, "Comma-separated bounds between the ranges, e.g. 1KB,100KB"),
+        switch("--csv-mode", "Treat quoted line breaks as part of the record"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `selftest`: the analyzer on its built-in corpus of tricky inputs (see the `selftest` module)
+pub const SELFTEST_COMMAND: CommandSpec = CommandSpec {
+    name: "selftest",
+    about: "Analyze a built-in corpus of tricky inputs and check the reports",
+    usage: &["[options]"],
+    positionals: &[],
+    flags: &[
+        flag("--case", "<name>", ValueKind::Choice(SELFTEST_CASE_NAMES), "Run only this case"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `bench-compare`: serial and `--parallel` run on one file (see the `bench_compare` module)
+pub const BENCH_COMPARE_COMMAND: CommandSpec = CommandSpec {
+    name: "bench-compare",
+    about: "Time a serial and a --parallel run on one file and compare their results",
+    usage: &["<input_csv_path> [options]"],
+    positionals: &[positional("input_csv_path", ValueKind::File, "File to analyze")],
+    flags: &[
+        flag("--runs", "<n>", ValueKind::Text, "Runs per engine; the fastest is shown (default: 1)"),
+        switch("--help", "Print this help"),
+    ],
+};
+
+/// `completions`: shell completion script generated from this registry
+pub const COMPLETIONS_COMMAND: CommandSpec = CommandSpec {
+    name: "completions",
+    about: "Print a shell completion script",
+    usage: &["<shell>"],
+    positionals: &[positional("shell", ValueKind::Choice(SHELLS), "bash, zsh, or fish")],
+    flags: &[switch("--help", "Print this help")],
+};
+
+/// `help`: the help of the analyzer or of a subcommand
+pub const HELP_COMMAND: CommandSpec = CommandSpec {
+    name: "help",
+    about: "Print the help of the analyzer or of a command",
+    usage: &["[command]"],
+    positionals: &[positional("command", ValueKind::Choice(&["analyze", "validate-encoding", "check", "preview-row", "translate-rows", "compare", "extract", "split", "query", "selftest", "bench-compare", "completions", "help"]), "Command to describe")],
+    flags: &[],
+};
+
+/// Subcommands that run instead of the full analysis
+pub const SUBCOMMANDS: &[&CommandSpec] = &[
+    &ANALYZE_COMMAND,
+    &VALIDATE_ENCODING_COMMAND,
+    &CHECK_COMMAND,
+    &PREVIEW_ROW_COMMAND,
+    &TRANSLATE_ROWS_COMMAND,
+    &COMPARE_COMMAND,
+    &EXTRACT_COMMAND,
+    &SPLIT_COMMAND,
+    &QUERY_COMMAND,
+    &SELFTEST_COMMAND,
+    &BENCH_COMPARE_COMMAND,
+    &COMPLETIONS_COMMAND,
+    &HELP_COMMAND,
+];
+
+/// Finds a subcommand by name.
+pub fn find_subcommand(name: &str) -> Option<&'static CommandSpec> {
+    SUBCOMMANDS.iter().copied().find(|command| command.name == name)
+}
+
+/// Flags and positional arguments of one command line, in the order given
+#[derive(Debug, Default)]
+pub struct ParsedArguments {
+    /// (flag name, value) pairs; the value is empty for switches
+    pub flags: Vec<(&'static str, String)>,
+    pub positionals: Vec<String>,
+}
+
+/// Parses arguments against a command's flag and positional declarations.
+///
+/// # Arguments
+///
+/// * `command` - Declarations of the command being parsed
+/// * `args` - Arguments after the program (and subcommand) name
+///
+/// # Returns
+///
+/// * `Result<ParsedArguments, String>` - The flags and positional arguments, or an error message
+pub fn parse_command_line(command: &'static CommandSpec, args: &[String]) -> Result<ParsedArguments, String> {
+    let mut parsed = ParsedArguments::default();
+    let mut args = args.iter();
+    let mut flags_ended = false;
+
+    while let Some(arg) = args.next() {
+        if flags_ended || !arg.starts_with("--") {
+            if parsed.positionals.len() >= command.positionals.len()
+                && !command.positionals.last().is_some_and(|spec| spec.repeated)
+            {
+                return Err(format!("Unexpected argument: {}", arg));
+            }
+            parsed.positionals.push(arg.clone());
+            continue;
+        }
+        if arg == "--" {
+            flags_ended = true;
+            continue;
+        }
+
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (arg.as_str(), None),
+        };
+        let spec = command.flags.iter()
+            .find(|spec| spec.name == name)
+            .ok_or_else(|| format!("Unknown argument: {}", name))?;
+
+        let value = match (spec.kind, inline_value) {
+            (ValueKind::Switch, None) => String::new(),
+            (ValueKind::Switch, Some(_)) => return Err(format!("{} does not take a value", spec.name)),
+            (_, Some(value)) => value.to_string(),
+            (_, None) => match args.next() {
+                Some(value) if !value.starts_with("--") => value.clone(),
+                Some(value) => return Err(format!(
+                    "{} requires a {} argument, got the flag '{}' (write {}={} if that is the value)",
+                    spec.name, spec.placeholder, value, spec.name, value
+                )),
+                None => return Err(format!("{} requires a {} argument", spec.name, spec.placeholder)),
+            },
+        };
+        parsed.flags.push((spec.name, value));
+    }
+    Ok(parsed)
+}
+
+/// Whether `--help` or `-h` appears before any `--` separator.
+pub fn wants_help(args: &[String]) -> bool {
+    args.iter()
+        .take_while(|arg| arg.as_str() != "--")
+        .any(|arg| arg == "--help" || arg == "-h")
+}
+
+/// Renders the full help of a command.
+///
+/// # Arguments
+///
+/// * `program` - Program name shown in the usage lines
+/// * `command` - Command to describe
+pub fn render_help(program: &str, command: &CommandSpec) -> String {
+    let mut help = String::new();
+    let prefix = if command.name.is_empty() { program.to_string() } else { format!("{} {}", program, command.name) };
+    let _ = writeln!(help, "{}\n", command.about);
+    for (index, usage) in command.usage.iter().enumerate() {
+        let label = if index == 0 { "Usage:" } else { "      " };
+        let _ = writeln!(help, "{} {} {}", label, prefix, usage);
+    }
+
+    if !command.positionals.is_empty() {
+        let _ = writeln!(help, "\nArguments:");
+        for spec in command.positionals {
+            let dots = if spec.repeated { "..." } else { "" };
+            let _ = writeln!(help, "  {:<28} {}", format!("<{}>{}", spec.name, dots), spec.help);
+        }
+    }
+    if !command.flags.is_empty() {
+        let _ = writeln!(help, "\nOptions:");
+        for spec in command.flags {
+            let _ = writeln!(help, "  {:<28} {}", format!("{} {}", spec.name, spec.placeholder).trim_end(), spec.help);
+        }
+    }
+    if command.name.is_empty() {
+        let _ = writeln!(help, "\nCommands:");
+        for subcommand in SUBCOMMANDS {
+            let _ = writeln!(help, "  {:<28} {}", subcommand.name, subcommand.about);
+        }
+        let _ = writeln!(help, "\nRun '{} help <command>' for the options of a command.", program);
+    }
+    help
+}
+
+/// Generates the completion script for a shell.
+///
+/// # Arguments
+///
+/// * `shell` - `bash`, `zsh`, or `fish`
+/// * `program` - Command name the script completes
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The script, or an error message for an unknown shell
+pub fn completion_script(shell: &str, program: &str) -> Result<String, String> {
+    match shell.to_lowercase().as_str() {
+        "bash" => Ok(bash_completion(program)),
+        "zsh" => Ok(zsh_completion(program)),
+        "fish" => Ok(fish_completion(program)),
+        _ => Err(format!("unsupported shell '{}' (use {})", shell, SHELLS.join(", "))),
+    }
+}
+
+/// Shell function name derived from the program name.
+fn function_name(program: &str) -> String {
+    format!("_{}", program.replace(|c: char| !c.is_ascii_alphanumeric(), "_"))
+}
+
+fn bash_completion(program: &str) -> String {
+    let function = function_name(program);
+    let mut script = String::new();
+    let _ = writeln!(script, "# bash completion for {}", program);
+    let _ = writeln!(script, "{}() {{", function);
+    let _ = writeln!(script, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"");
+    let _ = writeln!(script, "    local command=\"\"");
+    let _ = writeln!(script, "    if (( COMP_CWORD > 1 )); then command=\"${{COMP_WORDS[1]}}\"; fi");
+    let _ = writeln!(script, "    case \"$command\" in");
+    for command in SUBCOMMANDS {
+        let _ = writeln!(script, "        {})", command.name);
+        write_bash_command(&mut script, command);
+        let _ = writeln!(script, "            ;;");
+    }
+    let _ = writeln!(script, "        *)");
+    write_bash_command(&mut script, &MAIN_COMMAND);
+    let subcommands: Vec<&str> = SUBCOMMANDS.iter().map(|command| command.name).collect();
+    let _ = writeln!(script, "            if (( COMP_CWORD == 1 )); then COMPREPLY+=($(compgen -W \"{}\" -- \"$cur\")); fi", subcommands.join(" "));
+    let _ = writeln!(script, "            ;;");
+    let _ = writeln!(script, "    esac");
+    let _ = writeln!(script, "}}");
+    let _ = writeln!(script, "complete -o filenames -F {} {}", function, program);
+    script
+}
+
+/// Writes the bash completion of one command: flag values, flag names, then positionals.
+fn write_bash_command(script: &mut String, command: &CommandSpec) {
+    let _ = writeln!(script, "            case \"$prev\" in");
+    for spec in command.flags.iter().filter(|spec| spec.kind != ValueKind::Switch) {
+        let _ = writeln!(script, "                {}) {}; return ;;", spec.name, bash_values(spec.kind));
+    }
+    let _ = writeln!(script, "            esac");
+    let flags: Vec<&str> = command.flags.iter().map(|spec| spec.name).collect();
+    let _ = writeln!(script, "            if [[ \"$cur\" == -* ]]; then COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return; fi", flags.join(" "));
+    let positional_kind = command.positionals.first().map_or(ValueKind::File, |spec| spec.kind);
+    let _ = writeln!(script, "            {}", bash_values(positional_kind));
+}
+
+fn bash_values(kind: ValueKind) -> String {
+    match kind {
+        ValueKind::File => "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string(),
+        ValueKind::Directory => "COMPREPLY=($(compgen -d -- \"$cur\"))".to_string(),
+        ValueKind::Choice(choices) => format!("COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", choices.join(" ")),
+        ValueKind::Switch | ValueKind::Text => "COMPREPLY=()".to_string(),
+    }
+}
+
+fn zsh_completion(program: &str) -> String {
+    let function = function_name(program);
+    let mut script = String::new();
+    let _ = writeln!(script, "#compdef {}", program);
+    let _ = writeln!(script, "{}() {{", function);
+    let _ = writeln!(script, "    local -a commands");
+    let _ = writeln!(script, "    commands=(");
+    for command in SUBCOMMANDS {
+        let _ = writeln!(script, "        '{}:{}'", command.name, zsh_escape(command.about));
+    }
+    let _ = writeln!(script, "    )");
+    let _ = writeln!(script, "    if (( CURRENT > 2 )); then");
+    let _ = writeln!(script, "        case $words[2] in");
+    for command in SUBCOMMANDS {
+        let _ = writeln!(script, "            {})", command.name);
+        let _ = writeln!(script, "                shift words; (( CURRENT-- ))");
+        let _ = writeln!(script, "                _arguments {}", zsh_arguments(command, None));
+        let _ = writeln!(script, "                return ;;");
+    }
+    let _ = writeln!(script, "        esac");
+    let _ = writeln!(script, "    fi");
+    let _ = writeln!(script, "    _arguments {}", zsh_arguments(&MAIN_COMMAND, Some("'1: :->first'")));
+    let _ = writeln!(script, "    if [[ $state == first ]]; then");
+    let _ = writeln!(script, "        _alternative 'commands:command:_describe command commands' 'files:input file:_files'");
+    let _ = writeln!(script, "    fi");
+    let _ = writeln!(script, "}}");
+    let _ = writeln!(script, "compdef {} {}", function, program);
+    script
+}
+
+/// Builds the `_arguments` specs of a command; `first` replaces the first positional spec.
+fn zsh_arguments(command: &CommandSpec, first: Option<&str>) -> String {
+    let mut specs: Vec<String> = command.flags.iter()
+        .map(|spec| match spec.kind {
+            ValueKind::Switch => format!("'{}[{}]'", spec.name, zsh_escape(spec.help)),
+            kind => format!("'{}=[{}]:{}:{}'", spec.name, zsh_escape(spec.help), zsh_escape(spec.placeholder), zsh_action(kind)),
+        })
+        .collect();
+    for (index, spec) in command.positionals.iter().enumerate() {
+        match (index, first) {
+            (0, Some(first)) => specs.push(first.to_string()),
+            _ if spec.repeated => specs.push(format!("'*:{}:{}'", spec.name, zsh_action(spec.kind))),
+            _ => specs.push(format!("'{}:{}:{}'", index + 1, spec.name, zsh_action(spec.kind))),
+        }
+    }
+    specs.join(" ")
+}
+
+fn zsh_action(kind: ValueKind) -> String {
+    match kind {
+        ValueKind::File => "_files".to_string(),
+        ValueKind::Directory => "_files -/".to_string(),
+        ValueKind::Choice(choices) => format!("({})", choices.join(" ")),
+        ValueKind::Switch | ValueKind::Text => " ".to_string(),
+    }
+}
+
+/// Escapes help text for a single-quoted zsh `_arguments` spec.
+fn zsh_escape(text: &str) -> String {
+    text.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn fish_completion(program: &str) -> String {
+    let mut script = String::new();
+    let subcommands: Vec<&str> = SUBCOMMANDS.iter().map(|command| command.name).collect();
+    let _ = writeln!(script, "# fish completion for {}", program);
+    let _ = writeln!(script, "complete -c {} -e", program);
+    for command in SUBCOMMANDS {
+        let _ = writeln!(script, "complete -c {} -n '__fish_use_subcommand' -f -a {} -d '{}'", program, command.name, fish_escape(command.about));
+    }
+    let main_condition = format!("not __fish_seen_subcommand_from {}", subcommands.join(" "));
+    write_fish_flags(&mut script, program, &main_condition, &MAIN_COMMAND);
+    for command in SUBCOMMANDS {
+        let condition = format!("__fish_seen_subcommand_from {}", command.name);
+        write_fish_flags(&mut script, program, &condition, command);
+        if let Some(PositionalSpec { kind: ValueKind::Choice(choices), .. }) = command.positionals.first() {
+            let _ = writeln!(script, "complete -c {} -n '{}' -f -a '{}'", program, condition, choices.join(" "));
+        }
+    }
+    script
+}
+
+fn write_fish_flags(script: &mut String, program: &str, condition: &str, command: &CommandSpec) {
+    for spec in command.flags {
+        let long = spec.name.trim_start_matches("--");
+        let values = match spec.kind {
+            ValueKind::Switch => String::new(),
+            ValueKind::File => " -r -F".to_string(),
+            ValueKind::Directory => " -x -a '(__fish_complete_directories)'".to_string(),
+            ValueKind::Text => " -x".to_string(),
+            ValueKind::Choice(choices) => format!(" -x -a '{}'", choices.join(" ")),
+        };
+        let _ = writeln!(script, "complete -c {} -n '{}' -l {}{} -d '{}'", program, condition, long, values, fish_escape(spec.help));
+    }
+}
+
+/// Escapes text for a single-quoted fish string.
+fn fish_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\'', "\\'")
+}
@@ -0,0 +1,468 @@
+//! # Clock and Report Time Zone (`--timezone`)
+//!
+//! Every run reads the time once, from a [`Clock`]: the Unix timestamp in the report names
+//! and the history file, and the human-readable "Report generated" line of the outlier
+//! reports, all come from that one reading. The binary uses the system clock; the library
+//! can run the analysis on a fixed clock instead, which makes the report content
+//! repeatable in tests (see the `test_support` module).
+//!
+//! The human-readable time is shown in UTC unless `--timezone` names another zone:
+//!
+//! ```bash
+//! $ csv_row_analyzer feed.csv reports --timezone Europe/Berlin
+//! # Report generated: 2026-10-16 14:03:12 CEST (UTC+02:00)
+//! $ csv_row_analyzer feed.csv reports --timezone -05:00
+//! $ csv_row_analyzer feed.csv reports --timezone local
+//! ```
+//!
+//! * `UTC` - the default
+//! * `+HH:MM` / `-HH:MM` - a fixed offset from UTC
+//! * An IANA zone name such as `America/New_York` - read from the system's zone files
+//!   (`$TZDIR`, or `/usr/share/zoneinfo`), with daylight saving time; after the last
+//!   transition in the file, the POSIX `TZ` rule in its footer (such as
+//!   `CET-1CEST,M3.5.0,M10.5.0/3`) gives the offset, so zone files that list transitions
+//!   only up to the current rule (zic's default since 2020b) still change with the seasons
+//! * `local` - the zone of `$TZ`, or of `/etc/localtime`
+//!
+//! The timestamps in report names stay Unix seconds, and `--layout dated` keeps filing by
+//! the UTC date, so neither depends on where the run happens.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where the zone files are read from when `$TZDIR` is not set
+const DEFAULT_ZONE_DIR: &str = "/usr/share/zoneinfo";
+/// The system's local zone when `$TZ` is not set
+const LOCALTIME_PATH: &str = "/etc/localtime";
+
+/// Source of the current time of a run
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Clock {
+    /// The system clock
+    #[default]
+    System,
+    /// A fixed Unix timestamp, for repeatable reports
+    Fixed(u64),
+}
+
+impl Clock {
+    /// Returns the current Unix timestamp in seconds.
+    pub fn now(&self) -> Result<u64, io::Error> {
+        match self {
+            Clock::System => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .map_err(io::Error::other),
+            Clock::Fixed(seconds) => Ok(*seconds),
+        }
+    }
+}
+
+/// Offset and abbreviation of local time in one period of a zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalTimeType {
+    /// Seconds east of UTC
+    pub offset_seconds: i64,
+    /// Such as `CEST`, or empty
+    pub abbreviation: String,
+}
+
+/// Time zone of the human-readable times in the reports
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ReportTimeZone {
+    #[default]
+    Utc,
+    /// A fixed offset from UTC, in seconds
+    Offset(i64),
+    /// A zone read from a zone file: transition times with the index of the local time
+    /// type that starts at each, in time order, and the rule for times after the last one
+    Zone {
+        name: String,
+        transitions: Vec<(i64, usize)>,
+        types: Vec<LocalTimeType>,
+        rule: Option<Box<PosixRule>>,
+    },
+}
+
+/// A POSIX `TZ` rule: standard time, and the daylight saving time it switches to each year
+#[derive(Debug, Clone, PartialEq)]
+pub struct PosixRule {
+    pub standard: LocalTimeType,
+    pub daylight: Option<DaylightSaving>,
+}
+
+/// Daylight saving time of a [`PosixRule`] and when it starts and ends
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaylightSaving {
+    pub time_type: LocalTimeType,
+    /// Local standard time at which daylight saving time starts
+    pub start: RuleTransition,
+    /// Local daylight saving time at which it ends
+    pub end: RuleTransition,
+}
+
+/// Day of the year and time of day of a yearly transition
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuleTransition {
+    pub day: RuleDay,
+    /// Seconds after local midnight; may be negative or past 24 hours
+    pub time: i64,
+}
+
+/// Day of a yearly transition, in the three forms of POSIX `TZ` rules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuleDay {
+    /// `Jn`: day 1 to 365, never counting February 29
+    Julian(i64),
+    /// `n`: day 0 to 365, counting February 29 in leap years
+    ZeroBased(i64),
+    /// `Mm.w.d`: weekday `d` (0 = Sunday) of week `w` of month `m`; week 5 is the last
+    MonthWeekDay { month: i64, week: i64, weekday: i64 },
+}
+
+impl ReportTimeZone {
+    /// Parses a `--timezone` value: `UTC`, `local`, `+HH:MM`, `-HH:MM`, or a zone name.
+    pub fn parse(name: &str) -> Result<ReportTimeZone, String> {
+        match name {
+            "UTC" | "utc" | "Z" => Ok(ReportTimeZone::Utc),
+            "local" => match env::var("TZ").ok().filter(|tz| !tz.is_empty()) {
+                Some(tz) => ReportTimeZone::parse(tz.trim_start_matches(':')),
+                None => load_zone("local", PathBuf::from(LOCALTIME_PATH)),
+            },
+            _ if name.starts_with(['+', '-']) => parse_offset(name)
+                .map(ReportTimeZone::Offset)
+                .ok_or_else(|| format!("invalid time zone offset '{}' (use +HH:MM or -HH:MM)", name)),
+            _ => {
+                // Zone names are relative paths below the zone directory, never outside it
+                if name.is_empty() || name.starts_with('/') || name.split('/').any(|part| part == "..") {
+                    return Err(format!("unknown time zone '{}'", name));
+                }
+                let zone_dir = env::var_os("TZDIR").map_or_else(|| PathBuf::from(DEFAULT_ZONE_DIR), PathBuf::from);
+                load_zone(name, zone_dir.join(name))
+            },
+        }
+    }
+
+    /// Formats a Unix timestamp as local time in this zone, e.g.
+    /// `2026-10-16 14:03:12 CEST (UTC+02:00)`.
+    pub fn format(&self, unix_seconds: u64) -> String {
+        let (offset, abbreviation) = match self {
+            ReportTimeZone::Utc => return format!("{} UTC", date_time(unix_seconds as i64)),
+            ReportTimeZone::Offset(offset) => (*offset, None),
+            ReportTimeZone::Zone { transitions, types, rule, .. } => {
+                let started = transitions.partition_point(|&(at, _)| at <= unix_seconds as i64);
+                let local = match rule {
+                    Some(rule) if started == transitions.len() => rule.local_time_type(unix_seconds as i64),
+                    _ => started.checked_sub(1).map_or(&types[0], |index| &types[transitions[index].1]),
+                };
+                (local.offset_seconds, Some(local.abbreviation.as_str()))
+            },
+        };
+        let sign = if offset < 0 { '-' } else { '+' };
+        let offset_text = format!("UTC{}{:02}:{:02}", sign, offset.abs() / 3600, offset.abs() % 3600 / 60);
+        let local_time = date_time(unix_seconds as i64 + offset);
+        match abbreviation {
+            Some(abbreviation) if !abbreviation.is_empty() => format!("{} {} ({})", local_time, abbreviation, offset_text),
+            _ => format!("{} {}", local_time, offset_text),
+        }
+    }
+}
+
+impl PosixRule {
+    /// Parses a POSIX `TZ` rule as written in a TZif footer (RFC 8536, section 3.3), such
+    /// as `EST5EDT,M3.2.0,M11.1.0` or `<+0330>-3:30`. Offsets in the rule count west of
+    /// UTC; they are stored east of UTC like every other offset here.
+    pub fn parse(text: &str) -> Option<PosixRule> {
+        let mut parser = RuleParser { bytes: text.as_bytes(), position: 0 };
+        let standard_name = parser.name()?;
+        let standard_offset = -parser.time()?;
+        let standard = LocalTimeType { offset_seconds: standard_offset, abbreviation: standard_name };
+        if parser.at_end() {
+            return Some(PosixRule { standard, daylight: None });
+        }
+
+        let daylight_name = parser.name()?;
+        let daylight_offset = match parser.peek() {
+            Some(b'+' | b'-' | b'0'..=b'9') => -parser.time()?,
+            _ => standard_offset + 3600,
+        };
+        // Without dates, POSIX leaves the schedule to the implementation; the US one is usual
+        let (start, end) = if parser.at_end() {
+            (RuleTransition { day: RuleDay::MonthWeekDay { month: 3, week: 2, weekday: 0 }, time: 7200 },
+             RuleTransition { day: RuleDay::MonthWeekDay { month: 11, week: 1, weekday: 0 }, time: 7200 })
+        } else {
+            parser.expect(b',')?;
+            let start = parser.transition()?;
+            parser.expect(b',')?;
+            (start, parser.transition()?)
+        };
+        if !parser.at_end() {
+            return None;
+        }
+        let time_type = LocalTimeType { offset_seconds: daylight_offset, abbreviation: daylight_name };
+        Some(PosixRule { standard, daylight: Some(DaylightSaving { time_type, start, end }) })
+    }
+
+    /// The local time type in effect at a Unix timestamp.
+    pub fn local_time_type(&self, unix_seconds: i64) -> &LocalTimeType {
+        let Some(daylight) = &self.daylight else {
+            return &self.standard;
+        };
+        // The transitions of the year that is under way in standard time
+        let (year, _, _) = civil_date((unix_seconds + self.standard.offset_seconds).div_euclid(86_400));
+        let start = daylight.start.unix_seconds(year) - self.standard.offset_seconds;
+        let end = daylight.end.unix_seconds(year) - daylight.time_type.offset_seconds;
+        let in_daylight = if start < end {
+            (start..end).contains(&unix_seconds)
+        } else {
+            // Southern hemisphere: daylight saving time spans the new year
+            !(end..start).contains(&unix_seconds)
+        };
+        if in_daylight { &daylight.time_type } else { &self.standard }
+    }
+}
+
+impl RuleTransition {
+    /// Seconds from the epoch to the transition in `year`, read as if local time were UTC.
+    fn unix_seconds(&self, year: i64) -> i64 {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let day = match self.day {
+            RuleDay::Julian(day) => days_from_civil(year, 1, 1) + day - 1 + i64::from(leap && day >= 60),
+            RuleDay::ZeroBased(day) => days_from_civil(year, 1, 1) + day,
+            RuleDay::MonthWeekDay { month, week, weekday } => {
+                let first = days_from_civil(year, month, 1);
+                // 1970-01-01 was a Thursday
+                let first_weekday = (first + 4).rem_euclid(7);
+                let mut day = first + (weekday - first_weekday).rem_euclid(7) + (week - 1) * 7;
+                let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+                while day >= days_from_civil(next_year, next_month, 1) {
+                    day -= 7;
+                }
+                day
+            },
+        };
+        day * 86_400 + self.time
+    }
+}
+
+/// Reads the parts of a POSIX `TZ` rule
+struct RuleParser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl RuleParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn at_end(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.peek() == Some(byte)).then(|| self.position += 1)
+    }
+
+    /// A zone abbreviation: letters, or any text within `<` and `>`.
+    fn name(&mut self) -> Option<String> {
+        let (start, end) = if self.expect(b'<').is_some() {
+            let length = self.bytes[self.position..].iter().position(|&byte| byte == b'>')?;
+            self.position += length + 1;
+            (self.position - length - 1, self.position - 1)
+        } else {
+            let length = self.bytes[self.position..].iter().take_while(|byte| byte.is_ascii_alphabetic()).count();
+            self.position += length;
+            (self.position - length, self.position)
+        };
+        (end - start >= 3).then(|| String::from_utf8_lossy(&self.bytes[start..end]).into_owned())
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in seconds, with hours up to 167 as RFC 8536 allows.
+    fn time(&mut self) -> Option<i64> {
+        let sign = if self.peek() == Some(b'-') { -1 } else { 1 };
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        let mut seconds = self.number().filter(|hours| *hours <= 167)? * 3600;
+        for scale in [60, 1] {
+            if self.expect(b':').is_none() {
+                break;
+            }
+            seconds += self.number().filter(|value| *value <= 59)? * scale;
+        }
+        Some(sign * seconds)
+    }
+
+    /// `Jn`, `n`, or `Mm.w.d`, then an optional `/time` (02:00 by default).
+    fn transition(&mut self) -> Option<RuleTransition> {
+        let day = match self.peek()? {
+            b'J' => {
+                self.position += 1;
+                RuleDay::Julian(self.number().filter(|day| (1..=365).contains(day))?)
+            },
+            b'M' => {
+                self.position += 1;
+                let month = self.number().filter(|month| (1..=12).contains(month))?;
+                self.expect(b'.')?;
+                let week = self.number().filter(|week| (1..=5).contains(week))?;
+                self.expect(b'.')?;
+                let weekday = self.number().filter(|weekday| (0..=6).contains(weekday))?;
+                RuleDay::MonthWeekDay { month, week, weekday }
+            },
+            _ => RuleDay::ZeroBased(self.number().filter(|day| (0..=365).contains(day))?),
+        };
+        let time = if self.expect(b'/').is_some() { self.time()? } else { 7200 };
+        Some(RuleTransition { day, time })
+    }
+
+    fn number(&mut self) -> Option<i64> {
+        let length = self.bytes[self.position..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if length == 0 || length > 3 {
+            return None;
+        }
+        let text = std::str::from_utf8(&self.bytes[self.position..self.position + length]).ok()?;
+        self.position += length;
+        text.parse().ok()
+    }
+}
+
+/// Parses `+HH:MM`, `-HH:MM`, `+HHMM`, or `+HH` into seconds east of UTC.
+fn parse_offset(text: &str) -> Option<i64> {
+    let sign = if text.starts_with('-') { -1 } else { 1 };
+    let digits = text[1..].replace(':', "");
+    if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i64>().ok()?, 0),
+        4 => (digits[..2].parse::<i64>().ok()?, digits[2..].parse::<i64>().ok()?),
+        _ => return None,
+    };
+    (hours <= 14 && minutes < 60).then_some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Transitions (time, index of the local time type that starts) and local time types of a zone
+type ZoneRules = (Vec<(i64, usize)>, Vec<LocalTimeType>);
+/// Transitions and local time types of a zone, and the rule for times after them
+type Zone = (Vec<(i64, usize)>, Vec<LocalTimeType>, Option<PosixRule>);
+
+/// Reads a zone from a TZif file (RFC 8536).
+fn load_zone(name: &str, path: PathBuf) -> Result<ReportTimeZone, String> {
+    let bytes = fs::read(&path).map_err(|e| format!("unknown time zone '{}' ({}: {})", name, path.display(), e))?;
+    let (transitions, types, rule) = parse_tzif(&bytes)
+        .ok_or_else(|| format!("unknown time zone '{}' ({} is not a zone file)", name, path.display()))?;
+    Ok(ReportTimeZone::Zone { name: name.to_string(), transitions, types, rule: rule.map(Box::new) })
+}
+
+/// Parses the transitions and local time types of a TZif file, from its 64-bit data block
+/// when the file has one, and the POSIX `TZ` rule of its footer (version 2 and later).
+fn parse_tzif(bytes: &[u8]) -> Option<Zone> {
+    let ((transitions, types), v1_length) = parse_tzif_block(bytes, 4)?;
+    if bytes[4] < b'2' {
+        return Some((transitions, types, None));
+    }
+    let ((transitions, types), v2_length) = parse_tzif_block(&bytes[v1_length..], 8)?;
+    // The footer is the rule between two newlines; it is empty when no rule applies
+    let footer = bytes.get(v1_length + v2_length..)?.strip_prefix(b"\n")?;
+    let rule_text = &footer[..footer.iter().position(|&byte| byte == b'\n')?];
+    let rule = std::str::from_utf8(rule_text).ok().and_then(PosixRule::parse);
+    Some((transitions, types, rule))
+}
+
+/// Parses one header and data block, with `time_size`-byte transition times.
+///
+/// # Returns
+///
+/// * `Option<(ZoneRules, usize)>` - The block and its length in bytes, or None for a
+///   malformed block
+fn parse_tzif_block(bytes: &[u8], time_size: usize) -> Option<(ZoneRules, usize)> {
+    if bytes.get(..4)? != b"TZif" {
+        return None;
+    }
+    let count = |index: usize| -> Option<usize> {
+        let field = bytes.get(20 + 4 * index..24 + 4 * index)?;
+        Some(u32::from_be_bytes(field.try_into().ok()?) as usize)
+    };
+    let (utc_count, std_count, leap_count) = (count(0)?, count(1)?, count(2)?);
+    let (time_count, type_count, char_count) = (count(3)?, count(4)?, count(5)?);
+    if type_count == 0 {
+        return None;
+    }
+
+    let times_start = 44;
+    let indices_start = times_start + time_count * time_size;
+    let types_start = indices_start + time_count;
+    let chars_start = types_start + type_count * 6;
+    let block_length = chars_start + char_count + leap_count * (time_size + 4) + std_count + utc_count;
+    if bytes.len() < block_length {
+        return None;
+    }
+
+    let abbreviations = &bytes[chars_start..chars_start + char_count];
+    let types: Vec<LocalTimeType> = (0..type_count)
+        .map(|index| {
+            let entry = &bytes[types_start + index * 6..types_start + index * 6 + 6];
+            let offset_seconds = i32::from_be_bytes([entry[0], entry[1], entry[2], entry[3]]) as i64;
+            let start = (entry[5] as usize).min(abbreviations.len());
+            let end = abbreviations[start..].iter().position(|&byte| byte == 0).map_or(abbreviations.len(), |length| start + length);
+            LocalTimeType { offset_seconds, abbreviation: String::from_utf8_lossy(&abbreviations[start..end]).into_owned() }
+        })
+        .collect();
+    let transitions = (0..time_count)
+        .map(|index| {
+            let time = &bytes[times_start + index * time_size..times_start + (index + 1) * time_size];
+            let at = match time_size {
+                4 => i32::from_be_bytes(time.try_into().ok()?) as i64,
+                _ => i64::from_be_bytes(time.try_into().ok()?),
+            };
+            let type_index = bytes[indices_start + index] as usize;
+            (type_index < type_count).then_some((at, type_index))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(((transitions, types), block_length))
+}
+
+/// Formats a Unix timestamp as a UTC calendar date (`YYYY-MM-DD`).
+pub fn utc_date(unix_seconds: u64) -> String {
+    let (year, month, day) = civil_date((unix_seconds / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Formats seconds since the epoch (already shifted to local time) as
+/// `YYYY-MM-DD HH:MM:SS`.
+fn date_time(seconds: i64) -> String {
+    let (year, month, day) = civil_date(seconds.div_euclid(86_400));
+    let time_of_day = seconds.rem_euclid(86_400);
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            year, month, day, time_of_day / 3600, time_of_day % 3600 / 60, time_of_day % 60)
+}
+
+/// Day number since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Years start in March, so the leap day ends the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Year, month, and day of a day number since 1970-01-01.
+fn civil_date(days_since_epoch: i64) -> (i64, i64, i64) {
+    // Civil-from-days conversion for the proleptic Gregorian calendar
+    let days = days_since_epoch + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
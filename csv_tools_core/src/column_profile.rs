@@ -0,0 +1,1018 @@
+//! # Column Profiling
+//!
+//! Splits each row into fields and gathers per-column statistics, so the reports can
+//! say something about individual columns and not only about whole rows.
+//!
+//! The first check is a formatting-preservation audit: columns whose values look like
+//! numbers but would be damaged by numeric loading (Excel, pandas, parquet writers).
+//!
+//! * Leading zeros (`02134`) - ZIP codes, account numbers, and other identifiers
+//! * Fixed-width digit codes (`4411`, `0093`, ...) - every value has the same digit count
+//! * Long digit strings - more than 15 digits cannot be stored exactly as a double
+//! * Trailing zeros in decimals (`1.50`) - the written scale is lost as a float
+//! * Mixed decimal separators - some values use `.` as the decimal point (`1.5`,
+//!   `1,234.5`) and others `,` (`1,5`, `1.234,5`), so no single locale reads them all
+//!
+//! Values with one separator followed by exactly three digits (`1,234`, `1.234`) can be
+//! a decimal or a grouped integer; they are counted as ambiguous and never conflict.
+//!
+//! Each value is also classified (integer, number, boolean, ISO date or datetime) with
+//! its observed range, so a column type can be inferred for an inferred Table Schema.
+//! Numeric values are also summed for `--column-totals`.
+//!
+//! The shortest and longest non-empty value of each column is kept, cut to its first
+//! [`EXTREME_VALUE_CHARS`] characters, since a one-letter code next to a 90 KB JSON blob
+//! explains a length distribution at a glance; `--redact-examples` masks them in the
+//! reports like the sample rows (see the `row_samples` module).
+//!
+//! Text values are checked for mojibake and smart quotes (see the `mojibake` module),
+//! the marks of text that went through a wrong encoding before it reached the file.
+//!
+//! Rows are parsed as logical records: a quoted field may contain line breaks, so one
+//! record can span several physical lines. Values with embedded newlines or carriage
+//! returns are counted per column, since they break line-oriented downstream tools.
+//!
+//! Cross-column rules (see the `column_rules` module) are evaluated on the same records,
+//! and so is the scoring against a row-length model (see the `length_model` module).
+//! Values equal to a configured null token (see the `null_tokens` module) are profiled
+//! and checked as empty values. With `--null-report`, the empty, whitespace-only, and
+//! null-token cells are also counted as written (see the `null_report` module).
+//!
+//! Columns whose name matches `--exclude-columns-regex` are left out of every field-level
+//! analysis: they are not profiled, typed, or totaled, do not appear in the column
+//! reports or the table schema, and count as missing for column rules. Columns are
+//! still split at their delimiters, so the remaining columns keep their file positions.
+//!
+//! Wide files (genomics exports with 200k columns) keep one profile per column, so the
+//! profiler refuses records with more than `--max-columns` fields ([`DEFAULT_MAX_COLUMNS`]
+//! by default). `--sample-columns <n>` profiles n evenly spaced columns instead (every
+//! k-th column, k fixed by the header's width): only those get a profile, while column
+//! rules, the model scores, and the column sizes still see every field.
+
+use std::collections::HashMap;
+
+use crate::column_rules::{ColumnRuleChecker, ColumnRuleResult};
+use crate::column_sizes::ColumnSizes;
+use crate::column_totals::DecimalSum;
+use crate::count_mode::count_chars;
+use crate::fields::{ends_in_quotes, split_fields, split_fields_into, MAX_RECORD_LINES};
+use crate::length_model::{ModelScorer, ModelScores};
+use crate::mojibake::{encoding_suspects, EncodingSuspect};
+use crate::name_pattern::NamePattern;
+use crate::null_report::NullTally;
+use crate::null_tokens::NullTokens;
+use crate::parquet_sidecar::{RecordShape, RecordShapes};
+use crate::row_samples::{escape_controls, redact};
+use crate::value_shapes::ShapeTally;
+
+/// Doubles hold 15 significant decimal digits exactly; longer digit strings get rounded
+const MAX_EXACT_FLOAT_DIGITS: usize = 15;
+/// Number of example row indices kept per column for embedded line breaks
+const MAX_EXAMPLE_ROWS: usize = 5;
+/// Widest record profiled unless `--max-columns` or `--sample-columns` is set
+pub const DEFAULT_MAX_COLUMNS: usize = 10_000;
+/// Characters kept of the shortest and longest value of a column
+pub const EXTREME_VALUE_CHARS: usize = 60;
+
+/// Statistics for one column
+#[derive(Debug, Clone, Default)]
+pub struct ColumnProfile {
+    /// Rows that had a value in this column (including empty values)
+    pub values: u64,
+    /// Values that are not empty
+    pub non_empty_values: u64,
+    /// Values made only of ASCII digits
+    pub digit_only_values: u64,
+    /// Digit-only values with more than one digit that start with `0`
+    pub leading_zero_values: u64,
+    /// Digit-only values longer than a double can hold exactly
+    pub long_digit_values: u64,
+    /// Plain decimals (`12.50`) whose fractional part ends in `0`
+    pub trailing_zero_decimals: u64,
+    /// Shortest and longest digit-only value, in digits
+    pub digit_width_range: Option<(usize, usize)>,
+    /// First value that triggered a formatting finding
+    pub format_example: Option<String>,
+    /// First digit-only value, shown for code columns without other findings
+    digit_example: Option<String>,
+    /// Values containing a line feed (`\n`)
+    pub newline_values: u64,
+    /// Values containing a carriage return (`\r`)
+    pub carriage_return_values: u64,
+    /// Row indices (where the record starts) of the first values with a line break
+    pub line_break_example_rows: Vec<usize>,
+    /// Non-empty values that are integers (`-12`, `0042`)
+    pub integer_values: u64,
+    /// Non-empty values that are numbers, integers included (`3.5`, `-1e6`)
+    pub number_values: u64,
+    /// Non-empty values that are `true` or `false` (`True`, `TRUE`, ... as well)
+    pub boolean_values: u64,
+    /// Non-empty values that are ISO dates (`2024-03-01`)
+    pub date_values: u64,
+    /// Non-empty values that are ISO datetimes (`2024-03-01T12:30:00Z`)
+    pub datetime_values: u64,
+    /// Smallest and largest numeric value
+    pub numeric_range: Option<(f64, f64)>,
+    /// Sum of the numeric values (see the `column_totals` module)
+    pub sum: DecimalSum,
+    /// Earliest and latest date or datetime value, as written
+    pub temporal_range: Option<(String, String)>,
+    /// Shortest and longest non-empty value, in characters
+    pub length_range: Option<(usize, usize)>,
+    /// UTF-8 bytes of the widest non-empty value
+    pub max_bytes: usize,
+    /// Frequency of each non-empty value length, in characters
+    pub length_counts: HashMap<usize, u64>,
+    /// First non-empty value of the shortest length
+    pub shortest_value: Option<ExtremeValue>,
+    /// First non-empty value of the longest length
+    pub longest_value: Option<ExtremeValue>,
+    /// Values written with a decimal or thousands separator, per style, in order of first use
+    pub separator_styles: Vec<SeparatorStyleCount>,
+    /// Values showing signs of a wrong upstream encoding, per sign, in order of first use
+    pub encoding_suspects: Vec<EncodingSuspectCount>,
+    /// URL, email, UUID, and JSON conformity of the non-empty values, when checked
+    /// (see the `value_shapes` module)
+    pub value_shapes: ShapeTally,
+    /// Empty, whitespace-only, and null-token cells, when counted (see the `null_report` module)
+    pub nulls: NullTally,
+}
+
+/// The shortest or longest value of a column, cut to its first characters
+#[derive(Debug, Clone)]
+pub struct ExtremeValue {
+    pub row_index: usize,
+    /// Length of the whole value, in characters
+    pub length: usize,
+    /// First [`EXTREME_VALUE_CHARS`] characters of the value
+    pub start: String,
+}
+
+impl ExtremeValue {
+    fn new(row_index: usize, value: &str, length: usize) -> Self {
+        ExtremeValue { row_index, length, start: value.chars().take(EXTREME_VALUE_CHARS).collect() }
+    }
+
+    /// Returns the value as shown in the reports, masked when `redacted`, with the number
+    /// of characters cut off.
+    pub fn display(&self, redacted: bool) -> String {
+        let text = if redacted { redact(&self.start) } else { escape_controls(&self.start) };
+        if self.length > EXTREME_VALUE_CHARS {
+            format!("{} … (+{} chars)", text, self.length - EXTREME_VALUE_CHARS)
+        } else {
+            text
+        }
+    }
+}
+
+/// How a number with separators is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeparatorStyle {
+    /// `.` decimal point, no grouping (`1.5`)
+    DotDecimal,
+    /// `,` decimal point, no grouping (`1,5`)
+    CommaDecimal,
+    /// `,` thousands separator, optional `.` decimal point (`1,234.5`, `1,234,567`)
+    CommaGrouped,
+    /// `.` thousands separator, optional `,` decimal point (`1.234,5`, `1.234.567`)
+    DotGrouped,
+    /// One separator followed by three digits (`1,234`, `1.234`): either reading fits
+    Ambiguous,
+}
+
+impl SeparatorStyle {
+    /// Human-readable description used in the markdown and text reports.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            SeparatorStyle::DotDecimal => "'.' decimal",
+            SeparatorStyle::CommaDecimal => "',' decimal",
+            SeparatorStyle::CommaGrouped => "',' thousands with '.' decimal",
+            SeparatorStyle::DotGrouped => "'.' thousands with ',' decimal",
+            SeparatorStyle::Ambiguous => "ambiguous ('.' or ',' with three digits)",
+        }
+    }
+
+    /// The decimal separator the style implies, or None for ambiguous values.
+    pub fn decimal_separator(&self) -> Option<char> {
+        match self {
+            SeparatorStyle::DotDecimal | SeparatorStyle::CommaGrouped => Some('.'),
+            SeparatorStyle::CommaDecimal | SeparatorStyle::DotGrouped => Some(','),
+            SeparatorStyle::Ambiguous => None,
+        }
+    }
+}
+
+/// Values of one separator style in a column, with the first few examples
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeparatorStyleCount {
+    pub style: SeparatorStyle,
+    pub values: u64,
+    /// Row indices (where the record starts) and values of the first examples
+    pub examples: Vec<(usize, String)>,
+}
+
+/// Values of one encoding sign in a column, with the first few examples
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodingSuspectCount {
+    pub suspect: EncodingSuspect,
+    pub values: u64,
+    /// Row indices (where the record starts) and values of the first examples
+    pub examples: Vec<(usize, String)>,
+}
+
+/// A reason a column should not be loaded with numeric type inference
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormatFinding {
+    /// Some values have leading zeros that numeric loading strips
+    LeadingZeros,
+    /// All values are digit codes of the same width
+    FixedWidthCode(usize),
+    /// Some values have more digits than a double holds exactly
+    LongDigitStrings,
+    /// Some decimals carry trailing zeros that numeric loading drops
+    TrailingZeros,
+    /// Some values use `.` as the decimal separator and others `,`
+    MixedDecimalSeparators,
+}
+
+impl FormatFinding {
+    /// Short machine-readable name used in CSV reports.
+    pub fn code(&self) -> &'static str {
+        match self {
+            FormatFinding::LeadingZeros => "leading_zeros",
+            FormatFinding::FixedWidthCode(_) => "fixed_width_code",
+            FormatFinding::LongDigitStrings => "long_digit_strings",
+            FormatFinding::TrailingZeros => "trailing_zeros",
+            FormatFinding::MixedDecimalSeparators => "mixed_decimal_separators",
+        }
+    }
+
+    /// Human-readable description used in the markdown and text reports.
+    pub fn describe(&self) -> String {
+        match self {
+            FormatFinding::LeadingZeros => "values with leading zeros".to_string(),
+            FormatFinding::FixedWidthCode(width) => format!("fixed-width {}-digit codes", width),
+            FormatFinding::LongDigitStrings => format!("digit strings longer than {} digits", MAX_EXACT_FLOAT_DIGITS),
+            FormatFinding::TrailingZeros => "decimals with trailing zeros".to_string(),
+            FormatFinding::MixedDecimalSeparators => "mixed decimal separators ('.' and ',')".to_string(),
+        }
+    }
+
+    /// Whether numeric loading would change the values (rather than only their written form).
+    pub fn requires_text(&self) -> bool {
+        matches!(self, FormatFinding::LeadingZeros | FormatFinding::LongDigitStrings | FormatFinding::MixedDecimalSeparators)
+    }
+}
+
+impl ColumnProfile {
+    /// Adds one value of this column to the profile.
+    fn observe(&mut self, row_index: usize, value: &str) {
+        self.values += 1;
+        
+        let has_newline = value.contains('\n');
+        let has_carriage_return = value.contains('\r');
+        if has_newline {
+            self.newline_values += 1;
+        }
+        if has_carriage_return {
+            self.carriage_return_values += 1;
+        }
+        if (has_newline || has_carriage_return) && self.line_break_example_rows.len() < MAX_EXAMPLE_ROWS {
+            self.line_break_example_rows.push(row_index);
+        }
+        
+        let value = value.trim();
+        if value.is_empty() {
+            return;
+        }
+        self.non_empty_values += 1;
+        self.classify_type(row_index, value);
+
+        for suspect in encoding_suspects(value) {
+            match self.encoding_suspects.iter_mut().find(|count| count.suspect == suspect) {
+                Some(count) => {
+                    count.values += 1;
+                    if count.examples.len() < MAX_EXAMPLE_ROWS {
+                        count.examples.push((row_index, value.to_string()));
+                    }
+                },
+                None => self.encoding_suspects.push(EncodingSuspectCount {
+                    suspect,
+                    values: 1,
+                    examples: vec![(row_index, value.to_string())],
+                }),
+            }
+        }
+
+        if value.bytes().all(|b| b.is_ascii_digit()) {
+            let width = value.len();
+            self.digit_only_values += 1;
+            if self.digit_example.is_none() {
+                self.digit_example = Some(value.to_string());
+            }
+            self.digit_width_range = Some(match self.digit_width_range {
+                Some((min, max)) => (min.min(width), max.max(width)),
+                None => (width, width),
+            });
+            if width > 1 && value.starts_with('0') {
+                self.leading_zero_values += 1;
+                self.remember_example(value);
+            }
+            if width > MAX_EXACT_FLOAT_DIGITS {
+                self.long_digit_values += 1;
+                self.remember_example(value);
+            }
+        } else if let Some((whole, fraction)) = value.trim_start_matches(['-', '+']).split_once('.') {
+            let is_decimal = !fraction.is_empty()
+                && whole.bytes().all(|b| b.is_ascii_digit())
+                && fraction.bytes().all(|b| b.is_ascii_digit());
+            if is_decimal && fraction.ends_with('0') {
+                self.trailing_zero_decimals += 1;
+                self.remember_example(value);
+            }
+        }
+
+        if let Some(style) = separator_style(value) {
+            match self.separator_styles.iter_mut().find(|count| count.style == style) {
+                Some(count) => {
+                    count.values += 1;
+                    if count.examples.len() < MAX_EXAMPLE_ROWS {
+                        count.examples.push((row_index, value.to_string()));
+                    }
+                },
+                None => self.separator_styles.push(SeparatorStyleCount {
+                    style,
+                    values: 1,
+                    examples: vec![(row_index, value.to_string())],
+                }),
+            }
+        }
+    }
+
+    /// Counts the types a non-empty value can be read as and widens the observed ranges.
+    fn classify_type(&mut self, row_index: usize, value: &str) {
+        let length = count_chars(value);
+        self.length_range = Some(match self.length_range {
+            Some((min, max)) => (min.min(length), max.max(length)),
+            None => (length, length),
+        });
+        if self.shortest_value.as_ref().is_none_or(|shortest| length < shortest.length) {
+            self.shortest_value = Some(ExtremeValue::new(row_index, value, length));
+        }
+        if self.longest_value.as_ref().is_none_or(|longest| length > longest.length) {
+            self.longest_value = Some(ExtremeValue::new(row_index, value, length));
+        }
+        *self.length_counts.entry(length).or_insert(0) += 1;
+        self.max_bytes = self.max_bytes.max(value.len());
+
+        if is_number(value) && let Ok(number) = value.parse::<f64>() {
+            self.number_values += 1;
+            let digits = value.trim_start_matches(['-', '+']);
+            if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                self.integer_values += 1;
+            }
+            self.numeric_range = Some(match self.numeric_range {
+                Some((min, max)) => (min.min(number), max.max(number)),
+                None => (number, number),
+            });
+            self.sum.add(value, number);
+        } else if matches!(value, "true" | "True" | "TRUE" | "false" | "False" | "FALSE") {
+            self.boolean_values += 1;
+        } else if let Some(has_time) = iso_temporal(value) {
+            if has_time {
+                self.datetime_values += 1;
+            } else {
+                self.date_values += 1;
+            }
+            // ISO dates and datetimes in one format sort chronologically as text
+            match &mut self.temporal_range {
+                Some((min, max)) => {
+                    if value < min.as_str() {
+                        *min = value.to_string();
+                    } else if value > max.as_str() {
+                        *max = value.to_string();
+                    }
+                },
+                None => self.temporal_range = Some((value.to_string(), value.to_string())),
+            }
+        }
+    }
+
+    /// Returns the Table Schema type of the column.
+    ///
+    /// Columns that must be loaded as text to keep their values (leading zeros, long
+    /// digit strings) are strings even though every value is numeric.
+    pub fn inferred_type(&self) -> &'static str {
+        let non_empty = self.non_empty_values;
+        if non_empty == 0 {
+            "any"
+        } else if self.recommended_load_type() == Some("text") {
+            "string"
+        } else if self.integer_values == non_empty {
+            "integer"
+        } else if self.number_values == non_empty {
+            "number"
+        } else if self.boolean_values == non_empty {
+            "boolean"
+        } else if self.date_values == non_empty {
+            "date"
+        } else if self.datetime_values == non_empty {
+            "datetime"
+        } else {
+            "string"
+        }
+    }
+
+    fn remember_example(&mut self, value: &str) {
+        if self.format_example.is_none() {
+            self.format_example = Some(value.to_string());
+        }
+    }
+
+    /// Returns a value illustrating the findings.
+    pub fn example(&self) -> &str {
+        self.format_example.as_deref()
+            .or_else(|| self.conflicting_separator_example())
+            .or(self.digit_example.as_deref())
+            .unwrap_or("")
+    }
+
+    /// First value whose decimal separator differs from the one the column started with.
+    fn conflicting_separator_example(&self) -> Option<&str> {
+        let first = self.separator_styles.iter().find_map(|count| count.style.decimal_separator())?;
+        self.separator_styles.iter()
+            .find(|count| count.style.decimal_separator().is_some_and(|separator| separator != first))
+            .and_then(|count| count.examples.first())
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the column type a loader should use, or None when numeric inference is safe.
+    ///
+    /// Fixed-width codes without leading zeros survive numeric loading, so they only get
+    /// a suggestion; leading zeros and long digit strings do not, so they require text.
+    pub fn recommended_load_type(&self) -> Option<&'static str> {
+        let findings = self.format_findings();
+        if findings.iter().any(|f| f.requires_text()) {
+            Some("text")
+        } else if findings.iter().any(|f| matches!(f, FormatFinding::FixedWidthCode(_))) {
+            Some("text or integer")
+        } else if findings.contains(&FormatFinding::TrailingZeros) {
+            Some("decimal")
+        } else {
+            None
+        }
+    }
+
+    /// Whether values disagree on the decimal separator (ambiguous values never do).
+    pub fn has_mixed_decimal_separators(&self) -> bool {
+        let uses = |separator| self.separator_styles.iter()
+            .any(|count| count.style.decimal_separator() == Some(separator));
+        uses('.') && uses(',')
+    }
+
+    /// The values that look double-encoded, if any.
+    pub fn mojibake(&self) -> Option<&EncodingSuspectCount> {
+        self.encoding_suspects.iter().find(|count| count.suspect == EncodingSuspect::Mojibake)
+    }
+
+    /// Whether any value contains a line feed or carriage return.
+    pub fn has_line_breaks(&self) -> bool {
+        self.newline_values > 0 || self.carriage_return_values > 0
+    }
+
+    /// Returns the formatting findings for this column, most severe first.
+    pub fn format_findings(&self) -> Vec<FormatFinding> {
+        let mut findings = Vec::new();
+        if self.leading_zero_values > 0 {
+            findings.push(FormatFinding::LeadingZeros);
+        }
+        if self.long_digit_values > 0 {
+            findings.push(FormatFinding::LongDigitStrings);
+        }
+        // Only a column made entirely of same-width digit strings counts as a code column
+        if let Some((min, max)) = self.digit_width_range
+            && min == max
+            && min > 1
+            && self.non_empty_values > 1
+            && self.digit_only_values == self.non_empty_values
+        {
+            findings.push(FormatFinding::FixedWidthCode(min));
+        }
+        if self.trailing_zero_decimals > 0 {
+            findings.push(FormatFinding::TrailingZeros);
+        }
+        if self.has_mixed_decimal_separators() {
+            findings.push(FormatFinding::MixedDecimalSeparators);
+        }
+        findings
+    }
+}
+
+/// Whether a value is a plain decimal number (`-12`, `3.50`, `1e6`), without words like `inf`.
+fn is_number(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let mantissa = unsigned.split(['e', 'E']).next().unwrap_or("");
+    mantissa.bytes().any(|b| b.is_ascii_digit())
+        && unsigned.bytes().all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'-' | b'+'))
+}
+
+/// Classifies a number written with `.` or `,` separators, or returns None for anything
+/// else (plain integers, exponents, dates, versions with uneven groups).
+///
+/// With both separators present the last one is the decimal separator and the other
+/// groups thousands; a separator used more than once can only group thousands.
+fn separator_style(value: &str) -> Option<SeparatorStyle> {
+    let unsigned = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let bytes = unsigned.as_bytes();
+    if !bytes.first().is_some_and(u8::is_ascii_digit)
+        || !bytes.last().is_some_and(u8::is_ascii_digit)
+        || !bytes.iter().all(|&b| b.is_ascii_digit() || matches!(b, b'.' | b','))
+    {
+        return None;
+    }
+    let dots = unsigned.matches('.').count();
+    let commas = unsigned.matches(',').count();
+    // Groups after the first must have three digits, the first one to three
+    let is_grouped = |integer: &str, separator: char| {
+        let mut groups = integer.split(separator);
+        groups.next().is_some_and(|first| (1..=3).contains(&first.len()))
+            && groups.all(|group| group.len() == 3)
+    };
+
+    match (dots, commas) {
+        (0, 0) => None,
+        (_, 0) | (0, _) => {
+            let separator = if dots > 0 { '.' } else { ',' };
+            let count = dots.max(commas);
+            let (whole, fraction) = unsigned.split_once(separator)?;
+            if count == 1 && fraction.len() == 3 && whole.len() <= 3 && !whole.starts_with('0') {
+                Some(SeparatorStyle::Ambiguous)
+            } else if count == 1 {
+                Some(if separator == '.' { SeparatorStyle::DotDecimal } else { SeparatorStyle::CommaDecimal })
+            } else if is_grouped(unsigned, separator) {
+                Some(if separator == '.' { SeparatorStyle::DotGrouped } else { SeparatorStyle::CommaGrouped })
+            } else {
+                None
+            }
+        },
+        _ => {
+            let decimal = if unsigned.rfind('.') > unsigned.rfind(',') { '.' } else { ',' };
+            let grouping = if decimal == '.' { ',' } else { '.' };
+            let (integer, _) = unsigned.rsplit_once(decimal)?;
+            if integer.contains(decimal) || !is_grouped(integer, grouping) {
+                return None;
+            }
+            Some(if decimal == '.' { SeparatorStyle::CommaGrouped } else { SeparatorStyle::DotGrouped })
+        },
+    }
+}
+
+/// Recognizes ISO dates (`YYYY-MM-DD`) and datetimes (`YYYY-MM-DDTHH:MM:SS[.fff]` with an
+/// optional `Z` or offset), returning whether a time part is present.
+fn iso_temporal(value: &str) -> Option<bool> {
+    let bytes = value.as_bytes();
+    let is_digits = |range: std::ops::Range<usize>| bytes.get(range).is_some_and(|part| part.iter().all(u8::is_ascii_digit));
+    if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' || !is_digits(0..4) || !is_digits(5..7) || !is_digits(8..10) {
+        return None;
+    }
+    if bytes.len() == 10 {
+        return Some(false);
+    }
+    let valid_time = bytes[10] == b'T'
+        && bytes.len() >= 19
+        && is_digits(11..13)
+        && bytes[13] == b':'
+        && is_digits(14..16)
+        && bytes[16] == b':'
+        && is_digits(17..19)
+        && bytes[19..].iter().all(|&b| b.is_ascii_digit() || matches!(b, b':' | b'.' | b'Z' | b'+' | b'-'));
+    valid_time.then_some(true)
+}
+
+/// Accumulates column profiles over the rows of a file
+#[derive(Debug, Clone)]
+pub struct ColumnProfiler {
+    delimiter: char,
+    column_names: Vec<String>,
+    columns: Vec<ColumnProfile>,
+    /// Record still inside a quoted field: (starting row index, text so far, physical lines)
+    pending_record: Option<(usize, String, usize)>,
+    /// Logical records completed so far (including the header)
+    records: u64,
+    /// Records that span more than one physical line
+    multiline_records: u64,
+    /// Data records profiled (the header is not counted)
+    data_records: u64,
+    /// Cross-column rules evaluated on each data record, when configured
+    rule_checker: Option<ColumnRuleChecker>,
+    /// Scores each data record against a row-length model (`--score-against`)
+    model_scorer: Option<ModelScorer>,
+    /// Length and field count of every data record (`--parquet-sidecar`)
+    record_shapes: Option<Vec<RecordShape>>,
+    /// Written size of every column, when requested
+    column_sizes: Option<ColumnSizes>,
+    /// Whether values are checked for URL, email, UUID, and JSON shapes
+    check_value_shapes: bool,
+    /// Whether the missing cells of every column are counted
+    count_nulls: bool,
+    /// Whether record lengths count the final terminator
+    shape_line_endings: bool,
+    /// Whether the record still open contains an unreadable line
+    pending_unreadable: bool,
+    /// Values treated as empty
+    null_tokens: NullTokens,
+    /// Null tokens of each header column, resolved from `null_tokens`
+    column_null_tokens: Vec<Vec<String>>,
+    /// Columns whose names match this pattern are not analyzed
+    exclude_pattern: Option<NamePattern>,
+    /// Whether each column seen so far is excluded, by index
+    excluded_columns: Vec<bool>,
+    /// Fields of the record being profiled, reused from record to record
+    field_buffer: Vec<String>,
+    /// Largest number of fields profiled, unless columns are sampled
+    max_columns: usize,
+    /// Fields of the widest record over `max_columns`, which was not profiled
+    too_many_columns: Option<usize>,
+    /// Number of evenly spaced columns profiled (`--sample-columns`)
+    column_sample: Option<usize>,
+    /// Distance between sampled columns, set by the first record seen
+    sample_stride: Option<usize>,
+}
+
+impl ColumnProfiler {
+    /// Creates an empty profiler for the given field delimiter.
+    pub fn new(delimiter: char) -> Self {
+        ColumnProfiler {
+            delimiter,
+            column_names: Vec::new(),
+            columns: Vec::new(),
+            pending_record: None,
+            records: 0,
+            multiline_records: 0,
+            data_records: 0,
+            rule_checker: None,
+            model_scorer: None,
+            record_shapes: None,
+            column_sizes: None,
+            check_value_shapes: false,
+            count_nulls: false,
+            shape_line_endings: false,
+            pending_unreadable: false,
+            null_tokens: NullTokens::default(),
+            column_null_tokens: Vec::new(),
+            exclude_pattern: None,
+            excluded_columns: Vec::new(),
+            field_buffer: Vec::new(),
+            max_columns: DEFAULT_MAX_COLUMNS,
+            too_many_columns: None,
+            column_sample: None,
+            sample_stride: None,
+        }
+    }
+
+    /// Refuses to profile records with more than `max_columns` fields.
+    pub fn with_max_columns(mut self, max_columns: usize) -> Self {
+        self.max_columns = max_columns;
+        self
+    }
+
+    /// Profiles only `count` evenly spaced columns, with no limit on the record width.
+    pub fn with_column_sample(mut self, count: usize) -> Self {
+        self.column_sample = Some(count);
+        self
+    }
+
+    /// Evaluates the given rules on every data record as it is profiled.
+    pub fn with_rules(mut self, rule_checker: ColumnRuleChecker) -> Self {
+        self.rule_checker = Some(rule_checker);
+        self
+    }
+
+    /// Scores every data record against a row-length model as it is profiled.
+    pub fn with_model_scorer(mut self, model_scorer: ModelScorer) -> Self {
+        self.model_scorer = Some(model_scorer);
+        self
+    }
+
+    /// Records the length and field count of every data record, in file order.
+    pub fn with_record_shapes(mut self, include_line_endings: bool) -> Self {
+        self.record_shapes = Some(Vec::new());
+        self.shape_line_endings = include_line_endings;
+        self
+    }
+
+    /// Profiles values equal to one of the column's null tokens as empty values.
+    pub fn with_null_tokens(mut self, null_tokens: NullTokens) -> Self {
+        self.null_tokens = null_tokens;
+        self
+    }
+
+    /// Leaves the columns whose names match `pattern` out of the analysis.
+    pub fn with_excluded_columns(mut self, pattern: NamePattern) -> Self {
+        self.exclude_pattern = Some(pattern);
+        self
+    }
+
+    /// Returns the outcome of each column rule (empty when no rules were configured).
+    pub fn take_rule_results(&mut self) -> Vec<ColumnRuleResult> {
+        self.rule_checker.take().map(ColumnRuleChecker::into_results).unwrap_or_default()
+    }
+
+    /// Returns the scores against the row-length model, when one was given.
+    pub fn take_model_scores(&mut self) -> Option<ModelScores> {
+        self.model_scorer.take().map(ModelScorer::into_scores)
+    }
+
+    /// Measures the written size of every column, excluded columns included.
+    pub fn with_column_sizes(mut self) -> Self {
+        self.column_sizes = Some(ColumnSizes::default());
+        self
+    }
+
+    /// Checks the non-empty values of every column for URL, email, UUID, and JSON shapes.
+    pub fn with_value_shapes(mut self) -> Self {
+        self.check_value_shapes = true;
+        self
+    }
+
+    /// Counts the empty, whitespace-only, and null-token cells of every column.
+    pub fn with_null_counts(mut self) -> Self {
+        self.count_nulls = true;
+        self
+    }
+
+    /// Returns the column sizes, when measuring was requested.
+    pub fn take_column_sizes(&mut self) -> Option<ColumnSizes> {
+        self.column_sizes.take()
+    }
+
+    /// Returns the recorded shape of every data record, when recording was requested.
+    pub fn take_record_shapes(&mut self) -> Option<RecordShapes> {
+        self.record_shapes.take().map(|records| RecordShapes { header_fields: self.column_names.len(), records })
+    }
+
+    /// Adds one physical line, completing a logical record once no quoted field is open.
+    ///
+    /// Row 0 is taken as the header. The remaining rows are profiled as data.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_index` - 0-based physical row index of the line
+    /// * `line` - Line content without its terminator
+    /// * `terminator` - The stripped terminator, restored inside multi-line values
+    pub fn observe_line(&mut self, row_index: usize, line: &str, terminator: &str) {
+        let (start_row, mut record, line_count) = match self.pending_record.take() {
+            Some((start_row, record, line_count)) => (start_row, record, line_count + 1),
+            None => (row_index, String::new(), 1),
+        };
+        let continues = ends_in_quotes(line, self.delimiter, line_count > 1);
+        record.push_str(line);
+
+        if continues && !terminator.is_empty() && line_count < MAX_RECORD_LINES {
+            record.push_str(terminator);
+            self.pending_record = Some((start_row, record, line_count));
+            return;
+        }
+
+        if line_count > 1 {
+            self.multiline_records += 1;
+        }
+        self.complete_record(start_row, &record, terminator, line_count);
+    }
+
+    /// Notes a line that is not valid UTF-8, so the recorded shapes keep one entry per
+    /// record: it becomes part of the open record, or an unreadable record of its own.
+    pub fn observe_unreadable_line(&mut self, row_index: usize) {
+        let Some(shapes) = self.record_shapes.as_mut() else {
+            return;
+        };
+        if self.pending_record.is_some() {
+            self.pending_unreadable = true;
+        } else if row_index > 0 {
+            shapes.push(RecordShape { row_index, length: 0, fields: 0, multiline: false, unreadable: true });
+        }
+    }
+
+    /// Completes a record left open at the end of the input (unterminated quote).
+    pub fn finish(&mut self) {
+        if let Some((start_row, record, line_count)) = self.pending_record.take() {
+            if line_count > 1 {
+                self.multiline_records += 1;
+            }
+            self.complete_record(start_row, &record, "", line_count);
+        }
+    }
+
+    fn complete_record(&mut self, start_row: usize, record: &str, terminator: &str, line_count: usize) {
+        self.records += 1;
+        if let Some(sizes) = self.column_sizes.as_mut() {
+            if start_row == 0 {
+                sizes.observe_header(record, terminator);
+            } else {
+                sizes.observe_record(record, self.delimiter, terminator);
+            }
+        }
+        if start_row == 0 {
+            self.set_header(record);
+        } else {
+            let fields = self.observe_row(start_row, record, terminator);
+            if let Some(shapes) = self.record_shapes.as_mut() {
+                let mut length = count_chars(record);
+                if self.shape_line_endings {
+                    length += terminator.len();
+                }
+                shapes.push(RecordShape {
+                    row_index: start_row,
+                    length,
+                    fields,
+                    multiline: line_count > 1,
+                    unreadable: self.pending_unreadable,
+                });
+            }
+        }
+        self.pending_unreadable = false;
+    }
+
+    /// Counts a record that was joined from several lines before it was observed (`--csv-mode`).
+    pub fn count_multiline_record(&mut self) {
+        self.multiline_records += 1;
+    }
+
+    /// Number of logical records seen and how many of them span several physical lines.
+    pub fn record_counts(&self) -> (u64, u64) {
+        (self.records, self.multiline_records)
+    }
+
+    /// Number of data records profiled (the header is not counted).
+    pub fn data_records(&self) -> u64 {
+        self.data_records
+    }
+
+    /// Records the column names from the header row.
+    pub fn set_header(&mut self, header_line: &str) {
+        // A UTF-8 byte order mark is not part of the first column's name
+        let header_line = header_line.strip_prefix('\u{feff}').unwrap_or(header_line);
+        self.column_names = split_fields(header_line, self.delimiter);
+        self.check_width(self.column_names.len());
+        self.column_null_tokens = self.column_names.iter()
+            .map(|name| self.null_tokens.for_column(name))
+            .collect();
+        self.excluded_columns.clear();
+        self.update_excluded_columns(self.column_names.len());
+        // Rules and the model cannot reference excluded columns
+        let visible_names: Vec<String> = self.column_names.iter()
+            .zip(&self.excluded_columns)
+            .map(|(name, &excluded)| if excluded { String::new() } else { name.clone() })
+            .collect();
+        if let Some(checker) = self.rule_checker.as_mut() {
+            checker.resolve_header(&visible_names);
+        }
+        if let Some(scorer) = self.model_scorer.as_mut() {
+            scorer.resolve_header(&visible_names);
+        }
+    }
+
+    /// Adds the fields of one data record to the column profiles, returning the field count.
+    fn observe_row(&mut self, row_index: usize, record: &str, terminator: &str) -> usize {
+        self.data_records += 1;
+        let mut fields = std::mem::take(&mut self.field_buffer);
+        split_fields_into(record, self.delimiter, &mut fields);
+        if !self.check_width(fields.len()) {
+            let width = fields.len();
+            self.field_buffer = fields;
+            return width;
+        }
+        if self.count_nulls {
+            // Null tokens are counted as written, before they are cleared
+            let sampled_fields = fields.iter().enumerate().step_by(self.sample_stride.unwrap_or(1));
+            for (slot, (index, value)) in sampled_fields.enumerate().take(self.column_sample.unwrap_or(usize::MAX)) {
+                if slot >= self.columns.len() {
+                    self.columns.resize_with(slot + 1, ColumnProfile::default);
+                }
+                let tokens = self.column_null_tokens.get(index).unwrap_or(&self.null_tokens.all_columns);
+                self.columns[slot].nulls.observe(value, tokens);
+            }
+        }
+        if !self.null_tokens.is_empty() {
+            for (index, value) in fields.iter_mut().enumerate() {
+                let tokens = self.column_null_tokens.get(index).unwrap_or(&self.null_tokens.all_columns);
+                if tokens.iter().any(|token| token == value.trim()) {
+                    value.clear();
+                }
+            }
+        }
+        if fields.len() > self.excluded_columns.len() {
+            self.update_excluded_columns(fields.len());
+        }
+        let stride = self.sample_stride.unwrap_or(1);
+        let profiled = fields.len().div_ceil(stride).min(self.column_sample.unwrap_or(usize::MAX));
+        if profiled > self.columns.len() {
+            self.columns.resize_with(profiled, ColumnProfile::default);
+        }
+        let sampled_fields = fields.iter().enumerate().step_by(stride);
+        for (column, (index, value)) in self.columns.iter_mut().zip(sampled_fields) {
+            if !self.excluded_columns[index] {
+                column.observe(row_index, value);
+                if self.check_value_shapes && !value.trim().is_empty() {
+                    column.value_shapes.observe(row_index, value.trim());
+                }
+            }
+        }
+        if let Some(checker) = self.rule_checker.as_mut() {
+            checker.check_record(row_index, &fields);
+        }
+        if let Some(scorer) = self.model_scorer.as_mut() {
+            scorer.score_record(row_index, record, terminator, &fields);
+        }
+        let width = fields.len();
+        self.field_buffer = fields;
+        width
+    }
+
+    /// Checks the field count of a record against the column limit, fixing the sample
+    /// stride on the first record. Returns whether the record can be profiled.
+    fn check_width(&mut self, width: usize) -> bool {
+        match self.column_sample {
+            Some(count) => {
+                self.sample_stride.get_or_insert(width.div_ceil(count).max(1));
+                true
+            },
+            None if width > self.max_columns => {
+                self.too_many_columns = self.too_many_columns.max(Some(width));
+                false
+            },
+            None => true,
+        }
+    }
+
+    /// Fields of the widest record that was not profiled for having more than
+    /// `--max-columns` fields.
+    pub fn too_many_columns(&self) -> Option<usize> {
+        self.too_many_columns
+    }
+
+    /// Columns profiled, columns seen, and the distance between sampled columns, when
+    /// columns are sampled.
+    pub fn column_sample(&self) -> Option<(usize, usize, usize)> {
+        self.sample_stride.map(|stride| (self.columns.len(), self.excluded_columns.len().max(self.column_names.len()), stride))
+    }
+
+    /// Decides whether the columns up to `count` are excluded, by their display names.
+    fn update_excluded_columns(&mut self, count: usize) {
+        for index in self.excluded_columns.len()..count {
+            let excluded = self.exclude_pattern.as_ref().is_some_and(|pattern| pattern.is_match(&self.column_name(index)));
+            self.excluded_columns.push(excluded);
+        }
+    }
+
+    /// Returns the profiles as (0-based column index, name, profile), one per column seen
+    /// in any row (or per sampled column), except the excluded columns.
+    pub fn columns(&self) -> Vec<(usize, String, &ColumnProfile)> {
+        let stride = self.sample_stride.unwrap_or(1);
+        self.columns.iter()
+            .enumerate()
+            .map(|(slot, profile)| (slot * stride, profile))
+            .filter(|(index, _)| !self.excluded_columns.get(*index).copied().unwrap_or(false))
+            .map(|(index, profile)| (index, self.column_name(index), profile))
+            .collect()
+    }
+
+    /// Names of the columns left out by `--exclude-columns-regex`.
+    pub fn excluded_column_names(&self) -> Vec<String> {
+        self.excluded_columns.iter()
+            .enumerate()
+            .filter(|(_, excluded)| **excluded)
+            .map(|(index, _)| self.column_name(index))
+            .collect()
+    }
+
+    /// The exclusion pattern, when columns are excluded by name.
+    pub fn exclude_pattern(&self) -> Option<&NamePattern> {
+        self.exclude_pattern.as_ref()
+    }
+
+    /// Field delimiter the records are split on.
+    pub fn delimiter(&self) -> char {
+        self.delimiter
+    }
+
+    /// Null tokens that apply to every column.
+    pub fn null_tokens(&self) -> &[String] {
+        &self.null_tokens.all_columns
+    }
+
+    /// Null tokens of a column, including the ones for every column.
+    pub fn column_null_tokens(&self, index: usize) -> &[String] {
+        self.column_null_tokens.get(index).unwrap_or(&self.null_tokens.all_columns)
+    }
+
+    /// Returns the header name of a column, or `column_N` (1-based) when it has none.
+    ///
+    /// Line breaks inside a quoted header name are replaced with spaces for display.
+    pub fn column_name(&self, index: usize) -> String {
+        match self.column_names.get(index) {
+            Some(name) if !name.trim().is_empty() => name.replace("\r\n", " ").replace(['\r', '\n'], " "),
+            _ => format!("column_{}", index + 1),
+        }
+    }
+}
@@ -0,0 +1,505 @@
+//! # Column Consistency Rules
+//!
+//! Simple cross-column checks evaluated on every data record during the analysis pass,
+//! alongside column profiling, so rule checking does not need another scan of the file.
+//!
+//! ## Rule Syntax
+//!
+//! ```text
+//! <expression> <op> <expression> [within <tolerance>]
+//! ```
+//!
+//! * `<op>` is one of `>`, `>=`, `<`, `<=`, `=` (or `==`), `!=`
+//! * `<expression>` combines column names and numbers with `+`, `-`, `*`, `/` and parentheses
+//! * Column names are matched against the header; names with spaces or symbols are quoted:
+//!   `"order total"`
+//! * `within <tolerance>` allows an absolute difference, for rounded amounts
+//!
+//! Values are read as numbers, or as ISO dates (`2024-03-01`, `2024-03-01T12:30:00`),
+//! which compare chronologically and subtract to a number of days. A record whose value
+//! is empty or not a number or date is skipped for that rule and counted separately.
+//!
+//! Rules are configured in the `[column_rules]` section of the config file, or with
+//! `--rule` on the command line:
+//!
+//! ```text
+//! [column_rules]
+//! rule = end_date >= start_date
+//! rule = total = qty * price within 0.01
+//! rule = end_date - start_date <= 365
+//! ```
+
+use crate::config::AnalyzerConfig;
+
+/// Violating rows kept per rule for the violations report
+const MAX_LISTED_VIOLATIONS: usize = 1000;
+
+/// Comparison operator between the two sides of a rule
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+/// One token of a rule's text
+#[derive(Debug, Clone)]
+enum Token {
+    Number(f64),
+    Column(String),
+    Symbol(char),
+    Comparison(Comparison),
+    Within,
+}
+
+/// Arithmetic over column values; columns are indices into the rule's column list
+#[derive(Debug, Clone)]
+enum Expression {
+    Number(f64),
+    Column(usize),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, char, Box<Expression>),
+}
+
+impl Expression {
+    fn evaluate(&self, values: &[f64]) -> f64 {
+        match self {
+            Expression::Number(number) => *number,
+            Expression::Column(slot) => values[*slot],
+            Expression::Negate(inner) => -inner.evaluate(values),
+            Expression::Binary(left, operator, right) => {
+                let (left, right) = (left.evaluate(values), right.evaluate(values));
+                match operator {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    _ => left / right,
+                }
+            },
+        }
+    }
+}
+
+/// A parsed cross-column rule
+#[derive(Debug, Clone)]
+pub struct ColumnRule {
+    /// Rule text as written, used in the reports
+    text: String,
+    /// Column names the rule references, in order of first use
+    columns: Vec<String>,
+    left: Expression,
+    comparison: Comparison,
+    right: Expression,
+    /// Allowed absolute difference between the two sides
+    tolerance: f64,
+}
+
+impl ColumnRule {
+    /// Whether the two evaluated sides satisfy the rule.
+    fn holds(&self, left: f64, right: f64) -> bool {
+        let tolerance = self.tolerance;
+        match self.comparison {
+            Comparison::Greater => left > right - tolerance,
+            Comparison::GreaterOrEqual => left >= right - tolerance,
+            Comparison::Less => left < right + tolerance,
+            Comparison::LessOrEqual => left <= right + tolerance,
+            Comparison::Equal => (left - right).abs() <= tolerance,
+            Comparison::NotEqual => (left - right).abs() > tolerance,
+        }
+    }
+}
+
+/// One record that broke a rule
+#[derive(Debug, Clone)]
+pub struct RuleViolation {
+    /// 0-based row index where the record starts
+    pub row_index: usize,
+    /// Left side as shown in the report (the raw value for a bare column)
+    pub left: String,
+    /// Right side as shown in the report (the raw value for a bare column)
+    pub right: String,
+    /// (column name, raw value) of every column the rule references
+    pub values: Vec<(String, String)>,
+}
+
+/// Outcome of one rule over all records of a file
+#[derive(Debug, Clone)]
+pub struct ColumnRuleResult {
+    /// Rule text as written
+    pub rule: String,
+    /// Records the rule was evaluated on
+    pub checked: u64,
+    /// Records that broke the rule
+    pub violations: u64,
+    /// Records skipped because a value was missing, empty, or not a number or date
+    pub skipped: u64,
+    /// Referenced columns that are not in the header
+    pub missing_columns: Vec<String>,
+    /// The first violations, in file order
+    pub listed_violations: Vec<RuleViolation>,
+}
+
+/// Evaluates a rule set on each data record of a file
+#[derive(Debug, Clone)]
+pub struct ColumnRuleChecker {
+    rules: Vec<ColumnRule>,
+    /// Field index of each column a rule references, resolved from the header
+    field_indices: Vec<Vec<Option<usize>>>,
+    results: Vec<ColumnRuleResult>,
+}
+
+impl ColumnRuleChecker {
+    /// Creates a checker for the given rules; columns resolve once the header is seen.
+    pub fn new(rules: Vec<ColumnRule>) -> Self {
+        let field_indices = rules.iter().map(|rule| vec![None; rule.columns.len()]).collect();
+        let results = rules.iter()
+            .map(|rule| ColumnRuleResult {
+                rule: rule.text.clone(),
+                checked: 0,
+                violations: 0,
+                skipped: 0,
+                missing_columns: rule.columns.clone(),
+                listed_violations: Vec::new(),
+            })
+            .collect();
+        ColumnRuleChecker { rules, field_indices, results }
+    }
+
+    /// Maps the column names of every rule to field positions in the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `header_fields` - Fields of the header record
+    pub fn resolve_header(&mut self, header_fields: &[String]) {
+        for ((rule, indices), result) in self.rules.iter().zip(&mut self.field_indices).zip(&mut self.results) {
+            result.missing_columns.clear();
+            for (slot, name) in rule.columns.iter().enumerate() {
+                indices[slot] = header_fields.iter().position(|field| field.trim() == name);
+                if indices[slot].is_none() {
+                    result.missing_columns.push(name.clone());
+                }
+            }
+        }
+    }
+
+    /// Evaluates every rule on the fields of one data record.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_index` - 0-based row index where the record starts
+    /// * `fields` - Field values of the record
+    pub fn check_record(&mut self, row_index: usize, fields: &[String]) {
+        for ((rule, indices), result) in self.rules.iter().zip(&self.field_indices).zip(&mut self.results) {
+            let raw_values: Option<Vec<&str>> = indices.iter()
+                .map(|index| index.and_then(|index| fields.get(index)).map(|value| value.trim()))
+                .collect();
+            let numbers: Option<Vec<f64>> = raw_values.as_ref()
+                .and_then(|raw| raw.iter().map(|value| parse_value(value)).collect());
+            let (raw_values, numbers) = match (raw_values, numbers) {
+                (Some(raw_values), Some(numbers)) => (raw_values, numbers),
+                _ => {
+                    result.skipped += 1;
+                    continue;
+                },
+            };
+
+            let left = rule.left.evaluate(&numbers);
+            let right = rule.right.evaluate(&numbers);
+            // Division by zero leaves nothing meaningful to compare
+            if !left.is_finite() || !right.is_finite() {
+                result.skipped += 1;
+                continue;
+            }
+
+            result.checked += 1;
+            if rule.holds(left, right) {
+                continue;
+            }
+            result.violations += 1;
+            if result.listed_violations.len() < MAX_LISTED_VIOLATIONS {
+                let show = |side: &Expression, value: f64| match side {
+                    Expression::Column(slot) => raw_values[*slot].to_string(),
+                    _ => format_number(value),
+                };
+                result.listed_violations.push(RuleViolation {
+                    row_index,
+                    left: show(&rule.left, left),
+                    right: show(&rule.right, right),
+                    values: rule.columns.iter()
+                        .zip(&raw_values)
+                        .map(|(name, value)| (name.clone(), value.to_string()))
+                        .collect(),
+                });
+            }
+        }
+    }
+
+    /// Returns the outcome of each rule, in rule order.
+    pub fn into_results(self) -> Vec<ColumnRuleResult> {
+        self.results
+    }
+}
+
+/// Builds the column rule set for a run from `--rule` arguments and the optional config file.
+///
+/// Command line rules come first, followed by the `rule = ...` entries of the
+/// `[column_rules]` section.
+///
+/// # Arguments
+///
+/// * `command_line_rules` - Rules already parsed from `--rule`
+/// * `config` - Parsed configuration file, if one was given
+///
+/// # Returns
+///
+/// * `Result<Vec<ColumnRule>, String>` - The rules or a message naming the invalid entry
+pub fn load_column_rules(
+    command_line_rules: &[ColumnRule],
+    config: Option<&AnalyzerConfig>,
+) -> Result<Vec<ColumnRule>, String> {
+    let mut rules = command_line_rules.to_vec();
+    if let Some(config) = config {
+        for entry in config.section_entries("column_rules") {
+            if entry.key != "rule" {
+                return Err(format!("line {}: unknown key '{}' in [column_rules]", entry.line_number, entry.key));
+            }
+            rules.push(parse_column_rule(&entry.value).map_err(|e| format!("line {}: {}", entry.line_number, e))?);
+        }
+    }
+    Ok(rules)
+}
+
+/// Parses one rule from its textual form.
+///
+/// # Arguments
+///
+/// * `text` - Rule text such as `total = qty * price within 0.01`
+///
+/// # Returns
+///
+/// * `Result<ColumnRule, String>` - The parsed rule or a description of the problem
+pub fn parse_column_rule(text: &str) -> Result<ColumnRule, String> {
+    let tokens = tokenize(text)?;
+
+    let comparisons: Vec<usize> = tokens.iter()
+        .enumerate()
+        .filter(|(_, token)| matches!(token, Token::Comparison(_)))
+        .map(|(position, _)| position)
+        .collect();
+    let position = match comparisons.as_slice() {
+        [position] => *position,
+        [] => return Err(format!("rule '{}' has no comparison operator", text)),
+        _ => return Err(format!("rule '{}' has more than one comparison operator", text)),
+    };
+    let comparison = match tokens[position] {
+        Token::Comparison(comparison) => comparison,
+        _ => unreachable!("position was selected as a comparison token"),
+    };
+
+    let (right_tokens, tolerance) = match tokens.iter().position(|token| matches!(token, Token::Within)) {
+        Some(within) if within < position => return Err(format!("'within' must follow the comparison in rule '{}'", text)),
+        Some(within) => match &tokens[within + 1..] {
+            [Token::Number(tolerance)] => (&tokens[position + 1..within], *tolerance),
+            _ => return Err(format!("'within' must be followed by a single number in rule '{}'", text)),
+        },
+        None => (&tokens[position + 1..], 0.0),
+    };
+
+    let mut columns = Vec::new();
+    let left = parse_side(&tokens[..position], &mut columns, text)?;
+    let right = parse_side(right_tokens, &mut columns, text)?;
+    if columns.is_empty() {
+        return Err(format!("rule '{}' does not reference any column", text));
+    }
+
+    Ok(ColumnRule { text: text.trim().to_string(), columns, left, comparison, right, tolerance })
+}
+
+/// Splits rule text into tokens.
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            },
+            '+' | '-' | '*' | '/' | '(' | ')' => {
+                tokens.push(Token::Symbol(c));
+                chars.next();
+            },
+            '>' | '<' | '=' | '!' => {
+                chars.next();
+                let followed_by_equals = chars.next_if(|&(_, next)| next == '=').is_some();
+                let comparison = match (c, followed_by_equals) {
+                    ('>', false) => Comparison::Greater,
+                    ('>', true) => Comparison::GreaterOrEqual,
+                    ('<', false) => Comparison::Less,
+                    ('<', true) => Comparison::LessOrEqual,
+                    ('=', _) => Comparison::Equal,
+                    ('!', true) => Comparison::NotEqual,
+                    _ => return Err(format!("unexpected '!' in rule '{}'", text)),
+                };
+                tokens.push(Token::Comparison(comparison));
+            },
+            '"' | '`' => {
+                chars.next();
+                let name: String = chars.by_ref().map(|(_, ch)| ch).take_while(|&ch| ch != c).collect();
+                if !text[start + 1..].contains(c) {
+                    return Err(format!("unclosed {} in rule '{}'", c, text));
+                }
+                tokens.push(Token::Column(name));
+            },
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = start;
+                while let Some((position, ch)) = chars.next_if(|&(_, ch)| ch.is_ascii_digit() || ch == '.') {
+                    end = position + ch.len_utf8();
+                }
+                let number = text[start..end].parse()
+                    .map_err(|_| format!("invalid number '{}' in rule '{}'", &text[start..end], text))?;
+                tokens.push(Token::Number(number));
+            },
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start;
+                while let Some((position, ch)) = chars.next_if(|&(_, ch)| ch.is_alphanumeric() || ch == '_') {
+                    end = position + ch.len_utf8();
+                }
+                match &text[start..end] {
+                    "within" => tokens.push(Token::Within),
+                    name => tokens.push(Token::Column(name.to_string())),
+                }
+            },
+            c => return Err(format!("unexpected '{}' in rule '{}'", c, text)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parses one side of a rule, registering the columns it references.
+fn parse_side(tokens: &[Token], columns: &mut Vec<String>, text: &str) -> Result<Expression, String> {
+    if tokens.is_empty() {
+        return Err(format!("rule '{}' is missing an expression on one side of the comparison", text));
+    }
+    let mut position = 0;
+    let expression = parse_sum(tokens, &mut position, columns, text)?;
+    if position < tokens.len() {
+        return Err(format!("unexpected {:?} in rule '{}'", tokens[position], text));
+    }
+    Ok(expression)
+}
+
+/// `term (('+' | '-') term)*`
+fn parse_sum(tokens: &[Token], position: &mut usize, columns: &mut Vec<String>, text: &str) -> Result<Expression, String> {
+    let mut expression = parse_product(tokens, position, columns, text)?;
+    while let Some(Token::Symbol(operator @ ('+' | '-'))) = tokens.get(*position) {
+        *position += 1;
+        let right = parse_product(tokens, position, columns, text)?;
+        expression = Expression::Binary(Box::new(expression), *operator, Box::new(right));
+    }
+    Ok(expression)
+}
+
+/// `factor (('*' | '/') factor)*`
+fn parse_product(tokens: &[Token], position: &mut usize, columns: &mut Vec<String>, text: &str) -> Result<Expression, String> {
+    let mut expression = parse_factor(tokens, position, columns, text)?;
+    while let Some(Token::Symbol(operator @ ('*' | '/'))) = tokens.get(*position) {
+        *position += 1;
+        let right = parse_factor(tokens, position, columns, text)?;
+        expression = Expression::Binary(Box::new(expression), *operator, Box::new(right));
+    }
+    Ok(expression)
+}
+
+/// A number, a column, a negated factor, or a parenthesized expression.
+fn parse_factor(tokens: &[Token], position: &mut usize, columns: &mut Vec<String>, text: &str) -> Result<Expression, String> {
+    let token = tokens.get(*position)
+        .ok_or_else(|| format!("rule '{}' ends in the middle of an expression", text))?;
+    *position += 1;
+    match token {
+        Token::Number(number) => Ok(Expression::Number(*number)),
+        Token::Column(name) => {
+            let slot = match columns.iter().position(|column| column == name) {
+                Some(slot) => slot,
+                None => {
+                    columns.push(name.clone());
+                    columns.len() - 1
+                },
+            };
+            Ok(Expression::Column(slot))
+        },
+        Token::Symbol('-') => Ok(Expression::Negate(Box::new(parse_factor(tokens, position, columns, text)?))),
+        Token::Symbol('(') => {
+            let inner = parse_sum(tokens, position, columns, text)?;
+            match tokens.get(*position) {
+                Some(Token::Symbol(')')) => {
+                    *position += 1;
+                    Ok(inner)
+                },
+                _ => Err(format!("missing ')' in rule '{}'", text)),
+            }
+        },
+        other => Err(format!("unexpected {:?} in rule '{}'", other, text)),
+    }
+}
+
+/// Reads a field value as a number, or as an ISO date in days since 1970-01-01.
+fn parse_value(value: &str) -> Option<f64> {
+    let first = value.chars().next()?;
+    // Rejects words Rust would parse as numbers ("inf", "NaN")
+    if (first.is_ascii_digit() || matches!(first, '-' | '+' | '.'))
+        && let Ok(number) = value.parse::<f64>()
+    {
+        return Some(number);
+    }
+    parse_iso_date(value)
+}
+
+/// Parses `YYYY-MM-DD`, optionally followed by `T` or a space and `HH:MM[:SS[.fff]]` and `Z`.
+fn parse_iso_date(value: &str) -> Option<f64> {
+    let bytes = value.as_bytes();
+    if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let digits = |range: std::ops::Range<usize>| -> Option<i64> {
+        let part = value.get(range)?;
+        part.bytes().all(|b| b.is_ascii_digit()).then(|| part.parse().ok())?
+    };
+    let (year, month, day) = (digits(0..4)?, digits(5..7)?, digits(8..10)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut seconds = 0.0;
+    let rest = value[10..].trim_end_matches('Z');
+    if !rest.is_empty() {
+        let time = rest.strip_prefix(['T', ' '])?;
+        let mut parts = time.splitn(3, ':');
+        let hours: f64 = parts.next()?.parse().ok()?;
+        let minutes: f64 = parts.next()?.parse().ok()?;
+        let secs: f64 = parts.next().map_or(Some(0.0), |s| s.parse().ok())?;
+        seconds = hours * 3600.0 + minutes * 60.0 + secs;
+    }
+
+    // Days-from-civil for the proleptic Gregorian calendar
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year.div_euclid(400);
+    let year_of_era = shifted_year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Some(days as f64 + seconds / 86_400.0)
+}
+
+/// Formats whole numbers without decimals and everything else with up to six.
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.6}", value).trim_end_matches('0').to_string()
+    }
+}
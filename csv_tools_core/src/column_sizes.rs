@@ -0,0 +1,104 @@
+//! # Column Sizes (`--column-sizes`)
+//!
+//! Measures how much of the file each column accounts for, as treemap-ready data: which
+//! columns make up the bulk of a large feed, and so which are worth dropping, moving to
+//! another table, or compressing.
+//!
+//! ```bash
+//! $ csv_row_analyzer feed.csv reports --column-sizes
+//! $ csv_row_analyzer feed.csv reports --column-sizes --column-reports-json
+//! ```
+//!
+//! Fields are measured as written, quotes and escaped quotes included, in characters and
+//! UTF-8 bytes. The header row, the delimiters, and the line terminators get entries of
+//! their own, so the shares add up to the whole text the analyzer read (lines that are
+//! not valid UTF-8 are left out). Columns left out by `--exclude-columns-regex` are
+//! measured anyway, since they still take up space in the file.
+
+use std::cmp::Reverse;
+
+use crate::count_mode::count_chars;
+use crate::fields::field_widths;
+
+/// Characters and bytes written for one part of the file
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Size {
+    pub chars: u64,
+    pub bytes: u64,
+}
+
+impl Size {
+    fn add(&mut self, chars: usize, bytes: usize) {
+        self.chars += chars as u64;
+        self.bytes += bytes as u64;
+    }
+}
+
+/// Sizes of every column and of the text between them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnSizes {
+    /// Data fields per column, by position
+    pub columns: Vec<Size>,
+    /// The header record, delimiters and terminator included
+    pub header: Size,
+    /// Delimiters between the fields of data records
+    pub delimiters: Size,
+    /// Terminators after data records
+    pub line_endings: Size,
+}
+
+/// One entry of the report: a column or one of the other parts of the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeEntry {
+    /// Column position, or None for the header, delimiters, and line endings
+    pub index: Option<usize>,
+    pub name: String,
+    pub size: Size,
+    /// Percentage of all bytes measured
+    pub share: f64,
+}
+
+impl ColumnSizes {
+    /// Measures the header record.
+    pub fn observe_header(&mut self, record: &str, terminator: &str) {
+        self.header.add(count_chars(record) + terminator.len(), record.len() + terminator.len());
+    }
+
+    /// Measures the fields of one data record and what separates and ends them.
+    pub fn observe_record(&mut self, record: &str, delimiter: char, terminator: &str) {
+        let widths = field_widths(record, delimiter);
+        if widths.len() > self.columns.len() {
+            self.columns.resize(widths.len(), Size::default());
+        }
+        for (size, (chars, bytes)) in self.columns.iter_mut().zip(&widths) {
+            size.add(*chars, *bytes);
+        }
+        let delimiters = widths.len() - 1;
+        self.delimiters.add(delimiters, delimiters * delimiter.len_utf8());
+        self.line_endings.add(terminator.len(), terminator.len());
+    }
+
+    /// Returns the columns, largest first, followed by the header, delimiters, and line
+    /// endings, each with its share of all bytes measured.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - Name of the column at a position
+    pub fn entries(&self, column_name: impl Fn(usize) -> String) -> Vec<SizeEntry> {
+        let total: u64 = self.columns.iter()
+            .chain([&self.header, &self.delimiters, &self.line_endings])
+            .map(|size| size.bytes)
+            .sum();
+        let share = |size: &Size| if total > 0 { size.bytes as f64 * 100.0 / total as f64 } else { 0.0 };
+
+        let mut entries: Vec<SizeEntry> = self.columns.iter()
+            .enumerate()
+            .map(|(index, size)| SizeEntry { index: Some(index), name: column_name(index), size: *size, share: share(size) })
+            .collect();
+        entries.sort_by_key(|entry| Reverse(entry.size.bytes));
+        for (name, size) in [("(header)", &self.header), ("(delimiters)", &self.delimiters), ("(line endings)", &self.line_endings)] {
+            entries.push(SizeEntry { index: None, name: name.to_string(), size: *size, share: share(size) });
+        }
+        entries
+    }
+}
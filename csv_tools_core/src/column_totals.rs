@@ -0,0 +1,151 @@
+//! # Column Totals (`--column-totals`)
+//!
+//! Sums every numeric column in the same pass as the row analysis, so a control total
+//! (the total of an `amount` column, say) can be checked against a manifest without a
+//! separate `awk` pass:
+//!
+//! ```bash
+//! $ csv_row_analyzer payments.csv reports --column-totals
+//! ```
+//!
+//! A column is totaled when its inferred type (see the `column_profile` module) is
+//! `integer` or `number`; empty values are skipped. Columns that must stay text, such as
+//! identifiers with leading zeros, are not numeric and are not totaled.
+//!
+//! Plain decimals (`-12`, `1234.50`) are added exactly in decimal, so a money column
+//! totals to the cent however many rows it has. Once a column has a value in exponent
+//! notation (`1e6`), more than [`MAX_EXACT_SCALE`] decimal places, or a total too large
+//! for exact arithmetic, its total is a double and is marked as approximate.
+
+use crate::column_profile::ColumnProfiler;
+
+/// Most decimal places kept exactly
+pub const MAX_EXACT_SCALE: u32 = 18;
+
+/// Sum of the numeric values of one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecimalSum {
+    /// Exact total in units of 10^-scale, while every value was a plain decimal
+    units: Option<i128>,
+    scale: u32,
+    /// Total as a double, always kept as the fallback
+    approximate: f64,
+}
+
+impl Default for DecimalSum {
+    fn default() -> Self {
+        DecimalSum { units: Some(0), scale: 0, approximate: 0.0 }
+    }
+}
+
+impl DecimalSum {
+    /// Adds a numeric value as written.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A value accepted by the profiler's number check
+    /// * `number` - The same value parsed as a double
+    pub fn add(&mut self, value: &str, number: f64) {
+        self.approximate += number;
+        let Some(units) = self.units else {
+            return;
+        };
+        self.units = parse_plain_decimal(value).and_then(|(value_units, value_scale)| {
+            // Bring both to the larger scale, then add
+            let scale = self.scale.max(value_scale);
+            let total = units.checked_mul(10i128.checked_pow(scale - self.scale)?)?;
+            let value_units = value_units.checked_mul(10i128.checked_pow(scale - value_scale)?)?;
+            self.scale = scale;
+            total.checked_add(value_units)
+        });
+    }
+
+    /// Whether the total is exact.
+    pub fn is_exact(&self) -> bool {
+        self.units.is_some()
+    }
+
+    /// Formats the total: exact totals with the largest scale written in the column
+    /// (`1234.50`), approximate ones as the shortest double representation.
+    pub fn format(&self) -> String {
+        let Some(units) = self.units else {
+            return self.approximate.to_string();
+        };
+        if self.scale == 0 {
+            return units.to_string();
+        }
+        let digits = format!("{:0>width$}", units.unsigned_abs(), width = self.scale as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        format!("{}{}.{}", if units < 0 { "-" } else { "" }, whole, fraction)
+    }
+}
+
+/// Splits a plain decimal (`-12`, `+3.50`, `.5`) into units and scale; None for exponent
+/// notation or more than [`MAX_EXACT_SCALE`] decimal places.
+fn parse_plain_decimal(value: &str) -> Option<(i128, u32)> {
+    let (negative, unsigned) = match value.as_bytes().first() {
+        Some(b'-') => (true, &value[1..]),
+        Some(b'+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if fraction.len() > MAX_EXACT_SCALE as usize
+        || !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let mut units: i128 = 0;
+    for digit in whole.bytes().chain(fraction.bytes()) {
+        units = units.checked_mul(10)?.checked_add((digit - b'0') as i128)?;
+    }
+    Some((if negative { -units } else { units }, fraction.len() as u32))
+}
+
+/// Total of one numeric column, for the reports
+#[derive(Debug, Clone)]
+pub struct ColumnTotal {
+    /// 0-based column index
+    pub index: usize,
+    pub name: String,
+    /// `integer` or `number`
+    pub inferred_type: &'static str,
+    /// Non-empty values added
+    pub values: u64,
+    /// Records without a value in the column (empty or missing)
+    pub empty_values: u64,
+    pub sum: DecimalSum,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ColumnTotal {
+    /// Mean of the added values.
+    pub fn mean(&self) -> f64 {
+        if self.values == 0 { 0.0 } else { self.sum.approximate / self.values as f64 }
+    }
+}
+
+/// Lists the totals of the numeric columns, in column order.
+///
+/// # Arguments
+///
+/// * `profiler` - Finished column profiler of the file
+pub fn collect_column_totals(profiler: &ColumnProfiler) -> Vec<ColumnTotal> {
+    let data_records = profiler.data_records();
+    profiler.columns().into_iter()
+        .filter(|(_, _, profile)| matches!(profile.inferred_type(), "integer" | "number"))
+        .map(|(index, name, profile)| {
+            let (min, max) = profile.numeric_range.unwrap_or((0.0, 0.0));
+            ColumnTotal {
+                index,
+                name,
+                inferred_type: profile.inferred_type(),
+                values: profile.number_values,
+                empty_values: data_records.saturating_sub(profile.non_empty_values),
+                sum: profile.sum.clone(),
+                min,
+                max,
+            }
+        })
+        .collect()
+}
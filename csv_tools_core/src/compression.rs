@@ -0,0 +1,632 @@
+//! # Compressed Input
+//!
+//! Transparently decompresses gzip and Zstandard input (detected by its magic bytes, not
+//! its name) while it is read, so `.csv.gz` and `.csv.zst` files are analyzed without
+//! unpacking them first. The zstd decoder is in the `zstd` module.
+//!
+//! Gzip files may consist of several concatenated members (log shippers often append a
+//! new member per flush). All members are read to the end, each one checked against its
+//! CRC-32 and length trailer, and the rows ending in each member are counted so the
+//! reports can show how the file was assembled.
+//!
+//! The DEFLATE decoder is a small, std-only implementation of RFC 1951 (stored, fixed,
+//! and dynamic Huffman blocks) that keeps the dependency list empty.
+
+use std::cell::RefCell;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::read_ahead::{ReadAheadReader, READ_AHEAD_BUFFER_SIZE};
+use crate::read_buffer::{AdaptiveBufReader, ReadBufferSize};
+use crate::read_retry::ReadRetries;
+use crate::stdin_input::open_source;
+use crate::zstd::{ZstdDecoder, ZSTD_MAGIC};
+
+/// First two bytes of every gzip member
+pub const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// DEFLATE back-references reach at most 32 KiB into the output
+const WINDOW_SIZE: usize = 32 * 1024;
+/// Decode ahead until this many unread bytes are buffered
+const OUTPUT_CHUNK: usize = 64 * 1024;
+/// Longest Huffman code allowed by DEFLATE
+const MAX_CODE_BITS: usize = 15;
+
+/// Base lengths for length symbols 257..285
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+/// Extra bits for length symbols 257..285
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// Base distances for distance symbols 0..29
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049,
+    3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// Extra bits for distance symbols 0..29
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+/// Order in which code length code lengths are stored in a dynamic block header
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// One member of a (possibly multi-member) gzip file
+#[derive(Debug, Clone, Default)]
+pub struct GzipMember {
+    /// Byte offset of the member's header in the compressed file
+    pub compressed_offset: u64,
+    /// Number of bytes the member decompresses to
+    pub uncompressed_bytes: u64,
+    /// Rows whose line terminator is in this member (a final unterminated row counts
+    /// toward the last member)
+    pub rows: u64,
+}
+
+/// Shared view of the members decoded so far, readable after the decoder was moved
+/// into a line reader
+#[derive(Debug, Clone, Default)]
+pub struct GzipMembers(Rc<RefCell<Vec<GzipMember>>>);
+
+impl GzipMembers {
+    /// Returns a copy of the members decoded so far.
+    pub fn snapshot(&self) -> Vec<GzipMember> {
+        self.0.borrow().clone()
+    }
+}
+
+/// How an input file is compressed
+#[derive(Debug, Clone)]
+pub enum Compression {
+    /// gzip, with the members decoded so far
+    Gzip(GzipMembers),
+    /// Zstandard
+    Zstd,
+}
+
+/// Names the compression format a file starting with `start` is in, if any.
+pub fn compression_format(start: &[u8]) -> Option<&'static str> {
+    if start.starts_with(&GZIP_MAGIC) {
+        Some("gzip")
+    } else if start.starts_with(&ZSTD_MAGIC) {
+        Some("zstd")
+    } else {
+        None
+    }
+}
+
+/// Canonical Huffman code in the counts/symbols form used for bit-by-bit decoding
+struct Huffman {
+    /// Number of codes of each length (index 0 unused)
+    counts: [u16; MAX_CODE_BITS + 1],
+    /// Symbols ordered by code
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Builds the code from per-symbol code lengths (0 = symbol unused).
+    fn new(lengths: &[u8]) -> Result<Huffman, io::Error> {
+        let mut counts = [0u16; MAX_CODE_BITS + 1];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+
+        // Reject over-subscribed codes; incomplete codes are allowed (e.g. one distance code)
+        let mut left: i32 = 1;
+        for &count in &counts[1..] {
+            left = (left << 1) - count as i32;
+            if left < 0 {
+                return Err(invalid_data("over-subscribed Huffman code"));
+            }
+        }
+
+        let mut offsets = [0u16; MAX_CODE_BITS + 2];
+        for length in 1..=MAX_CODE_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0u16; offsets[MAX_CODE_BITS + 1] as usize];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Ok(Huffman { counts, symbols })
+    }
+}
+
+/// Where the decoder is within the gzip stream
+enum State {
+    /// Expecting a member header (or the end of the file between members)
+    MemberHeader,
+    /// Expecting a DEFLATE block header
+    BlockHeader,
+    /// Copying a stored block with this many bytes left
+    Stored(usize),
+    /// Decoding a compressed block with these literal/length and distance codes
+    Compressed(Rc<Huffman>, Rc<Huffman>),
+    /// Expecting the CRC-32 and size trailer of the current member
+    MemberTrailer,
+    /// All members read
+    Done,
+}
+
+/// Streaming decoder for single- and multi-member gzip data
+pub struct GzipDecoder<R: BufRead> {
+    inner: R,
+    state: State,
+    /// Whether the current block is the last one of its member
+    final_block: bool,
+    /// Bits read from the input but not used yet (least significant first)
+    bit_buffer: u32,
+    bit_count: u32,
+    /// Bytes consumed from the compressed input
+    compressed_position: u64,
+    /// Decoded output: the last `WINDOW_SIZE` bytes already read by the caller, then unread bytes
+    output: Vec<u8>,
+    /// Index of the first unread byte in `output`
+    read_position: usize,
+    /// Index in `output` up to which bytes are included in the member statistics
+    accounted: usize,
+    /// Running CRC-32 and length of the current member
+    member_crc: u32,
+    member_bytes: u64,
+    /// Whether the output so far ends in the middle of a row
+    pending_row: bool,
+    members: GzipMembers,
+    fixed_codes: Option<(Rc<Huffman>, Rc<Huffman>)>,
+}
+
+impl<R: BufRead> GzipDecoder<R> {
+    /// Creates a decoder reading gzip data from `inner`.
+    pub fn new(inner: R) -> Self {
+        GzipDecoder {
+            inner,
+            state: State::MemberHeader,
+            final_block: false,
+            bit_buffer: 0,
+            bit_count: 0,
+            compressed_position: 0,
+            output: Vec::new(),
+            read_position: 0,
+            accounted: 0,
+            member_crc: 0,
+            member_bytes: 0,
+            pending_row: false,
+            members: GzipMembers::default(),
+            fixed_codes: None,
+        }
+    }
+
+    /// Returns a handle to the member list that stays valid after the decoder is moved.
+    pub fn members(&self) -> GzipMembers {
+        self.members.clone()
+    }
+
+    /// Reads one byte of compressed input.
+    fn next_byte(&mut self) -> Result<u8, io::Error> {
+        let buffer = self.inner.fill_buf()?;
+        let byte = *buffer.first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "gzip data ends unexpectedly"))?;
+        self.inner.consume(1);
+        self.compressed_position += 1;
+        Ok(byte)
+    }
+
+    /// Reads a little-endian value of `count` bytes (at most 4).
+    fn next_le(&mut self, count: usize) -> Result<u32, io::Error> {
+        let mut value = 0u32;
+        for i in 0..count {
+            value |= (self.next_byte()? as u32) << (8 * i);
+        }
+        Ok(value)
+    }
+
+    /// Reads `count` bits (at most 16), least significant first.
+    fn bits(&mut self, count: u32) -> Result<u32, io::Error> {
+        while self.bit_count < count {
+            self.bit_buffer |= (self.next_byte()? as u32) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let value = self.bit_buffer & ((1u32 << count) - 1);
+        self.bit_buffer >>= count;
+        self.bit_count -= count;
+        Ok(value)
+    }
+
+    /// Drops the remaining bits of the current byte. Bits are pulled one byte at a time,
+    /// so no whole unread byte is ever left in the bit buffer.
+    fn align_to_byte(&mut self) {
+        self.bit_buffer = 0;
+        self.bit_count = 0;
+    }
+
+    /// Decodes one symbol with a canonical Huffman code, one bit at a time.
+    fn decode_symbol(&mut self, huffman: &Huffman) -> Result<u16, io::Error> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for length in 1..=MAX_CODE_BITS {
+            code |= self.bits(1)? as i32;
+            let count = huffman.counts[length] as i32;
+            if code - first < count {
+                return Ok(huffman.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid_data("invalid Huffman code in gzip data"))
+    }
+
+    /// Parses a member header, or finishes when the input ends between members.
+    fn read_member_header(&mut self) -> Result<(), io::Error> {
+        let member_offset = self.compressed_position;
+        if self.inner.fill_buf()?.is_empty() {
+            if self.members.0.borrow().is_empty() {
+                return Err(invalid_data("empty gzip input"));
+            }
+            self.state = State::Done;
+            return Ok(());
+        }
+
+        let magic = [self.next_byte()?, self.next_byte()?];
+        if magic != GZIP_MAGIC {
+            let member_number = self.members.0.borrow().len() + 1;
+            return Err(invalid_data(&format!(
+                "data at compressed offset {} is not gzip member {}", member_offset, member_number
+            )));
+        }
+        if self.next_byte()? != 8 {
+            return Err(invalid_data("unsupported gzip compression method"));
+        }
+        let flags = self.next_byte()?;
+        if flags & 0xE0 != 0 {
+            return Err(invalid_data("reserved gzip header flags are set"));
+        }
+        // MTIME (4), XFL (1), OS (1)
+        for _ in 0..6 {
+            self.next_byte()?;
+        }
+        if flags & 0x04 != 0 {
+            // FEXTRA
+            let extra_length = self.next_le(2)?;
+            for _ in 0..extra_length {
+                self.next_byte()?;
+            }
+        }
+        for flag in [0x08, 0x10] {
+            // FNAME and FCOMMENT are zero-terminated
+            if flags & flag != 0 {
+                while self.next_byte()? != 0 {}
+            }
+        }
+        if flags & 0x02 != 0 {
+            // FHCRC
+            self.next_le(2)?;
+        }
+
+        self.members.0.borrow_mut().push(GzipMember {
+            compressed_offset: member_offset,
+            ..GzipMember::default()
+        });
+        self.member_crc = 0;
+        self.member_bytes = 0;
+        self.final_block = false;
+        self.state = State::BlockHeader;
+        Ok(())
+    }
+
+    /// Parses a DEFLATE block header.
+    fn read_block_header(&mut self) -> Result<(), io::Error> {
+        self.final_block = self.bits(1)? == 1;
+        self.state = match self.bits(2)? {
+            0 => {
+                self.align_to_byte();
+                let length = self.next_le(2)?;
+                let complement = self.next_le(2)?;
+                if length != !complement & 0xFFFF {
+                    return Err(invalid_data("corrupt stored block length in gzip data"));
+                }
+                State::Stored(length as usize)
+            },
+            1 => {
+                let (literals, distances) = self.fixed_codes()?;
+                State::Compressed(literals, distances)
+            },
+            2 => {
+                let (literals, distances) = self.read_dynamic_codes()?;
+                State::Compressed(Rc::new(literals), Rc::new(distances))
+            },
+            _ => return Err(invalid_data("invalid block type in gzip data")),
+        };
+        Ok(())
+    }
+
+    /// Returns the fixed Huffman codes of RFC 1951, section 3.2.6.
+    fn fixed_codes(&mut self) -> Result<(Rc<Huffman>, Rc<Huffman>), io::Error> {
+        if self.fixed_codes.is_none() {
+            let mut lengths = [0u8; 288];
+            lengths[..144].fill(8);
+            lengths[144..256].fill(9);
+            lengths[256..280].fill(7);
+            lengths[280..].fill(8);
+            let literals = Huffman::new(&lengths)?;
+            let distances = Huffman::new(&[5u8; 30])?;
+            self.fixed_codes = Some((Rc::new(literals), Rc::new(distances)));
+        }
+        Ok(self.fixed_codes.clone().expect("fixed codes were just built"))
+    }
+
+    /// Reads the code descriptions at the start of a dynamic Huffman block.
+    fn read_dynamic_codes(&mut self) -> Result<(Huffman, Huffman), io::Error> {
+        let literal_count = self.bits(5)? as usize + 257;
+        let distance_count = self.bits(5)? as usize + 1;
+        let code_length_count = self.bits(4)? as usize + 4;
+        if literal_count > 286 || distance_count > 30 {
+            return Err(invalid_data("too many codes in gzip block header"));
+        }
+
+        let mut code_length_lengths = [0u8; 19];
+        for &position in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+            code_length_lengths[position] = self.bits(3)? as u8;
+        }
+        let code_length_code = Huffman::new(&code_length_lengths)?;
+
+        let mut lengths = vec![0u8; literal_count + distance_count];
+        let mut index = 0;
+        while index < lengths.len() {
+            let symbol = self.decode_symbol(&code_length_code)?;
+            let (value, repeat) = match symbol {
+                0..=15 => (symbol as u8, 1),
+                16 => {
+                    let previous = *lengths[..index].last()
+                        .ok_or_else(|| invalid_data("repeat with no previous code length in gzip data"))?;
+                    (previous, 3 + self.bits(2)? as usize)
+                },
+                17 => (0, 3 + self.bits(3)? as usize),
+                _ => (0, 11 + self.bits(7)? as usize),
+            };
+            if index + repeat > lengths.len() {
+                return Err(invalid_data("code lengths overflow in gzip block header"));
+            }
+            lengths[index..index + repeat].fill(value);
+            index += repeat;
+        }
+
+        if lengths[256] == 0 {
+            return Err(invalid_data("missing end-of-block code in gzip data"));
+        }
+        Ok((Huffman::new(&lengths[..literal_count])?, Huffman::new(&lengths[literal_count..])?))
+    }
+
+    /// Decodes compressed symbols until the block ends or enough output is buffered.
+    fn decode_compressed(&mut self, literals: &Huffman, distances: &Huffman) -> Result<bool, io::Error> {
+        while self.output.len() - self.read_position < OUTPUT_CHUNK {
+            let symbol = self.decode_symbol(literals)?;
+            match symbol {
+                0..=255 => self.output.push(symbol as u8),
+                256 => return Ok(true),
+                _ => {
+                    let length_index = (symbol - 257) as usize;
+                    if length_index >= LENGTH_BASE.len() {
+                        return Err(invalid_data("invalid length symbol in gzip data"));
+                    }
+                    let length = LENGTH_BASE[length_index] as usize
+                        + self.bits(LENGTH_EXTRA[length_index] as u32)? as usize;
+
+                    let distance_index = self.decode_symbol(distances)? as usize;
+                    if distance_index >= DISTANCE_BASE.len() {
+                        return Err(invalid_data("invalid distance symbol in gzip data"));
+                    }
+                    let distance = DISTANCE_BASE[distance_index] as usize
+                        + self.bits(DISTANCE_EXTRA[distance_index] as u32)? as usize;
+                    // Members are independent, so references may not reach into the previous one
+                    let member_output = self.member_bytes + (self.output.len() - self.accounted) as u64;
+                    if distance as u64 > member_output {
+                        return Err(invalid_data("back-reference before the start of gzip member"));
+                    }
+
+                    // Copy byte by byte: the source may overlap the bytes being written
+                    let start = self.output.len() - distance;
+                    for i in 0..length {
+                        let byte = self.output[start + i];
+                        self.output.push(byte);
+                    }
+                },
+            }
+        }
+        Ok(false)
+    }
+
+    /// Decodes until some output is available or the input is exhausted.
+    fn fill_output(&mut self) -> Result<(), io::Error> {
+        while self.read_position == self.output.len() && !matches!(self.state, State::Done) {
+            self.compact_output();
+            match std::mem::replace(&mut self.state, State::Done) {
+                State::MemberHeader => self.read_member_header()?,
+                State::BlockHeader => self.read_block_header()?,
+                State::Stored(remaining) => {
+                    let take = remaining.min(OUTPUT_CHUNK);
+                    for _ in 0..take {
+                        let byte = self.next_byte()?;
+                        self.output.push(byte);
+                    }
+                    self.state = if take < remaining {
+                        State::Stored(remaining - take)
+                    } else {
+                        self.end_of_block()
+                    };
+                },
+                State::Compressed(literals, distances) => {
+                    let block_done = self.decode_compressed(&literals, &distances)?;
+                    self.state = if block_done {
+                        self.end_of_block()
+                    } else {
+                        State::Compressed(literals, distances)
+                    };
+                },
+                State::MemberTrailer => {
+                    self.account_output();
+                    self.align_to_byte();
+                    let crc = self.next_le(4)?;
+                    let size = self.next_le(4)?;
+                    let member_number = self.members.0.borrow().len();
+                    if crc != self.member_crc {
+                        return Err(invalid_data(&format!("CRC-32 mismatch in gzip member {}", member_number)));
+                    }
+                    if size != self.member_bytes as u32 {
+                        return Err(invalid_data(&format!("length mismatch in gzip member {}", member_number)));
+                    }
+                    self.state = State::MemberHeader;
+                },
+                State::Done => {},
+            }
+            self.account_output();
+        }
+        Ok(())
+    }
+
+    /// State that follows the end of a block.
+    fn end_of_block(&self) -> State {
+        if self.final_block {
+            State::MemberTrailer
+        } else {
+            State::BlockHeader
+        }
+    }
+
+    /// Updates the member CRC, length, and row count for newly decoded output.
+    ///
+    /// Once all input is read, an unterminated last row is counted toward the last member.
+    fn account_output(&mut self) {
+        let new_bytes = &self.output[self.accounted..];
+        let mut members = self.members.0.borrow_mut();
+
+        if !new_bytes.is_empty() {
+            self.member_crc = crc32_update(self.member_crc, new_bytes);
+            self.member_bytes += new_bytes.len() as u64;
+            self.pending_row = new_bytes.last() != Some(&b'\n');
+            if let Some(member) = members.last_mut() {
+                member.uncompressed_bytes = self.member_bytes;
+                member.rows += new_bytes.iter().filter(|&&b| b == b'\n').count() as u64;
+            }
+            self.accounted = self.output.len();
+        }
+
+        if matches!(self.state, State::Done) && self.pending_row {
+            if let Some(member) = members.last_mut() {
+                member.rows += 1;
+            }
+            self.pending_row = false;
+        }
+    }
+
+    /// Drops output that is both read by the caller and outside the back-reference window.
+    fn compact_output(&mut self) {
+        if self.read_position > 2 * WINDOW_SIZE {
+            let drop_count = self.read_position - WINDOW_SIZE;
+            self.output.drain(..drop_count);
+            self.read_position -= drop_count;
+            self.accounted -= drop_count;
+        }
+    }
+}
+
+impl<R: BufRead> Read for GzipDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fill_output()?;
+        let available = &self.output[self.read_position..];
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.read_position += count;
+        Ok(count)
+    }
+}
+
+/// Opens a file for reading, decompressing it on the fly when it starts with the gzip or zstd magic bytes.
+///
+/// # Arguments
+///
+/// * `input_file_path` - File to open
+///
+/// # Returns
+///
+/// * `Result<(Box<dyn BufRead>, Option<Compression>), io::Error>` - Reader over the (decompressed)
+///   content, plus the compression of compressed input
+pub fn open_input(input_file_path: impl AsRef<Path>) -> Result<(Box<dyn BufRead>, Option<Compression>), io::Error> {
+    open_input_with_read_ahead(input_file_path, false, ReadBufferSize::Adaptive, &ReadRetries::default())
+}
+
+/// Opens a file like [`open_input`], optionally reading the file on a background thread.
+///
+/// With read-ahead, only the disk reads move to the background thread; compressed input
+/// is still decompressed on the calling thread.
+///
+/// # Arguments
+///
+/// * `input_file_path` - File to open (`-` for standard input)
+/// * `read_ahead` - Fill the next buffer on a background thread (see the `read_ahead` module)
+/// * `buffer_size` - Size of the read buffer (see the `read_buffer` module)
+/// * `retries` - Retry policy of failed reads, and the count of retries made (see the `read_retry` module)
+///
+/// # Returns
+///
+/// * `Result<(Box<dyn BufRead>, Option<Compression>), io::Error>` - Reader over the (decompressed)
+///   content, plus the compression of compressed input
+pub fn open_input_with_read_ahead(
+    input_file_path: impl AsRef<Path>,
+    read_ahead: bool,
+    buffer_size: ReadBufferSize,
+    retries: &ReadRetries,
+) -> Result<(Box<dyn BufRead>, Option<Compression>), io::Error> {
+    let file = retries.wrap(open_source(input_file_path)?);
+    let mut reader: Box<dyn BufRead> = if read_ahead {
+        Box::new(ReadAheadReader::new(file, buffer_size.fixed_or(READ_AHEAD_BUFFER_SIZE)))
+    } else {
+        Box::new(AdaptiveBufReader::new(file, buffer_size))
+    };
+    let start = reader.fill_buf()?;
+    if start.starts_with(&GZIP_MAGIC) {
+        let decoder = GzipDecoder::new(reader);
+        let members = decoder.members();
+        Ok((Box::new(AdaptiveBufReader::new(decoder, buffer_size)), Some(Compression::Gzip(members))))
+    } else if start.starts_with(&ZSTD_MAGIC) {
+        Ok((Box::new(AdaptiveBufReader::new(ZstdDecoder::new(reader), buffer_size)), Some(Compression::Zstd)))
+    } else {
+        Ok((reader, None))
+    }
+}
+
+/// Updates a CRC-32 (IEEE 802.3, as used by gzip) with more data.
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &byte in bytes {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// Lookup table for the reflected CRC-32 polynomial 0xEDB88320
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB88320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+pub fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
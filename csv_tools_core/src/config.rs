@@ -0,0 +1,131 @@
+//! # Analyzer Configuration File
+//!
+//! A minimal INI-style configuration format used to customize an analysis run
+//! without recompiling. The format is deliberately small:
+//!
+//! ```text
+//! # Lines starting with '#' or ';' are comments
+//! [recommendations]
+//! rule = max_length > 1000000 => Rows over 1MB must be routed to the blob pipeline ({max_length} chars).
+//! warning = error_rows > 0 => {error_rows} rows could not be read and are missing from the statistics.
+//! ```
+//!
+//! Keys may repeat within a section; every occurrence is kept in file order.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A single `key = value` line from the configuration file
+#[derive(Debug, Clone)]
+pub struct ConfigEntry {
+    /// Section the entry belongs to (empty string for entries before any section header)
+    pub section: String,
+    /// Key on the left-hand side of the `=`
+    pub key: String,
+    /// Value on the right-hand side of the `=`, trimmed of surrounding whitespace
+    pub value: String,
+    /// 1-based line number in the configuration file, used in error messages
+    pub line_number: usize,
+}
+
+/// Parsed contents of an analyzer configuration file
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzerConfig {
+    entries: Vec<ConfigEntry>,
+}
+
+impl AnalyzerConfig {
+    /// Reads and parses a configuration file from disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the configuration file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<AnalyzerConfig, io::Error>` - The parsed configuration, or an error if the file
+    ///   cannot be read or contains malformed lines
+    pub fn load(path: impl AsRef<Path>) -> Result<AnalyzerConfig, io::Error> {
+        let text = fs::read_to_string(path.as_ref())?;
+        AnalyzerConfig::parse(&text).map_err(|e| io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.as_ref().display(), e)
+        ))
+    }
+
+    /// Parses configuration text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Contents of a configuration file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<AnalyzerConfig, String>` - The parsed configuration or a message naming the bad line
+    pub fn parse(text: &str) -> Result<AnalyzerConfig, String> {
+        let mut entries = Vec::new();
+        let mut section = String::new();
+
+        for (idx, raw_line) in text.lines().enumerate() {
+            let line_number = idx + 1;
+            let line = raw_line.trim();
+
+            // Skip blank lines and comments
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            // Section header
+            if line.starts_with('[') {
+                if !line.ends_with(']') || line.len() < 3 {
+                    return Err(format!("line {}: malformed section header '{}'", line_number, line));
+                }
+                section = line[1..line.len() - 1].trim().to_lowercase();
+                continue;
+            }
+
+            // key = value
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| format!("line {}: expected 'key = value', found '{}'", line_number, line))?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(format!("line {}: missing key before '='", line_number));
+            }
+
+            entries.push(ConfigEntry {
+                section: section.clone(),
+                key: key.to_lowercase(),
+                value: value.trim().to_string(),
+                line_number,
+            });
+        }
+
+        Ok(AnalyzerConfig { entries })
+    }
+
+    /// Returns every entry in the given section, in file order.
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - Section name (case-insensitive)
+    pub fn section_entries<'a>(&'a self, section: &str) -> impl Iterator<Item = &'a ConfigEntry> + 'a {
+        let section = section.to_lowercase();
+        self.entries.iter().filter(move |entry| entry.section == section)
+    }
+
+    /// Returns the last value set for a key in a section, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - Section name (case-insensitive)
+    /// * `key` - Key name (case-insensitive)
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        let section = section.to_lowercase();
+        let key = key.to_lowercase();
+        self.entries.iter()
+            .rev()
+            .find(|entry| entry.section == section && entry.key == key)
+            .map(|entry| entry.value.as_str())
+    }
+}
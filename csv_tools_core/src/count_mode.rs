@@ -0,0 +1,100 @@
+//! # Row Length Unit (`--count-mode`)
+//!
+//! Row lengths are counted in characters by default. Decoding every row's UTF-8 to count
+//! its characters used to dominate the run on large files, so the count takes a fast path:
+//! a row that is all ASCII (checked a machine word at a time) has as many characters as
+//! bytes, and only rows with non-ASCII bytes are counted character by character. The
+//! reports are the same either way.
+//!
+//! A character count is not every system's length, so `--count-mode` picks the unit that
+//! matches where the rows are going:
+//!
+//! * `chars` - Unicode scalar values (the default)
+//! * `bytes` - UTF-8 bytes, for byte-based limits or the most throughput, since even the
+//!   ASCII check is skipped
+//! * `utf16` - UTF-16 code units, the length of SQL Server `NVARCHAR`, Java, JavaScript,
+//!   and .NET strings; an emoji or a rare CJK character outside the Basic Multilingual Plane
+//!   counts 2
+//! * `graphemes` - User-perceived characters (see the `graphemes` module): a flag, a
+//!   skin-toned emoji, or a letter with combining accents counts 1
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --count-mode utf16
+//! ```
+//!
+//! The mode applies to every row length: the statistics, the outlier fences, every report
+//! built from them, `--flag-stream`, and `--verify-sample`. The outlier reports state the
+//! unit when it is not characters. Column reports (`--profile-columns`, `--column-sizes`)
+//! keep counting the characters of values, and the length model (`--export-model`,
+//! `--score-against`) is fitted on characters, so it cannot be combined with another unit.
+//! Rows that are not valid UTF-8 stay unreadable in every mode.
+
+use crate::graphemes::count_graphemes;
+
+/// Unit of the row lengths
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CountMode {
+    /// Unicode scalar values (the default)
+    #[default]
+    Chars,
+    /// UTF-8 bytes
+    Bytes,
+    /// UTF-16 code units
+    Utf16,
+    /// Extended grapheme clusters
+    Graphemes,
+}
+
+impl CountMode {
+    /// Parses the value of `--count-mode`.
+    pub fn parse(value: &str) -> Result<CountMode, String> {
+        match value {
+            "chars" => Ok(CountMode::Chars),
+            "bytes" => Ok(CountMode::Bytes),
+            "utf16" => Ok(CountMode::Utf16),
+            "graphemes" => Ok(CountMode::Graphemes),
+            _ => Err(format!("invalid --count-mode value '{}' (use chars, bytes, utf16, or graphemes)", value)),
+        }
+    }
+
+    /// Name of the unit, as given to `--count-mode`.
+    pub fn name(self) -> &'static str {
+        match self {
+            CountMode::Chars => "chars",
+            CountMode::Bytes => "bytes",
+            CountMode::Utf16 => "utf16",
+            CountMode::Graphemes => "graphemes",
+        }
+    }
+
+    /// Length of a row's content in this unit.
+    pub fn count(self, text: &str) -> usize {
+        match self {
+            CountMode::Chars => count_chars(text),
+            CountMode::Bytes => text.len(),
+            CountMode::Utf16 if text.is_ascii() => text.len(),
+            CountMode::Utf16 => text.chars().map(char::len_utf16).sum(),
+            CountMode::Graphemes => count_graphemes(text),
+        }
+    }
+
+    /// Report header line naming the unit, or None for characters.
+    pub fn report_note(self) -> Option<&'static str> {
+        match self {
+            CountMode::Chars => None,
+            CountMode::Bytes => Some("Row lengths are counted in UTF-8 bytes (--count-mode bytes); every length in chars below is in bytes"),
+            CountMode::Utf16 => Some("Row lengths are counted in UTF-16 code units (--count-mode utf16); every length in chars below is in code units"),
+            CountMode::Graphemes => Some("Row lengths are counted in grapheme clusters (--count-mode graphemes); every length in chars below is in graphemes"),
+        }
+    }
+}
+
+/// Characters of a text: its byte length when it is all ASCII, as most rows are, and a
+/// full UTF-8 count otherwise.
+pub fn count_chars(text: &str) -> usize {
+    if text.is_ascii() {
+        text.len()
+    } else {
+        text.chars().count()
+    }
+}
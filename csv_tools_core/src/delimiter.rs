@@ -0,0 +1,153 @@
+//! # Field Delimiter (`--delimiter`)
+//!
+//! The field-level analyses (column profiles, field counts, row flags, `--csv-mode`
+//! records) split records at a delimiter, chosen for each file:
+//!
+//! 1. `--delimiter`, when set
+//! 2. The file's extension: tab for `.tsv` and `.tab`, pipe for `.psv` (before a `.gz`
+//!    or `.zst` suffix)
+//! 3. Sniffed from the first records: the one of comma, semicolon, tab, and pipe that
+//!    most records carry the same non-zero number of, outside quotes (the detection
+//!    `--all-files` uses, see the `file_selection` module)
+//! 4. Comma
+//!
+//! A directory run (`--extensions csv,tsv,txt`) thus reads each file with its own
+//! delimiter:
+//!
+//! ```bash
+//! $ csv_row_analyzer export_de.csv reports --field-counts                 # semicolon, detected
+//! $ csv_row_analyzer --directory exports reports --extensions csv,tsv --profile-columns
+//! $ csv_row_analyzer ledger.txt reports --profile-columns --delimiter pipe
+//! $ csv_row_analyzer odd.dat reports --field-counts --delimiter '^'
+//! ```
+//!
+//! The delimiter is `comma`, `semicolon`, `tab`, `pipe`, or any other single character
+//! but a quote or line break. Standard input and the streams of the async analyzer are
+//! read only once, so they are not sniffed: they use `--delimiter`, or comma. The report
+//! headers record the delimiter and where it came from, e.g. `Delimiter: semicolon
+//! (detected)`.
+
+use std::path::Path;
+
+use crate::file_selection::{detect_delimited_content, COMPRESSED_SUFFIXES};
+use crate::stdin_input::is_stdin;
+
+/// How the delimiter of a file was chosen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DelimiterSource {
+    /// Set with `--delimiter`
+    Flag,
+    /// Named by the file's extension
+    Extension,
+    /// Sniffed from the first records
+    Detected,
+    /// Comma, as nothing was set or detected
+    Default,
+}
+
+/// Field delimiter of a file, with how it was chosen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Delimiter {
+    pub symbol: char,
+    pub source: DelimiterSource,
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Delimiter { symbol: ',', source: DelimiterSource::Default }
+    }
+}
+
+impl Delimiter {
+    /// The `--delimiter` setting, or comma, for input that cannot be sniffed.
+    pub fn given(symbol: Option<char>) -> Self {
+        match symbol {
+            Some(symbol) => Delimiter { symbol, source: DelimiterSource::Flag },
+            None => Delimiter::default(),
+        }
+    }
+
+    /// Chooses the delimiter of a file: the `--delimiter` setting, else the one its
+    /// extension names, else the one sniffed from its first records, else comma.
+    ///
+    /// A start that cannot be read (a damaged compressed frame) is sniffed as nothing; the
+    /// pass over the file reports the unreadable rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_file_path` - File to sniff (gzip and zstd input is decompressed)
+    /// * `symbol` - Delimiter set with `--delimiter`, if any
+    pub fn resolve(input_file_path: impl AsRef<Path>, symbol: Option<char>) -> Self {
+        if symbol.is_some() || is_stdin(&input_file_path) {
+            return Delimiter::given(symbol);
+        }
+        if let Some(symbol) = extension_delimiter(&input_file_path) {
+            return Delimiter { symbol, source: DelimiterSource::Extension };
+        }
+        match detect_delimited_content(&input_file_path) {
+            Ok(Ok(symbol)) => Delimiter { symbol, source: DelimiterSource::Detected },
+            _ => Delimiter::default(),
+        }
+    }
+
+    /// Describes the delimiter for the report headers, e.g. `semicolon (detected)`.
+    pub fn describe(&self) -> String {
+        let source = match self.source {
+            DelimiterSource::Flag => "--delimiter",
+            DelimiterSource::Extension => "file extension",
+            DelimiterSource::Detected => "detected",
+            DelimiterSource::Default => "default, none detected",
+        };
+        format!("{} ({})", delimiter_name(self.symbol), source)
+    }
+}
+
+/// The delimiter named by a file's extension, if any.
+fn extension_delimiter(input_file_path: impl AsRef<Path>) -> Option<char> {
+    let name = input_file_path.as_ref().file_name()?.to_string_lossy().to_lowercase();
+    let name = COMPRESSED_SUFFIXES.iter().find_map(|suffix| name.strip_suffix(suffix)).unwrap_or(&name);
+    match name.rsplit_once('.')?.1 {
+        "tsv" | "tab" => Some('\t'),
+        "psv" => Some('|'),
+        _ => None,
+    }
+}
+
+/// Returns a readable name for a delimiter: `comma`, `tab`, `semicolon`, `pipe`, or the
+/// character in quotes.
+pub fn delimiter_name(delimiter: char) -> String {
+    match delimiter {
+        ',' => "comma".to_string(),
+        '\t' => "tab".to_string(),
+        ';' => "semicolon".to_string(),
+        '|' => "pipe".to_string(),
+        other => format!("'{}'", other),
+    }
+}
+
+/// Parses a `--delimiter` value: a delimiter name, `\t`, or a single character.
+///
+/// # Returns
+///
+/// * `Result<char, String>` - The delimiter, or an error message
+pub fn parse_delimiter(text: &str) -> Result<char, String> {
+    let symbol = match text.to_lowercase().as_str() {
+        "comma" => ',',
+        "semicolon" => ';',
+        "tab" | "\\t" => '\t',
+        "pipe" => '|',
+        _ => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(symbol), None) => symbol,
+                _ => return Err(format!(
+                    "invalid delimiter '{}' (expected comma, semicolon, tab, pipe, or a single character)", text
+                )),
+            }
+        },
+    };
+    if matches!(symbol, '"' | '\n' | '\r') {
+        return Err(format!("invalid delimiter {:?}: quotes and line breaks cannot separate fields", symbol));
+    }
+    Ok(symbol)
+}
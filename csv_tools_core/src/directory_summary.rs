@@ -0,0 +1,288 @@
+//! # Directory Summary Reports
+//!
+//! A directory run (`--directory` or `--glob`) writes the reports of every file and then
+//! one roll-up of the whole run, named after the directory:
+//!
+//! * `[directory]_directory_summary_[timestamp].csv` - One line per file (rows, unreadable
+//!   rows, characters, longest row, mean, median, and p99 length, outlier rows, and why a
+//!   file was not analyzed), then an
+//!   `(all files)` line with the totals and the statistics of every row of every file
+//! * `[directory]_directory_summary_[timestamp].md` - The same table, followed by the
+//!   statistics across all files and the files that could not be analyzed
+//!
+//! ```bash
+//! $ csv_row_analyzer --directory exports reports
+//! $ cat reports/exports_directory_summary_1767225600.csv
+//! file,status,total_rows,error_rows,total_chars,max_length,mean_length,median_length,p99_length,outlier_rows,detail,report_schema_version
+//! orders.csv,analyzed,1200,0,96000,412,80.00,78,240,14,,5
+//! users.csv,analyzed,300,2,9000,95,30.00,29,70,3,,5
+//! locked.csv,unreadable,,,,,,,,,permission denied,5
+//! (all files),aggregate,1500,2,105000,412,70.00,66,231,17,,5
+//! ```
+//!
+//! Each file's outlier rows are counted against that file's own fences, and the aggregate
+//! line adds them up. Only `analyzed` files count towards the aggregate line; the others
+//! are listed with a `detail`:
+//!
+//! * `truncated` - The reports were written, but the input appears truncated
+//! * `regressed` - The reports were written, but the file regressed from its
+//!   `--check-baseline` baseline; the detail names the regressions
+//! * `unreadable` - The file (or a subdirectory) could not be opened; the detail is the
+//!   kind of error, such as `permission denied`
+//! * `failed` - The file could be opened but not analyzed; the detail is the error
+//! * `skipped` - Left out by the selection (extension, `--all-files`, `--skip-hidden`,
+//!   `--min-size`/`--max-size`); the detail is the reason
+//!
+//! The run goes on after an unreadable or failed file, and then ends with exit status
+//! [`EXIT_FILES_FAILED`], so a scheduled job notices files it never analyzed. Skipped files
+//! do not change the exit status; truncated and regressed files end the run with their own
+//! exit statuses after that. No summary is written with `--no-reports`.
+
+/// Exit status of a directory run that could not read or analyze some of its files
+pub const EXIT_FILES_FAILED: i32 = 6;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::csv_row_analyzer::{statistics_from_length_counts, AnalysisResult, Statistics};
+use crate::report_table::{ReportTable, ReportValue, TableFormat};
+
+/// Outcome of one file of a directory run
+pub enum FileOutcome {
+    Analyzed(Box<AnalysisResult>),
+    /// Reports were written, but the input appears truncated
+    Truncated,
+    /// Reports were written, but the file regressed from its baseline, with the regressions
+    Regressed(String),
+    /// The file could not be opened, with the kind of error
+    Unreadable(String),
+    /// The file could not be analyzed, with the error
+    Failed(String),
+    /// The file was left out by the selection, with the reason
+    Skipped(String),
+}
+
+impl FileOutcome {
+    fn status(&self) -> &'static str {
+        match self {
+            FileOutcome::Analyzed(_) => "analyzed",
+            FileOutcome::Truncated => "truncated",
+            FileOutcome::Regressed(_) => "regressed",
+            FileOutcome::Unreadable(_) => "unreadable",
+            FileOutcome::Failed(_) => "failed",
+            FileOutcome::Skipped(_) => "skipped",
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            FileOutcome::Regressed(detail) | FileOutcome::Unreadable(detail) | FileOutcome::Failed(detail)
+            | FileOutcome::Skipped(detail) => Some(detail),
+            FileOutcome::Analyzed(_) | FileOutcome::Truncated => None,
+        }
+    }
+}
+
+/// Outcomes of the files of a directory run, in the order they were analyzed
+#[derive(Default)]
+pub struct DirectorySummary {
+    files: Vec<(String, FileOutcome)>,
+}
+
+/// Totals and statistics of the analyzed files
+struct Aggregate {
+    total_rows: u64,
+    error_rows: u64,
+    total_chars: usize,
+    outlier_rows: u64,
+    statistics: Statistics,
+}
+
+impl DirectorySummary {
+    /// Records the outcome of one file, named by its path relative to the directory.
+    pub fn push(&mut self, name: impl Into<String>, outcome: FileOutcome) {
+        self.files.push((name.into(), outcome));
+    }
+
+    /// Whether no file was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn count(&self, wanted: fn(&FileOutcome) -> bool) -> usize {
+        self.files.iter().filter(|(_, outcome)| wanted(outcome)).count()
+    }
+
+    /// Files whose reports were written, including the truncated and regressed ones.
+    pub fn processed_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, FileOutcome::Analyzed(_) | FileOutcome::Truncated | FileOutcome::Regressed(_)))
+    }
+
+    /// Files whose input appears truncated.
+    pub fn truncated_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, FileOutcome::Truncated))
+    }
+
+    /// Files that regressed from their baseline.
+    pub fn regressed_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, FileOutcome::Regressed(_)))
+    }
+
+    /// Files that could not be read or analyzed.
+    pub fn failed_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, FileOutcome::Unreadable(_) | FileOutcome::Failed(_)))
+    }
+
+    fn analyzed(&self) -> impl Iterator<Item = &AnalysisResult> {
+        self.files.iter().filter_map(|(_, outcome)| match outcome {
+            FileOutcome::Analyzed(result) => Some(result.as_ref()),
+            _ => None,
+        })
+    }
+
+    fn aggregate(&self) -> Aggregate {
+        let mut length_counts = Vec::new();
+        let mut aggregate = Aggregate {
+            total_rows: 0,
+            error_rows: 0,
+            total_chars: 0,
+            outlier_rows: 0,
+            statistics: statistics_from_length_counts(&[]),
+        };
+        for result in self.analyzed() {
+            aggregate.total_rows += result.total_rows;
+            aggregate.error_rows += result.error_rows;
+            aggregate.total_chars += result.total_chars;
+            aggregate.outlier_rows += result.outlier_rows;
+            length_counts.extend_from_slice(&result.length_counts);
+        }
+        aggregate.statistics = statistics_from_length_counts(&length_counts);
+        aggregate
+    }
+
+    /// Writes the CSV summary.
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut table = ReportTable::new(&[
+            "file", "status", "total_rows", "error_rows", "total_chars", "max_length", "mean_length",
+            "median_length", "p99_length", "outlier_rows", "detail",
+        ]);
+        for (name, outcome) in &self.files {
+            let mut row = vec![ReportValue::Text(name.clone()), ReportValue::Text(outcome.status().to_string())];
+            match outcome {
+                FileOutcome::Analyzed(result) => {
+                    row.extend(totals_row(result.total_rows, result.error_rows, result.total_chars, &result.statistics, result.outlier_rows));
+                },
+                _ => row.extend(std::iter::repeat_n(ReportValue::Empty, 8)),
+            }
+            row.push(outcome.detail().map_or(ReportValue::Empty, |detail| ReportValue::Text(detail.to_string())));
+            table.push(row);
+        }
+        let aggregate = self.aggregate();
+        let mut row = vec![ReportValue::Text("(all files)".to_string()), ReportValue::Text("aggregate".to_string())];
+        row.extend(totals_row(aggregate.total_rows, aggregate.error_rows, aggregate.total_chars, &aggregate.statistics, aggregate.outlier_rows));
+        row.push(ReportValue::Empty);
+        table.push(row);
+        table.write(path, TableFormat::Csv)
+    }
+
+    /// Writes the markdown summary.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Report path
+    /// * `directory` - The directory (or glob pattern) of the run, for the title
+    /// * `generated_at` - Time of the run, as shown in the other reports
+    pub fn write_markdown(&self, path: impl AsRef<Path>, directory: &str, generated_at: &str) -> io::Result<()> {
+        let mut report = BufWriter::new(File::create(path)?);
+        let analyzed = self.count(|outcome| matches!(outcome, FileOutcome::Analyzed(_)));
+        let skipped = self.count(|outcome| matches!(outcome, FileOutcome::Skipped(_)));
+
+        writeln!(report, "# Directory Summary for {}", directory)?;
+        writeln!(report, "\n{} files: {} analyzed, {} truncated, {} unreadable or failed, {} skipped",
+                 self.files.len(), analyzed, self.truncated_count(), self.failed_count(), skipped)?;
+        writeln!(report, "\n*Report generated: {}*", generated_at)?;
+
+        writeln!(report, "\n## Files")?;
+        writeln!(report, "\n| File | Status | Rows | Unreadable | Characters | Max Length | Median Length | Outlier Rows |")?;
+        writeln!(report, "|------|--------|------|------------|------------|------------|---------------|--------------|")?;
+        for (name, outcome) in &self.files {
+            match outcome {
+                FileOutcome::Analyzed(result) => writeln!(report, "| {} | analyzed | {} | {} | {} | {} | {} | {} |",
+                                                          name, result.total_rows, result.error_rows, result.total_chars,
+                                                          result.statistics.max, result.statistics.median, result.outlier_rows)?,
+                FileOutcome::Skipped(_) => {},
+                _ => writeln!(report, "| {} | {} | | | | | | |", name, outcome.status())?,
+            }
+        }
+
+        let aggregate = self.aggregate();
+        let stats = &aggregate.statistics;
+        writeln!(report, "\n## All Files")?;
+        writeln!(report, "- **Total Rows**: {} ({} unreadable)", aggregate.total_rows, aggregate.error_rows)?;
+        writeln!(report, "- **Total Characters**: {}", aggregate.total_chars)?;
+        if let Some((name, longest)) = self.files.iter()
+            .filter_map(|(name, outcome)| match outcome {
+                FileOutcome::Analyzed(result) => Some((name, result.statistics.max)),
+                _ => None,
+            })
+            .max_by_key(|&(_, max)| max)
+        {
+            writeln!(report, "- **Longest Row**: {} characters, in {}", longest, name)?;
+        }
+        writeln!(report, "- **Row Length**: min {}, Q1 {}, median {}, mean {:.2}, Q3 {}, p99 {}, max {}, std. dev. {:.2}",
+                 stats.min, stats.q1, stats.median, stats.mean, stats.q3, stats.p99, stats.max, stats.std_dev)?;
+        writeln!(report, "- **Outlier Rows**: {} (each file against its own fences)", aggregate.outlier_rows)?;
+
+        if self.failed_count() > 0 {
+            writeln!(report, "\n## Unreadable and Failed Files")?;
+            for (name, outcome) in &self.files {
+                if let FileOutcome::Unreadable(detail) | FileOutcome::Failed(detail) = outcome {
+                    writeln!(report, "- {} ({}): {}", name, outcome.status(), detail)?;
+                }
+            }
+        }
+        if self.regressed_count() > 0 {
+            writeln!(report, "\n## Regressed Files")?;
+            for (name, outcome) in &self.files {
+                if let FileOutcome::Regressed(regressions) = outcome {
+                    writeln!(report, "- {}: {}", name, regressions)?;
+                }
+            }
+        }
+        if skipped > 0 {
+            writeln!(report, "\n## Skipped Files")?;
+            for (name, outcome) in &self.files {
+                if let FileOutcome::Skipped(reason) = outcome {
+                    writeln!(report, "- {}: {}", name, reason)?;
+                }
+            }
+        }
+        report.flush()
+    }
+}
+
+/// The numeric cells of one summary line.
+fn totals_row(total_rows: u64, error_rows: u64, total_chars: usize, stats: &Statistics, outlier_rows: u64) -> Vec<ReportValue> {
+    vec![
+        ReportValue::Count(total_rows),
+        ReportValue::Count(error_rows),
+        ReportValue::Count(total_chars as u64),
+        ReportValue::Count(stats.max as u64),
+        ReportValue::Decimal(format!("{:.2}", stats.mean)),
+        ReportValue::Count(stats.median as u64),
+        ReportValue::Count(stats.p99 as u64),
+        ReportValue::Count(outlier_rows),
+    ]
+}
+
+/// Basename of the summary reports: the name of the walked directory.
+pub fn summary_basename(directory: impl AsRef<Path>) -> String {
+    let directory = directory.as_ref();
+    let name = match directory.file_name() {
+        Some(name) => Some(name.to_os_string()),
+        // `.` and `..` have no name of their own
+        None => directory.canonicalize().ok().and_then(|path| path.file_name().map(ToOwned::to_owned)),
+    };
+    name.map_or_else(|| "directory".to_string(), |name| name.to_string_lossy().into_owned())
+}
@@ -0,0 +1,205 @@
+//! # Recursive Directory Walk (`--recursive`, `--max-depth`)
+//!
+//! By default `--directory` analyzes the files directly in the directory. With
+//! `--recursive`, the files of every subdirectory are analyzed too, so a whole export tree
+//! is one run; `--max-depth <n>` stops n levels below the directory:
+//!
+//! ```bash
+//! $ csv_row_analyzer --directory lake_export reports --recursive --max-depth 3
+//! ```
+//!
+//! Files of different subdirectories often share a name (`2024/01/orders.csv`,
+//! `2024/02/orders.csv`), so the reports of a recursive run are named after the path
+//! relative to the directory, with `__` between its parts:
+//! `2024__01__orders_md_outliers_report_[timestamp].md`. Files directly in the directory
+//! keep their plain basename.
+//!
+//! Symbolic links are followed, but each directory is read once: a link back to a
+//! directory already walked (a loop, or a second path to the same directory) is listed
+//! with the skipped files instead of being read again. A subdirectory that cannot be read
+//! is reported as unreadable and the walk continues. With `--skip-hidden`, files and
+//! directories whose name starts with a dot are left out (and hidden directories are not
+//! read at all).
+//!
+//! `--glob <pattern>` walks the same way from the part of the pattern before its first
+//! wildcard and keeps the files whose path matches the rest:
+//!
+//! ```bash
+//! $ csv_row_analyzer --glob "exports/**/*_2024*.csv" reports
+//! ```
+//!
+//! `*` matches any characters within one path part, `?` one character, and a `**` part
+//! any number of directories (including none). The reports are named after the path
+//! relative to the walked directory (`exports` above), as in a recursive run.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Separator between the parts of a relative path in report basenames
+pub const PATH_SEPARATOR: &str = "__";
+
+/// Files found under a directory, and the entries that could not be walked
+#[derive(Debug, Default)]
+pub struct DirectoryWalk {
+    /// Every file found, sorted by path
+    pub files: Vec<PathBuf>,
+    /// Entries left out on purpose, with the reason
+    pub skipped: Vec<(PathBuf, String)>,
+    /// Directories and entries that could not be read, with the error
+    pub unreadable: Vec<(PathBuf, io::Error)>,
+}
+
+/// Lists the files of a directory and, up to `max_depth` levels down, of its subdirectories.
+///
+/// # Arguments
+///
+/// * `root` - Directory to walk
+/// * `max_depth` - Subdirectory levels to read: `Some(0)` for the directory alone, None for no limit
+/// * `skip_hidden` - Leave out files and directories whose name starts with a dot (`--skip-hidden`)
+///
+/// # Returns
+///
+/// * `Result<DirectoryWalk, io::Error>` - The files and the entries left out, or an Error if
+///   `root` itself cannot be read
+pub fn walk_directory(root: impl AsRef<Path>, max_depth: Option<usize>, skip_hidden: bool) -> Result<DirectoryWalk, io::Error> {
+    let root = root.as_ref();
+    let mut walk = DirectoryWalk::default();
+    let mut visited = HashSet::new();
+    visited.insert(fs::canonicalize(root)?);
+
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((directory, depth)) = pending.pop() {
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            // Only the root is required; a subdirectory that cannot be read is listed
+            Err(e) if depth == 0 => return Err(e),
+            Err(e) => {
+                walk.unreadable.push((directory, e));
+                continue;
+            },
+        };
+        for entry in entries {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    walk.unreadable.push((directory.clone(), e));
+                    continue;
+                },
+            };
+            let hidden = skip_hidden && path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if path.is_file() {
+                if hidden {
+                    walk.skipped.push((path, "hidden file".to_string()));
+                } else {
+                    walk.files.push(path);
+                }
+            } else if path.is_dir() {
+                if hidden {
+                    walk.skipped.push((path, "hidden directory".to_string()));
+                } else if max_depth.is_none_or(|max_depth| depth < max_depth) {
+                    match fs::canonicalize(&path) {
+                        Ok(canonical) if visited.insert(canonical.clone()) => pending.push((path, depth + 1)),
+                        Ok(canonical) => walk.skipped.push((path, format!("directory already walked as {}", canonical.display()))),
+                        Err(e) => walk.unreadable.push((path, e)),
+                    }
+                }
+            } else if path.is_symlink() {
+                walk.skipped.push((path, "broken symbolic link".to_string()));
+            }
+        }
+    }
+    walk.files.sort();
+    walk.skipped.sort();
+    walk.unreadable.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(walk)
+}
+
+/// Report basename of a file found under `root`: the path relative to `root` with `__`
+/// between its parts, ending in the file's own basename.
+///
+/// # Arguments
+///
+/// * `root` - The walked directory
+/// * `path` - A file found under it
+/// * `basename` - The file's basename (its name up to the first dot)
+pub fn relative_basename(root: impl AsRef<Path>, path: impl AsRef<Path>, basename: &str) -> String {
+    let directories = path.as_ref().strip_prefix(root).ok().and_then(Path::parent);
+    let mut parts: Vec<String> = directories.into_iter()
+        .flat_map(Path::components)
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    parts.push(basename.to_string());
+    parts.join(PATH_SEPARATOR)
+}
+
+/// Lists the files whose path matches a glob pattern.
+///
+/// # Arguments
+///
+/// * `pattern` - Path pattern with `*`, `?`, and `**` parts, separated by `/`
+/// * `skip_hidden` - Leave out files and directories whose name starts with a dot
+///
+/// # Returns
+///
+/// * `Result<(PathBuf, DirectoryWalk), io::Error>` - The directory walked (the pattern up to
+///   its first wildcard part) and the matching files, or an Error if that directory cannot be read
+pub fn expand_glob(pattern: &str, skip_hidden: bool) -> Result<(PathBuf, DirectoryWalk), io::Error> {
+    let root = glob_root(pattern);
+    let parts: Vec<&str> = pattern.split('/').collect();
+    let pattern_parts: Vec<&str> = parts[literal_parts(&parts)..].iter().copied().filter(|part| !part.is_empty()).collect();
+    let max_depth = if pattern_parts.contains(&"**") { None } else { Some(pattern_parts.len().saturating_sub(1)) };
+
+    let mut walk = walk_directory(&root, max_depth, skip_hidden)?;
+    let matches = |path: &Path| {
+        let relative: Vec<String> = path.strip_prefix(&root).unwrap_or(path).components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let relative: Vec<&str> = relative.iter().map(String::as_str).collect();
+        matches_parts(&pattern_parts, &relative)
+    };
+    walk.files.retain(|path| matches(path));
+    // Skipped files that the pattern would not have selected anyway are not worth listing
+    walk.skipped.retain(|(path, _)| path.is_dir() || matches(path));
+    Ok((root, walk))
+}
+
+/// Directory a glob pattern is walked from: the parts before its first wildcard part.
+pub fn glob_root(pattern: &str) -> PathBuf {
+    let parts: Vec<&str> = pattern.split('/').collect();
+    match parts[..literal_parts(&parts)].join("/") {
+        root if root.is_empty() && pattern.starts_with('/') => PathBuf::from("/"),
+        root if root.is_empty() => PathBuf::from("."),
+        root => PathBuf::from(root),
+    }
+}
+
+/// Number of leading pattern parts without a wildcard; the last part always names files.
+fn literal_parts(parts: &[&str]) -> usize {
+    parts[..parts.len() - 1].iter().take_while(|part| !has_wildcard(part)).count()
+}
+
+fn has_wildcard(part: &str) -> bool {
+    part.contains(['*', '?'])
+}
+
+/// Whether the parts of a relative path match the parts of a pattern.
+fn matches_parts(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skipped| matches_parts(rest, &path[skipped..])),
+        Some((part, rest)) => path.split_first()
+            .is_some_and(|(name, path_rest)| matches_name(&part.chars().collect::<Vec<_>>(), &name.chars().collect::<Vec<_>>()) && matches_parts(rest, path_rest)),
+    }
+}
+
+/// Whether one path part matches one pattern part (`*` and `?`).
+fn matches_name(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skipped| matches_name(rest, &name[skipped..])),
+        Some(('?', rest)) => !name.is_empty() && matches_name(rest, &name[1..]),
+        Some((character, rest)) => name.first() == Some(character) && matches_name(rest, &name[1..]),
+    }
+}
@@ -0,0 +1,277 @@
+//! # Encoding Validation (`validate-encoding` subcommand)
+//!
+//! A fast pre-check that only streams the file and confirms every byte sequence is valid
+//! in the expected encoding, without splitting rows or writing reports. It is meant to run
+//! before heavier analysis or loading, where one bad byte can fail a multi-hour load.
+//!
+//! ```bash
+//! $ csv_row_analyzer validate-encoding data.csv
+//! $ csv_row_analyzer validate-encoding data.csv --encoding windows-1252 --max-violations 50
+//! ```
+//!
+//! The first N violations are listed with their byte offset and 0-based row index. The
+//! process exits with status 0 when the file is valid and 2 when violations were found.
+//! Gzip input is decompressed first, so offsets refer to the decompressed content.
+
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::cli::{parse_command_line, VALIDATE_ENCODING_COMMAND};
+use crate::compression::open_input;
+
+/// Bytes read per chunk; large chunks keep validation close to disk speed
+const CHUNK_SIZE: usize = 1 << 20;
+/// Violations listed when `--max-violations` is not given
+const DEFAULT_MAX_VIOLATIONS: usize = 20;
+/// Exit status when the file contains violations
+pub const EXIT_INVALID: i32 = 2;
+
+/// Encodings the input can be checked against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Ascii,
+    /// Windows-1252 leaves five byte values undefined
+    Windows1252,
+}
+
+impl Encoding {
+    /// Parses an encoding name as given to `--encoding`.
+    pub fn from_name(name: &str) -> Result<Encoding, String> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Ok(Encoding::Utf8),
+            "ascii" | "us-ascii" => Ok(Encoding::Ascii),
+            "windows-1252" | "cp1252" => Ok(Encoding::Windows1252),
+            _ => Err(format!("unsupported encoding '{}' (use utf-8, ascii, or windows-1252)", name)),
+        }
+    }
+
+    /// Canonical name used in output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Ascii => "ASCII",
+            Encoding::Windows1252 => "Windows-1252",
+        }
+    }
+}
+
+/// One invalid byte sequence
+#[derive(Debug, Clone)]
+pub struct EncodingViolation {
+    /// Offset of the first invalid byte
+    pub byte_offset: u64,
+    /// 0-based row index of the line containing it
+    pub row_index: usize,
+    /// The offending bytes (up to 4)
+    pub bytes: Vec<u8>,
+}
+
+/// Result of validating one file
+#[derive(Debug, Clone, Default)]
+pub struct EncodingReport {
+    pub bytes_checked: u64,
+    /// Number of line feeds seen, plus one for a final line without terminator
+    pub rows: usize,
+    /// Total number of invalid sequences, including those not listed
+    pub violation_count: u64,
+    /// The first violations, in file order
+    pub violations: Vec<EncodingViolation>,
+    /// Whether the file starts with a UTF-8 byte order mark
+    pub has_bom: bool,
+}
+
+/// Options for the `validate-encoding` subcommand
+#[derive(Debug, Clone)]
+pub struct ValidateOptions {
+    pub input_path: String,
+    pub encoding: Encoding,
+    pub max_violations: usize,
+}
+
+/// Streams a file and collects the byte sequences that are invalid in `encoding`.
+///
+/// # Arguments
+///
+/// * `input_file_path` - File to check (gzip input is decompressed)
+/// * `encoding` - Expected encoding
+/// * `max_violations` - Number of violations to keep; all are still counted
+///
+/// # Returns
+///
+/// * `Result<EncodingReport, io::Error>` - Counts and the first violations, or a read error
+pub fn validate_encoding(
+    input_file_path: impl AsRef<Path>,
+    encoding: Encoding,
+    max_violations: usize,
+) -> Result<EncodingReport, io::Error> {
+    let (mut reader, _) = open_input(input_file_path)?;
+    let mut report = EncodingReport::default();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    // An incomplete UTF-8 sequence at the end of a chunk is carried into the next one
+    let mut carried = 0;
+    let mut chunk_start: u64 = 0;
+    let mut last_byte = None;
+
+    loop {
+        let bytes_read = reader.read(&mut buffer[carried..])?;
+        let at_end = bytes_read == 0;
+        let filled = carried + bytes_read;
+        let chunk = &buffer[..filled];
+        if chunk_start == 0 && chunk.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            report.has_bom = true;
+        }
+
+        // Row index of each violation: rows before this chunk plus newlines before the offset
+        let rows_before_chunk = report.rows;
+        let mut newline_scan = (0, rows_before_chunk);
+        let mut row_at = |offset: usize| {
+            let (scanned, rows) = &mut newline_scan;
+            *rows += chunk[*scanned..offset].iter().filter(|&&b| b == b'\n').count();
+            *scanned = offset;
+            *rows
+        };
+
+        let mut violations = Vec::new();
+        let consumed = match encoding {
+            Encoding::Utf8 => find_utf8_violations(chunk, at_end, &mut violations),
+            Encoding::Ascii => find_byte_violations(chunk, |b| b >= 0x80, &mut violations),
+            Encoding::Windows1252 => {
+                find_byte_violations(chunk, |b| matches!(b, 0x81 | 0x8D | 0x8F | 0x90 | 0x9D), &mut violations)
+            },
+        };
+        for (offset, length) in violations {
+            report.violation_count += 1;
+            if report.violations.len() < max_violations {
+                report.violations.push(EncodingViolation {
+                    byte_offset: chunk_start + offset as u64,
+                    row_index: row_at(offset),
+                    bytes: chunk[offset..offset + length].to_vec(),
+                });
+            }
+        }
+
+        report.rows = rows_before_chunk + chunk[..consumed].iter().filter(|&&b| b == b'\n').count();
+        if consumed > 0 {
+            last_byte = Some(chunk[consumed - 1]);
+        }
+        report.bytes_checked += consumed as u64;
+        chunk_start += consumed as u64;
+
+        if at_end {
+            break;
+        }
+        buffer.copy_within(consumed..filled, 0);
+        carried = filled - consumed;
+    }
+
+    if last_byte.is_some_and(|b| b != b'\n') {
+        report.rows += 1;
+    }
+    Ok(report)
+}
+
+/// Finds invalid UTF-8 sequences in a chunk, as (offset, length) pairs.
+///
+/// # Returns
+///
+/// * `usize` - Bytes fully checked; an incomplete sequence at the end of a chunk that is
+///   not the last one is left for the next chunk
+fn find_utf8_violations(chunk: &[u8], at_end: bool, violations: &mut Vec<(usize, usize)>) -> usize {
+    let mut position = 0;
+    while position < chunk.len() {
+        match std::str::from_utf8(&chunk[position..]) {
+            Ok(_) => return chunk.len(),
+            Err(error) => {
+                let invalid_at = position + error.valid_up_to();
+                match error.error_len() {
+                    Some(length) => {
+                        violations.push((invalid_at, length));
+                        position = invalid_at + length;
+                    },
+                    // Truncated sequence: wait for more input, unless the file ends here
+                    None if !at_end => return invalid_at,
+                    None => {
+                        violations.push((invalid_at, chunk.len() - invalid_at));
+                        return chunk.len();
+                    },
+                }
+            },
+        }
+    }
+    chunk.len()
+}
+
+/// Finds single bytes rejected by `is_invalid`, as (offset, 1) pairs.
+fn find_byte_violations(chunk: &[u8], is_invalid: impl Fn(u8) -> bool, violations: &mut Vec<(usize, usize)>) -> usize {
+    violations.extend(chunk.iter()
+        .enumerate()
+        .filter(|&(_, &b)| is_invalid(b))
+        .map(|(offset, _)| (offset, 1)));
+    chunk.len()
+}
+
+/// Parses the arguments that follow `validate-encoding`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<ValidateOptions, String>` - Parsed options or an error message
+pub fn parse_validate_arguments(args: &[String]) -> Result<ValidateOptions, String> {
+    let parsed = parse_command_line(&VALIDATE_ENCODING_COMMAND, args)?;
+    let mut encoding = Encoding::Utf8;
+    let mut max_violations = DEFAULT_MAX_VIOLATIONS;
+
+    for (flag, value) in &parsed.flags {
+        match *flag {
+            "--encoding" => encoding = Encoding::from_name(value)?,
+            "--max-violations" => {
+                max_violations = value.parse()
+                    .map_err(|_| format!("invalid --max-violations value '{}'", value))?;
+            },
+            _ => {},
+        }
+    }
+
+    Ok(ValidateOptions {
+        input_path: parsed.positionals.first().cloned().ok_or("Missing input file path")?,
+        encoding,
+        max_violations,
+    })
+}
+
+/// Runs the `validate-encoding` subcommand and prints its findings.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+///
+/// # Returns
+///
+/// * `Result<bool, io::Error>` - Whether the file is valid, or a read error
+pub fn run_validate_encoding(options: &ValidateOptions) -> Result<bool, io::Error> {
+    let report = validate_encoding(&options.input_path, options.encoding, options.max_violations)?;
+
+    println!("Checked {} bytes in {} rows of {} as {}",
+             report.bytes_checked, report.rows, options.input_path, options.encoding.name());
+    if report.has_bom {
+        println!("Note: the file starts with a UTF-8 byte order mark (EF BB BF)");
+    }
+
+    if report.violation_count == 0 {
+        println!("Valid {}", options.encoding.name());
+        return Ok(true);
+    }
+
+    println!("Found {} invalid byte sequence(s); showing the first {}:",
+             report.violation_count, report.violations.len());
+    println!("{:<15} {:<12} {:<15}", "Byte Offset", "Row Index", "Bytes");
+    for violation in &report.violations {
+        let hex = violation.bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ");
+        println!("{:<15} {:<12} {:<15}", violation.byte_offset, violation.row_index, hex);
+    }
+    Ok(false)
+}
@@ -0,0 +1,180 @@
+//! # Row Entropy (`--entropy`)
+//!
+//! Computes the Shannon entropy of each row's bytes, in bits per byte (0 to 8), and flags
+//! rows whose entropy is far from the rest of the file. This catches garbage rows that
+//! have an unremarkable length:
+//!
+//! * High entropy - compressed, encrypted, or base64 blobs pasted into a field
+//!   (base64 approaches 6 bits per byte, random bytes approach 8)
+//! * Low entropy - padding and filler such as long runs of `0`, spaces, or `,,,,`
+//!
+//! Ordinary delimited text usually sits between 4 and 5 bits per byte. A row is flagged
+//! when its entropy is outside `Q1 - 3 × IQR` or `Q3 + 3 × IQR` of the file's own rows
+//! (Tukey's "far out" fences), with the fences at least 0.5 bits from the quartiles so a
+//! very uniform file does not flag every small deviation.
+//!
+//! Rows shorter than [`MIN_ENTROPY_ROW_BYTES`] are not measured: a short row cannot
+//! reach a high entropy (at most log2 of its length), so its value says little.
+
+/// Rows with fewer bytes than this are not measured
+pub const MIN_ENTROPY_ROW_BYTES: usize = 32;
+/// Fence distance from the quartiles, in interquartile ranges
+const FENCE_IQR_FACTOR: f64 = 3.0;
+/// Smallest fence distance from the quartiles, in bits per byte
+const MIN_FENCE_DISTANCE: f64 = 0.5;
+/// Example row indices kept per flag for the summary
+const MAX_EXAMPLE_ROWS: usize = 5;
+
+/// Why a row's entropy was flagged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntropyFlag {
+    /// Above the upper fence (blob-like content)
+    High,
+    /// Below the lower fence (padding-like content)
+    Low,
+}
+
+impl EntropyFlag {
+    /// Short name used in the CSV report.
+    pub fn code(&self) -> &'static str {
+        match self {
+            EntropyFlag::High => "high",
+            EntropyFlag::Low => "low",
+        }
+    }
+}
+
+/// Entropy of one measured row
+#[derive(Debug, Clone, Copy)]
+pub struct RowEntropy {
+    pub row_index: usize,
+    pub byte_length: usize,
+    /// Shannon entropy in bits per byte
+    pub entropy: f32,
+}
+
+/// Distribution of row entropies and the rows outside the fences
+#[derive(Debug, Clone, Default)]
+pub struct EntropySummary {
+    /// Rows measured
+    pub measured_rows: u64,
+    /// Rows too short to measure
+    pub short_rows: u64,
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub low_fence: f64,
+    pub high_fence: f64,
+    pub high_rows: u64,
+    pub low_rows: u64,
+    /// First row indices flagged high and low
+    pub high_examples: Vec<usize>,
+    pub low_examples: Vec<usize>,
+}
+
+impl EntropySummary {
+    /// Flags an entropy value against the fences.
+    pub fn classify(&self, entropy: f32) -> Option<EntropyFlag> {
+        let entropy = entropy as f64;
+        if entropy > self.high_fence {
+            Some(EntropyFlag::High)
+        } else if entropy < self.low_fence {
+            Some(EntropyFlag::Low)
+        } else {
+            None
+        }
+    }
+}
+
+/// Per-row entropies gathered during a pass
+#[derive(Debug, Clone, Default)]
+pub struct EntropyProfile {
+    rows: Vec<RowEntropy>,
+    short_rows: u64,
+}
+
+impl EntropyProfile {
+    /// Measures one row (without its terminator).
+    pub fn observe(&mut self, row_index: usize, bytes: &[u8]) {
+        if bytes.len() < MIN_ENTROPY_ROW_BYTES {
+            self.short_rows += 1;
+            return;
+        }
+        self.rows.push(RowEntropy {
+            row_index,
+            byte_length: bytes.len(),
+            entropy: shannon_entropy(bytes) as f32,
+        });
+    }
+
+    /// The measured rows, in file order.
+    pub fn rows(&self) -> &[RowEntropy] {
+        &self.rows
+    }
+
+    /// Computes the distribution, fences, and flagged rows.
+    pub fn summarize(&self) -> EntropySummary {
+        let mut summary = EntropySummary { short_rows: self.short_rows, ..EntropySummary::default() };
+        if self.rows.is_empty() {
+            return summary;
+        }
+
+        let mut sorted: Vec<f64> = self.rows.iter().map(|row| row.entropy as f64).collect();
+        sorted.sort_by(f64::total_cmp);
+        let len = sorted.len();
+        summary.measured_rows = len as u64;
+        summary.min = sorted[0];
+        summary.max = sorted[len - 1];
+        summary.mean = sorted.iter().sum::<f64>() / len as f64;
+        summary.median = quantile(&sorted, 0.5);
+        summary.q1 = quantile(&sorted, 0.25);
+        summary.q3 = quantile(&sorted, 0.75);
+
+        let distance = (FENCE_IQR_FACTOR * (summary.q3 - summary.q1)).max(MIN_FENCE_DISTANCE);
+        summary.low_fence = summary.q1 - distance;
+        summary.high_fence = summary.q3 + distance;
+
+        for row in &self.rows {
+            let (count, examples) = match summary.classify(row.entropy) {
+                Some(EntropyFlag::High) => (&mut summary.high_rows, &mut summary.high_examples),
+                Some(EntropyFlag::Low) => (&mut summary.low_rows, &mut summary.low_examples),
+                None => continue,
+            };
+            *count += 1;
+            if examples.len() < MAX_EXAMPLE_ROWS {
+                examples.push(row.row_index);
+            }
+        }
+        summary
+    }
+}
+
+/// Shannon entropy of a byte string, in bits per byte.
+pub fn shannon_entropy(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0u64; 256];
+    for &byte in bytes {
+        counts[byte as usize] += 1;
+    }
+    let total = bytes.len() as f64;
+    counts.iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Linear-interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
@@ -0,0 +1,139 @@
+//! # Field Counts (`--field-counts`)
+//!
+//! Counts the delimiter-separated fields of every record, quote-aware, and compares each
+//! count with the header's column count. A ragged row (a dropped or doubled delimiter, a
+//! stray quote, a truncated write) is the most common CSV corruption, and it often has an
+//! unremarkable length:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --field-counts
+//! $ awk -F, '$5 == "false"' reports/orders_field_counts_report_1767225600.csv
+//! ```
+//!
+//! `[basename]_field_counts_report_[timestamp].csv` lists every data record with its
+//! `field_count` and whether it `matches_header`; the outlier reports summarize the
+//! counts and name the first rows of each count that differs from the header's.
+//!
+//! A record that spans several lines (a quoted field with line breaks) is counted once,
+//! at the row it starts on. Unreadable lines are not counted and leave the quoting as it
+//! was, as in the column profile.
+
+use crate::fields::{scan_quotes, split_fields, MAX_RECORD_LINES};
+
+/// Example row indices kept per differing field count for the summary
+const MAX_EXAMPLE_ROWS: usize = 5;
+
+/// A record whose quoted field is still open
+#[derive(Debug, Clone, Copy)]
+struct OpenRecord {
+    row_index: usize,
+    fields: usize,
+    lines: usize,
+}
+
+/// Field counts of the records of one pass
+#[derive(Debug, Clone)]
+pub struct FieldCounter {
+    delimiter: char,
+    open_record: Option<OpenRecord>,
+    header_fields: Option<usize>,
+    /// (row index, field count) of every data record, in file order
+    records: Vec<(usize, usize)>,
+}
+
+impl FieldCounter {
+    /// Creates an empty counter for the given field delimiter.
+    pub fn new(delimiter: char) -> Self {
+        FieldCounter { delimiter, open_record: None, header_fields: None, records: Vec::new() }
+    }
+
+    /// Adds one readable line; row 0 is the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_index` - 0-based row index of the line
+    /// * `line` - Line content without its terminator
+    pub fn observe_line(&mut self, row_index: usize, line: &str) {
+        let continues_record = self.open_record.is_some();
+        let (delimiters, in_quotes) = scan_quotes(line, self.delimiter, continues_record);
+        let mut record = self.open_record.take().unwrap_or(OpenRecord { row_index, fields: 1, lines: 0 });
+        record.fields += delimiters;
+        record.lines += 1;
+        if in_quotes && record.lines < MAX_RECORD_LINES {
+            self.open_record = Some(record);
+            return;
+        }
+        self.complete(record);
+    }
+
+    /// Uses a separately read header when the analyzed rows did not include row 0.
+    pub fn set_header_if_missing(&mut self, header_record: &str) {
+        if self.header_fields.is_none() {
+            self.header_fields = Some(split_fields(header_record, self.delimiter).len());
+        }
+    }
+
+    /// Completes the pass, counting a record left open at the end of the input.
+    pub fn finish(&mut self) {
+        if let Some(record) = self.open_record.take() {
+            self.complete(record);
+        }
+    }
+
+    fn complete(&mut self, record: OpenRecord) {
+        if record.row_index == 0 {
+            self.header_fields = Some(record.fields);
+        } else {
+            self.records.push((record.row_index, record.fields));
+        }
+    }
+
+    /// Number of columns of the header, if one was read.
+    pub fn header_fields(&self) -> Option<usize> {
+        self.header_fields
+    }
+
+    /// (row index, field count) of every data record, in file order.
+    pub fn records(&self) -> &[(usize, usize)] {
+        &self.records
+    }
+
+    /// Whether a field count matches the header's (true without a header).
+    pub fn matches_header(&self, fields: usize) -> bool {
+        self.header_fields.is_none_or(|header_fields| header_fields == fields)
+    }
+
+    /// Counts the records per field count.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<FieldCountRows>` - One entry per field count, the header's first and then by
+    ///   field count, each with the first row indices of that count
+    pub fn summarize(&self) -> Vec<FieldCountRows> {
+        let mut counts: Vec<FieldCountRows> = Vec::new();
+        for &(row_index, fields) in &self.records {
+            let entry = match counts.iter_mut().position(|entry| entry.fields == fields) {
+                Some(position) => &mut counts[position],
+                None => {
+                    counts.push(FieldCountRows { fields, rows: 0, examples: Vec::new() });
+                    counts.last_mut().expect("just pushed")
+                },
+            };
+            entry.rows += 1;
+            if entry.examples.len() < MAX_EXAMPLE_ROWS {
+                entry.examples.push(row_index);
+            }
+        }
+        counts.sort_by_key(|entry| (!self.matches_header(entry.fields), entry.fields));
+        counts
+    }
+}
+
+/// Records with one field count
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldCountRows {
+    pub fields: usize,
+    pub rows: u64,
+    /// Row indices of the first records with this count
+    pub examples: Vec<usize>,
+}
@@ -0,0 +1,252 @@
+//! # CSV Field Splitting
+//!
+//! Quote-aware splitting of a CSV line into fields, following RFC 4180 rules:
+//! fields may be wrapped in double quotes, delimiters inside quotes are literal,
+//! and a doubled quote (`""`) inside a quoted field is an escaped quote.
+//!
+//! A quoted field may also contain line breaks, so one logical record can span
+//! several physical lines; [`ends_in_quotes`] tells whether a record continues, and
+//! [`RecordJoiner`] joins the lines of a single record such as the header.
+
+/// A record still open after this many physical lines is treated as malformed quoting
+/// and closed, so one stray quote cannot pull the rest of the file into memory
+pub const MAX_RECORD_LINES: usize = 10_000;
+
+/// Splits a single CSV line into its fields.
+///
+/// Surrounding quotes are removed and escaped quotes are unescaped. Malformed
+/// quoting (e.g., an unterminated quote) is handled leniently: the rest of the
+/// line is treated as part of the current field.
+///
+/// # Arguments
+///
+/// * `line` - The line to split, without its line terminator
+/// * `delimiter` - Field delimiter character (usually `,`)
+///
+/// # Returns
+///
+/// * `Vec<String>` - The field values in column order
+pub fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    split_fields_into(line, delimiter, &mut fields);
+    fields
+}
+
+/// Splits a single CSV line into `fields`, reusing its strings.
+///
+/// Same rules as [`split_fields`]. Profiling a wide file splits every record into as many
+/// fields as it has columns, so reusing the buffers of the previous record saves an
+/// allocation per field.
+///
+/// # Arguments
+///
+/// * `line` - The line to split, without its line terminator
+/// * `delimiter` - Field delimiter character (usually `,`)
+/// * `fields` - Receives the field values in column order; its previous content is replaced
+pub fn split_fields_into(line: &str, delimiter: char, fields: &mut Vec<String>) {
+    let mut count = 0;
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    let mut current = next_field(fields, count);
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    // Escaped quote inside a quoted field
+                    fields[current].push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                fields[current].push(c);
+            }
+        } else if c == '"' && fields[current].is_empty() {
+            in_quotes = true;
+        } else if c == delimiter {
+            count += 1;
+            current = next_field(fields, count);
+        } else {
+            fields[current].push(c);
+        }
+    }
+
+    fields.truncate(count + 1);
+}
+
+/// Clears the field at `index` for reuse, adding it when the buffer is shorter.
+fn next_field(fields: &mut Vec<String>, index: usize) -> usize {
+    match fields.get_mut(index) {
+        Some(field) => field.clear(),
+        None => fields.push(String::new()),
+    }
+    index
+}
+
+/// Reports whether a physical line ends inside a quoted field, i.e. whether the
+/// logical record continues on the next line.
+///
+/// Uses the same quoting rules as [`split_fields`], so both agree on where records end.
+///
+/// # Arguments
+///
+/// * `line` - The line to scan, without its line terminator
+/// * `delimiter` - Field delimiter character (usually `,`)
+/// * `starts_in_quotes` - Whether the previous line ended inside a quoted field
+///
+/// # Returns
+///
+/// * `bool` - true if a quoted field is still open at the end of the line
+pub fn ends_in_quotes(line: &str, delimiter: char, starts_in_quotes: bool) -> bool {
+    scan_quotes(line, delimiter, starts_in_quotes).1
+}
+
+/// Joins the physical lines of one logical record, for readers that need a single
+/// record (the header) without profiling every column.
+#[derive(Debug, Clone, Default)]
+pub struct RecordJoiner {
+    record: String,
+    lines: usize,
+    in_quotes: bool,
+}
+
+impl RecordJoiner {
+    /// Adds the next physical line and reports whether the record is complete.
+    ///
+    /// The record ends at a line that closes every quoted field, at the last line of the
+    /// input (no terminator), or after [`MAX_RECORD_LINES`] lines. The terminators inside
+    /// the record are kept, as they are part of a quoted value.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to add, without its line terminator
+    /// * `terminator` - The stripped terminator, `""` for a last line without one
+    /// * `delimiter` - Field delimiter character (usually `,`)
+    pub fn push_line(&mut self, line: &str, terminator: &str, delimiter: char) -> bool {
+        self.in_quotes = ends_in_quotes(line, delimiter, self.in_quotes);
+        self.record.push_str(line);
+        self.lines += 1;
+        if self.in_quotes && !terminator.is_empty() && self.lines < MAX_RECORD_LINES {
+            self.record.push_str(terminator);
+            return false;
+        }
+        true
+    }
+
+    /// Whether no line has been added yet.
+    pub fn is_empty(&self) -> bool {
+        self.lines == 0
+    }
+
+    /// Returns the joined record, complete or not.
+    pub fn into_record(self) -> String {
+        self.record
+    }
+}
+
+/// Counts the delimiters outside quotes in a physical line and reports whether it ends
+/// inside a quoted field, with the same quoting rules as [`ends_in_quotes`].
+///
+/// # Arguments
+///
+/// * `line` - The line to scan, without its line terminator
+/// * `delimiter` - Field delimiter character (usually `,`)
+/// * `starts_in_quotes` - Whether the previous line ended inside a quoted field
+///
+/// # Returns
+///
+/// * `(usize, bool)` - Number of field-separating delimiters, and whether a quoted field
+///   is still open at the end of the line
+pub fn scan_quotes(line: &str, delimiter: char, starts_in_quotes: bool) -> (usize, bool) {
+    let mut in_quotes = starts_in_quotes;
+    // A continuation line starts in the middle of a field, not at its start
+    let mut at_field_start = !starts_in_quotes;
+    let mut delimiters = 0;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+        } else if c == '"' && at_field_start {
+            in_quotes = true;
+            at_field_start = false;
+        } else {
+            at_field_start = c == delimiter;
+            if at_field_start {
+                delimiters += 1;
+            }
+        }
+    }
+
+    (delimiters, in_quotes)
+}
+
+/// Measures each field of a record as written, quotes included, with the same quoting
+/// rules as [`scan_quotes`]. The delimiters themselves are not part of any field.
+///
+/// # Arguments
+///
+/// * `record` - The record to measure, without its final line terminator
+/// * `delimiter` - Field delimiter character (usually `,`)
+///
+/// # Returns
+///
+/// * `Vec<(usize, usize)>` - Characters and UTF-8 bytes of each field, in column order
+pub fn field_widths(record: &str, delimiter: char) -> Vec<(usize, usize)> {
+    let mut widths = Vec::new();
+    let mut current = (0, 0);
+    let mut in_quotes = false;
+    let mut at_field_start = true;
+    let mut chars = record.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if !in_quotes && c == delimiter {
+            widths.push(std::mem::take(&mut current));
+            at_field_start = true;
+            continue;
+        }
+        current.0 += 1;
+        current.1 += c.len_utf8();
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    current.0 += 1;
+                    current.1 += 1;
+                } else {
+                    in_quotes = false;
+                }
+            }
+        } else if c == '"' && at_field_start {
+            in_quotes = true;
+        }
+        at_field_start = false;
+    }
+
+    widths.push(current);
+    widths
+}
+
+/// Quotes a value for CSV output if it contains a delimiter, quote, or newline.
+///
+/// # Arguments
+///
+/// * `value` - Raw field value
+///
+/// # Returns
+///
+/// * `String` - The value, quoted and with quotes doubled when needed
+pub fn escape_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,237 @@
+//! # Directory File Selection (`--extensions`, `--all-files`)
+//!
+//! Decides which files of a `--directory` run are analyzed:
+//!
+//! * By default, files ending in `.csv` (or `.csv.gz`, `.csv.zst`), as before
+//! * `--extensions csv,tsv,txt,dat` - files ending in any of the listed extensions,
+//!   with or without a trailing `.gz` or `.zst`
+//! * `--all-files` - every file whose content looks delimited, whatever its name
+//!
+//! Content detection reads the first [`SNIFF_BYTES`] (decompressed for gzip and zstd input) and
+//! accepts the file when it has no NUL bytes and most records carry the same non-zero
+//! number of one delimiter (`,`, tab, `;` or `|`, outside quotes). The file's last record
+//! is not counted, as a truncated file ends in a short one. Files that are not
+//! analyzed are listed at the end of the run, so nothing is skipped silently; a `.tsv`,
+//! `.tab`, `.psv`, or `.txt` file left out by extension names the `--extensions` entry
+//! that would select it.
+//!
+//! Every selected file is split at its own delimiter (see the `delimiter` module), so
+//! `--extensions csv,tsv` analyzes tab-separated exports next to the CSV files.
+//!
+//! `--min-size` and `--max-size` (byte sizes such as `1K` or `2G`) also leave out files
+//! outside a size range, e.g. empty placeholder files or dumps too large for the run, and
+//! `--skip-hidden` leaves out the files and directories whose name starts with a dot.
+//! They apply to `--glob` runs as well.
+
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::compression::open_input;
+
+/// Suffixes of compressed files, selected by the extension before them
+pub const COMPRESSED_SUFFIXES: [&str; 2] = [".gz", ".zst"];
+/// Bytes read from the start of a file to detect delimited content
+pub const SNIFF_BYTES: usize = 64 * 1024;
+/// Records examined for a consistent delimiter count
+const SNIFF_RECORDS: usize = 50;
+/// Share of the examined records that must have the most common delimiter count
+const MIN_CONSISTENT_SHARE: f64 = 0.8;
+/// Delimiters tried by content detection, in order of preference on ties
+const CANDIDATE_DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+/// Extensions of delimited exports whose files get a hint when they are not selected
+const DELIMITED_EXTENSIONS: [&str; 5] = ["csv", "tsv", "tab", "psv", "txt"];
+
+/// Which files of a directory are analyzed
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileSelection {
+    /// Files with one of these extensions (lowercase, without the dot)
+    Extensions(Vec<String>),
+    /// Every file whose content looks delimited
+    AllFiles,
+}
+
+impl Default for FileSelection {
+    fn default() -> Self {
+        FileSelection::Extensions(vec!["csv".to_string()])
+    }
+}
+
+impl FileSelection {
+    /// Whether a file name is selected by extension (always true for `--all-files`,
+    /// whose files are checked by content instead).
+    pub fn matches_name(&self, file_name: &str) -> bool {
+        match self {
+            FileSelection::AllFiles => true,
+            FileSelection::Extensions(extensions) => {
+                let lower = file_name.to_lowercase();
+                let name = COMPRESSED_SUFFIXES.iter().find_map(|suffix| lower.strip_suffix(suffix)).unwrap_or(&lower);
+                extensions.iter().any(|extension| {
+                    name.strip_suffix(extension.as_str()).is_some_and(|stem| stem.len() > 1 && stem.ends_with('.'))
+                })
+            },
+        }
+    }
+
+    /// Why a file is left out by its name, or None when it is selected.
+    pub fn reason_to_skip(&self, file_name: &str) -> Option<String> {
+        if self.matches_name(file_name) {
+            return None;
+        }
+        let lower = file_name.to_lowercase();
+        let name = COMPRESSED_SUFFIXES.iter().find_map(|suffix| lower.strip_suffix(suffix)).unwrap_or(&lower);
+        Some(match name.rsplit_once('.').map(|(_, extension)| extension) {
+            Some(extension) if DELIMITED_EXTENSIONS.contains(&extension) => {
+                format!("extension not selected (add {} to --extensions)", extension)
+            },
+            _ => "extension not selected".to_string(),
+        })
+    }
+
+    /// Returns the selection as shown in progress messages.
+    pub fn describe(&self) -> String {
+        match self {
+            FileSelection::AllFiles => "all files with delimited content".to_string(),
+            FileSelection::Extensions(extensions) => extensions.iter()
+                .map(|extension| format!("*.{}", extension))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+/// Size range of the files a directory run analyzes (`--min-size`, `--max-size`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SizeLimits {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl SizeLimits {
+    /// Why a file of `size` bytes is left out, or None when its size is selected.
+    pub fn reason_to_skip(&self, size: u64) -> Option<String> {
+        match (self.min, self.max) {
+            (Some(min), _) if size < min => Some(format!("smaller than --min-size ({} of {} bytes)", size, min)),
+            (_, Some(max)) if size > max => Some(format!("larger than --max-size ({} of {} bytes)", size, max)),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a comma-separated extension list such as `csv,tsv,.txt,dat`.
+///
+/// # Arguments
+///
+/// * `text` - Extensions, with or without a leading dot
+///
+/// # Returns
+///
+/// * `Result<Vec<String>, String>` - Distinct lowercase extensions, or an error message
+pub fn parse_extensions(text: &str) -> Result<Vec<String>, String> {
+    let mut extensions: Vec<String> = Vec::new();
+    for extension in text.split(',') {
+        let extension = extension.trim().trim_start_matches('.').to_lowercase();
+        if extension.is_empty() {
+            return Err(format!("invalid extension list '{}': empty extension", text));
+        }
+        if !extensions.contains(&extension) {
+            extensions.push(extension);
+        }
+    }
+    Ok(extensions)
+}
+
+/// Checks whether the start of a file looks like delimited text.
+///
+/// # Arguments
+///
+/// * `path` - File to check (gzip and zstd input is decompressed)
+///
+/// # Returns
+///
+/// * `Result<Result<char, String>, io::Error>` - The detected delimiter, or the reason the
+///   content does not look delimited; an Error if the file cannot be read
+pub fn detect_delimited_content(path: impl AsRef<Path>) -> Result<Result<char, String>, io::Error> {
+    let (reader, _) = open_input(path)?;
+    let mut sample = Vec::with_capacity(SNIFF_BYTES);
+    reader.take(SNIFF_BYTES as u64).read_to_end(&mut sample)?;
+    Ok(detect_delimiter(&sample))
+}
+
+/// Finds the delimiter of a content sample, or explains why there is none.
+fn detect_delimiter(sample: &[u8]) -> Result<char, String> {
+    if sample.is_empty() {
+        return Err("empty file".to_string());
+    }
+    if sample.contains(&0) {
+        return Err("binary content (NUL bytes)".to_string());
+    }
+    let text = String::from_utf8_lossy(sample);
+
+    let mut best: Option<(char, usize)> = None;
+    for delimiter in CANDIDATE_DELIMITERS {
+        let counts = delimiter_counts(&text, delimiter, sample.len() == SNIFF_BYTES);
+        if counts.is_empty() {
+            continue;
+        }
+        // Most common non-zero count and how many records have it
+        let mut tally: Vec<(usize, usize)> = Vec::new();
+        for &count in counts.iter().filter(|&&count| count > 0) {
+            match tally.iter_mut().find(|(value, _)| *value == count) {
+                Some((_, records)) => *records += 1,
+                None => tally.push((count, 1)),
+            }
+        }
+        let Some(&(_, records)) = tally.iter().max_by_key(|(_, records)| *records) else {
+            continue;
+        };
+        if records as f64 >= MIN_CONSISTENT_SHARE * counts.len() as f64
+            && best.is_none_or(|(_, best_records)| records > best_records) {
+            best = Some((delimiter, records));
+        }
+    }
+    best.map(|(delimiter, _)| delimiter)
+        .ok_or_else(|| "no consistent delimiter in the first records".to_string())
+}
+
+/// Counts a delimiter outside quotes in each record of the sample.
+///
+/// The last record is dropped when the sample was cut off, since it may be incomplete, and
+/// so is the file's last record when the sample holds the whole file and more than one
+/// record: a truncated file ends in a short record, which must not outvote the others in
+/// a file of a few rows.
+fn delimiter_counts(text: &str, delimiter: char, truncated: bool) -> Vec<usize> {
+    let mut counts = Vec::new();
+    let mut count = 0;
+    let mut in_quotes = false;
+    let mut record_has_content = false;
+    for c in text.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '\n' if !in_quotes => {
+                if record_has_content {
+                    counts.push(count);
+                }
+                count = 0;
+                record_has_content = false;
+                if counts.len() == SNIFF_RECORDS {
+                    return counts;
+                }
+                continue;
+            },
+            c if c == delimiter && !in_quotes => count += 1,
+            _ => {},
+        }
+        if c != '\r' {
+            record_has_content = true;
+        }
+    }
+    if !truncated {
+        if record_has_content {
+            counts.push(count);
+        }
+        if counts.len() > 1 {
+            counts.pop();
+        }
+    }
+    counts
+}
@@ -0,0 +1,176 @@
+//! # Concurrent Files in Directory Runs (`--threads`)
+//!
+//! A directory or glob run analyzes several files at once on a pool of worker threads:
+//!
+//! ```bash
+//! $ csv_row_analyzer --directory exports reports --threads 4
+//! ```
+//!
+//! `--threads <n>` bounds the files analyzed at the same time. Without it, a run analyzes
+//! one file at a time, or as many files as the machine has cores with `--parallel`.
+//!
+//! The workers take the next file from the list as they finish one, so a large file does
+//! not hold up the small ones queued behind it. The outcomes are collected on the calling
+//! thread and handed on in file order. What a file prints to standard output while it is
+//! analyzed (its progress line, report status, `--porcelain` line, or summary) goes through
+//! [`print_file_output`], which holds it on a worker thread until the file's turn, so
+//! standard output and the directory summary read the same whatever the number of threads
+//! and whichever file finished first. Warnings on standard error (a truncated input, an
+//! unreadable row) still appear as they happen.
+//!
+//! Each file writes its own reports. The outputs shared by every file of the run, the
+//! `--history` file and the `--db-sink` tables, are written under [`SHARED_OUTPUT_LOCK`],
+//! one file at a time, in the order the files finish.
+//!
+//! Report files are opened through a pool of `--threads` writers ([`take_writer`]): a
+//! worker waits for a free writer before opening a report and frees it when the report is
+//! closed. With the worker's input, a run keeps at most twice `--threads` files open
+//! besides the standard streams, plus one `--flag-stream` file per worker when streaming
+//! flags.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex, PoisonError};
+use std::thread;
+
+/// Held while writing to an output shared by the files of a run (the history file, the
+/// database sink), so concurrent files do not interleave their writes
+pub static SHARED_OUTPUT_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Standard output of the file a worker thread is analyzing, held for the file's turn
+    static HELD_OUTPUT: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Report writers of the run a worker thread belongs to
+    static WRITER_POOL: RefCell<Option<Arc<WriterPool>>> = const { RefCell::new(None) };
+}
+
+/// Report files the workers of one run may hold open at once
+#[derive(Debug)]
+struct WriterPool {
+    /// Writers not in use
+    free: Mutex<usize>,
+    /// Signalled when a writer is freed
+    freed: Condvar,
+}
+
+/// A writer taken from the pool of the current run, freed when dropped.
+///
+/// Outside a run on worker threads (one file at a time, or the library and async entry
+/// points) there is no pool and holding a writer bounds nothing.
+#[derive(Debug)]
+pub struct WriterPermit {
+    pool: Option<Arc<WriterPool>>,
+}
+
+impl Drop for WriterPermit {
+    fn drop(&mut self) {
+        if let Some(pool) = &self.pool {
+            *pool.free.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+            pool.freed.notify_one();
+        }
+    }
+}
+
+/// Takes a writer for one report file, waiting until another worker frees one.
+///
+/// Hold the writer while the report is open. A worker must not hold two at once: with
+/// every worker waiting for a second writer, none would be freed.
+pub fn take_writer() -> WriterPermit {
+    let pool = WRITER_POOL.with_borrow(Option::clone);
+    if let Some(pool) = &pool {
+        let mut free = pool.free.lock().unwrap_or_else(PoisonError::into_inner);
+        while *free == 0 {
+            free = pool.freed.wait(free).unwrap_or_else(PoisonError::into_inner);
+        }
+        *free -= 1;
+    }
+    WriterPermit { pool }
+}
+
+/// Prints part of the standard output of the file being analyzed: at once, or on a worker
+/// thread, when the outcome of the file is handed on.
+pub fn print_file_output(text: &str) {
+    let held = HELD_OUTPUT.with_borrow_mut(|held| match held {
+        Some(held) => {
+            held.push_str(text);
+            true
+        },
+        None => false,
+    });
+    if !held {
+        print!("{}", text);
+    }
+}
+
+/// Prints one line of the standard output of the file being analyzed (see [`print_file_output`]).
+pub fn print_file_line(line: impl Display) {
+    print_file_output(&format!("{}\n", line));
+}
+
+/// Number of files analyzed at once with `--parallel` and no `--threads`: the cores of
+/// the machine, or 1 if they cannot be counted.
+pub fn default_file_threads() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// Runs `work` on every job on up to `threads` worker threads and passes each outcome to
+/// `done` in job order, after printing what the job printed with [`print_file_output`].
+///
+/// # Arguments
+///
+/// * `jobs` - The jobs, in the order their outcomes are handed on
+/// * `threads` - Most jobs run at once; 1 runs them one by one on the calling thread
+/// * `work` - Runs one job, on a worker thread
+/// * `done` - Called on the calling thread with each job and its outcome, in job order
+pub fn run_in_order<J, R>(jobs: &[J], threads: usize, work: impl Fn(&J) -> R + Sync, mut done: impl FnMut(&J, R))
+where
+    J: Sync,
+    R: Send,
+{
+    if threads <= 1 || jobs.len() <= 1 {
+        for job in jobs {
+            done(job, work(job));
+        }
+        return;
+    }
+
+    let next_job = AtomicUsize::new(0);
+    let workers = threads.min(jobs.len());
+    let pool = Arc::new(WriterPool { free: Mutex::new(workers), freed: Condvar::new() });
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (next_job, work) = (&next_job, &work);
+            let pool = Arc::clone(&pool);
+            scope.spawn(move || {
+                WRITER_POOL.set(Some(pool));
+                loop {
+                    let index = next_job.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = jobs.get(index) else { break };
+                    HELD_OUTPUT.set(Some(String::new()));
+                    let outcome = work(job);
+                    let output = HELD_OUTPUT.take().unwrap_or_default();
+                    if sender.send((index, (output, outcome))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Outcomes arrive as files finish; hold each until the ones before it are handed on
+        let mut pending = BTreeMap::new();
+        let mut next_done = 0;
+        for (index, outcome) in receiver {
+            pending.insert(index, outcome);
+            while let Some((output, outcome)) = pending.remove(&next_done) {
+                print!("{}", output);
+                done(&jobs[next_done], outcome);
+                next_done += 1;
+            }
+        }
+    });
+}
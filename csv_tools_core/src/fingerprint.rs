@@ -0,0 +1,58 @@
+//! # Header Fingerprinting
+//!
+//! Computes a stable fingerprint of a CSV header (column names and their order) so
+//! schema changes between runs can be detected by comparing a single hash field.
+//!
+//! The hash is the run's row hash (see the `row_hash` module), 64-bit FNV-1a by default.
+//! Every algorithm is fixed by specification and therefore stable across platforms,
+//! Rust versions, and program runs (unlike `DefaultHasher`).
+
+use crate::fields::split_fields;
+use crate::row_hash::RowHasher;
+
+/// Fingerprint of a header row
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderFingerprint {
+    /// Hash over the column names in order, as hex (see [`RowHasher::hash_hex`])
+    pub digest: String,
+    /// Number of columns in the header
+    pub column_count: usize,
+}
+
+impl HeaderFingerprint {
+    /// Returns the hash as a lowercase hex string.
+    pub fn hex(&self) -> &str {
+        &self.digest
+    }
+}
+
+/// Fingerprints a header line.
+///
+/// Column names are split quote-aware, so `"last, first",id` is two columns.
+/// Each name is hashed followed by a 0x1F (unit separator) byte so that
+/// `["ab", "c"]` and `["a", "bc"]` produce different fingerprints.
+///
+/// # Arguments
+///
+/// * `header_line` - The header record without its final line terminator; a quoted
+///   column name may hold the line breaks of a header that spans several lines
+/// * `delimiter` - Field delimiter of the file
+/// * `hasher` - The run's row hasher
+///
+/// # Returns
+///
+/// * `HeaderFingerprint` - Hash and column count of the header
+pub fn fingerprint_header(header_line: &str, delimiter: char, hasher: &RowHasher) -> HeaderFingerprint {
+    let columns = split_fields(header_line, delimiter);
+
+    let mut bytes = Vec::with_capacity(header_line.len() + columns.len());
+    for name in &columns {
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.push(0x1F);
+    }
+
+    HeaderFingerprint {
+        digest: hasher.hash_hex(&bytes),
+        column_count: columns.len(),
+    }
+}
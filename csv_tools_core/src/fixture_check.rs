@@ -0,0 +1,242 @@
+//! # Fixture Checks (`check` subcommand)
+//!
+//! A pre-commit and CI check for the small CSV files kept in a repository (test fixtures,
+//! seed data, lookup tables). It reads each file once, writes nothing, and prints one line
+//! per problem in the `file:line:column: message` form that editors, `grep -n` jump lists,
+//! and CI problem matchers understand:
+//!
+//! ```bash
+//! $ csv_row_analyzer check tests/fixtures/*.csv
+//! tests/fixtures/orders.csv:4:1: record has 3 fields, the header has 4
+//! tests/fixtures/orders.csv:7:18: trailing whitespace
+//! tests/fixtures/users.csv:2:9: invalid UTF-8 byte 0xE9
+//! ```
+//!
+//! `--format github` prints GitHub Actions workflow commands instead
+//! (`::error file=...,line=...,col=...::message`), which show up as annotations on the
+//! pull request. The exit status is 0 when every file is clean and 2 when any problem was
+//! found; a file that cannot be read is a problem too.
+//!
+//! The checks are:
+//!
+//! * Field count: every record (quote-aware, across quoted line breaks) has as many
+//!   fields as the header; a blank line counts as a record of one field, and a quoted
+//!   field that is never closed is reported where it opens
+//! * Trailing whitespace: a line ending in spaces or tabs outside a quoted field
+//! * Encoding: bytes that are not valid UTF-8
+//!
+//! Lines and columns are 1-based; columns count bytes, as editors do for jump targets.
+//! Gzip input is decompressed first. Fields are split at each file's own delimiter, or the
+//! one set with `--delimiter` (see the `delimiter` module).
+
+use std::io::{self, Read};
+
+use crate::cli::{parse_command_line, CHECK_COMMAND};
+use crate::compression::open_input;
+use crate::delimiter::{parse_delimiter, Delimiter};
+use crate::fields::scan_quotes;
+
+/// Exit status when any checked file has a problem
+pub const EXIT_CHECK_FAILED: i32 = 2;
+
+/// How problems are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CheckFormat {
+    /// `file:line:column: message`
+    #[default]
+    Gnu,
+    /// GitHub Actions `::error` workflow commands
+    Github,
+}
+
+/// Options for the `check` subcommand
+#[derive(Debug, Clone)]
+pub struct CheckOptions {
+    pub input_paths: Vec<String>,
+    pub format: CheckFormat,
+    /// Field delimiter of every file (`--delimiter`), or None to sniff each file's
+    pub delimiter: Option<char>,
+}
+
+/// One problem found in a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based byte column
+    pub column: usize,
+    pub message: String,
+}
+
+/// Checks the content of one file.
+///
+/// # Arguments
+///
+/// * `content` - The (decompressed) bytes of the file
+/// * `delimiter` - Field delimiter of the file
+///
+/// # Returns
+///
+/// * `Vec<Problem>` - The problems, in file order
+pub fn check_content(content: &[u8], delimiter: char) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut header_fields = None;
+    // First line and delimiters so far of the current record
+    let mut record: Option<(usize, usize)> = None;
+    let mut in_quotes = false;
+
+    // A final line feed ends the last line; it does not start an empty one
+    let content = content.strip_suffix(b"\n").unwrap_or(content);
+    if content.is_empty() {
+        return problems;
+    }
+    for (index, line) in content.split(|&b| b == b'\n').enumerate() {
+        let line_number = index + 1;
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let text = match std::str::from_utf8(line) {
+            Ok(text) => text.to_string(),
+            Err(_) => {
+                problems.extend(invalid_utf8_columns(line).into_iter().map(|(column, byte)| Problem {
+                    line: line_number,
+                    column,
+                    message: format!("invalid UTF-8 byte 0x{:02X}", byte),
+                }));
+                String::from_utf8_lossy(line).into_owned()
+            },
+        };
+
+        let starts_in_quotes = in_quotes;
+        let (delimiters, ends_in_quotes) = scan_quotes(&text, delimiter, starts_in_quotes);
+        in_quotes = ends_in_quotes;
+        let (first_line, record_delimiters) = record.get_or_insert((line_number, 0));
+        *record_delimiters += delimiters;
+
+        if !ends_in_quotes {
+            let trimmed = line.trim_ascii_end();
+            if trimmed.len() < line.len() && line[trimmed.len()..].iter().all(|&b| b == b' ' || b == b'\t') {
+                problems.push(Problem { line: line_number, column: trimmed.len() + 1, message: "trailing whitespace".to_string() });
+            }
+
+            let fields = *record_delimiters + 1;
+            let first_line = *first_line;
+            record = None;
+            match header_fields {
+                None => header_fields = Some(fields),
+                Some(expected) if fields != expected => {
+                    let message = if text.is_empty() && !starts_in_quotes {
+                        format!("blank line, the header has {} fields", expected)
+                    } else {
+                        format!("record has {} field{}, the header has {}", fields, if fields == 1 { "" } else { "s" }, expected)
+                    };
+                    problems.push(Problem { line: first_line, column: 1, message });
+                },
+                Some(_) => {},
+            }
+        }
+    }
+    if let Some((first_line, _)) = record {
+        problems.push(Problem { line: first_line, column: 1, message: "quoted field is never closed".to_string() });
+    }
+    problems.sort_by_key(|problem| (problem.line, problem.column));
+    problems
+}
+
+/// (1-based column, first byte) of every invalid UTF-8 sequence in a line.
+fn invalid_utf8_columns(line: &[u8]) -> Vec<(usize, u8)> {
+    let mut invalid = Vec::new();
+    let mut position = 0;
+    while position < line.len() {
+        match std::str::from_utf8(&line[position..]) {
+            Ok(_) => break,
+            Err(error) => {
+                let invalid_at = position + error.valid_up_to();
+                invalid.push((invalid_at + 1, line[invalid_at]));
+                position = invalid_at + error.error_len().unwrap_or(line.len() - invalid_at);
+            },
+        }
+    }
+    invalid
+}
+
+/// Formats one problem for the output.
+fn format_problem(path: &str, problem: &Problem, format: CheckFormat) -> String {
+    match format {
+        CheckFormat::Gnu => format!("{}:{}:{}: {}", path, problem.line, problem.column, problem.message),
+        CheckFormat::Github => format!("::error file={},line={},col={}::{}",
+                                       github_property(path), problem.line, problem.column, problem.message),
+    }
+}
+
+/// Escapes a workflow command property value.
+fn github_property(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A").replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Parses the arguments that follow `check`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<CheckOptions, String>` - Parsed options or an error message
+pub fn parse_check_arguments(args: &[String]) -> Result<CheckOptions, String> {
+    let parsed = parse_command_line(&CHECK_COMMAND, args)?;
+    let mut format = CheckFormat::Gnu;
+    let mut delimiter = None;
+    for (flag, value) in &parsed.flags {
+        match *flag {
+            "--format" => format = match value.as_str() {
+                "gnu" => CheckFormat::Gnu,
+                "github" => CheckFormat::Github,
+                _ => return Err(format!("invalid --format value '{}' (use gnu or github)", value)),
+            },
+            "--delimiter" => delimiter = Some(parse_delimiter(value)?),
+            _ => {},
+        }
+    }
+    if parsed.positionals.is_empty() {
+        return Err("Missing input file path".to_string());
+    }
+    Ok(CheckOptions { input_paths: parsed.positionals, format, delimiter })
+}
+
+/// Runs the `check` subcommand and prints every problem.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+///
+/// # Returns
+///
+/// * `bool` - Whether every file was read and had no problems
+pub fn run_check(options: &CheckOptions) -> bool {
+    let mut problem_count = 0;
+    let mut files_with_problems = 0;
+    for path in &options.input_paths {
+        let problems = match read_content(path) {
+            Ok(content) => check_content(&content, Delimiter::resolve(path, options.delimiter).symbol),
+            Err(e) => vec![Problem { line: 1, column: 1, message: format!("could not read the file: {}", e) }],
+        };
+        for problem in &problems {
+            println!("{}", format_problem(path, problem, options.format));
+        }
+        if !problems.is_empty() {
+            problem_count += problems.len();
+            files_with_problems += 1;
+        }
+    }
+    if problem_count > 0 {
+        eprintln!("{} problem{} in {} of {} files", problem_count, if problem_count == 1 { "" } else { "s" },
+                  files_with_problems, options.input_paths.len());
+    }
+    problem_count == 0
+}
+
+fn read_content(path: &str) -> Result<Vec<u8>, io::Error> {
+    let (mut reader, _) = open_input(path)?;
+    let mut content = Vec::new();
+    reader.read_to_end(&mut content)?;
+    Ok(content)
+}
@@ -0,0 +1,212 @@
+//! # Flagged Row Stream (`--flag-stream`)
+//!
+//! Appends one JSON object per flagged row to a JSON Lines file while the analysis runs,
+//! so a process tailing the file (`tail -F flags.jsonl | ...`) can act on problems, such
+//! as queueing a re-extraction, hours before the reports of a huge file are written:
+//!
+//! ```text
+//! {"file": "feed.csv", "row": 120431, "byte_offset": 88412093, "reason": "long_row", "length": 48211, "threshold": 212.5}
+//! {"file": "feed.csv", "row": 120877, "byte_offset": 88530117, "reason": "unreadable_row", "length": null, "threshold": null}
+//! ```
+//!
+//! Reasons:
+//!
+//! * `unreadable_row` - The row could not be read (for example, invalid UTF-8)
+//! * `long_row` - The row is longer than the upper outlier fence (Q3 + 1.5 × IQR unless
+//!   `--outlier-method` says otherwise, on the `--length-scale`) of the rows read so far;
+//!   `threshold` is that fence in characters
+//! * `truncated` - The file appears truncated (see the `truncation` module); `row` and
+//!   `byte_offset` are where the data stops
+//!
+//! The long-row fence needs the whole file, so the stream uses a provisional one: it is
+//! computed once [`MIN_FENCE_ROWS`] rows were read and refreshed every
+//! [`FENCE_REFRESH_ROWS`] rows. Early rows can therefore be flagged, or missed, against a
+//! fence that the finished reports revise; the outlier reports stay authoritative.
+//!
+//! The file is appended to, so a directory run streams the flags of every file into it,
+//! and each line is flushed as it is written. The file is opened at the first flag and
+//! closed when the file's pass ends. A stream that cannot be written is reported once
+//! and abandoned without failing the analysis.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+
+use crate::json::{json_number, json_string};
+use crate::length_scale::{LengthScale, OutlierFences, OutlierRule};
+use crate::messages::{Language, Message};
+use crate::truncation::Truncation;
+
+/// Rows read before the first provisional fence is computed
+pub const MIN_FENCE_ROWS: u64 = 1_000;
+/// Rows read between two refreshes of the provisional fence
+pub const FENCE_REFRESH_ROWS: u64 = 10_000;
+
+/// Why a row was flagged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlagReason {
+    UnreadableRow,
+    LongRow,
+    Truncated,
+}
+
+impl FlagReason {
+    /// Name written in the `reason` field.
+    pub fn code(&self) -> &'static str {
+        match self {
+            FlagReason::UnreadableRow => "unreadable_row",
+            FlagReason::LongRow => "long_row",
+            FlagReason::Truncated => "truncated",
+        }
+    }
+}
+
+/// Appends flagged rows of one input file to the stream file
+#[derive(Debug)]
+pub struct FlagStream {
+    path: String,
+    /// Input file named in every event
+    source: String,
+    /// Opened at the first event; None again after a failed write
+    writer: Option<BufWriter<File>>,
+    /// Set once writing failed, so the failure is reported once
+    failed: bool,
+    /// Scale the fence is computed on
+    length_scale: LengthScale,
+    /// Method and factor of the fence
+    outlier_rule: OutlierRule,
+    /// Current provisional long-row fence
+    fence: Option<f64>,
+    /// Rows counted when the fence was last computed
+    fence_rows: u64,
+    /// Language of the warning printed when writing fails
+    language: Language,
+}
+
+impl FlagStream {
+    /// Creates the stream for one input file; nothing is opened until the first event.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - JSON Lines file the events are appended to
+    /// * `source` - Input file named in the events
+    /// * `length_scale` - Scale the long-row fence is computed on
+    /// * `outlier_rule` - Method and factor of the long-row fence
+    pub fn new(path: &str, source: &str, length_scale: LengthScale, outlier_rule: OutlierRule) -> Self {
+        FlagStream {
+            path: path.to_string(),
+            source: source.to_string(),
+            writer: None,
+            failed: false,
+            length_scale,
+            outlier_rule,
+            fence: None,
+            fence_rows: 0,
+            language: Language::English,
+        }
+    }
+
+    /// Prints the warning about a failed write in `language` (`--lang`).
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Checks a row that was read against the provisional fence.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_index` - Index of the row
+    /// * `byte_offset` - Where the row starts
+    /// * `length` - Row length as counted for the reports
+    /// * `length_counts` - Rows per length so far, including this row
+    /// * `rows` - Rows counted so far, including this row
+    pub fn observe_row(
+        &mut self,
+        row_index: usize,
+        byte_offset: u64,
+        length: usize,
+        length_counts: &HashMap<usize, u64>,
+        rows: u64,
+    ) {
+        if rows >= MIN_FENCE_ROWS && (self.fence.is_none() || rows - self.fence_rows >= FENCE_REFRESH_ROWS) {
+            self.fence = Some(provisional_fence(length_counts, rows, self.length_scale, self.outlier_rule));
+            self.fence_rows = rows;
+        }
+        if let Some(fence) = self.fence
+            && length as f64 > fence
+        {
+            self.emit(row_index, byte_offset, FlagReason::LongRow, Some(length), Some(fence));
+        }
+    }
+
+    /// Flags a row that could not be read.
+    pub fn unreadable_row(&mut self, row_index: usize, byte_offset: u64) {
+        self.emit(row_index, byte_offset, FlagReason::UnreadableRow, None, None);
+    }
+
+    /// Flags a truncated file and closes the stream.
+    pub fn finish(mut self, truncation: Option<&Truncation>) {
+        if let Some(truncation) = truncation {
+            self.emit(truncation.row_index, truncation.byte_offset, FlagReason::Truncated, None, None);
+        }
+    }
+
+    /// Appends and flushes one event.
+    fn emit(&mut self, row_index: usize, byte_offset: u64, reason: FlagReason, length: Option<usize>, threshold: Option<f64>) {
+        if self.failed {
+            return;
+        }
+        let line = format!(
+            "{{\"file\": {}, \"row\": {}, \"byte_offset\": {}, \"reason\": {}, \"length\": {}, \"threshold\": {}}}\n",
+            json_string(&self.source),
+            row_index,
+            byte_offset,
+            json_string(reason.code()),
+            length.map_or("null".to_string(), |length| length.to_string()),
+            threshold.map_or("null".to_string(), json_number),
+        );
+        if let Err(e) = self.write_line(&line) {
+            eprintln!("{}", Message::FlagStreamFailed.text(self.language, &[&self.path, &e, &self.source]));
+            self.failed = true;
+            self.writer = None;
+        }
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if self.writer.is_none() {
+            let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+            self.writer = Some(BufWriter::new(file));
+        }
+        let writer = self.writer.as_mut().expect("writer was just opened");
+        writer.write_all(line.as_bytes())?;
+        writer.flush()
+    }
+}
+
+/// Computes the upper fence of the lengths so far, with the quartiles taken the way the
+/// reports take them from the sorted lengths.
+fn provisional_fence(length_counts: &HashMap<usize, u64>, rows: u64, length_scale: LengthScale, outlier_rule: OutlierRule) -> f64 {
+    let mut sorted: Vec<(usize, u64)> = length_counts.iter().map(|(&length, &count)| (length, count)).collect();
+    sorted.sort_unstable();
+    // Length at a 0-based position of the sorted lengths
+    let length_at = |position: u64| {
+        let mut seen = 0;
+        for &(length, count) in &sorted {
+            seen += count;
+            if position < seen {
+                return length;
+            }
+        }
+        sorted.last().map_or(0, |&(length, _)| length)
+    };
+    let quartile = |numerator: u64| {
+        let index = numerator * rows / 4;
+        if (numerator * rows).is_multiple_of(4) {
+            (length_at(index - 1) + length_at(index)) / 2
+        } else {
+            length_at(index)
+        }
+    };
+    OutlierFences::compute(outlier_rule, length_scale, quartile(1), quartile(3), &sorted).upper
+}
@@ -0,0 +1,671 @@
+//! Unicode property tables of the `graphemes` module.
+//!
+//! Generated by `scripts/unicode_tables.py` from the Unicode 17.0.0 character database
+//! (GraphemeBreakProperty.txt, emoji-data.txt, DerivedCoreProperties.txt). Do not edit;
+//! rerun the script instead.
+
+use crate::graphemes::{Break, Conjunct};
+
+/// Grapheme_Cluster_Break of the code points that are not Other
+pub const GRAPHEME_BREAK: &[(u32, u32, Break)] = &[
+    (0x0000, 0x0009, Break::Control), (0x000A, 0x000A, Break::Lf), (0x000B, 0x000C, Break::Control),
+    (0x000D, 0x000D, Break::Cr), (0x000E, 0x001F, Break::Control), (0x007F, 0x009F, Break::Control),
+    (0x00AD, 0x00AD, Break::Control), (0x0300, 0x036F, Break::Extend), (0x0483, 0x0489, Break::Extend),
+    (0x0591, 0x05BD, Break::Extend), (0x05BF, 0x05BF, Break::Extend), (0x05C1, 0x05C2, Break::Extend),
+    (0x05C4, 0x05C5, Break::Extend), (0x05C7, 0x05C7, Break::Extend), (0x0600, 0x0605, Break::Prepend),
+    (0x0610, 0x061A, Break::Extend), (0x061C, 0x061C, Break::Control), (0x064B, 0x065F, Break::Extend),
+    (0x0670, 0x0670, Break::Extend), (0x06D6, 0x06DC, Break::Extend), (0x06DD, 0x06DD, Break::Prepend),
+    (0x06DF, 0x06E4, Break::Extend), (0x06E7, 0x06E8, Break::Extend), (0x06EA, 0x06ED, Break::Extend),
+    (0x070F, 0x070F, Break::Prepend), (0x0711, 0x0711, Break::Extend), (0x0730, 0x074A, Break::Extend),
+    (0x07A6, 0x07B0, Break::Extend), (0x07EB, 0x07F3, Break::Extend), (0x07FD, 0x07FD, Break::Extend),
+    (0x0816, 0x0819, Break::Extend), (0x081B, 0x0823, Break::Extend), (0x0825, 0x0827, Break::Extend),
+    (0x0829, 0x082D, Break::Extend), (0x0859, 0x085B, Break::Extend), (0x0890, 0x0891, Break::Prepend),
+    (0x0897, 0x089F, Break::Extend), (0x08CA, 0x08E1, Break::Extend), (0x08E2, 0x08E2, Break::Prepend),
+    (0x08E3, 0x0902, Break::Extend), (0x0903, 0x0903, Break::SpacingMark), (0x093A, 0x093A, Break::Extend),
+    (0x093B, 0x093B, Break::SpacingMark), (0x093C, 0x093C, Break::Extend), (0x093E, 0x0940, Break::SpacingMark),
+    (0x0941, 0x0948, Break::Extend), (0x0949, 0x094C, Break::SpacingMark), (0x094D, 0x094D, Break::Extend),
+    (0x094E, 0x094F, Break::SpacingMark), (0x0951, 0x0957, Break::Extend), (0x0962, 0x0963, Break::Extend),
+    (0x0981, 0x0981, Break::Extend), (0x0982, 0x0983, Break::SpacingMark), (0x09BC, 0x09BC, Break::Extend),
+    (0x09BE, 0x09BE, Break::Extend), (0x09BF, 0x09C0, Break::SpacingMark), (0x09C1, 0x09C4, Break::Extend),
+    (0x09C7, 0x09C8, Break::SpacingMark), (0x09CB, 0x09CC, Break::SpacingMark), (0x09CD, 0x09CD, Break::Extend),
+    (0x09D7, 0x09D7, Break::Extend), (0x09E2, 0x09E3, Break::Extend), (0x09FE, 0x09FE, Break::Extend),
+    (0x0A01, 0x0A02, Break::Extend), (0x0A03, 0x0A03, Break::SpacingMark), (0x0A3C, 0x0A3C, Break::Extend),
+    (0x0A3E, 0x0A40, Break::SpacingMark), (0x0A41, 0x0A42, Break::Extend), (0x0A47, 0x0A48, Break::Extend),
+    (0x0A4B, 0x0A4D, Break::Extend), (0x0A51, 0x0A51, Break::Extend), (0x0A70, 0x0A71, Break::Extend),
+    (0x0A75, 0x0A75, Break::Extend), (0x0A81, 0x0A82, Break::Extend), (0x0A83, 0x0A83, Break::SpacingMark),
+    (0x0ABC, 0x0ABC, Break::Extend), (0x0ABE, 0x0AC0, Break::SpacingMark), (0x0AC1, 0x0AC5, Break::Extend),
+    (0x0AC7, 0x0AC8, Break::Extend), (0x0AC9, 0x0AC9, Break::SpacingMark), (0x0ACB, 0x0ACC, Break::SpacingMark),
+    (0x0ACD, 0x0ACD, Break::Extend), (0x0AE2, 0x0AE3, Break::Extend), (0x0AFA, 0x0AFF, Break::Extend),
+    (0x0B01, 0x0B01, Break::Extend), (0x0B02, 0x0B03, Break::SpacingMark), (0x0B3C, 0x0B3C, Break::Extend),
+    (0x0B3E, 0x0B3F, Break::Extend), (0x0B40, 0x0B40, Break::SpacingMark), (0x0B41, 0x0B44, Break::Extend),
+    (0x0B47, 0x0B48, Break::SpacingMark), (0x0B4B, 0x0B4C, Break::SpacingMark), (0x0B4D, 0x0B4D, Break::Extend),
+    (0x0B55, 0x0B57, Break::Extend), (0x0B62, 0x0B63, Break::Extend), (0x0B82, 0x0B82, Break::Extend),
+    (0x0BBE, 0x0BBE, Break::Extend), (0x0BBF, 0x0BBF, Break::SpacingMark), (0x0BC0, 0x0BC0, Break::Extend),
+    (0x0BC1, 0x0BC2, Break::SpacingMark), (0x0BC6, 0x0BC8, Break::SpacingMark), (0x0BCA, 0x0BCC, Break::SpacingMark),
+    (0x0BCD, 0x0BCD, Break::Extend), (0x0BD7, 0x0BD7, Break::Extend), (0x0C00, 0x0C00, Break::Extend),
+    (0x0C01, 0x0C03, Break::SpacingMark), (0x0C04, 0x0C04, Break::Extend), (0x0C3C, 0x0C3C, Break::Extend),
+    (0x0C3E, 0x0C40, Break::Extend), (0x0C41, 0x0C44, Break::SpacingMark), (0x0C46, 0x0C48, Break::Extend),
+    (0x0C4A, 0x0C4D, Break::Extend), (0x0C55, 0x0C56, Break::Extend), (0x0C62, 0x0C63, Break::Extend),
+    (0x0C81, 0x0C81, Break::Extend), (0x0C82, 0x0C83, Break::SpacingMark), (0x0CBC, 0x0CBC, Break::Extend),
+    (0x0CBE, 0x0CBE, Break::SpacingMark), (0x0CBF, 0x0CC0, Break::Extend), (0x0CC1, 0x0CC1, Break::SpacingMark),
+    (0x0CC2, 0x0CC2, Break::Extend), (0x0CC3, 0x0CC4, Break::SpacingMark), (0x0CC6, 0x0CC8, Break::Extend),
+    (0x0CCA, 0x0CCD, Break::Extend), (0x0CD5, 0x0CD6, Break::Extend), (0x0CE2, 0x0CE3, Break::Extend),
+    (0x0CF3, 0x0CF3, Break::SpacingMark), (0x0D00, 0x0D01, Break::Extend), (0x0D02, 0x0D03, Break::SpacingMark),
+    (0x0D3B, 0x0D3C, Break::Extend), (0x0D3E, 0x0D3E, Break::Extend), (0x0D3F, 0x0D40, Break::SpacingMark),
+    (0x0D41, 0x0D44, Break::Extend), (0x0D46, 0x0D48, Break::SpacingMark), (0x0D4A, 0x0D4C, Break::SpacingMark),
+    (0x0D4D, 0x0D4D, Break::Extend), (0x0D4E, 0x0D4E, Break::Prepend), (0x0D57, 0x0D57, Break::Extend),
+    (0x0D62, 0x0D63, Break::Extend), (0x0D81, 0x0D81, Break::Extend), (0x0D82, 0x0D83, Break::SpacingMark),
+    (0x0DCA, 0x0DCA, Break::Extend), (0x0DCF, 0x0DCF, Break::Extend), (0x0DD0, 0x0DD1, Break::SpacingMark),
+    (0x0DD2, 0x0DD4, Break::Extend), (0x0DD6, 0x0DD6, Break::Extend), (0x0DD8, 0x0DDE, Break::SpacingMark),
+    (0x0DDF, 0x0DDF, Break::Extend), (0x0DF2, 0x0DF3, Break::SpacingMark), (0x0E31, 0x0E31, Break::Extend),
+    (0x0E33, 0x0E33, Break::SpacingMark), (0x0E34, 0x0E3A, Break::Extend), (0x0E47, 0x0E4E, Break::Extend),
+    (0x0EB1, 0x0EB1, Break::Extend), (0x0EB3, 0x0EB3, Break::SpacingMark), (0x0EB4, 0x0EBC, Break::Extend),
+    (0x0EC8, 0x0ECE, Break::Extend), (0x0F18, 0x0F19, Break::Extend), (0x0F35, 0x0F35, Break::Extend),
+    (0x0F37, 0x0F37, Break::Extend), (0x0F39, 0x0F39, Break::Extend), (0x0F3E, 0x0F3F, Break::SpacingMark),
+    (0x0F71, 0x0F7E, Break::Extend), (0x0F7F, 0x0F7F, Break::SpacingMark), (0x0F80, 0x0F84, Break::Extend),
+    (0x0F86, 0x0F87, Break::Extend), (0x0F8D, 0x0F97, Break::Extend), (0x0F99, 0x0FBC, Break::Extend),
+    (0x0FC6, 0x0FC6, Break::Extend), (0x102D, 0x1030, Break::Extend), (0x1031, 0x1031, Break::SpacingMark),
+    (0x1032, 0x1037, Break::Extend), (0x1039, 0x103A, Break::Extend), (0x103B, 0x103C, Break::SpacingMark),
+    (0x103D, 0x103E, Break::Extend), (0x1056, 0x1057, Break::SpacingMark), (0x1058, 0x1059, Break::Extend),
+    (0x105E, 0x1060, Break::Extend), (0x1071, 0x1074, Break::Extend), (0x1082, 0x1082, Break::Extend),
+    (0x1084, 0x1084, Break::SpacingMark), (0x1085, 0x1086, Break::Extend), (0x108D, 0x108D, Break::Extend),
+    (0x109D, 0x109D, Break::Extend), (0x1100, 0x115F, Break::HangulL), (0x1160, 0x11A7, Break::HangulV),
+    (0x11A8, 0x11FF, Break::HangulT), (0x135D, 0x135F, Break::Extend), (0x1712, 0x1715, Break::Extend),
+    (0x1732, 0x1734, Break::Extend), (0x1752, 0x1753, Break::Extend), (0x1772, 0x1773, Break::Extend),
+    (0x17B4, 0x17B5, Break::Extend), (0x17B6, 0x17B6, Break::SpacingMark), (0x17B7, 0x17BD, Break::Extend),
+    (0x17BE, 0x17C5, Break::SpacingMark), (0x17C6, 0x17C6, Break::Extend), (0x17C7, 0x17C8, Break::SpacingMark),
+    (0x17C9, 0x17D3, Break::Extend), (0x17DD, 0x17DD, Break::Extend), (0x180B, 0x180D, Break::Extend),
+    (0x180E, 0x180E, Break::Control), (0x180F, 0x180F, Break::Extend), (0x1885, 0x1886, Break::Extend),
+    (0x18A9, 0x18A9, Break::Extend), (0x1920, 0x1922, Break::Extend), (0x1923, 0x1926, Break::SpacingMark),
+    (0x1927, 0x1928, Break::Extend), (0x1929, 0x192B, Break::SpacingMark), (0x1930, 0x1931, Break::SpacingMark),
+    (0x1932, 0x1932, Break::Extend), (0x1933, 0x1938, Break::SpacingMark), (0x1939, 0x193B, Break::Extend),
+    (0x1A17, 0x1A18, Break::Extend), (0x1A19, 0x1A1A, Break::SpacingMark), (0x1A1B, 0x1A1B, Break::Extend),
+    (0x1A55, 0x1A55, Break::SpacingMark), (0x1A56, 0x1A56, Break::Extend), (0x1A57, 0x1A57, Break::SpacingMark),
+    (0x1A58, 0x1A5E, Break::Extend), (0x1A60, 0x1A60, Break::Extend), (0x1A62, 0x1A62, Break::Extend),
+    (0x1A65, 0x1A6C, Break::Extend), (0x1A6D, 0x1A72, Break::SpacingMark), (0x1A73, 0x1A7C, Break::Extend),
+    (0x1A7F, 0x1A7F, Break::Extend), (0x1AB0, 0x1ADD, Break::Extend), (0x1AE0, 0x1AEB, Break::Extend),
+    (0x1B00, 0x1B03, Break::Extend), (0x1B04, 0x1B04, Break::SpacingMark), (0x1B34, 0x1B3D, Break::Extend),
+    (0x1B3E, 0x1B41, Break::SpacingMark), (0x1B42, 0x1B44, Break::Extend), (0x1B6B, 0x1B73, Break::Extend),
+    (0x1B80, 0x1B81, Break::Extend), (0x1B82, 0x1B82, Break::SpacingMark), (0x1BA1, 0x1BA1, Break::SpacingMark),
+    (0x1BA2, 0x1BA5, Break::Extend), (0x1BA6, 0x1BA7, Break::SpacingMark), (0x1BA8, 0x1BAD, Break::Extend),
+    (0x1BE6, 0x1BE6, Break::Extend), (0x1BE7, 0x1BE7, Break::SpacingMark), (0x1BE8, 0x1BE9, Break::Extend),
+    (0x1BEA, 0x1BEC, Break::SpacingMark), (0x1BED, 0x1BED, Break::Extend), (0x1BEE, 0x1BEE, Break::SpacingMark),
+    (0x1BEF, 0x1BF3, Break::Extend), (0x1C24, 0x1C2B, Break::SpacingMark), (0x1C2C, 0x1C33, Break::Extend),
+    (0x1C34, 0x1C35, Break::SpacingMark), (0x1C36, 0x1C37, Break::Extend), (0x1CD0, 0x1CD2, Break::Extend),
+    (0x1CD4, 0x1CE0, Break::Extend), (0x1CE1, 0x1CE1, Break::SpacingMark), (0x1CE2, 0x1CE8, Break::Extend),
+    (0x1CED, 0x1CED, Break::Extend), (0x1CF4, 0x1CF4, Break::Extend), (0x1CF7, 0x1CF7, Break::SpacingMark),
+    (0x1CF8, 0x1CF9, Break::Extend), (0x1DC0, 0x1DFF, Break::Extend), (0x200B, 0x200B, Break::Control),
+    (0x200C, 0x200C, Break::Extend), (0x200D, 0x200D, Break::Zwj), (0x200E, 0x200F, Break::Control),
+    (0x2028, 0x202E, Break::Control), (0x2060, 0x206F, Break::Control), (0x20D0, 0x20F0, Break::Extend),
+    (0x2CEF, 0x2CF1, Break::Extend), (0x2D7F, 0x2D7F, Break::Extend), (0x2DE0, 0x2DFF, Break::Extend),
+    (0x302A, 0x302F, Break::Extend), (0x3099, 0x309A, Break::Extend), (0xA66F, 0xA672, Break::Extend),
+    (0xA674, 0xA67D, Break::Extend), (0xA69E, 0xA69F, Break::Extend), (0xA6F0, 0xA6F1, Break::Extend),
+    (0xA802, 0xA802, Break::Extend), (0xA806, 0xA806, Break::Extend), (0xA80B, 0xA80B, Break::Extend),
+    (0xA823, 0xA824, Break::SpacingMark), (0xA825, 0xA826, Break::Extend), (0xA827, 0xA827, Break::SpacingMark),
+    (0xA82C, 0xA82C, Break::Extend), (0xA880, 0xA881, Break::SpacingMark), (0xA8B4, 0xA8C3, Break::SpacingMark),
+    (0xA8C4, 0xA8C5, Break::Extend), (0xA8E0, 0xA8F1, Break::Extend), (0xA8FF, 0xA8FF, Break::Extend),
+    (0xA926, 0xA92D, Break::Extend), (0xA947, 0xA951, Break::Extend), (0xA952, 0xA952, Break::SpacingMark),
+    (0xA953, 0xA953, Break::Extend), (0xA960, 0xA97C, Break::HangulL), (0xA980, 0xA982, Break::Extend),
+    (0xA983, 0xA983, Break::SpacingMark), (0xA9B3, 0xA9B3, Break::Extend), (0xA9B4, 0xA9B5, Break::SpacingMark),
+    (0xA9B6, 0xA9B9, Break::Extend), (0xA9BA, 0xA9BB, Break::SpacingMark), (0xA9BC, 0xA9BD, Break::Extend),
+    (0xA9BE, 0xA9BF, Break::SpacingMark), (0xA9C0, 0xA9C0, Break::Extend), (0xA9E5, 0xA9E5, Break::Extend),
+    (0xAA29, 0xAA2E, Break::Extend), (0xAA2F, 0xAA30, Break::SpacingMark), (0xAA31, 0xAA32, Break::Extend),
+    (0xAA33, 0xAA34, Break::SpacingMark), (0xAA35, 0xAA36, Break::Extend), (0xAA43, 0xAA43, Break::Extend),
+    (0xAA4C, 0xAA4C, Break::Extend), (0xAA4D, 0xAA4D, Break::SpacingMark), (0xAA7C, 0xAA7C, Break::Extend),
+    (0xAAB0, 0xAAB0, Break::Extend), (0xAAB2, 0xAAB4, Break::Extend), (0xAAB7, 0xAAB8, Break::Extend),
+    (0xAABE, 0xAABF, Break::Extend), (0xAAC1, 0xAAC1, Break::Extend), (0xAAEB, 0xAAEB, Break::SpacingMark),
+    (0xAAEC, 0xAAED, Break::Extend), (0xAAEE, 0xAAEF, Break::SpacingMark), (0xAAF5, 0xAAF5, Break::SpacingMark),
+    (0xAAF6, 0xAAF6, Break::Extend), (0xABE3, 0xABE4, Break::SpacingMark), (0xABE5, 0xABE5, Break::Extend),
+    (0xABE6, 0xABE7, Break::SpacingMark), (0xABE8, 0xABE8, Break::Extend), (0xABE9, 0xABEA, Break::SpacingMark),
+    (0xABEC, 0xABEC, Break::SpacingMark), (0xABED, 0xABED, Break::Extend), (0xAC00, 0xAC00, Break::HangulLv),
+    (0xAC01, 0xAC1B, Break::HangulLvt), (0xAC1C, 0xAC1C, Break::HangulLv), (0xAC1D, 0xAC37, Break::HangulLvt),
+    (0xAC38, 0xAC38, Break::HangulLv), (0xAC39, 0xAC53, Break::HangulLvt), (0xAC54, 0xAC54, Break::HangulLv),
+    (0xAC55, 0xAC6F, Break::HangulLvt), (0xAC70, 0xAC70, Break::HangulLv), (0xAC71, 0xAC8B, Break::HangulLvt),
+    (0xAC8C, 0xAC8C, Break::HangulLv), (0xAC8D, 0xACA7, Break::HangulLvt), (0xACA8, 0xACA8, Break::HangulLv),
+    (0xACA9, 0xACC3, Break::HangulLvt), (0xACC4, 0xACC4, Break::HangulLv), (0xACC5, 0xACDF, Break::HangulLvt),
+    (0xACE0, 0xACE0, Break::HangulLv), (0xACE1, 0xACFB, Break::HangulLvt), (0xACFC, 0xACFC, Break::HangulLv),
+    (0xACFD, 0xAD17, Break::HangulLvt), (0xAD18, 0xAD18, Break::HangulLv), (0xAD19, 0xAD33, Break::HangulLvt),
+    (0xAD34, 0xAD34, Break::HangulLv), (0xAD35, 0xAD4F, Break::HangulLvt), (0xAD50, 0xAD50, Break::HangulLv),
+    (0xAD51, 0xAD6B, Break::HangulLvt), (0xAD6C, 0xAD6C, Break::HangulLv), (0xAD6D, 0xAD87, Break::HangulLvt),
+    (0xAD88, 0xAD88, Break::HangulLv), (0xAD89, 0xADA3, Break::HangulLvt), (0xADA4, 0xADA4, Break::HangulLv),
+    (0xADA5, 0xADBF, Break::HangulLvt), (0xADC0, 0xADC0, Break::HangulLv), (0xADC1, 0xADDB, Break::HangulLvt),
+    (0xADDC, 0xADDC, Break::HangulLv), (0xADDD, 0xADF7, Break::HangulLvt), (0xADF8, 0xADF8, Break::HangulLv),
+    (0xADF9, 0xAE13, Break::HangulLvt), (0xAE14, 0xAE14, Break::HangulLv), (0xAE15, 0xAE2F, Break::HangulLvt),
+    (0xAE30, 0xAE30, Break::HangulLv), (0xAE31, 0xAE4B, Break::HangulLvt), (0xAE4C, 0xAE4C, Break::HangulLv),
+    (0xAE4D, 0xAE67, Break::HangulLvt), (0xAE68, 0xAE68, Break::HangulLv), (0xAE69, 0xAE83, Break::HangulLvt),
+    (0xAE84, 0xAE84, Break::HangulLv), (0xAE85, 0xAE9F, Break::HangulLvt), (0xAEA0, 0xAEA0, Break::HangulLv),
+    (0xAEA1, 0xAEBB, Break::HangulLvt), (0xAEBC, 0xAEBC, Break::HangulLv), (0xAEBD, 0xAED7, Break::HangulLvt),
+    (0xAED8, 0xAED8, Break::HangulLv), (0xAED9, 0xAEF3, Break::HangulLvt), (0xAEF4, 0xAEF4, Break::HangulLv),
+    (0xAEF5, 0xAF0F, Break::HangulLvt), (0xAF10, 0xAF10, Break::HangulLv), (0xAF11, 0xAF2B, Break::HangulLvt),
+    (0xAF2C, 0xAF2C, Break::HangulLv), (0xAF2D, 0xAF47, Break::HangulLvt), (0xAF48, 0xAF48, Break::HangulLv),
+    (0xAF49, 0xAF63, Break::HangulLvt), (0xAF64, 0xAF64, Break::HangulLv), (0xAF65, 0xAF7F, Break::HangulLvt),
+    (0xAF80, 0xAF80, Break::HangulLv), (0xAF81, 0xAF9B, Break::HangulLvt), (0xAF9C, 0xAF9C, Break::HangulLv),
+    (0xAF9D, 0xAFB7, Break::HangulLvt), (0xAFB8, 0xAFB8, Break::HangulLv), (0xAFB9, 0xAFD3, Break::HangulLvt),
+    (0xAFD4, 0xAFD4, Break::HangulLv), (0xAFD5, 0xAFEF, Break::HangulLvt), (0xAFF0, 0xAFF0, Break::HangulLv),
+    (0xAFF1, 0xB00B, Break::HangulLvt), (0xB00C, 0xB00C, Break::HangulLv), (0xB00D, 0xB027, Break::HangulLvt),
+    (0xB028, 0xB028, Break::HangulLv), (0xB029, 0xB043, Break::HangulLvt), (0xB044, 0xB044, Break::HangulLv),
+    (0xB045, 0xB05F, Break::HangulLvt), (0xB060, 0xB060, Break::HangulLv), (0xB061, 0xB07B, Break::HangulLvt),
+    (0xB07C, 0xB07C, Break::HangulLv), (0xB07D, 0xB097, Break::HangulLvt), (0xB098, 0xB098, Break::HangulLv),
+    (0xB099, 0xB0B3, Break::HangulLvt), (0xB0B4, 0xB0B4, Break::HangulLv), (0xB0B5, 0xB0CF, Break::HangulLvt),
+    (0xB0D0, 0xB0D0, Break::HangulLv), (0xB0D1, 0xB0EB, Break::HangulLvt), (0xB0EC, 0xB0EC, Break::HangulLv),
+    (0xB0ED, 0xB107, Break::HangulLvt), (0xB108, 0xB108, Break::HangulLv), (0xB109, 0xB123, Break::HangulLvt),
+    (0xB124, 0xB124, Break::HangulLv), (0xB125, 0xB13F, Break::HangulLvt), (0xB140, 0xB140, Break::HangulLv),
+    (0xB141, 0xB15B, Break::HangulLvt), (0xB15C, 0xB15C, Break::HangulLv), (0xB15D, 0xB177, Break::HangulLvt),
+    (0xB178, 0xB178, Break::HangulLv), (0xB179, 0xB193, Break::HangulLvt), (0xB194, 0xB194, Break::HangulLv),
+    (0xB195, 0xB1AF, Break::HangulLvt), (0xB1B0, 0xB1B0, Break::HangulLv), (0xB1B1, 0xB1CB, Break::HangulLvt),
+    (0xB1CC, 0xB1CC, Break::HangulLv), (0xB1CD, 0xB1E7, Break::HangulLvt), (0xB1E8, 0xB1E8, Break::HangulLv),
+    (0xB1E9, 0xB203, Break::HangulLvt), (0xB204, 0xB204, Break::HangulLv), (0xB205, 0xB21F, Break::HangulLvt),
+    (0xB220, 0xB220, Break::HangulLv), (0xB221, 0xB23B, Break::HangulLvt), (0xB23C, 0xB23C, Break::HangulLv),
+    (0xB23D, 0xB257, Break::HangulLvt), (0xB258, 0xB258, Break::HangulLv), (0xB259, 0xB273, Break::HangulLvt),
+    (0xB274, 0xB274, Break::HangulLv), (0xB275, 0xB28F, Break::HangulLvt), (0xB290, 0xB290, Break::HangulLv),
+    (0xB291, 0xB2AB, Break::HangulLvt), (0xB2AC, 0xB2AC, Break::HangulLv), (0xB2AD, 0xB2C7, Break::HangulLvt),
+    (0xB2C8, 0xB2C8, Break::HangulLv), (0xB2C9, 0xB2E3, Break::HangulLvt), (0xB2E4, 0xB2E4, Break::HangulLv),
+    (0xB2E5, 0xB2FF, Break::HangulLvt), (0xB300, 0xB300, Break::HangulLv), (0xB301, 0xB31B, Break::HangulLvt),
+    (0xB31C, 0xB31C, Break::HangulLv), (0xB31D, 0xB337, Break::HangulLvt), (0xB338, 0xB338, Break::HangulLv),
+    (0xB339, 0xB353, Break::HangulLvt), (0xB354, 0xB354, Break::HangulLv), (0xB355, 0xB36F, Break::HangulLvt),
+    (0xB370, 0xB370, Break::HangulLv), (0xB371, 0xB38B, Break::HangulLvt), (0xB38C, 0xB38C, Break::HangulLv),
+    (0xB38D, 0xB3A7, Break::HangulLvt), (0xB3A8, 0xB3A8, Break::HangulLv), (0xB3A9, 0xB3C3, Break::HangulLvt),
+    (0xB3C4, 0xB3C4, Break::HangulLv), (0xB3C5, 0xB3DF, Break::HangulLvt), (0xB3E0, 0xB3E0, Break::HangulLv),
+    (0xB3E1, 0xB3FB, Break::HangulLvt), (0xB3FC, 0xB3FC, Break::HangulLv), (0xB3FD, 0xB417, Break::HangulLvt),
+    (0xB418, 0xB418, Break::HangulLv), (0xB419, 0xB433, Break::HangulLvt), (0xB434, 0xB434, Break::HangulLv),
+    (0xB435, 0xB44F, Break::HangulLvt), (0xB450, 0xB450, Break::HangulLv), (0xB451, 0xB46B, Break::HangulLvt),
+    (0xB46C, 0xB46C, Break::HangulLv), (0xB46D, 0xB487, Break::HangulLvt), (0xB488, 0xB488, Break::HangulLv),
+    (0xB489, 0xB4A3, Break::HangulLvt), (0xB4A4, 0xB4A4, Break::HangulLv), (0xB4A5, 0xB4BF, Break::HangulLvt),
+    (0xB4C0, 0xB4C0, Break::HangulLv), (0xB4C1, 0xB4DB, Break::HangulLvt), (0xB4DC, 0xB4DC, Break::HangulLv),
+    (0xB4DD, 0xB4F7, Break::HangulLvt), (0xB4F8, 0xB4F8, Break::HangulLv), (0xB4F9, 0xB513, Break::HangulLvt),
+    (0xB514, 0xB514, Break::HangulLv), (0xB515, 0xB52F, Break::HangulLvt), (0xB530, 0xB530, Break::HangulLv),
+    (0xB531, 0xB54B, Break::HangulLvt), (0xB54C, 0xB54C, Break::HangulLv), (0xB54D, 0xB567, Break::HangulLvt),
+    (0xB568, 0xB568, Break::HangulLv), (0xB569, 0xB583, Break::HangulLvt), (0xB584, 0xB584, Break::HangulLv),
+    (0xB585, 0xB59F, Break::HangulLvt), (0xB5A0, 0xB5A0, Break::HangulLv), (0xB5A1, 0xB5BB, Break::HangulLvt),
+    (0xB5BC, 0xB5BC, Break::HangulLv), (0xB5BD, 0xB5D7, Break::HangulLvt), (0xB5D8, 0xB5D8, Break::HangulLv),
+    (0xB5D9, 0xB5F3, Break::HangulLvt), (0xB5F4, 0xB5F4, Break::HangulLv), (0xB5F5, 0xB60F, Break::HangulLvt),
+    (0xB610, 0xB610, Break::HangulLv), (0xB611, 0xB62B, Break::HangulLvt), (0xB62C, 0xB62C, Break::HangulLv),
+    (0xB62D, 0xB647, Break::HangulLvt), (0xB648, 0xB648, Break::HangulLv), (0xB649, 0xB663, Break::HangulLvt),
+    (0xB664, 0xB664, Break::HangulLv), (0xB665, 0xB67F, Break::HangulLvt), (0xB680, 0xB680, Break::HangulLv),
+    (0xB681, 0xB69B, Break::HangulLvt), (0xB69C, 0xB69C, Break::HangulLv), (0xB69D, 0xB6B7, Break::HangulLvt),
+    (0xB6B8, 0xB6B8, Break::HangulLv), (0xB6B9, 0xB6D3, Break::HangulLvt), (0xB6D4, 0xB6D4, Break::HangulLv),
+    (0xB6D5, 0xB6EF, Break::HangulLvt), (0xB6F0, 0xB6F0, Break::HangulLv), (0xB6F1, 0xB70B, Break::HangulLvt),
+    (0xB70C, 0xB70C, Break::HangulLv), (0xB70D, 0xB727, Break::HangulLvt), (0xB728, 0xB728, Break::HangulLv),
+    (0xB729, 0xB743, Break::HangulLvt), (0xB744, 0xB744, Break::HangulLv), (0xB745, 0xB75F, Break::HangulLvt),
+    (0xB760, 0xB760, Break::HangulLv), (0xB761, 0xB77B, Break::HangulLvt), (0xB77C, 0xB77C, Break::HangulLv),
+    (0xB77D, 0xB797, Break::HangulLvt), (0xB798, 0xB798, Break::HangulLv), (0xB799, 0xB7B3, Break::HangulLvt),
+    (0xB7B4, 0xB7B4, Break::HangulLv), (0xB7B5, 0xB7CF, Break::HangulLvt), (0xB7D0, 0xB7D0, Break::HangulLv),
+    (0xB7D1, 0xB7EB, Break::HangulLvt), (0xB7EC, 0xB7EC, Break::HangulLv), (0xB7ED, 0xB807, Break::HangulLvt),
+    (0xB808, 0xB808, Break::HangulLv), (0xB809, 0xB823, Break::HangulLvt), (0xB824, 0xB824, Break::HangulLv),
+    (0xB825, 0xB83F, Break::HangulLvt), (0xB840, 0xB840, Break::HangulLv), (0xB841, 0xB85B, Break::HangulLvt),
+    (0xB85C, 0xB85C, Break::HangulLv), (0xB85D, 0xB877, Break::HangulLvt), (0xB878, 0xB878, Break::HangulLv),
+    (0xB879, 0xB893, Break::HangulLvt), (0xB894, 0xB894, Break::HangulLv), (0xB895, 0xB8AF, Break::HangulLvt),
+    (0xB8B0, 0xB8B0, Break::HangulLv), (0xB8B1, 0xB8CB, Break::HangulLvt), (0xB8CC, 0xB8CC, Break::HangulLv),
+    (0xB8CD, 0xB8E7, Break::HangulLvt), (0xB8E8, 0xB8E8, Break::HangulLv), (0xB8E9, 0xB903, Break::HangulLvt),
+    (0xB904, 0xB904, Break::HangulLv), (0xB905, 0xB91F, Break::HangulLvt), (0xB920, 0xB920, Break::HangulLv),
+    (0xB921, 0xB93B, Break::HangulLvt), (0xB93C, 0xB93C, Break::HangulLv), (0xB93D, 0xB957, Break::HangulLvt),
+    (0xB958, 0xB958, Break::HangulLv), (0xB959, 0xB973, Break::HangulLvt), (0xB974, 0xB974, Break::HangulLv),
+    (0xB975, 0xB98F, Break::HangulLvt), (0xB990, 0xB990, Break::HangulLv), (0xB991, 0xB9AB, Break::HangulLvt),
+    (0xB9AC, 0xB9AC, Break::HangulLv), (0xB9AD, 0xB9C7, Break::HangulLvt), (0xB9C8, 0xB9C8, Break::HangulLv),
+    (0xB9C9, 0xB9E3, Break::HangulLvt), (0xB9E4, 0xB9E4, Break::HangulLv), (0xB9E5, 0xB9FF, Break::HangulLvt),
+    (0xBA00, 0xBA00, Break::HangulLv), (0xBA01, 0xBA1B, Break::HangulLvt), (0xBA1C, 0xBA1C, Break::HangulLv),
+    (0xBA1D, 0xBA37, Break::HangulLvt), (0xBA38, 0xBA38, Break::HangulLv), (0xBA39, 0xBA53, Break::HangulLvt),
+    (0xBA54, 0xBA54, Break::HangulLv), (0xBA55, 0xBA6F, Break::HangulLvt), (0xBA70, 0xBA70, Break::HangulLv),
+    (0xBA71, 0xBA8B, Break::HangulLvt), (0xBA8C, 0xBA8C, Break::HangulLv), (0xBA8D, 0xBAA7, Break::HangulLvt),
+    (0xBAA8, 0xBAA8, Break::HangulLv), (0xBAA9, 0xBAC3, Break::HangulLvt), (0xBAC4, 0xBAC4, Break::HangulLv),
+    (0xBAC5, 0xBADF, Break::HangulLvt), (0xBAE0, 0xBAE0, Break::HangulLv), (0xBAE1, 0xBAFB, Break::HangulLvt),
+    (0xBAFC, 0xBAFC, Break::HangulLv), (0xBAFD, 0xBB17, Break::HangulLvt), (0xBB18, 0xBB18, Break::HangulLv),
+    (0xBB19, 0xBB33, Break::HangulLvt), (0xBB34, 0xBB34, Break::HangulLv), (0xBB35, 0xBB4F, Break::HangulLvt),
+    (0xBB50, 0xBB50, Break::HangulLv), (0xBB51, 0xBB6B, Break::HangulLvt), (0xBB6C, 0xBB6C, Break::HangulLv),
+    (0xBB6D, 0xBB87, Break::HangulLvt), (0xBB88, 0xBB88, Break::HangulLv), (0xBB89, 0xBBA3, Break::HangulLvt),
+    (0xBBA4, 0xBBA4, Break::HangulLv), (0xBBA5, 0xBBBF, Break::HangulLvt), (0xBBC0, 0xBBC0, Break::HangulLv),
+    (0xBBC1, 0xBBDB, Break::HangulLvt), (0xBBDC, 0xBBDC, Break::HangulLv), (0xBBDD, 0xBBF7, Break::HangulLvt),
+    (0xBBF8, 0xBBF8, Break::HangulLv), (0xBBF9, 0xBC13, Break::HangulLvt), (0xBC14, 0xBC14, Break::HangulLv),
+    (0xBC15, 0xBC2F, Break::HangulLvt), (0xBC30, 0xBC30, Break::HangulLv), (0xBC31, 0xBC4B, Break::HangulLvt),
+    (0xBC4C, 0xBC4C, Break::HangulLv), (0xBC4D, 0xBC67, Break::HangulLvt), (0xBC68, 0xBC68, Break::HangulLv),
+    (0xBC69, 0xBC83, Break::HangulLvt), (0xBC84, 0xBC84, Break::HangulLv), (0xBC85, 0xBC9F, Break::HangulLvt),
+    (0xBCA0, 0xBCA0, Break::HangulLv), (0xBCA1, 0xBCBB, Break::HangulLvt), (0xBCBC, 0xBCBC, Break::HangulLv),
+    (0xBCBD, 0xBCD7, Break::HangulLvt), (0xBCD8, 0xBCD8, Break::HangulLv), (0xBCD9, 0xBCF3, Break::HangulLvt),
+    (0xBCF4, 0xBCF4, Break::HangulLv), (0xBCF5, 0xBD0F, Break::HangulLvt), (0xBD10, 0xBD10, Break::HangulLv),
+    (0xBD11, 0xBD2B, Break::HangulLvt), (0xBD2C, 0xBD2C, Break::HangulLv), (0xBD2D, 0xBD47, Break::HangulLvt),
+    (0xBD48, 0xBD48, Break::HangulLv), (0xBD49, 0xBD63, Break::HangulLvt), (0xBD64, 0xBD64, Break::HangulLv),
+    (0xBD65, 0xBD7F, Break::HangulLvt), (0xBD80, 0xBD80, Break::HangulLv), (0xBD81, 0xBD9B, Break::HangulLvt),
+    (0xBD9C, 0xBD9C, Break::HangulLv), (0xBD9D, 0xBDB7, Break::HangulLvt), (0xBDB8, 0xBDB8, Break::HangulLv),
+    (0xBDB9, 0xBDD3, Break::HangulLvt), (0xBDD4, 0xBDD4, Break::HangulLv), (0xBDD5, 0xBDEF, Break::HangulLvt),
+    (0xBDF0, 0xBDF0, Break::HangulLv), (0xBDF1, 0xBE0B, Break::HangulLvt), (0xBE0C, 0xBE0C, Break::HangulLv),
+    (0xBE0D, 0xBE27, Break::HangulLvt), (0xBE28, 0xBE28, Break::HangulLv), (0xBE29, 0xBE43, Break::HangulLvt),
+    (0xBE44, 0xBE44, Break::HangulLv), (0xBE45, 0xBE5F, Break::HangulLvt), (0xBE60, 0xBE60, Break::HangulLv),
+    (0xBE61, 0xBE7B, Break::HangulLvt), (0xBE7C, 0xBE7C, Break::HangulLv), (0xBE7D, 0xBE97, Break::HangulLvt),
+    (0xBE98, 0xBE98, Break::HangulLv), (0xBE99, 0xBEB3, Break::HangulLvt), (0xBEB4, 0xBEB4, Break::HangulLv),
+    (0xBEB5, 0xBECF, Break::HangulLvt), (0xBED0, 0xBED0, Break::HangulLv), (0xBED1, 0xBEEB, Break::HangulLvt),
+    (0xBEEC, 0xBEEC, Break::HangulLv), (0xBEED, 0xBF07, Break::HangulLvt), (0xBF08, 0xBF08, Break::HangulLv),
+    (0xBF09, 0xBF23, Break::HangulLvt), (0xBF24, 0xBF24, Break::HangulLv), (0xBF25, 0xBF3F, Break::HangulLvt),
+    (0xBF40, 0xBF40, Break::HangulLv), (0xBF41, 0xBF5B, Break::HangulLvt), (0xBF5C, 0xBF5C, Break::HangulLv),
+    (0xBF5D, 0xBF77, Break::HangulLvt), (0xBF78, 0xBF78, Break::HangulLv), (0xBF79, 0xBF93, Break::HangulLvt),
+    (0xBF94, 0xBF94, Break::HangulLv), (0xBF95, 0xBFAF, Break::HangulLvt), (0xBFB0, 0xBFB0, Break::HangulLv),
+    (0xBFB1, 0xBFCB, Break::HangulLvt), (0xBFCC, 0xBFCC, Break::HangulLv), (0xBFCD, 0xBFE7, Break::HangulLvt),
+    (0xBFE8, 0xBFE8, Break::HangulLv), (0xBFE9, 0xC003, Break::HangulLvt), (0xC004, 0xC004, Break::HangulLv),
+    (0xC005, 0xC01F, Break::HangulLvt), (0xC020, 0xC020, Break::HangulLv), (0xC021, 0xC03B, Break::HangulLvt),
+    (0xC03C, 0xC03C, Break::HangulLv), (0xC03D, 0xC057, Break::HangulLvt), (0xC058, 0xC058, Break::HangulLv),
+    (0xC059, 0xC073, Break::HangulLvt), (0xC074, 0xC074, Break::HangulLv), (0xC075, 0xC08F, Break::HangulLvt),
+    (0xC090, 0xC090, Break::HangulLv), (0xC091, 0xC0AB, Break::HangulLvt), (0xC0AC, 0xC0AC, Break::HangulLv),
+    (0xC0AD, 0xC0C7, Break::HangulLvt), (0xC0C8, 0xC0C8, Break::HangulLv), (0xC0C9, 0xC0E3, Break::HangulLvt),
+    (0xC0E4, 0xC0E4, Break::HangulLv), (0xC0E5, 0xC0FF, Break::HangulLvt), (0xC100, 0xC100, Break::HangulLv),
+    (0xC101, 0xC11B, Break::HangulLvt), (0xC11C, 0xC11C, Break::HangulLv), (0xC11D, 0xC137, Break::HangulLvt),
+    (0xC138, 0xC138, Break::HangulLv), (0xC139, 0xC153, Break::HangulLvt), (0xC154, 0xC154, Break::HangulLv),
+    (0xC155, 0xC16F, Break::HangulLvt), (0xC170, 0xC170, Break::HangulLv), (0xC171, 0xC18B, Break::HangulLvt),
+    (0xC18C, 0xC18C, Break::HangulLv), (0xC18D, 0xC1A7, Break::HangulLvt), (0xC1A8, 0xC1A8, Break::HangulLv),
+    (0xC1A9, 0xC1C3, Break::HangulLvt), (0xC1C4, 0xC1C4, Break::HangulLv), (0xC1C5, 0xC1DF, Break::HangulLvt),
+    (0xC1E0, 0xC1E0, Break::HangulLv), (0xC1E1, 0xC1FB, Break::HangulLvt), (0xC1FC, 0xC1FC, Break::HangulLv),
+    (0xC1FD, 0xC217, Break::HangulLvt), (0xC218, 0xC218, Break::HangulLv), (0xC219, 0xC233, Break::HangulLvt),
+    (0xC234, 0xC234, Break::HangulLv), (0xC235, 0xC24F, Break::HangulLvt), (0xC250, 0xC250, Break::HangulLv),
+    (0xC251, 0xC26B, Break::HangulLvt), (0xC26C, 0xC26C, Break::HangulLv), (0xC26D, 0xC287, Break::HangulLvt),
+    (0xC288, 0xC288, Break::HangulLv), (0xC289, 0xC2A3, Break::HangulLvt), (0xC2A4, 0xC2A4, Break::HangulLv),
+    (0xC2A5, 0xC2BF, Break::HangulLvt), (0xC2C0, 0xC2C0, Break::HangulLv), (0xC2C1, 0xC2DB, Break::HangulLvt),
+    (0xC2DC, 0xC2DC, Break::HangulLv), (0xC2DD, 0xC2F7, Break::HangulLvt), (0xC2F8, 0xC2F8, Break::HangulLv),
+    (0xC2F9, 0xC313, Break::HangulLvt), (0xC314, 0xC314, Break::HangulLv), (0xC315, 0xC32F, Break::HangulLvt),
+    (0xC330, 0xC330, Break::HangulLv), (0xC331, 0xC34B, Break::HangulLvt), (0xC34C, 0xC34C, Break::HangulLv),
+    (0xC34D, 0xC367, Break::HangulLvt), (0xC368, 0xC368, Break::HangulLv), (0xC369, 0xC383, Break::HangulLvt),
+    (0xC384, 0xC384, Break::HangulLv), (0xC385, 0xC39F, Break::HangulLvt), (0xC3A0, 0xC3A0, Break::HangulLv),
+    (0xC3A1, 0xC3BB, Break::HangulLvt), (0xC3BC, 0xC3BC, Break::HangulLv), (0xC3BD, 0xC3D7, Break::HangulLvt),
+    (0xC3D8, 0xC3D8, Break::HangulLv), (0xC3D9, 0xC3F3, Break::HangulLvt), (0xC3F4, 0xC3F4, Break::HangulLv),
+    (0xC3F5, 0xC40F, Break::HangulLvt), (0xC410, 0xC410, Break::HangulLv), (0xC411, 0xC42B, Break::HangulLvt),
+    (0xC42C, 0xC42C, Break::HangulLv), (0xC42D, 0xC447, Break::HangulLvt), (0xC448, 0xC448, Break::HangulLv),
+    (0xC449, 0xC463, Break::HangulLvt), (0xC464, 0xC464, Break::HangulLv), (0xC465, 0xC47F, Break::HangulLvt),
+    (0xC480, 0xC480, Break::HangulLv), (0xC481, 0xC49B, Break::HangulLvt), (0xC49C, 0xC49C, Break::HangulLv),
+    (0xC49D, 0xC4B7, Break::HangulLvt), (0xC4B8, 0xC4B8, Break::HangulLv), (0xC4B9, 0xC4D3, Break::HangulLvt),
+    (0xC4D4, 0xC4D4, Break::HangulLv), (0xC4D5, 0xC4EF, Break::HangulLvt), (0xC4F0, 0xC4F0, Break::HangulLv),
+    (0xC4F1, 0xC50B, Break::HangulLvt), (0xC50C, 0xC50C, Break::HangulLv), (0xC50D, 0xC527, Break::HangulLvt),
+    (0xC528, 0xC528, Break::HangulLv), (0xC529, 0xC543, Break::HangulLvt), (0xC544, 0xC544, Break::HangulLv),
+    (0xC545, 0xC55F, Break::HangulLvt), (0xC560, 0xC560, Break::HangulLv), (0xC561, 0xC57B, Break::HangulLvt),
+    (0xC57C, 0xC57C, Break::HangulLv), (0xC57D, 0xC597, Break::HangulLvt), (0xC598, 0xC598, Break::HangulLv),
+    (0xC599, 0xC5B3, Break::HangulLvt), (0xC5B4, 0xC5B4, Break::HangulLv), (0xC5B5, 0xC5CF, Break::HangulLvt),
+    (0xC5D0, 0xC5D0, Break::HangulLv), (0xC5D1, 0xC5EB, Break::HangulLvt), (0xC5EC, 0xC5EC, Break::HangulLv),
+    (0xC5ED, 0xC607, Break::HangulLvt), (0xC608, 0xC608, Break::HangulLv), (0xC609, 0xC623, Break::HangulLvt),
+    (0xC624, 0xC624, Break::HangulLv), (0xC625, 0xC63F, Break::HangulLvt), (0xC640, 0xC640, Break::HangulLv),
+    (0xC641, 0xC65B, Break::HangulLvt), (0xC65C, 0xC65C, Break::HangulLv), (0xC65D, 0xC677, Break::HangulLvt),
+    (0xC678, 0xC678, Break::HangulLv), (0xC679, 0xC693, Break::HangulLvt), (0xC694, 0xC694, Break::HangulLv),
+    (0xC695, 0xC6AF, Break::HangulLvt), (0xC6B0, 0xC6B0, Break::HangulLv), (0xC6B1, 0xC6CB, Break::HangulLvt),
+    (0xC6CC, 0xC6CC, Break::HangulLv), (0xC6CD, 0xC6E7, Break::HangulLvt), (0xC6E8, 0xC6E8, Break::HangulLv),
+    (0xC6E9, 0xC703, Break::HangulLvt), (0xC704, 0xC704, Break::HangulLv), (0xC705, 0xC71F, Break::HangulLvt),
+    (0xC720, 0xC720, Break::HangulLv), (0xC721, 0xC73B, Break::HangulLvt), (0xC73C, 0xC73C, Break::HangulLv),
+    (0xC73D, 0xC757, Break::HangulLvt), (0xC758, 0xC758, Break::HangulLv), (0xC759, 0xC773, Break::HangulLvt),
+    (0xC774, 0xC774, Break::HangulLv), (0xC775, 0xC78F, Break::HangulLvt), (0xC790, 0xC790, Break::HangulLv),
+    (0xC791, 0xC7AB, Break::HangulLvt), (0xC7AC, 0xC7AC, Break::HangulLv), (0xC7AD, 0xC7C7, Break::HangulLvt),
+    (0xC7C8, 0xC7C8, Break::HangulLv), (0xC7C9, 0xC7E3, Break::HangulLvt), (0xC7E4, 0xC7E4, Break::HangulLv),
+    (0xC7E5, 0xC7FF, Break::HangulLvt), (0xC800, 0xC800, Break::HangulLv), (0xC801, 0xC81B, Break::HangulLvt),
+    (0xC81C, 0xC81C, Break::HangulLv), (0xC81D, 0xC837, Break::HangulLvt), (0xC838, 0xC838, Break::HangulLv),
+    (0xC839, 0xC853, Break::HangulLvt), (0xC854, 0xC854, Break::HangulLv), (0xC855, 0xC86F, Break::HangulLvt),
+    (0xC870, 0xC870, Break::HangulLv), (0xC871, 0xC88B, Break::HangulLvt), (0xC88C, 0xC88C, Break::HangulLv),
+    (0xC88D, 0xC8A7, Break::HangulLvt), (0xC8A8, 0xC8A8, Break::HangulLv), (0xC8A9, 0xC8C3, Break::HangulLvt),
+    (0xC8C4, 0xC8C4, Break::HangulLv), (0xC8C5, 0xC8DF, Break::HangulLvt), (0xC8E0, 0xC8E0, Break::HangulLv),
+    (0xC8E1, 0xC8FB, Break::HangulLvt), (0xC8FC, 0xC8FC, Break::HangulLv), (0xC8FD, 0xC917, Break::HangulLvt),
+    (0xC918, 0xC918, Break::HangulLv), (0xC919, 0xC933, Break::HangulLvt), (0xC934, 0xC934, Break::HangulLv),
+    (0xC935, 0xC94F, Break::HangulLvt), (0xC950, 0xC950, Break::HangulLv), (0xC951, 0xC96B, Break::HangulLvt),
+    (0xC96C, 0xC96C, Break::HangulLv), (0xC96D, 0xC987, Break::HangulLvt), (0xC988, 0xC988, Break::HangulLv),
+    (0xC989, 0xC9A3, Break::HangulLvt), (0xC9A4, 0xC9A4, Break::HangulLv), (0xC9A5, 0xC9BF, Break::HangulLvt),
+    (0xC9C0, 0xC9C0, Break::HangulLv), (0xC9C1, 0xC9DB, Break::HangulLvt), (0xC9DC, 0xC9DC, Break::HangulLv),
+    (0xC9DD, 0xC9F7, Break::HangulLvt), (0xC9F8, 0xC9F8, Break::HangulLv), (0xC9F9, 0xCA13, Break::HangulLvt),
+    (0xCA14, 0xCA14, Break::HangulLv), (0xCA15, 0xCA2F, Break::HangulLvt), (0xCA30, 0xCA30, Break::HangulLv),
+    (0xCA31, 0xCA4B, Break::HangulLvt), (0xCA4C, 0xCA4C, Break::HangulLv), (0xCA4D, 0xCA67, Break::HangulLvt),
+    (0xCA68, 0xCA68, Break::HangulLv), (0xCA69, 0xCA83, Break::HangulLvt), (0xCA84, 0xCA84, Break::HangulLv),
+    (0xCA85, 0xCA9F, Break::HangulLvt), (0xCAA0, 0xCAA0, Break::HangulLv), (0xCAA1, 0xCABB, Break::HangulLvt),
+    (0xCABC, 0xCABC, Break::HangulLv), (0xCABD, 0xCAD7, Break::HangulLvt), (0xCAD8, 0xCAD8, Break::HangulLv),
+    (0xCAD9, 0xCAF3, Break::HangulLvt), (0xCAF4, 0xCAF4, Break::HangulLv), (0xCAF5, 0xCB0F, Break::HangulLvt),
+    (0xCB10, 0xCB10, Break::HangulLv), (0xCB11, 0xCB2B, Break::HangulLvt), (0xCB2C, 0xCB2C, Break::HangulLv),
+    (0xCB2D, 0xCB47, Break::HangulLvt), (0xCB48, 0xCB48, Break::HangulLv), (0xCB49, 0xCB63, Break::HangulLvt),
+    (0xCB64, 0xCB64, Break::HangulLv), (0xCB65, 0xCB7F, Break::HangulLvt), (0xCB80, 0xCB80, Break::HangulLv),
+    (0xCB81, 0xCB9B, Break::HangulLvt), (0xCB9C, 0xCB9C, Break::HangulLv), (0xCB9D, 0xCBB7, Break::HangulLvt),
+    (0xCBB8, 0xCBB8, Break::HangulLv), (0xCBB9, 0xCBD3, Break::HangulLvt), (0xCBD4, 0xCBD4, Break::HangulLv),
+    (0xCBD5, 0xCBEF, Break::HangulLvt), (0xCBF0, 0xCBF0, Break::HangulLv), (0xCBF1, 0xCC0B, Break::HangulLvt),
+    (0xCC0C, 0xCC0C, Break::HangulLv), (0xCC0D, 0xCC27, Break::HangulLvt), (0xCC28, 0xCC28, Break::HangulLv),
+    (0xCC29, 0xCC43, Break::HangulLvt), (0xCC44, 0xCC44, Break::HangulLv), (0xCC45, 0xCC5F, Break::HangulLvt),
+    (0xCC60, 0xCC60, Break::HangulLv), (0xCC61, 0xCC7B, Break::HangulLvt), (0xCC7C, 0xCC7C, Break::HangulLv),
+    (0xCC7D, 0xCC97, Break::HangulLvt), (0xCC98, 0xCC98, Break::HangulLv), (0xCC99, 0xCCB3, Break::HangulLvt),
+    (0xCCB4, 0xCCB4, Break::HangulLv), (0xCCB5, 0xCCCF, Break::HangulLvt), (0xCCD0, 0xCCD0, Break::HangulLv),
+    (0xCCD1, 0xCCEB, Break::HangulLvt), (0xCCEC, 0xCCEC, Break::HangulLv), (0xCCED, 0xCD07, Break::HangulLvt),
+    (0xCD08, 0xCD08, Break::HangulLv), (0xCD09, 0xCD23, Break::HangulLvt), (0xCD24, 0xCD24, Break::HangulLv),
+    (0xCD25, 0xCD3F, Break::HangulLvt), (0xCD40, 0xCD40, Break::HangulLv), (0xCD41, 0xCD5B, Break::HangulLvt),
+    (0xCD5C, 0xCD5C, Break::HangulLv), (0xCD5D, 0xCD77, Break::HangulLvt), (0xCD78, 0xCD78, Break::HangulLv),
+    (0xCD79, 0xCD93, Break::HangulLvt), (0xCD94, 0xCD94, Break::HangulLv), (0xCD95, 0xCDAF, Break::HangulLvt),
+    (0xCDB0, 0xCDB0, Break::HangulLv), (0xCDB1, 0xCDCB, Break::HangulLvt), (0xCDCC, 0xCDCC, Break::HangulLv),
+    (0xCDCD, 0xCDE7, Break::HangulLvt), (0xCDE8, 0xCDE8, Break::HangulLv), (0xCDE9, 0xCE03, Break::HangulLvt),
+    (0xCE04, 0xCE04, Break::HangulLv), (0xCE05, 0xCE1F, Break::HangulLvt), (0xCE20, 0xCE20, Break::HangulLv),
+    (0xCE21, 0xCE3B, Break::HangulLvt), (0xCE3C, 0xCE3C, Break::HangulLv), (0xCE3D, 0xCE57, Break::HangulLvt),
+    (0xCE58, 0xCE58, Break::HangulLv), (0xCE59, 0xCE73, Break::HangulLvt), (0xCE74, 0xCE74, Break::HangulLv),
+    (0xCE75, 0xCE8F, Break::HangulLvt), (0xCE90, 0xCE90, Break::HangulLv), (0xCE91, 0xCEAB, Break::HangulLvt),
+    (0xCEAC, 0xCEAC, Break::HangulLv), (0xCEAD, 0xCEC7, Break::HangulLvt), (0xCEC8, 0xCEC8, Break::HangulLv),
+    (0xCEC9, 0xCEE3, Break::HangulLvt), (0xCEE4, 0xCEE4, Break::HangulLv), (0xCEE5, 0xCEFF, Break::HangulLvt),
+    (0xCF00, 0xCF00, Break::HangulLv), (0xCF01, 0xCF1B, Break::HangulLvt), (0xCF1C, 0xCF1C, Break::HangulLv),
+    (0xCF1D, 0xCF37, Break::HangulLvt), (0xCF38, 0xCF38, Break::HangulLv), (0xCF39, 0xCF53, Break::HangulLvt),
+    (0xCF54, 0xCF54, Break::HangulLv), (0xCF55, 0xCF6F, Break::HangulLvt), (0xCF70, 0xCF70, Break::HangulLv),
+    (0xCF71, 0xCF8B, Break::HangulLvt), (0xCF8C, 0xCF8C, Break::HangulLv), (0xCF8D, 0xCFA7, Break::HangulLvt),
+    (0xCFA8, 0xCFA8, Break::HangulLv), (0xCFA9, 0xCFC3, Break::HangulLvt), (0xCFC4, 0xCFC4, Break::HangulLv),
+    (0xCFC5, 0xCFDF, Break::HangulLvt), (0xCFE0, 0xCFE0, Break::HangulLv), (0xCFE1, 0xCFFB, Break::HangulLvt),
+    (0xCFFC, 0xCFFC, Break::HangulLv), (0xCFFD, 0xD017, Break::HangulLvt), (0xD018, 0xD018, Break::HangulLv),
+    (0xD019, 0xD033, Break::HangulLvt), (0xD034, 0xD034, Break::HangulLv), (0xD035, 0xD04F, Break::HangulLvt),
+    (0xD050, 0xD050, Break::HangulLv), (0xD051, 0xD06B, Break::HangulLvt), (0xD06C, 0xD06C, Break::HangulLv),
+    (0xD06D, 0xD087, Break::HangulLvt), (0xD088, 0xD088, Break::HangulLv), (0xD089, 0xD0A3, Break::HangulLvt),
+    (0xD0A4, 0xD0A4, Break::HangulLv), (0xD0A5, 0xD0BF, Break::HangulLvt), (0xD0C0, 0xD0C0, Break::HangulLv),
+    (0xD0C1, 0xD0DB, Break::HangulLvt), (0xD0DC, 0xD0DC, Break::HangulLv), (0xD0DD, 0xD0F7, Break::HangulLvt),
+    (0xD0F8, 0xD0F8, Break::HangulLv), (0xD0F9, 0xD113, Break::HangulLvt), (0xD114, 0xD114, Break::HangulLv),
+    (0xD115, 0xD12F, Break::HangulLvt), (0xD130, 0xD130, Break::HangulLv), (0xD131, 0xD14B, Break::HangulLvt),
+    (0xD14C, 0xD14C, Break::HangulLv), (0xD14D, 0xD167, Break::HangulLvt), (0xD168, 0xD168, Break::HangulLv),
+    (0xD169, 0xD183, Break::HangulLvt), (0xD184, 0xD184, Break::HangulLv), (0xD185, 0xD19F, Break::HangulLvt),
+    (0xD1A0, 0xD1A0, Break::HangulLv), (0xD1A1, 0xD1BB, Break::HangulLvt), (0xD1BC, 0xD1BC, Break::HangulLv),
+    (0xD1BD, 0xD1D7, Break::HangulLvt), (0xD1D8, 0xD1D8, Break::HangulLv), (0xD1D9, 0xD1F3, Break::HangulLvt),
+    (0xD1F4, 0xD1F4, Break::HangulLv), (0xD1F5, 0xD20F, Break::HangulLvt), (0xD210, 0xD210, Break::HangulLv),
+    (0xD211, 0xD22B, Break::HangulLvt), (0xD22C, 0xD22C, Break::HangulLv), (0xD22D, 0xD247, Break::HangulLvt),
+    (0xD248, 0xD248, Break::HangulLv), (0xD249, 0xD263, Break::HangulLvt), (0xD264, 0xD264, Break::HangulLv),
+    (0xD265, 0xD27F, Break::HangulLvt), (0xD280, 0xD280, Break::HangulLv), (0xD281, 0xD29B, Break::HangulLvt),
+    (0xD29C, 0xD29C, Break::HangulLv), (0xD29D, 0xD2B7, Break::HangulLvt), (0xD2B8, 0xD2B8, Break::HangulLv),
+    (0xD2B9, 0xD2D3, Break::HangulLvt), (0xD2D4, 0xD2D4, Break::HangulLv), (0xD2D5, 0xD2EF, Break::HangulLvt),
+    (0xD2F0, 0xD2F0, Break::HangulLv), (0xD2F1, 0xD30B, Break::HangulLvt), (0xD30C, 0xD30C, Break::HangulLv),
+    (0xD30D, 0xD327, Break::HangulLvt), (0xD328, 0xD328, Break::HangulLv), (0xD329, 0xD343, Break::HangulLvt),
+    (0xD344, 0xD344, Break::HangulLv), (0xD345, 0xD35F, Break::HangulLvt), (0xD360, 0xD360, Break::HangulLv),
+    (0xD361, 0xD37B, Break::HangulLvt), (0xD37C, 0xD37C, Break::HangulLv), (0xD37D, 0xD397, Break::HangulLvt),
+    (0xD398, 0xD398, Break::HangulLv), (0xD399, 0xD3B3, Break::HangulLvt), (0xD3B4, 0xD3B4, Break::HangulLv),
+    (0xD3B5, 0xD3CF, Break::HangulLvt), (0xD3D0, 0xD3D0, Break::HangulLv), (0xD3D1, 0xD3EB, Break::HangulLvt),
+    (0xD3EC, 0xD3EC, Break::HangulLv), (0xD3ED, 0xD407, Break::HangulLvt), (0xD408, 0xD408, Break::HangulLv),
+    (0xD409, 0xD423, Break::HangulLvt), (0xD424, 0xD424, Break::HangulLv), (0xD425, 0xD43F, Break::HangulLvt),
+    (0xD440, 0xD440, Break::HangulLv), (0xD441, 0xD45B, Break::HangulLvt), (0xD45C, 0xD45C, Break::HangulLv),
+    (0xD45D, 0xD477, Break::HangulLvt), (0xD478, 0xD478, Break::HangulLv), (0xD479, 0xD493, Break::HangulLvt),
+    (0xD494, 0xD494, Break::HangulLv), (0xD495, 0xD4AF, Break::HangulLvt), (0xD4B0, 0xD4B0, Break::HangulLv),
+    (0xD4B1, 0xD4CB, Break::HangulLvt), (0xD4CC, 0xD4CC, Break::HangulLv), (0xD4CD, 0xD4E7, Break::HangulLvt),
+    (0xD4E8, 0xD4E8, Break::HangulLv), (0xD4E9, 0xD503, Break::HangulLvt), (0xD504, 0xD504, Break::HangulLv),
+    (0xD505, 0xD51F, Break::HangulLvt), (0xD520, 0xD520, Break::HangulLv), (0xD521, 0xD53B, Break::HangulLvt),
+    (0xD53C, 0xD53C, Break::HangulLv), (0xD53D, 0xD557, Break::HangulLvt), (0xD558, 0xD558, Break::HangulLv),
+    (0xD559, 0xD573, Break::HangulLvt), (0xD574, 0xD574, Break::HangulLv), (0xD575, 0xD58F, Break::HangulLvt),
+    (0xD590, 0xD590, Break::HangulLv), (0xD591, 0xD5AB, Break::HangulLvt), (0xD5AC, 0xD5AC, Break::HangulLv),
+    (0xD5AD, 0xD5C7, Break::HangulLvt), (0xD5C8, 0xD5C8, Break::HangulLv), (0xD5C9, 0xD5E3, Break::HangulLvt),
+    (0xD5E4, 0xD5E4, Break::HangulLv), (0xD5E5, 0xD5FF, Break::HangulLvt), (0xD600, 0xD600, Break::HangulLv),
+    (0xD601, 0xD61B, Break::HangulLvt), (0xD61C, 0xD61C, Break::HangulLv), (0xD61D, 0xD637, Break::HangulLvt),
+    (0xD638, 0xD638, Break::HangulLv), (0xD639, 0xD653, Break::HangulLvt), (0xD654, 0xD654, Break::HangulLv),
+    (0xD655, 0xD66F, Break::HangulLvt), (0xD670, 0xD670, Break::HangulLv), (0xD671, 0xD68B, Break::HangulLvt),
+    (0xD68C, 0xD68C, Break::HangulLv), (0xD68D, 0xD6A7, Break::HangulLvt), (0xD6A8, 0xD6A8, Break::HangulLv),
+    (0xD6A9, 0xD6C3, Break::HangulLvt), (0xD6C4, 0xD6C4, Break::HangulLv), (0xD6C5, 0xD6DF, Break::HangulLvt),
+    (0xD6E0, 0xD6E0, Break::HangulLv), (0xD6E1, 0xD6FB, Break::HangulLvt), (0xD6FC, 0xD6FC, Break::HangulLv),
+    (0xD6FD, 0xD717, Break::HangulLvt), (0xD718, 0xD718, Break::HangulLv), (0xD719, 0xD733, Break::HangulLvt),
+    (0xD734, 0xD734, Break::HangulLv), (0xD735, 0xD74F, Break::HangulLvt), (0xD750, 0xD750, Break::HangulLv),
+    (0xD751, 0xD76B, Break::HangulLvt), (0xD76C, 0xD76C, Break::HangulLv), (0xD76D, 0xD787, Break::HangulLvt),
+    (0xD788, 0xD788, Break::HangulLv), (0xD789, 0xD7A3, Break::HangulLvt), (0xD7B0, 0xD7C6, Break::HangulV),
+    (0xD7CB, 0xD7FB, Break::HangulT), (0xFB1E, 0xFB1E, Break::Extend), (0xFE00, 0xFE0F, Break::Extend),
+    (0xFE20, 0xFE2F, Break::Extend), (0xFEFF, 0xFEFF, Break::Control), (0xFF9E, 0xFF9F, Break::Extend),
+    (0xFFF0, 0xFFFB, Break::Control), (0x101FD, 0x101FD, Break::Extend), (0x102E0, 0x102E0, Break::Extend),
+    (0x10376, 0x1037A, Break::Extend), (0x10A01, 0x10A03, Break::Extend), (0x10A05, 0x10A06, Break::Extend),
+    (0x10A0C, 0x10A0F, Break::Extend), (0x10A38, 0x10A3A, Break::Extend), (0x10A3F, 0x10A3F, Break::Extend),
+    (0x10AE5, 0x10AE6, Break::Extend), (0x10D24, 0x10D27, Break::Extend), (0x10D69, 0x10D6D, Break::Extend),
+    (0x10EAB, 0x10EAC, Break::Extend), (0x10EFA, 0x10EFF, Break::Extend), (0x10F46, 0x10F50, Break::Extend),
+    (0x10F82, 0x10F85, Break::Extend), (0x11000, 0x11000, Break::SpacingMark), (0x11001, 0x11001, Break::Extend),
+    (0x11002, 0x11002, Break::SpacingMark), (0x11038, 0x11046, Break::Extend), (0x11070, 0x11070, Break::Extend),
+    (0x11073, 0x11074, Break::Extend), (0x1107F, 0x11081, Break::Extend), (0x11082, 0x11082, Break::SpacingMark),
+    (0x110B0, 0x110B2, Break::SpacingMark), (0x110B3, 0x110B6, Break::Extend), (0x110B7, 0x110B8, Break::SpacingMark),
+    (0x110B9, 0x110BA, Break::Extend), (0x110BD, 0x110BD, Break::Prepend), (0x110C2, 0x110C2, Break::Extend),
+    (0x110CD, 0x110CD, Break::Prepend), (0x11100, 0x11102, Break::Extend), (0x11127, 0x1112B, Break::Extend),
+    (0x1112C, 0x1112C, Break::SpacingMark), (0x1112D, 0x11134, Break::Extend), (0x11145, 0x11146, Break::SpacingMark),
+    (0x11173, 0x11173, Break::Extend), (0x11180, 0x11181, Break::Extend), (0x11182, 0x11182, Break::SpacingMark),
+    (0x111B3, 0x111B5, Break::SpacingMark), (0x111B6, 0x111BE, Break::Extend), (0x111BF, 0x111BF, Break::SpacingMark),
+    (0x111C0, 0x111C0, Break::Extend), (0x111C2, 0x111C3, Break::Prepend), (0x111C9, 0x111CC, Break::Extend),
+    (0x111CE, 0x111CE, Break::SpacingMark), (0x111CF, 0x111CF, Break::Extend), (0x1122C, 0x1122E, Break::SpacingMark),
+    (0x1122F, 0x11231, Break::Extend), (0x11232, 0x11233, Break::SpacingMark), (0x11234, 0x11237, Break::Extend),
+    (0x1123E, 0x1123E, Break::Extend), (0x11241, 0x11241, Break::Extend), (0x112DF, 0x112DF, Break::Extend),
+    (0x112E0, 0x112E2, Break::SpacingMark), (0x112E3, 0x112EA, Break::Extend), (0x11300, 0x11301, Break::Extend),
+    (0x11302, 0x11303, Break::SpacingMark), (0x1133B, 0x1133C, Break::Extend), (0x1133E, 0x1133E, Break::Extend),
+    (0x1133F, 0x1133F, Break::SpacingMark), (0x11340, 0x11340, Break::Extend), (0x11341, 0x11344, Break::SpacingMark),
+    (0x11347, 0x11348, Break::SpacingMark), (0x1134B, 0x1134C, Break::SpacingMark), (0x1134D, 0x1134D, Break::Extend),
+    (0x11357, 0x11357, Break::Extend), (0x11362, 0x11363, Break::SpacingMark), (0x11366, 0x1136C, Break::Extend),
+    (0x11370, 0x11374, Break::Extend), (0x113B8, 0x113B8, Break::Extend), (0x113B9, 0x113BA, Break::SpacingMark),
+    (0x113BB, 0x113C0, Break::Extend), (0x113C2, 0x113C2, Break::Extend), (0x113C5, 0x113C5, Break::Extend),
+    (0x113C7, 0x113C9, Break::Extend), (0x113CA, 0x113CA, Break::SpacingMark), (0x113CC, 0x113CD, Break::SpacingMark),
+    (0x113CE, 0x113D0, Break::Extend), (0x113D1, 0x113D1, Break::Prepend), (0x113D2, 0x113D2, Break::Extend),
+    (0x113E1, 0x113E2, Break::Extend), (0x11435, 0x11437, Break::SpacingMark), (0x11438, 0x1143F, Break::Extend),
+    (0x11440, 0x11441, Break::SpacingMark), (0x11442, 0x11444, Break::Extend), (0x11445, 0x11445, Break::SpacingMark),
+    (0x11446, 0x11446, Break::Extend), (0x1145E, 0x1145E, Break::Extend), (0x114B0, 0x114B0, Break::Extend),
+    (0x114B1, 0x114B2, Break::SpacingMark), (0x114B3, 0x114B8, Break::Extend), (0x114B9, 0x114B9, Break::SpacingMark),
+    (0x114BA, 0x114BA, Break::Extend), (0x114BB, 0x114BC, Break::SpacingMark), (0x114BD, 0x114BD, Break::Extend),
+    (0x114BE, 0x114BE, Break::SpacingMark), (0x114BF, 0x114C0, Break::Extend), (0x114C1, 0x114C1, Break::SpacingMark),
+    (0x114C2, 0x114C3, Break::Extend), (0x115AF, 0x115AF, Break::Extend), (0x115B0, 0x115B1, Break::SpacingMark),
+    (0x115B2, 0x115B5, Break::Extend), (0x115B8, 0x115BB, Break::SpacingMark), (0x115BC, 0x115BD, Break::Extend),
+    (0x115BE, 0x115BE, Break::SpacingMark), (0x115BF, 0x115C0, Break::Extend), (0x115DC, 0x115DD, Break::Extend),
+    (0x11630, 0x11632, Break::SpacingMark), (0x11633, 0x1163A, Break::Extend), (0x1163B, 0x1163C, Break::SpacingMark),
+    (0x1163D, 0x1163D, Break::Extend), (0x1163E, 0x1163E, Break::SpacingMark), (0x1163F, 0x11640, Break::Extend),
+    (0x116AB, 0x116AB, Break::Extend), (0x116AC, 0x116AC, Break::SpacingMark), (0x116AD, 0x116AD, Break::Extend),
+    (0x116AE, 0x116AF, Break::SpacingMark), (0x116B0, 0x116B7, Break::Extend), (0x1171D, 0x1171D, Break::Extend),
+    (0x1171E, 0x1171E, Break::SpacingMark), (0x1171F, 0x1171F, Break::Extend), (0x11722, 0x11725, Break::Extend),
+    (0x11726, 0x11726, Break::SpacingMark), (0x11727, 0x1172B, Break::Extend), (0x1182C, 0x1182E, Break::SpacingMark),
+    (0x1182F, 0x11837, Break::Extend), (0x11838, 0x11838, Break::SpacingMark), (0x11839, 0x1183A, Break::Extend),
+    (0x11930, 0x11930, Break::Extend), (0x11931, 0x11935, Break::SpacingMark), (0x11937, 0x11938, Break::SpacingMark),
+    (0x1193B, 0x1193E, Break::Extend), (0x1193F, 0x1193F, Break::Prepend), (0x11940, 0x11940, Break::SpacingMark),
+    (0x11941, 0x11941, Break::Prepend), (0x11942, 0x11942, Break::SpacingMark), (0x11943, 0x11943, Break::Extend),
+    (0x119D1, 0x119D3, Break::SpacingMark), (0x119D4, 0x119D7, Break::Extend), (0x119DA, 0x119DB, Break::Extend),
+    (0x119DC, 0x119DF, Break::SpacingMark), (0x119E0, 0x119E0, Break::Extend), (0x119E4, 0x119E4, Break::SpacingMark),
+    (0x11A01, 0x11A0A, Break::Extend), (0x11A33, 0x11A38, Break::Extend), (0x11A39, 0x11A39, Break::SpacingMark),
+    (0x11A3B, 0x11A3E, Break::Extend), (0x11A47, 0x11A47, Break::Extend), (0x11A51, 0x11A56, Break::Extend),
+    (0x11A57, 0x11A58, Break::SpacingMark), (0x11A59, 0x11A5B, Break::Extend), (0x11A84, 0x11A89, Break::Prepend),
+    (0x11A8A, 0x11A96, Break::Extend), (0x11A97, 0x11A97, Break::SpacingMark), (0x11A98, 0x11A99, Break::Extend),
+    (0x11B60, 0x11B60, Break::Extend), (0x11B61, 0x11B61, Break::SpacingMark), (0x11B62, 0x11B64, Break::Extend),
+    (0x11B65, 0x11B65, Break::SpacingMark), (0x11B66, 0x11B66, Break::Extend), (0x11B67, 0x11B67, Break::SpacingMark),
+    (0x11C2F, 0x11C2F, Break::SpacingMark), (0x11C30, 0x11C36, Break::Extend), (0x11C38, 0x11C3D, Break::Extend),
+    (0x11C3E, 0x11C3E, Break::SpacingMark), (0x11C3F, 0x11C3F, Break::Extend), (0x11C92, 0x11CA7, Break::Extend),
+    (0x11CA9, 0x11CA9, Break::SpacingMark), (0x11CAA, 0x11CB0, Break::Extend), (0x11CB1, 0x11CB1, Break::SpacingMark),
+    (0x11CB2, 0x11CB3, Break::Extend), (0x11CB4, 0x11CB4, Break::SpacingMark), (0x11CB5, 0x11CB6, Break::Extend),
+    (0x11D31, 0x11D36, Break::Extend), (0x11D3A, 0x11D3A, Break::Extend), (0x11D3C, 0x11D3D, Break::Extend),
+    (0x11D3F, 0x11D45, Break::Extend), (0x11D46, 0x11D46, Break::Prepend), (0x11D47, 0x11D47, Break::Extend),
+    (0x11D8A, 0x11D8E, Break::SpacingMark), (0x11D90, 0x11D91, Break::Extend), (0x11D93, 0x11D94, Break::SpacingMark),
+    (0x11D95, 0x11D95, Break::Extend), (0x11D96, 0x11D96, Break::SpacingMark), (0x11D97, 0x11D97, Break::Extend),
+    (0x11EF3, 0x11EF4, Break::Extend), (0x11EF5, 0x11EF6, Break::SpacingMark), (0x11F00, 0x11F01, Break::Extend),
+    (0x11F02, 0x11F02, Break::Prepend), (0x11F03, 0x11F03, Break::SpacingMark), (0x11F34, 0x11F35, Break::SpacingMark),
+    (0x11F36, 0x11F3A, Break::Extend), (0x11F3E, 0x11F3F, Break::SpacingMark), (0x11F40, 0x11F42, Break::Extend),
+    (0x11F5A, 0x11F5A, Break::Extend), (0x13430, 0x1343F, Break::Control), (0x13440, 0x13440, Break::Extend),
+    (0x13447, 0x13455, Break::Extend), (0x1611E, 0x16129, Break::Extend), (0x1612A, 0x1612C, Break::SpacingMark),
+    (0x1612D, 0x1612F, Break::Extend), (0x16AF0, 0x16AF4, Break::Extend), (0x16B30, 0x16B36, Break::Extend),
+    (0x16D63, 0x16D63, Break::HangulV), (0x16D67, 0x16D6A, Break::HangulV), (0x16F4F, 0x16F4F, Break::Extend),
+    (0x16F51, 0x16F87, Break::SpacingMark), (0x16F8F, 0x16F92, Break::Extend), (0x16FE4, 0x16FE4, Break::Extend),
+    (0x16FF0, 0x16FF1, Break::Extend), (0x1BC9D, 0x1BC9E, Break::Extend), (0x1BCA0, 0x1BCA3, Break::Control),
+    (0x1CF00, 0x1CF2D, Break::Extend), (0x1CF30, 0x1CF46, Break::Extend), (0x1D165, 0x1D169, Break::Extend),
+    (0x1D16D, 0x1D172, Break::Extend), (0x1D173, 0x1D17A, Break::Control), (0x1D17B, 0x1D182, Break::Extend),
+    (0x1D185, 0x1D18B, Break::Extend), (0x1D1AA, 0x1D1AD, Break::Extend), (0x1D242, 0x1D244, Break::Extend),
+    (0x1DA00, 0x1DA36, Break::Extend), (0x1DA3B, 0x1DA6C, Break::Extend), (0x1DA75, 0x1DA75, Break::Extend),
+    (0x1DA84, 0x1DA84, Break::Extend), (0x1DA9B, 0x1DA9F, Break::Extend), (0x1DAA1, 0x1DAAF, Break::Extend),
+    (0x1E000, 0x1E006, Break::Extend), (0x1E008, 0x1E018, Break::Extend), (0x1E01B, 0x1E021, Break::Extend),
+    (0x1E023, 0x1E024, Break::Extend), (0x1E026, 0x1E02A, Break::Extend), (0x1E08F, 0x1E08F, Break::Extend),
+    (0x1E130, 0x1E136, Break::Extend), (0x1E2AE, 0x1E2AE, Break::Extend), (0x1E2EC, 0x1E2EF, Break::Extend),
+    (0x1E4EC, 0x1E4EF, Break::Extend), (0x1E5EE, 0x1E5EF, Break::Extend), (0x1E6E3, 0x1E6E3, Break::Extend),
+    (0x1E6E6, 0x1E6E6, Break::Extend), (0x1E6EE, 0x1E6EF, Break::Extend), (0x1E6F5, 0x1E6F5, Break::Extend),
+    (0x1E8D0, 0x1E8D6, Break::Extend), (0x1E944, 0x1E94A, Break::Extend), (0x1F1E6, 0x1F1FF, Break::RegionalIndicator),
+    (0x1F3FB, 0x1F3FF, Break::Extend), (0xE0000, 0xE001F, Break::Control), (0xE0020, 0xE007F, Break::Extend),
+    (0xE0080, 0xE00FF, Break::Control), (0xE0100, 0xE01EF, Break::Extend), (0xE01F0, 0xE0FFF, Break::Control),
+];
+
+/// Code points with the Extended_Pictographic property
+pub const EXTENDED_PICTOGRAPHIC: &[(u32, u32)] = &[
+    (0x00A9, 0x00A9), (0x00AE, 0x00AE), (0x203C, 0x203C), (0x2049, 0x2049), (0x2122, 0x2122),
+    (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA), (0x231A, 0x231B), (0x2328, 0x2328),
+    (0x23CF, 0x23CF), (0x23E9, 0x23F3), (0x23F8, 0x23FA), (0x24C2, 0x24C2), (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FE), (0x2600, 0x2604), (0x260E, 0x260E),
+    (0x2611, 0x2611), (0x2614, 0x2615), (0x2618, 0x2618), (0x261D, 0x261D), (0x2620, 0x2620),
+    (0x2622, 0x2623), (0x2626, 0x2626), (0x262A, 0x262A), (0x262E, 0x262F), (0x2638, 0x263A),
+    (0x2640, 0x2640), (0x2642, 0x2642), (0x2648, 0x2653), (0x265F, 0x2660), (0x2663, 0x2663),
+    (0x2665, 0x2666), (0x2668, 0x2668), (0x267B, 0x267B), (0x267E, 0x267F), (0x2692, 0x2697),
+    (0x2699, 0x2699), (0x269B, 0x269C), (0x26A0, 0x26A1), (0x26A7, 0x26A7), (0x26AA, 0x26AB),
+    (0x26B0, 0x26B1), (0x26BD, 0x26BE), (0x26C4, 0x26C5), (0x26C8, 0x26C8), (0x26CE, 0x26CF),
+    (0x26D1, 0x26D1), (0x26D3, 0x26D4), (0x26E9, 0x26EA), (0x26F0, 0x26F5), (0x26F7, 0x26FA),
+    (0x26FD, 0x26FD), (0x2702, 0x2702), (0x2705, 0x2705), (0x2708, 0x270D), (0x270F, 0x270F),
+    (0x2712, 0x2712), (0x2714, 0x2714), (0x2716, 0x2716), (0x271D, 0x271D), (0x2721, 0x2721),
+    (0x2728, 0x2728), (0x2733, 0x2734), (0x2744, 0x2744), (0x2747, 0x2747), (0x274C, 0x274C),
+    (0x274E, 0x274E), (0x2753, 0x2755), (0x2757, 0x2757), (0x2763, 0x2764), (0x2795, 0x2797),
+    (0x27A1, 0x27A1), (0x27B0, 0x27B0), (0x27BF, 0x27BF), (0x2934, 0x2935), (0x2B05, 0x2B07),
+    (0x2B1B, 0x2B1C), (0x2B50, 0x2B50), (0x2B55, 0x2B55), (0x3030, 0x3030), (0x303D, 0x303D),
+    (0x3297, 0x3297), (0x3299, 0x3299), (0x1F004, 0x1F004), (0x1F02C, 0x1F02F), (0x1F094, 0x1F09F),
+    (0x1F0AF, 0x1F0B0), (0x1F0C0, 0x1F0C0), (0x1F0CF, 0x1F0D0), (0x1F0F6, 0x1F0FF), (0x1F170, 0x1F171),
+    (0x1F17E, 0x1F17F), (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A), (0x1F1AE, 0x1F1E5), (0x1F201, 0x1F20F),
+    (0x1F21A, 0x1F21A), (0x1F22F, 0x1F22F), (0x1F232, 0x1F23A), (0x1F23C, 0x1F23F), (0x1F249, 0x1F25F),
+    (0x1F266, 0x1F321), (0x1F324, 0x1F393), (0x1F396, 0x1F397), (0x1F399, 0x1F39B), (0x1F39E, 0x1F3F0),
+    (0x1F3F3, 0x1F3F5), (0x1F3F7, 0x1F3FA), (0x1F400, 0x1F4FD), (0x1F4FF, 0x1F53D), (0x1F549, 0x1F54E),
+    (0x1F550, 0x1F567), (0x1F56F, 0x1F570), (0x1F573, 0x1F57A), (0x1F587, 0x1F587), (0x1F58A, 0x1F58D),
+    (0x1F590, 0x1F590), (0x1F595, 0x1F596), (0x1F5A4, 0x1F5A5), (0x1F5A8, 0x1F5A8), (0x1F5B1, 0x1F5B2),
+    (0x1F5BC, 0x1F5BC), (0x1F5C2, 0x1F5C4), (0x1F5D1, 0x1F5D3), (0x1F5DC, 0x1F5DE), (0x1F5E1, 0x1F5E1),
+    (0x1F5E3, 0x1F5E3), (0x1F5E8, 0x1F5E8), (0x1F5EF, 0x1F5EF), (0x1F5F3, 0x1F5F3), (0x1F5FA, 0x1F64F),
+    (0x1F680, 0x1F6C5), (0x1F6CB, 0x1F6D2), (0x1F6D5, 0x1F6E5), (0x1F6E9, 0x1F6E9), (0x1F6EB, 0x1F6F0),
+    (0x1F6F3, 0x1F6FF), (0x1F7DA, 0x1F7FF), (0x1F80C, 0x1F80F), (0x1F848, 0x1F84F), (0x1F85A, 0x1F85F),
+    (0x1F888, 0x1F88F), (0x1F8AE, 0x1F8AF), (0x1F8BC, 0x1F8BF), (0x1F8C2, 0x1F8CF), (0x1F8D9, 0x1F8FF),
+    (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1F9FF), (0x1FA58, 0x1FA5F), (0x1FA6E, 0x1FAFF),
+    (0x1FC00, 0x1FFFD),
+];
+
+/// Indic_Conjunct_Break of the code points that are not None
+pub const INDIC_CONJUNCT_BREAK: &[(u32, u32, Conjunct)] = &[
+    (0x0300, 0x036F, Conjunct::Extend), (0x0483, 0x0489, Conjunct::Extend), (0x0591, 0x05BD, Conjunct::Extend),
+    (0x05BF, 0x05BF, Conjunct::Extend), (0x05C1, 0x05C2, Conjunct::Extend), (0x05C4, 0x05C5, Conjunct::Extend),
+    (0x05C7, 0x05C7, Conjunct::Extend), (0x0610, 0x061A, Conjunct::Extend), (0x064B, 0x065F, Conjunct::Extend),
+    (0x0670, 0x0670, Conjunct::Extend), (0x06D6, 0x06DC, Conjunct::Extend), (0x06DF, 0x06E4, Conjunct::Extend),
+    (0x06E7, 0x06E8, Conjunct::Extend), (0x06EA, 0x06ED, Conjunct::Extend), (0x0711, 0x0711, Conjunct::Extend),
+    (0x0730, 0x074A, Conjunct::Extend), (0x07A6, 0x07B0, Conjunct::Extend), (0x07EB, 0x07F3, Conjunct::Extend),
+    (0x07FD, 0x07FD, Conjunct::Extend), (0x0816, 0x0819, Conjunct::Extend), (0x081B, 0x0823, Conjunct::Extend),
+    (0x0825, 0x0827, Conjunct::Extend), (0x0829, 0x082D, Conjunct::Extend), (0x0859, 0x085B, Conjunct::Extend),
+    (0x0897, 0x089F, Conjunct::Extend), (0x08CA, 0x08E1, Conjunct::Extend), (0x08E3, 0x0902, Conjunct::Extend),
+    (0x0915, 0x0939, Conjunct::Consonant), (0x093A, 0x093A, Conjunct::Extend), (0x093C, 0x093C, Conjunct::Extend),
+    (0x0941, 0x0948, Conjunct::Extend), (0x094D, 0x094D, Conjunct::Linker), (0x0951, 0x0957, Conjunct::Extend),
+    (0x0958, 0x095F, Conjunct::Consonant), (0x0962, 0x0963, Conjunct::Extend), (0x0978, 0x097F, Conjunct::Consonant),
+    (0x0981, 0x0981, Conjunct::Extend), (0x0995, 0x09A8, Conjunct::Consonant), (0x09AA, 0x09B0, Conjunct::Consonant),
+    (0x09B2, 0x09B2, Conjunct::Consonant), (0x09B6, 0x09B9, Conjunct::Consonant), (0x09BC, 0x09BC, Conjunct::Extend),
+    (0x09BE, 0x09BE, Conjunct::Extend), (0x09C1, 0x09C4, Conjunct::Extend), (0x09CD, 0x09CD, Conjunct::Linker),
+    (0x09D7, 0x09D7, Conjunct::Extend), (0x09DC, 0x09DD, Conjunct::Consonant), (0x09DF, 0x09DF, Conjunct::Consonant),
+    (0x09E2, 0x09E3, Conjunct::Extend), (0x09F0, 0x09F1, Conjunct::Consonant), (0x09FE, 0x09FE, Conjunct::Extend),
+    (0x0A01, 0x0A02, Conjunct::Extend), (0x0A3C, 0x0A3C, Conjunct::Extend), (0x0A41, 0x0A42, Conjunct::Extend),
+    (0x0A47, 0x0A48, Conjunct::Extend), (0x0A4B, 0x0A4D, Conjunct::Extend), (0x0A51, 0x0A51, Conjunct::Extend),
+    (0x0A70, 0x0A71, Conjunct::Extend), (0x0A75, 0x0A75, Conjunct::Extend), (0x0A81, 0x0A82, Conjunct::Extend),
+    (0x0A95, 0x0AA8, Conjunct::Consonant), (0x0AAA, 0x0AB0, Conjunct::Consonant), (0x0AB2, 0x0AB3, Conjunct::Consonant),
+    (0x0AB5, 0x0AB9, Conjunct::Consonant), (0x0ABC, 0x0ABC, Conjunct::Extend), (0x0AC1, 0x0AC5, Conjunct::Extend),
+    (0x0AC7, 0x0AC8, Conjunct::Extend), (0x0ACD, 0x0ACD, Conjunct::Linker), (0x0AE2, 0x0AE3, Conjunct::Extend),
+    (0x0AF9, 0x0AF9, Conjunct::Consonant), (0x0AFA, 0x0AFF, Conjunct::Extend), (0x0B01, 0x0B01, Conjunct::Extend),
+    (0x0B15, 0x0B28, Conjunct::Consonant), (0x0B2A, 0x0B30, Conjunct::Consonant), (0x0B32, 0x0B33, Conjunct::Consonant),
+    (0x0B35, 0x0B39, Conjunct::Consonant), (0x0B3C, 0x0B3C, Conjunct::Extend), (0x0B3E, 0x0B3F, Conjunct::Extend),
+    (0x0B41, 0x0B44, Conjunct::Extend), (0x0B4D, 0x0B4D, Conjunct::Linker), (0x0B55, 0x0B57, Conjunct::Extend),
+    (0x0B5C, 0x0B5D, Conjunct::Consonant), (0x0B5F, 0x0B5F, Conjunct::Consonant), (0x0B62, 0x0B63, Conjunct::Extend),
+    (0x0B71, 0x0B71, Conjunct::Consonant), (0x0B82, 0x0B82, Conjunct::Extend), (0x0BBE, 0x0BBE, Conjunct::Extend),
+    (0x0BC0, 0x0BC0, Conjunct::Extend), (0x0BCD, 0x0BCD, Conjunct::Extend), (0x0BD7, 0x0BD7, Conjunct::Extend),
+    (0x0C00, 0x0C00, Conjunct::Extend), (0x0C04, 0x0C04, Conjunct::Extend), (0x0C15, 0x0C28, Conjunct::Consonant),
+    (0x0C2A, 0x0C39, Conjunct::Consonant), (0x0C3C, 0x0C3C, Conjunct::Extend), (0x0C3E, 0x0C40, Conjunct::Extend),
+    (0x0C46, 0x0C48, Conjunct::Extend), (0x0C4A, 0x0C4C, Conjunct::Extend), (0x0C4D, 0x0C4D, Conjunct::Linker),
+    (0x0C55, 0x0C56, Conjunct::Extend), (0x0C58, 0x0C5A, Conjunct::Consonant), (0x0C62, 0x0C63, Conjunct::Extend),
+    (0x0C81, 0x0C81, Conjunct::Extend), (0x0CBC, 0x0CBC, Conjunct::Extend), (0x0CBF, 0x0CC0, Conjunct::Extend),
+    (0x0CC2, 0x0CC2, Conjunct::Extend), (0x0CC6, 0x0CC8, Conjunct::Extend), (0x0CCA, 0x0CCD, Conjunct::Extend),
+    (0x0CD5, 0x0CD6, Conjunct::Extend), (0x0CE2, 0x0CE3, Conjunct::Extend), (0x0D00, 0x0D01, Conjunct::Extend),
+    (0x0D15, 0x0D3A, Conjunct::Consonant), (0x0D3B, 0x0D3C, Conjunct::Extend), (0x0D3E, 0x0D3E, Conjunct::Extend),
+    (0x0D41, 0x0D44, Conjunct::Extend), (0x0D4D, 0x0D4D, Conjunct::Linker), (0x0D57, 0x0D57, Conjunct::Extend),
+    (0x0D62, 0x0D63, Conjunct::Extend), (0x0D81, 0x0D81, Conjunct::Extend), (0x0DCA, 0x0DCA, Conjunct::Extend),
+    (0x0DCF, 0x0DCF, Conjunct::Extend), (0x0DD2, 0x0DD4, Conjunct::Extend), (0x0DD6, 0x0DD6, Conjunct::Extend),
+    (0x0DDF, 0x0DDF, Conjunct::Extend), (0x0E31, 0x0E31, Conjunct::Extend), (0x0E34, 0x0E3A, Conjunct::Extend),
+    (0x0E47, 0x0E4E, Conjunct::Extend), (0x0EB1, 0x0EB1, Conjunct::Extend), (0x0EB4, 0x0EBC, Conjunct::Extend),
+    (0x0EC8, 0x0ECE, Conjunct::Extend), (0x0F18, 0x0F19, Conjunct::Extend), (0x0F35, 0x0F35, Conjunct::Extend),
+    (0x0F37, 0x0F37, Conjunct::Extend), (0x0F39, 0x0F39, Conjunct::Extend), (0x0F71, 0x0F7E, Conjunct::Extend),
+    (0x0F80, 0x0F84, Conjunct::Extend), (0x0F86, 0x0F87, Conjunct::Extend), (0x0F8D, 0x0F97, Conjunct::Extend),
+    (0x0F99, 0x0FBC, Conjunct::Extend), (0x0FC6, 0x0FC6, Conjunct::Extend), (0x1000, 0x102A, Conjunct::Consonant),
+    (0x102D, 0x1030, Conjunct::Extend), (0x1032, 0x1037, Conjunct::Extend), (0x1039, 0x1039, Conjunct::Linker),
+    (0x103A, 0x103A, Conjunct::Extend), (0x103D, 0x103E, Conjunct::Extend), (0x103F, 0x103F, Conjunct::Consonant),
+    (0x1050, 0x1055, Conjunct::Consonant), (0x1058, 0x1059, Conjunct::Extend), (0x105A, 0x105D, Conjunct::Consonant),
+    (0x105E, 0x1060, Conjunct::Extend), (0x1061, 0x1061, Conjunct::Consonant), (0x1065, 0x1066, Conjunct::Consonant),
+    (0x106E, 0x1070, Conjunct::Consonant), (0x1071, 0x1074, Conjunct::Extend), (0x1075, 0x1081, Conjunct::Consonant),
+    (0x1082, 0x1082, Conjunct::Extend), (0x1085, 0x1086, Conjunct::Extend), (0x108D, 0x108D, Conjunct::Extend),
+    (0x108E, 0x108E, Conjunct::Consonant), (0x109D, 0x109D, Conjunct::Extend), (0x135D, 0x135F, Conjunct::Extend),
+    (0x1712, 0x1715, Conjunct::Extend), (0x1732, 0x1734, Conjunct::Extend), (0x1752, 0x1753, Conjunct::Extend),
+    (0x1772, 0x1773, Conjunct::Extend), (0x1780, 0x17B3, Conjunct::Consonant), (0x17B4, 0x17B5, Conjunct::Extend),
+    (0x17B7, 0x17BD, Conjunct::Extend), (0x17C6, 0x17C6, Conjunct::Extend), (0x17C9, 0x17D1, Conjunct::Extend),
+    (0x17D2, 0x17D2, Conjunct::Linker), (0x17D3, 0x17D3, Conjunct::Extend), (0x17DD, 0x17DD, Conjunct::Extend),
+    (0x180B, 0x180D, Conjunct::Extend), (0x180F, 0x180F, Conjunct::Extend), (0x1885, 0x1886, Conjunct::Extend),
+    (0x18A9, 0x18A9, Conjunct::Extend), (0x1920, 0x1922, Conjunct::Extend), (0x1927, 0x1928, Conjunct::Extend),
+    (0x1932, 0x1932, Conjunct::Extend), (0x1939, 0x193B, Conjunct::Extend), (0x1A17, 0x1A18, Conjunct::Extend),
+    (0x1A1B, 0x1A1B, Conjunct::Extend), (0x1A20, 0x1A54, Conjunct::Consonant), (0x1A56, 0x1A56, Conjunct::Extend),
+    (0x1A58, 0x1A5E, Conjunct::Extend), (0x1A60, 0x1A60, Conjunct::Linker), (0x1A62, 0x1A62, Conjunct::Extend),
+    (0x1A65, 0x1A6C, Conjunct::Extend), (0x1A73, 0x1A7C, Conjunct::Extend), (0x1A7F, 0x1A7F, Conjunct::Extend),
+    (0x1AB0, 0x1ADD, Conjunct::Extend), (0x1AE0, 0x1AEB, Conjunct::Extend), (0x1B00, 0x1B03, Conjunct::Extend),
+    (0x1B0B, 0x1B0C, Conjunct::Consonant), (0x1B13, 0x1B33, Conjunct::Consonant), (0x1B34, 0x1B3D, Conjunct::Extend),
+    (0x1B42, 0x1B43, Conjunct::Extend), (0x1B44, 0x1B44, Conjunct::Linker), (0x1B45, 0x1B4C, Conjunct::Consonant),
+    (0x1B6B, 0x1B73, Conjunct::Extend), (0x1B80, 0x1B81, Conjunct::Extend), (0x1B83, 0x1BA0, Conjunct::Consonant),
+    (0x1BA2, 0x1BA5, Conjunct::Extend), (0x1BA8, 0x1BAA, Conjunct::Extend), (0x1BAB, 0x1BAB, Conjunct::Linker),
+    (0x1BAC, 0x1BAD, Conjunct::Extend), (0x1BAE, 0x1BAF, Conjunct::Consonant), (0x1BBB, 0x1BBD, Conjunct::Consonant),
+    (0x1BE6, 0x1BE6, Conjunct::Extend), (0x1BE8, 0x1BE9, Conjunct::Extend), (0x1BED, 0x1BED, Conjunct::Extend),
+    (0x1BEF, 0x1BF3, Conjunct::Extend), (0x1C2C, 0x1C33, Conjunct::Extend), (0x1C36, 0x1C37, Conjunct::Extend),
+    (0x1CD0, 0x1CD2, Conjunct::Extend), (0x1CD4, 0x1CE0, Conjunct::Extend), (0x1CE2, 0x1CE8, Conjunct::Extend),
+    (0x1CED, 0x1CED, Conjunct::Extend), (0x1CF4, 0x1CF4, Conjunct::Extend), (0x1CF8, 0x1CF9, Conjunct::Extend),
+    (0x1DC0, 0x1DFF, Conjunct::Extend), (0x200D, 0x200D, Conjunct::Extend), (0x20D0, 0x20F0, Conjunct::Extend),
+    (0x2CEF, 0x2CF1, Conjunct::Extend), (0x2D7F, 0x2D7F, Conjunct::Extend), (0x2DE0, 0x2DFF, Conjunct::Extend),
+    (0x302A, 0x302F, Conjunct::Extend), (0x3099, 0x309A, Conjunct::Extend), (0xA66F, 0xA672, Conjunct::Extend),
+    (0xA674, 0xA67D, Conjunct::Extend), (0xA69E, 0xA69F, Conjunct::Extend), (0xA6F0, 0xA6F1, Conjunct::Extend),
+    (0xA802, 0xA802, Conjunct::Extend), (0xA806, 0xA806, Conjunct::Extend), (0xA80B, 0xA80B, Conjunct::Extend),
+    (0xA825, 0xA826, Conjunct::Extend), (0xA82C, 0xA82C, Conjunct::Extend), (0xA8C4, 0xA8C5, Conjunct::Extend),
+    (0xA8E0, 0xA8F1, Conjunct::Extend), (0xA8FF, 0xA8FF, Conjunct::Extend), (0xA926, 0xA92D, Conjunct::Extend),
+    (0xA947, 0xA951, Conjunct::Extend), (0xA953, 0xA953, Conjunct::Extend), (0xA980, 0xA982, Conjunct::Extend),
+    (0xA989, 0xA98B, Conjunct::Consonant), (0xA98F, 0xA9B2, Conjunct::Consonant), (0xA9B3, 0xA9B3, Conjunct::Extend),
+    (0xA9B6, 0xA9B9, Conjunct::Extend), (0xA9BC, 0xA9BD, Conjunct::Extend), (0xA9C0, 0xA9C0, Conjunct::Linker),
+    (0xA9E0, 0xA9E4, Conjunct::Consonant), (0xA9E5, 0xA9E5, Conjunct::Extend), (0xA9E7, 0xA9EF, Conjunct::Consonant),
+    (0xA9FA, 0xA9FE, Conjunct::Consonant), (0xAA29, 0xAA2E, Conjunct::Extend), (0xAA31, 0xAA32, Conjunct::Extend),
+    (0xAA35, 0xAA36, Conjunct::Extend), (0xAA43, 0xAA43, Conjunct::Extend), (0xAA4C, 0xAA4C, Conjunct::Extend),
+    (0xAA60, 0xAA6F, Conjunct::Consonant), (0xAA71, 0xAA73, Conjunct::Consonant), (0xAA7A, 0xAA7A, Conjunct::Consonant),
+    (0xAA7C, 0xAA7C, Conjunct::Extend), (0xAA7E, 0xAA7F, Conjunct::Consonant), (0xAAB0, 0xAAB0, Conjunct::Extend),
+    (0xAAB2, 0xAAB4, Conjunct::Extend), (0xAAB7, 0xAAB8, Conjunct::Extend), (0xAABE, 0xAABF, Conjunct::Extend),
+    (0xAAC1, 0xAAC1, Conjunct::Extend), (0xAAE0, 0xAAEA, Conjunct::Consonant), (0xAAEC, 0xAAED, Conjunct::Extend),
+    (0xAAF6, 0xAAF6, Conjunct::Linker), (0xABC0, 0xABDA, Conjunct::Consonant), (0xABE5, 0xABE5, Conjunct::Extend),
+    (0xABE8, 0xABE8, Conjunct::Extend), (0xABED, 0xABED, Conjunct::Extend), (0xFB1E, 0xFB1E, Conjunct::Extend),
+    (0xFE00, 0xFE0F, Conjunct::Extend), (0xFE20, 0xFE2F, Conjunct::Extend), (0xFF9E, 0xFF9F, Conjunct::Extend),
+    (0x101FD, 0x101FD, Conjunct::Extend), (0x102E0, 0x102E0, Conjunct::Extend), (0x10376, 0x1037A, Conjunct::Extend),
+    (0x10A00, 0x10A00, Conjunct::Consonant), (0x10A01, 0x10A03, Conjunct::Extend), (0x10A05, 0x10A06, Conjunct::Extend),
+    (0x10A0C, 0x10A0F, Conjunct::Extend), (0x10A10, 0x10A13, Conjunct::Consonant), (0x10A15, 0x10A17, Conjunct::Consonant),
+    (0x10A19, 0x10A35, Conjunct::Consonant), (0x10A38, 0x10A3A, Conjunct::Extend), (0x10A3F, 0x10A3F, Conjunct::Linker),
+    (0x10AE5, 0x10AE6, Conjunct::Extend), (0x10D24, 0x10D27, Conjunct::Extend), (0x10D69, 0x10D6D, Conjunct::Extend),
+    (0x10EAB, 0x10EAC, Conjunct::Extend), (0x10EFA, 0x10EFF, Conjunct::Extend), (0x10F46, 0x10F50, Conjunct::Extend),
+    (0x10F82, 0x10F85, Conjunct::Extend), (0x11001, 0x11001, Conjunct::Extend), (0x11038, 0x11046, Conjunct::Extend),
+    (0x11070, 0x11070, Conjunct::Extend), (0x11073, 0x11074, Conjunct::Extend), (0x1107F, 0x11081, Conjunct::Extend),
+    (0x110B3, 0x110B6, Conjunct::Extend), (0x110B9, 0x110BA, Conjunct::Extend), (0x110C2, 0x110C2, Conjunct::Extend),
+    (0x11100, 0x11102, Conjunct::Extend), (0x11103, 0x11126, Conjunct::Consonant), (0x11127, 0x1112B, Conjunct::Extend),
+    (0x1112D, 0x11132, Conjunct::Extend), (0x11133, 0x11133, Conjunct::Linker), (0x11134, 0x11134, Conjunct::Extend),
+    (0x11144, 0x11144, Conjunct::Consonant), (0x11147, 0x11147, Conjunct::Consonant), (0x11173, 0x11173, Conjunct::Extend),
+    (0x11180, 0x11181, Conjunct::Extend), (0x111B6, 0x111BE, Conjunct::Extend), (0x111C0, 0x111C0, Conjunct::Extend),
+    (0x111C9, 0x111CC, Conjunct::Extend), (0x111CF, 0x111CF, Conjunct::Extend), (0x1122F, 0x11231, Conjunct::Extend),
+    (0x11234, 0x11237, Conjunct::Extend), (0x1123E, 0x1123E, Conjunct::Extend), (0x11241, 0x11241, Conjunct::Extend),
+    (0x112DF, 0x112DF, Conjunct::Extend), (0x112E3, 0x112EA, Conjunct::Extend), (0x11300, 0x11301, Conjunct::Extend),
+    (0x1133B, 0x1133C, Conjunct::Extend), (0x1133E, 0x1133E, Conjunct::Extend), (0x11340, 0x11340, Conjunct::Extend),
+    (0x1134D, 0x1134D, Conjunct::Extend), (0x11357, 0x11357, Conjunct::Extend), (0x11366, 0x1136C, Conjunct::Extend),
+    (0x11370, 0x11374, Conjunct::Extend), (0x11380, 0x11389, Conjunct::Consonant), (0x1138B, 0x1138B, Conjunct::Consonant),
+    (0x1138E, 0x1138E, Conjunct::Consonant), (0x11390, 0x113B5, Conjunct::Consonant), (0x113B8, 0x113B8, Conjunct::Extend),
+    (0x113BB, 0x113C0, Conjunct::Extend), (0x113C2, 0x113C2, Conjunct::Extend), (0x113C5, 0x113C5, Conjunct::Extend),
+    (0x113C7, 0x113C9, Conjunct::Extend), (0x113CE, 0x113CF, Conjunct::Extend), (0x113D0, 0x113D0, Conjunct::Linker),
+    (0x113D2, 0x113D2, Conjunct::Extend), (0x113E1, 0x113E2, Conjunct::Extend), (0x11438, 0x1143F, Conjunct::Extend),
+    (0x11442, 0x11444, Conjunct::Extend), (0x11446, 0x11446, Conjunct::Extend), (0x1145E, 0x1145E, Conjunct::Extend),
+    (0x114B0, 0x114B0, Conjunct::Extend), (0x114B3, 0x114B8, Conjunct::Extend), (0x114BA, 0x114BA, Conjunct::Extend),
+    (0x114BD, 0x114BD, Conjunct::Extend), (0x114BF, 0x114C0, Conjunct::Extend), (0x114C2, 0x114C3, Conjunct::Extend),
+    (0x115AF, 0x115AF, Conjunct::Extend), (0x115B2, 0x115B5, Conjunct::Extend), (0x115BC, 0x115BD, Conjunct::Extend),
+    (0x115BF, 0x115C0, Conjunct::Extend), (0x115DC, 0x115DD, Conjunct::Extend), (0x11633, 0x1163A, Conjunct::Extend),
+    (0x1163D, 0x1163D, Conjunct::Extend), (0x1163F, 0x11640, Conjunct::Extend), (0x116AB, 0x116AB, Conjunct::Extend),
+    (0x116AD, 0x116AD, Conjunct::Extend), (0x116B0, 0x116B7, Conjunct::Extend), (0x1171D, 0x1171D, Conjunct::Extend),
+    (0x1171F, 0x1171F, Conjunct::Extend), (0x11722, 0x11725, Conjunct::Extend), (0x11727, 0x1172B, Conjunct::Extend),
+    (0x1182F, 0x11837, Conjunct::Extend), (0x11839, 0x1183A, Conjunct::Extend), (0x11900, 0x11906, Conjunct::Consonant),
+    (0x11909, 0x11909, Conjunct::Consonant), (0x1190C, 0x11913, Conjunct::Consonant), (0x11915, 0x11916, Conjunct::Consonant),
+    (0x11918, 0x1192F, Conjunct::Consonant), (0x11930, 0x11930, Conjunct::Extend), (0x1193B, 0x1193D, Conjunct::Extend),
+    (0x1193E, 0x1193E, Conjunct::Linker), (0x11943, 0x11943, Conjunct::Extend), (0x119D4, 0x119D7, Conjunct::Extend),
+    (0x119DA, 0x119DB, Conjunct::Extend), (0x119E0, 0x119E0, Conjunct::Extend), (0x11A00, 0x11A00, Conjunct::Consonant),
+    (0x11A01, 0x11A0A, Conjunct::Extend), (0x11A0B, 0x11A32, Conjunct::Consonant), (0x11A33, 0x11A38, Conjunct::Extend),
+    (0x11A3B, 0x11A3E, Conjunct::Extend), (0x11A47, 0x11A47, Conjunct::Linker), (0x11A50, 0x11A50, Conjunct::Consonant),
+    (0x11A51, 0x11A56, Conjunct::Extend), (0x11A59, 0x11A5B, Conjunct::Extend), (0x11A5C, 0x11A83, Conjunct::Consonant),
+    (0x11A8A, 0x11A96, Conjunct::Extend), (0x11A98, 0x11A98, Conjunct::Extend), (0x11A99, 0x11A99, Conjunct::Linker),
+    (0x11B60, 0x11B60, Conjunct::Extend), (0x11B62, 0x11B64, Conjunct::Extend), (0x11B66, 0x11B66, Conjunct::Extend),
+    (0x11C30, 0x11C36, Conjunct::Extend), (0x11C38, 0x11C3D, Conjunct::Extend), (0x11C3F, 0x11C3F, Conjunct::Extend),
+    (0x11C92, 0x11CA7, Conjunct::Extend), (0x11CAA, 0x11CB0, Conjunct::Extend), (0x11CB2, 0x11CB3, Conjunct::Extend),
+    (0x11CB5, 0x11CB6, Conjunct::Extend), (0x11D31, 0x11D36, Conjunct::Extend), (0x11D3A, 0x11D3A, Conjunct::Extend),
+    (0x11D3C, 0x11D3D, Conjunct::Extend), (0x11D3F, 0x11D45, Conjunct::Extend), (0x11D47, 0x11D47, Conjunct::Extend),
+    (0x11D90, 0x11D91, Conjunct::Extend), (0x11D95, 0x11D95, Conjunct::Extend), (0x11D97, 0x11D97, Conjunct::Extend),
+    (0x11EF3, 0x11EF4, Conjunct::Extend), (0x11F00, 0x11F01, Conjunct::Extend), (0x11F04, 0x11F10, Conjunct::Consonant),
+    (0x11F12, 0x11F33, Conjunct::Consonant), (0x11F36, 0x11F3A, Conjunct::Extend), (0x11F40, 0x11F41, Conjunct::Extend),
+    (0x11F42, 0x11F42, Conjunct::Linker), (0x11F5A, 0x11F5A, Conjunct::Extend), (0x13440, 0x13440, Conjunct::Extend),
+    (0x13447, 0x13455, Conjunct::Extend), (0x1611E, 0x16129, Conjunct::Extend), (0x1612D, 0x1612F, Conjunct::Extend),
+    (0x16AF0, 0x16AF4, Conjunct::Extend), (0x16B30, 0x16B36, Conjunct::Extend), (0x16F4F, 0x16F4F, Conjunct::Extend),
+    (0x16F8F, 0x16F92, Conjunct::Extend), (0x16FE4, 0x16FE4, Conjunct::Extend), (0x16FF0, 0x16FF1, Conjunct::Extend),
+    (0x1BC9D, 0x1BC9E, Conjunct::Extend), (0x1CF00, 0x1CF2D, Conjunct::Extend), (0x1CF30, 0x1CF46, Conjunct::Extend),
+    (0x1D165, 0x1D169, Conjunct::Extend), (0x1D16D, 0x1D172, Conjunct::Extend), (0x1D17B, 0x1D182, Conjunct::Extend),
+    (0x1D185, 0x1D18B, Conjunct::Extend), (0x1D1AA, 0x1D1AD, Conjunct::Extend), (0x1D242, 0x1D244, Conjunct::Extend),
+    (0x1DA00, 0x1DA36, Conjunct::Extend), (0x1DA3B, 0x1DA6C, Conjunct::Extend), (0x1DA75, 0x1DA75, Conjunct::Extend),
+    (0x1DA84, 0x1DA84, Conjunct::Extend), (0x1DA9B, 0x1DA9F, Conjunct::Extend), (0x1DAA1, 0x1DAAF, Conjunct::Extend),
+    (0x1E000, 0x1E006, Conjunct::Extend), (0x1E008, 0x1E018, Conjunct::Extend), (0x1E01B, 0x1E021, Conjunct::Extend),
+    (0x1E023, 0x1E024, Conjunct::Extend), (0x1E026, 0x1E02A, Conjunct::Extend), (0x1E08F, 0x1E08F, Conjunct::Extend),
+    (0x1E130, 0x1E136, Conjunct::Extend), (0x1E2AE, 0x1E2AE, Conjunct::Extend), (0x1E2EC, 0x1E2EF, Conjunct::Extend),
+    (0x1E4EC, 0x1E4EF, Conjunct::Extend), (0x1E5EE, 0x1E5EF, Conjunct::Extend), (0x1E6E3, 0x1E6E3, Conjunct::Extend),
+    (0x1E6E6, 0x1E6E6, Conjunct::Extend), (0x1E6EE, 0x1E6EF, Conjunct::Extend), (0x1E6F5, 0x1E6F5, Conjunct::Extend),
+    (0x1E8D0, 0x1E8D6, Conjunct::Extend), (0x1E944, 0x1E94A, Conjunct::Extend), (0x1F3FB, 0x1F3FF, Conjunct::Extend),
+    (0xE0020, 0xE007F, Conjunct::Extend), (0xE0100, 0xE01EF, Conjunct::Extend),
+];
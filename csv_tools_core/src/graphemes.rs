@@ -0,0 +1,141 @@
+//! # Grapheme Clusters
+//!
+//! Counts the user-perceived characters of a text (`--count-mode graphemes`): a flag, a
+//! skin-toned or ZWJ-joined emoji, a letter with combining accents, a Hangul syllable
+//! spelled in jamo, or an Indic conjunct with its vowel signs is one grapheme, however many
+//! code points it takes.
+//!
+//! The segmentation follows the extended grapheme cluster rules of Unicode Standard Annex
+//! #29, including the Indic conjunct rule (GB9c). The properties come from the tables in
+//! `grapheme_tables.rs`, generated from the Unicode character database by
+//! `scripts/unicode_tables.py`; a new Unicode version is picked up by rerunning the script.
+
+use crate::grapheme_tables::{EXTENDED_PICTOGRAPHIC, GRAPHEME_BREAK, INDIC_CONJUNCT_BREAK};
+
+/// Grapheme_Cluster_Break value of a character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Break {
+    Cr,
+    Lf,
+    Control,
+    /// Combining marks, variation selectors, emoji modifiers, and tags
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    /// Signs written before the letter they belong to
+    Prepend,
+    /// Vowel signs that take up their own width
+    SpacingMark,
+    HangulL,
+    HangulV,
+    HangulT,
+    HangulLv,
+    HangulLvt,
+    Other,
+}
+
+/// Indic_Conjunct_Break value of a character, for GB9c
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conjunct {
+    /// A virama that joins the consonants around it
+    Linker,
+    Consonant,
+    /// Marks that may sit between a consonant and a linker
+    Extend,
+}
+
+/// Value of the range holding a code point, in sorted, non-overlapping ranges.
+fn lookup<T: Copy>(code_point: u32, ranges: &[(u32, u32, T)]) -> Option<T> {
+    ranges.binary_search_by(|&(start, end, _)| {
+        if end < code_point {
+            std::cmp::Ordering::Less
+        } else if start > code_point {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }).ok().map(|index| ranges[index].2)
+}
+
+/// Whether a code point lies in one of the sorted, non-overlapping ranges.
+fn in_ranges(code_point: u32, ranges: &[(u32, u32)]) -> bool {
+    ranges.binary_search_by(|&(start, end)| {
+        if end < code_point {
+            std::cmp::Ordering::Less
+        } else if start > code_point {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }).is_ok()
+}
+
+/// Where the current cluster stands in an emoji ZWJ sequence (GB11)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmojiRun {
+    None,
+    /// A pictograph followed only by Extend characters
+    Pictograph,
+    /// Such a run ended by a ZWJ, which joins the next pictograph
+    Joiner,
+}
+
+/// Where the current cluster stands in an Indic conjunct (GB9c)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConjunctRun {
+    None,
+    /// A consonant followed only by conjunct Extend characters
+    Consonant,
+    /// Such a run with a linker in it, which joins the next consonant
+    Linked,
+}
+
+/// Number of extended grapheme clusters in a text.
+pub fn count_graphemes(text: &str) -> usize {
+    let mut count = 0;
+    let mut previous: Option<Break> = None;
+    let mut emoji = EmojiRun::None;
+    let mut conjunct = ConjunctRun::None;
+    // Regional indicators in the current run, to pair them into flags (GB12/13)
+    let mut regional_indicators = 0;
+
+    for c in text.chars() {
+        let code_point = c as u32;
+        let current = lookup(code_point, GRAPHEME_BREAK).unwrap_or(Break::Other);
+        let pictographic = in_ranges(code_point, EXTENDED_PICTOGRAPHIC);
+        let indic = lookup(code_point, INDIC_CONJUNCT_BREAK);
+        let joins = match (previous, current) {
+            (None, _) => false,
+            (Some(Break::Cr), Break::Lf) => true,
+            (Some(Break::Cr | Break::Lf | Break::Control), _) | (_, Break::Cr | Break::Lf | Break::Control) => false,
+            (Some(Break::HangulL), Break::HangulL | Break::HangulV | Break::HangulLv | Break::HangulLvt) => true,
+            (Some(Break::HangulLv | Break::HangulV), Break::HangulV | Break::HangulT) => true,
+            (Some(Break::HangulLvt | Break::HangulT), Break::HangulT) => true,
+            (_, Break::Extend | Break::Zwj | Break::SpacingMark) => true,
+            (Some(Break::Prepend), _) => true,
+            _ if conjunct == ConjunctRun::Linked && indic == Some(Conjunct::Consonant) => true,
+            _ if emoji == EmojiRun::Joiner && pictographic => true,
+            (Some(Break::RegionalIndicator), Break::RegionalIndicator) => regional_indicators % 2 == 1,
+            _ => false,
+        };
+        if !joins {
+            count += 1;
+        }
+
+        emoji = match (emoji, current) {
+            _ if pictographic => EmojiRun::Pictograph,
+            (EmojiRun::Pictograph, Break::Extend) => EmojiRun::Pictograph,
+            (EmojiRun::Pictograph, Break::Zwj) => EmojiRun::Joiner,
+            _ => EmojiRun::None,
+        };
+        conjunct = match (conjunct, indic) {
+            (_, Some(Conjunct::Consonant)) => ConjunctRun::Consonant,
+            (ConjunctRun::Consonant | ConjunctRun::Linked, Some(Conjunct::Linker)) => ConjunctRun::Linked,
+            (run, Some(Conjunct::Extend)) => run,
+            _ => ConjunctRun::None,
+        };
+        regional_indicators = if current == Break::RegionalIndicator { regional_indicators + 1 } else { 0 };
+        previous = Some(current);
+    }
+    count
+}
@@ -0,0 +1,169 @@
+//! # Analysis History Database
+//!
+//! An append-only CSV file with one line per analyzed file, so results can be
+//! compared across runs (e.g., detecting header changes via the fingerprint).
+//!
+//! New columns may be added to [`HISTORY_COLUMNS`] over time. When appending to an
+//! existing history file, values are written in the order of that file's own header,
+//! so older history files keep working; columns the file does not know are skipped.
+//!
+//! The latest earlier line for a basename is read back by [`read_last_record`] to compare
+//! a run with the previous one (see the `run_changes` module).
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::fields::{escape_field, split_fields};
+use crate::report_schema::SCHEMA_VERSION_COLUMN;
+
+/// Columns written to a new history file, in order
+pub const HISTORY_COLUMNS: [&str; 16] = [
+    "run_timestamp",
+    "basename",
+    "input_path",
+    "header_fingerprint",
+    "column_count",
+    "total_rows",
+    "error_rows",
+    "total_chars",
+    "min_length",
+    "max_length",
+    "mean_length",
+    "median_length",
+    "outlier_rows",
+    "p99_length",
+    "outlier_lengths",
+    SCHEMA_VERSION_COLUMN,
+];
+
+/// One history line as (column name, value) pairs
+#[derive(Debug, Clone, Default)]
+pub struct HistoryRecord {
+    values: Vec<(&'static str, String)>,
+}
+
+impl HistoryRecord {
+    /// Sets the value for a history column.
+    pub fn set(&mut self, column: &'static str, value: impl ToString) {
+        self.values.push((column, value.to_string()));
+    }
+
+    /// Returns the value for a column, or an empty string if it was never set.
+    pub fn value(&self, column: &str) -> &str {
+        self.values.iter()
+            .rev()
+            .find(|(name, _)| *name == column)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or("")
+    }
+}
+
+/// Appends a record to the history file, creating it with a header if needed.
+///
+/// # Arguments
+///
+/// * `history_path` - Path to the history CSV file
+/// * `record` - Values for the analyzed file
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Ok(()) on success, or an Error if the file cannot be read or written
+pub fn append_history_record(history_path: impl AsRef<Path>, record: &HistoryRecord) -> Result<(), io::Error> {
+    let history_path = history_path.as_ref();
+
+    // Use the existing header when there is one so older files stay aligned
+    let existing_header = read_history_header(history_path)?;
+
+    if let Some(parent) = history_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(history_path)?;
+
+    let columns: Vec<String> = match existing_header {
+        Some(columns) => columns,
+        None => {
+            writeln!(file, "{}", HISTORY_COLUMNS.join(","))?;
+            HISTORY_COLUMNS.iter().map(|c| c.to_string()).collect()
+        }
+    };
+
+    let line = columns.iter()
+        .map(|column| escape_field(record.value(column)))
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(file, "{}", line)?;
+
+    Ok(())
+}
+
+/// Reads the latest history line recorded for a basename.
+///
+/// Lines are appended in run order, so the last matching line is the previous run.
+/// Columns that are not in [`HISTORY_COLUMNS`] are ignored.
+///
+/// # Arguments
+///
+/// * `history_path` - Path to the history CSV file
+/// * `basename` - Basename of the analyzed file
+///
+/// # Returns
+///
+/// * `Result<Option<HistoryRecord>, io::Error>` - The latest record, None if the file does
+///   not exist or has no line for the basename, or an Error if the file cannot be read
+pub fn read_last_record(history_path: impl AsRef<Path>, basename: &str) -> Result<Option<HistoryRecord>, io::Error> {
+    let file = match File::open(history_path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let mut lines = BufReader::new(file).lines();
+    let header = match lines.next() {
+        Some(line) => split_fields(&line?, ','),
+        None => return Ok(None),
+    };
+    let Some(basename_index) = header.iter().position(|column| column == "basename") else {
+        return Ok(None);
+    };
+
+    let mut last_values = None;
+    for line in lines {
+        let values = split_fields(&line?, ',');
+        if values.get(basename_index).is_some_and(|value| value == basename) {
+            last_values = Some(values);
+        }
+    }
+
+    Ok(last_values.map(|values| {
+        let mut record = HistoryRecord::default();
+        for (column, value) in header.iter().zip(values) {
+            if let Some(&known) = HISTORY_COLUMNS.iter().find(|known| *known == column) {
+                record.set(known, value);
+            }
+        }
+        record
+    }))
+}
+
+/// Reads the header of an existing, non-empty history file.
+fn read_history_header(history_path: &Path) -> Result<Option<Vec<String>>, io::Error> {
+    let file = match File::open(history_path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let mut first_line = String::new();
+    BufReader::new(file).read_line(&mut first_line)?;
+    let first_line = first_line.trim_end();
+
+    if first_line.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(split_fields(first_line, ',')))
+}
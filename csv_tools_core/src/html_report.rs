@@ -0,0 +1,215 @@
+//! # HTML Report (`--html-report`)
+//!
+//! Writes the analysis of a file as one self-contained HTML page, for reading the results
+//! in a browser without a spreadsheet:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --html-report
+//! $ xdg-open reports/orders_html_report_1767225600.html
+//! ```
+//!
+//! The page shows the summary statistics, the row-length histogram and the page
+//! distribution as inline SVG bar charts (hover a bar for its exact counts), and the
+//! outliers in a table that sorts by any column when its heading is clicked. The charts
+//! and the sorting are inline, so the page needs no network access and can be attached
+//! to a ticket as it is.
+//!
+//! Row lengths spanning more than [`MAX_HISTOGRAM_BARS`] values are grouped into that many
+//! bars of equal width. The outlier table lists the same rows as the JSON analysis report.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::csv_row_analyzer::AnalysisResult;
+use crate::db_sink::OutlierRow;
+use crate::report_schema::REPORT_SCHEMA_VERSION;
+
+/// Most bars drawn in the row-length histogram
+pub const MAX_HISTOGRAM_BARS: usize = 60;
+
+const CHART_WIDTH: usize = 720;
+const CHART_HEIGHT: usize = 200;
+
+/// Sorts the outlier table by the clicked column, toggling the direction on each click
+const SORT_SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable th").forEach(function (heading, column) {
+  heading.addEventListener("click", function () {
+    var body = heading.closest("table").tBodies[0];
+    var ascending = heading.dataset.order !== "asc";
+    heading.dataset.order = ascending ? "asc" : "desc";
+    Array.from(body.rows)
+      .sort(function (a, b) {
+        var delta = parseFloat(a.cells[column].textContent) - parseFloat(b.cells[column].textContent);
+        return ascending ? delta : -delta;
+      })
+      .forEach(function (row) { body.appendChild(row); });
+  });
+});
+"#;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse;margin-bottom:1.5em}\
+th,td{border:1px solid #ccc;padding:4px 10px;text-align:right}\
+th{background:#f3f3f3}table.sortable th{cursor:pointer}\
+rect.bar{fill:#4a78b0}rect.bar:hover{fill:#e0803a}rect.outlier{fill:#c0504d}";
+
+/// Builds the HTML report.
+///
+/// # Arguments
+///
+/// * `input_file_path` - The analyzed file
+/// * `result` - Summary of the analysis
+/// * `generated_at` - Human-readable time of the run
+/// * `chars_per_page` - Characters per page of the page distribution
+/// * `page_counts` - (pages, rows) pairs sorted by page count
+/// * `outliers` - Rows above the upper outlier threshold
+///
+/// # Returns
+///
+/// * `String` - The HTML page
+pub fn html_report(
+    input_file_path: &Path,
+    result: &AnalysisResult,
+    generated_at: &str,
+    chars_per_page: usize,
+    page_counts: &[(usize, u64)],
+    outliers: &[OutlierRow],
+) -> String {
+    let stats = &result.statistics;
+    let title = format!("Row Length Analysis for {}", html_escape(&result.basename));
+    let mut page = String::new();
+    let _ = writeln!(page, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(page, "<title>{}</title>\n<style>{}</style>\n</head>\n<body>", title, STYLE);
+    let _ = writeln!(page, "<h1>{}</h1>", title);
+    let _ = writeln!(page, "<p>File: {}<br>Generated: {}<br>Report schema version: {}</p>",
+                     html_escape(&input_file_path.display().to_string()), html_escape(generated_at), REPORT_SCHEMA_VERSION);
+
+    let _ = writeln!(page, "<h2>Summary</h2>\n<table>");
+    let summary_rows = [
+        ("Rows", result.total_rows.to_string()),
+        ("Unreadable rows", result.error_rows.to_string()),
+        ("Characters", result.total_chars.to_string()),
+        ("Minimum length", stats.min.to_string()),
+        ("Median length", stats.median.to_string()),
+        ("Mean length", format!("{:.2}", stats.mean)),
+        ("Maximum length", stats.max.to_string()),
+        ("Standard deviation", format!("{:.2}", stats.std_dev)),
+        ("Upper outlier threshold", format!("{:.2}", result.outlier_threshold_upper)),
+        ("Outlier rows", result.outlier_rows.to_string()),
+    ];
+    for (label, value) in summary_rows {
+        let _ = writeln!(page, "<tr><th>{}</th><td>{}</td></tr>", label, value);
+    }
+    let _ = writeln!(page, "</table>");
+
+    let _ = writeln!(page, "<h2>Row Length Distribution</h2>");
+    let bars: Vec<Bar> = histogram_bins(&result.length_counts).into_iter()
+        .map(|(low, high, rows)| Bar {
+            label: if low == high { format!("{} characters", low) } else { format!("{}-{} characters", low, high) },
+            rows,
+            outlier: low as f64 > result.outlier_threshold_upper,
+        })
+        .collect();
+    page.push_str(&bar_chart(&bars, result.total_rows));
+
+    let _ = writeln!(page, "<h2>Page Distribution ({} characters per page)</h2>", chars_per_page);
+    let bars: Vec<Bar> = page_counts.iter()
+        .map(|&(pages, rows)| Bar { label: format!("{} pages", pages), rows, outlier: false })
+        .collect();
+    page.push_str(&bar_chart(&bars, result.total_rows));
+
+    let _ = writeln!(page, "<h2>Outliers</h2>");
+    if outliers.is_empty() {
+        let _ = writeln!(page, "<p>No rows above the upper outlier threshold.</p>");
+    } else {
+        let _ = writeln!(page, "<table class=\"sortable\">\n<thead><tr><th>row_index</th><th>file_row</th><th>data_index</th>\
+                                <th>character_length</th><th>std_devs_from_mean</th></tr></thead>\n<tbody>");
+        for outlier in outliers {
+            let std_devs = if stats.std_dev > 0.0 { (outlier.character_length as f64 - stats.mean) / stats.std_dev } else { 0.0 };
+            let _ = writeln!(page, "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td></tr>",
+                             outlier.row_index, outlier.file_row, outlier.row_index as i64 - 1,
+                             outlier.character_length, std_devs);
+        }
+        let _ = writeln!(page, "</tbody>\n</table>");
+    }
+
+    let _ = writeln!(page, "<script>{}</script>\n</body>\n</html>", SORT_SCRIPT);
+    page
+}
+
+/// One bar of a chart
+struct Bar {
+    label: String,
+    rows: u64,
+    /// Drawn in the outlier color
+    outlier: bool,
+}
+
+/// Draws the bars left to right as an inline SVG, each with a tooltip of its counts.
+fn bar_chart(bars: &[Bar], total_rows: u64) -> String {
+    if bars.is_empty() {
+        return "<p>No rows.</p>\n".to_string();
+    }
+    let tallest = bars.iter().map(|bar| bar.rows).max().unwrap_or(1).max(1);
+    let bar_width = CHART_WIDTH as f64 / bars.len() as f64;
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" role=\"img\">\n",
+        CHART_WIDTH, CHART_HEIGHT, CHART_WIDTH, CHART_HEIGHT
+    );
+    for (i, bar) in bars.iter().enumerate() {
+        let height = (bar.rows as f64 / tallest as f64 * CHART_HEIGHT as f64).max(if bar.rows > 0 { 1.0 } else { 0.0 });
+        let percentage = if total_rows == 0 { 0.0 } else { bar.rows as f64 * 100.0 / total_rows as f64 };
+        let _ = writeln!(
+            svg,
+            "<rect class=\"bar{}\" x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\"><title>{}: {} rows ({:.2}%)</title></rect>",
+            if bar.outlier { " outlier" } else { "" },
+            i as f64 * bar_width, CHART_HEIGHT as f64 - height, (bar_width - 1.0).max(0.5), height,
+            html_escape(&bar.label), bar.rows, percentage
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Groups (length, rows) pairs into at most [`MAX_HISTOGRAM_BARS`] bins of equal width.
+///
+/// # Returns
+///
+/// * `Vec<(usize, usize, u64)>` - (shortest length, longest length, rows) per bin, shortest
+///   first; bins without rows are kept so the x axis stays linear
+fn histogram_bins(length_counts: &[(usize, u64)]) -> Vec<(usize, usize, u64)> {
+    let (Some(shortest), Some(longest)) = (
+        length_counts.iter().map(|&(length, _)| length).min(),
+        length_counts.iter().map(|&(length, _)| length).max(),
+    ) else {
+        return Vec::new();
+    };
+    let bin_width = (longest - shortest) / MAX_HISTOGRAM_BARS + 1;
+    let bin_count = (longest - shortest) / bin_width + 1;
+    let mut bins: Vec<(usize, usize, u64)> = (0..bin_count)
+        .map(|bin| {
+            let low = shortest + bin * bin_width;
+            (low, (low + bin_width - 1).min(longest), 0)
+        })
+        .collect();
+    for &(length, rows) in length_counts {
+        bins[(length - shortest) / bin_width].2 += rows;
+    }
+    bins
+}
+
+/// Escapes text for HTML element content and attribute values.
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
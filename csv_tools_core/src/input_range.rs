@@ -0,0 +1,217 @@
+//! # Input Ranges
+//!
+//! Restricts an analysis to a slice of a huge file, either by row index
+//! (`--rows 1000000..2000000`) or by byte offset (`--bytes 5G..6G`).
+//!
+//! Both forms are half-open (`start..end`) and either bound may be omitted
+//! (`1000..`, `..5G`). Byte ranges are snapped to line boundaries: the slice
+//! contains every line that *starts* inside the range, read to its end.
+//! Row indices stay absolute (0-based, as in the full-file reports), which for
+//! byte ranges means the bytes before the range are scanned once to count lines.
+//! For gzip input, byte offsets refer to the decompressed content.
+
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use crate::compression::{open_input_with_read_ahead, Compression};
+use crate::line_reader::LineReader;
+use crate::read_buffer::ReadBufferSize;
+use crate::read_retry::ReadRetries;
+
+/// Line reader over a possibly decompressed input file
+pub type InputLineReader = LineReader<Box<dyn BufRead>>;
+
+/// A slice of the input to analyze
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputRange {
+    /// Rows with `start <= row_index < end`
+    Rows { start: usize, end: Option<usize> },
+    /// Rows that begin at a byte offset with `start <= offset < end`
+    Bytes { start: u64, end: Option<u64> },
+}
+
+impl InputRange {
+    /// Returns the range as it would be written on the command line.
+    pub fn describe(&self) -> String {
+        match self {
+            InputRange::Rows { start, end } => format!(
+                "rows {}..{}", start, end.map(|e| e.to_string()).unwrap_or_default()
+            ),
+            InputRange::Bytes { start, end } => format!(
+                "bytes {}..{}", start, end.map(|e| e.to_string()).unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Parses a `--rows` argument such as `1000000..2000000`.
+///
+/// # Arguments
+///
+/// * `text` - Range text in `start..end` form
+///
+/// # Returns
+///
+/// * `Result<InputRange, String>` - The parsed range or an error message
+pub fn parse_row_range(text: &str) -> Result<InputRange, String> {
+    let (start, end) = split_range(text)?;
+    let start = match start {
+        Some(s) => s.parse::<usize>().map_err(|_| format!("invalid row number '{}'", s))?,
+        None => 0,
+    };
+    let end = match end {
+        Some(e) => Some(e.parse::<usize>().map_err(|_| format!("invalid row number '{}'", e))?),
+        None => None,
+    };
+    if end.is_some_and(|e| e <= start) {
+        return Err(format!("row range '{}' is empty", text));
+    }
+    Ok(InputRange::Rows { start, end })
+}
+
+/// Parses a `--bytes` argument such as `5G..6G` (suffixes K, M, G, T are powers of 1024).
+///
+/// # Arguments
+///
+/// * `text` - Range text in `start..end` form
+///
+/// # Returns
+///
+/// * `Result<InputRange, String>` - The parsed range or an error message
+pub fn parse_byte_range(text: &str) -> Result<InputRange, String> {
+    let (start, end) = split_range(text)?;
+    let start = match start {
+        Some(s) => parse_byte_size(s)?,
+        None => 0,
+    };
+    let end = match end {
+        Some(e) => Some(parse_byte_size(e)?),
+        None => None,
+    };
+    if end.is_some_and(|e| e <= start) {
+        return Err(format!("byte range '{}' is empty", text));
+    }
+    Ok(InputRange::Bytes { start, end })
+}
+
+/// Parses a byte count with an optional binary suffix (`512`, `64K`, `5G`, `1.5GB`, `2TiB`).
+///
+/// # Arguments
+///
+/// * `text` - Size text
+///
+/// # Returns
+///
+/// * `Result<u64, String>` - Number of bytes or an error message
+pub fn parse_byte_size(text: &str) -> Result<u64, String> {
+    let trimmed = text.trim();
+    let upper = trimmed.to_uppercase();
+    let number_part = upper.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &upper[number_part.len()..];
+
+    let multiplier: u64 = match suffix {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        _ => return Err(format!("unknown size suffix in '{}'", trimmed)),
+    };
+
+    let value: f64 = number_part.parse()
+        .map_err(|_| format!("invalid byte size '{}'", trimmed))?;
+    if value < 0.0 {
+        return Err(format!("byte size '{}' must not be negative", trimmed));
+    }
+    Ok((value * multiplier as f64) as u64)
+}
+
+/// Splits `start..end` into its optional bounds.
+fn split_range(text: &str) -> Result<(Option<&str>, Option<&str>), String> {
+    let (start, end) = text.split_once("..")
+        .ok_or_else(|| format!("expected a range like 'start..end', found '{}'", text))?;
+    let start = start.trim();
+    let end = end.trim();
+    Ok((
+        if start.is_empty() { None } else { Some(start) },
+        if end.is_empty() { None } else { Some(end) },
+    ))
+}
+
+/// Opens a file (decompressing gzip input) and positions a line reader at the start of
+/// the requested range.
+///
+/// # Arguments
+///
+/// * `input_file_path` - File to read
+/// * `range` - Optional slice of the file; `None` reads the whole file
+/// * `read_ahead` - Read the file on a background thread (see the `read_ahead` module)
+/// * `buffer_size` - Size of the read buffer (see the `read_buffer` module)
+/// * `retries` - Retry policy of failed reads, and the count of retries made (see the `read_retry` module)
+///
+/// # Returns
+///
+/// * `Result<(InputLineReader, Option<Compression>), io::Error>` - Reader yielding
+///   only the rows in the range, plus the compression (and gzip member list) when the file is compressed
+pub fn open_line_reader(
+    input_file_path: impl AsRef<Path>,
+    range: Option<&InputRange>,
+    read_ahead: bool,
+    buffer_size: ReadBufferSize,
+    retries: &ReadRetries,
+) -> Result<(InputLineReader, Option<Compression>), io::Error> {
+    let (mut reader, compression) = open_input_with_read_ahead(input_file_path, read_ahead, buffer_size, retries)?;
+
+    let line_reader = match range {
+        None => LineReader::new(reader),
+        Some(InputRange::Rows { start, end }) => {
+            let mut line_reader = LineReader::new(reader).with_end_row(*end);
+            line_reader.skip_rows(*start)?;
+            line_reader
+        },
+        Some(InputRange::Bytes { start, end }) => {
+            let (row_index, byte_offset) = snap_to_line_start(&mut reader, *start)?;
+            LineReader::starting_at(reader, row_index, byte_offset).with_end_offset(*end)
+        },
+    };
+    Ok((line_reader, compression))
+}
+
+/// Advances a reader to the first line that begins at or after `start`.
+///
+/// Newlines are counted along the way so the returned row index is absolute.
+///
+/// # Returns
+///
+/// * `Result<(usize, u64), io::Error>` - (row index, byte offset) of that line
+fn snap_to_line_start<R: BufRead>(reader: &mut R, start: u64) -> Result<(usize, u64), io::Error> {
+    if start == 0 {
+        return Ok((0, 0));
+    }
+
+    // Count newlines in the bytes before the byte preceding `start`
+    let mut position: u64 = 0;
+    let mut newlines: usize = 0;
+    let target = start - 1;
+    while position < target {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok((newlines, position));
+        }
+        let take = buffer.len().min((target - position) as usize);
+        newlines += buffer[..take].iter().filter(|&&b| b == b'\n').count();
+        reader.consume(take);
+        position += take as u64;
+    }
+
+    // Finish the line that contains byte `start - 1`; if that byte is a newline,
+    // the line beginning exactly at `start` is the first one in range
+    let mut partial = Vec::new();
+    let bytes_read = reader.read_until(b'\n', &mut partial)?;
+    if partial.last() == Some(&b'\n') {
+        newlines += 1;
+    }
+    position += bytes_read as u64;
+
+    Ok((newlines, position))
+}
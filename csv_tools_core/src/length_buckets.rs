@@ -0,0 +1,136 @@
+//! # Length-Bucketed Row Lists (`--length-buckets <bounds>`)
+//!
+//! Writes one file per row-length bucket, each holding just the line numbers of the rows
+//! in that bucket, so recovery scripts can pull those rows out of the original file:
+//!
+//! ```bash
+//! $ csv_row_analyzer data.csv reports --length-buckets 100KB,1MB
+//! $ ls reports/data_length_buckets_1718000000/
+//! rows_100KB_1MB.txt  rows_over_1MB.txt
+//!
+//! # Print the rows over 1MB with sed, or route them with awk
+//! $ sed -n "$(sed 's/$/p/' rows_over_1MB.txt)" data.csv
+//! $ awk 'NR == FNR { rows[$1]; next } FNR in rows' rows_over_1MB.txt data.csv
+//! ```
+//!
+//! Line numbers are 1-based, as `sed` and `awk` count them (row index + 1), one per line in
+//! ascending order. Bounds use the same size suffixes as `--bytes` and compare against the
+//! row length in characters. Rows shorter than the smallest bound are not listed. Every
+//! bucket gets a file, even an empty one, so scripts can loop over a fixed set of names.
+//! With `--csv-mode`, a record spanning several lines lists every one of its lines.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::input_range::parse_byte_size;
+use crate::report_buffer::create_report;
+
+/// Parses a comma-separated list of bucket bounds such as `10KB,100KB,1MB`.
+///
+/// # Arguments
+///
+/// * `text` - Bounds in any order, with optional size suffixes
+///
+/// # Returns
+///
+/// * `Result<Vec<usize>, String>` - Distinct bounds in ascending order, or an error message
+pub fn parse_bucket_bounds(text: &str) -> Result<Vec<usize>, String> {
+    let mut bounds = text.split(',')
+        .map(|bound| match parse_byte_size(bound)? {
+            0 => Err(format!("bucket bound '{}' must be greater than zero", bound.trim())),
+            size => Ok(size as usize),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    bounds.sort_unstable();
+    bounds.dedup();
+    Ok(bounds)
+}
+
+/// Returns the file name of each bucket, from the shortest rows to the longest.
+///
+/// # Arguments
+///
+/// * `bounds` - Ascending bucket bounds
+pub fn bucket_file_names(bounds: &[usize]) -> Vec<String> {
+    let mut names: Vec<String> = bounds.windows(2)
+        .map(|pair| format!("rows_{}_{}.txt", size_label(pair[0]), size_label(pair[1])))
+        .collect();
+    if let Some(&largest) = bounds.last() {
+        names.push(format!("rows_over_{}.txt", size_label(largest)));
+    }
+    names
+}
+
+/// Writes the bucket files into a directory.
+///
+/// The directory is removed again if any file cannot be written, so a failed export
+/// never leaves a partial set of row lists behind.
+///
+/// # Arguments
+///
+/// * `bucket_dir` - Directory that receives the bucket files (created if missing)
+/// * `bounds` - Ascending bucket bounds
+/// * `row_indices_map` - Row indices for each row length
+/// * `record_lines` - First line and line count of every record (`--csv-mode`)
+/// * `buffer_size` - Size of the write buffer of each file (`--report-buffer-size`)
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Ok(()) on success, or an Error if a file cannot be written
+pub fn write_length_buckets(
+    bucket_dir: impl AsRef<Path>,
+    bounds: &[usize],
+    row_indices_map: &HashMap<usize, Vec<usize>>,
+    record_lines: Option<&[(usize, usize)]>,
+    buffer_size: usize,
+) -> Result<(), io::Error> {
+    let bucket_dir = bucket_dir.as_ref();
+    fs::create_dir_all(bucket_dir)?;
+    write_bucket_files(bucket_dir, bounds, row_indices_map, record_lines, buffer_size).inspect_err(|_| {
+        let _ = fs::remove_dir_all(bucket_dir);
+    })
+}
+
+fn write_bucket_files(
+    bucket_dir: &Path,
+    bounds: &[usize],
+    row_indices_map: &HashMap<usize, Vec<usize>>,
+    record_lines: Option<&[(usize, usize)]>,
+    buffer_size: usize,
+) -> Result<(), io::Error> {
+    for (bucket, file_name) in bucket_file_names(bounds).into_iter().enumerate() {
+        let low = bounds[bucket];
+        let high = bounds.get(bucket + 1).copied().unwrap_or(usize::MAX);
+        let mut rows: Vec<usize> = row_indices_map.iter()
+            .filter(|&(&length, _)| length >= low && length < high)
+            .flat_map(|(_, indices)| indices.iter().copied())
+            .collect();
+        rows.sort_unstable();
+
+        let mut file = create_report(bucket_dir.join(file_name), buffer_size)?;
+        for row_index in rows {
+            match record_lines.and_then(|record_lines| record_lines.get(row_index)) {
+                Some(&(first_line, lines)) => {
+                    for line in first_line..first_line + lines {
+                        writeln!(file, "{}", line + 1)?;
+                    }
+                },
+                None => writeln!(file, "{}", row_index + 1)?,
+            }
+        }
+        file.flush()?;
+    }
+    Ok(())
+}
+
+/// Formats a bound for a file name: `1MB`, `100KB`, or the plain number.
+pub fn size_label(size: usize) -> String {
+    match size {
+        size if size % (1 << 30) == 0 => format!("{}GB", size >> 30),
+        size if size % (1 << 20) == 0 => format!("{}MB", size >> 20),
+        size if size % (1 << 10) == 0 => format!("{}KB", size >> 10),
+        size => size.to_string(),
+    }
+}
@@ -0,0 +1,356 @@
+//! # Comparing Two Files (`compare` subcommand)
+//!
+//! Reads the row lengths of two files, usually two exports of the same dataset, and
+//! reports how the distribution moved from the first (the baseline) to the second:
+//!
+//! ```bash
+//! $ csv_row_analyzer compare orders_2024-06-03.csv orders_2024-06-10.csv reports
+//! Rows: 1200000 -> 1213450 (+13450)
+//! Mean: 182.40 -> 197.11 chars (+14.71)
+//! Median: 175 -> 176 chars (+1)
+//! KS distance: 0.0412
+//! New outlier lengths: 2 (3 rows)
+//! ```
+//!
+//! Two reports are written, `[baseline]_vs_[current]_compare_[timestamp].md` and a `.csv`
+//! with one line per metric (`metric,baseline,current,delta`):
+//!
+//! * Rows, mean, median, Q1, Q3, 99th percentile, minimum, maximum, and standard deviation,
+//!   each with its delta
+//! * The upper outlier fence of each file and its number of outlier rows
+//! * New outlier lengths: outlier lengths of the current file that no row of the baseline
+//!   has, with their rows; a new kind of long row shows up here even when the averages
+//!   hardly move
+//! * The KS distance: the largest gap between the two cumulative length distributions,
+//!   from 0 (the same shape) to 1 (no length in common), independent of the row counts
+//!
+//! Both files are read by the analyzer, without writing its reports, so the rows are
+//! measured and the outliers fenced exactly as in an analysis: every row counts, the
+//! header included, in characters without the line terminator unless `--count-mode` or
+//! `--include-line-endings` say otherwise, and the fences follow `--outlier-method` and
+//! `--outlier-factor` (1.5 × IQR by default). With `--csv-mode`, a row is a whole record.
+//! Rows that are not valid UTF-8 are left out and counted on stderr, and a truncated file
+//! fails the comparison as it fails an analysis.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::cli::{parse_command_line, COMPARE_COMMAND};
+use crate::clock::Clock;
+use crate::csv_row_analyzer::{
+    analyze_csv_row_lengths, extract_basename, generate_timestamp, parse_flags, AnalysisOptions, AnalysisResult, Statistics,
+};
+use crate::report_table::{ReportTable, ReportValue, TableFormat};
+
+/// Options of the `compare` subcommand
+pub struct CompareOptions {
+    pub baseline_path: String,
+    pub current_path: String,
+    pub output_dir: String,
+    /// How both files are read and their outliers fenced (`--csv-mode`, `--count-mode`,
+    /// `--include-line-endings`, `--outlier-method`, `--outlier-factor`)
+    pub analysis: AnalysisOptions,
+}
+
+/// Row lengths of one file with their statistics and outlier fence
+#[derive(Debug, Clone)]
+pub struct LengthDistribution {
+    /// (length, rows) in ascending order of length
+    pub length_counts: Vec<(usize, u64)>,
+    pub rows: u64,
+    pub statistics: Statistics,
+    /// Rows longer than this are outliers
+    pub upper_fence: f64,
+    /// Rows shorter than this are outliers (when positive)
+    pub lower_fence: f64,
+}
+
+impl LengthDistribution {
+    /// Takes the row lengths, statistics, and outlier fences of an analysis.
+    pub fn new(result: AnalysisResult) -> Self {
+        let mut length_counts = result.length_counts;
+        length_counts.reverse();
+        LengthDistribution {
+            rows: length_counts.iter().map(|&(_, rows)| rows).sum(),
+            length_counts,
+            statistics: result.statistics,
+            upper_fence: result.outlier_threshold_upper,
+            lower_fence: result.outlier_threshold_lower,
+        }
+    }
+
+    /// Reads the row lengths of a file with the analyzer, writing none of its reports.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_file_path` - File to read (compressed input is decompressed)
+    /// * `options` - How rows are read and counted and how the fences are set
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(LengthDistribution, u64), io::Error>` - The distribution and the number
+    ///   of rows left out as unreadable, or an Error if the file cannot be read
+    pub fn read(input_file_path: impl AsRef<Path>, options: &AnalysisOptions) -> Result<(LengthDistribution, u64), io::Error> {
+        let result = analyze_csv_row_lengths(input_file_path, "", options)?;
+        let unreadable = result.error_rows;
+        Ok((LengthDistribution::new(result), unreadable))
+    }
+
+    fn is_outlier(&self, length: usize) -> bool {
+        length as f64 > self.upper_fence || (self.lower_fence > 0.0 && (length as f64) < self.lower_fence)
+    }
+
+    /// Number of rows outside the fences.
+    pub fn outlier_rows(&self) -> u64 {
+        self.length_counts.iter()
+            .filter(|&&(length, _)| self.is_outlier(length))
+            .map(|&(_, rows)| rows)
+            .sum()
+    }
+}
+
+/// How the row lengths moved from a baseline to a current file
+#[derive(Debug, Clone)]
+pub struct LengthComparison {
+    pub baseline: LengthDistribution,
+    pub current: LengthDistribution,
+    /// (length, rows) of the current file's outlier lengths that no baseline row has
+    pub new_outlier_lengths: Vec<(usize, u64)>,
+    /// Largest gap between the two cumulative distributions, from 0 to 1
+    pub ks_distance: f64,
+    /// How the rows were measured and fenced, for the markdown report
+    pub method: String,
+}
+
+impl LengthComparison {
+    /// Compares two distributions.
+    ///
+    /// # Arguments
+    ///
+    /// * `baseline` - Distribution of the earlier file
+    /// * `current` - Distribution of the later file
+    /// * `options` - Options both files were read with
+    pub fn new(baseline: LengthDistribution, current: LengthDistribution, options: &AnalysisOptions) -> Self {
+        let baseline_lengths: BTreeMap<usize, u64> = baseline.length_counts.iter().copied().collect();
+        let new_outlier_lengths = current.length_counts.iter()
+            .filter(|&&(length, _)| current.is_outlier(length) && !baseline_lengths.contains_key(&length))
+            .copied()
+            .collect();
+        let ks_distance = ks_distance(&baseline.length_counts, &current.length_counts);
+        let method = format!(
+            "Row lengths in {}, {} line endings; outliers by the {} rule",
+            options.count_mode.name(),
+            if options.include_line_endings { "with" } else { "without" },
+            options.outlier_rule.describe(),
+        );
+        LengthComparison { baseline, current, new_outlier_lengths, ks_distance, method }
+    }
+
+    /// (metric, baseline, current) of every compared statistic, as in the reports.
+    fn metrics(&self) -> Vec<(&'static str, f64, f64)> {
+        let (old, new) = (&self.baseline.statistics, &self.current.statistics);
+        vec![
+            ("rows", self.baseline.rows as f64, self.current.rows as f64),
+            ("mean", old.mean, new.mean),
+            ("median", old.median as f64, new.median as f64),
+            ("q1", old.q1 as f64, new.q1 as f64),
+            ("q3", old.q3 as f64, new.q3 as f64),
+            ("p99", old.p99 as f64, new.p99 as f64),
+            ("min", old.min as f64, new.min as f64),
+            ("max", old.max as f64, new.max as f64),
+            ("std_dev", old.std_dev, new.std_dev),
+            ("upper_fence", self.baseline.upper_fence, self.current.upper_fence),
+            ("outlier_rows", self.baseline.outlier_rows() as f64, self.current.outlier_rows() as f64),
+        ]
+    }
+
+    /// Builds the CSV report: one line per metric, then the KS distance and new outlier lengths.
+    pub fn to_table(&self) -> ReportTable {
+        let mut table = ReportTable::new(&["metric", "baseline", "current", "delta"]);
+        for (metric, old, new) in self.metrics() {
+            table.push(vec![
+                ReportValue::Text(metric.to_string()),
+                ReportValue::Number(rounded(old)),
+                ReportValue::Number(rounded(new)),
+                ReportValue::Number(rounded(new - old)),
+            ]);
+        }
+        table.push(vec![
+            ReportValue::Text("ks_distance".to_string()),
+            ReportValue::Empty,
+            ReportValue::Empty,
+            ReportValue::Number((self.ks_distance * 10_000.0).round() / 10_000.0),
+        ]);
+        table.push(vec![
+            ReportValue::Text("new_outlier_lengths".to_string()),
+            ReportValue::Empty,
+            ReportValue::List(self.new_outlier_lengths.iter().map(|&(length, _)| ReportValue::Count(length as u64)).collect()),
+            ReportValue::Count(self.new_outlier_lengths.iter().map(|&(_, rows)| rows).sum()),
+        ]);
+        table
+    }
+
+    /// Writes the markdown report.
+    ///
+    /// # Arguments
+    ///
+    /// * `baseline_name` - Basename of the baseline file
+    /// * `current_name` - Basename of the current file
+    pub fn to_markdown(&self, baseline_name: &str, current_name: &str) -> String {
+        let mut md = format!("# Row Length Comparison: {} vs {}\n", baseline_name, current_name);
+        md.push_str(&format!("\nBaseline `{}` ({} rows), current `{}` ({} rows)\n",
+                             baseline_name, self.baseline.rows, current_name, self.current.rows));
+        md.push_str(&format!("\n*{}*\n", self.method));
+
+        md.push_str("\n## Statistics\n");
+        md.push_str("\n| Metric | Baseline | Current | Delta |\n");
+        md.push_str("|--------|----------|---------|-------|\n");
+        for (metric, old, new) in self.metrics() {
+            md.push_str(&format!("| {} | {} | {} | {:+} |\n", metric, rounded(old), rounded(new), rounded(new - old)));
+        }
+
+        md.push_str("\n## Distribution Distance\n");
+        md.push_str(&format!("- **KS Distance**: {:.4} ({})\n", self.ks_distance, describe_ks_distance(self.ks_distance)));
+
+        md.push_str("\n## New Outlier Lengths\n");
+        if self.new_outlier_lengths.is_empty() {
+            md.push_str("No outlier length of the current file is new.\n");
+        } else {
+            md.push_str("Outlier lengths of the current file that no baseline row has:\n");
+            md.push_str("\n| Length | Rows |\n");
+            md.push_str("|--------|------|\n");
+            for (length, rows) in &self.new_outlier_lengths {
+                md.push_str(&format!("| {} | {} |\n", length, rows));
+            }
+        }
+        md
+    }
+}
+
+/// Rounds a metric to two decimals for the reports.
+fn rounded(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Kolmogorov–Smirnov statistic of two histograms: the largest gap between their
+/// cumulative distributions.
+///
+/// # Arguments
+///
+/// * `baseline` - (length, rows) in ascending order of length
+/// * `current` - (length, rows) in ascending order of length
+///
+/// # Returns
+///
+/// * `f64` - The distance from 0 to 1, or 0 when either histogram is empty
+pub fn ks_distance(baseline: &[(usize, u64)], current: &[(usize, u64)]) -> f64 {
+    let total = |counts: &[(usize, u64)]| counts.iter().map(|&(_, rows)| rows).sum::<u64>();
+    let (baseline_rows, current_rows) = (total(baseline), total(current));
+    if baseline_rows == 0 || current_rows == 0 {
+        return 0.0;
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (mut baseline_seen, mut current_seen) = (0u64, 0u64);
+    let mut distance: f64 = 0.0;
+    while i < baseline.len() || j < current.len() {
+        // Step past the next length in either histogram, both at once when they share it
+        let length = match (baseline.get(i), current.get(j)) {
+            (Some(&(a, _)), Some(&(b, _))) => a.min(b),
+            (Some(&(a, _)), None) => a,
+            (None, Some(&(b, _))) => b,
+            (None, None) => break,
+        };
+        if let Some(&(a, rows)) = baseline.get(i) && a == length {
+            baseline_seen += rows;
+            i += 1;
+        }
+        if let Some(&(b, rows)) = current.get(j) && b == length {
+            current_seen += rows;
+            j += 1;
+        }
+        let gap = (baseline_seen as f64 / baseline_rows as f64 - current_seen as f64 / current_rows as f64).abs();
+        distance = distance.max(gap);
+    }
+    distance
+}
+
+/// Words for a KS distance in the markdown report.
+fn describe_ks_distance(distance: f64) -> &'static str {
+    match distance {
+        d if d < 0.05 => "about the same distribution",
+        d if d < 0.2 => "a noticeable shift",
+        _ => "a different distribution",
+    }
+}
+
+/// Parses the arguments that follow `compare`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<CompareOptions, String>` - Parsed options or an error message
+pub fn parse_compare_arguments(args: &[String]) -> Result<CompareOptions, String> {
+    let parsed = parse_command_line(&COMPARE_COMMAND, args)?;
+    let (_, mut analysis) = parse_flags(&parsed.flags)?;
+    analysis.no_reports = true;
+    let analysis = analysis.with_quiet(true);
+    match parsed.positionals.as_slice() {
+        [baseline_path, current_path, rest @ ..] => Ok(CompareOptions {
+            baseline_path: baseline_path.clone(),
+            current_path: current_path.clone(),
+            output_dir: rest.first().cloned().unwrap_or_else(|| "reports".to_string()),
+            analysis,
+        }),
+        _ => Err("Expected a baseline and a current file path".to_string()),
+    }
+}
+
+/// Runs the `compare` subcommand: prints the main deltas and writes both reports.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Ok(()) on success, or an Error if an input cannot be read
+///   or a report cannot be written
+pub fn run_compare(options: &CompareOptions) -> Result<(), io::Error> {
+    let (baseline, baseline_unreadable) = LengthDistribution::read(&options.baseline_path, &options.analysis)?;
+    let (current, current_unreadable) = LengthDistribution::read(&options.current_path, &options.analysis)?;
+    for (path, unreadable) in [(&options.baseline_path, baseline_unreadable), (&options.current_path, current_unreadable)] {
+        if unreadable > 0 {
+            eprintln!("Warning: {} rows of {} are not valid UTF-8 and were left out", unreadable, path);
+        }
+    }
+    let comparison = LengthComparison::new(baseline, current, &options.analysis);
+
+    let baseline_name = extract_basename(&options.baseline_path)?;
+    let current_name = extract_basename(&options.current_path)?;
+    let timestamp = generate_timestamp(&Clock::System)?;
+    let output_dir = Path::new(&options.output_dir);
+    fs::create_dir_all(output_dir)?;
+    let report_name = format!("{}_vs_{}_compare_{}", baseline_name, current_name, timestamp);
+    let md_path = output_dir.join(format!("{}.md", report_name));
+    fs::write(&md_path, comparison.to_markdown(&baseline_name, &current_name))?;
+    let csv_path = output_dir.join(format!("{}.csv", report_name));
+    comparison.to_table().write(&csv_path, TableFormat::Csv)?;
+
+    let (old, new) = (&comparison.baseline, &comparison.current);
+    let unit = options.analysis.count_mode.name();
+    println!("Rows: {} -> {} ({:+})", old.rows, new.rows, new.rows as i64 - old.rows as i64);
+    println!("Mean: {:.2} -> {:.2} {} ({:+.2})", old.statistics.mean, new.statistics.mean, unit, new.statistics.mean - old.statistics.mean);
+    println!("Median: {} -> {} {} ({:+})", old.statistics.median, new.statistics.median, unit,
+             new.statistics.median as i64 - old.statistics.median as i64);
+    println!("KS distance: {:.4}", comparison.ks_distance);
+    println!("New outlier lengths: {} ({} rows)", comparison.new_outlier_lengths.len(),
+             comparison.new_outlier_lengths.iter().map(|&(_, rows)| rows).sum::<u64>());
+    println!("Reports: {} and {}", md_path.display(), csv_path.display());
+    Ok(())
+}
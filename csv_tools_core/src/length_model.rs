@@ -0,0 +1,412 @@
+//! # Row-Length Models (`--export-model`, `--score-against`)
+//!
+//! The outlier fences of a run come from the file itself, so a file whose rows are all
+//! twice as long as usual has no outliers at all. A model keeps the length distribution
+//! of a file known to be good - of whole rows and of each column's values - so later
+//! files can be scored against that history instead of only against themselves.
+//!
+//! `--export-model model.json` writes the model of the analyzed file:
+//!
+//! ```json
+//! {
+//!   "model_version": 1,
+//!   "source": "orders",
+//!   "include_line_endings": false,
+//!   "rows": {"count": 1200, "min": 18, "max": 64, "mean": 31.2, "std_dev": 4.1, "q1": 28, "median": 31, "q3": 34},
+//!   "columns": [
+//!     {"name": "id", "length": {"count": 1199, "min": 1, "max": 4, ...}}
+//!   ]
+//! }
+//! ```
+//!
+//! `--score-against model.json` scores every data record of the analyzed file: its
+//! length against the model's rows, and the length of each non-empty value against the
+//! model column of the same name. A length outside the model's 1.5 × IQR fences (on the
+//! `--length-scale`) is unusual. The `model_scores` report lists the records with an
+//! unusual length or unusual values, with the z-score of the record length against the
+//! model's mean and standard deviation; the outlier reports summarize the counts.
+//!
+//! Value lengths are measured as column profiling measures them: trimmed, in characters,
+//! with null tokens counting as empty. A record spanning several lines is scored by its
+//! full length. A model only applies to files measured the same way, so scoring with
+//! a different `--include-line-endings` setting than the export is an error. In a
+//! `--directory` run every file exports to the same path, so the last file's model is
+//! the one kept.
+
+use std::collections::HashMap;
+use std::fs;
+
+use crate::column_profile::ColumnProfiler;
+use crate::count_mode::count_chars;
+use crate::json::{json_number, json_string, parse_json, JsonValue};
+use crate::length_scale::{LengthScale, OutlierFences};
+
+/// Version of the model file layout
+pub const MODEL_VERSION: u64 = 1;
+/// Unusual records kept for the scores report
+const MAX_LISTED_RECORDS: usize = 1000;
+
+/// Length distribution of rows or of one column's values, in characters
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthDistribution {
+    pub count: u64,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub q1: usize,
+    pub median: usize,
+    pub q3: usize,
+}
+
+impl LengthDistribution {
+    /// Fits the distribution to a length histogram, taking quartiles the way the outlier
+    /// reports take them from the sorted lengths. Returns None for an empty histogram.
+    pub fn from_counts(counts: &HashMap<usize, u64>) -> Option<Self> {
+        let mut sorted: Vec<(usize, u64)> = counts.iter().map(|(&length, &count)| (length, count)).collect();
+        sorted.sort_unstable();
+        let count: u64 = sorted.iter().map(|&(_, count)| count).sum();
+        if count == 0 {
+            return None;
+        }
+
+        // Length at a 0-based position of the sorted lengths
+        let length_at = |position: u64| {
+            let mut seen = 0;
+            for &(length, length_count) in &sorted {
+                seen += length_count;
+                if position < seen {
+                    return length;
+                }
+            }
+            sorted.last().map_or(0, |&(length, _)| length)
+        };
+        let quartile = |numerator: u64| {
+            let index = numerator * count / 4;
+            if (numerator * count).is_multiple_of(4) {
+                (length_at(index - 1) + length_at(index)) / 2
+            } else {
+                length_at(index)
+            }
+        };
+
+        let mean = sorted.iter().map(|&(length, n)| length as f64 * n as f64).sum::<f64>() / count as f64;
+        let variance = sorted.iter()
+            .map(|&(length, n)| {
+                let diff = length as f64 - mean;
+                diff * diff * n as f64
+            })
+            .sum::<f64>() / count as f64;
+        Some(LengthDistribution {
+            count,
+            min: sorted.first().map_or(0, |&(length, _)| length),
+            max: sorted.last().map_or(0, |&(length, _)| length),
+            mean,
+            std_dev: variance.sqrt(),
+            q1: quartile(1),
+            median: quartile(2),
+            q3: quartile(3),
+        })
+    }
+
+    /// Distance of a length from the mean, in standard deviations (None without spread).
+    pub fn z_score(&self, length: usize) -> Option<f64> {
+        (self.std_dev > 0.0).then(|| (length as f64 - self.mean) / self.std_dev)
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"count\": {}, \"min\": {}, \"max\": {}, \"mean\": {}, \"std_dev\": {}, \"q1\": {}, \"median\": {}, \"q3\": {}}}",
+            self.count, self.min, self.max, json_number(self.mean), json_number(self.std_dev), self.q1, self.median, self.q3
+        )
+    }
+
+    fn from_json(value: &JsonValue, what: &str) -> Result<Self, String> {
+        let number = |key: &str| {
+            value.get(key)
+                .and_then(JsonValue::as_f64)
+                .filter(|number| *number >= 0.0)
+                .ok_or_else(|| format!("{} has no valid '{}'", what, key))
+        };
+        Ok(LengthDistribution {
+            count: number("count")? as u64,
+            min: number("min")? as usize,
+            max: number("max")? as usize,
+            mean: number("mean")?,
+            std_dev: number("std_dev")?,
+            q1: number("q1")? as usize,
+            median: number("median")? as usize,
+            q3: number("q3")? as usize,
+        })
+    }
+}
+
+/// Length distribution of one named column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnModel {
+    pub name: String,
+    pub length: LengthDistribution,
+}
+
+/// The fitted length distributions of one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthModel {
+    /// Basename of the file the model was fitted to
+    pub source: String,
+    /// Whether row lengths counted the line terminator
+    pub include_line_endings: bool,
+    pub rows: LengthDistribution,
+    /// Columns with at least one non-empty value, in file order
+    pub columns: Vec<ColumnModel>,
+}
+
+impl LengthModel {
+    /// Fits a model to the row-length histogram and the column profiles of a file.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<LengthModel>` - The model, or None when the file had no rows
+    pub fn fit(
+        source: &str,
+        include_line_endings: bool,
+        row_length_counts: &HashMap<usize, u64>,
+        profiler: &ColumnProfiler,
+    ) -> Option<Self> {
+        Some(LengthModel {
+            source: source.to_string(),
+            include_line_endings,
+            rows: LengthDistribution::from_counts(row_length_counts)?,
+            columns: profiler.columns().into_iter()
+                .filter_map(|(_, name, profile)| {
+                    LengthDistribution::from_counts(&profile.length_counts).map(|length| ColumnModel { name, length })
+                })
+                .collect(),
+        })
+    }
+
+    /// Writes the model as a JSON document.
+    pub fn to_json(&self) -> String {
+        let columns: Vec<String> = self.columns.iter()
+            .map(|column| format!("    {{\"name\": {}, \"length\": {}}}", json_string(&column.name), column.length.to_json()))
+            .collect();
+        format!(
+            "{{\n  \"model_version\": {},\n  \"source\": {},\n  \"include_line_endings\": {},\n  \"rows\": {},\n  \"columns\": [\n{}\n  ]\n}}\n",
+            MODEL_VERSION,
+            json_string(&self.source),
+            self.include_line_endings,
+            self.rows.to_json(),
+            columns.join(",\n")
+        )
+    }
+
+    /// Reads a model written by `--export-model`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<LengthModel, String>` - The model, or a message naming the file and problem
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Could not read model {}: {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("Invalid model {}: {}", path, e))
+    }
+
+    /// Parses the JSON text of a model.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let document = parse_json(text)?;
+        match document.get("model_version").and_then(JsonValue::as_f64) {
+            Some(version) if version == MODEL_VERSION as f64 => {},
+            Some(version) => return Err(format!("unsupported model_version {} (expected {})", version, MODEL_VERSION)),
+            None => return Err("missing model_version".to_string()),
+        }
+        let rows = LengthDistribution::from_json(document.get("rows").ok_or("missing rows")?, "rows")?;
+        if rows.count == 0 {
+            return Err("the model has no rows".to_string());
+        }
+        let columns = document.get("columns")
+            .and_then(JsonValue::as_array)
+            .ok_or("missing columns")?
+            .iter()
+            .map(|column| {
+                let name = column.get("name").and_then(JsonValue::as_str).ok_or("a column has no name")?;
+                let what = format!("column '{}'", name);
+                let length = LengthDistribution::from_json(column.get("length").ok_or(format!("{} has no length", what))?, &what)?;
+                Ok(ColumnModel { name: name.to_string(), length })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(LengthModel {
+            source: document.get("source").and_then(JsonValue::as_str).unwrap_or("").to_string(),
+            include_line_endings: document.get("include_line_endings") == Some(&JsonValue::Bool(true)),
+            rows,
+            columns,
+        })
+    }
+}
+
+/// A record with an unusual length or unusual values
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredRecord {
+    /// Row index where the record starts
+    pub row_index: usize,
+    pub length: usize,
+    /// Z-score of the length against the model's rows
+    pub z_score: Option<f64>,
+    /// Whether the length is outside the model's fences
+    pub unusual_length: bool,
+    /// Columns whose value length is outside the model's fences, with that length
+    pub unusual_columns: Vec<(String, usize)>,
+}
+
+/// Scoring of one column's values
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnScore {
+    pub name: String,
+    /// Non-empty values scored
+    pub values: u64,
+    pub unusual_values: u64,
+}
+
+/// Outcome of scoring a file against a model
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelScores {
+    /// Source named in the model
+    pub source: String,
+    pub records: u64,
+    /// Records with an unusual length or at least one unusual value
+    pub unusual_records: u64,
+    /// Records whose length is outside the model's fences
+    pub unusual_lengths: u64,
+    /// One entry per model column found in the header
+    pub columns: Vec<ColumnScore>,
+    /// Header columns the model does not have; their values are not scored
+    pub unmatched_columns: Vec<String>,
+    /// Model columns missing from the header
+    pub missing_columns: Vec<String>,
+    /// The first unusual records
+    pub listed_records: Vec<ScoredRecord>,
+}
+
+/// Scores data records against a model as they are profiled
+#[derive(Debug, Clone)]
+pub struct ModelScorer {
+    model: LengthModel,
+    row_fences: OutlierFences,
+    /// Fences of each model column
+    column_fences: Vec<OutlierFences>,
+    /// Model column of each header column, by header index
+    header_columns: Vec<Option<usize>>,
+    scores: ModelScores,
+}
+
+impl ModelScorer {
+    /// Creates a scorer whose fences are computed on the given scale.
+    pub fn new(model: LengthModel, scale: LengthScale) -> Self {
+        let fences = |length: &LengthDistribution| OutlierFences::new(length.q1, length.q3, scale);
+        ModelScorer {
+            row_fences: fences(&model.rows),
+            column_fences: model.columns.iter().map(|column| fences(&column.length)).collect(),
+            header_columns: Vec::new(),
+            scores: ModelScores {
+                source: model.source.clone(),
+                records: 0,
+                unusual_records: 0,
+                unusual_lengths: 0,
+                columns: Vec::new(),
+                unmatched_columns: Vec::new(),
+                missing_columns: Vec::new(),
+                listed_records: Vec::new(),
+            },
+            model,
+        }
+    }
+
+    /// Matches the header columns to the model's by name; empty names are never matched.
+    pub fn resolve_header(&mut self, header_fields: &[String]) {
+        // Looked up by name, so wide files (100k+ columns) match in linear time
+        let mut model_indices: HashMap<&str, usize> = HashMap::with_capacity(self.model.columns.len());
+        for (index, column) in self.model.columns.iter().enumerate() {
+            model_indices.entry(column.name.as_str()).or_insert(index);
+        }
+        self.header_columns = header_fields.iter()
+            .map(|name| match name.trim() {
+                "" => None,
+                name => model_indices.get(name).copied(),
+            })
+            .collect();
+        self.scores.columns = self.model.columns.iter()
+            .map(|column| ColumnScore { name: column.name.clone(), values: 0, unusual_values: 0 })
+            .collect();
+        self.scores.unmatched_columns = header_fields.iter()
+            .zip(&self.header_columns)
+            .filter(|(name, model_column)| !name.trim().is_empty() && model_column.is_none())
+            .map(|(name, _)| name.clone())
+            .collect();
+        let mut matched = vec![false; self.model.columns.len()];
+        for &index in self.header_columns.iter().flatten() {
+            matched[index] = true;
+        }
+        self.scores.missing_columns = self.model.columns.iter()
+            .enumerate()
+            .filter(|(index, _)| !matched[*index])
+            .map(|(_, column)| column.name.clone())
+            .collect();
+    }
+
+    /// Scores one data record.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_index` - Row index where the record starts
+    /// * `record` - The record without its final terminator
+    /// * `terminator` - The final terminator, counted when the model counts them
+    /// * `fields` - The record's values, with null tokens already emptied
+    pub fn score_record(&mut self, row_index: usize, record: &str, terminator: &str, fields: &[String]) {
+        let mut length = count_chars(record);
+        if self.model.include_line_endings {
+            length += terminator.len();
+        }
+        let is_unusual = |fences: &OutlierFences, length: usize| {
+            let length = length as f64;
+            length > fences.upper || length < fences.lower
+        };
+
+        let mut unusual_columns = Vec::new();
+        for (value, model_column) in fields.iter().zip(&self.header_columns) {
+            let Some(model_column) = *model_column else {
+                continue;
+            };
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            let value_length = count_chars(value);
+            let score = &mut self.scores.columns[model_column];
+            score.values += 1;
+            if is_unusual(&self.column_fences[model_column], value_length) {
+                score.unusual_values += 1;
+                unusual_columns.push((score.name.clone(), value_length));
+            }
+        }
+
+        self.scores.records += 1;
+        let unusual_length = is_unusual(&self.row_fences, length);
+        if unusual_length {
+            self.scores.unusual_lengths += 1;
+        }
+        if unusual_length || !unusual_columns.is_empty() {
+            self.scores.unusual_records += 1;
+            if self.scores.listed_records.len() < MAX_LISTED_RECORDS {
+                self.scores.listed_records.push(ScoredRecord {
+                    row_index,
+                    length,
+                    z_score: self.model.rows.z_score(length),
+                    unusual_length,
+                    unusual_columns,
+                });
+            }
+        }
+    }
+
+    /// Returns the scores of every record seen.
+    pub fn into_scores(self) -> ModelScores {
+        self.scores
+    }
+}
@@ -0,0 +1,312 @@
+//! # Length Scale of the Outlier Fences (`--length-scale`)
+//!
+//! Outliers are rows longer than Q3 + 1.5 × IQR. Row lengths are often log-normal (most
+//! rows short, a long tail of legitimately long ones), and on such data the linear fence
+//! sits inside the tail and flags a large share of the file. The fences can instead be
+//! computed on transformed lengths and mapped back to characters:
+//!
+//! * `linear` - The lengths as they are (default)
+//! * `log` - `ln(1 + length)`, for log-normal lengths
+//! * `sqrt` - `√length`, a milder compression of the tail
+//!
+//! ```text
+//! $ csv_row_analyzer feed.csv reports --length-scale log
+//!
+//! [outliers]
+//! length_scale = log
+//! ```
+//!
+//! Every transform is increasing, so the quartiles of the transformed lengths are the
+//! transformed quartiles. On a non-linear scale, the outlier reports list the fences on
+//! both scales, and how many rows the linear fence would flag, for comparison. Everything
+//! that counts outliers (reports, history, porcelain, badges, the flag stream) uses the
+//! configured scale.
+//!
+//! ## Outlier method (`--outlier-method`, `--outlier-factor`)
+//!
+//! The fences are `factor` spreads away from the center of the (scaled) lengths:
+//!
+//! * `iqr` - Q1 - f × IQR and Q3 + f × IQR (default, f = 1.5)
+//! * `zscore` - mean ± f standard deviations (f = 3)
+//! * `mad` - median ± f × MAD / 0.6745, the modified z-score (f = 3.5); when more than
+//!   half the rows share one length the MAD is 0, and 1.2533 × the mean absolute
+//!   deviation is used instead
+//!
+//! ```text
+//! $ csv_row_analyzer feed.csv reports --outlier-method mad
+//! $ csv_row_analyzer feed.csv reports --outlier-method iqr --outlier-factor 3
+//! ```
+//!
+//! The outlier reports name the method and factor in their header. The fences of a
+//! `--export-model` model stay 1.5 × IQR.
+
+use crate::config::AnalyzerConfig;
+
+/// Scale the outlier fences are computed on
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LengthScale {
+    #[default]
+    Linear,
+    Log,
+    Sqrt,
+}
+
+impl LengthScale {
+    /// Parses a scale name (`linear`, `log`, or `sqrt`).
+    pub fn parse(text: &str) -> Result<LengthScale, String> {
+        match text.trim().to_lowercase().as_str() {
+            "linear" => Ok(LengthScale::Linear),
+            "log" => Ok(LengthScale::Log),
+            "sqrt" => Ok(LengthScale::Sqrt),
+            _ => Err(format!("invalid length scale '{}' (expected linear, log, or sqrt)", text)),
+        }
+    }
+
+    /// Name of the scale, as on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LengthScale::Linear => "linear",
+            LengthScale::Log => "log",
+            LengthScale::Sqrt => "sqrt",
+        }
+    }
+
+    /// The transformed quantity, as shown in the reports.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            LengthScale::Linear => "length",
+            LengthScale::Log => "ln(1 + length)",
+            LengthScale::Sqrt => "√length",
+        }
+    }
+
+    /// Maps a length onto the scale.
+    pub fn transform(&self, length: f64) -> f64 {
+        match self {
+            LengthScale::Linear => length,
+            LengthScale::Log => length.ln_1p(),
+            LengthScale::Sqrt => length.sqrt(),
+        }
+    }
+
+    /// Maps a value on the scale back to a length.
+    pub fn inverse(&self, value: f64) -> f64 {
+        match self {
+            LengthScale::Linear => value,
+            LengthScale::Log => value.exp_m1(),
+            LengthScale::Sqrt => value.max(0.0).powi(2),
+        }
+    }
+}
+
+/// Reads `[outliers] length_scale` from the config file.
+///
+/// # Returns
+///
+/// * `Result<Option<LengthScale>, String>` - The configured scale, if any, or an error
+///   naming the bad line
+pub fn load_length_scale(config: Option<&AnalyzerConfig>) -> Result<Option<LengthScale>, String> {
+    let Some(config) = config else {
+        return Ok(None);
+    };
+    let mut scale = None;
+    for entry in config.section_entries("outliers") {
+        match entry.key.as_str() {
+            "length_scale" => {
+                scale = Some(LengthScale::parse(&entry.value).map_err(|e| format!("line {}: {}", entry.line_number, e))?);
+            },
+            key => return Err(format!("line {}: unknown [outliers] key '{}'", entry.line_number, key)),
+        }
+    }
+    Ok(scale)
+}
+
+/// How the outlier fences are derived from the row lengths
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutlierMethod {
+    #[default]
+    Iqr,
+    ZScore,
+    Mad,
+}
+
+impl OutlierMethod {
+    /// Parses a method name (`iqr`, `zscore`, or `mad`).
+    pub fn parse(text: &str) -> Result<OutlierMethod, String> {
+        match text.trim().to_lowercase().as_str() {
+            "iqr" => Ok(OutlierMethod::Iqr),
+            "zscore" => Ok(OutlierMethod::ZScore),
+            "mad" => Ok(OutlierMethod::Mad),
+            _ => Err(format!("invalid outlier method '{}' (expected iqr, zscore, or mad)", text)),
+        }
+    }
+
+    /// Factor used when `--outlier-factor` is not given.
+    pub fn default_factor(&self) -> f64 {
+        match self {
+            OutlierMethod::Iqr => 1.5,
+            OutlierMethod::ZScore => 3.0,
+            OutlierMethod::Mad => 3.5,
+        }
+    }
+}
+
+/// Method and factor of the outlier fences
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierRule {
+    pub method: OutlierMethod,
+    /// Spreads between the center of the lengths and a fence
+    pub factor: f64,
+}
+
+impl Default for OutlierRule {
+    fn default() -> Self {
+        OutlierRule::new(OutlierMethod::Iqr, None)
+    }
+}
+
+impl OutlierRule {
+    /// A rule with the method's default factor unless one is given.
+    pub fn new(method: OutlierMethod, factor: Option<f64>) -> Self {
+        OutlierRule { method, factor: factor.unwrap_or_else(|| method.default_factor()) }
+    }
+
+    /// Name of the rule, e.g. `1.5 × IQR` or `modified z-score > 3.5`.
+    pub fn describe(&self) -> String {
+        match self.method {
+            OutlierMethod::Iqr => format!("{} × IQR", self.factor),
+            OutlierMethod::ZScore => format!("z-score > {}", self.factor),
+            OutlierMethod::Mad => format!("modified z-score > {}", self.factor),
+        }
+    }
+}
+
+/// Parses an `--outlier-factor` value, which must be a positive number.
+pub fn parse_outlier_factor(text: &str) -> Result<f64, String> {
+    text.trim().parse::<f64>().ok()
+        .filter(|factor| factor.is_finite() && *factor > 0.0)
+        .ok_or_else(|| format!("invalid --outlier-factor value '{}' (use a positive number)", text))
+}
+
+/// Outlier fences of one file, in characters and on the scale they were computed on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierFences {
+    pub rule: OutlierRule,
+    pub scale: LengthScale,
+    /// Rows longer than this many characters are outliers
+    pub upper: f64,
+    /// Rows shorter than this many characters are outliers (when positive)
+    pub lower: f64,
+    /// Upper fence on the scale
+    pub transformed_upper: f64,
+    /// Lower fence on the scale
+    pub transformed_lower: f64,
+}
+
+impl OutlierFences {
+    /// Computes the 1.5 × IQR fences from the quartiles of the row lengths.
+    ///
+    /// # Arguments
+    ///
+    /// * `q1` - 25th percentile of the row lengths
+    /// * `q3` - 75th percentile of the row lengths
+    /// * `scale` - Scale the fences are computed on
+    pub fn new(q1: usize, q3: usize, scale: LengthScale) -> Self {
+        Self::iqr(OutlierRule::default(), q1, q3, scale)
+    }
+
+    /// Computes the fences of a rule from the row lengths.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - Method and factor of the fences
+    /// * `scale` - Scale the fences are computed on
+    /// * `q1` - 25th percentile of the row lengths (for `iqr`)
+    /// * `q3` - 75th percentile of the row lengths (for `iqr`)
+    /// * `length_counts` - (length, rows) pairs in any order (for `zscore` and `mad`)
+    pub fn compute(rule: OutlierRule, scale: LengthScale, q1: usize, q3: usize, length_counts: &[(usize, u64)]) -> Self {
+        let values: Vec<(f64, u64)> = length_counts.iter()
+            .map(|&(length, rows)| (scale.transform(length as f64), rows))
+            .collect();
+        let (center, spread) = match rule.method {
+            OutlierMethod::Iqr => return Self::iqr(rule, q1, q3, scale),
+            OutlierMethod::ZScore => {
+                let rows: u64 = values.iter().map(|&(_, rows)| rows).sum();
+                let mean = values.iter().map(|&(value, rows)| value * rows as f64).sum::<f64>() / rows.max(1) as f64;
+                let variance = values.iter().map(|&(value, rows)| (value - mean).powi(2) * rows as f64).sum::<f64>() / rows.max(1) as f64;
+                (mean, variance.sqrt())
+            },
+            OutlierMethod::Mad => {
+                let median = weighted_median(values.clone());
+                let deviations: Vec<(f64, u64)> = values.iter().map(|&(value, rows)| ((value - median).abs(), rows)).collect();
+                let mad = weighted_median(deviations.clone());
+                let spread = if mad > 0.0 {
+                    mad / 0.6745
+                } else {
+                    let rows: u64 = deviations.iter().map(|&(_, rows)| rows).sum();
+                    1.2533 * deviations.iter().map(|&(deviation, rows)| deviation * rows as f64).sum::<f64>() / rows.max(1) as f64
+                };
+                (median, spread)
+            },
+        };
+        Self::from_transformed(rule, scale, center - rule.factor * spread, center + rule.factor * spread)
+    }
+
+    fn iqr(rule: OutlierRule, q1: usize, q3: usize, scale: LengthScale) -> Self {
+        let (q1, q3) = (scale.transform(q1 as f64), scale.transform(q3 as f64));
+        let iqr = q3 - q1;
+        Self::from_transformed(rule, scale, q1 - rule.factor * iqr, q3 + rule.factor * iqr)
+    }
+
+    fn from_transformed(rule: OutlierRule, scale: LengthScale, transformed_lower: f64, transformed_upper: f64) -> Self {
+        OutlierFences {
+            rule,
+            scale,
+            upper: scale.inverse(transformed_upper),
+            lower: scale.inverse(transformed_lower).max(0.0),
+            transformed_upper,
+            transformed_lower,
+        }
+    }
+
+    /// Name of the method, e.g. `1.5 × IQR of ln(1 + length)`.
+    pub fn method(&self) -> String {
+        match self.scale {
+            LengthScale::Linear => format!("{} method", self.rule.describe()),
+            scale => format!("{} of {}", self.rule.describe(), scale.describe()),
+        }
+    }
+
+    /// Short name of the fence for headings, e.g. `1.5 × IQR (log scale)`.
+    pub fn heading(&self) -> String {
+        match self.scale {
+            LengthScale::Linear => self.rule.describe(),
+            scale => format!("{} ({} scale)", self.rule.describe(), scale.name()),
+        }
+    }
+}
+
+/// Median of weighted values, the mean of the two middle ones for an even count.
+fn weighted_median(mut values: Vec<(f64, u64)>) -> f64 {
+    values.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let rows: u64 = values.iter().map(|&(_, rows)| rows).sum();
+    if rows == 0 {
+        return 0.0;
+    }
+    // Value at a 0-based position of the sorted values
+    let value_at = |position: u64| {
+        let mut seen = 0;
+        for &(value, count) in &values {
+            seen += count;
+            if position < seen {
+                return value;
+            }
+        }
+        values.last().map_or(0.0, |&(value, _)| value)
+    };
+    if rows.is_multiple_of(2) {
+        (value_at(rows / 2 - 1) + value_at(rows / 2)) / 2.0
+    } else {
+        value_at(rows / 2)
+    }
+}
@@ -0,0 +1,307 @@
+//! # Lengths-Only Mode (`--lengths-only`)
+//!
+//! A fast check for "is anything insane in this file" that computes only the row-length
+//! histogram and the summary statistics derived from it. No per-row report, index maps,
+//! or outlier listings are built, so nothing grows with the number of rows:
+//!
+//! ```bash
+//! $ csv_row_analyzer large_file.csv reports --lengths-only
+//! ```
+//!
+//! Instead of one reader feeding the workers, the file is split into one byte range per
+//! worker and every worker reads its own range through its own file handle, so the reads
+//! run in parallel and can saturate the disk. A range boundary usually falls inside a row:
+//! each row belongs to the range holding its first byte, so a worker skips the partial
+//! row at its start and reads past its end to finish its last row.
+//!
+//! Two reports are written:
+//!
+//! * `[basename]_value_counts_report_[timestamp].csv` - The same frequency distribution as
+//!   a full run
+//! * `[basename]_lengths_summary_report_[timestamp].csv` - One line of summary statistics,
+//!   including the outlier fences (`--outlier-method`) and how many rows are above the upper one
+//!
+//! Row lengths are counted in characters (or the unit of `--count-mode`), without the line
+//! terminator, as in a full run; rows that are not valid UTF-8 are counted as unreadable
+//! and left out of the statistics.
+//! Every worker checksums the bytes of its byte range as read, before they are split into
+//! rows, and the bytes of the rows it owns. The range checksums combined in range order
+//! must equal the row checksums combined, and the byte counts must add up to the file
+//! size, so a row lost or read twice at a range boundary stops the run before any report
+//! is written (see the `chunk_checksum` module).
+//!
+//! Byte ranges need the file itself, so gzip input, `--rows`/`--bytes` ranges, and the
+//! flags that need every row (column analyses, row flags, history, and the like) cannot
+//! be combined with `--lengths-only`; `--length-scale` applies to the fences as usual.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::thread;
+
+use crate::chunk_checksum::{Adler32, ChecksumReader};
+use crate::compression::compression_format;
+use crate::count_mode::CountMode;
+use crate::csv_row_analyzer::{generate_timestamp, generated_reports_message, statistics_from_length_counts, AnalysisOptions, AnalysisResult, Statistics};
+use crate::file_workers::{print_file_line, print_file_output};
+use crate::length_scale::OutlierFences;
+use crate::messages::Message;
+use crate::read_retry::ReadRetries;
+use crate::report_buffer::create_report;
+use crate::report_schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_COLUMN};
+use crate::report_status::ReportLog;
+
+/// Read buffer of each worker, unless `--read-buffer-size` is set; large reads keep the disk streaming
+const READ_BUFFER_BYTES: usize = 1 << 20;
+
+/// Length histogram of one byte range, or of the whole file once merged
+#[derive(Debug, Default)]
+struct LengthHistogram {
+    /// Map of row lengths to their frequency
+    row_length_counts: HashMap<usize, u64>,
+    /// Readable rows
+    rows: u64,
+    /// Rows that are not valid UTF-8
+    unreadable_rows: u64,
+    /// Length of all readable rows, in the unit of `--count-mode`
+    total_chars: u64,
+    /// Bytes of the rows owned by the range, terminators included
+    bytes: u64,
+    /// Checksum of the bytes of the rows owned by the range
+    rows_checksum: Adler32,
+    /// Checksum of the bytes of the range itself, as read
+    range_checksum: Adler32,
+}
+
+impl LengthHistogram {
+    /// Counts one row, given without its terminator.
+    fn add_row(&mut self, row: &[u8], count_mode: CountMode) {
+        match std::str::from_utf8(row) {
+            Ok(text) => {
+                let char_count = count_mode.count(text);
+                *self.row_length_counts.entry(char_count).or_insert(0) += 1;
+                self.rows += 1;
+                self.total_chars += char_count as u64;
+            },
+            Err(_) => self.unreadable_rows += 1,
+        }
+    }
+
+    /// Merges the histogram of another range into this one.
+    fn merge(&mut self, other: LengthHistogram) {
+        for (length, count) in other.row_length_counts {
+            *self.row_length_counts.entry(length).or_insert(0) += count;
+        }
+        self.rows += other.rows;
+        self.unreadable_rows += other.unreadable_rows;
+        self.total_chars += other.total_chars;
+        self.bytes += other.bytes;
+        self.rows_checksum.combine(&other.rows_checksum);
+        self.range_checksum.combine(&other.range_checksum);
+    }
+}
+
+/// Analyzes only the row lengths of a file, reading it in parallel byte ranges.
+///
+/// # Arguments
+///
+/// * `input_file_path` - Path to the input CSV file to analyze
+/// * `output_directory_path` - Directory where the two reports are saved (created if needed)
+/// * `options` - Run options; the output layout, fallback directory, clock, and length scale apply
+/// * `workers` - Number of byte ranges, each read by its own thread
+///
+/// # Returns
+///
+/// * `Result<AnalysisResult, io::Error>` - The file's summary once both reports are written
+///   (without a header fingerprint), or an Error if reading or writing fails
+pub fn analyze_lengths_only(
+    input_file_path: impl AsRef<Path>,
+    output_directory_path: impl AsRef<Path>,
+    options: &AnalysisOptions,
+    workers: usize,
+) -> Result<AnalysisResult, io::Error> {
+    let input_file_path = input_file_path.as_ref();
+    let basename = options.report_basename(input_file_path)?;
+    let timestamp = generate_timestamp(&options.clock)?;
+    let file_size = fs::metadata(input_file_path)?.len();
+
+    let mut magic = [0; 4];
+    let read = File::open(input_file_path)?.read(&mut magic)?;
+    if let Some(format) = compression_format(&magic[..read]) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+            "{} is {}-compressed; --lengths-only reads byte ranges of uncompressed files",
+            input_file_path.display(), format
+        )));
+    }
+
+    // Split the file into equal byte ranges; small files get fewer
+    let range_bytes = file_size.div_ceil(workers.max(1) as u64).max(1);
+    let mut handles = Vec::new();
+    let buffer_bytes = options.read_buffer_size.fixed_or(READ_BUFFER_BYTES);
+    let read_retries = ReadRetries::new(options.read_retry);
+    let mut start = 0;
+    while start < file_size {
+        let end = (start + range_bytes).min(file_size);
+        let path = input_file_path.to_path_buf();
+        let count_mode = options.count_mode;
+        let retries = read_retries.clone();
+        handles.push(thread::spawn(move || scan_byte_range(&path, start, end, buffer_bytes, count_mode, &retries)));
+        start = end;
+    }
+    if !options.porcelain && !options.quiet {
+        print_file_line(format_args!("Reading {} bytes in {} byte ranges (lengths only)", file_size, handles.len()));
+    }
+
+    // Merge in range order, which is file order
+    let mut histogram = LengthHistogram::default();
+    for handle in handles {
+        histogram.merge(handle.join().expect("Thread panicked")?);
+    }
+
+    if histogram.bytes != file_size {
+        return Err(io::Error::other(format!(
+            "byte ranges covered {} bytes, the file has {}; no reports were written",
+            histogram.bytes, file_size
+        )));
+    }
+    if histogram.rows_checksum != histogram.range_checksum {
+        return Err(io::Error::other(format!(
+            "byte range checksum mismatch: the rows counted have {}, the ranges read have {}; no reports were written",
+            histogram.rows_checksum, histogram.range_checksum
+        )));
+    }
+    if histogram.unreadable_rows > 0 {
+        eprintln!("{}", Message::UnreadableRowsLeftOut.text(options.language, &[&histogram.unreadable_rows]));
+    }
+    if read_retries.count() > 0 {
+        eprintln!("{}", Message::ReadsRetried.text(options.language, &[&input_file_path.display(), &read_retries.count()]));
+    }
+
+    let statistics = summarize(&histogram);
+
+    // Frequency distribution, sorted by row length (descending) as in a full run
+    let mut length_counts: Vec<(usize, u64)> = histogram.row_length_counts.iter()
+        .map(|(&length, &count)| (length, count))
+        .collect();
+    length_counts.sort_by_key(|entry| Reverse(entry.0));
+    let fences = OutlierFences::compute(options.outlier_rule, options.length_scale.unwrap_or_default(),
+                                        statistics.q1, statistics.q3, &length_counts);
+    let rows_above_upper_fence = length_counts.iter()
+        .filter(|&&(length, _)| length as f64 > fences.upper)
+        .map(|&(_, count)| count)
+        .sum();
+
+    let report_dir = options.output_layout.unwrap_or_default().report_dir(&output_directory_path, &basename, &timestamp);
+    let mut report_log = ReportLog::new(&report_dir, options.report_fallback_dir.as_deref(), options.report_retries)
+        .with_writes_disabled(options.no_reports)
+        .with_language(options.language);
+    let report_name = |kind: &str| format!("{}_{}_report_{}.csv", basename, kind, timestamp);
+    report_log.write("value_counts", &report_name("value_counts"), |path| {
+        let mut freq_report_file = create_report(path, options.report_buffer_size)?;
+        writeln!(freq_report_file, "character_length_of_rows,value_count,{}", SCHEMA_VERSION_COLUMN)?;
+        for (row_length, count) in &length_counts {
+            writeln!(freq_report_file, "{},{},{}", row_length, count, REPORT_SCHEMA_VERSION)?;
+        }
+        freq_report_file.flush()
+    });
+    report_log.write("lengths_summary", &report_name("lengths_summary"), |path| {
+        let mut summary_report_file = create_report(path, options.report_buffer_size)?;
+        writeln!(summary_report_file, "rows,unreadable_rows,total_chars,min,max,mean,median,q1,q3,std_dev,upper_fence,lower_fence,rows_above_upper_fence,{}",
+                 SCHEMA_VERSION_COLUMN)?;
+        writeln!(summary_report_file, "{},{},{},{},{},{:.2},{},{},{},{:.2},{:.2},{:.2},{},{}",
+                 histogram.rows,
+                 histogram.unreadable_rows,
+                 histogram.total_chars,
+                 statistics.min,
+                 statistics.max,
+                 statistics.mean,
+                 statistics.median,
+                 statistics.q1,
+                 statistics.q3,
+                 statistics.std_dev,
+                 fences.upper,
+                 fences.lower,
+                 rows_above_upper_fence,
+                 REPORT_SCHEMA_VERSION)?;
+        summary_report_file.flush()
+    });
+
+    if !options.porcelain && !options.quiet {
+        print_file_line(format_args!("Rows: {} (unreadable: {}), min {}, median {}, max {}, mean {:.2}",
+                                     histogram.rows, histogram.unreadable_rows, statistics.min, statistics.median, statistics.max, statistics.mean));
+        print_file_line(format_args!("Rows above the upper fence ({:.2} chars): {}", fences.upper, rows_above_upper_fence));
+    }
+
+    let result = AnalysisResult {
+        basename,
+        timestamp,
+        total_rows: histogram.rows,
+        error_rows: histogram.unreadable_rows,
+        total_chars: histogram.total_chars as usize,
+        statistics,
+        outlier_threshold_lower: fences.lower,
+        outlier_threshold_upper: fences.upper,
+        outlier_rows: rows_above_upper_fence,
+        short_rows: 0,
+        length_counts,
+        header_fingerprint: None,
+        column_count: 0,
+        read_retries: read_retries.count(),
+    };
+    let generated = if options.prints_progress() {
+        generated_reports_message(&result.basename, &report_log, options)
+    } else {
+        String::new()
+    };
+    report_log.finish().map(|()| result).inspect(|_| print_file_output(&generated))
+}
+
+/// Builds the histogram of the rows whose first byte lies in `start..end`.
+fn scan_byte_range(
+    path: &Path,
+    start: u64,
+    end: u64,
+    buffer_bytes: usize,
+    count_mode: CountMode,
+    retries: &ReadRetries,
+) -> Result<LengthHistogram, io::Error> {
+    let mut file = File::open(path)?;
+    let mut position = start.saturating_sub(1);
+    if start > 0 {
+        // Skip the rest of the row that began in the previous range; reading from the
+        // byte before the range keeps a row that starts exactly at `start`
+        file.seek(SeekFrom::Start(position))?;
+    }
+    let mut reader = ChecksumReader::with_window(BufReader::with_capacity(buffer_bytes, retries.wrap(file)), position, start..end);
+    let mut buffer = Vec::new();
+    if start > 0 {
+        position += reader.read_until(b'\n', &mut buffer)? as u64;
+    }
+
+    let mut histogram = LengthHistogram::default();
+    while position < end {
+        buffer.clear();
+        let read = reader.read_until(b'\n', &mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        position += read as u64;
+        histogram.bytes += read as u64;
+        histogram.rows_checksum.update(&buffer);
+        // Strip the terminator as a full run does
+        let row = buffer.strip_suffix(b"\n").map_or(&buffer[..], |row| row.strip_suffix(b"\r").unwrap_or(row));
+        histogram.add_row(row, count_mode);
+    }
+    histogram.range_checksum = reader.checksum;
+    Ok(histogram)
+}
+
+/// Computes the summary statistics from the merged histogram.
+fn summarize(histogram: &LengthHistogram) -> Statistics {
+    let length_counts: Vec<(usize, u64)> = histogram.row_length_counts.iter()
+        .map(|(&length, &count)| (length, count))
+        .collect();
+    statistics_from_length_counts(&length_counts)
+}
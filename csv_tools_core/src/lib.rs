@@ -0,0 +1,175 @@
+//! CSV Tools Core - Library
+//!
+//! The engine behind the `csv_row_analyzer_rust` binary. The binary's entry point is
+//! [`csv_row_analyzer_main`]. Crates that embed the analyzer call
+//! [`analyze_csv_row_lengths`] with [`AnalysisOptions`] built from the same flags as the
+//! command line, and get an [`AnalysisResult`] back along with the reports:
+//!
+//! ```no_run
+//! use csv_tools_core::{analyze_csv_row_lengths, calculate_statistics, AnalysisOptions};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let options = AnalysisOptions::from_flags(&["--column-totals"])?;
+//! let result = analyze_csv_row_lengths("feeds/orders.csv", "reports", &options)?;
+//! if result.outlier_rows > 0 {
+//!     println!("{}: {} rows longer than {:.0} characters", result.basename, result.outlier_rows, result.outlier_threshold_upper);
+//! }
+//!
+//! // The same statistics for lengths from elsewhere
+//! let statistics = calculate_statistics(&[12, 15, 15, 240]);
+//! assert_eq!(statistics.max, 240);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! They can also write golden tests against its reports with the [`test_support`] module,
+//! and add report formats of their own by implementing [`ReportWriter`] and registering it
+//! in a [`ReportWriterRegistry`]; `--writer <name>` then selects it like a built-in format.
+//! [`RowHasher`] hashes data the way `--hash-algorithm` and `--hash-salt` do, for hosts
+//! that compare their own hashes with the header fingerprints in the reports.
+//!
+//! # Examples
+//!
+//! The programs in `examples/` are end-to-end pipelines built on this API, and compile
+//! with the crate's tests, so they are the contract for the library surface:
+//!
+//! * `upload_handler` - An axum upload route that analyzes the posted file and answers
+//!   with its JSON summary ([`AnalysisResult::to_json`])
+//! * `batch_job` - Every file of a directory analyzed on a rayon thread pool
+//! * `json_summary` - A pipeline gate reading `summary-json` reports back with
+//!   [`AnalysisResult::from_json`]
+//!
+//! ```bash
+//! $ cargo run --release --example batch_job -- exports reports 8
+//! ```
+//!
+//! Embedded analyses use [`AnalysisOptions::with_quiet`] to keep the report status off
+//! the host's standard output. [`AnalysisOptions`] is `Send + Sync`, so one set of options
+//! can serve every thread of a service.
+//!
+//! With the `async` feature, `analyze_csv_file_async` and `analyze_csv_reader_async` run
+//! the same analysis on an existing tokio runtime, taking the options as an
+//! `Arc<AnalysisOptions>` shared with the report writes they hand to the blocking pool.
+//!
+//! # Engine and command line
+//!
+//! The workspace has one library crate, `csv_tools_core`, with the whole engine: reading,
+//! statistics, and report generation. The command-line tools are thin binary crates next
+//! to it; `csv_row_analyzer_rust/src/main.rs` only calls [`csv_row_analyzer_main`]. A tool
+//! embedding the analyzer depends on `csv_tools_core` instead of copying its modules, and
+//! a new command-line tool is another member crate calling into it the same way.
+//!
+//! The engine depends on nothing outside the standard library (tokio comes in only with
+//! the `async` feature), so the binary builds from this workspace alone and installs as a
+//! single file. The formats it reads and writes are implemented in their own modules
+//! (the config file, JSON, gzip and zstd input, Parquet, the row hashes, column name
+//! patterns), and the database sink drives the standard `psql` and `mysql` clients.
+//!
+//! # API stability
+//!
+//! The public API is what this file re-exports, plus [`test_support`]; every other module
+//! is private and can change in any release. The public API follows semantic versioning:
+//! while the version is 0.x, a breaking change to it bumps the minor version, and
+//! additions (new fields of [`AnalysisResult`], new flags accepted by
+//! [`AnalysisOptions::from_flags`]) bump the patch version. The report formats are
+//! versioned separately by the `report_schema_version` column.
+
+// Import the analyzer module and its supporting modules
+mod analysis_json;
+mod analysis_state;
+#[cfg(feature = "async")]
+mod async_analyzer;
+mod badges;
+mod baseline;
+mod bench_compare;
+mod char_report_input;
+mod chunk_checksum;
+mod chunk_sizes;
+mod cli;
+mod clock;
+mod column_profile;
+mod column_rules;
+mod column_sizes;
+mod column_totals;
+mod compression;
+mod config;
+mod count_mode;
+mod csv_row_analyzer;
+mod db_sink;
+mod directory_summary;
+mod directory_walk;
+mod encoding_check;
+mod entropy;
+mod extract_rows;
+mod delimiter;
+mod field_counts;
+mod fields;
+mod file_selection;
+mod file_workers;
+mod fingerprint;
+mod fixture_check;
+mod flag_stream;
+mod grapheme_tables;
+mod graphemes;
+mod history;
+mod html_report;
+mod input_range;
+mod json;
+mod length_buckets;
+mod length_compare;
+mod length_model;
+mod length_scale;
+mod lengths_only;
+mod line_reader;
+mod loader_hints;
+mod logical_records;
+mod low_memory;
+mod messages;
+mod mojibake;
+mod name_pattern;
+mod null_report;
+mod null_tokens;
+mod outlier_rows;
+mod output_layout;
+mod parallel_analyzer;
+mod parquet;
+mod parquet_sidecar;
+mod porcelain;
+mod read_ahead;
+mod read_buffer;
+mod read_retry;
+mod recommendations;
+mod report_buffer;
+mod report_schema;
+mod report_selection;
+mod report_status;
+mod report_table;
+mod report_writers;
+mod row_hash;
+mod row_flags;
+mod row_preview;
+mod row_samples;
+mod run_changes;
+mod seeded_rng;
+mod selftest;
+mod sorted_lengths;
+mod spot_check;
+mod split_rows;
+mod stdin_input;
+mod table_schema;
+pub mod test_support;
+mod translate_rows;
+mod transposed;
+mod truncation;
+mod value_shapes;
+mod zstd;
+
+#[cfg(feature = "async")]
+pub use async_analyzer::{analyze_csv_file_async, analyze_csv_reader_async};
+pub use clock::Clock;
+pub use csv_row_analyzer::{
+    analyze_csv_row_lengths, calculate_statistics, csv_row_analyzer_main, csv_row_analyzer_main_with_writers, AnalysisOptions,
+    AnalysisResult, Statistics,
+};
+pub use report_writers::{ReportWriter, ReportWriterRegistry};
+pub use row_hash::{HashAlgorithm, RowHasher};
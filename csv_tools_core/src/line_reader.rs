@@ -0,0 +1,246 @@
+//! # Physical Line Reader
+//!
+//! Streams a file line by line like `BufRead::lines()`, but also reports each line's
+//! row index, starting byte offset, and length in bytes. Knowing byte offsets lets the
+//! analyzer start or stop in the middle of a file and point back to exact positions.
+//!
+//! Line terminators (`\n` or `\r\n`) are stripped from the content, matching `lines()`,
+//! and reported separately so callers can rebuild records that span lines.
+
+use std::io::{self, BufRead};
+
+/// One physical line read from the input
+#[derive(Debug)]
+pub struct PhysicalLine {
+    /// 0-based index of the line in the file
+    pub row_index: usize,
+    /// Byte offset of the first byte of the line
+    pub byte_offset: u64,
+    /// Length of the line in bytes, including its terminator
+    pub byte_length: u64,
+    /// Line content without terminator, or the error encountered while reading it
+    pub content: Result<String, io::Error>,
+    /// The stripped terminator: `"\n"`, `"\r\n"`, or `""` for a last line without one
+    pub terminator: &'static str,
+}
+
+/// One physical line as read, before its terminator is stripped and its content decoded
+///
+/// Lets the parallel analyzer read on one thread and decode on others (see the
+/// `parallel_analyzer` module).
+#[derive(Debug)]
+pub struct RawLine {
+    /// 0-based index of the line in the file
+    pub row_index: usize,
+    /// Byte offset of the first byte of the line
+    pub byte_offset: u64,
+    /// Bytes of the line, including its terminator, or the error encountered while reading it
+    pub bytes: Result<Vec<u8>, io::Error>,
+}
+
+impl RawLine {
+    /// Decodes the line as the blocking reader does (see [`decode_line`]).
+    pub fn decode(self) -> PhysicalLine {
+        match self.bytes {
+            Ok(bytes) => {
+                let bytes_read = bytes.len();
+                decode_line(bytes, self.row_index, self.byte_offset, bytes_read)
+            },
+            Err(e) => PhysicalLine { row_index: self.row_index, byte_offset: self.byte_offset, byte_length: 0, content: Err(e), terminator: "" },
+        }
+    }
+}
+
+/// Number of lines ending in each kind of terminator
+///
+/// Only `\n` ends a line; a lone `\r` (classic Mac OS) is line content.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LineEndingCounts {
+    pub lf: u64,
+    pub crlf: u64,
+    /// The last line of a file without a final terminator
+    pub none: u64,
+}
+
+impl LineEndingCounts {
+    /// Counts the terminator of one line.
+    pub fn add(&mut self, terminator: &str) {
+        match terminator {
+            "\r\n" => self.crlf += 1,
+            "\n" => self.lf += 1,
+            _ => self.none += 1,
+        }
+    }
+
+    /// Whether both LF and CRLF terminators occur.
+    pub fn is_mixed(&self) -> bool {
+        self.lf > 0 && self.crlf > 0
+    }
+
+    /// Summary such as `LF 120, CRLF 3, none 1`.
+    pub fn describe(&self) -> String {
+        format!("LF {}, CRLF {}, none {}", self.lf, self.crlf, self.none)
+    }
+}
+
+/// Iterator over the physical lines of a buffered reader
+pub struct LineReader<R: BufRead> {
+    reader: R,
+    next_row_index: usize,
+    next_offset: u64,
+    end_row: Option<usize>,
+    end_offset: Option<u64>,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// Creates a reader positioned at the start of the input.
+    pub fn new(reader: R) -> Self {
+        LineReader::starting_at(reader, 0, 0)
+    }
+
+    /// Creates a reader whose underlying stream is already positioned at the start of
+    /// row `row_index`, which begins at byte `byte_offset`.
+    pub fn starting_at(reader: R, row_index: usize, byte_offset: u64) -> Self {
+        LineReader {
+            reader,
+            next_row_index: row_index,
+            next_offset: byte_offset,
+            end_row: None,
+            end_offset: None,
+            buffer: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Stops iteration before the row with this index (exclusive end).
+    pub fn with_end_row(mut self, end_row: Option<usize>) -> Self {
+        self.end_row = end_row;
+        self
+    }
+
+    /// Stops iteration before the first row that begins at or after this byte offset.
+    pub fn with_end_offset(mut self, end_offset: Option<u64>) -> Self {
+        self.end_offset = end_offset;
+        self
+    }
+
+    /// Wraps the underlying reader, keeping the position and the end of the range.
+    pub fn map_reader<S: BufRead>(self, wrap: impl FnOnce(R) -> S) -> LineReader<S> {
+        LineReader {
+            reader: wrap(self.reader),
+            next_row_index: self.next_row_index,
+            next_offset: self.next_offset,
+            end_row: self.end_row,
+            end_offset: self.end_offset,
+            buffer: self.buffer,
+            finished: self.finished,
+        }
+    }
+
+    /// The underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Skips rows without decoding them, returning how many were actually skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of rows to skip
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, io::Error>` - Rows skipped (less than `count` at end of input)
+    pub fn skip_rows(&mut self, count: usize) -> Result<usize, io::Error> {
+        let mut skipped = 0;
+        while skipped < count {
+            self.buffer.clear();
+            let bytes_read = self.reader.read_until(b'\n', &mut self.buffer)?;
+            if bytes_read == 0 {
+                self.finished = true;
+                break;
+            }
+            self.next_row_index += 1;
+            self.next_offset += bytes_read as u64;
+            skipped += 1;
+        }
+        Ok(skipped)
+    }
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// Reads the next line without decoding it, or returns None at the end of the input
+    /// or range.
+    pub fn next_raw(&mut self) -> Option<RawLine> {
+        if self.finished
+            || self.end_row.is_some_and(|end| self.next_row_index >= end)
+            || self.end_offset.is_some_and(|end| self.next_offset >= end)
+        {
+            return None;
+        }
+
+        self.buffer.clear();
+        let row_index = self.next_row_index;
+        let byte_offset = self.next_offset;
+
+        let bytes_read = match self.reader.read_until(b'\n', &mut self.buffer) {
+            Ok(0) => {
+                self.finished = true;
+                return None;
+            },
+            Ok(n) => n,
+            Err(e) => {
+                // A failed read leaves the stream position unknown, so stop after reporting it
+                self.finished = true;
+                return Some(RawLine { row_index, byte_offset, bytes: Err(e) });
+            }
+        };
+
+        self.next_row_index += 1;
+        self.next_offset += bytes_read as u64;
+
+        Some(RawLine { row_index, byte_offset, bytes: Ok(std::mem::take(&mut self.buffer)) })
+    }
+}
+
+impl<R: BufRead> Iterator for LineReader<R> {
+    type Item = PhysicalLine;
+
+    fn next(&mut self) -> Option<PhysicalLine> {
+        self.next_raw().map(RawLine::decode)
+    }
+}
+
+/// Builds a `PhysicalLine` from the raw bytes of one line, terminator included.
+///
+/// Strips the terminator the same way `BufRead::lines()` does and decodes the rest
+/// as UTF-8, so every reader (blocking or async) reports lines identically.
+///
+/// # Arguments
+///
+/// * `buffer` - Bytes of the line as read, including its terminator if any
+/// * `row_index` - 0-based index of the line in the file
+/// * `byte_offset` - Byte offset of the first byte of the line
+/// * `bytes_read` - Number of bytes read for the line
+///
+/// # Returns
+///
+/// * `PhysicalLine` - The decoded line and its position
+pub fn decode_line(mut buffer: Vec<u8>, row_index: usize, byte_offset: u64, bytes_read: usize) -> PhysicalLine {
+    let mut terminator = "";
+    if buffer.last() == Some(&b'\n') {
+        buffer.pop();
+        terminator = "\n";
+        if buffer.last() == Some(&b'\r') {
+            buffer.pop();
+            terminator = "\r\n";
+        }
+    }
+
+    let content = String::from_utf8(buffer)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8"));
+
+    PhysicalLine { row_index, byte_offset, byte_length: bytes_read as u64, content, terminator }
+}
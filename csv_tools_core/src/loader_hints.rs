@@ -0,0 +1,216 @@
+//! # Loader Hints (`--loader-hints`)
+//!
+//! Turns what the column profile found into load settings for PostgreSQL, Amazon
+//! Redshift, and Snowflake, written as `[basename]_loader_hints_[timestamp].sql`:
+//!
+//! ```sql
+//! -- PostgreSQL (a file on the server; use \copy in psql for a local one)
+//! COPY orders FROM '/data/orders.csv' WITH (FORMAT csv, HEADER true, DELIMITER ',', QUOTE '"', NULL '');
+//!
+//! -- Amazon Redshift
+//! COPY orders FROM 's3://<bucket>/orders.csv' IAM_ROLE '<role-arn>' CSV DELIMITER ',' QUOTE AS '"' IGNOREHEADER 1 EMPTYASNULL;
+//!
+//! -- Snowflake
+//! PUT file:///data/orders.csv @%orders;
+//! COPY INTO orders FROM @%orders FILE_FORMAT = (TYPE = CSV FIELD_DELIMITER = ',' FIELD_OPTIONALLY_ENCLOSED_BY = '"' SKIP_HEADER = 1 NULL_IF = ('') EMPTY_FIELD_AS_NULL = TRUE);
+//! ```
+//!
+//! The settings follow the file:
+//!
+//! * Delimiter and quote of the analysis, with the first row as the header
+//! * NULL token: the first `--null-tokens` token in PostgreSQL and Redshift, which take
+//!   one (the others are named in a comment, since they load as text), and all of them in
+//!   Snowflake's `NULL_IF`; without tokens, empty values load as NULL
+//! * Max field size: the widest value in bytes, checked against Redshift's `VARCHAR(65535)`
+//!   and Snowflake's 16 MB; a wider value gets `TRUNCATECOLUMNS` and a comment
+//! * Compression: gzip and zstd input is decompressed with `PROGRAM` in PostgreSQL and
+//!   declared with `GZIP`/`ZSTD` and `COMPRESSION` in the others
+//!
+//! Columns whose values contain line breaks are named in a comment, since those loads
+//! only work while the fields stay quoted. The table name comes from the file name, and
+//! the bucket and role are placeholders: review them, and the column types from
+//! `--profile-columns` or `--table-schema`, before running a load.
+
+use std::path::Path;
+
+use crate::column_profile::ColumnProfiler;
+
+/// Widest `VARCHAR` in Redshift, in bytes
+const REDSHIFT_MAX_VARCHAR: usize = 65_535;
+/// Width of a Redshift `VARCHAR` declared without one
+const REDSHIFT_DEFAULT_VARCHAR: usize = 256;
+/// Widest `VARCHAR` in Snowflake, in bytes
+const SNOWFLAKE_MAX_VARCHAR: usize = 16 << 20;
+
+/// Load settings derived from the column profile of one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoaderHints {
+    /// Field delimiter of the analysis
+    delimiter: char,
+    /// Null tokens for every column (`--null-tokens`)
+    null_tokens: Vec<String>,
+    /// (column name, bytes) of the widest value, if any column had a value
+    widest_field: Option<(String, usize)>,
+    /// Columns with line breaks inside their values
+    line_break_columns: Vec<String>,
+}
+
+impl LoaderHints {
+    /// Collects the settings from the profiled columns.
+    pub fn from_profile(profiler: &ColumnProfiler) -> Self {
+        let columns = profiler.columns();
+        let widest_field = columns.iter()
+            .filter(|(_, _, profile)| profile.non_empty_values > 0)
+            .max_by_key(|(index, _, profile)| (profile.max_bytes, std::cmp::Reverse(*index)))
+            .map(|(_, name, profile)| (name.clone(), profile.max_bytes));
+        LoaderHints {
+            delimiter: profiler.delimiter(),
+            null_tokens: profiler.null_tokens().to_vec(),
+            widest_field,
+            line_break_columns: columns.iter()
+                .filter(|(_, _, profile)| profile.has_line_breaks())
+                .map(|(_, name, _)| name.clone())
+                .collect(),
+        }
+    }
+
+    /// Writes the hints as SQL statements with comments.
+    ///
+    /// # Arguments
+    ///
+    /// * `basename` - Basename of the input, which names the table
+    /// * `input_file_path` - Path of the input, or None for standard input
+    /// * `compression` - `gzip` or `zstd` for compressed input
+    ///
+    /// # Returns
+    ///
+    /// * `String` - A PostgreSQL, a Redshift, and a Snowflake section
+    pub fn to_sql(&self, basename: &str, input_file_path: Option<&Path>, compression: Option<&str>) -> String {
+        let table = table_name(basename);
+        let path = input_file_path.map_or_else(|| "<file>".to_string(), |path| path.display().to_string());
+        let file_name = input_file_path
+            .and_then(Path::file_name)
+            .map_or_else(|| "<file>".to_string(), |name| name.to_string_lossy().into_owned());
+        let null_token = self.null_tokens.first().map_or("", String::as_str);
+        let widest = self.widest_field.as_ref().map_or(0, |(_, bytes)| *bytes);
+
+        let mut sql = format!("-- Loader hints for {}, generated by csv_row_analyzer\n", file_name);
+        sql.push_str(&format!("-- Delimiter {}, quote '\"', header row 1", describe_char(self.delimiter)));
+        match &self.widest_field {
+            Some((column, bytes)) => sql.push_str(&format!(", widest field {} bytes (column {})\n", bytes, column)),
+            None => sql.push_str(", no values\n"),
+        }
+        if !self.line_break_columns.is_empty() {
+            sql.push_str(&format!(
+                "-- Values with line breaks in {}: keep the fields quoted for these loads\n",
+                self.line_break_columns.join(", ")
+            ));
+        }
+        if self.null_tokens.len() > 1 {
+            sql.push_str(&format!(
+                "-- PostgreSQL and Redshift take one NULL token, so these load as text there: {}\n",
+                self.null_tokens[1..].join(", ")
+            ));
+        }
+
+        // PostgreSQL reads a compressed file through a program on the server
+        let source = match compression {
+            Some(format) => format!("PROGRAM {}", postgres_string(&format!("{} -dc \"{}\"", format, path))),
+            None if input_file_path.is_none() => "STDIN".to_string(),
+            None => postgres_string(&path),
+        };
+        sql.push_str("\n-- PostgreSQL (a file on the server; use \\copy in psql for a local one)\n");
+        sql.push_str(&format!(
+            "COPY {} FROM {} WITH (FORMAT csv, HEADER true, DELIMITER {}, QUOTE '\"', NULL {});\n",
+            table, source, postgres_char(self.delimiter), postgres_string(null_token)
+        ));
+
+        sql.push_str("\n-- Amazon Redshift\n");
+        let mut redshift = format!(
+            "COPY {} FROM {} IAM_ROLE '<role-arn>' CSV DELIMITER {} QUOTE AS '\"' IGNOREHEADER 1",
+            table, escaped_string(&format!("s3://<bucket>/{}", file_name)), escaped_char(self.delimiter)
+        );
+        if !null_token.is_empty() {
+            redshift.push_str(&format!(" NULL AS {}", escaped_string(null_token)));
+        }
+        redshift.push_str(" EMPTYASNULL");
+        if let Some(format) = compression {
+            redshift.push_str(&format!(" {}", format.to_uppercase()));
+        }
+        if widest > REDSHIFT_MAX_VARCHAR {
+            sql.push_str(&format!("-- The widest field is over VARCHAR({}), so it is cut to fit\n", REDSHIFT_MAX_VARCHAR));
+            redshift.push_str(" TRUNCATECOLUMNS");
+        } else if widest > REDSHIFT_DEFAULT_VARCHAR {
+            sql.push_str(&format!("-- Declare the text columns up to VARCHAR({}); a bare VARCHAR holds {} bytes\n",
+                                  widest, REDSHIFT_DEFAULT_VARCHAR));
+        }
+        sql.push_str(&redshift);
+        sql.push_str(";\n");
+
+        sql.push_str("\n-- Snowflake\n");
+        let null_if: Vec<String> = std::iter::once("")
+            .chain(self.null_tokens.iter().map(String::as_str))
+            .map(escaped_string)
+            .collect();
+        let mut format = format!(
+            "TYPE = CSV FIELD_DELIMITER = {} FIELD_OPTIONALLY_ENCLOSED_BY = '\"' SKIP_HEADER = 1 NULL_IF = ({}) EMPTY_FIELD_AS_NULL = TRUE",
+            escaped_char(self.delimiter), null_if.join(", ")
+        );
+        if let Some(compression) = compression {
+            format.push_str(&format!(" COMPRESSION = {}", compression.to_uppercase()));
+        }
+        let mut copy_options = String::new();
+        if widest > SNOWFLAKE_MAX_VARCHAR {
+            sql.push_str(&format!("-- The widest field is over {} bytes, so it is cut to fit\n", SNOWFLAKE_MAX_VARCHAR));
+            copy_options.push_str(" TRUNCATECOLUMNS = TRUE");
+        }
+        sql.push_str(&format!("PUT file://{} @%{};\n", path, table));
+        sql.push_str(&format!("COPY INTO {} FROM @%{} FILE_FORMAT = ({}){};\n", table, table, format, copy_options));
+        sql
+    }
+}
+
+/// Table name from a report basename: lowercase letters, digits, and underscores.
+fn table_name(basename: &str) -> String {
+    let name: String = basename.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    match name.chars().next() {
+        Some(first) if !first.is_ascii_digit() => name,
+        _ => format!("t_{}", name),
+    }
+}
+
+/// Names a delimiter for the header comment.
+fn describe_char(c: char) -> String {
+    match c {
+        '\t' => "tab".to_string(),
+        c => format!("'{}'", c),
+    }
+}
+
+/// PostgreSQL standard string, where only quotes are doubled.
+fn postgres_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// PostgreSQL literal of one character; a tab needs an escape string.
+fn postgres_char(c: char) -> String {
+    match c {
+        '\t' => "E'\\t'".to_string(),
+        c => postgres_string(&c.to_string()),
+    }
+}
+
+/// Redshift and Snowflake string, where backslashes are escapes too.
+fn escaped_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Redshift and Snowflake literal of one character.
+fn escaped_char(c: char) -> String {
+    match c {
+        '\t' => "'\\t'".to_string(),
+        c => escaped_string(&c.to_string()),
+    }
+}
@@ -0,0 +1,142 @@
+//! # Logical Records (`--csv-mode`)
+//!
+//! By default every physical line is a row, so a quoted field with a line break in it
+//! splits one CSV record into two short rows, and both skew the length statistics. With
+//! `--csv-mode`, the lines are joined into RFC 4180 records first, tracking whether a
+//! quoted field is still open at the end of each line (see the `fields` module):
+//!
+//! ```bash
+//! $ csv_row_analyzer notes.csv reports --csv-mode
+//! $ head -3 reports/notes_char_counts_report_1767225600.csv
+//! row_index,file_row,data_index,character_length,physical_lines,report_schema_version
+//! 0,1,-1,13,1,5
+//! 1,2,0,48,3,5
+//! ```
+//!
+//! Every row of the reports is then a record: `row_index` and `data_index` count records,
+//! `file_row` is the line number the record starts on, and the char_counts report adds
+//! `physical_lines`, the number of lines the record spans. The line breaks inside a
+//! record count as characters of its length, as they are part of a value; the final
+//! terminator only counts with `--include-line-endings`.
+//!
+//! A record with an unreadable line in it is unreadable as a whole. A record still open
+//! after [`MAX_RECORD_LINES`] lines, or at the end of the input, is closed there.
+
+use std::io;
+
+use crate::fields::{ends_in_quotes, MAX_RECORD_LINES};
+use crate::line_reader::PhysicalLine;
+
+/// One logical record, joined from the physical lines it spans
+#[derive(Debug)]
+pub struct LogicalRecord {
+    /// The record as one line: `row_index` is the 0-based record index, the content keeps
+    /// the line breaks inside quoted fields, and the terminator is the last line's
+    pub line: PhysicalLine,
+    /// Characters of the content, line breaks inside the record included
+    pub content_chars: usize,
+    /// 0-based index of the physical line the record starts on
+    pub first_line: usize,
+    /// Number of physical lines the record spans
+    pub physical_lines: usize,
+}
+
+/// A record whose quoted field is still open
+#[derive(Debug)]
+struct OpenRecord {
+    first_line: usize,
+    byte_offset: u64,
+    byte_length: u64,
+    content: String,
+    content_chars: usize,
+    physical_lines: usize,
+    /// The first unreadable line of the record, if any
+    error: Option<io::Error>,
+}
+
+/// Joins physical lines into logical records
+#[derive(Debug)]
+pub struct RecordAssembler {
+    delimiter: char,
+    next_record_index: usize,
+    open_record: Option<OpenRecord>,
+}
+
+impl RecordAssembler {
+    /// Creates an assembler for the given field delimiter, starting at record 0.
+    pub fn new(delimiter: char) -> Self {
+        RecordAssembler { delimiter, next_record_index: 0, open_record: None }
+    }
+
+    /// Adds the next physical line, returning the record it completes.
+    ///
+    /// # Arguments
+    ///
+    /// * `physical_line` - The next line of the input
+    /// * `content_chars` - Characters of the line without its terminator (0 for an unreadable line)
+    ///
+    /// # Returns
+    ///
+    /// * `Option<LogicalRecord>` - The completed record, or None while a quoted field is open
+    pub fn push(&mut self, physical_line: PhysicalLine, content_chars: usize) -> Option<LogicalRecord> {
+        let mut record = self.open_record.take().unwrap_or_else(|| OpenRecord {
+            first_line: physical_line.row_index,
+            byte_offset: physical_line.byte_offset,
+            byte_length: 0,
+            content: String::new(),
+            content_chars: 0,
+            physical_lines: 0,
+            error: None,
+        });
+        let starts_in_quotes = record.physical_lines > 0;
+        record.byte_length += physical_line.byte_length;
+        record.physical_lines += 1;
+        // The quoting of an unreadable line is unknown, so the record stays as open as it was
+        let in_quotes = match physical_line.content {
+            Ok(line) => {
+                let in_quotes = ends_in_quotes(&line, self.delimiter, starts_in_quotes);
+                record.content.push_str(&line);
+                record.content_chars += content_chars;
+                in_quotes
+            },
+            Err(e) => {
+                record.error.get_or_insert(e);
+                starts_in_quotes
+            },
+        };
+
+        let terminator = physical_line.terminator;
+        if in_quotes && !terminator.is_empty() && record.physical_lines < MAX_RECORD_LINES {
+            record.content.push_str(terminator);
+            record.content_chars += terminator.len();
+            self.open_record = Some(record);
+            return None;
+        }
+        Some(self.complete(record, terminator))
+    }
+
+    /// Closes a record left open at the end of the input (unterminated quote).
+    pub fn finish(&mut self) -> Option<LogicalRecord> {
+        let mut record = self.open_record.take()?;
+        // The open record kept the terminator of its last line as part of a value
+        let terminator = if record.content.ends_with("\r\n") { "\r\n" } else { "\n" };
+        record.content.truncate(record.content.len() - terminator.len());
+        record.content_chars -= terminator.len();
+        Some(self.complete(record, terminator))
+    }
+
+    fn complete(&mut self, record: OpenRecord, terminator: &'static str) -> LogicalRecord {
+        let row_index = self.next_record_index;
+        self.next_record_index += 1;
+        let content = match record.error {
+            Some(e) => Err(e),
+            None => Ok(record.content),
+        };
+        LogicalRecord {
+            line: PhysicalLine { row_index, byte_offset: record.byte_offset, byte_length: record.byte_length, content, terminator },
+            content_chars: record.content_chars,
+            first_line: record.first_line,
+            physical_lines: record.physical_lines,
+        }
+    }
+}
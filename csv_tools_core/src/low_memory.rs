@@ -0,0 +1,245 @@
+//! # Constant-Memory Lengths (`--low-memory`)
+//!
+//! By default the analyzer keeps the length of every row for the statistics (8 bytes a
+//! row, 8 GB for a billion rows) and up to `--max-example-rows` row indices for every
+//! distinct length. With `--low-memory`, what it keeps no longer grows with the rows:
+//!
+//! * No per-row lengths: the statistics come from the length histogram, exactly as long
+//!   as it has at most [`LOW_MEMORY_MAX_LENGTHS`] distinct lengths
+//! * Beyond that, lengths are grouped into buckets of equal width, doubled each time the
+//!   histogram fills up again, and each bucket is listed by its shortest length. The
+//!   median, quartiles, and 99th percentile are then P² estimates (Jain & Chlamtac, 1985)
+//!   kept over every row in five markers each; the minimum, maximum, mean, and standard
+//!   deviation stay exact
+//! * At most as many example rows per length (or bucket) as the reports show, the first
+//!   ones in file order, so a database sink from the config also gets only those
+//!
+//! Flags that keep something for every row (`--csv-mode`, `--row-flags`, `--field-counts`,
+//! `--entropy`, `--length-buckets`, `--extract-outliers`, `--verify-sample`,
+//! `--parquet-sidecar`) are refused. Both outlier reports say when the mode is on, and
+//! when the lengths were grouped.
+//!
+//! ```bash
+//! $ csv_row_analyzer billion_rows.csv reports --low-memory
+//! ```
+
+use std::collections::HashMap;
+
+use crate::csv_row_analyzer::Statistics;
+
+/// Distinct lengths (or buckets) the histogram holds before the buckets are widened
+pub const LOW_MEMORY_MAX_LENGTHS: usize = 1 << 16;
+
+/// Running P² estimate of one quantile, in five markers
+#[derive(Debug, Clone)]
+struct P2Quantile {
+    /// Quantile estimated, between 0 and 1
+    quantile: f64,
+    /// Marker heights; the middle one is the estimate
+    heights: [f64; 5],
+    /// Actual marker positions (1-based ranks)
+    positions: [f64; 5],
+    /// Desired marker positions
+    desired: [f64; 5],
+    /// Increments of the desired positions per observation
+    increments: [f64; 5],
+    observed: usize,
+}
+
+impl P2Quantile {
+    fn new(quantile: f64) -> Self {
+        P2Quantile {
+            quantile,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * quantile, 1.0 + 4.0 * quantile, 3.0 + 2.0 * quantile, 5.0],
+            increments: [0.0, quantile / 2.0, quantile, (1.0 + quantile) / 2.0, 1.0],
+            observed: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        if self.observed < 5 {
+            self.heights[self.observed] = value;
+            self.observed += 1;
+            if self.observed == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.observed += 1;
+
+        // Cell of the new value, widening the outer markers when it lies beyond them
+        let cell = if value < self.heights[0] {
+            self.heights[0] = value;
+            0
+        } else if value >= self.heights[4] {
+            self.heights[4] = value;
+            3
+        } else {
+            (0..4).rfind(|&i| self.heights[i] <= value).unwrap_or(0)
+        };
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        // Move the inner markers towards their desired positions
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            if (offset >= 1.0 && self.positions[i + 1] - self.positions[i] > 1.0)
+                || (offset <= -1.0 && self.positions[i - 1] - self.positions[i] < -1.0)
+            {
+                let step = offset.signum();
+                let parabolic = self.parabolic(i, step);
+                self.heights[i] = if self.heights[i - 1] < parabolic && parabolic < self.heights[i + 1] {
+                    parabolic
+                } else {
+                    let neighbour = if step > 0.0 { i + 1 } else { i - 1 };
+                    self.heights[i] + step * (self.heights[neighbour] - self.heights[i])
+                        / (self.positions[neighbour] - self.positions[i])
+                };
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    /// Piecewise-parabolic height of marker `i` moved by `step`.
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn estimate(&self) -> f64 {
+        if self.observed >= 5 {
+            return self.heights[2];
+        }
+        // Nearest rank among the few values seen
+        let mut seen = self.heights[..self.observed].to_vec();
+        seen.sort_by(f64::total_cmp);
+        let rank = ((self.quantile * seen.len() as f64).ceil() as usize).clamp(1, seen.len().max(1));
+        seen.get(rank - 1).copied().unwrap_or(0.0)
+    }
+}
+
+/// Bucket width of the histogram and running statistics of every row, in constant memory
+#[derive(Debug, Clone)]
+pub struct LowMemoryLengths {
+    /// Width of the length buckets, 1 until the histogram first fills up
+    bucket_width: usize,
+    rows: u64,
+    min: usize,
+    max: usize,
+    mean: f64,
+    /// Sum of squared differences from the running mean (Welford)
+    squared_deviations: f64,
+    median: P2Quantile,
+    q1: P2Quantile,
+    q3: P2Quantile,
+    p99: P2Quantile,
+}
+
+impl Default for LowMemoryLengths {
+    fn default() -> Self {
+        LowMemoryLengths {
+            bucket_width: 1,
+            rows: 0,
+            min: usize::MAX,
+            max: 0,
+            mean: 0.0,
+            squared_deviations: 0.0,
+            median: P2Quantile::new(0.5),
+            q1: P2Quantile::new(0.25),
+            q3: P2Quantile::new(0.75),
+            p99: P2Quantile::new(0.99),
+        }
+    }
+}
+
+impl LowMemoryLengths {
+    /// Notes the length of one row and returns its histogram key (the bucket's shortest length).
+    pub fn observe(&mut self, length: usize) -> usize {
+        self.rows += 1;
+        self.min = self.min.min(length);
+        self.max = self.max.max(length);
+        let value = length as f64;
+        let delta = value - self.mean;
+        self.mean += delta / self.rows as f64;
+        self.squared_deviations += delta * (value - self.mean);
+        for quantile in [&mut self.median, &mut self.q1, &mut self.q3, &mut self.p99] {
+            quantile.observe(value);
+        }
+        length / self.bucket_width * self.bucket_width
+    }
+
+    /// Widens the buckets while the histogram holds more than [`LOW_MEMORY_MAX_LENGTHS`]
+    /// keys, merging their counts and keeping the first `example_rows` row indices of each.
+    pub fn fit_histogram(&mut self, counts: &mut HashMap<usize, u64>, examples: &mut HashMap<usize, Vec<usize>>, example_rows: usize) {
+        while counts.len() > LOW_MEMORY_MAX_LENGTHS {
+            self.bucket_width *= 2;
+            let width = self.bucket_width;
+            let mut merged_counts = HashMap::with_capacity(counts.len() / 2 + 1);
+            for (length, count) in counts.drain() {
+                *merged_counts.entry(length / width * width).or_insert(0) += count;
+            }
+            *counts = merged_counts;
+
+            let mut merged_examples: HashMap<usize, Vec<usize>> = HashMap::with_capacity(examples.len() / 2 + 1);
+            for (length, indices) in examples.drain() {
+                merged_examples.entry(length / width * width).or_default().extend(indices);
+            }
+            for indices in merged_examples.values_mut() {
+                indices.sort_unstable();
+                indices.truncate(example_rows);
+            }
+            *examples = merged_examples;
+        }
+    }
+
+    /// Width of the length buckets (1 while every length has its own entry).
+    pub fn bucket_width(&self) -> usize {
+        self.bucket_width
+    }
+
+    /// Statistics of every row observed, with estimated quantiles.
+    pub fn estimated_statistics(&self) -> Statistics {
+        if self.rows == 0 {
+            return Statistics { min: 0, max: 0, mean: 0.0, median: 0, q1: 0, q3: 0, p99: 0, std_dev: 0.0 };
+        }
+        // An estimate never lies outside the lengths seen
+        let length = |quantile: &P2Quantile| (quantile.estimate().round() as usize).clamp(self.min, self.max);
+        Statistics {
+            min: self.min,
+            max: self.max,
+            mean: self.mean,
+            median: length(&self.median),
+            q1: length(&self.q1),
+            q3: length(&self.q3),
+            p99: length(&self.p99),
+            std_dev: (self.squared_deviations / self.rows as f64).sqrt(),
+        }
+    }
+}
+
+/// Describes the mode for the outlier reports.
+///
+/// # Arguments
+///
+/// * `bucket_width` - Width of the length buckets at the end of the pass
+/// * `example_rows` - Example rows kept per length or bucket
+pub fn describe_low_memory(bucket_width: usize, example_rows: usize) -> String {
+    if bucket_width == 1 {
+        format!("Low-memory mode: statistics from the length histogram, at most {} example rows per length", example_rows)
+    } else {
+        format!(
+            "Low-memory mode: more than {} distinct lengths, so lengths are grouped into buckets of {} characters \
+             (listed by their shortest length) and the median, quartiles, and 99th percentile are P² estimates; \
+             at most {} example rows per bucket",
+            LOW_MEMORY_MAX_LENGTHS, bucket_width, example_rows
+        )
+    }
+}
@@ -0,0 +1,695 @@
+//! # Message Catalog (`--lang`)
+//!
+//! Console messages of an analysis run and the section headings of the outliers reports,
+//! in English (`en`, the default), Spanish (`es`), and Portuguese (`pt`):
+//!
+//! ```text
+//! $ csv_row_analyzer_rust pedidos.csv --lang es
+//! Analizando el archivo CSV: pedidos.csv (pedidos.csv)
+//! Los informes se guardarán en: reports
+//! ```
+//!
+//! Only what an operator reads is translated: the progress and summary lines, and every
+//! warning of an analysis run, including the details of truncation, transposition, run
+//! changes, baseline regressions, and failed spot checks. Everything a program parses
+//! stays in English whatever the language: report file names, CSV and JSON columns, the
+//! `--porcelain` line, the history file, and the rows of the status table. Argument
+//! errors (the language is not known yet), `--help`, the subcommands, and the body text
+//! of the reports are also English, as are the names the reports share with the
+//! warnings: metrics, decimal separator styles, and file-selection reasons.
+//!
+//! A message is a template with `{}` placeholders, filled in order by [`Message::text`].
+//! Adding a language means adding a [`Language`] variant and one column to
+//! [`Message::templates`]; the compiler then points at every message missing it.
+
+use std::fmt::Display;
+
+/// Language of console messages and report headings
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    Portuguese,
+}
+
+impl Language {
+    /// Parses a language code (`en`, `es`, `pt`); a region such as `pt-BR` or `es_MX`
+    /// is accepted and ignored.
+    pub fn parse(code: &str) -> Result<Language, String> {
+        let language = code.split(['-', '_']).next().unwrap_or("").trim().to_lowercase();
+        match language.as_str() {
+            "en" => Ok(Language::English),
+            "es" => Ok(Language::Spanish),
+            "pt" => Ok(Language::Portuguese),
+            _ => Err(format!("unknown language '{}' (expected en, es, or pt)", code)),
+        }
+    }
+}
+
+/// A translated console message or report heading
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Message {
+    AnalyzingFile,
+    AnalyzingDirectory,
+    AnalyzingGlob,
+    ReportsSavedTo,
+    ProcessingFile,
+    ProcessedFiles,
+    ProcessedMatchingFiles,
+    TruncatedFiles,
+    FailedFiles,
+    RegressedFiles,
+    SkippedFiles,
+    SkippedFilesHint,
+    ErrorReadingRow,
+    ErrorLoadingConfig,
+    ErrorAnalyzingFile,
+    ErrorAnalyzingNamedFile,
+    ErrorProcessingDirectory,
+    ReportStatus,
+    FileWarning,
+    PreviewFailed,
+    GzipMembersRead,
+    ReadsRetried,
+    UnreadableRowsLeftOut,
+    RowReportFailed,
+    FlagStreamFailed,
+    NoRowsForModel,
+    HistoryReadFailed,
+    TruncatedAt,
+    Truncated,
+    OpenQuotedField,
+    OpenQuotedFieldOnRow,
+    ShortFinalRow,
+    Transposed,
+    MixedSeparatorsWarning,
+    DoubleEncodedValues,
+    ChangedSinceRun,
+    MetricChanged,
+    NewOutlierLengths,
+    BaselineRegression,
+    OutlierShareRose,
+    LongestRowGrew,
+    FieldCountMatchFell,
+    PercentagePoint,
+    PercentagePoints,
+    SpotCheckFailed,
+    SpotCheckLengthDiffers,
+    SpotCheckNoLongerOutlier,
+    SpotCheckUnreadable,
+    SampleRowsFailed,
+    BadgeFailed,
+    FileUnreadable,
+    DirectorySummaryFailed,
+    GeneratedReports,
+    CharCountsReport,
+    ValueCountsReport,
+    MarkdownReport,
+    TextReport,
+    PagesReport,
+    LengthsSummaryReport,
+    ColumnFormatReport,
+    TableSchemaReport,
+    LoaderHintsReport,
+    ColumnTotalsReport,
+    ValueShapesReport,
+    NullReport,
+    ColumnRulesReport,
+    ColumnSizesReport,
+    RowFlagsColumn,
+    ModelScoresReport,
+    ParquetSidecar,
+    StateFile,
+    EntropyReport,
+    FieldCountsReport,
+    OutlierRowsExtract,
+    SpotCheckReport,
+    LengthBucketsReport,
+    FlagStreamFile,
+    AnalysisJsonReport,
+    HtmlReport,
+    WriterReport,
+    RowLengthAnalysisFor,
+    FileStatistics,
+    GzipMembers,
+    DescriptiveStatistics,
+    OutlierDetectionThreshold,
+    CommonRowLengths,
+    TopPageLengths,
+    SuggestedChunkSize,
+    ExtremeRowLengths,
+    RowsAboveThreshold,
+    RowsBelowThreshold,
+    SampleRows,
+    ColumnFormatting,
+    EmbeddedLineBreaks,
+    MixedDecimalSeparators,
+    EncodingSuspects,
+    ColumnTotals,
+    ValueShapes,
+    Completeness,
+    FieldCounts,
+    ColumnRules,
+    ModelScores,
+    RowEntropy,
+    BitsPerByte,
+    ChangesSinceLastRun,
+    Recommendations,
+    ExtremelyLargeRows,
+    GeneralDataQuality,
+}
+
+impl Message {
+    /// Templates in English, Spanish, and Portuguese.
+    fn templates(self) -> [&'static str; 3] {
+        match self {
+            Message::AnalyzingFile => [
+                "Analyzing CSV file: {} ({})",
+                "Analizando el archivo CSV: {} ({})",
+                "Analisando o arquivo CSV: {} ({})",
+            ],
+            Message::AnalyzingDirectory => [
+                "Analyzing all CSV files in directory: {}",
+                "Analizando todos los archivos CSV del directorio: {}",
+                "Analisando todos os arquivos CSV do diretório: {}",
+            ],
+            Message::AnalyzingGlob => [
+                "Analyzing the files matching: {}",
+                "Analizando los archivos que coinciden con: {}",
+                "Analisando os arquivos que correspondem a: {}",
+            ],
+            Message::ReportsSavedTo => [
+                "Reports will be saved to: {}",
+                "Los informes se guardarán en: {}",
+                "Os relatórios serão salvos em: {}",
+            ],
+            Message::ProcessingFile => [
+                "Processing CSV file: {}{}",
+                "Procesando el archivo CSV: {}{}",
+                "Processando o arquivo CSV: {}{}",
+            ],
+            Message::ProcessedFiles => [
+                "Successfully processed {} CSV files from directory",
+                "Se procesaron correctamente {} archivos CSV del directorio",
+                "{} arquivos CSV do diretório foram processados com sucesso",
+            ],
+            Message::ProcessedMatchingFiles => [
+                "Successfully processed {} matching files",
+                "Se procesaron correctamente {} archivos coincidentes",
+                "{} arquivos correspondentes foram processados com sucesso",
+            ],
+            Message::TruncatedFiles => [
+                "{} of the processed files appear truncated",
+                "{} de los archivos procesados parecen truncados",
+                "{} dos arquivos processados parecem truncados",
+            ],
+            Message::FailedFiles => [
+                "{} files could not be read or analyzed",
+                "{} archivos no se pudieron leer o analizar",
+                "{} arquivos não puderam ser lidos ou analisados",
+            ],
+            Message::RegressedFiles => [
+                "{} of the processed files regressed from the baseline",
+                "{} de los archivos procesados empeoraron respecto a la línea base",
+                "{} dos arquivos processados pioraram em relação à linha de base",
+            ],
+            Message::SkippedFiles => [
+                "Skipped {} files (selection: {}):",
+                "Se omitieron {} archivos (selección: {}):",
+                "{} arquivos foram ignorados (seleção: {}):",
+            ],
+            Message::SkippedFilesHint => [
+                "Use --extensions <list> or --all-files to analyze other delimited files.",
+                "Use --extensions <lista> o --all-files para analizar otros archivos delimitados.",
+                "Use --extensions <lista> ou --all-files para analisar outros arquivos delimitados.",
+            ],
+            Message::ErrorReadingRow => [
+                "Warning: Error reading row {}: {}",
+                "Aviso: Error al leer la fila {}: {}",
+                "Aviso: Erro ao ler a linha {}: {}",
+            ],
+            Message::ErrorLoadingConfig => [
+                "Error loading config file: {}",
+                "Error al cargar el archivo de configuración: {}",
+                "Erro ao carregar o arquivo de configuração: {}",
+            ],
+            Message::ErrorAnalyzingFile => [
+                "Error analyzing CSV file: {}",
+                "Error al analizar el archivo CSV: {}",
+                "Erro ao analisar o arquivo CSV: {}",
+            ],
+            Message::ErrorAnalyzingNamedFile => [
+                "Error analyzing CSV file {}: {}",
+                "Error al analizar el archivo CSV {}: {}",
+                "Erro ao analisar o arquivo CSV {}: {}",
+            ],
+            Message::ErrorProcessingDirectory => [
+                "Error processing directory: {}",
+                "Error al procesar el directorio: {}",
+                "Erro ao processar o diretório: {}",
+            ],
+            Message::ReportStatus => [
+                "Report status:",
+                "Estado de los informes:",
+                "Estado dos relatórios:",
+            ],
+            Message::FileWarning => [
+                "Warning: {}: {}",
+                "Aviso: {}: {}",
+                "Aviso: {}: {}",
+            ],
+            Message::PreviewFailed => [
+                "Warning: Could not preview the largest rows: {}",
+                "Aviso: No se pudieron mostrar las filas más largas: {}",
+                "Aviso: Não foi possível mostrar as linhas mais longas: {}",
+            ],
+            Message::GzipMembersRead => [
+                "Warning: {} has {} concatenated gzip members; all were read",
+                "Aviso: {} tiene {} miembros gzip concatenados; se leyeron todos",
+                "Aviso: {} tem {} membros gzip concatenados; todos foram lidos",
+            ],
+            Message::ReadsRetried => [
+                "Warning: {}: {} reads were retried after transient I/O errors",
+                "Aviso: {}: se reintentaron {} lecturas tras errores de E/S transitorios",
+                "Aviso: {}: {} leituras foram repetidas após erros de E/S transitórios",
+            ],
+            Message::UnreadableRowsLeftOut => [
+                "Warning: {} rows are not valid UTF-8 and were left out of the statistics",
+                "Aviso: {} filas no son UTF-8 válido y se dejaron fuera de las estadísticas",
+                "Aviso: {} linhas não são UTF-8 válido e ficaram fora das estatísticas",
+            ],
+            Message::RowReportFailed => [
+                "Warning: Could not write the char_counts report: {}",
+                "Aviso: No se pudo escribir el informe char_counts: {}",
+                "Aviso: Não foi possível gravar o relatório char_counts: {}",
+            ],
+            Message::FlagStreamFailed => [
+                "Warning: Could not write flag stream {}: {}; no more flags are streamed for {}",
+                "Aviso: No se pudo escribir el flujo de marcas {}: {}; no se emiten más marcas para {}",
+                "Aviso: Não foi possível gravar o fluxo de marcas {}: {}; não são emitidas mais marcas para {}",
+            ],
+            Message::NoRowsForModel => [
+                "Warning: {}: no rows to fit a length model to; {} was not written",
+                "Aviso: {}: no hay filas para ajustar un modelo de longitudes; no se escribió {}",
+                "Aviso: {}: não há linhas para ajustar um modelo de comprimentos; {} não foi gravado",
+            ],
+            Message::HistoryReadFailed => [
+                "Warning: Could not read the previous run from {}: {}",
+                "Aviso: No se pudo leer la ejecución anterior de {}: {}",
+                "Aviso: Não foi possível ler a execução anterior de {}: {}",
+            ],
+            Message::TruncatedAt => [
+                "file appears truncated at row {} / byte {}: {}",
+                "el archivo parece truncado en la fila {} / byte {}: {}",
+                "o arquivo parece truncado na linha {} / byte {}: {}",
+            ],
+            Message::Truncated => [
+                "file appears truncated: {}",
+                "el archivo parece truncado: {}",
+                "o arquivo parece truncado: {}",
+            ],
+            Message::OpenQuotedField => [
+                "the last record ends inside a quoted field",
+                "el último registro termina dentro de un campo entre comillas",
+                "o último registro termina dentro de um campo entre aspas",
+            ],
+            Message::OpenQuotedFieldOnRow => [
+                "the last record ends inside a quoted field opened on row {}",
+                "el último registro termina dentro de un campo entre comillas abierto en la fila {}",
+                "o último registro termina dentro de um campo entre aspas aberto na linha {}",
+            ],
+            Message::ShortFinalRow => [
+                "the last row has {} of the header's {} fields",
+                "la última fila tiene {} de los {} campos del encabezado",
+                "a última linha tem {} dos {} campos do cabeçalho",
+            ],
+            Message::Transposed => [
+                "file appears transposed: {} rows of {} fields each, and the first field of every row reads like a column name{}; each row is likely a column of a {}-column table, so transpose the file before loading it",
+                "el archivo parece transpuesto: {} filas de {} campos cada una, y el primer campo de cada fila parece un nombre de columna{}; cada fila es probablemente una columna de una tabla de {} columnas, así que transponga el archivo antes de cargarlo",
+                "o arquivo parece transposto: {} linhas de {} campos cada uma, e o primeiro campo de cada linha parece um nome de coluna{}; cada linha é provavelmente uma coluna de uma tabela de {} colunas, então transponha o arquivo antes de carregá-lo",
+            ],
+            Message::MixedSeparatorsWarning => [
+                "Warning: {}: column '{}' mixes decimal separators ({})",
+                "Aviso: {}: la columna '{}' mezcla separadores decimales ({})",
+                "Aviso: {}: a coluna '{}' mistura separadores decimais ({})",
+            ],
+            Message::DoubleEncodedValues => [
+                "Warning: {}: column '{}' has {} values that look double-encoded (e.g., {})",
+                "Aviso: {}: la columna '{}' tiene {} valores que parecen doblemente codificados (p. ej., {})",
+                "Aviso: {}: a coluna '{}' tem {} valores que parecem duplamente codificados (p. ex., {})",
+            ],
+            Message::ChangedSinceRun => [
+                "Warning: {}: {} since the run of {}",
+                "Aviso: {}: {} desde la ejecución de {}",
+                "Aviso: {}: {} desde a execução de {}",
+            ],
+            Message::MetricChanged => [
+                "{} changed beyond ±{}%: {}",
+                "{} cambió más de ±{}%: {}",
+                "{} mudou mais de ±{}%: {}",
+            ],
+            Message::NewOutlierLengths => [
+                "{} new outlier lengths: {}",
+                "{} longitudes atípicas nuevas: {}",
+                "{} comprimentos atípicos novos: {}",
+            ],
+            Message::BaselineRegression => [
+                "Warning: {}: {} against the baseline of {}",
+                "Aviso: {}: {} frente a la línea base de {}",
+                "Aviso: {}: {} em relação à linha de base de {}",
+            ],
+            Message::OutlierShareRose => [
+                "outlier rows rose from {}% to {}% (tolerance +{} {})",
+                "las filas atípicas subieron del {}% al {}% (tolerancia +{} {})",
+                "as linhas atípicas subiram de {}% para {}% (tolerância +{} {})",
+            ],
+            Message::LongestRowGrew => [
+                "the longest row grew from {} to {} (tolerance +{}%)",
+                "la fila más larga creció de {} a {} (tolerancia +{}%)",
+                "a linha mais longa cresceu de {} para {} (tolerância +{}%)",
+            ],
+            Message::FieldCountMatchFell => [
+                "records with the header's field count fell from {}% to {}% (tolerance -{} {})",
+                "los registros con el número de campos del encabezado bajaron del {}% al {}% (tolerancia -{} {})",
+                "os registros com o número de campos do cabeçalho caíram de {}% para {}% (tolerância -{} {})",
+            ],
+            Message::PercentagePoint => [
+                "point",
+                "punto",
+                "ponto",
+            ],
+            Message::PercentagePoints => [
+                "points",
+                "puntos",
+                "pontos",
+            ],
+            Message::SpotCheckFailed => [
+                "Warning: {}: spot check failed: {}",
+                "Aviso: {}: falló la verificación por muestreo: {}",
+                "Aviso: {}: a verificação por amostragem falhou: {}",
+            ],
+            Message::SpotCheckLengthDiffers => [
+                "row {} (line {}) measures {} characters at byte {}, but {} were recorded",
+                "la fila {} (línea {}) mide {} caracteres en el byte {}, pero se registraron {}",
+                "a linha {} (linha do arquivo {}) mede {} caracteres no byte {}, mas foram registrados {}",
+            ],
+            Message::SpotCheckNoLongerOutlier => [
+                "row {} (line {}) measures {} characters, no longer above the outlier threshold",
+                "la fila {} (línea {}) mide {} caracteres, ya no supera el umbral de valores atípicos",
+                "a linha {} (linha do arquivo {}) mede {} caracteres e já não passa do limite de valores atípicos",
+            ],
+            Message::SpotCheckUnreadable => [
+                "row {} (line {}) could not be re-read at byte {} (not UTF-8, or past the end of the input)",
+                "la fila {} (línea {}) no se pudo releer en el byte {} (no es UTF-8, o está más allá del final de la entrada)",
+                "a linha {} (linha do arquivo {}) não pôde ser relida no byte {} (não é UTF-8, ou está além do fim da entrada)",
+            ],
+            Message::SampleRowsFailed => [
+                "Warning: Could not read the sample rows: {}",
+                "Aviso: No se pudieron leer las filas de muestra: {}",
+                "Aviso: Não foi possível ler as linhas de amostra: {}",
+            ],
+            Message::BadgeFailed => [
+                "Warning: Could not write the status badge: {}",
+                "Aviso: No se pudo escribir la insignia de estado: {}",
+                "Aviso: Não foi possível gravar o selo de status: {}",
+            ],
+            Message::FileUnreadable => [
+                "Warning: {}: could not be read: {}",
+                "Aviso: {}: no se pudo leer: {}",
+                "Aviso: {}: não pôde ser lido: {}",
+            ],
+            Message::DirectorySummaryFailed => [
+                "Warning: Could not write the directory summary: {}",
+                "Aviso: No se pudo escribir el resumen del directorio: {}",
+                "Aviso: Não foi possível gravar o resumo do diretório: {}",
+            ],
+            Message::GeneratedReports => [
+                "Generated these report files with prefix '{}_':",
+                "Se generaron estos informes con el prefijo '{}_':",
+                "Foram gerados estes relatórios com o prefixo '{}_':",
+            ],
+            Message::CharCountsReport => [
+                "  {} - Contains character count for each row",
+                "  {} - Cantidad de caracteres de cada fila",
+                "  {} - Quantidade de caracteres de cada linha",
+            ],
+            Message::ValueCountsReport => [
+                "  {} - Contains frequency distribution of row lengths (sorted by count)",
+                "  {} - Distribución de frecuencias de las longitudes de fila (ordenada por cantidad)",
+                "  {} - Distribuição de frequência dos comprimentos de linha (ordenada por quantidade)",
+            ],
+            Message::MarkdownReport => [
+                "  {} - Contains descriptive statistics and potential outliers",
+                "  {} - Estadísticas descriptivas y posibles valores atípicos",
+                "  {} - Estatísticas descritivas e possíveis valores atípicos",
+            ],
+            Message::TextReport => [
+                "  {} - Plain text version of outliers report with evenly spaced columns",
+                "  {} - Versión en texto plano del informe de valores atípicos",
+                "  {} - Versão em texto simples do relatório de valores atípicos",
+            ],
+            Message::PagesReport => [
+                "  {} - Contains distribution of rows by page length ({} chars per page)",
+                "  {} - Distribución de las filas por longitud en páginas ({} caracteres por página)",
+                "  {} - Distribuição das linhas por comprimento em páginas ({} caracteres por página)",
+            ],
+            Message::LengthsSummaryReport => [
+                "  {} - One line of summary statistics with the outlier fences",
+                "  {} - Una línea de estadísticas de resumen con los límites de valores atípicos",
+                "  {} - Uma linha de estatísticas de resumo com os limites de valores atípicos",
+            ],
+            Message::ColumnFormatReport => [
+                "  With --profile-columns: {} - Columns that must be loaded as text",
+                "  Con --profile-columns: {} - Columnas que deben cargarse como texto",
+                "  Com --profile-columns: {} - Colunas que devem ser carregadas como texto",
+            ],
+            Message::TableSchemaReport => [
+                "  With --table-schema: {} - Inferred Frictionless Table Schema of the columns",
+                "  Con --table-schema: {} - Frictionless Table Schema inferido de las columnas",
+                "  Com --table-schema: {} - Frictionless Table Schema inferido das colunas",
+            ],
+            Message::LoaderHintsReport => [
+                "  With --loader-hints: {} - COPY settings for PostgreSQL, Redshift, and Snowflake",
+                "  Con --loader-hints: {} - Opciones de COPY para PostgreSQL, Redshift y Snowflake",
+                "  Com --loader-hints: {} - Opções de COPY para PostgreSQL, Redshift e Snowflake",
+            ],
+            Message::ColumnTotalsReport => [
+                "  With --column-totals: {} - Sum, min, max, and mean of each numeric column",
+                "  Con --column-totals: {} - Suma, mínimo, máximo y media de cada columna numérica",
+                "  Com --column-totals: {} - Soma, mínimo, máximo e média de cada coluna numérica",
+            ],
+            Message::ValueShapesReport => [
+                "  With --value-shapes: {} - Conformity of URL, email, UUID, and JSON columns",
+                "  Con --value-shapes: {} - Conformidad de las columnas de URL, email, UUID y JSON",
+                "  Com --value-shapes: {} - Conformidade das colunas de URL, e-mail, UUID e JSON",
+            ],
+            Message::NullReport => [
+                "  With --null-report: {} - Empty, whitespace-only, and null-token cells of each column",
+                "  Con --null-report: {} - Celdas vacías, solo con espacios y con tokens nulos de cada columna",
+                "  Com --null-report: {} - Células vazias, só com espaços e com tokens nulos de cada coluna",
+            ],
+            Message::ColumnRulesReport => [
+                "  With --rule: {} - Rows that broke a cross-column rule",
+                "  Con --rule: {} - Filas que incumplieron una regla entre columnas",
+                "  Com --rule: {} - Linhas que violaram uma regra entre colunas",
+            ],
+            Message::ColumnSizesReport => [
+                "  With --column-sizes: {} - Characters, bytes, and share of the file per column",
+                "  Con --column-sizes: {} - Caracteres, bytes y proporción del archivo por columna",
+                "  Com --column-sizes: {} - Caracteres, bytes e proporção do arquivo por coluna",
+            ],
+            Message::RowFlagsColumn => [
+                "  With --row-flags: {} has a flags column (O=outlier, S=short, B=blank, E=error, Q=quote issue, F=field count)",
+                "  Con --row-flags: {} tiene una columna flags (O=atípica, S=corta, B=vacía, E=error, Q=comillas, F=número de campos)",
+                "  Com --row-flags: {} tem uma coluna flags (O=atípica, S=curta, B=vazia, E=erro, Q=aspas, F=número de campos)",
+            ],
+            Message::ParquetSidecar => [
+                "  With --parquet-sidecar: {} - Length, field count, and flags of every data record",
+                "  Con --parquet-sidecar: {} - Longitud, número de campos y marcas de cada registro",
+                "  Com --parquet-sidecar: {} - Comprimento, número de campos e marcas de cada registro",
+            ],
+            Message::ModelScoresReport => [
+                "  With --score-against: {} - Records with lengths unusual for the model",
+                "  Con --score-against: {} - Registros con longitudes inusuales para el modelo",
+                "  Com --score-against: {} - Registros com comprimentos incomuns para o modelo",
+            ],
+            Message::StateFile => [
+                "  With --save-state: {} - Length histogram, row indices, and column lengths for the query command",
+                "  Con --save-state: {} - Histograma de longitudes, índices de filas y longitudes de columnas para el comando query",
+                "  Com --save-state: {} - Histograma de comprimentos, índices das linhas e comprimentos das colunas para o comando query",
+            ],
+            Message::EntropyReport => [
+                "  With --entropy: {} - Byte entropy of each row, with abnormal rows flagged",
+                "  Con --entropy: {} - Entropía de bytes de cada fila, con las filas anómalas marcadas",
+                "  Com --entropy: {} - Entropia de bytes de cada linha, com as linhas anômalas marcadas",
+            ],
+            Message::FieldCountsReport => [
+                "  With --field-counts: {} - Field count of each row and whether it matches the header",
+                "  Con --field-counts: {} - Número de campos de cada fila y si coincide con el encabezado",
+                "  Com --field-counts: {} - Número de campos de cada linha e se coincide com o cabeçalho",
+            ],
+            Message::OutlierRowsExtract => [
+                "  With --extract-outliers: {} - Content of the outlier rows, prefixed with their line numbers",
+                "  Con --extract-outliers: {} - Contenido de las filas atípicas, precedido de sus números de línea",
+                "  Com --extract-outliers: {} - Conteúdo das linhas atípicas, precedido dos números de linha",
+            ],
+            Message::SpotCheckReport => [
+                "  With --verify-sample: {} - Sampled outlier rows re-read by byte offset and measured again",
+                "  Con --verify-sample: {} - Filas atípicas de muestra releídas por posición de byte y medidas de nuevo",
+                "  Com --verify-sample: {} - Linhas atípicas de amostra relidas pela posição de byte e medidas de novo",
+            ],
+            Message::LengthBucketsReport => [
+                "  With --length-buckets: {}/rows_*.txt - Line numbers of the rows in each length bucket",
+                "  Con --length-buckets: {}/rows_*.txt - Números de línea de las filas de cada rango de longitud",
+                "  Com --length-buckets: {}/rows_*.txt - Números de linha das linhas de cada faixa de comprimento",
+            ],
+            Message::FlagStreamFile => [
+                "  With --flag-stream <path>: Flagged rows appended to <path> as JSON lines while the file was read",
+                "  Con --flag-stream <ruta>: Filas marcadas añadidas a <ruta> como líneas JSON durante la lectura",
+                "  Com --flag-stream <caminho>: Linhas marcadas acrescentadas a <caminho> como linhas JSON durante a leitura",
+            ],
+            Message::AnalysisJsonReport => [
+                "  With --format json: {} - Statistics, distributions, and outliers as one JSON document",
+                "  Con --format json: {} - Estadísticas, distribuciones y filas atípicas en un documento JSON",
+                "  Com --format json: {} - Estatísticas, distribuições e linhas atípicas em um documento JSON",
+            ],
+            Message::WriterReport => [
+                "  With --writer {}: {}",
+                "  Con --writer {}: {}",
+                "  Com --writer {}: {}",
+            ],
+            Message::HtmlReport => [
+                "  With --html-report: {} - Length and page charts and a sortable outlier table",
+                "  Con --html-report: {} - Gráficos de longitudes y páginas y una tabla ordenable de filas atípicas",
+                "  Com --html-report: {} - Gráficos de comprimentos e páginas e uma tabela ordenável de linhas atípicas",
+            ],
+            Message::RowLengthAnalysisFor => [
+                "Row Length Analysis for {}",
+                "Análisis de longitud de filas de {}",
+                "Análise do comprimento das linhas de {}",
+            ],
+            Message::FileStatistics => ["File Statistics", "Estadísticas del archivo", "Estatísticas do arquivo"],
+            Message::GzipMembers => ["Gzip Members", "Miembros gzip", "Membros gzip"],
+            Message::DescriptiveStatistics => [
+                "Descriptive Statistics for Row Lengths",
+                "Estadísticas descriptivas de las longitudes de fila",
+                "Estatísticas descritivas dos comprimentos de linha",
+            ],
+            Message::OutlierDetectionThreshold => [
+                "Outlier Detection Threshold ({})",
+                "Umbral de detección de valores atípicos ({})",
+                "Limite de detecção de valores atípicos ({})",
+            ],
+            Message::CommonRowLengths => [
+                "Common Row Lengths",
+                "Longitudes de fila frecuentes",
+                "Comprimentos de linha frequentes",
+            ],
+            Message::TopPageLengths => [
+                "Top 10 Common Page Lengths",
+                "Las 10 longitudes en páginas más frecuentes",
+                "Os 10 comprimentos em páginas mais frequentes",
+            ],
+            Message::SuggestedChunkSize => [
+                "Suggested Chunk Size",
+                "Tamaño de bloque sugerido",
+                "Tamanho de bloco sugerido",
+            ],
+            Message::ExtremeRowLengths => [
+                "Extreme Row Lengths (Largest Rows)",
+                "Longitudes de fila extremas (filas más largas)",
+                "Comprimentos de linha extremos (linhas mais longas)",
+            ],
+            Message::RowsAboveThreshold => [
+                "Rows Above {} Threshold",
+                "Filas por encima del umbral {}",
+                "Linhas acima do limite {}",
+            ],
+            Message::RowsBelowThreshold => [
+                "Rows Below {} Threshold",
+                "Filas por debajo del umbral {}",
+                "Linhas abaixo do limite {}",
+            ],
+            Message::ColumnFormatting => ["Column Formatting", "Formato de columnas", "Formato das colunas"],
+            Message::EmbeddedLineBreaks => [
+                "Embedded Line Breaks",
+                "Saltos de línea incrustados",
+                "Quebras de linha incorporadas",
+            ],
+            Message::MixedDecimalSeparators => [
+                "Mixed Decimal Separators",
+                "Separadores decimales mezclados",
+                "Separadores decimais misturados",
+            ],
+            Message::EncodingSuspects => [
+                "Mojibake and Smart Quotes",
+                "Mojibake y comillas tipográficas",
+                "Mojibake e aspas tipográficas",
+            ],
+            Message::SampleRows => ["Sample Rows", "Filas de muestra", "Linhas de amostra"],
+            Message::ColumnTotals => ["Column Totals", "Totales de columnas", "Totais das colunas"],
+            Message::ValueShapes => ["Value Shapes", "Formas de los valores", "Formas dos valores"],
+            Message::Completeness => ["Completeness", "Completitud", "Completude"],
+            Message::FieldCounts => ["Field Counts", "Número de campos", "Número de campos"],
+            Message::ColumnRules => ["Column Rules", "Reglas de columnas", "Regras de colunas"],
+            Message::ModelScores => [
+                "Scores Against Model of {}",
+                "Puntuaciones frente al modelo de {}",
+                "Pontuações em relação ao modelo de {}",
+            ],
+            Message::RowEntropy => ["Row Entropy", "Entropía de filas", "Entropia das linhas"],
+            Message::BitsPerByte => ["bits per byte", "bits por byte", "bits por byte"],
+            Message::ChangesSinceLastRun => [
+                "Changes Since Last Run",
+                "Cambios desde la última ejecución",
+                "Mudanças desde a última execução",
+            ],
+            Message::Recommendations => ["Recommendations", "Recomendaciones", "Recomendações"],
+            Message::ExtremelyLargeRows => [
+                "Extremely Large Rows",
+                "Filas extremadamente grandes",
+                "Linhas extremamente grandes",
+            ],
+            Message::GeneralDataQuality => [
+                "General Data Quality",
+                "Calidad general de los datos",
+                "Qualidade geral dos dados",
+            ],
+        }
+    }
+
+    fn template(self, language: Language) -> &'static str {
+        let [english, spanish, portuguese] = self.templates();
+        match language {
+            Language::English => english,
+            Language::Spanish => spanish,
+            Language::Portuguese => portuguese,
+        }
+    }
+
+    /// Fills the message's template in `language` with `args`, in order.
+    pub fn text(self, language: Language, args: &[&dyn Display]) -> String {
+        fill(self.template(language), args)
+    }
+
+    /// The message in `language` as a plain-text report heading: the template in upper
+    /// case, filled with `args` as given.
+    pub fn heading(self, language: Language, args: &[&dyn Display]) -> String {
+        fill(&self.template(language).to_uppercase(), args)
+    }
+}
+
+/// Replaces each `{}` of `template` with the next argument.
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut text = parts.next().unwrap_or("").to_string();
+    let mut args = args.iter();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
@@ -0,0 +1,88 @@
+//! # Mojibake and Smart Quotes
+//!
+//! Text that was encoded twice upstream reads as valid UTF-8 but is garbled: UTF-8 bytes
+//! decoded as Windows-1252 (or Latin-1) and encoded again turn `’` into `â€™` and `é`
+//! into `Ã©`. The analyzer cannot see the damage as an encoding error, so column
+//! profiling checks each text value for two signs of it:
+//!
+//! * Mojibake - a run of characters whose Windows-1252 bytes form a valid multi-byte
+//!   UTF-8 sequence (`â€™`, `Ã©`, `Â `), which is how double-encoded text looks
+//! * Smart quotes - typographic quotes (`‘ ’ “ ”`) pasted from word processors; they
+//!   survive a correct UTF-8 pipeline but are the first casualties of a Windows-1252
+//!   one, and often sit next to values that already broke
+//!
+//! A value can show both signs. Values that are pure ASCII are skipped without a scan.
+
+/// Typographic quotes: left and right single, left and right double
+const SMART_QUOTES: [char; 4] = ['\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}'];
+
+/// Windows-1252 characters of the bytes 0x80 to 0x9F; the five undefined bytes decode to
+/// their C1 control characters, as most decoders do
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// A sign that a text value went through a wrong encoding upstream
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncodingSuspect {
+    /// UTF-8 bytes decoded as Windows-1252 or Latin-1 and encoded again
+    Mojibake,
+    /// Typographic quotes
+    SmartQuotes,
+}
+
+impl EncodingSuspect {
+    /// Human-readable description used in the markdown and text reports.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            EncodingSuspect::Mojibake => "mojibake (double-encoded UTF-8)",
+            EncodingSuspect::SmartQuotes => "smart quotes",
+        }
+    }
+}
+
+/// Returns the signs of a wrong encoding found in a value, mojibake first.
+pub fn encoding_suspects(value: &str) -> Vec<EncodingSuspect> {
+    let mut suspects = Vec::new();
+    if value.is_ascii() {
+        return suspects;
+    }
+    if has_mojibake(value) {
+        suspects.push(EncodingSuspect::Mojibake);
+    }
+    if value.contains(SMART_QUOTES) {
+        suspects.push(EncodingSuspect::SmartQuotes);
+    }
+    suspects
+}
+
+/// Whether the value holds a run of characters that re-encodes as one UTF-8 sequence.
+fn has_mojibake(value: &str) -> bool {
+    let bytes: Vec<Option<u8>> = value.chars().map(windows_1252_byte).collect();
+    bytes.iter().enumerate().any(|(index, lead)| {
+        let length = match lead {
+            Some(0xC2..=0xDF) => 2,
+            Some(0xE0..=0xEF) => 3,
+            Some(0xF0..=0xF4) => 4,
+            _ => return false,
+        };
+        let Some(sequence) = bytes.get(index..index + length) else {
+            return false;
+        };
+        let sequence: Option<Vec<u8>> = sequence.iter().copied().collect();
+        sequence.is_some_and(|sequence| {
+            sequence[1..].iter().all(|byte| (0x80..=0xBF).contains(byte)) && std::str::from_utf8(&sequence).is_ok()
+        })
+    })
+}
+
+/// The byte a character has in Windows-1252 (with Latin-1's C1 controls), if any.
+fn windows_1252_byte(c: char) -> Option<u8> {
+    match c as u32 {
+        code @ (0x00..=0x7F | 0xA0..=0xFF) => Some(code as u8),
+        _ => WINDOWS_1252_HIGH.iter().position(|&high| high == c).map(|index| 0x80 + index as u8),
+    }
+}
@@ -0,0 +1,454 @@
+//! # Column Name Patterns
+//!
+//! A small regular-expression matcher for column names (`--exclude-columns-regex`). A
+//! pattern matches a name when it matches anywhere in it, as with `grep -E`; anchor it
+//! with `^` and `$` to match whole names.
+//!
+//! Supported syntax:
+//!
+//! * Literals, `.` (any character), and `\` escapes of special characters (`\.`, `\|`)
+//! * Anchors `^` and `$`
+//! * Alternation `a|b` and groups `(...)` (also written `(?:...)`)
+//! * Quantifiers `*`, `+`, `?`, `{n}`, `{n,}`, `{n,m}` (greedy)
+//! * Classes `[abc]`, `[a-z]`, `[^0-9]`, and the shorthands `\d`, `\w`, `\s` (and their
+//!   negations `\D`, `\W`, `\S`)
+//!
+//! Matching is case-sensitive and works on characters, not bytes. Backreferences,
+//! lookaround, and flags are not supported and are rejected when parsed.
+//!
+//! A parsed pattern is compiled to an automaton that follows every way of matching at
+//! once, instead of trying them one after another. A name is matched in time proportional
+//! to its length times the size of the pattern, whatever the pattern: nested quantifiers
+//! such as `(a*)*b` cannot backtrack exponentially, and a long name does not deepen the
+//! stack. Counted repetitions are expanded when compiling, so a pattern is refused when it
+//! expands to more than [`MAX_INSTRUCTIONS`] instructions or nests more than
+//! [`MAX_GROUP_DEPTH`] groups. Repeats of an empty group, such as `(){1000000}`, compile to
+//! nothing, whatever their count.
+
+/// Most instructions a pattern may compile to, counting every copy of a repeated atom
+pub const MAX_INSTRUCTIONS: usize = 10_000;
+/// Most groups a pattern may nest inside one another
+pub const MAX_GROUP_DEPTH: usize = 100;
+
+/// One element of a pattern
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Literal(char),
+    AnyChar,
+    Class(CharClass),
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    Repeat { node: Box<Node>, min: usize, max: Option<usize> },
+}
+
+impl Node {
+    /// Whether the node compiles to no instructions at all, as `()` and `(){3}` do.
+    fn compiles_to_nothing(&self) -> bool {
+        match self {
+            Node::Literal(_) | Node::AnyChar | Node::Class(_) | Node::Start | Node::End => false,
+            // Every alternative after the first adds a split
+            Node::Group(alternatives) => alternatives.len() == 1 && alternatives[0].iter().all(Node::compiles_to_nothing),
+            Node::Repeat { node, max, .. } => *max == Some(0) || node.compiles_to_nothing(),
+        }
+    }
+}
+
+/// A bracketed class or shorthand such as `\d`
+#[derive(Debug, Clone, PartialEq)]
+struct CharClass {
+    /// Inclusive character ranges; a single character is a range of one
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        self.ranges.iter().any(|&(low, high)| low <= c && c <= high) != self.negated
+    }
+
+    /// The class of a shorthand escape (`d`, `w`, `s`, or their upper-case negations).
+    fn shorthand(letter: char) -> Option<CharClass> {
+        let ranges = match letter.to_ascii_lowercase() {
+            'd' => vec![('0', '9')],
+            'w' => vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')],
+            's' => vec![(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r'), ('\x0b', '\x0c')],
+            _ => return None,
+        };
+        Some(CharClass { ranges, negated: letter.is_ascii_uppercase() })
+    }
+}
+
+/// One step of a compiled pattern
+#[derive(Debug, Clone, PartialEq)]
+enum Instruction {
+    /// Consumes this character
+    Char(char),
+    /// Consumes any character
+    AnyChar,
+    /// Consumes a character of the class
+    Class(CharClass),
+    /// Continues only at the start of the name
+    Start,
+    /// Continues only at the end of the name
+    End,
+    /// Continues at both instructions
+    Split(usize, usize),
+    Jump(usize),
+    /// The pattern matched
+    Match,
+}
+
+/// What a backslash escape stands for
+enum Escape {
+    Char(char),
+    Class(CharClass),
+}
+
+/// A parsed column name pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamePattern {
+    source: String,
+    program: Vec<Instruction>,
+}
+
+impl NamePattern {
+    /// Parses a pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<NamePattern, String>` - The pattern, or a description of the syntax error
+    pub fn parse(source: &str) -> Result<NamePattern, String> {
+        let mut parser = Parser { chars: source.chars().collect(), position: 0, depth: 0 };
+        let alternatives = parser.alternatives()
+            .map_err(|e| format!("invalid pattern '{}': {}", source, e))?;
+        if parser.position < parser.chars.len() {
+            return Err(format!("invalid pattern '{}': unmatched ')'", source));
+        }
+        let mut compiler = Compiler { program: Vec::new() };
+        compiler.alternatives(&alternatives)
+            .and_then(|()| compiler.emit(Instruction::Match))
+            .map_err(|e| format!("invalid pattern '{}': {}", source, e))?;
+        Ok(NamePattern { source: source.to_string(), program: compiler.program })
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the pattern matches anywhere in `name`.
+    pub fn is_match(&self, name: &str) -> bool {
+        let chars: Vec<char> = name.chars().collect();
+        // Instructions waiting for the character at the current position, and for the next one
+        let (mut current, mut next) = (Vec::new(), Vec::new());
+        // The position each instruction was last reached at, so it is followed once per position
+        let mut reached = vec![usize::MAX; self.program.len()];
+        for position in 0..=chars.len() {
+            // A match may start at any position
+            if self.follow(0, position, chars.len(), &mut reached, &mut current) {
+                return true;
+            }
+            let Some(&c) = chars.get(position) else { break };
+            for &pc in &current {
+                let consumed = match &self.program[pc] {
+                    Instruction::Char(expected) => c == *expected,
+                    Instruction::AnyChar => true,
+                    Instruction::Class(class) => class.matches(c),
+                    _ => false,
+                };
+                if consumed && self.follow(pc + 1, position + 1, chars.len(), &mut reached, &mut next) {
+                    return true;
+                }
+            }
+            current.clear();
+            std::mem::swap(&mut current, &mut next);
+        }
+        false
+    }
+
+    /// Follows the instructions from `start` that consume nothing, adding the ones that
+    /// consume a character to `waiting`.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the end of the pattern was reached
+    fn follow(&self, start: usize, position: usize, length: usize, reached: &mut [usize], waiting: &mut Vec<usize>) -> bool {
+        let mut pending = vec![start];
+        while let Some(pc) = pending.pop() {
+            if reached[pc] == position {
+                continue;
+            }
+            reached[pc] = position;
+            match &self.program[pc] {
+                Instruction::Match => return true,
+                Instruction::Jump(target) => pending.push(*target),
+                Instruction::Split(first, second) => pending.extend([*second, *first]),
+                Instruction::Start if position == 0 => pending.push(pc + 1),
+                Instruction::End if position == length => pending.push(pc + 1),
+                Instruction::Start | Instruction::End => {},
+                Instruction::Char(_) | Instruction::AnyChar | Instruction::Class(_) => waiting.push(pc),
+            }
+        }
+        false
+    }
+}
+
+/// Recursive-descent parser over the pattern's characters
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    /// Groups open at the current position
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += usize::from(c.is_some());
+        c
+    }
+
+    /// Parses `seq|seq|...` up to a closing parenthesis or the end.
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.position += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some('(') => {
+                if self.chars[self.position..].starts_with(&['?', ':']) {
+                    self.position += 2;
+                } else if self.peek() == Some('?') {
+                    return Err("lookaround and flags are not supported".to_string());
+                }
+                if self.depth == MAX_GROUP_DEPTH {
+                    return Err(format!("groups are nested more than {} deep", MAX_GROUP_DEPTH));
+                }
+                self.depth += 1;
+                let alternatives = self.alternatives()?;
+                self.depth -= 1;
+                if self.next() != Some(')') {
+                    return Err("unclosed '('".to_string());
+                }
+                Ok(Node::Group(alternatives))
+            },
+            Some('[') => self.class(),
+            Some('.') => Ok(Node::AnyChar),
+            Some('^') => Ok(Node::Start),
+            Some('$') => Ok(Node::End),
+            Some('\\') => self.escape().map(|escape| match escape {
+                Escape::Char(c) => Node::Literal(c),
+                Escape::Class(class) => Node::Class(class),
+            }),
+            Some(c @ ('*' | '+' | '?' | '{')) => Err(format!("'{}' does not follow anything to repeat", c)),
+            Some(c) => Ok(Node::Literal(c)),
+            None => Err("unexpected end".to_string()),
+        }
+    }
+
+    /// Parses the character after a backslash: a literal, or the class of a shorthand.
+    fn escape(&mut self) -> Result<Escape, String> {
+        match self.next() {
+            Some(c) if c.is_ascii_digit() => Err("backreferences are not supported".to_string()),
+            Some(c) => Ok(match CharClass::shorthand(c) {
+                Some(class) => Escape::Class(class),
+                None => Escape::Char(match c {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    c => c,
+                }),
+            }),
+            None => Err("pattern ends with '\\'".to_string()),
+        }
+    }
+
+    fn class(&mut self) -> Result<Node, String> {
+        let negated = self.peek() == Some('^');
+        self.position += usize::from(negated);
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let low = match self.next() {
+                None => return Err("unclosed '['".to_string()),
+                Some(']') if !first => break,
+                Some('\\') => match self.escape()? {
+                    Escape::Char(c) => c,
+                    Escape::Class(class) => {
+                        if class.negated {
+                            return Err("negated shorthands are not supported inside '[...]'".to_string());
+                        }
+                        ranges.extend(class.ranges);
+                        first = false;
+                        continue;
+                    },
+                },
+                Some(c) => c,
+            };
+            first = false;
+            // A '-' between two characters makes a range; elsewhere it is a literal
+            if self.peek() == Some('-') && self.chars.get(self.position + 1).is_some_and(|&c| c != ']') {
+                self.position += 1;
+                let high = match self.next() {
+                    Some('\\') => match self.escape()? {
+                        Escape::Char(c) => c,
+                        Escape::Class(_) => return Err("a class shorthand cannot end a range".to_string()),
+                    },
+                    Some(c) => c,
+                    None => return Err("unclosed '['".to_string()),
+                };
+                if high < low {
+                    return Err(format!("range '{}-{}' is out of order", low, high));
+                }
+                ranges.push((low, high));
+            } else {
+                ranges.push((low, low));
+            }
+        }
+        Ok(Node::Class(CharClass { ranges, negated }))
+    }
+
+    /// Applies the quantifiers that follow an atom.
+    fn quantified(&mut self, mut node: Node) -> Result<Node, String> {
+        loop {
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') => {
+                    self.position += 1;
+                    let bounds = self.bounds()?;
+                    node = Node::Repeat { node: Box::new(node), min: bounds.0, max: bounds.1 };
+                    continue;
+                },
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            node = Node::Repeat { node: Box::new(node), min, max };
+        }
+    }
+
+    /// Parses `n}`, `n,}`, or `n,m}` after an opening brace.
+    fn bounds(&mut self) -> Result<(usize, Option<usize>), String> {
+        let end = self.chars[self.position..].iter().position(|&c| c == '}')
+            .ok_or("unclosed '{'")?;
+        let text: String = self.chars[self.position..self.position + end].iter().collect();
+        self.position += end + 1;
+        let number = |part: &str| part.trim().parse::<usize>().map_err(|_| format!("invalid repetition '{{{}}}'", text));
+        let (min, max) = match text.split_once(',') {
+            None => (number(&text)?, Some(number(&text)?)),
+            Some((min, "")) => (number(min)?, None),
+            Some((min, max)) => (number(min)?, Some(number(max)?)),
+        };
+        if max.is_some_and(|max| max < min) {
+            return Err(format!("invalid repetition '{{{}}}'", text));
+        }
+        Ok((min, max))
+    }
+}
+
+/// Translates parsed nodes into instructions
+struct Compiler {
+    program: Vec<Instruction>,
+}
+
+impl Compiler {
+    fn emit(&mut self, instruction: Instruction) -> Result<usize, String> {
+        if self.program.len() == MAX_INSTRUCTIONS {
+            return Err(format!("expands to more than {} instructions; use smaller repetition counts", MAX_INSTRUCTIONS));
+        }
+        self.program.push(instruction);
+        Ok(self.program.len() - 1)
+    }
+
+    /// Points the placeholder split at `index` to the next instruction and to `exit`.
+    fn patch_split(&mut self, index: usize, exit: usize) {
+        self.program[index] = Instruction::Split(index + 1, exit);
+    }
+
+    fn alternatives(&mut self, alternatives: &[Vec<Node>]) -> Result<(), String> {
+        let (last, others) = alternatives.split_last().expect("a pattern has at least one alternative");
+        let mut jumps_to_end = Vec::new();
+        for sequence in others {
+            let split = self.emit(Instruction::Split(0, 0))?;
+            self.sequence(sequence)?;
+            jumps_to_end.push(self.emit(Instruction::Jump(0))?);
+            let next_alternative = self.program.len();
+            self.patch_split(split, next_alternative);
+        }
+        self.sequence(last)?;
+        let end = self.program.len();
+        for jump in jumps_to_end {
+            self.program[jump] = Instruction::Jump(end);
+        }
+        Ok(())
+    }
+
+    fn sequence(&mut self, sequence: &[Node]) -> Result<(), String> {
+        sequence.iter().try_for_each(|node| self.node(node))
+    }
+
+    fn node(&mut self, node: &Node) -> Result<(), String> {
+        match node {
+            Node::Literal(c) => self.emit(Instruction::Char(*c)).map(drop),
+            Node::AnyChar => self.emit(Instruction::AnyChar).map(drop),
+            Node::Class(class) => self.emit(Instruction::Class(class.clone())).map(drop),
+            Node::Start => self.emit(Instruction::Start).map(drop),
+            Node::End => self.emit(Instruction::End).map(drop),
+            Node::Group(alternatives) => self.alternatives(alternatives),
+            // Copies of a node that compiles to nothing (such as `()`) would never reach the
+            // instruction limit, however many there are
+            Node::Repeat { node, .. } if node.compiles_to_nothing() => Ok(()),
+            Node::Repeat { node, min, max } => {
+                for _ in 0..*min {
+                    self.node(node)?;
+                }
+                match max {
+                    // A loop back to a split; an empty iteration is followed once per position
+                    None => {
+                        let split = self.emit(Instruction::Split(0, 0))?;
+                        self.node(node)?;
+                        self.emit(Instruction::Jump(split))?;
+                        let exit = self.program.len();
+                        self.patch_split(split, exit);
+                    },
+                    // Up to max - min optional copies, each able to skip to the end
+                    Some(max) => {
+                        let mut splits = Vec::new();
+                        for _ in *min..*max {
+                            splits.push(self.emit(Instruction::Split(0, 0))?);
+                            self.node(node)?;
+                        }
+                        let exit = self.program.len();
+                        for split in splits {
+                            self.patch_split(split, exit);
+                        }
+                    },
+                }
+                Ok(())
+            },
+        }
+    }
+}
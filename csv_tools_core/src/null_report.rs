@@ -0,0 +1,112 @@
+//! # Null Report (`--null-report`)
+//!
+//! Counts the cells of every column that hold no value, so a column that is 40% blank is
+//! known before a `NOT NULL` constraint rejects the load:
+//!
+//! ```bash
+//! $ csv_row_analyzer customers.csv reports --null-report
+//! ```
+//!
+//! A cell is missing when it is:
+//!
+//! * empty - nothing between the delimiters
+//! * whitespace-only - spaces or tabs and nothing else
+//! * a null token - one of [`COMMON_NULL_TOKENS`] or a configured null token of the
+//!   column (see the `null_tokens` module), after trimming
+//!
+//! The tokens are matched case-sensitively, so `NA` is a token while `Na` (sodium) is a
+//! value. Only the cells present in a record are counted: a record with fewer fields than
+//! the header is a field-count problem (see the `field_counts` module), not a blank cell.
+
+use crate::column_profile::ColumnProfiler;
+
+/// Null tokens counted in every column, in addition to the configured ones
+pub const COMMON_NULL_TOKENS: [&str; 3] = ["NULL", "NA", "-"];
+
+/// Missing cells of one column, by kind
+#[derive(Debug, Clone, Default)]
+pub struct NullTally {
+    /// Cells seen
+    values: u64,
+    /// Cells with nothing between the delimiters
+    empty: u64,
+    /// Cells of whitespace only
+    whitespace_only: u64,
+    /// Cells equal to each null token, in order of first use
+    null_tokens: Vec<(String, u64)>,
+}
+
+impl NullTally {
+    /// Counts one cell as written, before null tokens are cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The unquoted cell
+    /// * `column_tokens` - Configured null tokens of the column
+    pub fn observe(&mut self, value: &str, column_tokens: &[String]) {
+        self.values += 1;
+        let trimmed = value.trim();
+        if value.is_empty() {
+            self.empty += 1;
+        } else if trimmed.is_empty() {
+            self.whitespace_only += 1;
+        } else if COMMON_NULL_TOKENS.contains(&trimmed) || column_tokens.iter().any(|token| token == trimmed) {
+            match self.null_tokens.iter_mut().find(|(token, _)| token == trimmed) {
+                Some((_, count)) => *count += 1,
+                None => self.null_tokens.push((trimmed.to_string(), 1)),
+            }
+        }
+    }
+}
+
+/// Missing cells of a column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnCompleteness {
+    /// 0-based column index
+    pub index: usize,
+    pub name: String,
+    /// Cells seen
+    pub values: u64,
+    pub empty: u64,
+    pub whitespace_only: u64,
+    /// Cells equal to each null token, in order of first use
+    pub null_tokens: Vec<(String, u64)>,
+}
+
+impl ColumnCompleteness {
+    /// Cells equal to any null token.
+    pub fn null_token_cells(&self) -> u64 {
+        self.null_tokens.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Cells holding no value, of every kind.
+    pub fn missing(&self) -> u64 {
+        self.empty + self.whitespace_only + self.null_token_cells()
+    }
+
+    /// Share of cells holding a value, in percent (100 for a column without cells).
+    pub fn completeness_percent(&self) -> f64 {
+        if self.values == 0 {
+            100.0
+        } else {
+            (self.values - self.missing()) as f64 / self.values as f64 * 100.0
+        }
+    }
+}
+
+/// Collects the missing cells of every profiled column, in column order.
+pub fn collect_column_completeness(profiler: &ColumnProfiler) -> Vec<ColumnCompleteness> {
+    profiler.columns().into_iter()
+        .map(|(index, name, profile)| {
+            let tally = &profile.nulls;
+            ColumnCompleteness {
+                index,
+                name,
+                values: tally.values,
+                empty: tally.empty,
+                whitespace_only: tally.whitespace_only,
+                null_tokens: tally.null_tokens.clone(),
+            }
+        })
+        .collect()
+}
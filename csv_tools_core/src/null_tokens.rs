@@ -0,0 +1,107 @@
+//! # Null Tokens (`--null-tokens`)
+//!
+//! Exports rarely leave missing values truly empty: database dumps write `NULL` or `\N`,
+//! R and pandas write `NA`, and hand-kept sheets use `-`. Values equal to a configured
+//! null token are profiled as empty, so the empty-value counts of the column profile and
+//! column totals, the `required` constraints of the table schema, and the column rules
+//! treat them as missing values and not as text.
+//!
+//! Tokens apply to every column on the command line, or per column in the config file:
+//!
+//! ```text
+//! $ csv_row_analyzer export.csv reports --profile-columns --null-tokens 'NA,NULL,\N'
+//!
+//! [null_tokens]
+//! tokens = NA, NULL         # every column
+//! column.status = -, n/a    # only the column named status
+//! ```
+//!
+//! Tokens are comma-separated and compared with the trimmed value, case-sensitively. An
+//! empty value always counts as null. Column names in the config are matched ignoring
+//! case, since config keys are case-insensitive; a column's own tokens are added to the
+//! ones for every column.
+
+use crate::config::AnalyzerConfig;
+
+/// Config key prefix for the tokens of a single column
+const COLUMN_KEY_PREFIX: &str = "column.";
+
+/// Values treated as missing, for every column and per column
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NullTokens {
+    /// Tokens for every column
+    pub all_columns: Vec<String>,
+    /// Tokens for single columns: (lowercased column name, tokens)
+    pub per_column: Vec<(String, Vec<String>)>,
+}
+
+impl NullTokens {
+    /// Whether no token is configured, so only empty values are null.
+    pub fn is_empty(&self) -> bool {
+        self.all_columns.is_empty() && self.per_column.is_empty()
+    }
+
+    /// Tokens that apply to a column: the ones for every column plus its own.
+    pub fn for_column(&self, column_name: &str) -> Vec<String> {
+        let name = column_name.trim().to_lowercase();
+        let mut tokens = self.all_columns.clone();
+        for (_, column_tokens) in self.per_column.iter().filter(|(column, _)| *column == name) {
+            for token in column_tokens {
+                if !tokens.contains(token) {
+                    tokens.push(token.clone());
+                }
+            }
+        }
+        tokens
+    }
+}
+
+/// Parses a comma-separated token list such as `NA, NULL, \N`.
+///
+/// Blank entries and `""` are dropped, since empty values are always null.
+pub fn parse_null_tokens(text: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    for token in text.split(',').map(str::trim) {
+        if !token.is_empty() && token != "\"\"" && !tokens.iter().any(|t| t == token) {
+            tokens.push(token.to_string());
+        }
+    }
+    tokens
+}
+
+/// Combines the command-line tokens with the `[null_tokens]` section of the config file.
+///
+/// # Arguments
+///
+/// * `command_line_tokens` - Tokens from `--null-tokens`, for every column
+/// * `config` - Parsed config file, if any
+///
+/// # Returns
+///
+/// * `Result<NullTokens, String>` - The tokens, or an error naming the bad line
+pub fn load_null_tokens(
+    command_line_tokens: &[String],
+    config: Option<&AnalyzerConfig>,
+) -> Result<NullTokens, String> {
+    let mut null_tokens = NullTokens { all_columns: command_line_tokens.to_vec(), per_column: Vec::new() };
+    let Some(config) = config else {
+        return Ok(null_tokens);
+    };
+    for entry in config.section_entries("null_tokens") {
+        let tokens = parse_null_tokens(&entry.value);
+        if entry.key == "tokens" {
+            for token in tokens {
+                if !null_tokens.all_columns.contains(&token) {
+                    null_tokens.all_columns.push(token);
+                }
+            }
+        } else if let Some(column) = entry.key.strip_prefix(COLUMN_KEY_PREFIX)
+            && !column.trim().is_empty()
+        {
+            null_tokens.per_column.push((column.trim().to_string(), tokens));
+        } else {
+            return Err(format!("line {}: unknown [null_tokens] key '{}'", entry.line_number, entry.key));
+        }
+    }
+    Ok(null_tokens)
+}
@@ -0,0 +1,88 @@
+//! # Outlier Row Extraction (`--extract-outliers`)
+//!
+//! The outlier reports name the rows above the upper threshold and below the lower one;
+//! looking at them still means opening the original file, which may be tens of gigabytes.
+//! With `--extract-outliers`, the input is read a second time and the content of every
+//! outlier row, long or short, is copied into a small CSV of its own:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --extract-outliers
+//! $ head -3 reports/orders_outlier_rows_1767225600.csv
+//! file_row,id,name,notes
+//! 4182,4181,Ana,"Lorem ipsum dolor sit amet, ..."
+//! 90211,90210,Bo,"Lorem ipsum dolor sit amet, ..."
+//! ```
+//!
+//! The first line is the input's header with a `file_row` column in front; every other
+//! line is one outlier row as it appears in the input, prefixed with the 1-based line
+//! number it starts on. Rows are in file order. With `--csv-mode` a row is a whole
+//! record, line breaks inside quoted fields included. The file keeps the input's columns,
+//! so it has no `report_schema_version` column.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::input_range::open_line_reader;
+use crate::logical_records::RecordAssembler;
+use crate::read_buffer::ReadBufferSize;
+use crate::read_retry::ReadRetries;
+use crate::report_buffer::create_report;
+
+/// Copies the given rows of the input into a CSV prefixed with their line numbers.
+///
+/// # Arguments
+///
+/// * `input_file_path` - The analyzed file (gzip input is decompressed)
+/// * `row_indices` - Row indices to copy, in ascending order
+/// * `csv_mode` - Whether rows are records joined across quoted line breaks
+/// * `report_path` - Path of the CSV to write
+/// * `buffer_size` - Size of the write buffer (`--report-buffer-size`)
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Ok(()) on success, or an Error if the input cannot be
+///   read or the CSV cannot be written
+pub fn write_outlier_rows(
+    input_file_path: impl AsRef<Path>,
+    row_indices: &[usize],
+    csv_mode: bool,
+    report_path: impl AsRef<Path>,
+    buffer_size: usize,
+) -> Result<(), io::Error> {
+    let (lines, _) = open_line_reader(&input_file_path, None, false, ReadBufferSize::Adaptive, &ReadRetries::default())?;
+    let mut assembler = csv_mode.then(|| RecordAssembler::new(','));
+    let mut report_file = create_report(report_path, buffer_size)?;
+    let mut wanted = row_indices.iter().copied().filter(|&row_index| row_index > 0).peekable();
+
+    let mut copy_row = |row_index: usize, file_row: usize, content: io::Result<String>| -> io::Result<()> {
+        if row_index == 0 {
+            // The header row heads the extract whether or not it is an outlier itself
+            return writeln!(report_file, "file_row,{}", content.unwrap_or_default());
+        }
+        if wanted.next_if_eq(&row_index).is_some() {
+            writeln!(report_file, "{},{}", file_row, content?)?;
+        }
+        Ok(())
+    };
+
+    // The rest of the file is skipped once the last wanted row is copied
+    let last_row = row_indices.last().copied().unwrap_or(0);
+    for physical_line in lines {
+        let row = match assembler.as_mut() {
+            Some(assembler) => assembler.push(physical_line, 0)
+                .map(|record| (record.line.row_index, record.first_line + 1, record.line.content)),
+            None => Some((physical_line.row_index, physical_line.row_index + 1, physical_line.content)),
+        };
+        if let Some((row_index, file_row, content)) = row {
+            copy_row(row_index, file_row, content)?;
+            if row_index >= last_row {
+                break;
+            }
+        }
+    }
+    if let Some(record) = assembler.as_mut().and_then(|assembler| assembler.finish()) {
+        copy_row(record.line.row_index, record.first_line + 1, record.line.content)?;
+    }
+
+    report_file.flush()
+}
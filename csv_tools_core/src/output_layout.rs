@@ -0,0 +1,89 @@
+//! # Output Layout (`--layout`)
+//!
+//! By default every report goes straight into the output directory. A daily feed of
+//! many files fills that directory with thousands of reports, so `--layout dated` files
+//! each run's reports under the run's date and the input's basename instead:
+//!
+//! ```text
+//! $ csv_row_analyzer --directory feeds reports --layout dated
+//!
+//! reports/
+//! └── 2026-10-16/
+//!     ├── orders/
+//!     │   ├── orders_char_counts_report_1792108800.csv
+//!     │   └── ...
+//!     └── customers/
+//!         └── ...
+//! ```
+//!
+//! The date is the UTC date of the run's timestamp, so it agrees with the timestamp in
+//! the report names. The layout can also be set as `layout` in the `[output]` section of
+//! the config file; the command line takes precedence. Only the output directory is laid
+//! out: `--fallback-dir`, `--history`, `--badge-dir`, and `--export-model` keep the paths
+//! they are given.
+
+use std::path::{Path, PathBuf};
+
+use crate::clock::utc_date;
+use crate::config::AnalyzerConfig;
+
+/// Where the reports of one run go, relative to the output directory
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutputLayout {
+    /// Directly in the output directory
+    #[default]
+    Flat,
+    /// In `<YYYY-MM-DD>/<basename>/` below the output directory
+    Dated,
+}
+
+impl OutputLayout {
+    /// Parses a layout name (`flat` or `dated`).
+    pub fn parse(name: &str) -> Result<OutputLayout, String> {
+        match name {
+            "flat" => Ok(OutputLayout::Flat),
+            "dated" => Ok(OutputLayout::Dated),
+            _ => Err(format!("unknown layout '{}' (use flat or dated)", name)),
+        }
+    }
+
+    /// Returns the directory that receives the reports of one run.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_dir` - The output directory of the run
+    /// * `basename` - Basename of the analyzed input
+    /// * `timestamp` - Unix timestamp of the run, as used in the report names
+    pub fn report_dir(&self, output_dir: impl AsRef<Path>, basename: &str, timestamp: &str) -> PathBuf {
+        match self {
+            OutputLayout::Flat => output_dir.as_ref().to_path_buf(),
+            OutputLayout::Dated => output_dir.as_ref()
+                .join(utc_date(timestamp.parse().unwrap_or(0)))
+                .join(basename),
+        }
+    }
+}
+
+/// Reads the layout from the `[output]` section of the config file.
+///
+/// # Arguments
+///
+/// * `config` - The loaded config file, if any
+///
+/// # Returns
+///
+/// * `Result<Option<OutputLayout>, String>` - The configured layout, or an error naming the bad line
+pub fn load_output_layout(config: Option<&AnalyzerConfig>) -> Result<Option<OutputLayout>, String> {
+    let Some(config) = config else {
+        return Ok(None);
+    };
+    let mut layout = None;
+    for entry in config.section_entries("output") {
+        match entry.key.as_str() {
+            "layout" => layout = Some(OutputLayout::parse(&entry.value)
+                .map_err(|e| format!("line {}: {}", entry.line_number, e))?),
+            key => return Err(format!("line {}: unknown [output] key '{}'", entry.line_number, key)),
+        }
+    }
+    Ok(layout)
+}
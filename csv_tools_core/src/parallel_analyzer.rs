@@ -0,0 +1,224 @@
+//! # Parallel Analysis (`--parallel`)
+//!
+//! Decodes and counts the rows on worker threads, for files whose rows are long enough
+//! that UTF-8 decoding and character counting dominate the run:
+//!
+//! ```bash
+//! $ csv_row_analyzer large_file.csv reports --parallel --threads 8
+//! ```
+//!
+//! The main thread reads the file in batches of about 1 MiB and queues them for
+//! `--threads` workers (one per core by default). Each worker decodes its batch, counts
+//! the characters of every row, and tallies the batch's own length frequencies and row
+//! indices per length; the main thread takes the counted batches back in file order,
+//! feeds their rows to the same accumulator as a serial run, and merges each batch's
+//! tally into the accumulator's maps. Merging in file order appends every index list in
+//! ascending row order, so every report, flag, and range works the same way and the
+//! reports are identical to a serial run's. At most two batches per worker are read
+//! ahead of the accumulator, so the line contents in memory stay bounded however large
+//! the file is.
+//!
+//! The rows are tallied by the accumulator itself when the length of a row depends on
+//! more than the row: in `--csv-mode` (records span lines and batches), with
+//! `--low-memory` (the histogram is bucketed as it grows), with `--min-row-chars`, and
+//! with `--flag-stream` (each row is scored against the counts so far).
+//!
+//! Every worker checksums the bytes of its batch; the combined checksums must match the
+//! checksum of the read buffers, taken before they are split into rows, before the
+//! summary reports are written (see the `chunk_checksum` module).
+//!
+//! `--parallel` replaces the separate `csv_row_analyzer_parallel_rust` binary. Its
+//! `length_sorted` report is not written; it is the char_counts report sorted by
+//! `character_length`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::chunk_checksum::{Adler32, ChecksumReader};
+use crate::count_mode::CountMode;
+use crate::line_reader::{LineReader, PhysicalLine, RawLine};
+
+/// Batches read ahead of the accumulator per worker before reading waits for the workers
+const BATCHES_IN_FLIGHT_PER_WORKER: usize = 2;
+/// Bytes of rows gathered before a batch is handed to a worker
+const BATCH_BYTES: usize = 1 << 20;
+
+/// How the workers count and tally the rows
+#[derive(Debug, Clone, Copy)]
+pub struct ParallelCounting {
+    /// Worker threads (`--threads`)
+    pub threads: usize,
+    /// Unit the length of each line is counted in
+    pub count_mode: CountMode,
+    /// Whether a row's length includes its terminator (`--include-line-endings`)
+    pub include_line_endings: bool,
+    /// Whether the workers tally the length frequencies and row indices of their batches
+    pub tally_lengths: bool,
+}
+
+/// Length frequencies and row indices of the readable rows of one batch
+#[derive(Debug, Default)]
+pub struct LengthTally {
+    /// Map of row lengths to their frequency
+    pub length_counts: HashMap<usize, u64>,
+    /// Map of row lengths to the row indices having that length, in ascending order
+    pub row_indices: HashMap<usize, Vec<usize>>,
+}
+
+/// Decoded lines of one batch with their content character counts, the batch's tally,
+/// and the checksum of its bytes
+struct CountedBatch {
+    lines: Vec<(PhysicalLine, usize)>,
+    tally: Option<LengthTally>,
+    checksum: Adler32,
+}
+
+/// Reads every line of `lines`, counts the characters of each on the worker threads, and
+/// passes the batches to `consume` in file order.
+///
+/// # Arguments
+///
+/// * `lines` - Reader positioned at the first row to analyze
+/// * `counting` - Worker threads, count mode, and whether the workers tally lengths
+/// * `consume` - Called once per batch, in file order, with its lines and the length of
+///   each line's content (0 for an unreadable line), then the batch's tally when
+///   `counting.tally_lengths` is set
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Ok(()) once every line was consumed, or an Error when the
+///   batch checksums do not add up to the bytes read
+pub fn count_lines_in_parallel<R: BufRead>(
+    lines: LineReader<R>,
+    counting: ParallelCounting,
+    mut consume: impl FnMut(Vec<(PhysicalLine, usize)>, Option<LengthTally>),
+) -> Result<(), io::Error> {
+    let threads = counting.threads.max(1);
+    let max_batches_in_flight = threads * BATCHES_IN_FLIGHT_PER_WORKER;
+    let (batch_sender, batch_receiver) = mpsc::channel::<(usize, Vec<RawLine>)>();
+    let batch_receiver = Mutex::new(batch_receiver);
+    let (result_sender, result_receiver) = mpsc::channel::<(usize, CountedBatch)>();
+    // Checksum of every byte read, compared with the workers' combined checksums
+    let mut lines = lines.map_reader(ChecksumReader::new);
+
+    let (read_checksum, counted_checksum) = thread::scope(|scope| {
+        for _ in 0..threads {
+            let batch_receiver = &batch_receiver;
+            let result_sender = result_sender.clone();
+            scope.spawn(move || count_batches(batch_receiver, result_sender, counting));
+        }
+        drop(result_sender);
+
+        let mut counted_checksum = Adler32::default();
+        // Batches finished ahead of an earlier one wait here until the earlier one is consumed
+        let mut finished_early: BTreeMap<usize, CountedBatch> = BTreeMap::new();
+        let (mut batches_sent, mut batches_consumed) = (0, 0);
+        loop {
+            let batch = read_batch(&mut lines);
+            let end_of_input = batch.is_empty();
+            if !end_of_input {
+                // The workers only stop taking batches if one of them panicked
+                if batch_sender.send((batches_sent, batch)).is_err() {
+                    break;
+                }
+                batches_sent += 1;
+            }
+
+            // Consume what is ready; wait when too much is in flight or the input is done
+            loop {
+                let must_wait = batches_sent - batches_consumed >= max_batches_in_flight
+                    || (end_of_input && batches_consumed < batches_sent);
+                let next = if must_wait { result_receiver.recv().ok() } else { result_receiver.try_recv().ok() };
+                let Some((batch_index, batch)) = next else {
+                    break;
+                };
+                finished_early.insert(batch_index, batch);
+                while let Some(batch) = finished_early.remove(&batches_consumed) {
+                    counted_checksum.combine(&batch.checksum);
+                    consume(batch.lines, batch.tally);
+                    batches_consumed += 1;
+                }
+            }
+            if end_of_input {
+                break;
+            }
+        }
+        drop(batch_sender);
+        (lines.get_mut().checksum, counted_checksum)
+    });
+
+    // Statistics built from dropped or duplicated rows would be silently wrong, so stop
+    if counted_checksum != read_checksum {
+        return Err(io::Error::other(format!(
+            "chunk checksum mismatch: workers processed {}, the reader read {}; the summary reports were not written",
+            counted_checksum, read_checksum
+        )));
+    }
+    Ok(())
+}
+
+/// Reads lines until the batch holds about [`BATCH_BYTES`], or the input ends.
+fn read_batch<R: BufRead>(lines: &mut LineReader<ChecksumReader<R>>) -> Vec<RawLine> {
+    let mut batch = Vec::new();
+    let mut batch_bytes = 0;
+    while batch_bytes < BATCH_BYTES {
+        let checksum_before = lines.get_mut().checksum;
+        let Some(raw_line) = lines.next_raw() else {
+            break;
+        };
+        match &raw_line.bytes {
+            Ok(bytes) => batch_bytes += bytes.len(),
+            // The workers get no bytes of a line that failed to read, and reading stops
+            // after it, so the part read before the error is left out here too
+            Err(_) => lines.get_mut().checksum = checksum_before,
+        }
+        batch.push(raw_line);
+    }
+    batch
+}
+
+/// Worker loop: takes the next queued batch until the reader is done, and sends back its
+/// decoded lines with their character counts and, if requested, their tally.
+fn count_batches(
+    batch_receiver: &Mutex<Receiver<(usize, Vec<RawLine>)>>,
+    result_sender: Sender<(usize, CountedBatch)>,
+    counting: ParallelCounting,
+) {
+    loop {
+        // The lock is held only while taking a batch, not while counting it
+        let next_batch = batch_receiver.lock().expect("Batch queue poisoned").recv();
+        let Ok((batch_index, batch)) = next_batch else {
+            break;
+        };
+        let mut counted = CountedBatch {
+            lines: Vec::with_capacity(batch.len()),
+            tally: counting.tally_lengths.then(LengthTally::default),
+            checksum: Adler32::default(),
+        };
+        for raw_line in batch {
+            if let Ok(bytes) = &raw_line.bytes {
+                counted.checksum.update(bytes);
+            }
+            let physical_line = raw_line.decode();
+            let content_chars = physical_line.content.as_ref().map_or(0, |line| counting.count_mode.count(line));
+            if let Some(tally) = counted.tally.as_mut()
+                && physical_line.content.is_ok()
+            {
+                let char_count = if counting.include_line_endings {
+                    content_chars + physical_line.terminator.len()
+                } else {
+                    content_chars
+                };
+                *tally.length_counts.entry(char_count).or_insert(0) += 1;
+                tally.row_indices.entry(char_count).or_default().push(physical_line.row_index);
+            }
+            counted.lines.push((physical_line, content_chars));
+        }
+        if result_sender.send((batch_index, counted)).is_err() {
+            break;
+        }
+    }
+}
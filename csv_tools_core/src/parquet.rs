@@ -0,0 +1,305 @@
+//! # Minimal Parquet Writer
+//!
+//! Just enough Parquet writing for the `--parquet-sidecar` file, without a dependency:
+//! flat schemas of required `INT32`, `INT64`, and `BOOLEAN` columns, one row group,
+//! uncompressed `PLAIN` data pages (format v1). Any Parquet reader (pyarrow, DuckDB,
+//! Spark) can read the result.
+//!
+//! ```text
+//! PAR1 | column chunk 1 | ... | column chunk n | FileMetaData | metadata length | PAR1
+//! ```
+//!
+//! Page headers and the file metadata are Thrift structs in the compact protocol,
+//! written by [`CompactWriter`] with the field ids of `parquet.thrift`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Values per data page, so no page gets large however many rows the file has
+const VALUES_PER_PAGE: usize = 64 * 1024;
+const MAGIC: &[u8] = b"PAR1";
+
+// parquet.thrift enum values
+const TYPE_BOOLEAN: i32 = 0;
+const TYPE_INT32: i32 = 1;
+const TYPE_INT64: i32 = 2;
+const REPETITION_REQUIRED: i32 = 0;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_DATA: i32 = 0;
+
+// Thrift compact protocol type ids
+const COMPACT_I32: u8 = 5;
+const COMPACT_I64: u8 = 6;
+const COMPACT_BINARY: u8 = 8;
+const COMPACT_LIST: u8 = 9;
+const COMPACT_STRUCT: u8 = 12;
+
+/// Values of one column
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValues {
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Boolean(Vec<bool>),
+}
+
+impl ColumnValues {
+    fn len(&self) -> usize {
+        match self {
+            ColumnValues::Int32(values) => values.len(),
+            ColumnValues::Int64(values) => values.len(),
+            ColumnValues::Boolean(values) => values.len(),
+        }
+    }
+
+    fn physical_type(&self) -> i32 {
+        match self {
+            ColumnValues::Int32(_) => TYPE_INT32,
+            ColumnValues::Int64(_) => TYPE_INT64,
+            ColumnValues::Boolean(_) => TYPE_BOOLEAN,
+        }
+    }
+
+    /// PLAIN encoding of the values in `range`: little-endian integers, or booleans
+    /// packed one bit each, least significant bit first.
+    fn plain(&self, range: std::ops::Range<usize>) -> Vec<u8> {
+        match self {
+            ColumnValues::Int32(values) => values[range].iter().flat_map(|v| v.to_le_bytes()).collect(),
+            ColumnValues::Int64(values) => values[range].iter().flat_map(|v| v.to_le_bytes()).collect(),
+            ColumnValues::Boolean(values) => {
+                let values = &values[range];
+                let mut bytes = vec![0u8; values.len().div_ceil(8)];
+                for (index, _) in values.iter().enumerate().filter(|(_, value)| **value) {
+                    bytes[index / 8] |= 1 << (index % 8);
+                }
+                bytes
+            },
+        }
+    }
+}
+
+/// A named column of a flat table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetColumn {
+    pub name: &'static str,
+    pub values: ColumnValues,
+}
+
+/// Where a column chunk was written, for the file metadata
+struct ChunkLocation {
+    data_page_offset: i64,
+    total_size: i64,
+}
+
+/// Writes the columns (all of the same length) as a Parquet file with one row group.
+///
+/// # Arguments
+///
+/// * `path` - File to create
+/// * `columns` - The columns, in schema order
+/// * `key_values` - Entries of the file's key/value metadata
+/// * `created_by` - Application named in the file metadata
+pub fn write_parquet(
+    path: impl AsRef<Path>,
+    columns: &[ParquetColumn],
+    key_values: &[(&str, String)],
+    created_by: &str,
+) -> io::Result<()> {
+    let num_rows = columns.first().map_or(0, |column| column.values.len());
+    if columns.iter().any(|column| column.values.len() != num_rows) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "parquet columns differ in length"));
+    }
+
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(MAGIC)?;
+    let mut offset = MAGIC.len() as i64;
+
+    let mut locations = Vec::new();
+    for column in columns {
+        let chunk_start = offset;
+        // Always at least one page, so an empty column still has a data page to point at
+        let mut page_start = 0;
+        loop {
+            let page_end = (page_start + VALUES_PER_PAGE).min(num_rows);
+            let data = column.values.plain(page_start..page_end);
+            let mut header = CompactWriter::default();
+            header.field_i32(1, PAGE_DATA);
+            header.field_i32(2, data.len() as i32);
+            header.field_i32(3, data.len() as i32);
+            header.field_struct_begin(5);
+            header.field_i32(1, (page_end - page_start) as i32);
+            header.field_i32(2, ENCODING_PLAIN);
+            header.field_i32(3, ENCODING_RLE);
+            header.field_i32(4, ENCODING_RLE);
+            header.struct_end();
+            header.struct_end();
+            file.write_all(&header.bytes)?;
+            file.write_all(&data)?;
+            offset += (header.bytes.len() + data.len()) as i64;
+            page_start = page_end;
+            if page_start >= num_rows {
+                break;
+            }
+        }
+        locations.push(ChunkLocation { data_page_offset: chunk_start, total_size: offset - chunk_start });
+    }
+
+    let metadata = file_metadata(columns, &locations, num_rows as i64, key_values, created_by);
+    file.write_all(&metadata)?;
+    file.write_all(&(metadata.len() as u32).to_le_bytes())?;
+    file.write_all(MAGIC)?;
+    file.flush()
+}
+
+/// Encodes the `FileMetaData` struct of the footer.
+fn file_metadata(
+    columns: &[ParquetColumn],
+    locations: &[ChunkLocation],
+    num_rows: i64,
+    key_values: &[(&str, String)],
+    created_by: &str,
+) -> Vec<u8> {
+    let mut meta = CompactWriter::default();
+    meta.field_i32(1, 1);
+
+    // Schema: the root, then one required leaf per column
+    meta.field_list_begin(2, COMPACT_STRUCT, columns.len() + 1);
+    meta.list_struct_begin();
+    meta.field_binary(4, b"schema");
+    meta.field_i32(5, columns.len() as i32);
+    meta.struct_end();
+    for column in columns {
+        meta.list_struct_begin();
+        meta.field_i32(1, column.values.physical_type());
+        meta.field_i32(3, REPETITION_REQUIRED);
+        meta.field_binary(4, column.name.as_bytes());
+        meta.struct_end();
+    }
+
+    meta.field_i64(3, num_rows);
+
+    meta.field_list_begin(4, COMPACT_STRUCT, 1);
+    meta.list_struct_begin();
+    meta.field_list_begin(1, COMPACT_STRUCT, columns.len());
+    for (column, location) in columns.iter().zip(locations) {
+        meta.list_struct_begin();
+        meta.field_i64(2, location.data_page_offset);
+        meta.field_struct_begin(3);
+        meta.field_i32(1, column.values.physical_type());
+        meta.field_list_begin(2, COMPACT_I32, 2);
+        meta.list_i32(ENCODING_PLAIN);
+        meta.list_i32(ENCODING_RLE);
+        meta.field_list_begin(3, COMPACT_BINARY, 1);
+        meta.list_binary(column.name.as_bytes());
+        meta.field_i32(4, CODEC_UNCOMPRESSED);
+        meta.field_i64(5, num_rows);
+        meta.field_i64(6, location.total_size);
+        meta.field_i64(7, location.total_size);
+        meta.field_i64(9, location.data_page_offset);
+        meta.struct_end();
+        meta.struct_end();
+    }
+    meta.field_i64(2, locations.iter().map(|location| location.total_size).sum());
+    meta.field_i64(3, num_rows);
+    meta.struct_end();
+
+    if !key_values.is_empty() {
+        meta.field_list_begin(5, COMPACT_STRUCT, key_values.len());
+        for (key, value) in key_values {
+            meta.list_struct_begin();
+            meta.field_binary(1, key.as_bytes());
+            meta.field_binary(2, value.as_bytes());
+            meta.struct_end();
+        }
+    }
+    meta.field_binary(6, created_by.as_bytes());
+    meta.struct_end();
+    meta.bytes
+}
+
+/// Thrift compact protocol encoder for the structs above
+#[derive(Default)]
+struct CompactWriter {
+    bytes: Vec<u8>,
+    /// Last field id of each open struct; field headers store the delta
+    last_field_ids: Vec<i16>,
+    last_field_id: i16,
+}
+
+impl CompactWriter {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    fn zigzag(&mut self, value: i64) {
+        self.varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn field_header(&mut self, id: i16, compact_type: u8) {
+        let delta = id - self.last_field_id;
+        if (1..=15).contains(&delta) {
+            self.bytes.push(((delta as u8) << 4) | compact_type);
+        } else {
+            self.bytes.push(compact_type);
+            self.zigzag(id as i64);
+        }
+        self.last_field_id = id;
+    }
+
+    fn field_i32(&mut self, id: i16, value: i32) {
+        self.field_header(id, COMPACT_I32);
+        self.zigzag(value as i64);
+    }
+
+    fn field_i64(&mut self, id: i16, value: i64) {
+        self.field_header(id, COMPACT_I64);
+        self.zigzag(value);
+    }
+
+    fn field_binary(&mut self, id: i16, value: &[u8]) {
+        self.field_header(id, COMPACT_BINARY);
+        self.list_binary(value);
+    }
+
+    fn field_struct_begin(&mut self, id: i16) {
+        self.field_header(id, COMPACT_STRUCT);
+        self.list_struct_begin();
+    }
+
+    fn field_list_begin(&mut self, id: i16, element_type: u8, size: usize) {
+        self.field_header(id, COMPACT_LIST);
+        if size < 15 {
+            self.bytes.push(((size as u8) << 4) | element_type);
+        } else {
+            self.bytes.push(0xF0 | element_type);
+            self.varint(size as u64);
+        }
+    }
+
+    /// Starts a struct element of a list (or a struct field, after its header).
+    fn list_struct_begin(&mut self) {
+        self.last_field_ids.push(self.last_field_id);
+        self.last_field_id = 0;
+    }
+
+    fn list_i32(&mut self, value: i32) {
+        self.zigzag(value as i64);
+    }
+
+    fn list_binary(&mut self, value: &[u8]) {
+        self.varint(value.len() as u64);
+        self.bytes.extend_from_slice(value);
+    }
+
+    /// Ends the innermost struct with a stop byte.
+    fn struct_end(&mut self) {
+        self.bytes.push(0);
+        self.last_field_id = self.last_field_ids.pop().unwrap_or(0);
+    }
+}
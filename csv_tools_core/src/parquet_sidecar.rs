@@ -0,0 +1,80 @@
+//! # Parquet Sidecar (`--parquet-sidecar`)
+//!
+//! Writes `[basename]_sidecar_[timestamp].parquet` with one row per data record, in file
+//! order, so the quality flags can be joined positionally to the Parquet version of the
+//! same data (which has one row per record and no header row):
+//!
+//! | Column | Type | Meaning |
+//! |--------|------|---------|
+//! | `record_number` | INT64 | 0-based position among the data records (the join key) |
+//! | `row_index` | INT64 | Physical row where the record starts, as in the `char_counts` report |
+//! | `record_length` | INT64 | Characters, embedded line breaks included |
+//! | `field_count` | INT32 | Fields of the record |
+//! | `length_outlier` | BOOLEAN | Longer than the upper outlier fence of the run |
+//! | `field_count_mismatch` | BOOLEAN | Field count differs from the header's |
+//! | `multiline` | BOOLEAN | A quoted value spans several physical lines |
+//! | `unreadable` | BOOLEAN | The record contains a line that is not valid UTF-8 |
+//!
+//! A line that is not valid UTF-8 still gets its row (with length and field count 0), so
+//! the positions stay aligned with the data. The record length counts the final line
+//! terminator only with `--include-line-endings`. The file's key/value metadata carries
+//! `report_schema_version`, the source basename, and the upper fence used for
+//! `length_outlier`. With `--rows` or `--bytes`, the sidecar covers the analyzed slice,
+//! and `record_number` counts from its first record.
+
+use std::io;
+use std::path::Path;
+
+use crate::parquet::{write_parquet, ColumnValues, ParquetColumn};
+use crate::report_schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_COLUMN};
+
+/// Length and field count of one data record
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordShape {
+    /// Row index where the record starts
+    pub row_index: usize,
+    pub length: usize,
+    pub fields: usize,
+    pub multiline: bool,
+    pub unreadable: bool,
+}
+
+/// The shapes of all data records of a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordShapes {
+    /// Fields of the header, or 0 when no header was read
+    pub header_fields: usize,
+    pub records: Vec<RecordShape>,
+}
+
+/// Writes the sidecar file for the recorded shapes.
+///
+/// # Arguments
+///
+/// * `path` - File to create
+/// * `basename` - Basename of the analyzed input, stored in the metadata
+/// * `shapes` - Shapes of the data records, in file order
+/// * `upper_fence` - Records longer than this are flagged as length outliers
+pub fn write_sidecar(path: impl AsRef<Path>, basename: &str, shapes: &RecordShapes, upper_fence: f64) -> io::Result<()> {
+    let records = &shapes.records;
+    let flag = |test: &dyn Fn(&RecordShape) -> bool| ColumnValues::Boolean(records.iter().map(test).collect());
+    let columns = [
+        ParquetColumn { name: "record_number", values: ColumnValues::Int64((0..records.len() as i64).collect()) },
+        ParquetColumn { name: "row_index", values: ColumnValues::Int64(records.iter().map(|r| r.row_index as i64).collect()) },
+        ParquetColumn { name: "record_length", values: ColumnValues::Int64(records.iter().map(|r| r.length as i64).collect()) },
+        ParquetColumn { name: "field_count", values: ColumnValues::Int32(records.iter().map(|r| r.fields as i32).collect()) },
+        ParquetColumn { name: "length_outlier", values: flag(&|r| r.length as f64 > upper_fence) },
+        ParquetColumn {
+            name: "field_count_mismatch",
+            values: flag(&|r| !r.unreadable && shapes.header_fields > 0 && r.fields != shapes.header_fields),
+        },
+        ParquetColumn { name: "multiline", values: flag(&|r| r.multiline) },
+        ParquetColumn { name: "unreadable", values: flag(&|r| r.unreadable) },
+    ];
+    let key_values = [
+        (SCHEMA_VERSION_COLUMN, REPORT_SCHEMA_VERSION.to_string()),
+        ("source", basename.to_string()),
+        ("length_upper_fence", format!("{:.2}", upper_fence)),
+    ];
+    write_parquet(path, &columns, &key_values, concat!("csv_tools_core ", env!("CARGO_PKG_VERSION")))
+}
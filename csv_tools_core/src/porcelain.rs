@@ -0,0 +1,90 @@
+//! # Porcelain Output (`--porcelain`)
+//!
+//! Prints exactly one tab-separated line of key metrics per analyzed file to stdout, and
+//! nothing else (warnings and errors still go to stderr), so shell scripts can read the
+//! result without JSON tooling:
+//!
+//! ```bash
+//! $ IFS=$'\t' read -r version status path rows error_rows chars min max mean median std_dev threshold outliers \
+//!     < <(csv_row_analyzer data.csv reports --porcelain)
+//! ```
+//!
+//! The fields, in order, are [`PORCELAIN_FIELDS`]. There is no header line. The line
+//! follows the report schema guarantees (see the `report_schema` module): the first
+//! field is the schema version, and fields are only ever appended at the end, so a
+//! script that reads the first N fields keeps working.
+//!
+//! `status` is one of:
+//!
+//! * `ok` - every row was read and every report was written
+//! * `read_errors` - some rows could not be read (`error_rows` says how many)
+//! * `report_errors` - some report could not be written (the exit code is non-zero)
+//! * `failed` - the file could not be analyzed; every metric field is empty
+//!
+//! A file that appears truncated keeps its status; the run exits with status 3 instead
+//! (see the `truncation` module).
+//!
+//! Tabs, carriage returns, and line feeds in the input path are written as `\t`, `\r`,
+//! and `\n`, so the line always has the same number of fields.
+
+use std::path::Path;
+
+use crate::report_schema::REPORT_SCHEMA_VERSION;
+
+/// Names of the porcelain fields, in output order
+pub const PORCELAIN_FIELDS: [&str; 13] = [
+    "report_schema_version",
+    "status",
+    "input_path",
+    "total_rows",
+    "error_rows",
+    "total_chars",
+    "min_length",
+    "max_length",
+    "mean_length",
+    "median_length",
+    "std_dev_length",
+    "outlier_threshold",
+    "outlier_rows",
+];
+
+/// Outcome of the analysis of one file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PorcelainStatus {
+    Ok,
+    ReadErrors,
+    ReportErrors,
+    Failed,
+}
+
+impl PorcelainStatus {
+    /// Value of the `status` field.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PorcelainStatus::Ok => "ok",
+            PorcelainStatus::ReadErrors => "read_errors",
+            PorcelainStatus::ReportErrors => "report_errors",
+            PorcelainStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Formats the porcelain line of one file, without the line terminator.
+///
+/// # Arguments
+///
+/// * `status` - Outcome of the analysis
+/// * `input_path` - Path of the analyzed file, as given
+/// * `metrics` - Values of the fields after `input_path`, or None for a failed file
+pub fn porcelain_line(status: PorcelainStatus, input_path: impl AsRef<Path>, metrics: Option<&[String]>) -> String {
+    let path = input_path.as_ref().display().to_string()
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n");
+    let mut fields = vec![REPORT_SCHEMA_VERSION.to_string(), status.code().to_string(), path];
+    match metrics {
+        Some(metrics) => fields.extend(metrics.iter().cloned()),
+        None => fields.resize(PORCELAIN_FIELDS.len(), String::new()),
+    }
+    fields.join("\t")
+}
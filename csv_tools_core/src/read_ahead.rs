@@ -0,0 +1,131 @@
+//! # Read-Ahead Double Buffering (`--read-ahead`)
+//!
+//! A background thread reads the next buffer from disk while the analyzer counts the
+//! current one, so reading and counting overlap. For I/O-bound inputs (network mounts,
+//! cold disks) this gives most of the parallel analyzer's speedup while the analysis
+//! itself stays serial and single-pass.
+//!
+//! Exactly two buffers circulate: one is being filled by the reader thread, the other is
+//! being consumed. A consumed buffer is handed back to the thread for reuse, so memory
+//! use stays at two buffers regardless of file size.
+
+use std::io::{self, BufRead, Read};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Size of each of the two buffers
+pub const READ_AHEAD_BUFFER_SIZE: usize = 1 << 20;
+
+/// A buffered reader whose next buffer is filled by a background thread
+pub struct ReadAheadReader {
+    /// Buffers filled by the thread; an empty buffer marks the end of the input
+    filled: Receiver<io::Result<Vec<u8>>>,
+    /// Consumed buffers returned to the thread
+    empty: Sender<Vec<u8>>,
+    current: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl ReadAheadReader {
+    /// Starts the reader thread over `inner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - Source to read ahead from, moved to the reader thread
+    /// * `buffer_size` - Size of each of the two buffers
+    pub fn new<R: Read + Send + 'static>(mut inner: R, buffer_size: usize) -> Self {
+        let (filled_sender, filled) = mpsc::sync_channel(2);
+        let (empty, empty_receiver) = mpsc::channel::<Vec<u8>>();
+        // The thread starts with one buffer; the second one waits in the return channel
+        let _ = empty.send(Vec::with_capacity(buffer_size));
+
+        thread::spawn(move || {
+            let mut buffer = Vec::with_capacity(buffer_size);
+            loop {
+                let (bytes_read, error) = fill_buffer(&mut inner, &mut buffer, buffer_size);
+                let at_end = bytes_read == 0 || error.is_some();
+                if bytes_read > 0 && filled_sender.send(Ok(buffer)).is_err() {
+                    return;
+                }
+                if at_end {
+                    let _ = filled_sender.send(error.map_or(Ok(Vec::new()), Err));
+                    return;
+                }
+                // Blocks until the consumer is done with a buffer; ends once the reader is dropped
+                buffer = match empty_receiver.recv() {
+                    Ok(buffer) => buffer,
+                    Err(_) => return,
+                };
+            }
+        });
+
+        ReadAheadReader { filled, empty, current: Vec::new(), position: 0, finished: false }
+    }
+}
+
+/// Reads until the buffer holds `buffer_size` bytes or the input ends.
+///
+/// # Returns
+///
+/// * `(usize, Option<io::Error>)` - Bytes read, and the error that stopped reading early, if any
+fn fill_buffer<R: Read>(inner: &mut R, buffer: &mut Vec<u8>, buffer_size: usize) -> (usize, Option<io::Error>) {
+    buffer.clear();
+    buffer.resize(buffer_size, 0);
+    let mut filled = 0;
+    let mut error = None;
+    while filled < buffer_size {
+        match inner.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => {
+                error = Some(e);
+                break;
+            },
+        }
+    }
+    buffer.truncate(filled);
+    (filled, error)
+}
+
+impl BufRead for ReadAheadReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position >= self.current.len() && !self.finished {
+            let consumed = std::mem::take(&mut self.current);
+            if consumed.capacity() > 0 {
+                let _ = self.empty.send(consumed);
+            }
+            self.position = 0;
+            match self.filled.recv() {
+                Ok(Ok(buffer)) => {
+                    self.finished = buffer.is_empty();
+                    self.current = buffer;
+                },
+                Ok(Err(e)) => {
+                    self.finished = true;
+                    return Err(e);
+                },
+                Err(_) => {
+                    self.finished = true;
+                    return Err(io::Error::other("the read-ahead thread stopped unexpectedly"));
+                },
+            }
+        }
+        Ok(&self.current[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.current.len());
+    }
+}
+
+impl Read for ReadAheadReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let count = available.len().min(out.len());
+        out[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
@@ -0,0 +1,123 @@
+//! # Adaptive Read Buffer (`--read-buffer-size`)
+//!
+//! The input is read through a buffer that starts at [`INITIAL_READ_BUFFER_SIZE`] and
+//! grows while rows longer than the buffer are read. A row that does not fit is
+//! otherwise gathered in many small reads, one per buffer refill, which thrashes on files
+//! whose typical rows are several megabytes long.
+//!
+//! Whenever the buffer is refilled while the row in progress has already taken at least a
+//! whole buffer, the next read uses a buffer twice the size, up to [`MAX_READ_BUFFER_SIZE`].
+//! The buffer never shrinks: a file with some long rows tends to have more.
+//!
+//! `--read-buffer-size` turns the adaptation off and reads with a fixed buffer instead:
+//!
+//! ```bash
+//! $ csv_row_analyzer huge_rows.csv reports --read-buffer-size 8M
+//! ```
+//!
+//! The size also applies to the two buffers of `--read-ahead` (1 MiB each otherwise), to
+//! the byte-range readers of `--lengths-only`, and to the reader of `--async`, which
+//! cannot grow its buffer and starts at the initial size otherwise.
+
+use std::io::{self, BufRead, Read};
+
+use crate::input_range::parse_byte_size;
+
+/// Size of the read buffer at the start of every file
+pub const INITIAL_READ_BUFFER_SIZE: usize = 64 << 10;
+/// Largest size the read buffer grows to
+pub const MAX_READ_BUFFER_SIZE: usize = 64 << 20;
+/// Smallest size accepted by `--read-buffer-size`
+const MIN_READ_BUFFER_SIZE: usize = 4 << 10;
+
+/// Size of the input read buffer
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ReadBufferSize {
+    /// Start small and grow while long rows are read
+    #[default]
+    Adaptive,
+    /// Always this many bytes (`--read-buffer-size`)
+    Fixed(usize),
+}
+
+impl ReadBufferSize {
+    /// Parses a `--read-buffer-size` value such as `8M` or `262144`.
+    pub fn parse(text: &str) -> Result<ReadBufferSize, String> {
+        let size = parse_byte_size(text)? as usize;
+        if !(MIN_READ_BUFFER_SIZE..=MAX_READ_BUFFER_SIZE).contains(&size) {
+            return Err(format!("--read-buffer-size must be between 4K and 64M, found '{}'", text));
+        }
+        Ok(ReadBufferSize::Fixed(size))
+    }
+
+    /// The fixed size, or `adaptive_default` for readers that cannot grow.
+    pub fn fixed_or(&self, adaptive_default: usize) -> usize {
+        match self {
+            ReadBufferSize::Adaptive => adaptive_default,
+            ReadBufferSize::Fixed(size) => *size,
+        }
+    }
+}
+
+/// A buffered reader whose buffer grows while rows longer than it are read
+pub struct AdaptiveBufReader<R: Read> {
+    inner: R,
+    buffer: Vec<u8>,
+    position: usize,
+    filled: usize,
+    /// Bytes read so far of the row that the buffer ends in
+    open_row_bytes: usize,
+    /// Whether the buffer may grow
+    adaptive: bool,
+}
+
+impl<R: Read> AdaptiveBufReader<R> {
+    /// Creates a reader over `inner` with the given buffer size.
+    pub fn new(inner: R, size: ReadBufferSize) -> Self {
+        let (capacity, adaptive) = match size {
+            ReadBufferSize::Adaptive => (INITIAL_READ_BUFFER_SIZE, true),
+            ReadBufferSize::Fixed(size) => (size, false),
+        };
+        AdaptiveBufReader { inner, buffer: vec![0; capacity], position: 0, filled: 0, open_row_bytes: 0, adaptive }
+    }
+}
+
+impl<R: Read> Read for AdaptiveBufReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+impl<R: Read> BufRead for AdaptiveBufReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position >= self.filled {
+            // Searching from the end finds a line break at once in files of short rows
+            let read = &self.buffer[..self.filled];
+            self.open_row_bytes = match read.iter().rposition(|&b| b == b'\n') {
+                Some(line_break) => read.len() - line_break - 1,
+                None => self.open_row_bytes + read.len(),
+            };
+            if self.adaptive && self.open_row_bytes >= self.buffer.len() && self.buffer.len() < MAX_READ_BUFFER_SIZE {
+                let grown = (self.buffer.len() * 2).min(MAX_READ_BUFFER_SIZE);
+                self.buffer.resize(grown, 0);
+            }
+            self.filled = loop {
+                match self.inner.read(&mut self.buffer) {
+                    Ok(count) => break count,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                }
+            };
+            self.position = 0;
+        }
+        Ok(&self.buffer[self.position..self.filled])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.filled);
+    }
+}
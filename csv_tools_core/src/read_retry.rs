@@ -0,0 +1,132 @@
+//! # Read Retries (`--read-retries`, `--read-retry-delay`)
+//!
+//! Network mounts (NFS, SMB) fail a read now and then with an error that is gone a moment
+//! later: a timeout, a reset or busy connection, a generic I/O error while the server fails
+//! over. The line reader stops at the first failed read, so without a retry one hiccup
+//! counts the row as unreadable and leaves every row after it out of the statistics.
+//!
+//! A read that fails with such an error is retried up to `--read-retries` times (default
+//! [`DEFAULT_READ_RETRIES`]), after `--read-retry-delay` milliseconds (default 100) and
+//! twice as long before each further retry, up to [`MAX_READ_RETRY_DELAY`]. A failed read
+//! consumes no input, so the retry continues where the failed one stopped. Errors a retry
+//! cannot fix (permission denied, invalid data) fail at once, and `--read-retries 0` turns
+//! retrying off:
+//!
+//! ```bash
+//! $ csv_row_analyzer /mnt/exports/orders.csv reports --read-retries 5 --read-retry-delay 500
+//! ```
+//!
+//! The retries of a file are shown in its outlier reports and on stderr, and returned as
+//! `AnalysisResult::read_retries`. A read that still fails after the last retry ends the
+//! pass as before, with the number of retries in the error.
+
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Retries of a failed read, unless `--read-retries` is set
+pub const DEFAULT_READ_RETRIES: u32 = 3;
+/// Wait before the first retry, unless `--read-retry-delay` is set
+pub const DEFAULT_READ_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// Longest wait before one retry
+pub const MAX_READ_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// How often and after how long a failed read is retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadRetryPolicy {
+    /// Retries of one read (`--read-retries`)
+    pub retries: u32,
+    /// Wait before the first retry, doubled for each further one (`--read-retry-delay`)
+    pub delay: Duration,
+}
+
+impl Default for ReadRetryPolicy {
+    fn default() -> Self {
+        ReadRetryPolicy { retries: DEFAULT_READ_RETRIES, delay: DEFAULT_READ_RETRY_DELAY }
+    }
+}
+
+impl ReadRetryPolicy {
+    /// Wait before retry number `retry` (0 for the first).
+    fn delay_before(&self, retry: u32) -> Duration {
+        self.delay.saturating_mul(1 << retry.min(16)).min(MAX_READ_RETRY_DELAY)
+    }
+}
+
+/// The retry policy of one analysis and the retries made so far, shared by its readers
+#[derive(Debug, Clone, Default)]
+pub struct ReadRetries {
+    policy: ReadRetryPolicy,
+    made: Arc<AtomicU64>,
+}
+
+impl ReadRetries {
+    /// Starts counting the retries of one analysis.
+    pub fn new(policy: ReadRetryPolicy) -> Self {
+        ReadRetries { policy, made: Arc::default() }
+    }
+
+    /// Retries made so far by every reader wrapped with this count.
+    pub fn count(&self) -> u64 {
+        self.made.load(Ordering::Relaxed)
+    }
+
+    /// Wraps a source so its failed reads are retried and counted here.
+    pub fn wrap<R: Read>(&self, inner: R) -> RetryingReader<R> {
+        RetryingReader { inner, retries: self.clone() }
+    }
+}
+
+/// A reader that retries reads failing with a transient error
+pub struct RetryingReader<R: Read> {
+    inner: R,
+    retries: ReadRetries,
+}
+
+impl<R: Read> Read for RetryingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let policy = self.retries.policy;
+        let mut retry = 0;
+        loop {
+            match self.inner.read(buf) {
+                Err(e) if retry < policy.retries && is_transient(&e) => {
+                    thread::sleep(policy.delay_before(retry));
+                    retry += 1;
+                    self.retries.made.fetch_add(1, Ordering::Relaxed);
+                },
+                Err(e) if retry > 0 => {
+                    return Err(io::Error::new(e.kind(), format!("{} (still failing after {} retries)", e, retry)));
+                },
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Whether a read error may be gone on the next attempt.
+///
+/// `Interrupted` is left out: the buffered readers repeat such a read at once.
+fn is_transient(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::TimedOut
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ResourceBusy
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+    ) || error.raw_os_error().is_some_and(is_transient_os_error)
+}
+
+/// OS errors of network filesystems that a retry can fix: EIO while an NFS server fails
+/// over, and on Windows the SMB errors for an unexpected network error, a dropped share
+/// connection, and a semaphore timeout.
+fn is_transient_os_error(code: i32) -> bool {
+    if cfg!(windows) {
+        matches!(code, 59 | 64 | 121)
+    } else {
+        code == 5
+    }
+}
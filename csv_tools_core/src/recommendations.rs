@@ -0,0 +1,356 @@
+//! # Recommendations Rules Engine
+//!
+//! Turns the computed row-length metrics into the "Recommendations" section of the
+//! outlier reports. Each rule is a condition over named metrics mapped to a message
+//! template, so teams can encode house rules that show up in every report.
+//!
+//! ## Rule Syntax
+//!
+//! ```text
+//! <metric> <op> <operand> [&& <metric> <op> <operand> ...] => <template>
+//! ```
+//!
+//! * `<op>` is one of `>`, `>=`, `<`, `<=`, `==`, `!=`
+//! * `<operand>` is a number, a metric name, or `metric * number`
+//! * `<template>` may reference metrics as `{metric_name}`
+//!
+//! Rules are configured in the `[recommendations]` section of the config file:
+//!
+//! ```text
+//! [recommendations]
+//! # Set to false to drop the built-in rules and use only the ones below
+//! defaults = true
+//! rule = max_length > 1000000 => Rows over 1MB must be routed to the blob pipeline (largest: {max_length} chars).
+//! warning = error_rows > 0 => {error_rows} rows could not be read.
+//! ```
+//!
+//! ## Available Metrics
+//!
+//! `total_rows`, `total_chars`, `error_rows`, `unique_lengths`, `min_length`, `max_length`,
+//! `mean_length`, `median_length`, `q1_length`, `q3_length`, `iqr_length`, `std_dev_length`,
+//! `upper_threshold`, `lower_threshold`, `outlier_rows`, `outlier_percent`, `max_pages`
+//!
+//! The thresholds, and the outliers counted against them, are the fences on the
+//! `--length-scale` (see the `length_scale` module), in characters.
+
+use crate::config::AnalyzerConfig;
+
+/// Names of all metrics that rules may reference
+pub const METRIC_NAMES: [&str; 17] = [
+    "total_rows",
+    "total_chars",
+    "error_rows",
+    "unique_lengths",
+    "min_length",
+    "max_length",
+    "mean_length",
+    "median_length",
+    "q1_length",
+    "q3_length",
+    "iqr_length",
+    "std_dev_length",
+    "upper_threshold",
+    "lower_threshold",
+    "outlier_rows",
+    "outlier_percent",
+    "max_pages",
+];
+
+/// How prominently a triggered rule is displayed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    /// A regular recommendation bullet
+    Advice,
+    /// A bullet prefixed with "Warning"
+    Warning,
+}
+
+/// Comparison operator used in a rule condition
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+/// Right-hand side of a rule condition
+#[derive(Debug, Clone)]
+enum Operand {
+    /// A literal number
+    Number(f64),
+    /// Another metric, multiplied by a factor (1.0 when no factor was given)
+    Metric(String, f64),
+}
+
+/// A single `metric op operand` comparison
+#[derive(Debug, Clone)]
+struct Condition {
+    metric: String,
+    comparison: Comparison,
+    operand: Operand,
+}
+
+/// A recommendation rule: all conditions must hold for the template to be emitted
+#[derive(Debug, Clone)]
+pub struct RecommendationRule {
+    severity: Severity,
+    conditions: Vec<Condition>,
+    template: String,
+}
+
+/// A triggered rule with its template filled in
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub severity: Severity,
+    pub text: String,
+}
+
+/// Named metric values a rule set is evaluated against
+#[derive(Debug, Clone, Default)]
+pub struct RecommendationMetrics {
+    values: Vec<(&'static str, f64)>,
+}
+
+impl RecommendationMetrics {
+    /// Sets (or replaces) the value of a metric.
+    pub fn set(&mut self, name: &'static str, value: f64) {
+        match self.values.iter_mut().find(|(n, _)| *n == name) {
+            Some(entry) => entry.1 = value,
+            None => self.values.push((name, value)),
+        }
+    }
+
+    /// Looks up the value of a metric by name.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.iter().find(|(n, _)| *n == name).map(|&(_, v)| v)
+    }
+}
+
+/// Returns the built-in rules that reproduce the tool's standard data-quality advice.
+pub fn default_rules() -> Vec<RecommendationRule> {
+    [
+        (Severity::Warning,
+         "outlier_percent > 10 => More than 10% of rows are statistical outliers, suggesting high variability in row structure."),
+        (Severity::Advice,
+         "mean_length > median_length * 1.5 => The distribution is heavily skewed right (mean much larger than median), suggesting some extremely large values are affecting the average."),
+    ]
+    .iter()
+    .map(|(severity, text)| parse_rule(*severity, text).expect("built-in rule must parse"))
+    .collect()
+}
+
+/// Builds the rule set for a run from the optional config file.
+///
+/// Built-in rules come first (unless `defaults = false` is set), followed by any
+/// `rule = ...` and `warning = ...` entries from the `[recommendations]` section.
+///
+/// # Arguments
+///
+/// * `config` - Parsed configuration file, if one was given
+///
+/// # Returns
+///
+/// * `Result<Vec<RecommendationRule>, String>` - The rules or a message naming the invalid entry
+pub fn load_rules(config: Option<&AnalyzerConfig>) -> Result<Vec<RecommendationRule>, String> {
+    let config = match config {
+        Some(config) => config,
+        None => return Ok(default_rules()),
+    };
+
+    let use_defaults = match config.get("recommendations", "defaults") {
+        None | Some("true") => true,
+        Some("false") => false,
+        Some(other) => return Err(format!("[recommendations] defaults must be true or false, found '{}'", other)),
+    };
+
+    let mut rules = if use_defaults { default_rules() } else { Vec::new() };
+
+    for entry in config.section_entries("recommendations") {
+        let severity = match entry.key.as_str() {
+            "rule" => Severity::Advice,
+            "warning" => Severity::Warning,
+            "defaults" => continue,
+            other => return Err(format!("line {}: unknown key '{}' in [recommendations]", entry.line_number, other)),
+        };
+        let rule = parse_rule(severity, &entry.value)
+            .map_err(|e| format!("line {}: {}", entry.line_number, e))?;
+        rules.push(rule);
+    }
+
+    Ok(rules)
+}
+
+/// Parses one rule from its textual form.
+///
+/// # Arguments
+///
+/// * `severity` - How the rule is displayed when triggered
+/// * `text` - Rule text in the form `condition [&& condition] => template`
+///
+/// # Returns
+///
+/// * `Result<RecommendationRule, String>` - The parsed rule or a description of the problem
+pub fn parse_rule(severity: Severity, text: &str) -> Result<RecommendationRule, String> {
+    let (condition_text, template) = text.split_once("=>")
+        .ok_or_else(|| format!("rule '{}' is missing '=> template'", text))?;
+
+    let template = template.trim().to_string();
+    if template.is_empty() {
+        return Err(format!("rule '{}' has an empty template", text));
+    }
+    validate_template(&template)?;
+
+    let conditions = condition_text
+        .split("&&")
+        .map(parse_condition)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(RecommendationRule { severity, conditions, template })
+}
+
+/// Evaluates rules against the metrics, returning the triggered recommendations in rule order.
+///
+/// # Arguments
+///
+/// * `rules` - Rule set to evaluate
+/// * `metrics` - Metric values computed for the analyzed file
+pub fn evaluate_rules(rules: &[RecommendationRule], metrics: &RecommendationMetrics) -> Vec<Recommendation> {
+    rules.iter()
+        .filter(|rule| rule.conditions.iter().all(|condition| condition_holds(condition, metrics)))
+        .map(|rule| Recommendation {
+            severity: rule.severity,
+            text: render_template(&rule.template, metrics),
+        })
+        .collect()
+}
+
+/// Parses a single `metric op operand` comparison.
+fn parse_condition(text: &str) -> Result<Condition, String> {
+    let text = text.trim();
+
+    // Two-character operators must be tried before their one-character prefixes
+    let operators = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+    ];
+
+    let (position, symbol, comparison) = operators.iter()
+        .filter_map(|&(symbol, comparison)| text.find(symbol).map(|pos| (pos, symbol, comparison)))
+        .min_by_key(|&(pos, symbol, _)| (pos, std::cmp::Reverse(symbol.len())))
+        .ok_or_else(|| format!("condition '{}' has no comparison operator", text))?;
+
+    let metric = text[..position].trim().to_string();
+    validate_metric_name(&metric)?;
+
+    let operand_text = text[position + symbol.len()..].trim();
+    let operand = parse_operand(operand_text)?;
+
+    Ok(Condition { metric, comparison, operand })
+}
+
+/// Parses the right-hand side of a condition.
+fn parse_operand(text: &str) -> Result<Operand, String> {
+    if let Ok(number) = text.parse::<f64>() {
+        return Ok(Operand::Number(number));
+    }
+
+    let (name, factor) = match text.split_once('*') {
+        Some((name, factor)) => {
+            let factor = factor.trim().parse::<f64>()
+                .map_err(|_| format!("invalid multiplier in '{}'", text))?;
+            (name.trim(), factor)
+        },
+        None => (text, 1.0),
+    };
+
+    validate_metric_name(name)?;
+    Ok(Operand::Metric(name.to_string(), factor))
+}
+
+/// Ensures a metric name is one the analyzer actually computes.
+fn validate_metric_name(name: &str) -> Result<(), String> {
+    if METRIC_NAMES.contains(&name) {
+        Ok(())
+    } else {
+        Err(format!("unknown metric '{}' (available: {})", name, METRIC_NAMES.join(", ")))
+    }
+}
+
+/// Ensures every `{placeholder}` in a template names a known metric.
+fn validate_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after.find('}')
+            .ok_or_else(|| format!("unclosed '{{' in template '{}'", template))?;
+        validate_metric_name(&after[..end])?;
+        rest = &after[end + 1..];
+    }
+    Ok(())
+}
+
+/// Checks whether a condition holds; conditions on missing metrics never hold.
+fn condition_holds(condition: &Condition, metrics: &RecommendationMetrics) -> bool {
+    let left = match metrics.get(&condition.metric) {
+        Some(value) => value,
+        None => return false,
+    };
+    let right = match &condition.operand {
+        Operand::Number(value) => *value,
+        Operand::Metric(name, factor) => match metrics.get(name) {
+            Some(value) => value * factor,
+            None => return false,
+        },
+    };
+
+    match condition.comparison {
+        Comparison::Greater => left > right,
+        Comparison::GreaterOrEqual => left >= right,
+        Comparison::Less => left < right,
+        Comparison::LessOrEqual => left <= right,
+        Comparison::Equal => left == right,
+        Comparison::NotEqual => left != right,
+    }
+}
+
+/// Replaces `{metric}` placeholders with formatted metric values.
+fn render_template(template: &str, metrics: &RecommendationMetrics) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match metrics.get(name) {
+                    Some(value) => output.push_str(&format_metric(value)),
+                    None => output.push_str("N/A"),
+                }
+                rest = &after[end + 1..];
+            },
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Formats whole numbers without decimals and everything else with two.
+fn format_metric(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.2}", value)
+    }
+}
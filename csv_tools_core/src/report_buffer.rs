@@ -0,0 +1,41 @@
+//! # Buffered Report Writes (`--report-buffer-size`)
+//!
+//! The row report gets one line per row of the input and the frequency and page reports
+//! one per distinct length, so a multi-million-row file means millions of small writes.
+//! Written straight to a `File`, each of them is a system call; every report is therefore
+//! written through a buffer of [`DEFAULT_REPORT_BUFFER_SIZE`] and flushed once at the end,
+//! where a failed flush fails the report like any other write.
+//!
+//! `--report-buffer-size` sets the buffer size. `0` writes every line as it comes, which
+//! is only useful to measure the difference (`cargo bench --bench report_writes`):
+//!
+//! ```bash
+//! $ csv_row_analyzer huge_file.csv reports --report-buffer-size 1M
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use crate::input_range::parse_byte_size;
+
+/// Size of the buffer every report is written through
+pub const DEFAULT_REPORT_BUFFER_SIZE: usize = 64 << 10;
+/// Largest size accepted by `--report-buffer-size`
+const MAX_REPORT_BUFFER_SIZE: usize = 64 << 20;
+
+/// Parses a `--report-buffer-size` value such as `1M` or `0`.
+pub fn parse_report_buffer_size(text: &str) -> Result<usize, String> {
+    let size = parse_byte_size(text)? as usize;
+    if size > MAX_REPORT_BUFFER_SIZE {
+        return Err(format!("--report-buffer-size must be between 0 and 64M, found '{}'", text));
+    }
+    Ok(size)
+}
+
+/// Creates a report file written through a buffer of `buffer_size` bytes.
+///
+/// The caller flushes it once the report is complete, so a failed final write is seen.
+pub fn create_report(path: impl AsRef<Path>, buffer_size: usize) -> io::Result<BufWriter<File>> {
+    Ok(BufWriter::with_capacity(buffer_size, File::create(path)?))
+}
@@ -0,0 +1,60 @@
+//! # Report Schema Version
+//!
+//! Every machine-readable output carries `report_schema_version` as its last column, so a
+//! downstream parser can check the version before relying on the column set:
+//!
+//! * CSV reports (`char_counts`, `value_counts`, `pages_valuecounts`, `column_format`,
+//!   `column_rules`, `column_totals`, `value_shapes`, `column_sizes`, `model_scores`,
+//!   `entropy`, `field_counts`, `spot_check`, `directory_summary`, and `summary` of
+//!   `--writer summary-csv`) - on every line
+//! * the JSON form of the column reports (`--column-reports-json`) - as the last key of
+//!   every object, with the same keys as the CSV columns
+//! * the JSON analysis report (`--format json`) and the `--writer summary-json` report -
+//!   as their first key
+//! * the history file (`--history`) and the `csv_analysis_runs` table (`--db-sink`) - on
+//!   every run, so rows written by different versions can be told apart
+//! * the `--porcelain` line - as its first field
+//! * the Parquet sidecar (`--parquet-sidecar`) - as an entry of its key/value metadata
+//!
+//! Three outputs keep formats defined elsewhere: badge JSON follows the shields.io endpoint
+//! schema, `--table-schema` follows the Frictionless Table Schema spec, and the
+//! `--length-buckets` files are bare line numbers for `sed`/`awk`. The `--extract-outliers`
+//! rows keep the input's own columns, behind a `file_row` column. The `--export-model`
+//! model is an input to later runs and is versioned by its own `model_version` key.
+//! Reports of writers registered by an application have the formats it defines.
+//!
+//! ## Compatibility Guarantees
+//!
+//! * Within one version, the column names, their order, and their meaning (including
+//!   units) do not change in any output.
+//! * Any change to a column set bumps the version: adding, removing, renaming, or
+//!   reordering a column, or changing what a column means. There are no silent additions.
+//! * Columns added to the history file and database table go after the existing ones,
+//!   just before `report_schema_version`; older history files keep their own header (and
+//!   so never get the new columns), and existing tables get the new column added on the
+//!   next write.
+//!
+//! ## Version History
+//!
+//! * `1` - First versioned schema: the columns as of the addition of this column.
+//! * `2` - History file and `csv_analysis_runs` table: added `p99_length` and
+//!   `outlier_lengths` (distinct outlier row lengths, largest first, `;`-separated),
+//!   before `report_schema_version`.
+//! * `3` - `char_counts`: added the `flags` column (`--row-flags`, see the `row_flags`
+//!   module) before `report_schema_version`; without `--row-flags` the column is absent.
+//! * `4` - `char_counts`: added `file_row` (1-based line number) and `data_index` (0 for
+//!   the first row after the header, -1 for the header) after `row_index`, the columns of
+//!   the former parallel analyzer's report.
+//! * `5` - `char_counts`: added the `physical_lines` column (`--csv-mode`, see the
+//!   `logical_records` module) after `character_length`; without `--csv-mode` the column
+//!   is absent. In `--csv-mode`, `row_index` and `data_index` count records and `file_row`
+//!   is the line a record starts on, in every output.
+//! * `6` - `column_format`: added `min_length`, `shortest_value`, `shortest_value_row`,
+//!   `max_length`, `longest_value`, and `longest_value_row` (each column's shortest and
+//!   longest non-empty value, cut to 60 characters) before `report_schema_version`.
+
+/// Version of the column sets of all machine-readable outputs
+pub const REPORT_SCHEMA_VERSION: u32 = 6;
+
+/// Name of the column that carries [`REPORT_SCHEMA_VERSION`]
+pub const SCHEMA_VERSION_COLUMN: &str = "report_schema_version";
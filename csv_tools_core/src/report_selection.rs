@@ -0,0 +1,91 @@
+//! # Report Selection (`--reports`)
+//!
+//! Every run writes five standard reports per file, which in directory mode adds up to a
+//! lot of files nobody opens. `--reports` names the standard reports to write; the others
+//! are not generated at all:
+//!
+//! ```bash
+//! $ csv_row_analyzer --directory feeds reports --reports char_counts,outliers_md,pages
+//! ```
+//!
+//! The names are `char_counts`, `value_counts`, `pages` (pages_valuecounts),
+//! `outliers_md`, and `outliers_txt`. Reports enabled by their own flags (such as
+//! `--profile-columns` or `--format json`) are written whenever their flag is given.
+
+/// One of the standard reports
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StandardReport {
+    CharCounts,
+    ValueCounts,
+    Pages,
+    OutliersMd,
+    OutliersTxt,
+}
+
+impl StandardReport {
+    /// Every standard report, in the order they are listed
+    pub const ALL: [StandardReport; 5] = [
+        StandardReport::CharCounts,
+        StandardReport::ValueCounts,
+        StandardReport::OutliersMd,
+        StandardReport::OutliersTxt,
+        StandardReport::Pages,
+    ];
+
+    /// Name of the report in `--reports`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StandardReport::CharCounts => "char_counts",
+            StandardReport::ValueCounts => "value_counts",
+            StandardReport::Pages => "pages",
+            StandardReport::OutliersMd => "outliers_md",
+            StandardReport::OutliersTxt => "outliers_txt",
+        }
+    }
+}
+
+/// The standard reports a run writes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportSelection {
+    reports: Vec<StandardReport>,
+}
+
+impl Default for ReportSelection {
+    fn default() -> Self {
+        ReportSelection { reports: StandardReport::ALL.to_vec() }
+    }
+}
+
+impl ReportSelection {
+    /// Parses a comma-separated list of report names, such as `char_counts,outliers_md`.
+    pub fn parse(list: &str) -> Result<ReportSelection, String> {
+        let mut reports = Vec::new();
+        for name in list.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            let report = StandardReport::ALL.into_iter()
+                .find(|report| report.name() == name)
+                .ok_or_else(|| format!(
+                    "unknown report '{}' in --reports (use {})",
+                    name,
+                    StandardReport::ALL.map(|report| report.name()).join(", ")
+                ))?;
+            if !reports.contains(&report) {
+                reports.push(report);
+            }
+        }
+        if reports.is_empty() {
+            return Err("--reports needs at least one report name (use --no-reports to write none)".to_string());
+        }
+        Ok(ReportSelection { reports })
+    }
+
+    /// Whether a standard report is written.
+    pub fn includes(&self, report: StandardReport) -> bool {
+        self.reports.contains(&report)
+    }
+
+    /// The same selection without one report.
+    pub fn without(mut self, report: StandardReport) -> ReportSelection {
+        self.reports.retain(|&selected| selected != report);
+        self
+    }
+}
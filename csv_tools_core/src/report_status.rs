@@ -0,0 +1,327 @@
+//! # Report Write Status
+//!
+//! Writes each report independently, so one report that cannot be written (disk full,
+//! missing permission) does not discard the others that were already computed.
+//!
+//! A failed report is retried (`--report-retries <n>`, with a short growing delay) and
+//! then written to an alternate directory (`--fallback-dir <path>`) when one is given.
+//! At the end of a run a status line is printed per report, and the run fails only if
+//! some report could not be written anywhere. With `--no-reports`, nothing is written and
+//! no status is printed.
+
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::file_workers::{print_file_output, take_writer, WriterPermit};
+use crate::messages::{Language, Message};
+
+/// Delay before the first retry; each further retry waits one more step
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Where one report ended up
+#[derive(Debug)]
+pub struct ReportStatus {
+    /// Short report name, matching the report file name infix (e.g. `md_outliers`)
+    pub name: Cow<'static, str>,
+    /// Location written to, or the last error
+    pub outcome: Result<String, io::Error>,
+    /// Whether the report went to the fallback directory
+    pub used_fallback: bool,
+}
+
+/// Error returned when some reports could not be written anywhere, after the analysis
+/// itself completed
+#[derive(Debug)]
+pub struct ReportWriteError {
+    failed: String,
+    failed_count: usize,
+    total: usize,
+}
+
+impl std::fmt::Display for ReportWriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} of {} reports could not be written: {}", self.failed_count, self.total, self.failed)
+    }
+}
+
+impl std::error::Error for ReportWriteError {}
+
+impl ReportWriteError {
+    /// Whether an error is a [`ReportWriteError`], i.e. the analysis completed.
+    pub fn is_report_write_error(error: &io::Error) -> bool {
+        error.get_ref().is_some_and(|inner| inner.is::<ReportWriteError>())
+    }
+}
+
+/// A report file written while streaming, holding its writer until it is closed
+#[derive(Debug)]
+pub struct StreamedReport {
+    file: File,
+    _writer: WriterPermit,
+}
+
+impl StreamedReport {
+    /// Returns the open file and frees its writer, for the async analyzer, which does not
+    /// run on the workers of a directory run.
+    #[cfg(feature = "async")]
+    pub fn into_file(self) -> File {
+        self.file
+    }
+}
+
+impl Write for StreamedReport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Writes reports with retries and a fallback directory, and tracks their outcomes
+#[derive(Debug)]
+pub struct ReportLog {
+    output_dir: PathBuf,
+    fallback_dir: Option<PathBuf>,
+    retries: u32,
+    statuses: Vec<ReportStatus>,
+    /// Skip every write (`--no-reports`)
+    disabled: bool,
+    /// Language of the status heading and of warnings printed while writing reports
+    language: Language,
+}
+
+impl ReportLog {
+    /// Creates a log for reports written to `output_dir`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_dir` - Directory reports are written to first
+    /// * `fallback_dir` - Directory used when writing to `output_dir` keeps failing
+    /// * `retries` - Extra attempts per directory before giving up on it
+    pub fn new(output_dir: impl AsRef<Path>, fallback_dir: Option<&str>, retries: u32) -> Self {
+        ReportLog {
+            output_dir: output_dir.as_ref().to_path_buf(),
+            fallback_dir: fallback_dir.map(PathBuf::from),
+            retries,
+            statuses: Vec::new(),
+            disabled: false,
+            language: Language::English,
+        }
+    }
+
+    /// Skips every write when `disabled` is set, leaving the disk untouched (`--no-reports`).
+    pub fn with_writes_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Prints the status heading in `language` (`--lang`).
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Returns the language set with [`ReportLog::with_language`].
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Writes one report into the output directory, falling back to the alternate one.
+    ///
+    /// A partially written file is removed before the next attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Short report name used in the status summary
+    /// * `file_name` - Name of the report file
+    /// * `write` - Writes the complete report to the path it is given
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the report was written
+    pub fn write(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        file_name: &str,
+        write: impl FnMut(&Path) -> Result<(), io::Error>,
+    ) -> bool {
+        let _writer = take_writer();
+        self.write_file(name, file_name, write)
+    }
+
+    /// Writes one report like [`ReportLog::write`], with a writer already taken.
+    fn write_file(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        file_name: &str,
+        mut write: impl FnMut(&Path) -> Result<(), io::Error>,
+    ) -> bool {
+        if self.disabled {
+            return false;
+        }
+        let mut outcome = Err(io::Error::other("not attempted"));
+        let mut used_fallback = false;
+
+        for (dir, is_fallback) in self.directories() {
+            let path = dir.join(file_name);
+            outcome = self.with_retries(|| {
+                fs::create_dir_all(&dir)?;
+                write(&path).inspect_err(|_| {
+                    let _ = fs::remove_file(&path);
+                })
+            }).map(|_| path.display().to_string());
+            used_fallback = is_fallback;
+            if outcome.is_ok() {
+                break;
+            }
+        }
+
+        let written = outcome.is_ok();
+        self.statuses.push(ReportStatus { name: name.into(), outcome, used_fallback });
+        written
+    }
+
+    /// Creates a report file that is written while streaming, trying the fallback directory
+    /// when the output directory does not accept it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Short report name used in the status summary
+    /// * `file_name` - Name of the report file
+    ///
+    /// # Returns
+    ///
+    /// * `Option<StreamedReport>` - The open file, or None when it could not be created anywhere
+    pub fn create(&mut self, name: &'static str, file_name: &str) -> Option<StreamedReport> {
+        let writer = take_writer();
+        let mut file = None;
+        self.write_file(name, file_name, |path| {
+            file = Some(File::create(path)?);
+            Ok(())
+        });
+        file.map(|file| StreamedReport { file, _writer: writer })
+    }
+
+    /// Marks a report created with [`ReportLog::create`] as failed part-way through and
+    /// removes the partial file, which also frees the space for the remaining reports.
+    pub fn fail(&mut self, name: impl Into<Cow<'static, str>>, error: io::Error) {
+        let name = name.into();
+        match self.statuses.iter_mut().find(|status| status.name == name) {
+            Some(status) => {
+                if let Ok(path) = &status.outcome {
+                    let _ = fs::remove_file(path);
+                }
+                status.outcome = Err(error);
+            },
+            None => self.statuses.push(ReportStatus { name, outcome: Err(error), used_fallback: false }),
+        }
+    }
+
+    /// Runs a write that has no alternate location (history file, database), with retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Short name used in the status summary
+    /// * `location` - Description of the destination, shown on success
+    /// * `write` - The write to attempt
+    pub fn write_to(&mut self, name: impl Into<Cow<'static, str>>, location: &str, write: impl FnMut() -> Result<(), io::Error>) {
+        if self.disabled {
+            return;
+        }
+        let _writer = take_writer();
+        let outcome = self.with_retries(write).map(|_| location.to_string());
+        self.statuses.push(ReportStatus { name: name.into(), outcome, used_fallback: false });
+    }
+
+    /// Returns the short name and file name of every report written so far, in the order
+    /// they were written.
+    pub fn written(&self) -> Vec<(&str, String)> {
+        self.statuses.iter()
+            .filter_map(|status| {
+                let location = status.outcome.as_ref().ok()?;
+                let file_name = Path::new(location).file_name()?.to_string_lossy().into_owned();
+                Some((status.name.as_ref(), file_name))
+            })
+            .collect()
+    }
+
+    /// Prints one status line per report.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), io::Error>` - Ok(()) when every report was written, or an Error naming the failed ones
+    pub fn finish(self) -> Result<(), io::Error> {
+        if !self.disabled {
+            self.print_statuses();
+        }
+        self.outcome()
+    }
+
+    fn print_statuses(&self) {
+        let mut lines = vec![Message::ReportStatus.text(self.language, &[])];
+        for status in &self.statuses {
+            lines.push(match &status.outcome {
+                Ok(location) if status.used_fallback => {
+                    format!("  {:<10} {:<20} {} (fallback directory)", "ok", status.name, location)
+                },
+                Ok(location) => format!("  {:<10} {:<20} {}", "ok", status.name, location),
+                Err(e) => format!("  {:<10} {:<20} {}", "FAILED", status.name, e),
+            });
+        }
+        // One block, so the statuses of a file analyzed on a worker thread stay together
+        print_file_output(&(lines.join("\n") + "\n"));
+    }
+
+    /// Returns the overall outcome without printing anything.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), io::Error>` - Ok(()) when every report was written, or a [`ReportWriteError`]
+    pub fn outcome(self) -> Result<(), io::Error> {
+        let failed: Vec<&str> = self.statuses.iter()
+            .filter(|status| status.outcome.is_err())
+            .map(|status| status.name.as_ref())
+            .collect();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(io::Error::other(ReportWriteError {
+                failed: failed.join(", "),
+                failed_count: failed.len(),
+                total: self.statuses.len(),
+            }))
+        }
+    }
+
+    /// Output directory first, then the fallback directory (if any and different).
+    fn directories(&self) -> Vec<(PathBuf, bool)> {
+        let mut directories = vec![(self.output_dir.clone(), false)];
+        if let Some(fallback) = &self.fallback_dir
+            && fallback != &self.output_dir
+        {
+            directories.push((fallback.clone(), true));
+        }
+        directories
+    }
+
+    /// Runs `attempt` until it succeeds or the retries are used up.
+    fn with_retries(&self, mut attempt: impl FnMut() -> Result<(), io::Error>) -> Result<(), io::Error> {
+        let mut retry = 0;
+        loop {
+            match attempt() {
+                Ok(()) => return Ok(()),
+                Err(e) if retry >= self.retries => return Err(e),
+                Err(_) => {
+                    retry += 1;
+                    thread::sleep(RETRY_DELAY * retry);
+                },
+            }
+        }
+    }
+}
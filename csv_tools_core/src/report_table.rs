@@ -0,0 +1,163 @@
+//! # Column Report Tables
+//!
+//! The reports that carry column names and values copied from the data (`column_format`,
+//! `column_totals`, `value_shapes`, `column_rules`) are built as typed tables and then written as CSV or,
+//! with `--column-reports-json`, as JSON.
+//!
+//! In CSV, every text cell goes through RFC 4180 quoting (`escape_field`), so names and
+//! examples containing the delimiter, quotes, or line breaks stay in their cell. Lists are
+//! `;`-separated within one cell and name/value pairs are written `name=value`, which a
+//! parser has to split again; a name or value that itself contains `;` or `=` makes
+//! that split ambiguous. The JSON form avoids any such ambiguity: the report is an array
+//! with one object per CSV line, keyed by the CSV column names, with lists as arrays,
+//! pairs as objects, numbers as numbers, and empty cells as `null`.
+//!
+//! ```json
+//! [
+//!   {"column_index": 0, "column_name": "zip", "findings": ["leading_zeros", "fixed_width_code"], ..., "report_schema_version": 3}
+//! ]
+//! ```
+//!
+//! Both forms end every row with `report_schema_version` (see the `report_schema` module).
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::fields::escape_field;
+use crate::json::{json_number, json_string};
+use crate::report_schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_COLUMN};
+
+/// File format of the column reports
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TableFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl TableFormat {
+    /// File extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::Json => "json",
+        }
+    }
+}
+
+/// One cell of a report table
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportValue {
+    Text(String),
+    Count(u64),
+    Number(f64),
+    /// A decimal already formatted as a number literal (exact column totals)
+    Decimal(String),
+    Bool(bool),
+    /// No value: an empty CSV cell, `null` in JSON
+    Empty,
+    /// `;`-separated in CSV, an array in JSON
+    List(Vec<ReportValue>),
+    /// `name=value;...` in CSV, an object in JSON
+    Pairs(Vec<(String, String)>),
+}
+
+impl ReportValue {
+    /// The cell's text before CSV quoting.
+    fn csv_text(&self) -> String {
+        match self {
+            ReportValue::Text(text) | ReportValue::Decimal(text) => text.clone(),
+            ReportValue::Count(count) => count.to_string(),
+            ReportValue::Number(number) => number.to_string(),
+            ReportValue::Bool(value) => value.to_string(),
+            ReportValue::Empty => String::new(),
+            ReportValue::List(items) => items.iter().map(ReportValue::csv_text).collect::<Vec<_>>().join(";"),
+            ReportValue::Pairs(pairs) => pairs.iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join(";"),
+        }
+    }
+
+    fn json(&self) -> String {
+        match self {
+            ReportValue::Text(text) => json_string(text),
+            ReportValue::Count(count) => count.to_string(),
+            ReportValue::Number(number) => json_number(*number),
+            // An approximate total can be `inf`, which JSON has no literal for
+            ReportValue::Decimal(text) if text.parse::<f64>().is_ok_and(f64::is_finite) => text.clone(),
+            ReportValue::Decimal(_) => "null".to_string(),
+            ReportValue::Bool(value) => value.to_string(),
+            ReportValue::Empty => "null".to_string(),
+            ReportValue::List(items) => {
+                format!("[{}]", items.iter().map(ReportValue::json).collect::<Vec<_>>().join(", "))
+            },
+            ReportValue::Pairs(pairs) => format!(
+                "{{{}}}",
+                pairs.iter()
+                    .map(|(name, value)| format!("{}: {}", json_string(name), json_string(value)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// A report as named columns and typed rows
+#[derive(Debug, Clone)]
+pub struct ReportTable {
+    /// Column names, without `report_schema_version` (added when written)
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<ReportValue>>,
+}
+
+impl ReportTable {
+    /// Creates an empty table with the given column names.
+    pub fn new(columns: &[&'static str]) -> Self {
+        ReportTable { columns: columns.to_vec(), rows: Vec::new() }
+    }
+
+    /// Adds a row; it must have one value per column.
+    pub fn push(&mut self, row: Vec<ReportValue>) {
+        debug_assert_eq!(row.len(), self.columns.len(), "report row does not match the columns");
+        self.rows.push(row);
+    }
+
+    /// Writes the table to `path` in the given format.
+    pub fn write(&self, path: impl AsRef<Path>, format: TableFormat) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        match format {
+            TableFormat::Csv => self.write_csv(&mut file)?,
+            TableFormat::Json => self.write_json(&mut file)?,
+        }
+        file.flush()
+    }
+
+    fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{},{}", self.columns.join(","), SCHEMA_VERSION_COLUMN)?;
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|value| escape_field(&value.csv_text())).collect();
+            writeln!(out, "{},{}", cells.join(","), REPORT_SCHEMA_VERSION)?;
+        }
+        Ok(())
+    }
+
+    fn write_json(&self, out: &mut impl Write) -> io::Result<()> {
+        let objects: Vec<String> = self.rows.iter()
+            .map(|row| {
+                let fields: Vec<String> = self.columns.iter()
+                    .zip(row)
+                    .map(|(column, value)| format!("{}: {}", json_string(column), value.json()))
+                    .chain(std::iter::once(format!("{}: {}", json_string(SCHEMA_VERSION_COLUMN), REPORT_SCHEMA_VERSION)))
+                    .collect();
+                format!("  {{{}}}", fields.join(", "))
+            })
+            .collect();
+        if objects.is_empty() {
+            writeln!(out, "[]")
+        } else {
+            writeln!(out, "[\n{}\n]", objects.join(",\n"))
+        }
+    }
+}
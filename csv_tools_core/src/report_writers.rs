@@ -0,0 +1,235 @@
+//! # Custom Report Writers (`--writer <name>`)
+//!
+//! Applications that need the results in a format of their own (a protobuf message, a
+//! line for their metrics pipeline) implement [`ReportWriter`] for it and register it
+//! with a [`ReportWriterRegistry`]. The writers selected with `--writer` then write
+//! their report from the [`AnalysisResult`] of every analyzed file, next to the built-in
+//! reports:
+//!
+//! ```no_run
+//! use std::io::{self, Write};
+//! use csv_tools_core::{csv_row_analyzer_main_with_writers, AnalysisResult, ReportWriter, ReportWriterRegistry};
+//!
+//! struct MetricsLine;
+//!
+//! impl ReportWriter for MetricsLine {
+//!     fn name(&self) -> &str { "metrics-line" }
+//!     fn extension(&self) -> &str { "txt" }
+//!     fn write(&self, result: &AnalysisResult, output: &mut dyn Write) -> io::Result<()> {
+//!         writeln!(output, "csv.rows={} csv.outliers={}", result.total_rows, result.outlier_rows)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut writers = ReportWriterRegistry::with_builtin_writers();
+//!     writers.register(Box::new(MetricsLine)).expect("unique writer name");
+//!     // my_analyzer orders.csv reports --writer metrics-line
+//!     csv_row_analyzer_main_with_writers(writers);
+//! }
+//! ```
+//!
+//! A writer's report is named `[basename]_[writer name]_report_[timestamp].[extension]`
+//! and is written like every other report: a failed write is reported and does not stop
+//! the others. The built-in writers are `summary-json` and `summary-csv`, the fields of
+//! the [`AnalysisResult`] as one JSON object or one CSV row. A service that consumes the
+//! `summary-json` reports of another process reads them back with
+//! [`AnalysisResult::from_json`].
+
+use std::io::{self, Write};
+
+use crate::csv_row_analyzer::{statistics_from_length_counts, AnalysisResult, Statistics};
+use crate::json::{json_number, json_string, parse_json, JsonValue};
+use crate::report_schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_COLUMN};
+
+/// A report format written from the summary of an analyzed file
+pub trait ReportWriter: Send + Sync {
+    /// Name that selects the writer (`--writer <name>`) and names its report
+    fn name(&self) -> &str;
+
+    /// Extension of the report file, without the dot
+    fn extension(&self) -> &str;
+
+    /// Writes the report of one analyzed file.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - Summary of the analyzed file
+    /// * `output` - The report file (buffered; flushed after the call)
+    fn write(&self, result: &AnalysisResult, output: &mut dyn Write) -> io::Result<()>;
+}
+
+/// The report writers that `--writer` can select, by name
+pub struct ReportWriterRegistry {
+    writers: Vec<Box<dyn ReportWriter>>,
+}
+
+impl ReportWriterRegistry {
+    /// A registry with no writers.
+    pub fn new() -> Self {
+        ReportWriterRegistry { writers: Vec::new() }
+    }
+
+    /// A registry with the built-in writers (`summary-json`, `summary-csv`).
+    pub fn with_builtin_writers() -> Self {
+        ReportWriterRegistry { writers: vec![Box::new(SummaryJson), Box::new(SummaryCsv)] }
+    }
+
+    /// Adds a writer.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Ok(()), or a message if a writer of that name is already registered
+    pub fn register(&mut self, writer: Box<dyn ReportWriter>) -> Result<(), String> {
+        if self.get(writer.name()).is_some() {
+            return Err(format!("a report writer named '{}' is already registered", writer.name()));
+        }
+        self.writers.push(writer);
+        Ok(())
+    }
+
+    /// The writer of a name, if registered.
+    pub fn get(&self, name: &str) -> Option<&dyn ReportWriter> {
+        self.writers.iter().find(|writer| writer.name() == name).map(|writer| writer.as_ref())
+    }
+
+    /// Names of the registered writers, in registration order.
+    pub fn names(&self) -> Vec<&str> {
+        self.writers.iter().map(|writer| writer.name()).collect()
+    }
+}
+
+impl Default for ReportWriterRegistry {
+    fn default() -> Self {
+        ReportWriterRegistry::with_builtin_writers()
+    }
+}
+
+/// The summary as one JSON object (`summary-json`)
+struct SummaryJson;
+
+impl ReportWriter for SummaryJson {
+    fn name(&self) -> &str {
+        "summary-json"
+    }
+
+    fn extension(&self) -> &str {
+        "json"
+    }
+
+    fn write(&self, result: &AnalysisResult, output: &mut dyn Write) -> io::Result<()> {
+        writeln!(output, "{}", result.to_json())
+    }
+}
+
+impl AnalysisResult {
+    /// The summary as one JSON object, as the `summary-json` writer writes it.
+    pub fn to_json(&self) -> String {
+        let stats = &self.statistics;
+        let length_counts = self.length_counts.iter()
+            .map(|(length, count)| format!("[{},{}]", length, count))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{{\"{}\":{},\"basename\":{},\"timestamp\":{},\"total_rows\":{},\"error_rows\":{},\"total_chars\":{},\
+                 \"statistics\":{{\"min\":{},\"max\":{},\"mean\":{},\"median\":{},\"q1\":{},\"q3\":{},\"std_dev\":{}}},\
+                 \"outlier_threshold_lower\":{},\"outlier_threshold_upper\":{},\"outlier_rows\":{},\"short_rows\":{},\
+                 \"header_fingerprint\":{},\"column_count\":{},\"read_retries\":{},\"length_counts\":[{}]}}",
+                SCHEMA_VERSION_COLUMN, REPORT_SCHEMA_VERSION, json_string(&self.basename), json_string(&self.timestamp),
+                self.total_rows, self.error_rows, self.total_chars,
+                stats.min, stats.max, json_number(stats.mean), stats.median, stats.q1, stats.q3, json_number(stats.std_dev),
+                json_number(self.outlier_threshold_lower), json_number(self.outlier_threshold_upper),
+                self.outlier_rows, self.short_rows,
+                self.header_fingerprint.as_deref().map_or("null".to_string(), json_string), self.column_count,
+                self.read_retries, length_counts)
+    }
+
+    /// Reads a summary written by the `summary-json` writer (or [`AnalysisResult::to_json`])
+    /// back, for a service that consumes the summaries of another process. The 99th
+    /// percentile, which the JSON leaves out, is recomputed from `length_counts`, and a
+    /// summary without `read_retries` (written before it was added) reads it as 0.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<AnalysisResult, String>` - The summary, or a message naming the missing or
+    ///   invalid key, or the unsupported `report_schema_version`
+    pub fn from_json(text: &str) -> Result<AnalysisResult, String> {
+        let summary = parse_json(text)?;
+        let version = summary.get(SCHEMA_VERSION_COLUMN).and_then(JsonValue::as_f64);
+        if version != Some(REPORT_SCHEMA_VERSION as f64) {
+            return Err(format!("unsupported {} {} (this version reads {})", SCHEMA_VERSION_COLUMN,
+                               version.map_or("(missing)".to_string(), |version| version.to_string()), REPORT_SCHEMA_VERSION));
+        }
+        let invalid = |key: &str| format!("missing or invalid \"{}\"", key);
+        let number = |object: &JsonValue, key: &str| object.get(key).and_then(JsonValue::as_f64).ok_or_else(|| invalid(key));
+        let count = |object: &JsonValue, key: &str| number(object, key)
+            .and_then(|value| if value >= 0.0 && value.fract() == 0.0 { Ok(value as u64) } else { Err(invalid(key)) });
+        let text = |key: &str| summary.get(key).and_then(JsonValue::as_str).map(str::to_string).ok_or_else(|| invalid(key));
+
+        let length_counts = summary.get("length_counts").and_then(JsonValue::as_array).ok_or_else(|| invalid("length_counts"))?
+            .iter()
+            .map(|pair| match pair.as_array() {
+                Some([JsonValue::Number(length), JsonValue::Number(count)]) => Ok((*length as usize, *count as u64)),
+                _ => Err(invalid("length_counts")),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let stats = summary.get("statistics").ok_or_else(|| invalid("statistics"))?;
+        let statistics = Statistics {
+            min: count(stats, "min")? as usize,
+            max: count(stats, "max")? as usize,
+            mean: number(stats, "mean")?,
+            median: count(stats, "median")? as usize,
+            q1: count(stats, "q1")? as usize,
+            q3: count(stats, "q3")? as usize,
+            p99: statistics_from_length_counts(&length_counts).p99,
+            std_dev: number(stats, "std_dev")?,
+        };
+        let header_fingerprint = match summary.get("header_fingerprint") {
+            Some(JsonValue::Null) | None => None,
+            Some(value) => Some(value.as_str().ok_or_else(|| invalid("header_fingerprint"))?.to_string()),
+        };
+
+        Ok(AnalysisResult {
+            basename: text("basename")?,
+            timestamp: text("timestamp")?,
+            total_rows: count(&summary, "total_rows")?,
+            error_rows: count(&summary, "error_rows")?,
+            total_chars: count(&summary, "total_chars")? as usize,
+            statistics,
+            outlier_threshold_lower: number(&summary, "outlier_threshold_lower")?,
+            outlier_threshold_upper: number(&summary, "outlier_threshold_upper")?,
+            outlier_rows: count(&summary, "outlier_rows")?,
+            short_rows: count(&summary, "short_rows")?,
+            length_counts,
+            header_fingerprint,
+            column_count: count(&summary, "column_count")? as usize,
+            read_retries: match summary.get("read_retries") {
+                Some(_) => count(&summary, "read_retries")?,
+                None => 0,
+            },
+        })
+    }
+}
+
+/// The summary as a header line and one CSV row (`summary-csv`)
+struct SummaryCsv;
+
+impl ReportWriter for SummaryCsv {
+    fn name(&self) -> &str {
+        "summary-csv"
+    }
+
+    fn extension(&self) -> &str {
+        "csv"
+    }
+
+    fn write(&self, result: &AnalysisResult, output: &mut dyn Write) -> io::Result<()> {
+        let stats = &result.statistics;
+        writeln!(output, "basename,timestamp,total_rows,error_rows,total_chars,min,max,mean,median,q1,q3,std_dev,\
+                          outlier_threshold_lower,outlier_threshold_upper,outlier_rows,short_rows,header_fingerprint,column_count,{}",
+                 SCHEMA_VERSION_COLUMN)?;
+        writeln!(output, "{},{},{},{},{},{},{},{:.2},{},{},{},{:.2},{:.2},{:.2},{},{},{},{},{}",
+                 result.basename, result.timestamp, result.total_rows, result.error_rows, result.total_chars,
+                 stats.min, stats.max, stats.mean, stats.median, stats.q1, stats.q3, stats.std_dev,
+                 result.outlier_threshold_lower, result.outlier_threshold_upper, result.outlier_rows, result.short_rows,
+                 result.header_fingerprint.as_deref().unwrap_or(""), result.column_count, REPORT_SCHEMA_VERSION)
+    }
+}
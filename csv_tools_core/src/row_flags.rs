@@ -0,0 +1,231 @@
+//! # Row Flags (`--row-flags`)
+//!
+//! Adds a `flags` column to the char_counts report, with one letter for each condition a
+//! row meets, so that one report can be filtered by any combination of conditions instead
+//! of cross-referencing the outlier, column, and truncation reports:
+//!
+//! | Letter | Condition |
+//! |--------|-----------|
+//! | `O` | Outlier: longer than the upper outlier fence |
+//! | `S` | Short: shorter than the lower outlier fence |
+//! | `B` | Blank: empty, or only whitespace |
+//! | `E` | Error: the row could not be read |
+//! | `Q` | Quote issue: a quote inside an unquoted field, text after a closing quote, or a quoted field that never closes |
+//! | `F` | Field count: the record has a different number of fields than the header |
+//!
+//! ```text
+//! row_index,file_row,data_index,character_length,flags,report_schema_version
+//! 0,1,-1,23,,4
+//! 1,2,0,19,,4
+//! 2,3,1,0,B,4
+//! 3,4,2,4812,OF,4
+//! 4,5,3,error_reading_line,E,4
+//! ```
+//!
+//! Letters appear in the order of the table, and a row that meets no condition has an
+//! empty `flags` field, so `awk -F, '$5 ~ /O/ && $5 ~ /F/'` finds the long rows with the
+//! wrong number of fields. The fences are the ones of the outlier reports (see the
+//! `length_scale` module).
+//!
+//! Quotes and fields are read per logical record, so a quoted value with line breaks is
+//! not a quote issue: `F` goes on the first row of the record, and an unclosed quote on the
+//! row that opened it. Without a header (a `--rows` range that skips row 0 reads it
+//! separately), no row gets `F`.
+//!
+//! The fences need the whole file, so with `--row-flags` the char_counts report is written
+//! with the other reports after the pass instead of while reading, and the row index,
+//! length, and flags of every row are kept until then.
+
+use crate::fields::{scan_quotes, split_fields, MAX_RECORD_LINES};
+use crate::length_scale::OutlierFences;
+
+/// Conditions met by one row, as a bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RowFlags(u8);
+
+impl RowFlags {
+    pub const OUTLIER: RowFlags = RowFlags(1);
+    pub const SHORT: RowFlags = RowFlags(1 << 1);
+    pub const BLANK: RowFlags = RowFlags(1 << 2);
+    pub const ERROR: RowFlags = RowFlags(1 << 3);
+    pub const QUOTE_ISSUE: RowFlags = RowFlags(1 << 4);
+    pub const FIELD_COUNT: RowFlags = RowFlags(1 << 5);
+
+    /// Report letter of each flag, in report order
+    const LETTERS: [(RowFlags, char); 6] = [
+        (RowFlags::OUTLIER, 'O'),
+        (RowFlags::SHORT, 'S'),
+        (RowFlags::BLANK, 'B'),
+        (RowFlags::ERROR, 'E'),
+        (RowFlags::QUOTE_ISSUE, 'Q'),
+        (RowFlags::FIELD_COUNT, 'F'),
+    ];
+
+    /// Whether every flag of `other` is set.
+    pub fn contains(&self, other: RowFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn insert(&mut self, other: RowFlags) {
+        self.0 |= other.0;
+    }
+
+    /// Letter codes of the set flags, e.g. `OF`, or an empty string.
+    pub fn letters(&self) -> String {
+        RowFlags::LETTERS.iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, letter)| letter)
+            .collect()
+    }
+}
+
+/// One row of the flagged char_counts report
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlaggedRow {
+    pub row_index: usize,
+    /// Character length, or None for a row that could not be read
+    pub length: Option<usize>,
+    /// Flags known during the pass; the outlier flags are added by [`RowFlagger::flags`]
+    flags: RowFlags,
+    /// Field count of the data record that starts on this row, or 0
+    fields: usize,
+}
+
+/// A record whose quoted field is still open
+#[derive(Debug, Clone)]
+struct OpenRecord {
+    /// Position of the record's first row in `rows`
+    first: usize,
+    fields: usize,
+    lines: usize,
+}
+
+/// Collects the rows of one pass and the flags they earn
+#[derive(Debug, Clone)]
+pub struct RowFlagger {
+    delimiter: char,
+    rows: Vec<FlaggedRow>,
+    open_record: Option<OpenRecord>,
+    header_fields: Option<usize>,
+}
+
+impl RowFlagger {
+    /// Creates an empty flagger for the given field delimiter.
+    pub fn new(delimiter: char) -> Self {
+        RowFlagger { delimiter, rows: Vec::new(), open_record: None, header_fields: None }
+    }
+
+    /// Adds one readable physical line.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_index` - 0-based physical row index of the line
+    /// * `line` - Line content without its terminator
+    /// * `length` - Length of the row as reported
+    pub fn observe_line(&mut self, row_index: usize, line: &str, length: usize) {
+        let continues_record = self.open_record.is_some();
+        let mut flags = RowFlags::default();
+        if !continues_record && line.trim().is_empty() {
+            flags.insert(RowFlags::BLANK);
+        }
+        if has_stray_quote(line, self.delimiter, continues_record) {
+            flags.insert(RowFlags::QUOTE_ISSUE);
+        }
+        self.rows.push(FlaggedRow { row_index, length: Some(length), flags, fields: 0 });
+
+        let (delimiters, in_quotes) = scan_quotes(line, self.delimiter, continues_record);
+        let mut record = self.open_record.take().unwrap_or(OpenRecord { first: self.rows.len() - 1, fields: 1, lines: 0 });
+        record.fields += delimiters;
+        record.lines += 1;
+        if in_quotes && record.lines < MAX_RECORD_LINES {
+            self.open_record = Some(record);
+            return;
+        }
+        if in_quotes {
+            self.rows[record.first].flags.insert(RowFlags::QUOTE_ISSUE);
+        }
+        let first = &mut self.rows[record.first];
+        if first.row_index == 0 {
+            self.header_fields = Some(record.fields);
+        } else if !first.flags.contains(RowFlags::BLANK) {
+            first.fields = record.fields;
+        }
+    }
+
+    /// Adds a row that could not be read.
+    pub fn observe_unreadable(&mut self, row_index: usize) {
+        self.rows.push(FlaggedRow { row_index, length: None, flags: RowFlags::ERROR, fields: 0 });
+    }
+
+    /// Uses a separately read header when the analyzed rows did not include row 0.
+    pub fn set_header_if_missing(&mut self, header_record: &str) {
+        if self.header_fields.is_none() {
+            self.header_fields = Some(split_fields(header_record, self.delimiter).len());
+        }
+    }
+
+    /// Completes the pass: flags a quote left open at the end of the input and the records
+    /// whose field count differs from the header's.
+    pub fn finish(&mut self) {
+        if let Some(record) = self.open_record.take() {
+            self.rows[record.first].flags.insert(RowFlags::QUOTE_ISSUE);
+        }
+        if let Some(header_fields) = self.header_fields {
+            for row in self.rows.iter_mut().filter(|row| row.fields > 0 && row.fields != header_fields) {
+                row.flags.insert(RowFlags::FIELD_COUNT);
+            }
+        }
+    }
+
+    /// The rows in file order.
+    pub fn rows(&self) -> &[FlaggedRow] {
+        &self.rows
+    }
+
+    /// All flags of a row, including the outlier flags from the file's fences.
+    pub fn flags(&self, row: &FlaggedRow, fences: &OutlierFences) -> RowFlags {
+        let mut flags = row.flags;
+        if let Some(length) = row.length {
+            if length as f64 > fences.upper {
+                flags.insert(RowFlags::OUTLIER);
+            }
+            if (length as f64) < fences.lower {
+                flags.insert(RowFlags::SHORT);
+            }
+        }
+        flags
+    }
+}
+
+/// Whether a line breaks the quoting rules of the `fields` module: a quote inside an
+/// unquoted field, or text between a closing quote and the next delimiter.
+fn has_stray_quote(line: &str, delimiter: char, starts_in_quotes: bool) -> bool {
+    let mut in_quotes = starts_in_quotes;
+    let mut at_field_start = !starts_in_quotes;
+    let mut after_closing_quote = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                    after_closing_quote = true;
+                }
+            }
+        } else if c == delimiter {
+            at_field_start = true;
+            after_closing_quote = false;
+        } else if c == '"' && at_field_start {
+            in_quotes = true;
+            at_field_start = false;
+        } else if c == '"' || after_closing_quote {
+            return true;
+        } else {
+            at_field_start = false;
+        }
+    }
+    false
+}
@@ -0,0 +1,374 @@
+//! # Row Hashing (`--hash-algorithm`, `--hash-salt`)
+//!
+//! One hasher for every feature that turns data into a hash, currently the header
+//! fingerprint, so the algorithm and salt are chosen once per run:
+//!
+//! * `fnv1a` (default) - 64-bit FNV-1a. Fast and stable, but trivially reversible for
+//!   short inputs such as column names; fine while hashes stay internal.
+//! * `xxh64` - 64-bit xxHash, for speed on long rows.
+//! * `sha256` - SHA-256, for evidentiary use where a collision must be infeasible.
+//!
+//! With a salt, `sha256` becomes HMAC-SHA256 keyed with the salt, which is the keyed hash
+//! to use when hashes leave the environment: without the salt, nobody can confirm a
+//! guessed value against a published hash. The fast algorithms only mix the salt in
+//! (`fnv1a` hashes it before the data, `xxh64` derives its seed from it); that changes
+//! every hash but is not a cryptographic keyed hash.
+//!
+//! Hashes are lowercase hex. Unsalted FNV-1a hashes are written bare, as before this
+//! option existed; every other hash is prefixed with its [`RowHasher::name`]
+//! (`sha256:...`, `hmac-sha256:...`), so hashes from runs with different settings never
+//! compare equal by accident.
+//!
+//! The salt can be given with `--hash-salt` or as `salt` in the `[hashing]` section of
+//! the config file, which keeps it out of shell history and process listings:
+//!
+//! ```text
+//! [hashing]
+//! algorithm = sha256
+//! salt = 7f3e...
+//! ```
+//!
+//! All three algorithms are implemented here from their specifications; tests/row_hash.rs
+//! checks them against the published test vectors. The zstd reader checks the content
+//! checksum of its frames with the same streaming XXH64.
+
+use crate::config::AnalyzerConfig;
+
+/// Hash algorithm of the row hashing features
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum HashAlgorithm {
+    #[default]
+    Fnv1a,
+    Xxh64,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Parses an algorithm name as given to `--hash-algorithm`.
+    pub fn parse(name: &str) -> Result<HashAlgorithm, String> {
+        match name.trim().to_lowercase().as_str() {
+            "fnv1a" | "fnv-1a" => Ok(HashAlgorithm::Fnv1a),
+            "xxh64" | "xxhash" => Ok(HashAlgorithm::Xxh64),
+            "sha256" | "sha-256" => Ok(HashAlgorithm::Sha256),
+            _ => Err(format!("unknown hash algorithm '{}' (expected fnv1a, xxh64, or sha256)", name)),
+        }
+    }
+
+    /// Name of the algorithm, as accepted by [`HashAlgorithm::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Fnv1a => "fnv1a",
+            HashAlgorithm::Xxh64 => "xxh64",
+            HashAlgorithm::Sha256 => "sha256",
+        }
+    }
+}
+
+/// Hashes data with the run's algorithm and salt
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowHasher {
+    pub algorithm: HashAlgorithm,
+    pub salt: Option<Vec<u8>>,
+}
+
+impl RowHasher {
+    /// Creates the hasher of a run; the salt is used as its UTF-8 bytes.
+    pub fn new(algorithm: HashAlgorithm, salt: Option<&str>) -> Self {
+        RowHasher { algorithm, salt: salt.map(|salt| salt.as_bytes().to_vec()) }
+    }
+
+    /// Name written before the hex digest: the algorithm, marked when salted.
+    pub fn name(&self) -> String {
+        match (self.algorithm, &self.salt) {
+            (HashAlgorithm::Sha256, Some(_)) => "hmac-sha256".to_string(),
+            (algorithm, Some(_)) => format!("{}+salt", algorithm.name()),
+            (algorithm, None) => algorithm.name().to_string(),
+        }
+    }
+
+    /// Hashes `bytes` and returns the digest as lowercase hex, prefixed with the hasher's
+    /// name unless it is unsalted FNV-1a.
+    pub fn hash_hex(&self, bytes: &[u8]) -> String {
+        let hex = match (self.algorithm, &self.salt) {
+            (HashAlgorithm::Fnv1a, None) => return format!("{:016x}", fnv1a_64(bytes)),
+            (HashAlgorithm::Fnv1a, Some(salt)) => {
+                format!("{:016x}", fnv1a_64_continue(fnv1a_64(salt), bytes))
+            },
+            (HashAlgorithm::Xxh64, salt) => {
+                let seed = salt.as_deref().map_or(0, |salt| xxh64(salt, 0));
+                format!("{:016x}", xxh64(bytes, seed))
+            },
+            (HashAlgorithm::Sha256, None) => to_hex(&sha256(bytes)),
+            (HashAlgorithm::Sha256, Some(salt)) => to_hex(&hmac_sha256(salt, bytes)),
+        };
+        format!("{}:{}", self.name(), hex)
+    }
+}
+
+/// Reads the algorithm and salt from the `[hashing]` section of the config file.
+///
+/// # Arguments
+///
+/// * `config` - The loaded config file, if any
+///
+/// # Returns
+///
+/// * `Result<(Option<HashAlgorithm>, Option<String>), String>` - The algorithm and salt
+///   the config sets, or an error naming the bad config line
+pub fn load_hashing(config: Option<&AnalyzerConfig>) -> Result<(Option<HashAlgorithm>, Option<String>), String> {
+    let mut algorithm = None;
+    let mut salt = None;
+    let Some(config) = config else {
+        return Ok((algorithm, salt));
+    };
+    for entry in config.section_entries("hashing") {
+        match entry.key.as_str() {
+            "algorithm" => {
+                algorithm = Some(HashAlgorithm::parse(&entry.value)
+                    .map_err(|e| format!("line {}: {}", entry.line_number, e))?);
+            },
+            "salt" => salt = Some(parse_salt(&entry.value).map_err(|e| format!("line {}: {}", entry.line_number, e))?),
+            key => return Err(format!("line {}: unknown [hashing] key '{}'", entry.line_number, key)),
+        }
+    }
+    Ok((algorithm, salt))
+}
+
+/// Checks a salt as given to `--hash-salt`; an empty salt would silently mean no salt.
+pub fn parse_salt(salt: &str) -> Result<String, String> {
+    if salt.is_empty() {
+        Err("the hash salt is empty".to_string())
+    } else {
+        Ok(salt.to_string())
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Computes the 64-bit FNV-1a hash of a byte sequence.
+///
+/// # Arguments
+///
+/// * `bytes` - Data to hash
+///
+/// # Returns
+///
+/// * `u64` - The hash value
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    fnv1a_64_continue(FNV_OFFSET_BASIS, bytes)
+}
+
+/// Continues an FNV-1a hash from `hash` over more bytes.
+fn fnv1a_64_continue(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+const XXH_PRIME64_1: u64 = 0x9E3779B185EBCA87;
+const XXH_PRIME64_2: u64 = 0xC2B2AE3D27D4EB4F;
+const XXH_PRIME64_3: u64 = 0x165667B19E3779F9;
+const XXH_PRIME64_4: u64 = 0x85EBCA77C2B2AE63;
+const XXH_PRIME64_5: u64 = 0x27D4EB2F165667C5;
+
+/// Computes the 64-bit xxHash (XXH64) of a byte sequence.
+pub fn xxh64(bytes: &[u8], seed: u64) -> u64 {
+    let mut hasher = Xxh64Hasher::new(seed);
+    hasher.update(bytes);
+    hasher.digest()
+}
+
+/// Streaming XXH64, for input that arrives in pieces (the content checksum of zstd frames)
+pub struct Xxh64Hasher {
+    seed: u64,
+    accumulators: [u64; 4],
+    /// Bytes of the current 32-byte stripe not yet folded into the accumulators
+    buffer: [u8; 32],
+    buffered: usize,
+    total: u64,
+}
+
+impl Xxh64Hasher {
+    /// Starts a hash with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Xxh64Hasher {
+            seed,
+            accumulators: [
+                seed.wrapping_add(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_2),
+                seed.wrapping_add(XXH_PRIME64_2),
+                seed,
+                seed.wrapping_sub(XXH_PRIME64_1),
+            ],
+            buffer: [0; 32],
+            buffered: 0,
+            total: 0,
+        }
+    }
+
+    fn round(accumulator: u64, lane: u64) -> u64 {
+        accumulator.wrapping_add(lane.wrapping_mul(XXH_PRIME64_2))
+            .rotate_left(31)
+            .wrapping_mul(XXH_PRIME64_1)
+    }
+
+    fn merge_round(hash: u64, accumulator: u64) -> u64 {
+        (hash ^ Self::round(0, accumulator)).wrapping_mul(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_4)
+    }
+
+    fn stripe(&mut self, stripe: &[u8]) {
+        for (accumulator, lane) in self.accumulators.iter_mut().zip(stripe.chunks_exact(8)) {
+            *accumulator = Self::round(*accumulator, u64::from_le_bytes(lane.try_into().expect("8 bytes")));
+        }
+    }
+
+    /// Adds bytes to the end of the input.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        self.total += bytes.len() as u64;
+        if self.buffered > 0 {
+            let taken = (32 - self.buffered).min(bytes.len());
+            self.buffer[self.buffered..self.buffered + taken].copy_from_slice(&bytes[..taken]);
+            self.buffered += taken;
+            bytes = &bytes[taken..];
+            if self.buffered < 32 {
+                return;
+            }
+            let buffer = self.buffer;
+            self.stripe(&buffer);
+            self.buffered = 0;
+        }
+        let mut stripes = bytes.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.stripe(stripe);
+        }
+        let rest = stripes.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// The hash of the input so far.
+    pub fn digest(&self) -> u64 {
+        let mut hash = if self.total >= 32 {
+            let [a1, a2, a3, a4] = self.accumulators;
+            let hash = a1.rotate_left(1)
+                .wrapping_add(a2.rotate_left(7))
+                .wrapping_add(a3.rotate_left(12))
+                .wrapping_add(a4.rotate_left(18));
+            self.accumulators.into_iter().fold(hash, Self::merge_round)
+        } else {
+            self.seed.wrapping_add(XXH_PRIME64_5)
+        };
+        hash = hash.wrapping_add(self.total);
+
+        let mut rest = &self.buffer[..self.buffered];
+        while rest.len() >= 8 {
+            hash ^= Self::round(0, u64::from_le_bytes(rest[..8].try_into().expect("8 bytes")));
+            hash = hash.rotate_left(27).wrapping_mul(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_4);
+            rest = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            hash ^= (u32::from_le_bytes(rest[..4].try_into().expect("4 bytes")) as u64).wrapping_mul(XXH_PRIME64_1);
+            hash = hash.rotate_left(23).wrapping_mul(XXH_PRIME64_2).wrapping_add(XXH_PRIME64_3);
+            rest = &rest[4..];
+        }
+        for &byte in rest {
+            hash ^= (byte as u64).wrapping_mul(XXH_PRIME64_5);
+            hash = hash.rotate_left(11).wrapping_mul(XXH_PRIME64_1);
+        }
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(XXH_PRIME64_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(XXH_PRIME64_3);
+        hash ^ (hash >> 32)
+    }
+}
+
+/// SHA-256 round constants
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Computes the SHA-256 digest of a byte sequence.
+pub fn sha256(bytes: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+
+    // Pad with 0x80, zeros, and the bit length so the message fills whole 64-byte blocks
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((bytes.len() as u64).wrapping_mul(8)).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut schedule = [0u32; 64];
+        for (word, chunk) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes(chunk.try_into().expect("4 bytes"));
+        }
+        for i in 16..64 {
+            let s0 = schedule[i - 15].rotate_right(7) ^ schedule[i - 15].rotate_right(18) ^ (schedule[i - 15] >> 3);
+            let s1 = schedule[i - 2].rotate_right(17) ^ schedule[i - 2].rotate_right(19) ^ (schedule[i - 2] >> 10);
+            schedule[i] = schedule[i - 16].wrapping_add(s0).wrapping_add(schedule[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let temp1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(SHA256_K[i]).wrapping_add(schedule[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(majority);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+        for (word, added) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(added);
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(state) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// Computes HMAC-SHA256 (RFC 2104) of `bytes` keyed with `key`.
+pub fn hmac_sha256(key: &[u8], bytes: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..32].copy_from_slice(&sha256(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner: Vec<u8> = block_key.iter().map(|byte| byte ^ 0x36).collect();
+    inner.extend_from_slice(bytes);
+    let mut outer: Vec<u8> = block_key.iter().map(|byte| byte ^ 0x5c).collect();
+    outer.extend_from_slice(&sha256(&inner));
+    sha256(&outer)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,326 @@
+//! # Row Previews (`preview-row` subcommand)
+//!
+//! Shows the first and last K bytes of a row without reading the whole row into memory.
+//! The rows worth previewing are usually the pathological ones (a missing closing quote
+//! can merge millions of records into one multi-hundred-MB line), so the row is streamed
+//! from its byte offset and only the two K-byte ends are kept.
+//!
+//! ```bash
+//! $ csv_row_analyzer preview-row data.csv 1048576
+//! $ csv_row_analyzer preview-row data.csv 1048576 --bytes 500
+//! ```
+//!
+//! The reports use the same streaming preview for the largest rows.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::cli::{parse_command_line, PREVIEW_ROW_COMMAND};
+use crate::compression::open_input;
+
+/// Bytes kept from each end of a row when `--bytes` is not given
+const DEFAULT_PREVIEW_BYTES: usize = 200;
+
+/// The two ends of one row
+#[derive(Debug, Clone)]
+pub struct RowPreview {
+    /// 0-based row index
+    pub row_index: usize,
+    /// Byte offset where the row starts
+    pub byte_offset: u64,
+    /// Length of the row in bytes, without its terminator
+    pub byte_length: u64,
+    /// First bytes of the row
+    pub head: Vec<u8>,
+    /// Last bytes of the row (not overlapping `head`)
+    pub tail: Vec<u8>,
+}
+
+impl RowPreview {
+    /// Whether bytes between the head and the tail were left out.
+    pub fn is_truncated(&self) -> bool {
+        self.byte_length > (self.head.len() + self.tail.len()) as u64
+    }
+
+    /// The head as text, ending at a character boundary, with control characters escaped.
+    pub fn head_text(&self) -> String {
+        let end = match std::str::from_utf8(&self.head) {
+            Err(e) if e.error_len().is_none() && self.is_truncated() => e.valid_up_to(),
+            _ => self.head.len(),
+        };
+        display_text(&self.head[..end])
+    }
+
+    /// The tail as text, starting at a character boundary, with control characters escaped.
+    pub fn tail_text(&self) -> String {
+        let start = if self.is_truncated() {
+            self.tail.iter().take(3).take_while(|&&b| b & 0xC0 == 0x80).count()
+        } else {
+            0
+        };
+        display_text(&self.tail[start..])
+    }
+
+    /// One-line preview: the whole row when it is short, otherwise `head … tail`.
+    pub fn summary(&self) -> String {
+        if self.is_truncated() {
+            format!("{} … {}", self.head_text(), self.tail_text())
+        } else {
+            format!("{}{}", self.head_text(), self.tail_text())
+        }
+    }
+}
+
+/// Reads one row from the reader's current position, keeping only `max_bytes` from each end.
+///
+/// The reader is left positioned after the row's terminator.
+///
+/// # Arguments
+///
+/// * `reader` - Reader positioned at the start of the row
+/// * `row_index` - Index of the row, recorded in the preview
+/// * `byte_offset` - Offset of the row, recorded in the preview
+/// * `max_bytes` - Bytes to keep from each end
+///
+/// # Returns
+///
+/// * `Result<(RowPreview, u64), io::Error>` - The preview and the number of bytes consumed
+///   (terminator included)
+pub fn stream_row_preview<R: BufRead>(
+    reader: &mut R,
+    row_index: usize,
+    byte_offset: u64,
+    max_bytes: usize,
+) -> Result<(RowPreview, u64), io::Error> {
+    let mut head = Vec::with_capacity(max_bytes.min(1 << 16));
+    let mut tail: VecDeque<u8> = VecDeque::with_capacity(max_bytes.min(1 << 16));
+    let mut row_bytes: u64 = 0;
+    let mut consumed: u64 = 0;
+
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            break;
+        }
+        let (content, found_newline) = match buffer.iter().position(|&b| b == b'\n') {
+            Some(position) => (&buffer[..position], true),
+            None => (buffer, false),
+        };
+
+        let to_head = content.len().min(max_bytes - head.len());
+        head.extend_from_slice(&content[..to_head]);
+        let rest = &content[to_head..];
+        if rest.len() >= max_bytes {
+            tail.clear();
+            tail.extend(&rest[rest.len() - max_bytes..]);
+        } else {
+            let overflow = (tail.len() + rest.len()).saturating_sub(max_bytes);
+            tail.drain(..overflow);
+            tail.extend(rest);
+        }
+
+        row_bytes += content.len() as u64;
+        let used = content.len() + usize::from(found_newline);
+        reader.consume(used);
+        consumed += used as u64;
+        if found_newline {
+            break;
+        }
+    }
+
+    // A CRLF terminator leaves its \r as the last byte of the content
+    let ends_in_cr = tail.back().or(head.last()) == Some(&b'\r');
+    if ends_in_cr && consumed > row_bytes {
+        if tail.pop_back().is_none() {
+            head.pop();
+        }
+        row_bytes -= 1;
+    }
+
+    let preview = RowPreview { row_index, byte_offset, byte_length: row_bytes, head, tail: tail.into() };
+    Ok((preview, consumed))
+}
+
+/// Skips forward by `count` bytes without keeping them.
+fn skip_bytes<R: BufRead>(reader: &mut R, mut count: u64) -> Result<(), io::Error> {
+    while count > 0 {
+        let available = reader.fill_buf()?.len();
+        if available == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "offset is past the end of the input"));
+        }
+        let take = available.min(count as usize);
+        reader.consume(take);
+        count -= take as u64;
+    }
+    Ok(())
+}
+
+/// Previews rows whose byte offsets are known (for example from the analysis pass).
+///
+/// Plain files are read with a seek per row. Gzip input cannot seek, so it is read once
+/// from the start, skipping to each row in offset order.
+///
+/// # Arguments
+///
+/// * `input_file_path` - File the offsets refer to (decompressed offsets for gzip input)
+/// * `rows` - (row index, byte offset) of each row to preview
+/// * `max_bytes` - Bytes to keep from each end of a row
+///
+/// # Returns
+///
+/// * `Result<Vec<RowPreview>, io::Error>` - Previews in the order of `rows`
+pub fn preview_rows_at(
+    input_file_path: impl AsRef<Path>,
+    rows: &[(usize, u64)],
+    max_bytes: usize,
+) -> Result<Vec<RowPreview>, io::Error> {
+    let (mut reader, gzip_members) = open_input(&input_file_path)?;
+
+    if gzip_members.is_none() {
+        let mut file = BufReader::new(File::open(&input_file_path)?);
+        let mut previews = Vec::with_capacity(rows.len());
+        for &(row_index, byte_offset) in rows {
+            file.seek(SeekFrom::Start(byte_offset))?;
+            previews.push(stream_row_preview(&mut file, row_index, byte_offset, max_bytes)?.0);
+        }
+        return Ok(previews);
+    }
+
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    order.sort_by_key(|&i| rows[i].1);
+    let mut previews: Vec<Option<RowPreview>> = vec![None; rows.len()];
+    let mut previous: Option<RowPreview> = None;
+    let mut position: u64 = 0;
+    for i in order {
+        let (row_index, byte_offset) = rows[i];
+        if let Some(preview) = previous.as_ref().filter(|preview| preview.byte_offset == byte_offset) {
+            previews[i] = Some(preview.clone());
+            continue;
+        }
+        skip_bytes(&mut reader, byte_offset - position)?;
+        let (preview, consumed) = stream_row_preview(&mut reader, row_index, byte_offset, max_bytes)?;
+        position = byte_offset + consumed;
+        previews[i] = Some(preview.clone());
+        previous = Some(preview);
+    }
+    Ok(previews.into_iter().flatten().collect())
+}
+
+/// Previews a row by its index, counting newlines to find it without reading rows into memory.
+///
+/// # Arguments
+///
+/// * `input_file_path` - File to read (gzip input is decompressed)
+/// * `row_index` - 0-based index of the row
+/// * `max_bytes` - Bytes to keep from each end of the row
+///
+/// # Returns
+///
+/// * `Result<RowPreview, io::Error>` - The preview, or an error when the file has fewer rows
+pub fn preview_row(input_file_path: impl AsRef<Path>, row_index: usize, max_bytes: usize) -> Result<RowPreview, io::Error> {
+    let (mut reader, _) = open_input(input_file_path)?;
+    let mut rows_skipped = 0;
+    let mut byte_offset: u64 = 0;
+
+    while rows_skipped < row_index {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("row {} is past the end of the file ({} rows)", row_index, rows_skipped),
+            ));
+        }
+        let used = match buffer.iter().position(|&b| b == b'\n') {
+            Some(position) => {
+                rows_skipped += 1;
+                position + 1
+            },
+            None => buffer.len(),
+        };
+        reader.consume(used);
+        byte_offset += used as u64;
+    }
+
+    if reader.fill_buf()?.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("row {} is past the end of the file ({} rows)", row_index, rows_skipped),
+        ));
+    }
+    Ok(stream_row_preview(&mut reader, row_index, byte_offset, max_bytes)?.0)
+}
+
+/// Options for the `preview-row` subcommand
+#[derive(Debug, Clone)]
+pub struct PreviewOptions {
+    pub input_path: String,
+    pub row_index: usize,
+    pub max_bytes: usize,
+}
+
+/// Parses the arguments that follow `preview-row`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<PreviewOptions, String>` - Parsed options or an error message
+pub fn parse_preview_arguments(args: &[String]) -> Result<PreviewOptions, String> {
+    let parsed = parse_command_line(&PREVIEW_ROW_COMMAND, args)?;
+    let mut max_bytes = DEFAULT_PREVIEW_BYTES;
+
+    for (flag, value) in &parsed.flags {
+        if *flag == "--bytes" {
+            max_bytes = value.parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| format!("invalid --bytes value '{}'", value))?;
+        }
+    }
+
+    match parsed.positionals.as_slice() {
+        [input_path, row_index] => Ok(PreviewOptions {
+            input_path: input_path.clone(),
+            row_index: row_index.parse().map_err(|_| format!("invalid row index '{}'", row_index))?,
+            max_bytes,
+        }),
+        _ => Err("Expected an input file path and a row index".to_string()),
+    }
+}
+
+/// Runs the `preview-row` subcommand and prints the preview.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Ok(()) on success, or an Error if the row cannot be read
+pub fn run_preview_row(options: &PreviewOptions) -> Result<(), io::Error> {
+    let preview = preview_row(&options.input_path, options.row_index, options.max_bytes)?;
+
+    println!("Row {} of {} starts at byte offset {} and is {} bytes long",
+             preview.row_index, options.input_path, preview.byte_offset, preview.byte_length);
+    if preview.is_truncated() {
+        println!("First {} bytes:", preview.head.len());
+        println!("{}", preview.head_text());
+        println!("Last {} bytes:", preview.tail.len());
+        println!("{}", preview.tail_text());
+    } else {
+        println!("{}", preview.summary());
+    }
+    Ok(())
+}
+
+/// Decodes bytes for display, escaping control characters so a preview stays on one line.
+fn display_text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .chars()
+        .map(|c| if c.is_control() { c.escape_default().to_string() } else { c.to_string() })
+        .collect()
+}
@@ -0,0 +1,220 @@
+//! # Sample Rows in the Markdown Report (`--sample-rows <n>`)
+//!
+//! Lengths and row indices say where the unusual rows are, not what a row of the file
+//! looks like. With `--sample-rows <n>`, the markdown outliers report gets a Sample Rows
+//! section with up to n typical rows (the lengths nearest the median) and up to n rows of
+//! each outlier tier, read back from the input after the statistics are computed:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --sample-rows 3
+//! ```
+//!
+//! The tiers are relative to the upper threshold: up to twice the threshold, two to ten
+//! times, and over ten times. The rows of a tier are spread over its lengths, from the
+//! shortest to the longest.
+//!
+//! Rows are redacted before they are written: every letter becomes `x` (`X` when
+//! uppercase) and every digit `9`, while delimiters, quotes, spaces, and punctuation are
+//! kept, so the shape of a row shows without its values. The header row is shown as it
+//! is. Each row is cut to its first 160 characters.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use crate::input_range::open_line_reader;
+use crate::logical_records::RecordAssembler;
+use crate::read_buffer::ReadBufferSize;
+use crate::read_retry::ReadRetries;
+
+/// Characters of a row shown in the report
+const SAMPLE_ROW_CHARS: usize = 160;
+
+/// One group of sample rows (typical rows, or one outlier tier)
+#[derive(Debug, Clone)]
+pub struct SampleGroup {
+    /// Heading of the group, e.g. "Outliers up to 2× the threshold (120–240 chars)"
+    pub label: String,
+    /// (row index, character length) of every sampled row, in file order
+    pub rows: Vec<(usize, usize)>,
+    /// Redacted, truncated text of each row, in the order of `rows`
+    pub texts: Vec<String>,
+}
+
+/// Sample rows of a file, ready for the markdown report
+#[derive(Debug, Clone)]
+pub struct RowSamples {
+    /// The header row as it is (truncated), if the file has one
+    pub header: Option<String>,
+    pub groups: Vec<SampleGroup>,
+}
+
+/// Outlier tiers as (label, lowest multiple of the threshold, highest multiple)
+const OUTLIER_TIERS: [(&str, f64, f64); 3] = [
+    ("Outliers up to 2× the threshold", 1.0, 2.0),
+    ("Outliers 2–10× the threshold", 2.0, 10.0),
+    ("Outliers over 10× the threshold", 10.0, f64::INFINITY),
+];
+
+/// Picks the rows to sample: up to `count` rows nearest the median length, and up to
+/// `count` rows of each outlier tier that has rows.
+///
+/// # Arguments
+///
+/// * `row_indices_map` - Row indices per row length (possibly capped)
+/// * `median` - Median row length
+/// * `outlier_threshold_upper` - Rows longer than this are outliers
+/// * `count` - Rows per group
+///
+/// # Returns
+///
+/// * `Vec<SampleGroup>` - The groups with their rows picked and no text yet
+pub fn pick_sample_rows(
+    row_indices_map: &HashMap<usize, Vec<usize>>,
+    median: usize,
+    outlier_threshold_upper: f64,
+    count: usize,
+) -> Vec<SampleGroup> {
+    // Every data row the index map knows of, by length and then file order
+    let mut candidates: Vec<(usize, usize)> = row_indices_map.iter()
+        .flat_map(|(&length, indices)| indices.iter().filter(|&&row_index| row_index > 0).map(move |&row_index| (row_index, length)))
+        .collect();
+    candidates.sort_by_key(|&(row_index, length)| (length, row_index));
+
+    let mut groups = Vec::new();
+    let mut typical: Vec<(usize, usize)> = candidates.iter()
+        .filter(|&&(_, length)| length as f64 <= outlier_threshold_upper)
+        .copied()
+        .collect();
+    typical.sort_by_key(|&(row_index, length)| (length.abs_diff(median), row_index));
+    typical.truncate(count);
+    if !typical.is_empty() {
+        groups.push(new_group(format!("Typical rows (near the median of {} chars)", median), typical));
+    }
+
+    for (label, low, high) in OUTLIER_TIERS {
+        let tier: Vec<(usize, usize)> = candidates.iter()
+            .filter(|&&(_, length)| {
+                let length = length as f64;
+                length > outlier_threshold_upper * low && length <= outlier_threshold_upper * high
+            })
+            .copied()
+            .collect();
+        if tier.is_empty() {
+            continue;
+        }
+        let (shortest, longest) = (tier[0].1, tier[tier.len() - 1].1);
+        groups.push(new_group(format!("{} ({}–{} chars)", label, shortest, longest), spread(&tier, count)));
+    }
+    groups
+}
+
+fn new_group(label: String, mut rows: Vec<(usize, usize)>) -> SampleGroup {
+    rows.sort_unstable();
+    SampleGroup { label, rows, texts: Vec::new() }
+}
+
+/// Up to `count` entries evenly spaced over `sorted`, first and last included.
+fn spread(sorted: &[(usize, usize)], count: usize) -> Vec<(usize, usize)> {
+    if sorted.len() <= count {
+        return sorted.to_vec();
+    }
+    if count == 1 {
+        return vec![sorted[sorted.len() / 2]];
+    }
+    (0..count).map(|i| sorted[i * (sorted.len() - 1) / (count - 1)]).collect()
+}
+
+/// Reads the picked rows and the header from the input, redacting the rows.
+///
+/// The input is read once, stopping after the last picked row.
+///
+/// # Arguments
+///
+/// * `input_file_path` - The analyzed file (gzip input is decompressed)
+/// * `groups` - Groups returned by [`pick_sample_rows`]
+/// * `csv_mode` - Whether rows are records joined across quoted line breaks
+///
+/// # Returns
+///
+/// * `Result<RowSamples, io::Error>` - The samples, or an Error if the input cannot be read
+pub fn read_sample_rows(input_file_path: impl AsRef<Path>, mut groups: Vec<SampleGroup>, csv_mode: bool) -> Result<RowSamples, io::Error> {
+    let (lines, _) = open_line_reader(&input_file_path, None, false, ReadBufferSize::Adaptive, &ReadRetries::default())?;
+    let mut assembler = csv_mode.then(|| RecordAssembler::new(','));
+    let last_row = groups.iter().flat_map(|group| &group.rows).map(|&(row_index, _)| row_index).max().unwrap_or(0);
+    let mut header = None;
+    let mut texts: HashMap<usize, String> = HashMap::new();
+
+    let mut keep_row = |row_index: usize, content: io::Result<String>| {
+        let Ok(content) = content else {
+            return;
+        };
+        if row_index == 0 {
+            header = Some(truncate(&escape_controls(&content)));
+        } else if groups.iter().any(|group| group.rows.iter().any(|&(row, _)| row == row_index)) {
+            texts.insert(row_index, truncate(&redact(&content)));
+        }
+    };
+
+    for physical_line in lines {
+        let row = match assembler.as_mut() {
+            Some(assembler) => assembler.push(physical_line, 0).map(|record| (record.line.row_index, record.line.content)),
+            None => Some((physical_line.row_index, physical_line.content)),
+        };
+        if let Some((row_index, content)) = row {
+            keep_row(row_index, content);
+            if row_index >= last_row {
+                break;
+            }
+        }
+    }
+    if let Some(record) = assembler.as_mut().and_then(|assembler| assembler.finish()) {
+        keep_row(record.line.row_index, record.line.content);
+    }
+
+    // A row that could not be read is left out of its group
+    for group in &mut groups {
+        group.rows.retain(|(row_index, _)| texts.contains_key(row_index));
+        group.texts = group.rows.iter().map(|(row_index, _)| texts[row_index].clone()).collect();
+    }
+    groups.retain(|group| !group.rows.is_empty());
+    Ok(RowSamples { header, groups })
+}
+
+/// Masks letters and digits, keeping the delimiters, quotes, and punctuation.
+pub fn redact(text: &str) -> String {
+    escape_controls(&text.chars()
+        .map(|c| match c {
+            c if c.is_uppercase() => 'X',
+            c if c.is_alphabetic() => 'x',
+            c if c.is_numeric() => '9',
+            c => c,
+        })
+        .collect::<String>())
+}
+
+/// Escapes control characters, so a record with line breaks stays on one line.
+pub fn escape_controls(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_control() { c.escape_default().to_string() } else { c.to_string() })
+        .collect()
+}
+
+/// Cuts a row to its first `SAMPLE_ROW_CHARS` characters, noting how many were left out.
+fn truncate(text: &str) -> String {
+    let total = text.chars().count();
+    if total <= SAMPLE_ROW_CHARS {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(SAMPLE_ROW_CHARS).collect();
+    format!("{} … (+{} chars)", kept, total - SAMPLE_ROW_CHARS)
+}
+
+/// A code fence longer than any run of backticks in the lines, so no row can close it.
+pub fn code_fence<'a>(lines: impl IntoIterator<Item = &'a str>) -> String {
+    let longest_run = lines.into_iter()
+        .flat_map(|line| line.split(|c| c != '`').map(str::len))
+        .max()
+        .unwrap_or(0);
+    "`".repeat((longest_run + 1).max(3))
+}
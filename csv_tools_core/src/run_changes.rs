@@ -0,0 +1,193 @@
+//! # Changes Since the Last Run
+//!
+//! When a history file is configured (`--history`, or `[history] path`), each run is
+//! compared with the latest earlier run of the same basename in that file. The outlier
+//! reports then get a "Changes Since Last Run" section:
+//!
+//! * Rows - change of `total_rows`, in percent
+//! * p99 - change of the 99th-percentile row length, in percent
+//! * New outlier lengths - outlier row lengths that the previous run did not have
+//!
+//! A change beyond its tolerance is flagged as significant and printed as a warning.
+//! Tolerances are set in the config file:
+//!
+//! ```text
+//! [changes]
+//! rows_pct = 10             # flag a row count change of more than ±10%
+//! p99_pct = 20              # flag a p99 change of more than ±20%
+//! new_outlier_lengths = 0   # flag more than 0 new outlier lengths
+//! ```
+//!
+//! Each history line keeps at most [`MAX_RECORDED_OUTLIER_LENGTHS`] outlier lengths (the
+//! largest), so after a run with more distinct outlier lengths than that, a smaller length
+//! can show as new although the previous run had it below the cut.
+//!
+//! History lines written before `p99_length` and `outlier_lengths` existed (report schema
+//! version 1) only support the row comparison; the other metrics show as not available.
+
+use crate::config::AnalyzerConfig;
+use crate::history::HistoryRecord;
+use crate::messages::{Language, Message};
+
+/// Outlier lengths kept per history line, largest first
+pub const MAX_RECORDED_OUTLIER_LENGTHS: usize = 50;
+
+/// Limits beyond which a change since the last run is flagged
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeTolerances {
+    /// Allowed row count change, in percent either way
+    pub rows_pct: f64,
+    /// Allowed p99 row length change, in percent either way
+    pub p99_pct: f64,
+    /// Allowed number of outlier lengths the previous run did not have
+    pub new_outlier_lengths: usize,
+}
+
+impl Default for ChangeTolerances {
+    fn default() -> Self {
+        ChangeTolerances { rows_pct: 10.0, p99_pct: 20.0, new_outlier_lengths: 0 }
+    }
+}
+
+/// Reads the `[changes]` tolerances from the config file, keeping the defaults for keys
+/// that are not set.
+///
+/// # Arguments
+///
+/// * `config` - Parsed config file, if any
+///
+/// # Returns
+///
+/// * `Result<ChangeTolerances, String>` - The tolerances, or an error naming the bad line
+pub fn load_change_tolerances(config: Option<&AnalyzerConfig>) -> Result<ChangeTolerances, String> {
+    let mut tolerances = ChangeTolerances::default();
+    let Some(config) = config else {
+        return Ok(tolerances);
+    };
+    for entry in config.section_entries("changes") {
+        let invalid = || format!("line {}: invalid value '{}' for {}", entry.line_number, entry.value, entry.key);
+        match entry.key.as_str() {
+            "rows_pct" => tolerances.rows_pct = entry.value.parse().ok().filter(|v: &f64| *v >= 0.0).ok_or_else(invalid)?,
+            "p99_pct" => tolerances.p99_pct = entry.value.parse().ok().filter(|v: &f64| *v >= 0.0).ok_or_else(invalid)?,
+            "new_outlier_lengths" => tolerances.new_outlier_lengths = entry.value.parse().map_err(|_| invalid())?,
+            key => return Err(format!("line {}: unknown [changes] key '{}'", entry.line_number, key)),
+        }
+    }
+    Ok(tolerances)
+}
+
+/// Change of one metric between the previous and the current run
+#[derive(Debug, Clone)]
+pub struct MetricChange {
+    pub name: &'static str,
+    /// Value in the previous run, when it was recorded
+    pub previous: Option<f64>,
+    pub current: f64,
+    /// Tolerance in percent
+    pub tolerance_pct: f64,
+}
+
+impl MetricChange {
+    /// Change in percent of the previous value (None when there is nothing to compare).
+    pub fn change_pct(&self) -> Option<f64> {
+        match self.previous {
+            Some(previous) if previous != 0.0 => Some((self.current - previous) / previous * 100.0),
+            Some(_) if self.current == 0.0 => Some(0.0),
+            _ => None,
+        }
+    }
+
+    /// Whether the change is beyond the tolerance (growth from zero always is).
+    pub fn is_significant(&self) -> bool {
+        match (self.previous, self.change_pct()) {
+            (_, Some(change)) => change.abs() > self.tolerance_pct,
+            (Some(_), None) => true,
+            (None, None) => false,
+        }
+    }
+
+    /// Describes the change, e.g. `1200 -> 1500 (+25.0%)`.
+    pub fn describe(&self) -> String {
+        match (self.previous, self.change_pct()) {
+            (Some(previous), Some(change)) => format!("{} -> {} ({:+.1}%)", previous, self.current, change),
+            (Some(previous), None) => format!("{} -> {} (new)", previous, self.current),
+            (None, _) => format!("{} (previous run did not record it)", self.current),
+        }
+    }
+}
+
+/// Comparison of a run with the previous run of the same file
+#[derive(Debug, Clone)]
+pub struct RunChanges {
+    /// `run_timestamp` of the previous run
+    pub previous_timestamp: String,
+    /// Row count and p99 changes
+    pub metrics: Vec<MetricChange>,
+    /// Outlier lengths the previous run did not have, largest first (None when the
+    /// previous run did not record its outlier lengths)
+    pub new_outlier_lengths: Option<Vec<usize>>,
+    pub new_outlier_tolerance: usize,
+}
+
+impl RunChanges {
+    /// Lists the significant changes, one sentence each in `language`.
+    pub fn significant_changes(&self, language: Language) -> Vec<String> {
+        let mut changes: Vec<String> = self.metrics.iter()
+            .filter(|metric| metric.is_significant())
+            .map(|metric| Message::MetricChanged.text(language, &[&metric.name, &metric.tolerance_pct, &metric.describe()]))
+            .collect();
+        if let Some(lengths) = &self.new_outlier_lengths
+            && lengths.len() > self.new_outlier_tolerance
+        {
+            changes.push(Message::NewOutlierLengths.text(language, &[&lengths.len(), &format_lengths(lengths).replace(';', ", ")]));
+        }
+        changes
+    }
+}
+
+/// Formats a list of outlier lengths for a history field (`;`-separated).
+pub fn format_lengths(lengths: &[usize]) -> String {
+    lengths.iter().map(|length| length.to_string()).collect::<Vec<_>>().join(";")
+}
+
+/// Compares the current run summary with the previous one.
+///
+/// # Arguments
+///
+/// * `previous` - History record of the previous run of the file
+/// * `current` - History record of this run
+/// * `tolerances` - Limits for flagging a change
+pub fn compare_runs(previous: &HistoryRecord, current: &HistoryRecord, tolerances: &ChangeTolerances) -> RunChanges {
+    let number = |record: &HistoryRecord, column: &str| record.value(column).parse::<f64>().ok();
+    let metric = |name, column: &str, tolerance_pct| MetricChange {
+        name,
+        previous: number(previous, column),
+        current: number(current, column).unwrap_or(0.0),
+        tolerance_pct,
+    };
+
+    let lengths = |record: &HistoryRecord| -> Option<Vec<usize>> {
+        let field = record.value("outlier_lengths");
+        // Version 1 lines have no such column; an empty field in a newer line means no outliers
+        if field.is_empty() && record.value("p99_length").is_empty() {
+            return None;
+        }
+        Some(field.split(';').filter_map(|length| length.parse().ok()).collect())
+    };
+    let new_outlier_lengths = match (lengths(previous), lengths(current)) {
+        (Some(previous), Some(current)) => Some(current.into_iter()
+            .filter(|length| !previous.contains(length))
+            .collect()),
+        _ => None,
+    };
+
+    RunChanges {
+        previous_timestamp: previous.value("run_timestamp").to_string(),
+        metrics: vec![
+            metric("Rows", "total_rows", tolerances.rows_pct),
+            metric("p99 row length", "p99_length", tolerances.p99_pct),
+        ],
+        new_outlier_lengths,
+        new_outlier_tolerance: tolerances.new_outlier_lengths,
+    }
+}
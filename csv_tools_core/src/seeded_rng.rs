@@ -0,0 +1,123 @@
+//! # Seeded Random Numbers (`--seed`)
+//!
+//! Every random pick of the analyzer draws from [`SeededRng`], so a run can be repeated
+//! exactly:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --verify-sample 20 --seed 42
+//! ```
+//!
+//! Without `--seed`, the seed is the run timestamp (the number in the report file names),
+//! so a run can still be repeated after the fact with `--seed <timestamp>`.
+//!
+//! The picks must not depend on how many threads did the work, so the serial and parallel
+//! engines give the same reports. The generator therefore has no running state to share
+//! or to advance in some order: the number for an item is derived from the seed and the
+//! item's index alone (its row index, say), whichever thread asks and whenever it asks.
+//! Each feature draws from its own stream, split off the run's generator with
+//! [`SeededRng::split`], so adding a sampling feature does not change the picks of
+//! another one under the same seed. [`Reservoir`] keeps a uniform sample by giving every
+//! item such a number as its priority, so the sample is the same in whatever order, and
+//! on however many threads, the items are offered.
+//!
+//! The numbers come from the SplitMix64 output function: small, fast, and plenty for
+//! picking rows, but not meant for anything security-related.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Offset of the item indices before mixing (the 64-bit golden ratio)
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Deterministic source of pseudo-random numbers, keyed by item index
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    seed: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { seed }
+    }
+
+    /// Independent generator for one stream, such as one sampling feature. It depends only
+    /// on this generator's seed and `stream`.
+    pub fn split(&self, stream: u64) -> SeededRng {
+        SeededRng::new(self.value(stream))
+    }
+
+    /// Pseudo-random number of the item with the given index, the same on every thread.
+    pub fn value(&self, index: u64) -> u64 {
+        mix(self.seed ^ mix(index.wrapping_add(GOLDEN_GAMMA)))
+    }
+}
+
+/// SplitMix64 output function
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Uniform sample of up to `capacity` items, independent of the order the items are
+/// offered in and of how they are spread over threads.
+#[derive(Debug, Clone)]
+pub struct Reservoir<T> {
+    rng: SeededRng,
+    capacity: usize,
+    /// The kept items; the top is the one to drop first
+    items: BinaryHeap<Entry<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<T> {
+    priority: u64,
+    index: u64,
+    item: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.priority, self.index) == (other.priority, other.index)
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority, self.index).cmp(&(other.priority, other.index))
+    }
+}
+
+impl<T> Reservoir<T> {
+    /// Creates an empty reservoir whose priorities come from `rng`.
+    pub fn new(rng: &SeededRng, capacity: usize) -> Self {
+        Reservoir { rng: rng.clone(), capacity, items: BinaryHeap::with_capacity(capacity + 1) }
+    }
+
+    /// Offers the item with the given index (unique within the sample, e.g. its row index).
+    pub fn offer(&mut self, index: u64, item: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.items.push(Entry { priority: self.rng.value(index), index, item });
+        if self.items.len() > self.capacity {
+            self.items.pop();
+        }
+    }
+
+    /// The sampled items, ordered by index.
+    pub fn into_sorted(self) -> Vec<T> {
+        let mut entries = self.items.into_vec();
+        entries.sort_by_key(|entry| entry.index);
+        entries.into_iter().map(|entry| entry.item).collect()
+    }
+}
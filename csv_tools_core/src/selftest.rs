@@ -0,0 +1,214 @@
+//! # Self-Test (`selftest` subcommand)
+//!
+//! Runs the analyzer on a built-in corpus of tricky inputs and checks invariants of the
+//! reports, so an operator can confirm that a deployed binary behaves correctly on their
+//! platform (filesystem, temp directory, line handling) before trusting its reports:
+//!
+//! ```bash
+//! $ csv_row_analyzer selftest
+//! ok    quoted_newlines      a quoted value spanning two lines
+//! ok    byte_order_mark      UTF-8 BOM before the header
+//! ...
+//! 7 of 7 cases passed
+//! $ csv_row_analyzer selftest --case huge_line
+//! ```
+//!
+//! Every case goes through the same code path as a real run (see the `test_support`
+//! module), with its reports written to and read back from the system temp directory;
+//! the report status of each run is printed above its result line.
+//! The exit code is 0 when every case passes and [`EXIT_SELFTEST_FAILED`] otherwise.
+
+use crate::cli::{parse_command_line, SELFTEST_COMMAND};
+use crate::test_support::{analyze_fixture, GoldenReports};
+
+/// Exit code when at least one self-test case fails
+pub const EXIT_SELFTEST_FAILED: i32 = 4;
+
+/// Length of the `huge_line` fixture, well past any read buffer
+const HUGE_LINE_CHARS: usize = 8 * 1024 * 1024;
+
+/// One fixture and the invariants its reports must satisfy
+struct SelftestCase {
+    name: &'static str,
+    description: &'static str,
+    content: fn() -> Vec<u8>,
+    args: &'static [&'static str],
+    check: fn(&GoldenReports) -> Result<(), String>,
+}
+
+/// Names of the built-in cases, in the order they run
+pub const SELFTEST_CASE_NAMES: &[&str] = &[
+    "quoted_newlines",
+    "byte_order_mark",
+    "crlf",
+    "crlf_with_line_endings",
+    "invalid_utf8",
+    "huge_line",
+    "empty_file",
+];
+
+const CASES: &[SelftestCase] = &[
+    SelftestCase {
+        name: "quoted_newlines",
+        description: "a quoted value spanning two lines",
+        content: || b"id,note\n1,\"first\nsecond\"\n2,plain\n".to_vec(),
+        args: &["--profile-columns"],
+        check: |reports| {
+            expect_row_lengths(reports, &[(0, 7), (1, 8), (2, 7), (3, 7)])?;
+            expect_contains(report(reports, "_md_outliers_report_")?, "1 of 3 records span multiple lines")?;
+            expect_contains(report(reports, "_column_format_report_")?, "\n1,note,")
+        },
+    },
+    SelftestCase {
+        name: "byte_order_mark",
+        description: "UTF-8 BOM before the header",
+        content: || b"\xef\xbb\xbfid,name\n1,ab\n2,cde\n".to_vec(),
+        args: &["--profile-columns"],
+        check: |reports| {
+            // The BOM is one character of the header row and nothing else
+            expect_row_lengths(reports, &[(0, 8), (1, 4), (2, 5)])?;
+            expect_contains(report(reports, "_column_format_report_")?, "\n0,id,")
+        },
+    },
+    SelftestCase {
+        name: "crlf",
+        description: "CRLF line endings, not counted by default",
+        content: || b"id,name\r\n1,ab\r\n2,cde\r\n".to_vec(),
+        args: &[],
+        check: |reports| {
+            expect_row_lengths(reports, &[(0, 7), (1, 4), (2, 5)])?;
+            expect_contains(report(reports, "_md_outliers_report_")?, "CRLF")
+        },
+    },
+    SelftestCase {
+        name: "crlf_with_line_endings",
+        description: "CRLF line endings counted with --include-line-endings",
+        content: || b"id,name\r\n1,ab\r\n2,cde\n".to_vec(),
+        args: &["--include-line-endings"],
+        check: |reports| expect_row_lengths(reports, &[(0, 9), (1, 6), (2, 6)]),
+    },
+    SelftestCase {
+        name: "invalid_utf8",
+        description: "a row that is not valid UTF-8 between valid ones",
+        content: || b"id,name\n1,ab\n2,\xff\xfe\n3,cde\n".to_vec(),
+        args: &[],
+        check: |reports| {
+            expect_row_lengths(reports, &[(0, 7), (1, 4), (3, 5)])?;
+            expect_contains(report(reports, "_char_counts_report_")?, "\n2,3,1,error_reading_line,")
+        },
+    },
+    SelftestCase {
+        name: "huge_line",
+        description: "one 8 MiB row without a final newline",
+        content: || {
+            let mut content = b"id,blob\n1,".to_vec();
+            content.resize(content.len() + HUGE_LINE_CHARS, b'x');
+            content
+        },
+        args: &[],
+        check: |reports| expect_row_lengths(reports, &[(0, 7), (1, HUGE_LINE_CHARS + 2)]),
+    },
+    SelftestCase {
+        name: "empty_file",
+        description: "a file without any rows",
+        content: Vec::new,
+        args: &[],
+        check: |reports| expect_row_lengths(reports, &[]),
+    },
+];
+
+/// Options of the `selftest` subcommand
+#[derive(Debug, Clone, Default)]
+pub struct SelftestOptions {
+    /// Run only this case (`--case`)
+    pub case: Option<String>,
+}
+
+/// Parses the arguments that follow `selftest`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<SelftestOptions, String>` - Parsed options or an error message
+pub fn parse_selftest_arguments(args: &[String]) -> Result<SelftestOptions, String> {
+    let parsed = parse_command_line(&SELFTEST_COMMAND, args)?;
+    let mut options = SelftestOptions::default();
+    for (flag, value) in &parsed.flags {
+        if *flag == "--case" {
+            if !SELFTEST_CASE_NAMES.contains(&value.as_str()) {
+                return Err(format!("unknown case '{}' (use {})", value, SELFTEST_CASE_NAMES.join(", ")));
+            }
+            options.case = Some(value.clone());
+        }
+    }
+    Ok(options)
+}
+
+/// Runs the `selftest` subcommand, printing one line per case.
+///
+/// # Returns
+///
+/// * `bool` - Whether every case that ran passed
+pub fn run_selftest(options: &SelftestOptions) -> bool {
+    let cases: Vec<&SelftestCase> = CASES.iter()
+        .filter(|case| options.case.as_deref().is_none_or(|name| name == case.name))
+        .collect();
+    let mut passed = 0;
+    for case in &cases {
+        let outcome = analyze_fixture(&format!("selftest_{}.csv", case.name), &(case.content)(), case.args)
+            .and_then(|reports| (case.check)(&reports));
+        match outcome {
+            Ok(()) => {
+                passed += 1;
+                println!("ok    {:<24} {}", case.name, case.description);
+            },
+            Err(reason) => println!("FAIL  {:<24} {}: {}", case.name, case.description, reason),
+        }
+    }
+    println!("{} of {} cases passed", passed, cases.len());
+    passed == cases.len()
+}
+
+/// Finds the one report whose name contains `marker`.
+fn report<'a>(reports: &'a GoldenReports, marker: &str) -> Result<&'a str, String> {
+    reports.names().into_iter()
+        .find(|name| name.contains(marker))
+        .and_then(|name| reports.get(name))
+        .ok_or_else(|| format!("no report containing '{}'", marker))
+}
+
+fn expect_contains(text: &str, expected: &str) -> Result<(), String> {
+    if text.contains(expected) {
+        Ok(())
+    } else {
+        Err(format!("expected '{}' in the report", expected.trim()))
+    }
+}
+
+/// Checks the readable rows of the char_counts report, and that the value_counts
+/// report accounts for exactly those rows.
+fn expect_row_lengths(reports: &GoldenReports, expected: &[(usize, usize)]) -> Result<(), String> {
+    let rows: Vec<(usize, usize)> = report(reports, "_char_counts_report_")?
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split(',');
+            Some((fields.next()?.parse().ok()?, fields.nth(2)?.parse().ok()?))
+        })
+        .collect();
+    if rows != expected {
+        return Err(format!("row lengths {:?}, expected {:?}", rows, expected));
+    }
+    let counted: u64 = report(reports, "_value_counts_report_")?
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split(',').nth(1)?.parse::<u64>().ok())
+        .sum();
+    if counted != expected.len() as u64 {
+        return Err(format!("value_counts covers {} rows, expected {}", counted, expected.len()));
+    }
+    Ok(())
+}
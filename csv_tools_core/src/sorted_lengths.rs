@@ -0,0 +1,79 @@
+//! # Quantiles of Sorted Lengths
+//!
+//! The reports take their quantiles from either the sorted row lengths of a run or a
+//! length histogram (the merged tallies of `--parallel` and `--lengths-only`, a directory
+//! aggregate, a saved state). Both are read through [`SortedLengths`], so a quantile is
+//! the same whichever form the lengths come in:
+//!
+//! * The median and quartiles are the length at `n/2`, `n/4`, and `3n/4` of the sorted
+//!   lengths, averaging the two middle lengths when the position falls between them.
+//! * Every other percentile (the 99th of the reports, the 95th of the suggested chunk
+//!   size, `query --percentile`) is the nearest rank: the shortest length that at least
+//!   p% of the lengths are at or below.
+
+/// Lengths in ascending order, listed one by one or as a histogram
+#[derive(Debug, Clone, Copy)]
+pub enum SortedLengths<'a> {
+    /// Every length, in ascending order
+    Values(&'a [usize]),
+    /// (length, count) pairs in ascending order of length
+    Counts(&'a [(usize, u64)]),
+}
+
+impl SortedLengths<'_> {
+    /// Number of lengths.
+    pub fn count(&self) -> u64 {
+        match self {
+            SortedLengths::Values(values) => values.len() as u64,
+            SortedLengths::Counts(counts) => counts.iter().map(|&(_, count)| count).sum(),
+        }
+    }
+
+    /// Length at a 0-based position of the sorted lengths; the longest one past the end.
+    pub fn at(&self, position: u64) -> usize {
+        match self {
+            SortedLengths::Values(values) => {
+                values.get(position as usize).or(values.last()).copied().unwrap_or(0)
+            },
+            SortedLengths::Counts(counts) => {
+                let mut seen = 0;
+                for &(length, count) in counts.iter() {
+                    seen += count;
+                    if position < seen {
+                        return length;
+                    }
+                }
+                counts.last().map_or(0, |&(length, _)| length)
+            },
+        }
+    }
+
+    /// Quantile at `numerator`/4 of the lengths (1 for Q1, 2 for the median, 3 for Q3),
+    /// or 0 without lengths.
+    pub fn quartile(&self, numerator: u64) -> usize {
+        let position = numerator * self.count();
+        let index = position / 4;
+        if position == 0 {
+            0
+        } else if position.is_multiple_of(4) {
+            (self.at(index - 1) + self.at(index)) / 2
+        } else {
+            self.at(index)
+        }
+    }
+
+    /// Nearest-rank percentile, or None without lengths.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - Percentile between 0 (exclusive) and 100
+    pub fn percentile(&self, percentile: f64) -> Option<usize> {
+        let total = self.count();
+        if total == 0 {
+            return None;
+        }
+        // Multiplying first keeps whole-number ranks exact, e.g. 99% of 100 is rank 99
+        let rank = ((percentile * total as f64 / 100.0).ceil() as u64).clamp(1, total);
+        Some(self.at(rank - 1))
+    }
+}
@@ -0,0 +1,135 @@
+//! # Splitting by Row Length (`split` subcommand)
+//!
+//! Splits a file into one CSV per row-length range, each starting with the header, so the
+//! typical rows can be loaded as they are while the few huge ones go to a separate,
+//! slower path (or a human):
+//!
+//! ```bash
+//! $ csv_row_analyzer split orders.csv parts --lengths 1KB,100KB
+//! $ ls parts/
+//! orders_rows_under_1KB.csv  orders_rows_1KB_100KB.csv  orders_rows_over_100KB.csv
+//! ```
+//!
+//! Bounds use the same size suffixes as `--length-buckets`; a row belongs to the range
+//! that starts at or below its length in characters, and rows shorter than the smallest
+//! bound go to the `under` file, so every row of the input lands in exactly one file.
+//! Every range gets a file, even an empty one with only the header. Rows keep their
+//! content and end in `\n`. With `--csv-mode`, a row is a whole record, line breaks
+//! inside quoted fields included. Rows that are not valid UTF-8 are left out and counted
+//! on stderr.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{parse_command_line, SPLIT_COMMAND};
+use crate::count_mode::count_chars;
+use crate::extract_rows::visit_rows;
+use crate::length_buckets::{parse_bucket_bounds, size_label};
+
+/// Options of the `split` subcommand
+#[derive(Debug, Clone)]
+pub struct SplitOptions {
+    pub input_path: String,
+    pub output_dir: String,
+    /// Ascending bounds between the length ranges (`--lengths`)
+    pub bounds: Vec<usize>,
+    /// Rows are records joined across quoted line breaks (`--csv-mode`)
+    pub csv_mode: bool,
+}
+
+/// Names the file of every length range, from the shortest rows to the longest.
+///
+/// # Arguments
+///
+/// * `basename` - Basename of the input
+/// * `bounds` - Ascending bounds
+pub fn split_file_names(basename: &str, bounds: &[usize]) -> Vec<String> {
+    let mut names = Vec::with_capacity(bounds.len() + 1);
+    if let Some(&smallest) = bounds.first() {
+        names.push(format!("{}_rows_under_{}.csv", basename, size_label(smallest)));
+    }
+    names.extend(bounds.windows(2).map(|pair| format!("{}_rows_{}_{}.csv", basename, size_label(pair[0]), size_label(pair[1]))));
+    if let Some(&largest) = bounds.last() {
+        names.push(format!("{}_rows_over_{}.csv", basename, size_label(largest)));
+    }
+    names
+}
+
+/// Parses the arguments that follow `split`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<SplitOptions, String>` - Parsed options or an error message
+pub fn parse_split_arguments(args: &[String]) -> Result<SplitOptions, String> {
+    let parsed = parse_command_line(&SPLIT_COMMAND, args)?;
+    let mut bounds = None;
+    let mut csv_mode = false;
+
+    for (flag, value) in &parsed.flags {
+        match *flag {
+            "--lengths" => bounds = Some(parse_bucket_bounds(value)?),
+            "--csv-mode" => csv_mode = true,
+            _ => {},
+        }
+    }
+
+    let bounds = bounds.ok_or("Give the bounds between the length ranges with --lengths (e.g., 1KB,100KB)")?;
+    match parsed.positionals.as_slice() {
+        [input_path, output_dir] => Ok(SplitOptions { input_path: input_path.clone(), output_dir: output_dir.clone(), bounds, csv_mode }),
+        _ => Err("Expected an input file path and an output directory".to_string()),
+    }
+}
+
+/// Runs the `split` subcommand and prints the rows written to each file.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+/// * `basename` - Basename of the input, which starts the file names
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Ok(()) on success, or an Error if the input cannot be read
+///   or a file cannot be written
+pub fn run_split(options: &SplitOptions, basename: &str) -> Result<(), io::Error> {
+    let output_dir = Path::new(&options.output_dir);
+    fs::create_dir_all(output_dir)?;
+    let paths: Vec<PathBuf> = split_file_names(basename, &options.bounds)
+        .into_iter()
+        .map(|name| output_dir.join(name))
+        .collect();
+    let mut files = paths.iter()
+        .map(|path| File::create(path).map(BufWriter::new))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut rows = vec![0u64; files.len()];
+
+    let unreadable = visit_rows(&options.input_path, None, options.csv_mode, |row_index, content| {
+        if row_index == 0 {
+            for file in files.iter_mut() {
+                writeln!(file, "{}", content)?;
+            }
+        } else {
+            let length = count_chars(content);
+            let range = options.bounds.partition_point(|&bound| bound <= length);
+            writeln!(files[range], "{}", content)?;
+            rows[range] += 1;
+        }
+        Ok(true)
+    })?;
+    for file in files.iter_mut() {
+        file.flush()?;
+    }
+
+    for (path, rows) in paths.iter().zip(rows) {
+        println!("{}: {} rows", path.display(), rows);
+    }
+    if unreadable > 0 {
+        eprintln!("Warning: {} rows of {} are not valid UTF-8 and were left out", unreadable, options.input_path);
+    }
+    Ok(())
+}
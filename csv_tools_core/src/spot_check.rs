@@ -0,0 +1,187 @@
+//! # Spot-Check Verification (`--verify-sample <n>`)
+//!
+//! An end-to-end integrity check for audited runs: after the statistics are computed, `n`
+//! outlier rows picked at random are read again straight from their recorded byte offsets,
+//! independently of the pass that measured them, and measured again:
+//!
+//! ```bash
+//! $ csv_row_analyzer orders.csv reports --verify-sample 20
+//! $ cat reports/orders_spot_check_report_1767225600.csv
+//! row_index,file_row,byte_offset,byte_length,recorded_length,measured_length,still_flagged,verified,report_schema_version
+//! 4181,4182,2097152,20480,20478,20478,true,true,5
+//! ```
+//!
+//! A row is verified when its measured length equals the recorded one and it is still above
+//! the upper outlier threshold. Every mismatch is also printed as a warning. A row that is
+//! no longer valid UTF-8, or that lies past the end of the input, is not verified.
+//!
+//! The pick is seeded with `--seed`, or else the run timestamp, so a run with the same seed
+//! checks the same rows every time, with or without `--parallel` (see the `seeded_rng`
+//! module). Remembering where every row starts costs 16 bytes per row, so the
+//! offsets are only kept with `--verify-sample`.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::compression::open_input;
+use crate::count_mode::CountMode;
+use crate::db_sink::OutlierRow;
+use crate::messages::{Language, Message};
+use crate::report_schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_COLUMN};
+use crate::seeded_rng::{Reservoir, SeededRng};
+
+/// Stream of the run's generator the pick draws from
+const SPOT_CHECK_STREAM: u64 = 1;
+
+/// Byte offset and byte length (terminator included) of every row of a pass
+#[derive(Debug, Clone, Default)]
+pub struct RowOffsets {
+    first_row: usize,
+    spans: Vec<(u64, u64)>,
+}
+
+impl RowOffsets {
+    /// Records the next row; rows arrive in ascending, contiguous order.
+    pub fn push(&mut self, row_index: usize, byte_offset: u64, byte_length: u64) {
+        if self.spans.is_empty() {
+            self.first_row = row_index;
+        }
+        self.spans.push((byte_offset, byte_length));
+    }
+
+    /// (byte offset, byte length) of a row, if it was read.
+    pub fn get(&self, row_index: usize) -> Option<(u64, u64)> {
+        self.spans.get(row_index.checked_sub(self.first_row)?).copied()
+    }
+}
+
+/// Outcome of re-reading one sampled row
+#[derive(Debug, Clone)]
+pub struct SpotCheck {
+    pub outlier: OutlierRow,
+    pub byte_offset: u64,
+    pub byte_length: u64,
+    /// Length measured from the re-read bytes, or None if they could not be measured
+    pub measured_length: Option<usize>,
+    pub still_flagged: bool,
+}
+
+impl SpotCheck {
+    /// Whether the re-read row matches what the pass recorded.
+    pub fn verified(&self) -> bool {
+        self.measured_length == Some(self.outlier.character_length) && self.still_flagged
+    }
+
+    /// Warning line for a row that did not verify.
+    pub fn describe_mismatch(&self, language: Language) -> String {
+        let OutlierRow { row_index, file_row, character_length } = &self.outlier;
+        match self.measured_length {
+            Some(length) if length != *character_length => Message::SpotCheckLengthDiffers.text(
+                language, &[row_index, file_row, &length, &self.byte_offset, character_length],
+            ),
+            Some(length) => Message::SpotCheckNoLongerOutlier.text(language, &[row_index, file_row, &length]),
+            None => Message::SpotCheckUnreadable.text(language, &[row_index, file_row, &self.byte_offset]),
+        }
+    }
+}
+
+/// Picks up to `count` of the outlier rows at random, in file order.
+///
+/// # Arguments
+///
+/// * `outliers` - Every outlier row of the analysis
+/// * `count` - Rows to pick
+/// * `rng` - The run's generator
+pub fn sample_outliers(outliers: &[OutlierRow], count: usize, rng: &SeededRng) -> Vec<OutlierRow> {
+    let mut sample = Reservoir::new(&rng.split(SPOT_CHECK_STREAM), count);
+    for row in outliers {
+        sample.offer(row.row_index as u64, *row);
+    }
+    sample.into_sorted()
+}
+
+/// Re-reads the sampled rows from their byte offsets and measures them again.
+///
+/// Plain files are read with a seek per row; gzip input is decompressed once from the
+/// start, skipping to each row.
+///
+/// # Arguments
+///
+/// * `input_file_path` - The analyzed file
+/// * `sample` - Rows to check, in file order
+/// * `offsets` - Byte spans recorded during the pass
+/// * `include_line_endings` - Whether the recorded lengths count the terminator
+/// * `count_mode` - Unit of the recorded lengths
+/// * `outlier_threshold_upper` - Rows must still be longer than this
+///
+/// # Returns
+///
+/// * `Result<Vec<SpotCheck>, io::Error>` - One outcome per row, or an Error if the input cannot be opened
+pub fn verify_sample(
+    input_file_path: impl AsRef<Path>,
+    sample: &[OutlierRow],
+    offsets: &RowOffsets,
+    include_line_endings: bool,
+    count_mode: CountMode,
+    outlier_threshold_upper: f64,
+) -> Result<Vec<SpotCheck>, io::Error> {
+    let (mut decoded, gzip_members) = open_input(&input_file_path)?;
+    let mut file = gzip_members.is_none().then(|| File::open(&input_file_path)).transpose()?.map(BufReader::new);
+    let mut position: u64 = 0;
+    let mut checks = Vec::with_capacity(sample.len());
+    for outlier in sample {
+        let Some((byte_offset, byte_length)) = offsets.get(outlier.row_index) else {
+            continue;
+        };
+        let bytes = match file.as_mut() {
+            Some(file) => {
+                file.seek(SeekFrom::Start(byte_offset))?;
+                read_span(file, byte_length)
+            },
+            None => {
+                io::copy(&mut decoded.by_ref().take(byte_offset - position), &mut io::sink())?;
+                position = byte_offset + byte_length;
+                read_span(&mut decoded, byte_length)
+            },
+        };
+        let measured_length = bytes.and_then(|bytes| measure_row(&bytes, include_line_endings, count_mode));
+        checks.push(SpotCheck {
+            outlier: *outlier,
+            byte_offset,
+            byte_length,
+            still_flagged: measured_length.is_some_and(|length| length as f64 > outlier_threshold_upper),
+            measured_length,
+        });
+    }
+    Ok(checks)
+}
+
+/// Writes the outcome of every checked row.
+pub fn write_spot_check_report(report_path: impl AsRef<Path>, checks: &[SpotCheck]) -> Result<(), io::Error> {
+    let mut report_file = BufWriter::new(File::create(report_path)?);
+    writeln!(report_file, "row_index,file_row,byte_offset,byte_length,recorded_length,measured_length,still_flagged,verified,{}", SCHEMA_VERSION_COLUMN)?;
+    for check in checks {
+        writeln!(report_file, "{},{},{},{},{},{},{},{},{}",
+                 check.outlier.row_index, check.outlier.file_row, check.byte_offset, check.byte_length,
+                 check.outlier.character_length,
+                 check.measured_length.map_or(String::new(), |length| length.to_string()),
+                 check.still_flagged, check.verified(), REPORT_SCHEMA_VERSION)?;
+    }
+    report_file.flush()
+}
+
+/// Reads exactly `byte_length` bytes, or None if the input ends first.
+fn read_span(reader: &mut impl BufRead, byte_length: u64) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(byte_length).read_to_end(&mut bytes).ok()?;
+    (bytes.len() as u64 == byte_length).then_some(bytes)
+}
+
+/// Length of a row's bytes, measured as the pass does.
+fn measure_row(bytes: &[u8], include_line_endings: bool, count_mode: CountMode) -> Option<usize> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let content = text.strip_suffix("\r\n").or_else(|| text.strip_suffix('\n')).unwrap_or(text);
+    let terminator = text.len() - content.len();
+    Some(count_mode.count(content) + if include_line_endings { terminator } else { 0 })
+}
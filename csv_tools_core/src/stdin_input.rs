@@ -0,0 +1,53 @@
+//! # Standard Input (`-`)
+//!
+//! An input path of `-` reads the data from standard input, with the same streaming
+//! reader as a file (gzip is still detected by its magic bytes), so an export can be
+//! analyzed without first landing on disk:
+//!
+//! ```bash
+//! $ cat huge.csv | csv_row_analyzer - --stdout-summary
+//! $ zcat feed.csv.gz | csv_row_analyzer - reports
+//! ```
+//!
+//! The reports are named after `stdin` (`stdin_md_outliers_report_[timestamp].md`).
+//! Standard input can only be read once, so the largest rows are not previewed and the
+//! flags that read the input a second time (`--rows`, `--bytes`, `--extract-outliers`,
+//! `--verify-sample`, `--sample-rows`) or open it themselves (`--lengths-only`,
+//! `--async`, `--input-kind char-report`) are refused.
+//!
+//! With `--stdout-summary`, the summary of the run is the only thing printed to stdout
+//! (as JSON with `--format json`); progress and report status are left out. Without an
+//! output directory argument nothing is written to disk, as with `--no-reports`.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Input path that stands for standard input
+pub const STDIN_PATH: &str = "-";
+
+/// Basename of the reports of standard input
+pub const STDIN_BASENAME: &str = "stdin";
+
+/// Flags that read the input more than once or open it themselves, refused with `-`
+pub const STDIN_REFUSED_FLAGS: &[&str] = &[
+    "--rows", "--bytes", "--extract-outliers", "--verify-sample", "--sample-rows", "--lengths-only", "--async",
+    "--input-kind",
+];
+
+/// Whether an input path stands for standard input.
+pub fn is_stdin(input_file_path: impl AsRef<Path>) -> bool {
+    input_file_path.as_ref() == Path::new(STDIN_PATH)
+}
+
+/// Opens a file for reading, or standard input for `-`.
+///
+/// # Returns
+///
+/// * `Result<Box<dyn Read + Send>, io::Error>` - The unbuffered source, or an Error if the file cannot be opened
+pub fn open_source(input_file_path: impl AsRef<Path>) -> Result<Box<dyn Read + Send>, io::Error> {
+    if is_stdin(&input_file_path) {
+        return Ok(Box::new(io::stdin()));
+    }
+    Ok(Box::new(File::open(input_file_path)?))
+}
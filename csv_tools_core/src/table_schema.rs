@@ -0,0 +1,125 @@
+//! # Inferred Table Schema (`--table-schema`)
+//!
+//! Writes the column profile as a [Frictionless Table Schema](https://specs.frictionlessdata.io/table-schema/)
+//! descriptor, so the analysis can seed the validation configs of other tools
+//! (frictionless-py, goodtables, CSV Lint) without retyping the columns:
+//!
+//! ```json
+//! {
+//!   "fields": [
+//!     {"name": "id", "type": "integer", "constraints": {"required": true, "minimum": 1, "maximum": 9120}},
+//!     {"name": "zip", "type": "string", "constraints": {"required": true, "minLength": 5, "maxLength": 5, "pattern": "^[0-9]{5}$"}}
+//!   ],
+//!   "missingValues": [""]
+//! }
+//! ```
+//!
+//! Types are inferred from every value of the column: `integer`, `number`, `boolean`,
+//! `date` and `datetime` (ISO 8601) when all non-empty values parse as that type, `any`
+//! for a column with no values, and `string` otherwise. Digit columns that must keep
+//! their text (leading zeros, codes longer than an integer) are `string` with a digit
+//! `pattern`, as the `--profile-columns` load recommendations suggest.
+//!
+//! Constraints are the ranges observed in this file, so they describe the data rather
+//! than a contract: widen them before validating future deliveries against the schema.
+//! A column is `required` when no record left it empty or short.
+//!
+//! Null tokens (`--null-tokens`, `[null_tokens]` in the config) are listed in the
+//! descriptor's `missingValues`; a column with tokens of its own gets a field-level
+//! `missingValues` list.
+//!
+//! Columns excluded with `--exclude-columns-regex` are not described, so a schema of a
+//! file with excluded columns lists fewer fields than the file has.
+
+use crate::column_profile::{ColumnProfile, ColumnProfiler};
+use crate::json::{json_number, json_string};
+
+/// Builds the Table Schema descriptor for the profiled columns.
+///
+/// # Arguments
+///
+/// * `profiler` - Column profiles of the completed pass
+///
+/// # Returns
+///
+/// * `String` - The descriptor as pretty-printed JSON
+pub fn build_table_schema(profiler: &ColumnProfiler) -> String {
+    let fields: Vec<String> = profiler.columns()
+        .into_iter()
+        .map(|(index, name, profile)| {
+            let null_tokens = profiler.column_null_tokens(index);
+            let missing_values = (null_tokens != profiler.null_tokens()).then(|| missing_values(null_tokens));
+            field_descriptor(&name, profile, profiler.data_records(), missing_values)
+        })
+        .collect();
+    format!(
+        "{{\n  \"fields\": [\n{}\n  ],\n  \"missingValues\": {}\n}}\n",
+        fields.join(",\n"),
+        missing_values(profiler.null_tokens())
+    )
+}
+
+/// Formats a `missingValues` list: the empty string followed by the null tokens.
+fn missing_values(null_tokens: &[String]) -> String {
+    let values: Vec<String> = std::iter::once("")
+        .chain(null_tokens.iter().map(String::as_str))
+        .map(json_string)
+        .collect();
+    format!("[{}]", values.join(", "))
+}
+
+/// Builds the descriptor of one field as a single JSON line.
+fn field_descriptor(name: &str, profile: &ColumnProfile, data_records: u64, missing_values: Option<String>) -> String {
+    let field_type = profile.inferred_type();
+    let mut constraints = Vec::new();
+    if data_records > 0 && profile.non_empty_values == data_records {
+        constraints.push("\"required\": true".to_string());
+    }
+
+    match field_type {
+        "integer" | "number" => {
+            if let Some((min, max)) = profile.numeric_range {
+                constraints.push(format!("\"minimum\": {}", json_number(min)));
+                constraints.push(format!("\"maximum\": {}", json_number(max)));
+            }
+        },
+        "date" | "datetime" => {
+            if let Some((min, max)) = &profile.temporal_range {
+                constraints.push(format!("\"minimum\": {}", json_string(min)));
+                constraints.push(format!("\"maximum\": {}", json_string(max)));
+            }
+        },
+        "string" => {
+            if let Some((min, max)) = profile.length_range {
+                constraints.push(format!("\"minLength\": {}", min));
+                constraints.push(format!("\"maxLength\": {}", max));
+            }
+            if let Some(pattern) = digit_pattern(profile) {
+                constraints.push(format!("\"pattern\": {}", json_string(&pattern)));
+            }
+        },
+        _ => {},
+    }
+
+    let mut descriptor = format!("    {{\"name\": {}, \"type\": {}", json_string(name), json_string(field_type));
+    if !constraints.is_empty() {
+        descriptor.push_str(&format!(", \"constraints\": {{{}}}", constraints.join(", ")));
+    }
+    if let Some(missing_values) = missing_values {
+        descriptor.push_str(&format!(", \"missingValues\": {}", missing_values));
+    }
+    descriptor.push('}');
+    descriptor
+}
+
+/// Pattern for a column whose values are all digits: a fixed width when every value
+/// has the same number of digits, otherwise any run of digits.
+fn digit_pattern(profile: &ColumnProfile) -> Option<String> {
+    if profile.non_empty_values == 0 || profile.digit_only_values != profile.non_empty_values {
+        return None;
+    }
+    match profile.digit_width_range {
+        Some((min, max)) if min == max => Some(format!("^[0-9]{{{}}}$", min)),
+        _ => Some("^[0-9]+$".to_string()),
+    }
+}
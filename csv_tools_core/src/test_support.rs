@@ -0,0 +1,285 @@
+//! # Golden Report Tests
+//!
+//! Runs the analyzer on a fixture held in memory and returns the reports as normalized
+//! strings, so crates that embed the analyzer can pin the report content produced by
+//! their own flags and config files:
+//!
+//! ```no_run
+//! use csv_tools_core::test_support::analyze_fixture;
+//!
+//! let reports = analyze_fixture("orders.csv", b"id,total\n1,9.50\n2,12.00\n", &["--column-totals"])
+//!     .expect("analysis runs");
+//! let totals = reports.get("orders_column_totals_report_<timestamp>.csv").expect("totals report");
+//! assert!(totals.contains("1,total,number,2,0,21.50,true"));
+//! reports.compare_with_golden("tests/golden/orders").expect("reports match the golden files");
+//! ```
+//!
+//! The arguments are the command-line flags of a single-file run (`--directory` and the
+//! positional paths are supplied by the harness). The fixture is written to a scratch
+//! directory under the system temp directory, analyzed with the same code path as the
+//! binary, and the scratch directory is removed once the reports are read.
+//!
+//! The run reads its time from a fixed clock at [`FIXTURE_TIME`], so times written into
+//! the reports (such as the "Report generated" line, in the `--timezone` of the run) are
+//! the same on every run; [`analyze_fixture_with_clock`] picks another clock. Normalization
+//! then makes the rest repeatable:
+//!
+//! * The run timestamp is replaced with [`TIMESTAMP_PLACEHOLDER`], in report names and
+//!   content
+//! * The scratch directory is replaced with [`FIXTURE_DIR_PLACEHOLDER`]
+//!
+//! Only the reports in the output directory are returned; files that flags write
+//! elsewhere (`--history`, `--badge-dir`, `--flag-stream`) are left where the flags put
+//! them. Set [`UPDATE_GOLDEN_VARIABLE`] to rewrite the golden files from the current
+//! reports instead of comparing against them.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::clock::Clock;
+use crate::csv_row_analyzer::{analyze_file, parse_arguments, InputSource};
+use crate::report_status::ReportWriteError;
+use crate::truncation::TruncatedInputError;
+
+/// Unix time of the fixed clock of [`analyze_fixture`] (2026-01-01 00:00:00 UTC)
+pub const FIXTURE_TIME: u64 = 1_767_225_600;
+/// Replaces the run timestamp in report names and content
+pub const TIMESTAMP_PLACEHOLDER: &str = "<timestamp>";
+/// Replaces the scratch directory holding the fixture and its reports
+pub const FIXTURE_DIR_PLACEHOLDER: &str = "<fixture_dir>";
+/// Environment variable that makes [`GoldenReports::compare_with_golden`] rewrite the
+/// golden files
+pub const UPDATE_GOLDEN_VARIABLE: &str = "UPDATE_GOLDEN_REPORTS";
+
+/// Scratch directories created by this process, so parallel tests do not share one
+static SCRATCH_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Normalized reports of one analysis, keyed by normalized report name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GoldenReports {
+    reports: BTreeMap<String, String>,
+}
+
+impl GoldenReports {
+    /// Normalized report names, in sorted order. Reports in a subdirectory (such as the
+    /// `--length-buckets` files) are named by their relative path with `/`.
+    pub fn names(&self) -> Vec<&str> {
+        self.reports.keys().map(String::as_str).collect()
+    }
+
+    /// Content of a report by its normalized name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.reports.get(name).map(String::as_str)
+    }
+
+    /// Compares every report with the file of the same name in `golden_dir`.
+    ///
+    /// With [`UPDATE_GOLDEN_VARIABLE`] set, the golden files are written from the reports
+    /// instead.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Ok when all reports match, or a description of each
+    ///   missing, extra, or differing report with its first differing line
+    pub fn compare_with_golden(&self, golden_dir: impl AsRef<Path>) -> Result<(), String> {
+        let golden_dir = golden_dir.as_ref();
+        if env::var_os(UPDATE_GOLDEN_VARIABLE).is_some() {
+            return self.write_golden(golden_dir)
+                .map_err(|e| format!("could not write golden files to {}: {}", golden_dir.display(), e));
+        }
+
+        let mut golden = GoldenReports::default();
+        read_reports(golden_dir, golden_dir, &mut golden.reports)
+            .map_err(|e| format!("could not read golden files from {}: {}", golden_dir.display(), e))?;
+
+        let mut problems = Vec::new();
+        for (name, content) in &self.reports {
+            match golden.get(name) {
+                None => problems.push(format!("{}: no golden file", name)),
+                Some(expected) if expected != content => {
+                    problems.push(format!("{}: {}", name, first_difference(expected, content)));
+                },
+                Some(_) => {},
+            }
+        }
+        for name in golden.reports.keys().filter(|name| !self.reports.contains_key(*name)) {
+            problems.push(format!("{}: golden file has no report", name));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "reports differ from {} (set {}=1 to update):\n  {}",
+                golden_dir.display(), UPDATE_GOLDEN_VARIABLE, problems.join("\n  ")
+            ))
+        }
+    }
+
+    fn write_golden(&self, golden_dir: &Path) -> io::Result<()> {
+        if golden_dir.exists() {
+            fs::remove_dir_all(golden_dir)?;
+        }
+        for (name, content) in &self.reports {
+            let path = golden_dir.join(name);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+/// Analyzes an in-memory fixture and returns its normalized reports.
+///
+/// # Arguments
+///
+/// * `file_name` - Name the fixture is saved under; its basename names the reports
+/// * `content` - The fixture's bytes
+/// * `args` - Command-line flags for the run, such as `["--profile-columns"]`
+///
+/// # Returns
+///
+/// * `Result<GoldenReports, String>` - The reports, or a description of why the analysis
+///   or a report failed. A truncated fixture is not an error: its reports are complete.
+pub fn analyze_fixture(file_name: &str, content: &[u8], args: &[&str]) -> Result<GoldenReports, String> {
+    analyze_fixture_with_clock(file_name, content, args, Clock::Fixed(FIXTURE_TIME))
+}
+
+/// Analyzes an in-memory fixture with the run's time read from `clock`, and returns its
+/// normalized reports.
+///
+/// # Arguments
+///
+/// * `file_name` - Name the fixture is saved under; its basename names the reports
+/// * `content` - The fixture's bytes
+/// * `args` - Command-line flags for the run, such as `["--profile-columns"]`
+/// * `clock` - Source of the run's time, e.g. `Clock::Fixed(unix_seconds)`
+///
+/// # Returns
+///
+/// * `Result<GoldenReports, String>` - The reports, or a description of why the analysis
+///   or a report failed
+pub fn analyze_fixture_with_clock(file_name: &str, content: &[u8], args: &[&str], clock: Clock) -> Result<GoldenReports, String> {
+    let scratch = env::temp_dir().join(format!(
+        "csv_row_analyzer_fixture_{}_{}",
+        std::process::id(),
+        SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&scratch);
+    let result = run_fixture(&scratch, file_name, content, args, clock);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+/// Creates an empty scratch directory under the system temp directory, for tests that
+/// write their own files (config files, inputs read through the library or the binary).
+///
+/// # Arguments
+///
+/// * `name` - Name of the directory, unique among the tests of one test binary; the
+///   process id keeps test binaries running at the same time apart
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("csv_tools_test_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("create scratch dir");
+    dir
+}
+
+fn run_fixture(scratch: &Path, file_name: &str, content: &[u8], args: &[&str], clock: Clock) -> Result<GoldenReports, String> {
+    fs::create_dir_all(scratch).map_err(|e| format!("could not create {}: {}", scratch.display(), e))?;
+    let input = scratch.join(file_name);
+    let output = scratch.join("reports");
+    fs::write(&input, content).map_err(|e| format!("could not write fixture {}: {}", input.display(), e))?;
+
+    let mut command_line = vec![
+        "csv_row_analyzer".to_string(),
+        input.to_string_lossy().into_owned(),
+        output.to_string_lossy().into_owned(),
+    ];
+    command_line.extend(args.iter().map(|arg| arg.to_string()));
+    let (input_source, _, mut options) = parse_arguments(&command_line)?;
+    if !matches!(input_source, InputSource::SingleFile(_)) {
+        return Err("--directory and --glob cannot be used with a fixture".to_string());
+    }
+    options.apply_config_file()?;
+    options.clock = clock;
+
+    match analyze_file(&input, &output, &options) {
+        Ok(_) => {},
+        Err(e) if ReportWriteError::is_report_write_error(&e) => return Err(e.to_string()),
+        // A truncated fixture still gets every report
+        Err(e) if TruncatedInputError::is_truncated_input_error(&e) => {},
+        Err(e) => return Err(format!("analysis failed: {}", e)),
+    }
+
+    let mut reports = BTreeMap::new();
+    read_reports(&output, &output, &mut reports).map_err(|e| format!("could not read reports: {}", e))?;
+    Ok(GoldenReports { reports: normalize(reports, scratch) })
+}
+
+/// Reads every file below `dir` into `reports`, keyed by its path relative to `root`.
+fn read_reports(root: &Path, dir: &Path, reports: &mut BTreeMap<String, String>) -> io::Result<()> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            read_reports(root, &path, reports)?;
+        } else {
+            let name = path.strip_prefix(root).unwrap_or(&path)
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            reports.insert(name, String::from_utf8_lossy(&fs::read(&path)?).into_owned());
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the run timestamp and the scratch directory in names and content.
+fn normalize(reports: BTreeMap<String, String>, scratch: &Path) -> BTreeMap<String, String> {
+    // The streamed row report is written by every run except `--input-kind char-report`,
+    // and its name ends with the timestamp; otherwise any report name ends with it
+    let timestamp = reports.keys()
+        .find_map(|name| name.split_once("_char_counts_report_"))
+        .and_then(|(_, rest)| rest.strip_suffix(".csv"))
+        .or_else(|| reports.keys().find_map(|name| {
+            let (_, rest) = name.rsplit_once("_report_")?;
+            let (timestamp, _) = rest.split_once('.')?;
+            (!timestamp.is_empty() && timestamp.bytes().all(|b| b.is_ascii_digit())).then_some(timestamp)
+        }))
+        .map(str::to_string);
+    let scratch = scratch.to_string_lossy().into_owned();
+    let replace = |text: &str| {
+        let text = text.replace(&scratch, FIXTURE_DIR_PLACEHOLDER);
+        match &timestamp {
+            Some(timestamp) => text.replace(timestamp.as_str(), TIMESTAMP_PLACEHOLDER),
+            None => text,
+        }
+    };
+    reports.into_iter().map(|(name, content)| (replace(&name), replace(&content))).collect()
+}
+
+/// Describes the first line where two reports differ.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line_number = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line_number += 1,
+            (Some(e), Some(a)) => return format!("line {}: expected {:?}, found {:?}", line_number, e, a),
+            (Some(e), None) => return format!("line {}: expected {:?}, found end of report", line_number, e),
+            (None, Some(a)) => return format!("line {}: expected end of report, found {:?}", line_number, a),
+            (None, None) => return "line endings differ".to_string(),
+        }
+    }
+}
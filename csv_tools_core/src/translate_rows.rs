@@ -0,0 +1,179 @@
+//! # Row Number Translation (`translate-rows` subcommand)
+//!
+//! Line-based tools (`sed`, `awk`, database loaders, most error messages) number physical
+//! lines, while the reports of a `--csv-mode` run number records. The char_counts report
+//! of such a run lists where every record starts and how many lines it spans, so it maps
+//! one numbering to the other:
+//!
+//! ```bash
+//! $ csv_row_analyzer notes.csv reports --csv-mode
+//! $ csv_row_analyzer translate-rows reports/notes_char_counts_report_1767225600.csv 5,9
+//! line 5 -> row_index 3 (data_index 2), lines 4-6
+//! line 9 -> row_index 5 (data_index 4), lines 9-9
+//! $ csv_row_analyzer translate-rows reports/notes_char_counts_report_1767225600.csv 3 --from record
+//! row_index 3 (data_index 2) -> lines 4-6
+//! ```
+//!
+//! Lines are 1-based, as `sed`, `awk`, and the `file_row` column count them. Records are
+//! the 0-based `row_index` of the reports; `data_index` is the same record counted from
+//! the first row after the header. Several numbers are separated by commas. Each
+//! translation is one line on stdout; a number outside the report is named on stderr
+//! and the command exits with 1.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+use crate::cli::{parse_command_line, TRANSLATE_ROWS_COMMAND};
+
+/// Which numbering the given numbers are in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowNumbering {
+    /// 1-based physical line numbers
+    Line,
+    /// 0-based record indices (`row_index`)
+    Record,
+}
+
+/// Options of the `translate-rows` subcommand
+#[derive(Debug, Clone)]
+pub struct TranslateOptions {
+    pub report_path: String,
+    pub numbers: Vec<usize>,
+    pub from: RowNumbering,
+}
+
+/// Where one record lies in the file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordSpan {
+    /// 0-based record index, as `row_index` in the reports
+    pub row_index: usize,
+    /// 1-based line the record starts on
+    pub first_line: usize,
+    /// Number of lines the record spans
+    pub physical_lines: usize,
+}
+
+impl RecordSpan {
+    /// 1-based line the record ends on.
+    pub fn last_line(&self) -> usize {
+        self.first_line + self.physical_lines - 1
+    }
+
+    fn describe(&self) -> String {
+        format!("row_index {} (data_index {})", self.row_index, self.row_index as i64 - 1)
+    }
+}
+
+/// Record spans read from a `--csv-mode` char_counts report, in file order
+#[derive(Debug, Clone, Default)]
+pub struct RowMapping {
+    spans: Vec<RecordSpan>,
+}
+
+impl RowMapping {
+    /// Reads the mapping from a char_counts report written with `--csv-mode`.
+    ///
+    /// The columns are found by name, so reports with the `--row-flags` column work too.
+    pub fn from_report(reader: impl BufRead) -> Result<RowMapping, io::Error> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = reader.lines();
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let columns: Vec<&str> = header.split(',').collect();
+        let column = |name: &str| columns.iter().position(|&column| column == name);
+        let (Some(row_index), Some(file_row), Some(physical_lines)) = (column("row_index"), column("file_row"), column("physical_lines")) else {
+            return Err(invalid("not a char_counts report written with --csv-mode (no physical_lines column)".to_string()));
+        };
+
+        let mut spans = Vec::new();
+        for (line_number, line) in lines.enumerate() {
+            let line = line?;
+            let fields: Vec<&str> = line.split(',').collect();
+            let field = |index: usize| fields.get(index).and_then(|value| value.parse::<usize>().ok());
+            match (field(row_index), field(file_row), field(physical_lines)) {
+                (Some(row_index), Some(first_line), Some(physical_lines)) if physical_lines > 0 => {
+                    spans.push(RecordSpan { row_index, first_line, physical_lines });
+                },
+                _ => return Err(invalid(format!("malformed line {} of the report: {}", line_number + 2, line))),
+            }
+        }
+        Ok(RowMapping { spans })
+    }
+
+    /// The record that contains a 1-based line.
+    pub fn record_of_line(&self, line: usize) -> Option<RecordSpan> {
+        let after = self.spans.partition_point(|span| span.first_line <= line);
+        self.spans[..after].last().copied().filter(|span| line <= span.last_line())
+    }
+
+    /// The lines of a record.
+    pub fn lines_of_record(&self, row_index: usize) -> Option<RecordSpan> {
+        self.spans.binary_search_by_key(&row_index, |span| span.row_index).ok().map(|i| self.spans[i])
+    }
+}
+
+/// Parses the arguments that follow `translate-rows`.
+///
+/// # Arguments
+///
+/// * `args` - Arguments after the subcommand name
+///
+/// # Returns
+///
+/// * `Result<TranslateOptions, String>` - Parsed options or an error message
+pub fn parse_translate_arguments(args: &[String]) -> Result<TranslateOptions, String> {
+    let parsed = parse_command_line(&TRANSLATE_ROWS_COMMAND, args)?;
+    let mut from = RowNumbering::Line;
+
+    for (flag, value) in &parsed.flags {
+        if *flag == "--from" {
+            from = match value.as_str() {
+                "line" => RowNumbering::Line,
+                "record" => RowNumbering::Record,
+                _ => return Err(format!("invalid --from value '{}' (use line or record)", value)),
+            };
+        }
+    }
+
+    match parsed.positionals.as_slice() {
+        [report_path, numbers] => Ok(TranslateOptions {
+            report_path: report_path.clone(),
+            numbers: numbers.split(',')
+                .map(|number| number.trim().parse().map_err(|_| format!("invalid row number '{}'", number)))
+                .collect::<Result<_, _>>()?,
+            from,
+        }),
+        _ => Err("Expected a char_counts report and row numbers".to_string()),
+    }
+}
+
+/// Runs the `translate-rows` subcommand and prints one translation per number.
+///
+/// # Arguments
+///
+/// * `options` - Parsed subcommand options
+///
+/// # Returns
+///
+/// * `Result<bool, io::Error>` - true if every number was found in the report, or an
+///   Error if the report cannot be read
+pub fn run_translate_rows(options: &TranslateOptions) -> Result<bool, io::Error> {
+    let mapping = RowMapping::from_report(BufReader::new(File::open(&options.report_path)?))?;
+    let mut all_found = true;
+    for &number in &options.numbers {
+        let translation = match options.from {
+            RowNumbering::Line => mapping.record_of_line(number)
+                .map(|span| format!("line {} -> {}, lines {}-{}", number, span.describe(), span.first_line, span.last_line())),
+            RowNumbering::Record => mapping.lines_of_record(number)
+                .map(|span| format!("{} -> lines {}-{}", span.describe(), span.first_line, span.last_line())),
+        };
+        match translation {
+            Some(translation) => println!("{}", translation),
+            None => {
+                let kind = if options.from == RowNumbering::Line { "line" } else { "row_index" };
+                eprintln!("{} {} is not in {}", kind, number, options.report_path);
+                all_found = false;
+            },
+        }
+    }
+    Ok(all_found)
+}
@@ -0,0 +1,136 @@
+//! # Transposed File Detection
+//!
+//! A delivery written with its rows and columns swapped has one row per column of the
+//! original table and one field per record. It reads as a handful of very long rows, so
+//! the statistics say little more than "every row is an outlier". Three signs of that are
+//! checked on the first [`MAX_TRANSPOSED_ROWS`] rows of every whole-file analysis:
+//!
+//! * Few, very wide rows: at most [`MAX_TRANSPOSED_ROWS`] rows in the file, each with at
+//!   least [`MIN_TRANSPOSED_FIELDS`] fields and [`FIELDS_PER_ROW_FACTOR`] times as many
+//!   fields as there are rows
+//! * Every row has the same number of fields, as a table's records all do
+//! * The first field of every row reads like a column name: it starts with a letter or
+//!   `_`, and no two rows share it
+//!
+//! All three produce a "file appears transposed" warning on stderr and at the top of both
+//! outlier reports, naming the first few would-be column names, so the delivery can be
+//! transposed back before loading. The exit status is not changed. `--rows`/`--bytes`
+//! analyses are not checked, and with `--aggregates-only` the names are left out.
+
+use crate::fields::scan_quotes;
+use crate::messages::{Language, Message};
+
+/// Files with more rows than this are never taken as transposed
+pub const MAX_TRANSPOSED_ROWS: usize = 1000;
+/// Fewest fields per row of a transposed file
+pub const MIN_TRANSPOSED_FIELDS: usize = 100;
+/// A transposed file's rows have at least this many times as many fields as it has rows
+pub const FIELDS_PER_ROW_FACTOR: usize = 10;
+/// Would-be column names shown in the warning
+const LISTED_NAMES: usize = 5;
+
+/// A file that appears transposed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transposed {
+    /// Rows of the file, each likely a column of the original table
+    pub rows: usize,
+    /// Fields of every row, each likely a record of the original table (the first is its name)
+    pub fields: usize,
+    /// First fields of the first rows, likely the original column names
+    pub names: Vec<String>,
+}
+
+impl Transposed {
+    /// Describes the file, e.g. `file appears transposed: 12 rows of 5001 fields each ...`.
+    pub fn describe(&self, language: Language) -> String {
+        let names = if self.names.is_empty() {
+            String::new()
+        } else {
+            let more = if self.rows > self.names.len() { ", ..." } else { "" };
+            format!(" ({}{})", self.names.join(", "), more)
+        };
+        Message::Transposed.text(language, &[&self.rows, &self.fields, &names, &self.rows])
+    }
+
+    /// The same finding without the names, which are file content (`--aggregates-only`).
+    pub fn without_names(self) -> Transposed {
+        Transposed { names: Vec::new(), ..self }
+    }
+}
+
+/// Field count and first field of the first rows, to check the signs at the end of the pass
+#[derive(Debug)]
+pub struct TransposeTracker {
+    /// Field delimiter of the rows
+    delimiter: char,
+    /// (fields, first field) of every non-blank row so far
+    rows: Vec<(usize, String)>,
+    /// Set once the file has more rows than a transposed file would
+    too_many_rows: bool,
+}
+
+impl TransposeTracker {
+    /// Creates a tracker for rows split at `delimiter`.
+    pub fn new(delimiter: char) -> Self {
+        TransposeTracker { delimiter, rows: Vec::new(), too_many_rows: false }
+    }
+
+    /// Notes one readable line, without its terminator.
+    pub fn observe_line(&mut self, line: &str) {
+        if self.too_many_rows || line.trim().is_empty() {
+            return;
+        }
+        if self.rows.len() == MAX_TRANSPOSED_ROWS {
+            self.too_many_rows = true;
+            self.rows = Vec::new();
+            return;
+        }
+        let (delimiters, _) = scan_quotes(line, self.delimiter, false);
+        self.rows.push((delimiters + 1, first_field(line, self.delimiter)));
+    }
+
+    /// Checks the signs once every row was seen.
+    pub fn finish(self) -> Option<Transposed> {
+        let rows = self.rows.len();
+        let fields = self.rows.first()?.0;
+        if self.too_many_rows || rows < 2 || fields < MIN_TRANSPOSED_FIELDS || fields < rows * FIELDS_PER_ROW_FACTOR
+            || self.rows.iter().any(|(row_fields, _)| *row_fields != fields)
+        {
+            return None;
+        }
+
+        let mut names: Vec<&str> = Vec::with_capacity(rows);
+        for (_, name) in &self.rows {
+            let name = name.trim();
+            if !name.starts_with(|c: char| c.is_alphabetic() || c == '_') || names.contains(&name) {
+                return None;
+            }
+            names.push(name);
+        }
+        Some(Transposed {
+            rows,
+            fields,
+            names: names.iter().take(LISTED_NAMES).map(|name| name.to_string()).collect(),
+        })
+    }
+}
+
+/// The first field of a line, unquoted.
+fn first_field(line: &str, delimiter: char) -> String {
+    let line = line.strip_prefix('\u{feff}').unwrap_or(line);
+    let Some(quoted) = line.strip_prefix('"') else {
+        return line.split(delimiter).next().unwrap_or_default().to_string();
+    };
+    let mut field = String::new();
+    let mut chars = quoted.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            if chars.peek() != Some(&'"') {
+                break;
+            }
+            chars.next();
+        }
+        field.push(c);
+    }
+    field
+}
@@ -0,0 +1,186 @@
+//! # Truncated File Detection
+//!
+//! An interrupted download or copy usually cuts a CSV file in the middle of a record. Two
+//! signs of that are checked at the end of every whole-file analysis:
+//!
+//! * The last record ends inside a quoted field (the closing quote never came)
+//! * The last record has fewer fields than the header
+//!
+//! Either one produces a "file appears truncated at row N / byte M" warning on stderr and
+//! at the top of both outlier reports, and exit status [`EXIT_TRUNCATED`] once every report
+//! is written, unless the file also regressed from its `--check-baseline` baseline, which
+//! exits with status 2 instead. Row N is the last row read and byte M is where the data stops. Blank lines
+//! at the end of the file are ignored; `--rows`/`--bytes` analyses are not checked, since
+//! their last row is not the end of the file. With `--aggregates-only` the warning is
+//! just "file appears truncated", without the row and byte.
+
+use std::fmt;
+use std::io;
+
+use crate::fields::scan_quotes;
+use crate::messages::{Language, Message};
+
+/// Exit status when an analyzed file appears truncated (after all reports were written)
+pub const EXIT_TRUNCATED: i32 = 3;
+
+/// Why a file appears truncated
+#[derive(Debug, Clone, PartialEq)]
+pub enum TruncationKind {
+    /// The last record opened a quoted field on `record_start_row` that never closes
+    OpenQuotedField { record_start_row: usize },
+    /// The last record has `fields` fields where the header has `expected`
+    ShortFinalRow { fields: usize, expected: usize },
+}
+
+/// Where a file appears to be cut off
+#[derive(Debug, Clone, PartialEq)]
+pub struct Truncation {
+    /// Index of the last row read
+    pub row_index: usize,
+    /// Byte offset where the data stops
+    pub byte_offset: u64,
+    pub kind: TruncationKind,
+    /// Whether the description leaves out the row and byte (`--aggregates-only`)
+    pub position_hidden: bool,
+}
+
+impl Truncation {
+    /// Describes the truncation, e.g. `file appears truncated at row 1203 / byte 88412: ...`.
+    pub fn describe(&self, language: Language) -> String {
+        let reason = match &self.kind {
+            TruncationKind::OpenQuotedField { .. } if self.position_hidden => Message::OpenQuotedField.text(language, &[]),
+            TruncationKind::OpenQuotedField { record_start_row } => {
+                Message::OpenQuotedFieldOnRow.text(language, &[record_start_row])
+            },
+            TruncationKind::ShortFinalRow { fields, expected } => Message::ShortFinalRow.text(language, &[fields, expected]),
+        };
+        if self.position_hidden {
+            return Message::Truncated.text(language, &[&reason]);
+        }
+        Message::TruncatedAt.text(language, &[&self.row_index, &self.byte_offset, &reason])
+    }
+
+    /// The same truncation, described without the rows and bytes where it happened.
+    pub fn without_position(self) -> Truncation {
+        Truncation { position_hidden: true, ..self }
+    }
+}
+
+/// Follows quoting across physical lines to find out how the last record ends
+#[derive(Debug)]
+pub struct TruncationTracker {
+    /// Field delimiter of the records
+    delimiter: char,
+    /// Whether the current record continues on the next line
+    in_quotes: bool,
+    /// First row of the current record
+    record_start_row: usize,
+    /// Delimiters seen so far in the current record
+    record_delimiters: usize,
+    /// Fields in the header record, once it is complete
+    header_fields: Option<usize>,
+    /// Fields of the last complete non-blank record after the header
+    last_record_fields: Option<usize>,
+    /// Last non-blank row and the byte offset where it ends
+    last_row: Option<(usize, u64)>,
+}
+
+impl TruncationTracker {
+    /// Creates a tracker for records split at `delimiter`.
+    pub fn new(delimiter: char) -> Self {
+        TruncationTracker {
+            delimiter,
+            in_quotes: false,
+            record_start_row: 0,
+            record_delimiters: 0,
+            header_fields: None,
+            last_record_fields: None,
+            last_row: None,
+        }
+    }
+
+    /// Adds one physical line.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_index` - Index of the line in the file
+    /// * `line_end` - Byte offset just past the line and its terminator
+    /// * `line` - Line content without terminator
+    pub fn observe_line(&mut self, row_index: usize, line_end: u64, line: &str) {
+        if !self.in_quotes {
+            if line.is_empty() {
+                return;
+            }
+            self.record_start_row = row_index;
+            self.record_delimiters = 0;
+        }
+        let (delimiters, in_quotes) = scan_quotes(line, self.delimiter, self.in_quotes);
+        self.in_quotes = in_quotes;
+        self.record_delimiters += delimiters;
+        self.last_row = Some((row_index, line_end));
+
+        if !in_quotes {
+            let fields = self.record_delimiters + 1;
+            match self.header_fields {
+                None => self.header_fields = Some(fields),
+                Some(_) => self.last_record_fields = Some(fields),
+            }
+        }
+    }
+
+    /// Checks how the input ended.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Truncation>` - Where the file appears to be cut off, or None if it ends
+    ///   with a complete record
+    pub fn finish(&self) -> Option<Truncation> {
+        let (row_index, byte_offset) = self.last_row?;
+        if self.in_quotes {
+            return Some(Truncation {
+                row_index,
+                byte_offset,
+                kind: TruncationKind::OpenQuotedField { record_start_row: self.record_start_row },
+                position_hidden: false,
+            });
+        }
+        let expected = self.header_fields?;
+        match self.last_record_fields {
+            Some(fields) if fields < expected => Some(Truncation {
+                row_index,
+                byte_offset,
+                kind: TruncationKind::ShortFinalRow { fields, expected },
+                position_hidden: false,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned once every report of a truncated file was written, so the run can end
+/// with [`EXIT_TRUNCATED`]
+#[derive(Debug)]
+pub struct TruncatedInputError {
+    truncation: Truncation,
+}
+
+impl fmt::Display for TruncatedInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.truncation.describe(Language::English))
+    }
+}
+
+impl std::error::Error for TruncatedInputError {}
+
+impl TruncatedInputError {
+    /// Wraps a truncation in an `io::Error`.
+    pub fn into_io_error(truncation: Truncation) -> io::Error {
+        io::Error::new(io::ErrorKind::UnexpectedEof, TruncatedInputError { truncation })
+    }
+
+    /// Whether an error is a [`TruncatedInputError`], i.e. the analysis and its reports
+    /// completed.
+    pub fn is_truncated_input_error(error: &io::Error) -> bool {
+        error.get_ref().is_some_and(|inner| inner.is::<TruncatedInputError>())
+    }
+}
@@ -0,0 +1,185 @@
+//! # Value Shapes (`--value-shapes`)
+//!
+//! Finds the columns whose values are mostly URLs, email addresses, UUIDs, or embedded
+//! JSON, and measures how many of their values actually have that shape, so a column an
+//! ingestion contract declares as `email` can be checked before the load rejects it:
+//!
+//! ```bash
+//! $ csv_row_analyzer signups.csv reports --value-shapes
+//! ```
+//!
+//! A column has a shape when at least [`MIN_CONFORMING_PERCENT`] of its non-empty values
+//! match it; the report lists its conformity and the first nonconforming values with
+//! their row indices. The checks are simple validators, not full RFC parsers:
+//!
+//! * `url` - `http://`, `https://`, or `ftp://`, a host, and no whitespace
+//! * `email` - one `@`, a local part, and a dotted domain with a top-level domain of
+//!   two or more letters
+//! * `uuid` - 32 hex digits in 8-4-4-4-12 groups
+//! * `json` - an object or array that parses as a complete JSON document
+//!
+//! Empty values and null tokens are not counted, so an optional column is judged by the
+//! values it has.
+
+use crate::column_profile::ColumnProfiler;
+use crate::json::parse_json;
+
+/// Share of non-empty values that must match for a column to have a shape
+pub const MIN_CONFORMING_PERCENT: f64 = 80.0;
+/// Nonconforming values kept per column and shape
+const MAX_EXAMPLE_ROWS: usize = 5;
+
+/// A recognizable shape of text values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueShape {
+    Url,
+    Email,
+    Uuid,
+    Json,
+}
+
+impl ValueShape {
+    /// Every shape, in report order
+    pub const ALL: [ValueShape; 4] = [ValueShape::Url, ValueShape::Email, ValueShape::Uuid, ValueShape::Json];
+
+    /// Short machine-readable name used in the reports.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValueShape::Url => "url",
+            ValueShape::Email => "email",
+            ValueShape::Uuid => "uuid",
+            ValueShape::Json => "json",
+        }
+    }
+
+    /// Whether a trimmed, non-empty value has this shape.
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            ValueShape::Url => is_url(value),
+            ValueShape::Email => is_email(value),
+            ValueShape::Uuid => is_uuid(value),
+            ValueShape::Json => value.starts_with(['{', '[']) && parse_json(value).is_ok(),
+        }
+    }
+}
+
+/// Values of one column matching each shape, with the first values that do not
+#[derive(Debug, Clone, Default)]
+pub struct ShapeTally {
+    /// Non-empty values checked
+    values: u64,
+    /// Values matching each shape, in the order of [`ValueShape::ALL`]
+    conforming: [u64; 4],
+    /// Row indices (where the record starts) and values of the first values not matching each shape
+    nonconforming_examples: [Vec<(usize, String)>; 4],
+}
+
+impl ShapeTally {
+    /// Checks one trimmed, non-empty value against every shape.
+    pub fn observe(&mut self, row_index: usize, value: &str) {
+        self.values += 1;
+        for (index, shape) in ValueShape::ALL.iter().enumerate() {
+            if shape.matches(value) {
+                self.conforming[index] += 1;
+            } else if self.nonconforming_examples[index].len() < MAX_EXAMPLE_ROWS {
+                self.nonconforming_examples[index].push((row_index, value.to_string()));
+            }
+        }
+    }
+}
+
+/// A column whose values mostly have one shape
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnShape {
+    /// 0-based column index
+    pub index: usize,
+    pub name: String,
+    pub shape: ValueShape,
+    /// Non-empty values checked
+    pub values: u64,
+    /// Values with the shape
+    pub conforming: u64,
+    /// Row indices and values of the first values without it
+    pub nonconforming_examples: Vec<(usize, String)>,
+}
+
+impl ColumnShape {
+    /// Percentage of the non-empty values that have the shape.
+    pub fn conformity_percent(&self) -> f64 {
+        if self.values == 0 { 0.0 } else { self.conforming as f64 * 100.0 / self.values as f64 }
+    }
+
+    /// Values without the shape.
+    pub fn nonconforming(&self) -> u64 {
+        self.values - self.conforming
+    }
+}
+
+/// Lists the columns that have a shape, in column order.
+///
+/// # Arguments
+///
+/// * `profiler` - Finished column profiler of the file, with shape checks enabled
+pub fn collect_column_shapes(profiler: &ColumnProfiler) -> Vec<ColumnShape> {
+    profiler.columns().into_iter()
+        .filter_map(|(index, name, profile)| {
+            let tally = &profile.value_shapes;
+            // The shape most values have; the first in report order on a tie
+            let (best, &conforming) = tally.conforming.iter()
+                .enumerate()
+                .rev()
+                .max_by_key(|&(_, &conforming)| conforming)?;
+            let shape = ColumnShape {
+                index,
+                name,
+                shape: ValueShape::ALL[best],
+                values: tally.values,
+                conforming,
+                nonconforming_examples: tally.nonconforming_examples[best].clone(),
+            };
+            (conforming > 0 && shape.conformity_percent() >= MIN_CONFORMING_PERCENT).then_some(shape)
+        })
+        .collect()
+}
+
+/// `scheme://host[...]` with an http, https, or ftp scheme and no whitespace.
+fn is_url(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once("://") else {
+        return false;
+    };
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    matches!(scheme.to_ascii_lowercase().as_str(), "http" | "https" | "ftp")
+        && !host.is_empty()
+        && host.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b':' | b'@' | b'[' | b']' | b'_'))
+        && !value.chars().any(char::is_whitespace)
+}
+
+/// `local@domain.tld` with one `@`, no whitespace, and a top-level domain of letters.
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    let labels: Vec<&str> = domain.split('.').collect();
+    let top_level = labels.last().copied().unwrap_or("");
+    !local.is_empty()
+        && !domain.contains('@')
+        && !value.chars().any(char::is_whitespace)
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+        && top_level.len() >= 2
+        && top_level.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+/// 32 hex digits in 8-4-4-4-12 groups (`123e4567-e89b-12d3-a456-426614174000`).
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, length)| {
+            group.len() == length && group.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+}
@@ -0,0 +1,814 @@
+//! # Zstandard Input
+//!
+//! Decodes Zstandard (`.csv.zst`) input while it is read. The `compression` module picks
+//! this decoder when a file starts with the zstd magic bytes, as it picks the gzip decoder
+//! for gzip input, so no temporary decompressed copy is needed.
+//!
+//! The decoder is a small, std-only implementation of RFC 8878: raw, RLE, and compressed
+//! blocks, Huffman-coded literals, and FSE-coded sequences with repeat offsets. Files of
+//! several concatenated frames are read to the end, skippable frames are passed over, and
+//! each frame is checked against its content size and XXH64 checksum when it has them.
+//! Frames compressed with a dictionary, and windows over [`MAX_WINDOW_SIZE`], are refused.
+
+use std::io::{self, BufRead, Read};
+
+use crate::compression::invalid_data;
+use crate::row_hash::Xxh64Hasher;
+
+/// First four bytes of every zstd frame
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+/// Largest window accepted (what `zstd --long=31` may need)
+pub const MAX_WINDOW_SIZE: u64 = 1 << 31;
+/// Largest number of bytes one block decodes to
+const MAX_BLOCK_SIZE: usize = 128 * 1024;
+/// Decode ahead until this many unread bytes are buffered
+const OUTPUT_CHUNK: usize = 64 * 1024;
+/// Longest Huffman code allowed for literals
+const MAX_HUFFMAN_BITS: u32 = 11;
+
+/// Literal length codes: baseline and number of extra bits
+const LITERAL_LENGTH_BASE: [u32; 36] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 18, 20, 22, 24, 28, 32, 40, 48, 64,
+    128, 256, 512, 1024, 2048, 4096, 8192, 16384, 32768, 65536,
+];
+const LITERAL_LENGTH_EXTRA: [u32; 36] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 3, 3, 4, 6, 7, 8, 9, 10, 11,
+    12, 13, 14, 15, 16,
+];
+/// Match length codes: baseline and number of extra bits
+const MATCH_LENGTH_BASE: [u32; 53] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28,
+    29, 30, 31, 32, 33, 34, 35, 37, 39, 41, 43, 47, 51, 59, 67, 83, 99, 131, 259, 515, 1027, 2051,
+    4099, 8195, 16387, 32771, 65539,
+];
+const MATCH_LENGTH_EXTRA: [u32; 53] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    1, 1, 1, 1, 2, 2, 3, 3, 4, 4, 5, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+];
+/// Largest offset code accepted
+const MAX_OFFSET_CODE: u8 = 31;
+
+/// Default distributions of RFC 8878, section 3.1.1.3.2.2
+const LITERAL_LENGTH_DEFAULT: [i16; 36] = [
+    4, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 2, 1, 1, 1, 1, 1,
+    -1, -1, -1, -1,
+];
+const MATCH_LENGTH_DEFAULT: [i16; 53] = [
+    1, 4, 3, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, -1, -1, -1, -1, -1, -1, -1,
+];
+const OFFSET_DEFAULT: [i16; 29] = [
+    1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, -1, -1, -1, -1, -1,
+];
+
+/// The three kinds of sequence codes, each with its own FSE table
+#[derive(Clone, Copy)]
+enum SequenceCode {
+    LiteralLength,
+    Offset,
+    MatchLength,
+}
+
+impl SequenceCode {
+    /// Largest accuracy log of a table sent in the block, and the largest symbol
+    fn limits(self) -> (u32, u8) {
+        match self {
+            SequenceCode::LiteralLength => (9, 35),
+            SequenceCode::Offset => (8, MAX_OFFSET_CODE),
+            SequenceCode::MatchLength => (9, 52),
+        }
+    }
+
+    fn default_table(self) -> Result<FseTable, io::Error> {
+        match self {
+            SequenceCode::LiteralLength => FseTable::from_distribution(6, &LITERAL_LENGTH_DEFAULT),
+            SequenceCode::Offset => FseTable::from_distribution(5, &OFFSET_DEFAULT),
+            SequenceCode::MatchLength => FseTable::from_distribution(6, &MATCH_LENGTH_DEFAULT),
+        }
+    }
+}
+
+/// Decoding table of a finite state entropy (tANS) code
+#[derive(Clone)]
+struct FseTable {
+    accuracy_log: u32,
+    /// Symbol of each state
+    symbols: Vec<u8>,
+    /// Bits read to find the next state
+    bits: Vec<u8>,
+    /// Next state before the bits read are added
+    bases: Vec<u16>,
+}
+
+impl FseTable {
+    /// Builds the table of a normalized distribution (-1 = "less than one").
+    fn from_distribution(accuracy_log: u32, distribution: &[i16]) -> Result<FseTable, io::Error> {
+        let size = 1usize << accuracy_log;
+        let mut symbols = vec![0u8; size];
+        let mut next = vec![0u16; distribution.len()];
+
+        // Symbols of probability "less than one" take one state each at the end
+        let mut high = size;
+        for (symbol, &probability) in distribution.iter().enumerate() {
+            if probability == -1 {
+                high -= 1;
+                symbols[high] = symbol as u8;
+                next[symbol] = 1;
+            }
+        }
+
+        // Spread the other symbols over the remaining states
+        let step = (size >> 1) + (size >> 3) + 3;
+        let mut position = 0;
+        for (symbol, &probability) in distribution.iter().enumerate() {
+            if probability <= 0 {
+                continue;
+            }
+            next[symbol] = probability as u16;
+            for _ in 0..probability {
+                symbols[position] = symbol as u8;
+                position = (position + step) & (size - 1);
+                while position >= high {
+                    position = (position + step) & (size - 1);
+                }
+            }
+        }
+        if position != 0 {
+            return Err(invalid_data("corrupt FSE distribution in zstd data"));
+        }
+
+        let mut bits = vec![0u8; size];
+        let mut bases = vec![0u16; size];
+        for state in 0..size {
+            let symbol = symbols[state] as usize;
+            let next_state = next[symbol] as u32;
+            next[symbol] += 1;
+            let count = accuracy_log - next_state.ilog2();
+            bits[state] = count as u8;
+            bases[state] = ((next_state << count) - size as u32) as u16;
+        }
+        Ok(FseTable { accuracy_log, symbols, bits, bases })
+    }
+
+    /// Table that always decodes `symbol` without reading bits.
+    fn rle(symbol: u8) -> FseTable {
+        FseTable { accuracy_log: 0, symbols: vec![symbol], bits: vec![0], bases: vec![0] }
+    }
+
+    /// Reads a table description; returns the table and the bytes it took.
+    fn read(data: &[u8], max_accuracy_log: u32, max_symbol: u8) -> Result<(FseTable, usize), io::Error> {
+        let mut bits = ForwardBits { data, position: 0 };
+        let accuracy_log = bits.read(4)? as u32 + 5;
+        if accuracy_log > max_accuracy_log {
+            return Err(invalid_data("FSE accuracy log too large in zstd data"));
+        }
+
+        let mut distribution: Vec<i16> = Vec::new();
+        let mut remaining = 1i32 << accuracy_log;
+        while remaining > 0 && distribution.len() <= max_symbol as usize {
+            // Values up to `remaining + 1` fit in `width` bits; the small ones take one bit less
+            let width = (remaining + 1).ilog2() + 1;
+            let mut value = bits.read(width)? as i32;
+            let lower_mask = (1i32 << (width - 1)) - 1;
+            let threshold = (1i32 << width) - 1 - (remaining + 1);
+            if value & lower_mask < threshold {
+                bits.position -= 1;
+                value &= lower_mask;
+            } else if value > lower_mask {
+                value -= threshold;
+            }
+            let probability = (value - 1) as i16;
+            remaining -= probability.unsigned_abs() as i32;
+            distribution.push(probability);
+
+            if probability == 0 {
+                loop {
+                    let repeat = bits.read(2)?;
+                    distribution.resize(distribution.len() + repeat as usize, 0);
+                    if repeat != 3 {
+                        break;
+                    }
+                }
+            }
+        }
+        if remaining != 0 || distribution.len() > max_symbol as usize + 1 {
+            return Err(invalid_data("corrupt FSE table description in zstd data"));
+        }
+        Ok((FseTable::from_distribution(accuracy_log, &distribution)?, bits.position.div_ceil(8)))
+    }
+}
+
+/// Position in an FSE-coded bitstream's state machine
+struct FseState<'t> {
+    table: &'t FseTable,
+    state: usize,
+}
+
+impl<'t> FseState<'t> {
+    fn new(table: &'t FseTable, bits: &mut BackwardBits) -> Self {
+        let state = bits.read(table.accuracy_log) as usize;
+        FseState { table, state }
+    }
+
+    fn symbol(&self) -> u8 {
+        self.table.symbols[self.state]
+    }
+
+    fn update(&mut self, bits: &mut BackwardBits) {
+        let extra = bits.read(self.table.bits[self.state] as u32) as usize;
+        self.state = self.table.bases[self.state] as usize + extra;
+    }
+}
+
+/// Decoding table of the Huffman code for literals
+struct HuffmanTable {
+    max_bits: u32,
+    /// Symbol and code length for every `max_bits`-bit prefix
+    symbols: Vec<u8>,
+    bits: Vec<u8>,
+}
+
+impl HuffmanTable {
+    /// Reads a Huffman tree description; returns the table and the bytes it took.
+    fn read(data: &[u8]) -> Result<(HuffmanTable, usize), io::Error> {
+        let header = *data.first().ok_or_else(|| invalid_data("missing Huffman table in zstd data"))? as usize;
+        let (mut weights, used) = if header < 128 {
+            // FSE-compressed weights, decoded by two interleaved states
+            let compressed = data.get(1..1 + header).ok_or_else(|| invalid_data("truncated Huffman table in zstd data"))?;
+            let (table, table_bytes) = FseTable::read(compressed, 6, 255)?;
+            let mut bits = BackwardBits::new(&compressed[table_bytes..])?;
+            let mut states = [FseState::new(&table, &mut bits), FseState::new(&table, &mut bits)];
+            let mut weights = Vec::new();
+            'decode: loop {
+                for current in 0..2 {
+                    weights.push(states[current].symbol());
+                    states[current].update(&mut bits);
+                    if bits.position < 0 {
+                        weights.push(states[1 - current].symbol());
+                        break 'decode;
+                    }
+                }
+                if weights.len() > 255 {
+                    return Err(invalid_data("too many Huffman weights in zstd data"));
+                }
+            }
+            (weights, 1 + header)
+        } else {
+            // Weights stored directly, four bits each
+            let count = header - 127;
+            let bytes = data.get(1..1 + count.div_ceil(2)).ok_or_else(|| invalid_data("truncated Huffman table in zstd data"))?;
+            let weights = (0..count).map(|i| if i % 2 == 0 { bytes[i / 2] >> 4 } else { bytes[i / 2] & 0x0F }).collect();
+            (weights, 1 + count.div_ceil(2))
+        };
+        if weights.len() > 255 || weights.iter().any(|&weight| weight > MAX_HUFFMAN_BITS as u8) {
+            return Err(invalid_data("corrupt Huffman weights in zstd data"));
+        }
+
+        // The weight of the last symbol is implied: it completes the code to a power of two
+        let total: u32 = weights.iter().filter(|&&weight| weight > 0).map(|&weight| 1u32 << (weight - 1)).sum();
+        if total == 0 {
+            return Err(invalid_data("empty Huffman code in zstd data"));
+        }
+        let max_bits = total.ilog2() + 1;
+        let left = (1u32 << max_bits) - total;
+        if max_bits > MAX_HUFFMAN_BITS || !left.is_power_of_two() {
+            return Err(invalid_data("corrupt Huffman weights in zstd data"));
+        }
+        weights.push((left.ilog2() + 1) as u8);
+
+        // Shorter codes (higher weights) follow longer ones; each fills 2^(weight-1) entries
+        let size = 1usize << max_bits;
+        let mut symbols = vec![0u8; size];
+        let mut bits = vec![0u8; size];
+        let mut position = 0;
+        for weight in 1..=max_bits as u8 {
+            for (symbol, _) in weights.iter().enumerate().filter(|&(_, &w)| w == weight) {
+                let entries = 1usize << (weight - 1);
+                symbols[position..position + entries].fill(symbol as u8);
+                bits[position..position + entries].fill((max_bits + 1 - weight as u32) as u8);
+                position += entries;
+            }
+        }
+        Ok((HuffmanTable { max_bits, symbols, bits }, used))
+    }
+
+    /// Decodes `count` literals from one stream.
+    fn decode_stream(&self, stream: &[u8], count: usize, literals: &mut Vec<u8>) -> Result<(), io::Error> {
+        let mut bits = BackwardBits::new(stream)?;
+        for _ in 0..count {
+            let prefix = bits.peek(self.max_bits) as usize;
+            literals.push(self.symbols[prefix]);
+            bits.position -= self.bits[prefix] as isize;
+        }
+        if bits.position != 0 {
+            return Err(invalid_data("corrupt Huffman-coded literals in zstd data"));
+        }
+        Ok(())
+    }
+}
+
+/// Returns `count` bits (at most 56) starting at bit `start`; bits before the start of
+/// the data read as zeros.
+fn bits_at(data: &[u8], start: isize, count: u32) -> u64 {
+    if count == 0 {
+        return 0;
+    }
+    if start < 0 {
+        let missing = start.unsigned_abs() as u32;
+        return if missing >= count { 0 } else { bits_at(data, 0, count - missing) << missing };
+    }
+    let first = start as usize / 8;
+    let mut word = [0u8; 8];
+    let available = data.len().saturating_sub(first).min(8);
+    word[..available].copy_from_slice(&data[first..first + available]);
+    (u64::from_le_bytes(word) >> (start % 8)) & ((1u64 << count) - 1)
+}
+
+/// Bitstream read from the end towards the start, as FSE and Huffman streams are written
+struct BackwardBits<'a> {
+    data: &'a [u8],
+    /// Bits not read yet; negative once the stream is overread
+    position: isize,
+}
+
+impl<'a> BackwardBits<'a> {
+    /// Starts below the end mark, the highest set bit of the last byte.
+    fn new(data: &'a [u8]) -> Result<Self, io::Error> {
+        match data.last() {
+            Some(&last) if last != 0 => Ok(BackwardBits { data, position: ((data.len() - 1) * 8) as isize + last.ilog2() as isize }),
+            _ => Err(invalid_data("missing bitstream end mark in zstd data")),
+        }
+    }
+
+    fn peek(&self, count: u32) -> u64 {
+        bits_at(self.data, self.position - count as isize, count)
+    }
+
+    fn read(&mut self, count: u32) -> u64 {
+        self.position -= count as isize;
+        bits_at(self.data, self.position, count)
+    }
+}
+
+/// Bitstream read from the start, least significant bit first (FSE table descriptions)
+struct ForwardBits<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl ForwardBits<'_> {
+    fn read(&mut self, count: u32) -> Result<u64, io::Error> {
+        if self.position + count as usize > self.data.len() * 8 {
+            return Err(invalid_data("truncated FSE table description in zstd data"));
+        }
+        let value = bits_at(self.data, self.position as isize, count);
+        self.position += count as usize;
+        Ok(value)
+    }
+}
+
+/// One literal run and match of a compressed block
+struct Sequence {
+    literal_length: usize,
+    match_length: usize,
+    offset: usize,
+}
+
+/// State of the frame being decoded; tables and offsets carry over between its blocks
+struct Frame {
+    number: u64,
+    window_size: usize,
+    content_size: Option<u64>,
+    checksum: Option<Xxh64Hasher>,
+    /// Bytes decoded in this frame so far
+    decoded: u64,
+    repeat_offsets: [usize; 3],
+    huffman: Option<HuffmanTable>,
+    /// Last FSE tables used for literal lengths, offsets, and match lengths
+    tables: [Option<FseTable>; 3],
+}
+
+impl Frame {
+    /// Decodes a compressed block, appending its output.
+    fn decode_block(&mut self, block: &[u8], output: &mut Vec<u8>) -> Result<(), io::Error> {
+        let (literals, literals_bytes) = self.decode_literals(block)?;
+        let sequences = self.decode_sequences(&block[literals_bytes..])?;
+
+        let block_start = output.len();
+        let mut literal_position = 0;
+        for sequence in sequences {
+            let literal_end = literal_position + sequence.literal_length;
+            let run = literals.get(literal_position..literal_end)
+                .ok_or_else(|| invalid_data("sequence uses more literals than decoded in zstd data"))?;
+            output.extend_from_slice(run);
+            literal_position = literal_end;
+
+            let available = self.decoded as usize + (output.len() - block_start);
+            if sequence.offset == 0 || sequence.offset > available || sequence.offset > self.window_size {
+                return Err(invalid_data("match offset beyond the decoded data in zstd data"));
+            }
+            let start = output.len() - sequence.offset;
+            if sequence.offset >= sequence.match_length {
+                output.extend_from_within(start..start + sequence.match_length);
+            } else {
+                // Overlapping match: each byte may repeat one just written
+                for i in 0..sequence.match_length {
+                    output.push(output[start + i]);
+                }
+            }
+        }
+        output.extend_from_slice(&literals[literal_position..]);
+        if output.len() - block_start > MAX_BLOCK_SIZE {
+            return Err(invalid_data("block decodes to more than 128 KiB in zstd data"));
+        }
+        Ok(())
+    }
+
+    /// Decodes the literals section; returns the literals and the bytes it took.
+    fn decode_literals(&mut self, block: &[u8]) -> Result<(Vec<u8>, usize), io::Error> {
+        let byte = |i: usize| block.get(i).map(|&b| b as usize).ok_or_else(|| invalid_data("truncated literals section in zstd data"));
+        let first = byte(0)?;
+        let kind = first & 3;
+        let size_format = (first >> 2) & 3;
+
+        if kind < 2 {
+            // Raw or RLE literals
+            let (size, header) = match size_format {
+                0 | 2 => (first >> 3, 1),
+                1 => ((first >> 4) + (byte(1)? << 4), 2),
+                _ => ((first >> 4) + (byte(1)? << 4) + (byte(2)? << 12), 3),
+            };
+            if size > MAX_BLOCK_SIZE {
+                return Err(invalid_data("literals section too large in zstd data"));
+            }
+            return if kind == 0 {
+                let literals = block.get(header..header + size).ok_or_else(|| invalid_data("truncated literals in zstd data"))?;
+                Ok((literals.to_vec(), header + size))
+            } else {
+                Ok((vec![byte(header)? as u8; size], header + 1))
+            };
+        }
+
+        // Huffman-coded literals, with a new table or the previous one
+        let (streams, header, field_bits) = match size_format {
+            0 => (1, 3, 10),
+            1 => (4, 3, 10),
+            2 => (4, 4, 14),
+            _ => (4, 5, 18),
+        };
+        let mut value = 0usize;
+        for i in 0..header {
+            value |= byte(i)? << (8 * i);
+        }
+        let mask = (1usize << field_bits) - 1;
+        let regenerated = (value >> 4) & mask;
+        let compressed = (value >> (4 + field_bits)) & mask;
+        if regenerated > MAX_BLOCK_SIZE {
+            return Err(invalid_data("literals section too large in zstd data"));
+        }
+        let data = block.get(header..header + compressed).ok_or_else(|| invalid_data("truncated literals in zstd data"))?;
+
+        let mut table_bytes = 0;
+        if kind == 2 {
+            let (table, used) = HuffmanTable::read(data)?;
+            self.huffman = Some(table);
+            table_bytes = used;
+        }
+        let table = self.huffman.as_ref().ok_or_else(|| invalid_data("literals reuse a missing Huffman table in zstd data"))?;
+        let data = &data[table_bytes.min(data.len())..];
+
+        let mut literals = Vec::with_capacity(regenerated);
+        if streams == 1 {
+            table.decode_stream(data, regenerated, &mut literals)?;
+        } else {
+            // Four streams behind a jump table of the first three sizes
+            let jump = data.get(..6).ok_or_else(|| invalid_data("truncated literal streams in zstd data"))?;
+            let mut sizes: Vec<usize> = jump.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]]) as usize).collect();
+            let per_stream = regenerated.div_ceil(4);
+            let first_three: usize = sizes.iter().sum();
+            if 3 * per_stream > regenerated || first_three > data.len() - 6 {
+                return Err(invalid_data("corrupt literal streams in zstd data"));
+            }
+            sizes.push(data.len() - 6 - first_three);
+            let counts = [per_stream, per_stream, per_stream, regenerated - 3 * per_stream];
+            let mut start = 6;
+            for (size, count) in sizes.into_iter().zip(counts) {
+                table.decode_stream(&data[start..start + size], count, &mut literals)?;
+                start += size;
+            }
+        }
+        Ok((literals, header + compressed))
+    }
+
+    /// Decodes the sequences section, resolving repeat offsets.
+    fn decode_sequences(&mut self, data: &[u8]) -> Result<Vec<Sequence>, io::Error> {
+        let byte = |i: usize| data.get(i).map(|&b| b as usize).ok_or_else(|| invalid_data("truncated sequences section in zstd data"));
+        let (count, mut position) = match byte(0)? {
+            0 => return Ok(Vec::new()),
+            first @ 1..=127 => (first, 1),
+            first @ 128..=254 => (((first - 128) << 8) + byte(1)?, 2),
+            _ => (byte(1)? + (byte(2)? << 8) + 0x7F00, 3),
+        };
+        let modes = byte(position)?;
+        position += 1;
+        if modes & 3 != 0 {
+            return Err(invalid_data("reserved sequence compression mode bits are set in zstd data"));
+        }
+
+        let codes = [SequenceCode::LiteralLength, SequenceCode::Offset, SequenceCode::MatchLength];
+        for (index, (code, shift)) in codes.into_iter().zip([6, 4, 2]).enumerate() {
+            let (max_accuracy_log, max_symbol) = code.limits();
+            match (modes >> shift) & 3 {
+                0 => self.tables[index] = Some(code.default_table()?),
+                1 => {
+                    let symbol = byte(position)? as u8;
+                    position += 1;
+                    if symbol > max_symbol {
+                        return Err(invalid_data("sequence code out of range in zstd data"));
+                    }
+                    self.tables[index] = Some(FseTable::rle(symbol));
+                },
+                2 => {
+                    let rest = data.get(position..).unwrap_or_default();
+                    let (table, used) = FseTable::read(rest, max_accuracy_log, max_symbol)?;
+                    self.tables[index] = Some(table);
+                    position += used;
+                },
+                _ => {
+                    if self.tables[index].is_none() {
+                        return Err(invalid_data("sequences reuse a missing FSE table in zstd data"));
+                    }
+                },
+            }
+        }
+        let [Some(literal_table), Some(offset_table), Some(match_table)] = &self.tables else {
+            unreachable!("every table was just set or checked");
+        };
+
+        let mut bits = BackwardBits::new(data.get(position..).unwrap_or_default())?;
+        let mut literal_state = FseState::new(literal_table, &mut bits);
+        let mut offset_state = FseState::new(offset_table, &mut bits);
+        let mut match_state = FseState::new(match_table, &mut bits);
+        let mut sequences = Vec::with_capacity(count);
+        for i in 0..count {
+            let offset_code = offset_state.symbol();
+            let match_code = match_state.symbol() as usize;
+            let literal_code = literal_state.symbol() as usize;
+            if offset_code > MAX_OFFSET_CODE || match_code >= MATCH_LENGTH_BASE.len() || literal_code >= LITERAL_LENGTH_BASE.len() {
+                return Err(invalid_data("sequence code out of range in zstd data"));
+            }
+            let offset_value = (1usize << offset_code) + bits.read(offset_code as u32) as usize;
+            let match_length = (MATCH_LENGTH_BASE[match_code] as u64 + bits.read(MATCH_LENGTH_EXTRA[match_code])) as usize;
+            let literal_length = (LITERAL_LENGTH_BASE[literal_code] as u64 + bits.read(LITERAL_LENGTH_EXTRA[literal_code])) as usize;
+            if i + 1 < count {
+                literal_state.update(&mut bits);
+                match_state.update(&mut bits);
+                offset_state.update(&mut bits);
+            }
+            if bits.position < 0 {
+                return Err(invalid_data("corrupt sequences in zstd data"));
+            }
+            let offset = resolve_offset(&mut self.repeat_offsets, offset_value, literal_length);
+            sequences.push(Sequence { literal_length, match_length, offset });
+        }
+        if bits.position != 0 {
+            return Err(invalid_data("corrupt sequences in zstd data"));
+        }
+        Ok(sequences)
+    }
+}
+
+/// Turns an offset value into a distance, updating the three repeat offsets.
+fn resolve_offset(history: &mut [usize; 3], offset_value: usize, literal_length: usize) -> usize {
+    if offset_value > 3 {
+        let offset = offset_value - 3;
+        *history = [offset, history[0], history[1]];
+        return offset;
+    }
+    // Values 1..3 name a repeat offset, shifted by one after an empty literal run
+    let index = offset_value - 1 + usize::from(literal_length == 0);
+    if index == 0 {
+        return history[0];
+    }
+    let offset = if index < 3 { history[index] } else { history[0].wrapping_sub(1) };
+    if index > 1 {
+        history[2] = history[1];
+    }
+    history[1] = history[0];
+    history[0] = offset;
+    offset
+}
+
+/// Streaming decoder for zstd data of one or more frames
+pub struct ZstdDecoder<R: BufRead> {
+    inner: R,
+    /// Bytes consumed from the compressed input
+    compressed_position: u64,
+    /// Frame being decoded, or `None` between frames
+    frame: Option<Frame>,
+    frames: u64,
+    done: bool,
+    /// Content of the current compressed block
+    block: Vec<u8>,
+    /// Decoded output: at least the current window of bytes already read by the caller,
+    /// then unread bytes
+    output: Vec<u8>,
+    /// Index of the first unread byte in `output`
+    read_position: usize,
+}
+
+impl<R: BufRead> ZstdDecoder<R> {
+    /// Creates a decoder reading zstd data from `inner`.
+    pub fn new(inner: R) -> Self {
+        ZstdDecoder {
+            inner,
+            compressed_position: 0,
+            frame: None,
+            frames: 0,
+            done: false,
+            block: Vec::new(),
+            output: Vec::new(),
+            read_position: 0,
+        }
+    }
+
+    /// Reads exactly `buffer.len()` bytes of compressed input.
+    fn read_exact_input(&mut self, buffer: &mut [u8]) -> Result<(), io::Error> {
+        self.inner.read_exact(buffer).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => io::Error::new(io::ErrorKind::UnexpectedEof, "zstd data ends unexpectedly"),
+            _ => e,
+        })?;
+        self.compressed_position += buffer.len() as u64;
+        Ok(())
+    }
+
+    /// Reads a little-endian value of `count` bytes (at most 8).
+    fn next_le(&mut self, count: usize) -> Result<u64, io::Error> {
+        let mut bytes = [0u8; 8];
+        self.read_exact_input(&mut bytes[..count])?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Decodes blocks until `OUTPUT_CHUNK` unread bytes are buffered or the input ends.
+    fn fill_output(&mut self) -> Result<(), io::Error> {
+        self.compact_output();
+        while self.output.len() - self.read_position < OUTPUT_CHUNK && !self.done {
+            if self.frame.is_some() {
+                self.read_block()?;
+            } else {
+                self.read_frame_header()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the next frame header, skipping skippable frames, or finishes at the end of the input.
+    fn read_frame_header(&mut self) -> Result<(), io::Error> {
+        let frame_offset = self.compressed_position;
+        if self.inner.fill_buf()?.is_empty() {
+            if self.frames == 0 {
+                return Err(invalid_data("empty zstd input"));
+            }
+            self.done = true;
+            return Ok(());
+        }
+
+        let magic = self.next_le(4)? as u32;
+        if magic & 0xFFFF_FFF0 == 0x184D_2A50 {
+            let size = self.next_le(4)?;
+            let skipped = io::copy(&mut self.inner.by_ref().take(size), &mut io::sink())?;
+            if skipped < size {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "zstd data ends unexpectedly"));
+            }
+            self.compressed_position += size;
+            return Ok(());
+        }
+        if magic != u32::from_le_bytes(ZSTD_MAGIC) {
+            return Err(invalid_data(&format!(
+                "data at compressed offset {} is not zstd frame {}", frame_offset, self.frames + 1
+            )));
+        }
+
+        let descriptor = self.next_le(1)? as u8;
+        if descriptor & 0x08 != 0 {
+            return Err(invalid_data("reserved zstd frame header bit is set"));
+        }
+        let single_segment = descriptor & 0x20 != 0;
+        let mut window_size = 0;
+        if !single_segment {
+            let window_descriptor = self.next_le(1)?;
+            let base = 1u64 << (10 + (window_descriptor >> 3));
+            window_size = base + base / 8 * (window_descriptor & 7);
+        }
+        let dictionary_id = self.next_le([0, 1, 2, 4][(descriptor & 3) as usize])?;
+        if dictionary_id != 0 {
+            return Err(invalid_data(&format!(
+                "zstd frame {} needs dictionary {}; decompress it with the dictionary first", self.frames + 1, dictionary_id
+            )));
+        }
+        let content_size = match (descriptor >> 6, single_segment) {
+            (0, false) => None,
+            (0, true) => Some(self.next_le(1)?),
+            (1, _) => Some(self.next_le(2)? + 256),
+            (2, _) => Some(self.next_le(4)?),
+            _ => Some(self.next_le(8)?),
+        };
+        if single_segment {
+            window_size = content_size.unwrap_or(0);
+        }
+        if window_size > MAX_WINDOW_SIZE {
+            return Err(invalid_data(&format!(
+                "zstd frame {} needs a {} MiB window, more than the {} MiB supported",
+                self.frames + 1, window_size >> 20, MAX_WINDOW_SIZE >> 20
+            )));
+        }
+
+        self.frames += 1;
+        self.frame = Some(Frame {
+            number: self.frames,
+            window_size: window_size as usize,
+            content_size,
+            checksum: (descriptor & 0x04 != 0).then(|| Xxh64Hasher::new(0)),
+            decoded: 0,
+            repeat_offsets: [1, 4, 8],
+            huffman: None,
+            tables: [None, None, None],
+        });
+        Ok(())
+    }
+
+    /// Decodes one block of the current frame, and checks the frame after its last block.
+    fn read_block(&mut self) -> Result<(), io::Error> {
+        let mut frame = self.frame.take().expect("a frame is being decoded");
+        let header = self.next_le(3)? as usize;
+        let last_block = header & 1 != 0;
+        let size = header >> 3;
+        if size > MAX_BLOCK_SIZE {
+            return Err(invalid_data("block larger than 128 KiB in zstd data"));
+        }
+
+        let start = self.output.len();
+        match (header >> 1) & 3 {
+            0 => {
+                self.output.resize(start + size, 0);
+                let mut raw = std::mem::take(&mut self.output);
+                let result = self.read_exact_input(&mut raw[start..]);
+                self.output = raw;
+                result?;
+            },
+            1 => {
+                let byte = self.next_le(1)? as u8;
+                self.output.resize(start + size, byte);
+            },
+            2 => {
+                let mut block = std::mem::take(&mut self.block);
+                block.resize(size, 0);
+                self.read_exact_input(&mut block)?;
+                frame.decode_block(&block, &mut self.output)?;
+                self.block = block;
+            },
+            _ => return Err(invalid_data("reserved block type in zstd data")),
+        }
+        let new_bytes = &self.output[start..];
+        frame.decoded += new_bytes.len() as u64;
+        if let Some(checksum) = frame.checksum.as_mut() {
+            checksum.update(new_bytes);
+        }
+
+        if !last_block {
+            self.frame = Some(frame);
+            return Ok(());
+        }
+        if frame.content_size.is_some_and(|size| size != frame.decoded) {
+            return Err(invalid_data(&format!("content size mismatch in zstd frame {}", frame.number)));
+        }
+        if let Some(checksum) = frame.checksum {
+            let stored = self.next_le(4)? as u32;
+            if stored != checksum.digest() as u32 {
+                return Err(invalid_data(&format!("checksum mismatch in zstd frame {}", frame.number)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops output that is both read by the caller and outside the window of the frame.
+    fn compact_output(&mut self) {
+        let window = self.frame.as_ref().map_or(0, |frame| frame.window_size);
+        if self.read_position > window + window.max(OUTPUT_CHUNK) {
+            let drop_count = self.read_position - window;
+            self.output.drain(..drop_count);
+            self.read_position -= drop_count;
+        }
+    }
+}
+
+impl<R: BufRead> Read for ZstdDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fill_output()?;
+        let available = &self.output[self.read_position..];
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.read_position += count;
+        Ok(count)
+    }
+}
@@ -0,0 +1,56 @@
+//! The async entry points (feature `async`): services call them from inside their own
+//! tokio runtime and get the same summary and reports as the blocking analyzer.
+
+#![cfg(feature = "async")]
+
+use std::fs;
+use std::sync::Arc;
+
+use csv_tools_core::test_support::scratch_dir;
+use csv_tools_core::{analyze_csv_file_async, analyze_csv_reader_async, analyze_csv_row_lengths, AnalysisOptions, Clock};
+
+const ORDERS: &str = "id,name\n1,Ana\n2,Bo\n3,Bartholomew\n";
+
+fn options() -> Arc<AnalysisOptions> {
+    Arc::new(AnalysisOptions::default().with_clock(Clock::Fixed(1_767_225_600)).with_quiet(true))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn file_analysis_matches_the_blocking_analyzer() {
+    let dir = scratch_dir("file");
+    let input = dir.join("orders.csv");
+    fs::write(&input, ORDERS).expect("write fixture");
+
+    let result = analyze_csv_file_async(&input, dir.join("async"), options()).await.expect("async analysis");
+    let blocking = analyze_csv_row_lengths(&input, dir.join("blocking"), &options()).expect("blocking analysis");
+    assert_eq!(result, blocking);
+    assert_eq!((result.total_rows, result.total_chars), (4, 29));
+    for name in ["char_counts", "value_counts"] {
+        let report = format!("orders_{}_report_1767225600.csv", name);
+        assert_eq!(
+            fs::read_to_string(dir.join("async").join(&report)).expect("async report"),
+            fs::read_to_string(dir.join("blocking").join(&report)).expect("blocking report"),
+            "{}",
+            report
+        );
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn reader_analysis_runs_on_a_single_threaded_runtime() {
+    let dir = scratch_dir("reader");
+
+    // Concurrent analyses share one set of options
+    let options = options();
+    let (first, second) = tokio::join!(
+        analyze_csv_reader_async(ORDERS.as_bytes(), "uploads/orders.csv", dir.join("first"), Arc::clone(&options)),
+        analyze_csv_reader_async(&b"a\nbb\n"[..], "uploads/short.csv", dir.join("second"), Arc::clone(&options)),
+    );
+    let (first, second) = (first.expect("first analysis"), second.expect("second analysis"));
+    assert_eq!((first.basename.as_str(), first.total_rows), ("orders", 4));
+    assert_eq!((second.basename.as_str(), second.total_rows), ("short", 2));
+    assert!(dir.join("first/orders_value_counts_report_1767225600.csv").exists());
+    assert!(dir.join("second/short_char_counts_report_1767225600.csv").exists());
+    let _ = fs::remove_dir_all(&dir);
+}
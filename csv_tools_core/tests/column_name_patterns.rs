@@ -0,0 +1,104 @@
+//! Column name patterns (`--exclude-columns-regex`) through the analyzer's reports.
+//!
+//! Each case profiles a fixture whose header holds the names under test and reads back
+//! which columns the text report lists as excluded. Patterns that backtrack
+//! exponentially in a naive matcher, and names long enough to exhaust the stack of a
+//! recursive one, must still finish.
+
+use csv_tools_core::test_support::{analyze_fixture, TIMESTAMP_PLACEHOLDER};
+
+/// Names of the columns in `header` that `pattern` excludes, as the text report lists them.
+fn excluded(header: &str, pattern: &str) -> String {
+    let row = vec!["1"; header.split(',').count()].join(",");
+    let content = format!("{}\n{}\n", header, row);
+    let reports = analyze_fixture("names.csv", content.as_bytes(), &["--profile-columns", "--exclude-columns-regex", pattern])
+        .unwrap_or_else(|e| panic!("{}: {}", pattern, e));
+    let report = reports.get(&format!("names_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    let prefix = format!("Excluded columns matching '{}': ", pattern);
+    report.lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .unwrap_or_else(|| panic!("no excluded columns line for {}: {}", pattern, report))
+        .to_string()
+}
+
+#[test]
+fn anchors_tie_the_pattern_to_either_end_of_the_name() {
+    let header = "_tmp_id,id_tmp_,user_raw,raw_user";
+    assert_eq!(excluded(header, "^_tmp_"), "_tmp_id");
+    assert_eq!(excluded(header, "_raw$"), "user_raw");
+    assert_eq!(excluded(header, "^raw_user$"), "raw_user");
+    assert_eq!(excluded(header, "tmp"), "_tmp_id, id_tmp_");
+    assert_eq!(excluded(header, "^tmp$"), "none");
+}
+
+#[test]
+fn classes_match_ranges_negations_and_shorthands() {
+    let header = "col1,col22,colA,col_,col-x";
+    assert_eq!(excluded(header, "^col[0-9]+$"), "col1, col22");
+    assert_eq!(excluded(header, "^col\\d$"), "col1");
+    assert_eq!(excluded(header, "^col[^0-9]$"), "colA, col_");
+    assert_eq!(excluded(header, "^col\\w$"), "col1, colA, col_");
+    assert_eq!(excluded(header, "^col[A_-]"), "colA, col_, col-x");
+    assert_eq!(excluded(header, "^col.{2}$"), "col22, col-x");
+}
+
+#[test]
+fn alternation_and_groups_choose_between_branches() {
+    let header = "created_at,updated_at,deleted_at,created_by";
+    assert_eq!(excluded(header, "^created|^updated"), "created_at, updated_at, created_by");
+    assert_eq!(excluded(header, "^(created|deleted)_at$"), "created_at, deleted_at");
+    assert_eq!(excluded(header, "_(at|by)$"), "created_at, updated_at, deleted_at, created_by");
+    assert_eq!(excluded(header, "^(up)?dated_at$"), "updated_at");
+}
+
+#[test]
+fn escapes_match_metacharacters_literally() {
+    let header = "price.usd,priceXusd,a|b,a,b,total(net)";
+    assert_eq!(excluded(header, "price\\.usd"), "price.usd");
+    assert_eq!(excluded(header, "price.usd"), "price.usd, priceXusd");
+    assert_eq!(excluded(header, "^a\\|b$"), "a|b");
+    assert_eq!(excluded(header, "\\(net\\)$"), "total(net)");
+}
+
+#[test]
+fn nested_quantifiers_do_not_backtrack_exponentially() {
+    // A backtracking matcher tries every split of the a's before failing on the missing b
+    let header = format!("{},{}b", "a".repeat(40), "a".repeat(40));
+    assert_eq!(excluded(&header, "^(a*)*b$"), format!("{}b", "a".repeat(40)));
+    assert_eq!(excluded(&header, "^(a|aa)+$"), "a".repeat(40));
+}
+
+#[test]
+fn long_names_do_not_exhaust_the_stack() {
+    let long_name = "x".repeat(200_000);
+    let header = format!("{},short", long_name);
+    assert_eq!(excluded(&header, "^x+$"), long_name);
+    assert_eq!(excluded(&header, "(x|y)*z"), "none");
+}
+
+#[test]
+fn repeated_empty_groups_compile_at_once() {
+    // Each copy of `()` compiles to nothing, so the instruction limit cannot stop these
+    let header = "x,y,xx";
+    assert_eq!(excluded(header, "^(){99999999999}x$"), "x");
+    assert_eq!(excluded(header, "^((){99999}){99999}x$"), "x");
+    assert_eq!(excluded(header, "^(a{0}){99999999999,}y$"), "y");
+    assert_eq!(excluded(header, "^(x{0,0}()){5,99999999999}xx$"), "xx");
+}
+
+#[test]
+fn unsupported_and_malformed_patterns_are_rejected() {
+    for (pattern, reason) in [
+        ("(a", "unclosed '('"),
+        ("a)", "unmatched ')'"),
+        ("(a)\\1", "backreference"),
+        ("(?=a)", "lookaround"),
+        ("a{1000}{1000}", "instructions"),
+        ("(|){99999999999}", "instructions"),
+        (&format!("{}a{}", "(".repeat(101), ")".repeat(101)), "nested"),
+    ] {
+        let error = analyze_fixture("names.csv", b"a\n1\n", &["--exclude-columns-regex", pattern])
+            .expect_err(pattern);
+        assert!(error.contains(reason), "{}: {}", pattern, error);
+    }
+}
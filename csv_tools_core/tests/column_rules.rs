@@ -0,0 +1,77 @@
+//! Cross-column rules (`--rule` and the `[column_rules]` section of the config file).
+//!
+//! Every data record must be checked against every rule in the analysis pass. A
+//! violation must be listed with the values of both sides and of the columns involved,
+//! a record with an empty or non-numeric value must be skipped rather than counted as a
+//! violation, and a rule that cannot be checked must say why.
+
+use std::fs;
+
+use csv_tools_core::test_support::{analyze_fixture, scratch_dir, GoldenReports, TIMESTAMP_PLACEHOLDER};
+
+/// Row 2 ends before it starts, row 3 is 0.50 off its total, and row 4 lasts 517 days
+/// and has no numeric quantity. Row 1 is 0.01 off, within the tolerance below.
+const ORDERS: &[u8] = b"\
+id,start_date,end_date,qty,price,order total
+1,2024-01-01,2024-02-01,2,1.50,3.01
+2,2024-03-05,2024-03-01,3,2.00,6.00
+3,2024-01-01,,1,5.00,5.50
+4,2024-01-01,2025-06-01,x,2,2
+";
+
+const RULES: [&str; 3] = ["end_date >= start_date", "\"order total\" = qty * price within 0.01", "end_date - start_date <= 365"];
+
+/// Content of the report `name` of the orders fixture.
+fn report<'a>(reports: &'a GoldenReports, name: &str, extension: &str) -> &'a str {
+    reports.get(&format!("orders_{}_report_{}.{}", name, TIMESTAMP_PLACEHOLDER, extension)).expect(name)
+}
+
+#[test]
+fn violations_are_listed_with_the_values_of_both_sides() {
+    let mut args = Vec::new();
+    for rule in RULES {
+        args.extend(["--rule", rule]);
+    }
+    let reports = analyze_fixture("orders.csv", ORDERS, &args).expect("run");
+    let violations: Vec<&str> = report(&reports, "column_rules", "csv").lines().collect();
+    assert_eq!(violations, [
+        "rule,row_index,left_value,right_value,column_values,report_schema_version",
+        "end_date >= start_date,2,2024-03-01,2024-03-05,end_date=2024-03-01;start_date=2024-03-05,6",
+        "\"\"\"order total\"\" = qty * price within 0.01\",3,5.50,5,order total=5.50;qty=1;price=5.00,6",
+        "end_date - start_date <= 365,4,517,365,end_date=2025-06-01;start_date=2024-01-01,6",
+    ]);
+
+    // Each rule skips one record: row 3 has no end date, row 4 no numeric quantity
+    let markdown = report(&reports, "md_outliers", "md");
+    for row in [
+        "| `end_date >= start_date` | 3 | 1 | 1 | 2 |",
+        "| `\"order total\" = qty * price within 0.01` | 3 | 1 | 1 | 3 |",
+        "| `end_date - start_date <= 365` | 3 | 1 | 1 | 4 |",
+    ] {
+        assert!(markdown.contains(row), "{}\n{}", row, markdown);
+    }
+}
+
+#[test]
+fn rules_from_the_config_file_are_checked_like_flags() {
+    let dir = scratch_dir("config");
+    let config = dir.join("rules.conf");
+    fs::write(&config, format!("[column_rules]\nrule = {}\n", RULES[0])).expect("write config");
+    let reports = analyze_fixture("orders.csv", ORDERS, &["--config", &config.to_string_lossy(), "--rule", RULES[2]]).expect("run");
+    let violations = report(&reports, "column_rules", "csv");
+    assert!(violations.contains("\nend_date >= start_date,2,"), "{}", violations);
+    assert!(violations.contains("\nend_date - start_date <= 365,4,"), "{}", violations);
+    assert_eq!(violations.lines().count(), 3, "{}", violations);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn rules_that_cannot_be_checked_are_reported() {
+    let reports = analyze_fixture("orders.csv", ORDERS, &["--rule", "ends >= start_date"]).expect("run");
+    let text = report(&reports, "txt_outliers", "txt");
+    assert!(text.contains("Warning: rule 'ends >= start_date' references columns missing from the header: ends"), "{}", text);
+    assert_eq!(report(&reports, "column_rules", "csv").lines().count(), 1);
+
+    let error = analyze_fixture("orders.csv", ORDERS, &["--rule", "end_date >> start_date"]).expect_err("two operators");
+    assert!(error.contains("rule 'end_date >> start_date' has more than one comparison operator"), "{}", error);
+}
@@ -0,0 +1,66 @@
+//! Numeric column totals (`--column-totals`).
+//!
+//! Every integer or number column must be summed in the analysis pass, exactly for
+//! plain decimals however many rows add up, approximately (and marked so) once a value
+//! needs a double, while empty values are counted apart and text columns are left out.
+
+use csv_tools_core::test_support::{analyze_fixture, GoldenReports, TIMESTAMP_PLACEHOLDER};
+
+/// A thousand payments of 0.10, a quantity column with one empty value, a measurement
+/// column with one value in exponent notation, and zero-padded account codes.
+fn payments() -> Vec<u8> {
+    let mut content = "account,amount,quantity,reading,note\n".to_string();
+    for i in 0..1000 {
+        let quantity = if i == 500 { String::new() } else { (i % 7).to_string() };
+        let reading = if i == 999 { "1e3".to_string() } else { "0.5".to_string() };
+        content.push_str(&format!("{:05},0.10,{},{},paid\n", i, quantity, reading));
+    }
+    content.into_bytes()
+}
+
+/// Content of the report `name` of the payments fixture.
+fn report<'a>(reports: &'a GoldenReports, name: &str, extension: &str) -> &'a str {
+    reports.get(&format!("payments_{}_report_{}.{}", name, TIMESTAMP_PLACEHOLDER, extension)).expect(name)
+}
+
+#[test]
+fn numeric_columns_are_totaled_in_every_report() {
+    let reports = analyze_fixture("payments.csv", &payments(), &["--column-totals"]).expect("run");
+    // 0.10 added a thousand times drifts as a double (see the mean) but not as a decimal
+    let totals: Vec<&str> = report(&reports, "column_totals", "csv").lines().collect();
+    assert_eq!(totals, [
+        "column_index,column_name,inferred_type,values,empty_values,sum,sum_is_exact,min,max,mean,report_schema_version",
+        "1,amount,number,1000,0,100.00,true,0.1,0.1,0.09999999999999859,6",
+        "2,quantity,integer,999,1,2994,true,0,6,2.996996996996997,6",
+        "3,reading,number,1000,0,1499.5,false,0.5,1000,1.4995,6",
+    ]);
+
+    let text = report(&reports, "txt_outliers", "txt");
+    for line in [
+        "amount                    1000       100.00                    0         ",
+        "reading                   1000       1499.5 (approx.)          0         ",
+    ] {
+        assert!(text.contains(line), "{}\n{}", line, text);
+    }
+    let markdown = report(&reports, "md_outliers", "md");
+    assert!(markdown.contains("| quantity | integer | 999 | 2994 | 0 | 6 | 1 |"), "{}", markdown);
+    assert!(markdown.contains("| reading | number | 1000 | 1499.5 (approx.) | 0.5 | 1000 | 0 |"), "{}", markdown);
+
+    // Without the flag there is neither a report nor a section
+    let plain = analyze_fixture("payments.csv", &payments(), &[]).expect("run");
+    assert!(plain.names().iter().all(|name| !name.contains("column_totals")), "{:?}", plain.names());
+    assert!(!report(&plain, "txt_outliers", "txt").contains("COLUMN TOTALS"));
+}
+
+#[test]
+fn decimals_of_different_scales_add_exactly() {
+    let fixture = b"id,delta\n1,-12\n2,1234.5\n3,+0.125\n4,.5\n5,\n";
+    let reports = analyze_fixture("payments.csv", fixture, &["--column-totals"]).expect("run");
+    let totals = report(&reports, "column_totals", "csv");
+    assert!(totals.contains("\n1,delta,number,4,1,1223.125,true,-12,1234.5,"), "{}", totals);
+    assert!(totals.contains("\n0,id,integer,5,0,15,true,1,5,3,"), "{}", totals);
+
+    let text_only = analyze_fixture("payments.csv", b"id,name\n007,Ann\n008,Bo\n", &["--column-totals"]).expect("run");
+    assert_eq!(report(&text_only, "column_totals", "csv").lines().count(), 1);
+    assert!(report(&text_only, "txt_outliers", "txt").contains("No numeric columns to total."));
+}
@@ -0,0 +1,68 @@
+//! The formatting-preservation audit of `--profile-columns`.
+//!
+//! Columns whose values look numeric but would be damaged by numeric loading (leading
+//! zeros, fixed-width codes, digit strings past a double's precision, trailing zeros
+//! in decimals) must be flagged with what to load them as, and plain numbers must not.
+
+use csv_tools_core::test_support::{analyze_fixture, GoldenReports, TIMESTAMP_PLACEHOLDER};
+
+const FIXTURE: &[u8] = b"\
+zip,store,account,amount,quantity,ratio,name
+02134,4411,1234567890123456,1.50,1,0.5,Ann
+90210,7720,2234567890123456,2.25,22,0,Bo
+10001,5531,3234567890123456,3.00,333,10,Cy
+";
+
+/// (name, findings, load_as_text) of each column in the column_format report.
+fn findings(reports: &GoldenReports) -> Vec<(String, String, String)> {
+    reports.get(&format!("codes_column_format_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("column format report")
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            (fields[1].to_string(), fields[9].to_string(), fields[10].to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn numeric_looking_identifiers_must_be_loaded_as_text() {
+    let reports = analyze_fixture("codes.csv", FIXTURE, &["--profile-columns"]).expect("run");
+    let expected = [
+        ("zip", "leading_zeros;fixed_width_code", "true"),
+        // Codes without leading zeros survive integer loading, so text is not required
+        ("store", "fixed_width_code", "false"),
+        ("account", "long_digit_strings;fixed_width_code", "true"),
+        ("amount", "trailing_zeros", "false"),
+        ("quantity", "", "false"),
+        ("ratio", "", "false"),
+        ("name", "", "false"),
+    ];
+    let expected: Vec<(String, String, String)> = expected.iter()
+        .map(|&(name, findings, text)| (name.to_string(), findings.to_string(), text.to_string()))
+        .collect();
+    assert_eq!(findings(&reports), expected);
+
+    let markdown = reports.get(&format!("codes_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    for row in [
+        "| zip | text | values with leading zeros, fixed-width 5-digit codes | `02134` |",
+        "| store | text or integer | fixed-width 4-digit codes | `4411` |",
+        "| account | text | digit strings longer than 15 digits, fixed-width 16-digit codes | `1234567890123456` |",
+        "| amount | decimal | decimals with trailing zeros | `1.50` |",
+    ] {
+        assert!(markdown.contains(row), "{}\n{}", row, markdown);
+    }
+    for column in ["quantity", "ratio", "name"] {
+        assert!(!markdown.contains(&format!("| {} |", column)), "{}\n{}", column, markdown);
+    }
+}
+
+#[test]
+fn one_value_with_a_leading_zero_flags_the_column() {
+    let fixture = b"id,postcode\n1,2134\n2,90210\n3,01001\n";
+    let reports = analyze_fixture("codes.csv", fixture, &["--profile-columns"]).expect("run");
+    let format = reports.get(&format!("codes_column_format_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("column format report");
+    // Three digit-only values, one with a leading zero, of widths 4 to 5
+    assert!(format.contains("\n1,postcode,3,3,1,0,0,4,5,leading_zeros,true,01001,"), "{}", format);
+    assert!(format.contains("\n0,id,3,3,0,0,0,1,1,,false,"), "{}", format);
+}
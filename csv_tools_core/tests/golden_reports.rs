@@ -0,0 +1,637 @@
+//! Golden report comparison through the public test-support API.
+//!
+//! Reports of an in-memory fixture must come back with the run timestamp and scratch
+//! directory normalized, be identical across runs, and a changed golden file must be
+//! reported with the first differing line.
+
+use std::fs;
+
+use csv_tools_core::test_support::{analyze_fixture, analyze_fixture_with_clock, scratch_dir, TIMESTAMP_PLACEHOLDER};
+use csv_tools_core::Clock;
+
+const FIXTURE: &[u8] = b"id,total\n1,9.50\n2,12.00\n3,100.25\n";
+
+#[test]
+fn reports_are_normalized_and_repeatable() {
+    let first = analyze_fixture("orders.csv", FIXTURE, &["--column-totals"]).expect("first run");
+    let second = analyze_fixture("orders.csv", FIXTURE, &["--column-totals"]).expect("second run");
+    assert_eq!(first, second);
+
+    let totals_name = format!("orders_column_totals_report_{}.csv", TIMESTAMP_PLACEHOLDER);
+    assert!(first.names().contains(&totals_name.as_str()), "{:?}", first.names());
+    let totals = first.get(&totals_name).expect("totals report");
+    assert!(totals.contains("1,total,number,3,0,121.75,true"), "{}", totals);
+    assert!(first.names().iter().all(|name| name.contains(TIMESTAMP_PLACEHOLDER)), "{:?}", first.names());
+}
+
+#[test]
+fn changed_golden_file_is_reported() {
+    let reports = analyze_fixture("orders.csv", FIXTURE, &[]).expect("run");
+    let golden_dir = scratch_dir("golden");
+    for name in reports.names() {
+        fs::write(golden_dir.join(name), reports.get(name).expect("report")).expect("write golden file");
+    }
+    assert_eq!(reports.compare_with_golden(&golden_dir), Ok(()));
+
+    let value_counts = format!("orders_value_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER);
+    fs::write(golden_dir.join(&value_counts), "row_length,count\n").expect("edit golden file");
+    let error = reports.compare_with_golden(&golden_dir).expect_err("golden file differs");
+    assert!(error.contains(&value_counts), "{}", error);
+    assert!(error.contains("line 1: expected"), "{}", error);
+
+    let _ = fs::remove_dir_all(&golden_dir);
+}
+
+#[test]
+fn column_reports_can_be_written_as_json() {
+    let fixture = b"id,\"a;b=c\"\n1,\"x,\"\"y\"\"\"\n2,7\n";
+    let reports = analyze_fixture("names.csv", fixture, &["--column-totals", "--column-reports-json"]).expect("run");
+    let totals_name = format!("names_column_totals_report_{}.json", TIMESTAMP_PLACEHOLDER);
+    let totals = reports.get(&totals_name).expect("JSON totals report");
+    assert!(totals.contains("\"column_name\": \"id\", \"inferred_type\": \"integer\""), "{}", totals);
+    assert!(totals.contains("\"report_schema_version\": 6}"), "{}", totals);
+    assert!(!reports.names().iter().any(|name| name.starts_with("names_column_totals_report_") && name.ends_with(".csv")));
+}
+
+#[test]
+fn mixed_decimal_separators_are_flagged() {
+    let fixture = b"id,amount,price\n1,1.5,\"1,234.50\"\n2,\"1,5\",2.25\n3,\"2,000\",\"10,000\"\n";
+    let reports = analyze_fixture("prices.csv", fixture, &["--profile-columns"]).expect("run");
+    let format_name = format!("prices_column_format_report_{}.csv", TIMESTAMP_PLACEHOLDER);
+    let format = reports.get(&format_name).expect("column format report");
+    assert!(format.contains("1,amount,3,0,0,0,0,,,mixed_decimal_separators,true,\"1,5\""), "{}", format);
+    assert!(format.contains("2,price,3,0,0,0,0,,,,false,"), "{}", format);
+
+    let markdown_name = format!("prices_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER);
+    let markdown = reports.get(&markdown_name).expect("markdown report");
+    assert!(markdown.contains("| amount | ',' decimal | 1 | 2 (1,5) |"), "{}", markdown);
+    assert!(!markdown.contains("| price |"), "{}", markdown);
+}
+
+#[test]
+fn mojibake_and_smart_quotes_are_flagged() {
+    let fixture = "id,name,note\n1,CafÃ©,ok\n2,donâ€™t,“quoted”\n3,São Paulo,naïve\n";
+    let reports = analyze_fixture("names.csv", fixture.as_bytes(), &["--profile-columns"]).expect("run");
+    let markdown_name = format!("names_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER);
+    let markdown = reports.get(&markdown_name).expect("markdown report");
+    assert!(markdown.contains("| name | mojibake (double-encoded UTF-8) | 2 | 1 (CafÃ©), 2 (donâ€™t) |"), "{}", markdown);
+    assert!(markdown.contains("| note | smart quotes | 1 | 2 (“quoted”) |"), "{}", markdown);
+    assert!(!markdown.contains("São Paulo") && !markdown.contains("naïve"), "{}", markdown);
+}
+
+#[test]
+fn column_format_shows_shortest_and_longest_values() {
+    let fixture = format!("id,name,note\n1,Ann,short\n22,Bartholomew,{}\n3,Cy,\n", "long".repeat(20));
+    let format_name = format!("people_column_format_report_{}.csv", TIMESTAMP_PLACEHOLDER);
+    let reports = analyze_fixture("people.csv", fixture.as_bytes(), &["--profile-columns"]).expect("run");
+    let format = reports.get(&format_name).expect("column format report");
+    assert!(format.contains(",2,Cy,3,11,Bartholomew,2,6\n"), "{}", format);
+    assert!(format.contains(&format!(",5,short,1,80,{} … (+20 chars),2,6\n", "long".repeat(15))), "{}", format);
+
+    let reports = analyze_fixture("people.csv", fixture.as_bytes(), &["--profile-columns", "--redact-examples"]).expect("run");
+    let format = reports.get(&format_name).expect("column format report");
+    assert!(format.contains(",2,Xx,3,11,Xxxxxxxxxxx,2,6\n"), "{}", format);
+    assert!(!format.contains("Bartholomew") && !format.contains(",short,"), "{}", format);
+}
+
+#[test]
+fn loader_hints_carry_the_file_settings() {
+    let fixture = format!("id,note\n1,\"two\nlines\"\n2,NA\n3,{}\n", "x".repeat(300));
+    let reports = analyze_fixture("orders.csv", fixture.as_bytes(), &["--loader-hints", "--null-tokens", "NA,\\N"]).expect("run");
+    let hints = reports.get(&format!("orders_loader_hints_{}.sql", TIMESTAMP_PLACEHOLDER)).expect("loader hints");
+    assert!(hints.contains("widest field 300 bytes (column note)"), "{}", hints);
+    assert!(hints.contains("line breaks in note"), "{}", hints);
+    assert!(hints.contains("COPY orders FROM '<fixture_dir>/orders.csv' WITH (FORMAT csv, HEADER true, DELIMITER ',', QUOTE '\"', NULL 'NA');"), "{}", hints);
+    assert!(hints.contains("IGNOREHEADER 1 NULL AS 'NA' EMPTYASNULL;"), "{}", hints);
+    assert!(hints.contains("VARCHAR(300)"), "{}", hints);
+    assert!(hints.contains("NULL_IF = ('', 'NA', '\\\\N')"), "{}", hints);
+}
+
+#[test]
+fn column_sizes_add_up_to_the_file() {
+    let fixture = b"id,name,blob\n1,Ana,\"x,\"\"y\"\"\"\n2,B\xc3\xa9,zzzzzzzzzz\r\n";
+    let reports = analyze_fixture("sizes.csv", fixture, &["--column-sizes"]).expect("run");
+    let sizes = reports.get(&format!("sizes_column_sizes_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("sizes report");
+    let lines: Vec<&str> = sizes.lines().collect();
+    assert_eq!(lines, [
+        "column_index,column_name,chars,bytes,share_of_file_percent,report_schema_version",
+        "2,blob,19,19,40.43,6",
+        "1,name,5,6,12.77,6",
+        "0,id,2,2,4.26,6",
+        ",(header),13,13,27.66,6",
+        ",(delimiters),4,4,8.51,6",
+        ",(line endings),3,3,6.38,6",
+    ]);
+}
+
+#[test]
+fn quoted_header_names_keep_embedded_delimiters() {
+    let fixture = b"id,\"last, first\",\"notes\n(free text)\",amount\n1,\"Doe, Jane\",ok,9.50\n2,\"Roe, Rick\",\"a\nb\",3\n";
+    for args in [&["--profile-columns"][..], &["--profile-columns", "--rows", "3..5"]] {
+        let reports = analyze_fixture("vendor.csv", fixture, args).expect("run");
+        let text = reports.get(&format!("vendor_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+        assert!(text.contains("(4 columns)"), "{:?}: {}", args, text);
+        let format = reports.get(&format!("vendor_column_format_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("format report");
+        for column in ["\n0,id,", "\n1,\"last, first\",", "\n2,notes (free text),", "\n3,amount,"] {
+            assert!(format.contains(column), "{:?}: {}", args, format);
+        }
+        assert_eq!(format.lines().count(), 5, "{:?}: {}", args, format);
+    }
+}
+
+#[test]
+fn report_time_follows_clock_and_timezone() {
+    let fixture = b"id,name\n1,ann\n2,bob\n";
+    let text_name = format!("times_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER);
+    let markdown_name = format!("times_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER);
+    let cases: [(&[&str], u64, &str); 3] = [
+        (&[], 1_767_225_600, "2026-01-01 00:00:00 UTC"),
+        (&["--timezone", "-05:00"], 1_767_225_600, "2025-12-31 19:00:00 UTC-05:00"),
+        (&["--timezone", "Europe/Berlin"], 1_783_000_000, "2026-07-02 15:46:40 CEST (UTC+02:00)"),
+    ];
+    for (args, unix_seconds, expected) in cases {
+        let reports = analyze_fixture_with_clock("times.csv", fixture, args, Clock::Fixed(unix_seconds)).expect("run");
+        let text = reports.get(&text_name).expect("text report");
+        assert!(text.contains(&format!("Report generated: {}\n", expected)), "{:?}: {}", args, text);
+        let markdown = reports.get(&markdown_name).expect("markdown report");
+        assert!(markdown.contains(&format!("*Report generated: {}*", expected)), "{:?}: {}", args, markdown);
+    }
+    assert!(analyze_fixture("times.csv", fixture, &["--timezone", "../etc/passwd"]).is_err());
+}
+
+#[test]
+fn capped_example_rows_point_to_the_char_counts_report() {
+    let fixture = b"id,v\n1,aa\n2,bb\n3,cc\n4,dd\n5,ee\n6,ff\n7,gg\n8,hhhh\n";
+    let reports = analyze_fixture("capped.csv", fixture, &["--max-example-rows", "5"]).expect("run");
+    let text = reports.get(&format!("capped_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    let note = format!("Row indices are kept for the first 5 rows of each length; 1 length has more (up to 8 rows). \
+                        Every row and its length is listed in capped_char_counts_report_{}.csv.", TIMESTAMP_PLACEHOLDER);
+    assert!(text.contains(&format!("Note: {}", note)), "{}", text);
+    let markdown = reports.get(&format!("capped_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains(&format!("*{}*", note)), "{}", markdown);
+    assert!(markdown.contains("| 1 | 9 | 100.00% | 0, 1, 2 |"), "{}", markdown);
+
+    let uncapped = analyze_fixture("capped.csv", fixture, &[]).expect("run");
+    assert!(!uncapped.get(&format!("capped_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report").contains("Row indices are kept"));
+    assert!(analyze_fixture("capped.csv", fixture, &["--max-example-rows", "4"]).is_err());
+}
+
+#[test]
+fn row_flags_mark_each_condition() {
+    let fixture = b"id,name,note\n1,ann,ok\n2,bob,ok\n\n3,\"c\nd\",ok\n4,5\" tv,ok\n5,eve,ok,x\n6,fay,ok\n7,gus,zzzzzzzzzzzzzzzzzzzz\n8,\xff\n";
+    let reports = analyze_fixture("flags.csv", fixture, &["--row-flags"]).expect("run");
+    let char_counts = reports.get(&format!("flags_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report");
+    let flags: Vec<&str> = char_counts.lines().map(|line| line.split(',').nth(4).unwrap_or("")).collect();
+    assert_eq!(flags, ["flags", "", "", "", "B", "", "", "Q", "F", "", "O", "E"], "{}", char_counts);
+}
+
+#[test]
+fn parquet_sidecar_is_written() {
+    let reports = analyze_fixture("orders.csv", FIXTURE, &["--parquet-sidecar"]).expect("run");
+    let sidecar = reports.get(&format!("orders_sidecar_{}.parquet", TIMESTAMP_PLACEHOLDER)).expect("sidecar");
+    assert!(sidecar.starts_with("PAR1") && sidecar.ends_with("PAR1"));
+    for column in ["record_number", "record_length", "field_count", "length_outlier", "report_schema_version"] {
+        assert!(sidecar.contains(column), "missing {}", column);
+    }
+}
+
+#[test]
+fn value_shapes_report_conformity_and_nonconforming_rows() {
+    let fixture = b"id,site,contact,payload\n\
+        1,https://example.com/a,ana@example.com,\"{\"\"a\"\": 1}\"\n\
+        2,http://example.org,bo@example.co.uk,[1]\n\
+        3,https://example.net?q=1,bo(at)example.com,[]\n\
+        4,,cy@example.com,\"{\"\"b\"\": [2, 3]}\"\n\
+        5,ftp://files.example.com/x,di@example.io,{oops\n";
+    let reports = analyze_fixture("contacts.csv", fixture, &["--value-shapes"]).expect("run");
+    let shapes = reports.get(&format!("contacts_value_shapes_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("value shapes report");
+    assert!(shapes.contains("1,site,url,4,4,100.00,0,,,6"), "{}", shapes);
+    assert!(shapes.contains("2,contact,email,5,4,80.00,1,3,bo(at)example.com,6"), "{}", shapes);
+    assert!(shapes.contains("3,payload,json,5,4,80.00,1,5,{oops,6"), "{}", shapes);
+    assert!(!shapes.contains(",id,"), "{}", shapes);
+    let markdown = reports.get(&format!("contacts_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("| contact | email | 5 | 80.00% | 3 (bo(at)example.com) |"), "{}", markdown);
+}
+
+#[test]
+fn null_report_counts_missing_cells_per_column() {
+    let fixture = b"id,name,score\n1,ana,NULL\n2,,NA\n3,   ,-\n4,bo,7\n5,cy,NA\n";
+    let reports = analyze_fixture("scores.csv", fixture, &["--null-report"]).expect("run");
+    let nulls = reports.get(&format!("scores_null_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("null report");
+    assert!(nulls.contains("0,id,5,0,0,0,,0,100.00,6"), "{}", nulls);
+    assert!(nulls.contains("1,name,5,1,1,0,,2,60.00,6"), "{}", nulls);
+    assert!(nulls.contains("2,score,5,0,0,4,NULL=1;NA=2;-=1,4,20.00,6"), "{}", nulls);
+    let markdown = reports.get(&format!("scores_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("## Completeness"), "{}", markdown);
+    assert!(markdown.contains("| score | 5 | 0 | 0 | NULL (1), NA (2), - (1) | 20.00% |"), "{}", markdown);
+}
+
+#[test]
+fn delimiter_is_detected_per_file_or_set_with_the_flag() {
+    let fixture = b"id;name;note\n1;ana;\"a;b\"\n2;bo;x\n3;cy;NA\n";
+    let reports = analyze_fixture("export.csv", fixture, &["--field-counts"]).expect("run");
+    let markdown = reports.get(&format!("export_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("*Delimiter: semicolon (detected)*"), "{}", markdown);
+    assert!(markdown.contains("- **Header Columns**: 3"), "{}", markdown);
+    assert!(markdown.contains("- **Rows With Another Field Count**: 0"), "{}", markdown);
+
+    assert!(markdown.contains("- **Header Fingerprint**: `"), "{}", markdown);
+    assert!(markdown.contains("` (3 columns)"), "{}", markdown);
+
+    let fixture = b"id\tname\tnote\n1\tana\ta, b\n";
+    let reports = analyze_fixture("export.tsv", fixture, &[]).expect("run");
+    let markdown = reports.get(&format!("export_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("*Delimiter: tab (file extension)*"), "{}", markdown);
+    assert!(markdown.contains("` (3 columns)"), "{}", markdown);
+
+    let fixture = b"id|name\n1|a,b\n2|c,d\n";
+    let reports = analyze_fixture("ledger.txt", fixture, &["--field-counts", "--delimiter", "pipe"]).expect("run");
+    let text = reports.get(&format!("ledger_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    assert!(text.contains("Delimiter: pipe (--delimiter)"), "{}", text);
+}
+
+#[test]
+fn analysis_json_report_lists_outliers_with_file_rows() {
+    let mut fixture = b"id,v\n".to_vec();
+    for i in 0..8 {
+        fixture.extend_from_slice(format!("{},ab\n", i).as_bytes());
+    }
+    fixture.extend_from_slice(b"8,a much longer value\n");
+    let reports = analyze_fixture("feed.csv", &fixture, &["--format", "json"]).expect("run");
+    let json = reports.get(&format!("feed_analysis_report_{}.json", TIMESTAMP_PLACEHOLDER)).expect("JSON analysis report");
+    assert!(json.starts_with("{\n  \"report_schema_version\": 6,\n  \"basename\": \"feed\","), "{}", json);
+    assert!(json.contains("\"total_rows\": 10,\n  \"error_rows\": 0,"), "{}", json);
+    assert!(json.contains("\"length_distribution\": [\n    {\"length\": 21, \"rows\": 1, \"percentage\": 10},"), "{}", json);
+    assert!(json.contains("\"page_distribution\": [\n    {\"pages\": 1, \"rows\": 10, \"percentage\": 100}\n  ],"), "{}", json);
+    assert!(json.contains("\"outlier_rows\": 1,\n  \"outliers\": [\n    {\"row_index\": 9, \"file_row\": 10, \"data_index\": 8, \"character_length\": 21,"), "{}", json);
+
+    let plain = analyze_fixture("feed.csv", &fixture, &[]).expect("run");
+    assert!(!plain.names().iter().any(|name| name.contains("_analysis_report_")), "{:?}", plain.names());
+}
+
+#[test]
+fn html_report_charts_lengths_and_lists_outliers() {
+    let mut fixture = b"id,v\n".to_vec();
+    for i in 0..8 {
+        fixture.extend_from_slice(format!("{},ab\n", i).as_bytes());
+    }
+    fixture.extend_from_slice(b"8,<a much longer>\n");
+    let reports = analyze_fixture("feed.csv", &fixture, &["--html-report"]).expect("run");
+    let html = reports.get(&format!("feed_html_report_{}.html", TIMESTAMP_PLACEHOLDER)).expect("HTML report");
+    assert!(html.starts_with("<!DOCTYPE html>"), "{}", html);
+    assert!(html.contains("<title>4 characters: 9 rows (90.00%)</title>"), "{}", html);
+    assert!(html.contains("class=\"bar outlier\""), "{}", html);
+    assert!(html.contains("<title>1 pages: 10 rows (100.00%)</title>"), "{}", html);
+    assert!(html.contains("<tr><td>9</td><td>10</td><td>8</td><td>17</td>"), "{}", html);
+    assert!(!html.contains("<a much"), "row contents are not copied into the page");
+    assert!(!html.contains("src=") && !html.contains("href="), "the page loads nothing");
+}
+
+#[test]
+fn csv_mode_measures_records_with_embedded_line_breaks() {
+    let fixture = b"id,note\n1,\"first\nsecond\"\n2,plain\n3,\"never closed\n";
+    let reports = analyze_fixture("notes.csv", fixture, &["--csv-mode"]).expect("run");
+    let char_counts = reports.get(&format!("notes_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report");
+    assert_eq!(char_counts, "row_index,file_row,data_index,character_length,physical_lines,report_schema_version\n\
+                             0,1,-1,7,1,6\n1,2,0,16,2,6\n2,4,1,7,1,6\n3,5,2,15,1,6\n");
+
+    let parallel = analyze_fixture("notes.csv", fixture, &["--csv-mode", "--parallel"]).expect("parallel run");
+    assert_eq!(parallel, reports);
+
+    let lines = analyze_fixture("notes.csv", fixture, &[]).expect("line run");
+    let line_counts = lines.get(&format!("notes_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report");
+    assert_eq!(line_counts.lines().count(), 6, "{}", line_counts);
+    assert!(analyze_fixture("notes.csv", fixture, &["--csv-mode", "--rows", "1..3"]).is_err());
+}
+
+#[test]
+fn min_row_chars_counts_short_rows_apart_from_the_statistics() {
+    let mut fixture = b"id,value\n".to_vec();
+    for i in 1..=9 {
+        fixture.extend_from_slice(format!("{},abcdef\n", i).as_bytes());
+        if i % 3 == 0 {
+            fixture.extend_from_slice(b"\x0c\n");
+        }
+    }
+    let reports = analyze_fixture("export.csv", &fixture, &["--min-row-chars", "3"]).expect("run");
+    let text = reports.get(&format!("export_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    assert!(text.contains("Total Rows:                 10\n"), "{}", text);
+    assert!(text.contains("Short Rows:                 3 under 3 characters, left out of the statistics (e.g., rows 4, 8, 12)"), "{}", text);
+    let markdown = reports.get(&format!("export_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("- **Minimum**: 8 chars"), "{}", markdown);
+    let char_counts = reports.get(&format!("export_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report");
+    assert!(char_counts.contains("\n4,5,3,1,6\n"), "{}", char_counts);
+}
+
+#[test]
+fn field_counts_report_flags_rows_that_differ_from_the_header() {
+    let fixture = b"id,name,city\n1,Ana,Lima\n2,\"Smith, Jo\",Oslo\n3,Bo\n4,Ed,Rome,extra\n5,\"Li\nWu\",Pune\n";
+    let reports = analyze_fixture("people.csv", fixture, &["--field-counts"]).expect("run");
+    let field_counts = reports.get(&format!("people_field_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("field_counts report");
+    assert_eq!(
+        field_counts,
+        "row_index,file_row,data_index,field_count,matches_header,report_schema_version\n\
+         1,2,0,3,true,6\n2,3,1,3,true,6\n3,4,2,2,false,6\n4,5,3,4,false,6\n5,6,4,3,true,6\n"
+    );
+    let markdown = reports.get(&format!("people_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("- **Header Columns**: 3\n- **Rows With Another Field Count**: 2\n"), "{}", markdown);
+    assert!(markdown.contains("| 2 | 1 | 3 |\n| 4 | 1 | 4 |\n"), "{}", markdown);
+}
+
+#[test]
+fn extract_outliers_copies_the_outlier_rows_with_their_line_numbers() {
+    let mut fixture = b"id,note\n".to_vec();
+    for i in 1..=12 {
+        let note = if i == 5 || i == 11 { "x".repeat(60) } else { "abc".to_string() };
+        fixture.extend_from_slice(format!("{},{}\n", i, note).as_bytes());
+    }
+    let reports = analyze_fixture("notes.csv", &fixture, &["--extract-outliers"]).expect("run");
+    let extract = reports.get(&format!("notes_outlier_rows_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("outlier rows");
+    assert_eq!(extract, format!("file_row,id,note\n6,5,{}\n12,11,{}\n", "x".repeat(60), "x".repeat(60)));
+}
+
+#[test]
+fn verify_sample_rereads_outlier_rows_by_byte_offset() {
+    let mut fixture = b"id,note\n".to_vec();
+    for i in 1..=12 {
+        let note = if i % 4 == 0 { "é".repeat(30) } else { "abc".to_string() };
+        fixture.extend_from_slice(format!("{},{}\r\n", i, note).as_bytes());
+    }
+    let reports = analyze_fixture("notes.csv", &fixture, &["--verify-sample", "2"]).expect("run");
+    let spot_check = reports.get(&format!("notes_spot_check_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("spot_check report");
+    let rows: Vec<&str> = spot_check.lines().skip(1).collect();
+    assert_eq!(rows.len(), 2, "{}", spot_check);
+    assert!(rows.iter().all(|row| row.ends_with(",32,32,true,true,6") || row.ends_with(",33,33,true,true,6")), "{}", spot_check);
+
+    // The same seed picks the same rows in the serial and parallel engines
+    let serial = analyze_fixture("notes.csv", &fixture, &["--verify-sample", "2", "--seed", "7"]).expect("serial run");
+    let parallel = analyze_fixture("notes.csv", &fixture, &["--verify-sample", "2", "--seed", "7", "--parallel"]).expect("parallel run");
+    assert_eq!(parallel, serial);
+}
+
+#[test]
+fn outlier_method_and_factor_set_the_threshold_named_in_the_headers() {
+    // Lengths 4 (header), 8 x 3, 5, and 14: mean 4.27, standard deviation 3.14
+    let mut fixture = b"id,v\n".to_vec();
+    for i in 1..=8 {
+        fixture.extend_from_slice(format!("{},a\n", i).as_bytes());
+    }
+    fixture.extend_from_slice(b"9,bcd\n10,abcdefghijk\n");
+    let reports = analyze_fixture("skewed.csv", &fixture, &["--outlier-method", "zscore", "--outlier-factor", "2"]).expect("run");
+    let text = reports.get(&format!("skewed_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    assert!(text.contains("Outliers by the z-score > 2 method (threshold 10.54 chars)"), "{}", text);
+    let markdown = reports.get(&format!("skewed_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("*Outliers by the z-score > 2 method (threshold 10.54 chars)*"), "{}", markdown);
+}
+
+#[test]
+fn char_report_input_recomputes_the_reports_of_an_earlier_run() {
+    let mut fixture = b"id,value\n".to_vec();
+    for i in 1..=9 {
+        fixture.extend_from_slice(format!("{},{}\n", i, "v".repeat(i)).as_bytes());
+    }
+    let first = analyze_fixture("orders.csv", &fixture, &[]).expect("first run");
+    let char_counts = first.get(&format!("orders_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report");
+
+    let rerun = analyze_fixture("orders_char_counts_report_1767225600.csv", char_counts.as_bytes(),
+                                &["--input-kind", "char-report"]).expect("rerun");
+    assert!(rerun.get(&format!("orders_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).is_none(), "{:?}", rerun.names());
+    for kind in ["value_counts_report", "pages_valuecounts_report"] {
+        let name = format!("orders_{}_{}.csv", kind, TIMESTAMP_PLACEHOLDER);
+        assert_eq!(rerun.get(&name), first.get(&name), "{}", name);
+    }
+
+    let error = analyze_fixture("orders.csv", &fixture, &["--input-kind", "char-report", "--entropy"]).expect_err("refused");
+    assert!(error.contains("--entropy needs the original input"), "{}", error);
+}
+
+#[test]
+fn sample_rows_embeds_redacted_typical_and_outlier_rows_in_the_markdown_report() {
+    let mut fixture = b"id,note\n".to_vec();
+    for i in 1..=12 {
+        let note = if i == 5 || i == 11 { "Zz".repeat(30) } else { "Abc".to_string() };
+        fixture.extend_from_slice(format!("{},{}\n", i, note).as_bytes());
+    }
+    let reports = analyze_fixture("notes.csv", &fixture, &["--sample-rows", "2"]).expect("run");
+    let markdown = reports.get(&format!("notes_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("### Typical rows (near the median of 5 chars)\nRows 1 (5 chars), 2 (5 chars):\n\n```\nid,note\n9,Xxx\n9,Xxx\n```"), "{}", markdown);
+    assert!(markdown.contains(&format!("Rows 5 (62 chars), 11 (63 chars):\n\n```\nid,note\n9,{}\n99,{}\n```", "Xx".repeat(30), "Xx".repeat(30))), "{}", markdown);
+}
+
+#[test]
+fn rows_below_the_lower_threshold_are_reported_and_extracted() {
+    let mut fixture = b"id,note\n".to_vec();
+    for i in 1..=9 {
+        let note = if i == 7 { "" } else { "abcdefghij" };
+        fixture.extend_from_slice(format!("{},{}\n", i, note).as_bytes());
+    }
+    let reports = analyze_fixture("notes.csv", &fixture, &["--extract-outliers"]).expect("run");
+    let markdown = reports.get(&format!("notes_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("Found 2 rows (20.00% of total) below the lower threshold."), "{}", markdown);
+    assert!(markdown.contains("| 2 | 1 | 7 |"), "{}", markdown);
+    let text = reports.get(&format!("notes_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    assert!(text.contains("ROWS BELOW 1.5 × IQR THRESHOLD"), "{}", text);
+    let extract = reports.get(&format!("notes_outlier_rows_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("outlier rows");
+    assert_eq!(extract, "file_row,id,note\n8,7,\n");
+}
+
+#[test]
+fn reports_flag_writes_only_the_selected_standard_reports() {
+    let reports = analyze_fixture("orders.csv", FIXTURE, &["--reports", "char_counts,outliers_md"]).expect("run");
+    assert_eq!(reports.names(), [
+        format!("orders_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER),
+        format!("orders_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER),
+    ]);
+    let error = analyze_fixture("orders.csv", FIXTURE, &["--reports", "char_counts,histogram"]).expect_err("unknown report");
+    assert!(error.contains("unknown report 'histogram' in --reports"), "{}", error);
+}
+
+#[test]
+fn aggregates_only_leaves_row_indices_and_content_out_of_the_reports() {
+    let mut fixture = b"id,note\n".to_vec();
+    for i in 1..=9 {
+        let note = if i == 7 { "" } else { "abcdefghij" };
+        fixture.extend_from_slice(format!("{},{}\n", i, note).as_bytes());
+    }
+    let reports = analyze_fixture("notes.csv", &fixture, &["--aggregates-only"]).expect("run");
+    assert!(!reports.names().iter().any(|name| name.starts_with("notes_char_counts_report_")), "{:?}", reports.names());
+    let markdown = reports.get(&format!("notes_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("*Aggregates only: row indices, row content, and the header fingerprint are left out*"), "{}", markdown);
+    assert!(markdown.contains("| 2 | 1 | N/A |"), "{}", markdown);
+    assert!(!markdown.contains("Header Fingerprint"), "{}", markdown);
+    let error = analyze_fixture("notes.csv", &fixture, &["--aggregates-only", "--extract-outliers"]).expect_err("row-level flag");
+    assert!(error.contains("--extract-outliers writes row-level details"), "{}", error);
+}
+
+#[test]
+fn outlier_reports_suggest_a_chunk_size_from_the_95th_percentile() {
+    let mut fixture = String::from("id,note\n");
+    for row in 0..18 {
+        fixture.push_str(&format!("{:02},{}\n", row, "x".repeat(1000 + row * 100)));
+    }
+    fixture.push_str(&format!("18,{}\n", "y".repeat(5000)));
+    let reports = analyze_fixture("chunks.csv", fixture.as_bytes(), &[]).expect("run");
+    let markdown = reports.get(&format!("chunks_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    // 19 of the 20 rows are at most 2703 characters long; the longest is 5003
+    assert!(markdown.contains("- **95th Percentile Row Length**: 2703 chars\n"), "{}", markdown);
+    assert!(markdown.contains("- **Suggested Chunk Size**: 4096 chars"), "{}", markdown);
+    assert!(markdown.contains("\
+| Chunk Size | Rows Above | Percentage |
+|------------|------------|------------|
+| 1024 | 18 | 90.00% |
+| 3000 | 1 | 5.00% |
+| 4096 | 1 | 5.00% |
+| 16384 | 0 | 0.00% |
+"), "{}", markdown);
+}
+
+#[test]
+fn count_mode_bytes_counts_utf8_bytes_of_every_row() {
+    let fixture = "id,note\n1,abc\n2,éé\n3,日本語\n".as_bytes();
+    let markdown_name = format!("utf8_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER);
+    let chars = analyze_fixture("utf8.csv", fixture, &[]).expect("chars run");
+    let chars_markdown = chars.get(&markdown_name).expect("markdown report");
+    assert!(chars_markdown.contains("- **Maximum**: 7 chars"), "{}", chars_markdown);
+    assert!(!chars_markdown.contains("--count-mode bytes"), "{}", chars_markdown);
+
+    // 日本語 is 9 bytes, éé is 4
+    let bytes = analyze_fixture("utf8.csv", fixture, &["--count-mode", "bytes"]).expect("bytes run");
+    let bytes_markdown = bytes.get(&markdown_name).expect("markdown report");
+    assert!(bytes_markdown.contains("- **Maximum**: 11 chars"), "{}", bytes_markdown);
+    assert!(bytes_markdown.contains("*Row lengths are counted in UTF-8 bytes (--count-mode bytes)"), "{}", bytes_markdown);
+    let char_counts = bytes.get(&format!("utf8_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report");
+    assert!(char_counts.contains("\n2,3,1,6,6\n3,4,2,11,6\n"), "{}", char_counts);
+
+    let parallel = analyze_fixture("utf8.csv", fixture, &["--count-mode", "bytes", "--parallel"]).expect("parallel run");
+    assert_eq!(parallel, bytes);
+}
+
+#[test]
+fn count_mode_utf16_and_graphemes_match_the_target_systems_length() {
+    // A flag, a skin-toned thumbs up, a family joined by ZWJ, and a decomposed é
+    let fixture = "id,note\n1,🇩🇪\n2,👍🏽\n3,👨‍👩‍👧\n4,e\u{301}\n".as_bytes();
+    let char_counts_name = format!("symbols_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER);
+    let lengths = |mode: &str| {
+        let reports = analyze_fixture("symbols.csv", fixture, &["--count-mode", mode]).expect("run");
+        let char_counts = reports.get(&char_counts_name).expect("char_counts report");
+        char_counts.lines().skip(2).map(|row| row.split(',').nth(3).expect("length").to_string()).collect::<Vec<_>>().join(" ")
+    };
+    assert_eq!(lengths("chars"), "4 4 7 4");
+    assert_eq!(lengths("utf16"), "6 6 10 4");
+    assert_eq!(lengths("graphemes"), "3 3 3 3");
+
+    let reports = analyze_fixture("symbols.csv", fixture, &["--count-mode", "graphemes"]).expect("run");
+    let text = reports.get(&format!("symbols_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    assert!(text.contains("Row lengths are counted in grapheme clusters (--count-mode graphemes)"), "{}", text);
+}
+
+#[test]
+fn count_mode_graphemes_joins_emoji_hangul_indic_and_khmer_clusters() {
+    let fixture = concat!(
+        "id,text\n",
+        // A rainbow flag and a technologist with a skin tone, both ZWJ sequences
+        "1,🏳️‍🌈🧑🏾‍💻\n",
+        // Three precomposed syllables, then 한 spelled in jamo
+        "2,한국어\u{1112}\u{1161}\u{11AB}\n",
+        // न म स्ते: the virama joins स and त into one conjunct (GB9c)
+        "3,नमस्ते\n",
+        // ញ with a sign, a spacing vowel sign, and a nasal mark
+        "4,ញ៉ាំ\n",
+        // The Bengali conjunct ক্ষ, then the Arabic number sign before its digit (Prepend)
+        "5,ক্ষ\u{600}١\n",
+    ).as_bytes();
+    let reports = analyze_fixture("scripts.csv", fixture, &["--count-mode", "graphemes"]).expect("run");
+    let char_counts = reports.get(&format!("scripts_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report");
+    // Each row is its id and comma plus the clusters of its text
+    let lengths: Vec<&str> = char_counts.lines().skip(2).map(|row| row.split(',').nth(3).expect("length")).collect();
+    assert_eq!(lengths, ["4", "6", "5", "3", "4"], "{}", char_counts);
+}
+
+#[test]
+fn wide_files_are_refused_or_profiled_from_a_column_sample() {
+    let header: Vec<String> = (1..=12).map(|column| format!("c{}", column)).collect();
+    let row: Vec<String> = (1..=12).map(|column| format!("0{}", column)).collect();
+    let fixture = format!("{}\n{}\n{}\n", header.join(","), row.join(","), row.join(","));
+
+    let error = analyze_fixture("wide.csv", fixture.as_bytes(), &["--profile-columns", "--max-columns", "10"]).expect_err("too wide");
+    assert!(error.to_string().contains("records of 12 columns, more than --max-columns 10"), "{}", error);
+    assert!(error.to_string().contains("--sample-columns <n>"), "{}", error);
+
+    let reports = analyze_fixture("wide.csv", fixture.as_bytes(), &["--profile-columns", "--max-columns", "10", "--sample-columns", "4"])
+        .expect("sampled run");
+    let format = reports.get(&format!("wide_column_format_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("column format report");
+    let columns: Vec<&str> = format.lines().skip(1).map(|line| line.split(',').nth(1).expect("name")).collect();
+    assert_eq!(columns, ["c1", "c4", "c7", "c10"], "{}", format);
+    let markdown = reports.get(&format!("wide_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("*Sampled columns: 4 of 12, one in every 3 from the first (--sample-columns)"), "{}", markdown);
+}
+
+#[test]
+fn transposed_files_are_flagged_at_the_top_of_the_outlier_reports() {
+    let row = |name: &str| {
+        let values: Vec<String> = (1..=150).map(|record| format!("{}{}", &name[..1], record)).collect();
+        format!("{},{}\n", name, values.join(","))
+    };
+    let fixture = ["id", "name", "email", "country"].map(row).concat();
+    let reports = analyze_fixture("swapped.csv", fixture.as_bytes(), &[]).expect("run");
+    let markdown = reports.get(&format!("swapped_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("> **Warning**: file appears transposed: 4 rows of 151 fields each, and the first field of every \
+                               row reads like a column name (id, name, email, country); each row is likely a column of a 4-column table"),
+            "{}", markdown);
+    let text = reports.get(&format!("swapped_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    assert!(text.contains("WARNING: file appears transposed: 4 rows of 151 fields each"), "{}", text);
+
+    // The same width with a header and records in the usual orientation is not flagged
+    let header: Vec<String> = (1..=151).map(|column| format!("c{}", column)).collect();
+    let records: Vec<String> = (1..=151).map(|column| column.to_string()).collect();
+    let usual = format!("{}\n{}\n{}\n", header.join(","), records.join(","), records.join(","));
+    let reports = analyze_fixture("usual.csv", usual.as_bytes(), &[]).expect("run");
+    let markdown = reports.get(&format!("usual_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(!markdown.contains("transposed"), "{}", markdown);
+}
+
+#[test]
+fn low_memory_reports_match_a_default_run_while_the_lengths_fit() {
+    let fixture = "id,name,amount\n1,ann,10\n2,bob,200\n3,cy,3\n4,dora,4000\n5,eve,50\n";
+    let usual = analyze_fixture("small.csv", fixture.as_bytes(), &[]).expect("default run");
+    let low = analyze_fixture("small.csv", fixture.as_bytes(), &["--low-memory"]).expect("low-memory run");
+
+    let counts = format!("small_value_counts_report_{}.txt", TIMESTAMP_PLACEHOLDER);
+    assert_eq!(low.get(&counts), usual.get(&counts));
+    let text_name = format!("small_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER);
+    let note = "Low-memory mode: statistics from the length histogram, at most";
+    let text = low.get(&text_name).expect("text report");
+    assert!(text.contains(note), "{}", text);
+    let without_note: String = text.lines().filter(|line| !line.starts_with(note)).map(|line| format!("{}\n", line)).collect();
+    assert_eq!(&without_note, usual.get(&text_name).expect("text report"));
+
+    let error = analyze_fixture("small.csv", fixture.as_bytes(), &["--low-memory", "--csv-mode"]).expect_err("conflict");
+    assert!(error.to_string().contains("--csv-mode keeps data for every row; it cannot be combined with --low-memory"), "{}", error);
+}
+
+#[test]
+fn zstd_input_is_decompressed_while_reading() {
+    let plain = "id,city\n1,Paris\n2,Oslo\n3,Paris\n4,Oslo\n5,Paris\n6,Oslo\n7,Paris\n8,Lima\n";
+    // `zstd -19` of the rows above: one compressed block with a checksum
+    let compressed: [u8; 63] = [
+        0x28, 0xb5, 0x2f, 0xfd, 0x24, 0x44, 0x95, 0x01, 0x00, 0x34, 0x02, 0x69, 0x64, 0x2c, 0x63, 0x69,
+        0x74, 0x79, 0x0a, 0x31, 0x2c, 0x50, 0x61, 0x72, 0x69, 0x73, 0x0a, 0x32, 0x2c, 0x4f, 0x73, 0x6c,
+        0x6f, 0x0a, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x2c, 0x4c, 0x69, 0x6d, 0x61, 0x0a, 0x05, 0x00,
+        0x20, 0x0b, 0x18, 0x07, 0x32, 0x03, 0x43, 0x20, 0xd9, 0x1c, 0x03, 0x59, 0xe5, 0x72, 0x70,
+    ];
+    let usual = analyze_fixture("cities.csv", plain.as_bytes(), &[]).expect("plain run");
+    let zstd = analyze_fixture("cities.csv.zst", &compressed, &[]).expect("zstd run");
+
+    let counts = format!("cities_value_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER);
+    assert!(usual.get(&counts).is_some_and(|report| report.lines().count() > 1));
+    assert_eq!(zstd.get(&counts), usual.get(&counts));
+    let markdown = zstd.get(&format!("cities_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("- **Compression**: zstd"), "{}", markdown);
+
+    // A damaged file fails its checksum like an unreadable row
+    let mut corrupt = compressed;
+    corrupt[62] ^= 0x01;
+    let reports = analyze_fixture("cities.csv.zst", &corrupt, &[]).expect("run");
+    let markdown = reports.get(&format!("cities_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("Analysis performed on 0 rows (1 with errors)"), "{}", markdown);
+}
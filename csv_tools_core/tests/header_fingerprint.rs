@@ -0,0 +1,75 @@
+//! Header fingerprints in the summary and the run history.
+//!
+//! The fingerprint must depend on the column names and their order only, so two files
+//! with the same header and different rows share it while a renamed or moved column
+//! changes it, and it must be the same in the result, the text report, and the history.
+
+use std::fs;
+use std::path::Path;
+
+use csv_tools_core::test_support::{analyze_fixture, scratch_dir, TIMESTAMP_PLACEHOLDER};
+use csv_tools_core::{analyze_csv_row_lengths, AnalysisOptions, HashAlgorithm, RowHasher};
+
+/// Analyzes `content` and returns the fingerprint and column count of its summary.
+fn fingerprint(dir: &Path, name: &str, content: &str, flags: &[&str]) -> (Option<String>, usize) {
+    let input = dir.join(name);
+    fs::write(&input, content).expect("write fixture");
+    let options = AnalysisOptions::from_flags(flags).expect("flags").with_quiet(true);
+    let result = analyze_csv_row_lengths(&input, dir.join("reports"), &options).expect("analysis");
+    (result.header_fingerprint, result.column_count)
+}
+
+#[test]
+fn fingerprint_follows_the_column_names_and_order() {
+    let dir = scratch_dir("order");
+    let (orders, columns) = fingerprint(&dir, "orders.csv", "id,name,total\n1,Ana,9.50\n", &[]);
+    assert_eq!(columns, 3);
+    let orders = orders.expect("fingerprint");
+
+    // The names end in the unit separator, so a,bc and ab,c hash apart
+    let names = b"id\x1fname\x1ftotal\x1f";
+    assert_eq!(orders, RowHasher::new(HashAlgorithm::Fnv1a, None).hash_hex(names));
+
+    let same_header = fingerprint(&dir, "more_orders.csv", "id,name,total\n2,Bo,1\n3,Cy,2\n4,Di,3\n", &[]);
+    assert_eq!(same_header, (Some(orders.clone()), 3));
+    let quoted = fingerprint(&dir, "quoted.csv", "\"id\",\"name\",\"total\"\n1,Ana,9.50\n", &[]);
+    assert_eq!(quoted, (Some(orders.clone()), 3));
+    let semicolons = fingerprint(&dir, "semicolons.csv", "id;name;total\n1;Ana;9.50\n", &["--delimiter", ";"]);
+    assert_eq!(semicolons, (Some(orders.clone()), 3));
+
+    for (name, header) in [("moved.csv", "name,id,total"), ("renamed.csv", "id,name,amount"), ("added.csv", "id,name,total,note")] {
+        let row = vec!["1"; header.split(',').count()].join(",");
+        let (changed, _) = fingerprint(&dir, name, &format!("{}\n{}\n", header, row), &[]);
+        assert_ne!(changed.as_deref(), Some(orders.as_str()), "{}", header);
+    }
+
+    let (sha256, _) = fingerprint(&dir, "orders.csv", "id,name,total\n1,Ana,9.50\n", &["--hash-algorithm", "sha256"]);
+    assert_eq!(sha256, Some(RowHasher::new(HashAlgorithm::Sha256, None).hash_hex(names)));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn fingerprint_is_written_to_the_report_and_the_history() {
+    let dir = scratch_dir("history");
+    let history = dir.join("history.csv");
+    let history_arg = history.to_string_lossy().to_string();
+    let expected = RowHasher::new(HashAlgorithm::Fnv1a, None).hash_hex(b"id\x1fname\x1f");
+
+    for rows in ["1,Ana\n", "1,Ana\n2,Bartholomew\n"] {
+        let reports = analyze_fixture("people.csv", format!("id,name\n{}", rows).as_bytes(), &["--history", &history_arg]).expect("run");
+        let text = reports.get(&format!("people_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+        assert!(text.contains(&format!("Header Fingerprint:         {} (2 columns)\n", expected)), "{}", text);
+    }
+
+    let history = fs::read_to_string(&history).expect("history written");
+    let mut lines = history.lines();
+    let columns: Vec<&str> = lines.next().expect("history header").split(',').collect();
+    let fingerprint_column = columns.iter().position(|&c| c == "header_fingerprint").expect("header_fingerprint column");
+    let count_column = columns.iter().position(|&c| c == "column_count").expect("column_count column");
+    let runs: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert_eq!(runs.len(), 2, "{}", history);
+    for run in runs {
+        assert_eq!((run[fingerprint_column], run[count_column]), (expected.as_str(), "2"), "{}", history);
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
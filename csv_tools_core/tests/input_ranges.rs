@@ -0,0 +1,91 @@
+//! `--rows` and `--bytes` ranges.
+//!
+//! A range must analyze only its rows, keep their absolute row indices, and say which
+//! part of the file the reports cover. Byte ranges take every line that starts inside
+//! them, read to its end, and accept size suffixes.
+
+use csv_tools_core::test_support::{analyze_fixture, GoldenReports, TIMESTAMP_PLACEHOLDER};
+
+/// The header and ten rows `i,` followed by `i` x's, so row index `i + 1` is `i + 2`
+/// characters long. Lines start at bytes 0, 7, 10, 14, 19, 25, 32, 40, 49, 59, 70.
+fn fixture() -> Vec<u8> {
+    let mut content = "id,val\n".to_string();
+    for i in 0..10 {
+        content.push_str(&format!("{},{}\n", i, "x".repeat(i)));
+    }
+    content.into_bytes()
+}
+
+/// (row_index, character_length) of every row in the char_counts report.
+fn rows(reports: &GoldenReports) -> Vec<(usize, usize)> {
+    reports.get(&format!("rows_char_counts_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("char_counts report")
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            (fields[0].parse().expect("row index"), fields[3].parse().expect("length"))
+        })
+        .collect()
+}
+
+/// The line of the text report naming the analyzed range, or "" for a whole-file run.
+fn partial_note(reports: &GoldenReports) -> String {
+    let text = reports.get(&format!("rows_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    text.lines().find(|line| line.starts_with("Partial analysis of")).unwrap_or("").to_string()
+}
+
+#[test]
+fn row_ranges_keep_absolute_row_indices() {
+    let reports = analyze_fixture("rows.csv", &fixture(), &["--rows", "3..6"]).expect("run");
+    assert_eq!(rows(&reports), [(3, 4), (4, 5), (5, 6)]);
+    assert_eq!(partial_note(&reports), "Partial analysis of rows 3..6 (file rows 3 to 5, bytes 14..32)");
+    let markdown = reports.get(&format!("rows_md_outliers_report_{}.md", TIMESTAMP_PLACEHOLDER)).expect("markdown report");
+    assert!(markdown.contains("*Partial analysis of rows 3..6 (file rows 3 to 5, bytes 14..32)*"), "{}", markdown);
+
+    let open_ended = analyze_fixture("rows.csv", &fixture(), &["--rows", "9.."]).expect("run");
+    assert_eq!(rows(&open_ended), [(9, 10), (10, 11)]);
+    let from_start = analyze_fixture("rows.csv", &fixture(), &["--rows", "..2"]).expect("run");
+    assert_eq!(rows(&from_start), [(0, 6), (1, 2)]);
+
+    let whole_file = analyze_fixture("rows.csv", &fixture(), &[]).expect("run");
+    assert_eq!(rows(&whole_file).len(), 11);
+    assert_eq!(partial_note(&whole_file), "");
+}
+
+#[test]
+fn byte_ranges_snap_to_line_starts() {
+    // Bytes 20..40 hold the end of row 4 and rows 5 and 6; row 6 ends at byte 39
+    let reports = analyze_fixture("rows.csv", &fixture(), &["--bytes", "20..40"]).expect("run");
+    assert_eq!(rows(&reports), [(5, 6), (6, 7)]);
+    assert_eq!(partial_note(&reports), "Partial analysis of bytes 20..40 (file rows 5 to 6, bytes 25..40)");
+
+    // A range starting on a line start includes that line, and one ending inside a line
+    // reads the line to its end
+    let reports = analyze_fixture("rows.csv", &fixture(), &["--bytes", "19..26"]).expect("run");
+    assert_eq!(rows(&reports), [(4, 5), (5, 6)]);
+
+    let mut long = "id,val\n".to_string();
+    for i in 0..20 {
+        long.push_str(&format!("{:02},{}\n", i, "x".repeat(97)));
+    }
+    // Rows are 101 bytes after the 7-byte header: row 11 starts at byte 1017, row 12 at 1118
+    let reports = analyze_fixture("rows.csv", long.as_bytes(), &["--bytes", "1K..2K"]).expect("run");
+    let indices: Vec<usize> = rows(&reports).into_iter().map(|(index, _)| index).collect();
+    assert_eq!(indices, (12..=20).collect::<Vec<_>>());
+
+    let reports = analyze_fixture("rows.csv", &fixture(), &["--bytes", "1K.."]).expect("run");
+    assert_eq!(rows(&reports), []);
+    assert_eq!(partial_note(&reports), "Partial analysis of bytes 1024.. (no rows in range)");
+}
+
+#[test]
+fn empty_and_malformed_ranges_are_rejected() {
+    for (flag, range, error) in [
+        ("--rows", "6..3", "row range '6..3' is empty"),
+        ("--rows", "a..3", "invalid row number 'a'"),
+        ("--bytes", "2K..1K", "is empty"),
+    ] {
+        let result = analyze_fixture("rows.csv", &fixture(), &[flag, range]).expect_err(range);
+        assert!(result.contains(error), "{} {}: {}", flag, range, result);
+    }
+}
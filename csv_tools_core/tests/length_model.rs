@@ -0,0 +1,39 @@
+//! `--export-model` and `--score-against` round trip.
+//!
+//! A model exported from one file must flag the records of another file whose lengths
+//! are unusual for the first, even when they are not outliers within the second.
+
+use std::fs;
+
+use csv_tools_core::test_support::{analyze_fixture, scratch_dir, TIMESTAMP_PLACEHOLDER};
+
+#[test]
+fn records_are_scored_against_an_exported_model() {
+    let dir = scratch_dir("model");
+    let model_path = dir.join("model.json");
+    let model_arg = model_path.to_string_lossy().to_string();
+
+    let mut good = b"id,name\n".to_vec();
+    for i in 0..40 {
+        good.extend_from_slice(format!("{},{}\n", i % 10, "x".repeat(5 + i % 3)).as_bytes());
+    }
+    analyze_fixture("good.csv", &good, &["--export-model", &model_arg]).expect("export run");
+    let model = fs::read_to_string(&model_path).expect("model written");
+    assert!(model.contains("\"model_version\": 1"), "{}", model);
+    assert!(model.contains("{\"name\": \"name\", \"length\": {\"count\": 40, \"min\": 5, \"max\": 7"), "{}", model);
+
+    // Every name is long, so within this file alone none of them is an outlier
+    let mut new = b"id,name\n".to_vec();
+    for i in 0..20 {
+        new.extend_from_slice(format!("{},{}\n", i % 10, "x".repeat(30)).as_bytes());
+    }
+    new.extend_from_slice(b"3,xxxxxx\n");
+    let reports = analyze_fixture("new.csv", &new, &["--score-against", &model_arg]).expect("scoring run");
+    let scores = reports.get(&format!("new_model_scores_report_{}.csv", TIMESTAMP_PLACEHOLDER)).expect("scores report");
+    assert_eq!(scores.lines().count(), 21, "{}", scores);
+    assert!(scores.contains("\n1,32,"), "{}", scores);
+    assert!(scores.contains(",true,name=30,6\n"), "{}", scores);
+    assert!(!scores.contains("\n21,"), "{}", scores);
+
+    let _ = fs::remove_dir_all(&dir);
+}
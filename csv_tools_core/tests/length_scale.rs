@@ -0,0 +1,95 @@
+//! `--length-scale` outlier fences.
+//!
+//! On the `log` and `sqrt` scales the fences must be 1.5 × IQR of the transformed
+//! lengths mapped back to characters, so a long tail of legitimate rows stops being
+//! flagged, and both outlier reports must give the fences on the scale next to the
+//! linear ones. The scale can come from the flag or from `[outliers] length_scale`.
+
+use std::fs;
+
+use csv_tools_core::test_support::{analyze_fixture, scratch_dir, GoldenReports, TIMESTAMP_PLACEHOLDER};
+
+/// Log-normal-ish row lengths: 40 rows of 10 characters, 40 of 100, 5 of 1000, and one
+/// of 5000. Q1 is 10 and Q3 is 100, so the linear upper fence is 100 + 1.5 × 90 = 235.
+fn feed() -> Vec<u8> {
+    let mut content = String::new();
+    for (length, rows) in [(10, 40), (100, 40), (1000, 5), (5000, 1)] {
+        for _ in 0..rows {
+            content.push_str(&"x".repeat(length));
+            content.push('\n');
+        }
+    }
+    content.into_bytes()
+}
+
+/// Content of the report `name` of the feed fixture.
+fn report<'a>(reports: &'a GoldenReports, name: &str, extension: &str) -> &'a str {
+    reports.get(&format!("feed_{}_report_{}.{}", name, TIMESTAMP_PLACEHOLDER, extension)).expect(name)
+}
+
+/// Asserts that every line is in the text report (`txt`) or the markdown report (`md`).
+fn assert_lines(reports: &GoldenReports, name: &str, extension: &str, lines: &[&str]) {
+    let report = report(reports, name, extension);
+    for line in lines {
+        assert!(report.lines().any(|l| l == *line), "{}\n{}", line, report);
+    }
+}
+
+#[test]
+fn log_scale_fences_leave_the_tail_alone() {
+    // ln(11) = 2.398 and ln(101) = 4.615, so the upper fence is 4.615 + 1.5 × 2.217 = 7.941,
+    // e^7.941 - 1 = 2809 chars: only the 5000-character row is above it
+    let reports = analyze_fixture("feed.csv", &feed(), &["--length-scale", "log"]).expect("run");
+    assert_lines(&reports, "txt_outliers", "txt", &[
+        "Outliers by the 1.5 × IQR of ln(1 + length) (threshold 2809.05 chars)",
+        "Values above: 2809 chars may be considered outliers",
+        "On the ln(1 + length) scale: above 7.941, below -0.928",
+        "Linear scale, for comparison: above 235 chars (6 rows), below 0 chars",
+    ]);
+    assert_lines(&reports, "md_outliers", "md", &[
+        "*Outliers by the 1.5 × IQR of ln(1 + length) (threshold 2809.05 chars)*",
+        "- On the ln(1 + length) scale: above 7.941, below -0.928",
+        "- Linear scale, for comparison: above 235 chars (6 rows), below 0 chars",
+        "Found 1 rows (1.16% of total) exceeding the outlier threshold.",
+    ]);
+
+    // The linear fence flags the 1000-character rows too, and has no comparison line
+    let linear = analyze_fixture("feed.csv", &feed(), &[]).expect("run");
+    assert_lines(&linear, "md_outliers", "md", &[
+        "- Values above: 235 chars may be considered outliers",
+        "Found 6 rows (6.98% of total) exceeding the outlier threshold.",
+    ]);
+    assert!(!report(&linear, "txt_outliers", "txt").contains("Linear scale, for comparison"));
+}
+
+#[test]
+fn sqrt_scale_compresses_the_tail_less() {
+    // √10 = 3.162 and √100 = 10, so the upper fence is 10 + 1.5 × 6.838 = 20.257, or 410 chars
+    let reports = analyze_fixture("feed.csv", &feed(), &["--length-scale", "sqrt"]).expect("run");
+    assert_lines(&reports, "txt_outliers", "txt", &[
+        "Values above: 410 chars may be considered outliers",
+        "On the √length scale: above 20.257, below -7.094",
+        "Linear scale, for comparison: above 235 chars (6 rows), below 0 chars",
+    ]);
+    assert_lines(&reports, "md_outliers", "md", &["Found 6 rows (6.98% of total) exceeding the outlier threshold."]);
+}
+
+#[test]
+fn scale_comes_from_the_config_file_unless_given_on_the_command_line() {
+    let dir = scratch_dir("config");
+    let config = dir.join("scale.conf");
+    fs::write(&config, "[outliers]\nlength_scale = log\n").expect("write config");
+    let config_arg = config.to_string_lossy().to_string();
+
+    let from_config = analyze_fixture("feed.csv", &feed(), &["--config", &config_arg]).expect("run");
+    assert_eq!(from_config, analyze_fixture("feed.csv", &feed(), &["--length-scale", "log"]).expect("run"));
+    let overridden = analyze_fixture("feed.csv", &feed(), &["--config", &config_arg, "--length-scale", "linear"]).expect("run");
+    assert_lines(&overridden, "txt_outliers", "txt", &["Values above: 235 chars may be considered outliers"]);
+
+    let error = analyze_fixture("feed.csv", &feed(), &["--length-scale", "cubic"]).expect_err("unknown scale");
+    assert!(error.contains("invalid length scale 'cubic' (expected linear, log, or sqrt)"), "{}", error);
+    fs::write(&config, "[outliers]\nlength_scale = cubic\n").expect("write config");
+    let error = analyze_fixture("feed.csv", &feed(), &["--config", &config_arg]).expect_err("unknown scale");
+    assert!(error.contains("line 2: invalid length scale 'cubic'"), "{}", error);
+    let _ = fs::remove_dir_all(&dir);
+}
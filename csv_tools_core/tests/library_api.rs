@@ -0,0 +1,100 @@
+//! The library API: embedding tools call `analyze_csv_row_lengths` directly and get the
+//! summary back, with options built from the same flags as the command line, and read
+//! the `summary-json` report back into the same summary. The programs in `examples/` must
+//! compile against this API.
+
+use std::fs;
+use std::io::{self, Write};
+
+use csv_tools_core::test_support::scratch_dir;
+use csv_tools_core::{
+    analyze_csv_row_lengths, calculate_statistics, AnalysisOptions, AnalysisResult, Clock, ReportWriter, ReportWriterRegistry,
+};
+
+#[test]
+fn analysis_returns_its_summary() {
+    let dir = scratch_dir("summary");
+    let input = dir.join("orders.csv");
+    fs::write(&input, "id,name\n1,Ana\n2,Bo\n3,Bartholomew\n").expect("write fixture");
+
+    let options = AnalysisOptions::from_flags(&["--chars-per-page", "10"]).expect("flags")
+        .with_clock(Clock::Fixed(1_767_225_600));
+    let result = analyze_csv_row_lengths(&input, dir.join("reports"), &options).expect("analysis");
+    assert_eq!((result.basename.as_str(), result.timestamp.as_str()), ("orders", "1767225600"));
+    assert_eq!((result.total_rows, result.error_rows, result.total_chars, result.column_count), (4, 0, 29, 2));
+    assert_eq!(result.statistics, calculate_statistics(&[7, 5, 4, 13]));
+    assert_eq!(result.length_counts, [(13, 1), (7, 1), (5, 1), (4, 1)]);
+    assert_eq!(result.read_retries, 0);
+    assert!(dir.join("reports/orders_value_counts_report_1767225600.csv").exists());
+
+    assert!(AnalysisOptions::from_flags(&["--chars-per-page", "0"]).is_err());
+    assert!(AnalysisOptions::from_flags(&["--read-retries", "0", "--read-retry-delay", "500"]).is_ok());
+    assert!(AnalysisOptions::from_flags(&["--read-retry-delay", "0.5s"]).is_err_and(|e| e.contains("milliseconds")));
+    assert!(AnalysisOptions::from_flags(&["data.csv"]).is_err());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+struct MetricsLine;
+
+impl ReportWriter for MetricsLine {
+    fn name(&self) -> &str {
+        "metrics-line"
+    }
+
+    fn extension(&self) -> &str {
+        "txt"
+    }
+
+    fn write(&self, result: &AnalysisResult, output: &mut dyn Write) -> io::Result<()> {
+        writeln!(output, "csv.rows={} csv.outliers={}", result.total_rows, result.outlier_rows)
+    }
+}
+
+#[test]
+fn registered_report_writers_are_selected_by_name() {
+    let dir = scratch_dir("writers");
+    let input = dir.join("orders.csv");
+    fs::write(&input, "id,name\n1,Ana\n2,Bo\n3,Bartholomew\n").expect("write fixture");
+
+    let mut writers = ReportWriterRegistry::with_builtin_writers();
+    writers.register(Box::new(MetricsLine)).expect("register");
+    assert!(writers.register(Box::new(MetricsLine)).is_err());
+    let options = AnalysisOptions::from_flags(&["--writer", "metrics-line", "--writer", "summary-csv"]).expect("flags")
+        .with_clock(Clock::Fixed(1_767_225_600))
+        .with_report_writers(writers)
+        .expect("registered writers");
+    analyze_csv_row_lengths(&input, dir.join("reports"), &options).expect("analysis");
+    let metrics = fs::read_to_string(dir.join("reports/orders_metrics-line_report_1767225600.txt")).expect("custom report");
+    assert_eq!(metrics, "csv.rows=4 csv.outliers=0\n");
+    let summary = fs::read_to_string(dir.join("reports/orders_summary-csv_report_1767225600.csv")).expect("summary report");
+    assert!(summary.lines().nth(1).is_some_and(|row| row.starts_with("orders,1767225600,4,0,29,")), "{}", summary);
+
+    let unknown = AnalysisOptions::from_flags(&["--writer", "protobuf"]).expect("flags")
+        .with_report_writers(ReportWriterRegistry::with_builtin_writers());
+    assert!(unknown.is_err_and(|e| e.contains("unknown report writer 'protobuf'")));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn summary_json_reads_back_into_the_result() {
+    let dir = scratch_dir("json");
+    let input = dir.join("orders.csv");
+    fs::write(&input, "id,name\n1,Ana\n2,Bo\n3,Bartholomew\n").expect("write fixture");
+
+    let options = AnalysisOptions::from_flags(&["--writer", "summary-json"]).expect("flags")
+        .with_clock(Clock::Fixed(1_767_225_600))
+        .with_quiet(true);
+    let result = analyze_csv_row_lengths(&input, dir.join("reports"), &options).expect("analysis");
+    let written = fs::read_to_string(dir.join("reports/orders_summary-json_report_1767225600.json")).expect("summary report");
+    assert_eq!(written, format!("{}\n", result.to_json()));
+    assert_eq!(AnalysisResult::from_json(&written), Ok(result));
+
+    let future = written.replacen("\"report_schema_version\":6", "\"report_schema_version\":7", 1);
+    assert!(AnalysisResult::from_json(&future).is_err_and(|e| e.contains("unsupported report_schema_version 7")));
+    let truncated = written.replacen("\"total_rows\":4,", "", 1);
+    assert!(AnalysisResult::from_json(&truncated).is_err_and(|e| e.contains("\"total_rows\"")));
+    // Summaries written before read_retries was added still read back
+    let earlier = written.replacen("\"read_retries\":0,", "", 1);
+    assert_eq!(AnalysisResult::from_json(&earlier), AnalysisResult::from_json(&written));
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,358 @@
+//! The `--parquet-sidecar` file.
+//!
+//! The sidecar must be a Parquet file any reader can open: the footer is decoded here
+//! with a Thrift compact-protocol reader written from `parquet.thrift`, independently of
+//! the writer, and every column chunk it points at is decoded page by page. The columns
+//! must hold one row per data record, in file order, with the flags of each record.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use csv_tools_core::test_support::scratch_dir;
+use csv_tools_core::{analyze_csv_row_lengths, AnalysisOptions};
+
+/// A value of the Thrift compact protocol
+#[derive(Debug, Clone, PartialEq)]
+enum Thrift {
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    Binary(Vec<u8>),
+    List(Vec<Thrift>),
+    Struct(BTreeMap<i16, Thrift>),
+}
+
+impl Thrift {
+    /// Field `id` of a struct.
+    fn field(&self, id: i16) -> &Thrift {
+        match self {
+            Thrift::Struct(fields) => fields.get(&id).unwrap_or_else(|| panic!("missing field {} in {:?}", id, fields.keys())),
+            other => panic!("field {} of a non-struct {:?}", id, other),
+        }
+    }
+
+    fn has_field(&self, id: i16) -> bool {
+        matches!(self, Thrift::Struct(fields) if fields.contains_key(&id))
+    }
+
+    fn int(&self) -> i64 {
+        match self {
+            Thrift::Int(value) => *value,
+            other => panic!("expected an integer, found {:?}", other),
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Thrift::Binary(bytes) => std::str::from_utf8(bytes).expect("UTF-8 string"),
+            other => panic!("expected a string, found {:?}", other),
+        }
+    }
+
+    fn list(&self) -> &[Thrift] {
+        match self {
+            Thrift::List(items) => items,
+            other => panic!("expected a list, found {:?}", other),
+        }
+    }
+}
+
+/// Decoder of Thrift compact-protocol structs
+struct CompactReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl CompactReader<'_> {
+    fn byte(&mut self) -> u8 {
+        let byte = self.bytes[self.position];
+        self.position += 1;
+        byte
+    }
+
+    fn varint(&mut self) -> u64 {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte();
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+        panic!("varint longer than 10 bytes at {}", self.position);
+    }
+
+    fn zigzag(&mut self) -> i64 {
+        let value = self.varint();
+        (value >> 1) as i64 ^ -((value & 1) as i64)
+    }
+
+    /// Reads a struct up to its stop byte.
+    fn read_struct(&mut self) -> Thrift {
+        let mut fields = BTreeMap::new();
+        let mut last_id = 0i16;
+        loop {
+            let header = self.byte();
+            if header == 0 {
+                return Thrift::Struct(fields);
+            }
+            let delta = (header >> 4) as i16;
+            let id = if delta == 0 { self.zigzag() as i16 } else { last_id + delta };
+            let value = match header & 0x0F {
+                // Booleans are stored in the field header
+                1 => Thrift::Bool(true),
+                2 => Thrift::Bool(false),
+                element_type => self.read_value(element_type),
+            };
+            assert!(fields.insert(id, value).is_none(), "field {} repeated", id);
+            last_id = id;
+        }
+    }
+
+    fn read_value(&mut self, element_type: u8) -> Thrift {
+        match element_type {
+            1 | 2 => Thrift::Bool(self.byte() == 1),
+            3 => Thrift::Int(self.byte() as i8 as i64),
+            4..=6 => Thrift::Int(self.zigzag()),
+            7 => {
+                let bytes: [u8; 8] = self.bytes[self.position..self.position + 8].try_into().expect("double");
+                self.position += 8;
+                Thrift::Double(f64::from_le_bytes(bytes))
+            },
+            8 => {
+                let length = self.varint() as usize;
+                let bytes = self.bytes[self.position..self.position + length].to_vec();
+                self.position += length;
+                Thrift::Binary(bytes)
+            },
+            9 | 10 => {
+                let header = self.byte();
+                let size = match header >> 4 {
+                    15 => self.varint() as usize,
+                    size => size as usize,
+                };
+                Thrift::List((0..size).map(|_| self.read_value(header & 0x0F)).collect())
+            },
+            12 => self.read_struct(),
+            other => panic!("unsupported compact type {} at {}", other, self.position),
+        }
+    }
+}
+
+/// Decoded sidecar: the file metadata, and the values and data pages of each column by name
+struct Sidecar {
+    metadata: Thrift,
+    columns: BTreeMap<String, Vec<i64>>,
+    pages: BTreeMap<String, usize>,
+}
+
+/// Analyzes `content` with `--parquet-sidecar` (and `flags`) and returns the sidecar bytes.
+fn sidecar_bytes(dir: &Path, content: &[u8], flags: &[&str]) -> Vec<u8> {
+    let input = dir.join("feed.csv");
+    let output = dir.join("reports");
+    fs::write(&input, content).expect("write fixture");
+    let mut all_flags = vec!["--parquet-sidecar"];
+    all_flags.extend_from_slice(flags);
+    let options = AnalysisOptions::from_flags(&all_flags).expect("flags").with_quiet(true);
+    analyze_csv_row_lengths(&input, &output, &options).expect("analysis");
+    let sidecar = fs::read_dir(&output).expect("reports").map(|entry| entry.expect("entry").path())
+        .find(|path| path.file_name().is_some_and(|name| {
+            let name = name.to_string_lossy();
+            name.starts_with("feed_sidecar_") && name.ends_with(".parquet")
+        }))
+        .expect("sidecar written");
+    fs::read(sidecar).expect("read sidecar")
+}
+
+/// Decodes the footer, then every page of every column chunk it lists, checking the
+/// layout on the way.
+fn decode(bytes: &[u8]) -> Sidecar {
+    assert!(bytes.len() >= 12, "{} bytes", bytes.len());
+    assert_eq!(&bytes[..4], b"PAR1");
+    assert_eq!(&bytes[bytes.len() - 4..], b"PAR1");
+    let footer_length = u32::from_le_bytes(bytes[bytes.len() - 8..bytes.len() - 4].try_into().expect("length")) as usize;
+    let footer_start = bytes.len() - 8 - footer_length;
+    let mut reader = CompactReader { bytes: &bytes[footer_start..bytes.len() - 8], position: 0 };
+    let metadata = reader.read_struct();
+    assert_eq!(reader.position, footer_length, "FileMetaData does not fill the footer");
+
+    let schema = metadata.field(2).list();
+    let num_rows = metadata.field(3).int();
+    let row_groups = metadata.field(4).list();
+    assert_eq!(row_groups.len(), 1);
+    let chunks = row_groups[0].field(1).list();
+    assert_eq!(chunks.len(), schema.len() - 1);
+    assert_eq!(row_groups[0].field(3).int(), num_rows);
+
+    let mut columns = BTreeMap::new();
+    let mut pages = BTreeMap::new();
+    let mut chunk_start = 4;
+    let mut total_size = 0;
+    for (chunk, element) in chunks.iter().zip(&schema[1..]) {
+        let name = element.field(4).text().to_string();
+        let physical_type = element.field(1).int();
+        let meta = chunk.field(3);
+        assert_eq!(meta.field(1).int(), physical_type, "{}", name);
+        assert_eq!(meta.field(3).list(), [Thrift::Binary(name.clone().into_bytes())]);
+        assert_eq!(meta.field(4).int(), 0, "{} is not uncompressed", name);
+        assert_eq!(meta.field(5).int(), num_rows, "{}", name);
+        let size = meta.field(7).int();
+        assert_eq!(meta.field(6).int(), size, "{}", name);
+        // Chunks follow each other from the magic to the footer
+        assert_eq!(meta.field(9).int(), chunk_start, "{}", name);
+        assert_eq!(chunk.field(2).int(), chunk_start, "{}", name);
+
+        let chunk_end = (chunk_start + size) as usize;
+        let mut reader = CompactReader { bytes, position: chunk_start as usize };
+        let mut values = Vec::new();
+        let mut page_count = 0;
+        while reader.position < chunk_end {
+            let page = reader.read_struct();
+            assert_eq!(page.field(1).int(), 0, "{}: not a data page", name);
+            let page_size = page.field(3).int() as usize;
+            assert_eq!(page.field(2).int() as usize, page_size, "{}", name);
+            let data_page = page.field(5);
+            assert_eq!(data_page.field(2).int(), 0, "{}: not PLAIN", name);
+            let page_values = data_page.field(1).int() as usize;
+            let data = &bytes[reader.position..reader.position + page_size];
+            reader.position += page_size;
+            values.extend(decode_plain(physical_type, data, page_values));
+            page_count += 1;
+        }
+        assert_eq!(reader.position, chunk_end, "{}: pages overrun the chunk", name);
+        assert_eq!(values.len() as i64, num_rows, "{}", name);
+        pages.insert(name.clone(), page_count);
+        columns.insert(name, values);
+        chunk_start += size;
+        total_size += size;
+    }
+    assert_eq!(chunk_start as usize, footer_start, "bytes between the last chunk and the footer");
+    assert_eq!(row_groups[0].field(2).int(), total_size);
+    Sidecar { metadata, columns, pages }
+}
+
+/// PLAIN values of a required column: BOOLEAN (0) bit-packed, INT32 (1), INT64 (2).
+fn decode_plain(physical_type: i64, data: &[u8], count: usize) -> Vec<i64> {
+    match physical_type {
+        0 => {
+            assert_eq!(data.len(), count.div_ceil(8));
+            (0..count).map(|index| ((data[index / 8] >> (index % 8)) & 1) as i64).collect()
+        },
+        1 => {
+            assert_eq!(data.len(), count * 4);
+            data.chunks(4).map(|bytes| i32::from_le_bytes(bytes.try_into().expect("INT32")) as i64).collect()
+        },
+        2 => {
+            assert_eq!(data.len(), count * 8);
+            data.chunks(8).map(|bytes| i64::from_le_bytes(bytes.try_into().expect("INT64"))).collect()
+        },
+        other => panic!("unexpected physical type {}", other),
+    }
+}
+
+/// The key/value metadata of the file.
+fn key_values(metadata: &Thrift) -> BTreeMap<String, String> {
+    metadata.field(5).list().iter()
+        .map(|entry| (entry.field(1).text().to_string(), entry.field(2).text().to_string()))
+        .collect()
+}
+
+/// A header and 13 records: row 2 spans rows 2 and 3, row 4 is short a field, row 5 is
+/// 207 characters long, and row 6 is not valid UTF-8.
+const FEED: &[u8] = b"\
+id,name,note
+1,ann,ok
+2,\"b
+ob\",ok
+3,cy
+4,dee,zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz
+5,\xff
+6,eve,ok
+7,fay,ok
+8,gus,ok
+9,hal,ok
+10,ivy,ok
+11,jo,ok
+12,kim,ok
+13,lu,ok
+";
+
+#[test]
+fn footer_describes_the_schema_and_the_row_group() {
+    let dir = scratch_dir("footer");
+    let sidecar = decode(&sidecar_bytes(&dir, FEED, &[]));
+    let metadata = &sidecar.metadata;
+    assert_eq!(metadata.field(1).int(), 1);
+    assert_eq!(metadata.field(3).int(), 13);
+
+    // The root names its children; the leaves are required INT64 (2), INT32 (1), BOOLEAN (0)
+    let schema = metadata.field(2).list();
+    assert_eq!(schema[0].field(4).text(), "schema");
+    assert_eq!(schema[0].field(5).int(), 8);
+    assert!(!schema[0].has_field(1));
+    let leaves: Vec<(&str, i64, i64)> = schema[1..].iter()
+        .map(|element| (element.field(4).text(), element.field(1).int(), element.field(3).int()))
+        .collect();
+    assert_eq!(leaves, [
+        ("record_number", 2, 0),
+        ("row_index", 2, 0),
+        ("record_length", 2, 0),
+        ("field_count", 1, 0),
+        ("length_outlier", 0, 0),
+        ("field_count_mismatch", 0, 0),
+        ("multiline", 0, 0),
+        ("unreadable", 0, 0),
+    ]);
+
+    let key_values = key_values(metadata);
+    assert_eq!(key_values.keys().collect::<Vec<_>>(), ["length_upper_fence", "report_schema_version", "source"]);
+    assert_eq!(key_values["report_schema_version"], "6");
+    assert_eq!(key_values["source"], "feed");
+    assert!(metadata.field(6).text().starts_with("csv_tools_core "), "{:?}", metadata.field(6));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn columns_hold_one_row_per_record() {
+    let dir = scratch_dir("columns");
+    let sidecar = decode(&sidecar_bytes(&dir, FEED, &[]));
+    let column = |name: &str| sidecar.columns[name].clone();
+
+    assert_eq!(column("record_number"), (0..13).collect::<Vec<_>>());
+    // Records after the two-line one start a row later
+    assert_eq!(column("row_index"), [1, 2, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+    assert_eq!(column("record_length"), [8, 11, 4, 207, 0, 8, 8, 8, 8, 9, 8, 9, 8]);
+    assert_eq!(column("field_count"), [3, 3, 2, 3, 0, 3, 3, 3, 3, 3, 3, 3, 3]);
+    // The fence comes from the physical rows (Q3 9 + 1.5 × 1), so the two-line record is above it
+    assert_eq!(key_values(&sidecar.metadata)["length_upper_fence"], "10.50");
+    assert_eq!(column("length_outlier"), [0, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(column("field_count_mismatch"), [0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(column("multiline"), [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(column("unreadable"), [0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn large_files_are_split_into_pages() {
+    // More records than the 65536 values of a page, and a row range starting mid-file
+    let dir = scratch_dir("pages");
+    let mut content = b"id,flag\n".to_vec();
+    for i in 0..70_000 {
+        content.extend_from_slice(if i % 3 == 0 { format!("{},yes,extra\n", i) } else { format!("{},no\n", i) }.as_bytes());
+    }
+    let bytes = sidecar_bytes(&dir, &content, &[]);
+    let sidecar = decode(&bytes);
+    assert_eq!(sidecar.metadata.field(3).int(), 70_000);
+    assert_eq!(sidecar.columns["row_index"], (1..=70_000).collect::<Vec<_>>());
+    let mismatches: Vec<i64> = (0..70_000).map(|i| (i % 3 == 0) as i64).collect();
+    assert_eq!(sidecar.columns["field_count_mismatch"], mismatches);
+    assert!(sidecar.pages.values().all(|&pages| pages == 2), "{:?}", sidecar.pages);
+
+    let dir_range = scratch_dir("pages_range");
+    let sidecar = decode(&sidecar_bytes(&dir_range, &content, &["--rows", "65530..65545"]));
+    assert_eq!(sidecar.columns["record_number"], (0..15).collect::<Vec<_>>());
+    assert_eq!(sidecar.columns["row_index"], (65530..65545).collect::<Vec<_>>());
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::remove_dir_all(&dir_range);
+}
@@ -0,0 +1,81 @@
+//! `--read-ahead` double buffering.
+//!
+//! Reading on a background thread must not change what is counted: rows split across
+//! buffer boundaries, line endings, quoted records spanning lines, and ranges must come
+//! out of a read-ahead run exactly as out of a plain one, whether the two buffers are
+//! recycled thousands of times (`--read-buffer-size 4K`) or a few times (1 MiB each), and
+//! `--parallel` must still find the checksum of the read buffers in its rows.
+
+use csv_tools_core::test_support::{analyze_fixture, GoldenReports};
+
+/// About `size` bytes of rows from 1 to 9000 characters, with CRLF and LF endings and
+/// a quoted field spanning two lines every 97 rows.
+fn fixture(size: usize) -> Vec<u8> {
+    let mut content = b"id,note\n".to_vec();
+    let mut row = 0;
+    while content.len() < size {
+        let length = (row * 7919) % 9000 + 1;
+        let value = if row % 97 == 0 {
+            format!("\"{}\n{}\"", "q".repeat(length / 2), "r".repeat(length / 2))
+        } else {
+            "x".repeat(length)
+        };
+        let ending = if row % 5 == 0 { "\r\n" } else { "\n" };
+        content.extend_from_slice(format!("{},{}{}", row, value, ending).as_bytes());
+        row += 1;
+    }
+    content
+}
+
+/// Reports of `content` analyzed with `args`.
+fn analyze(content: &[u8], args: &[&str]) -> GoldenReports {
+    analyze_fixture("feed.csv", content, args).unwrap_or_else(|e| panic!("{:?}: {}", args, e))
+}
+
+#[test]
+fn small_buffers_split_rows_without_changing_the_reports() {
+    let content = fixture(400 << 10);
+    let plain = analyze(&content, &["--profile-columns"]);
+    assert_eq!(analyze(&content, &["--profile-columns", "--read-ahead", "--read-buffer-size", "4K"]), plain);
+    assert_eq!(analyze(&content, &["--profile-columns", "--read-ahead"]), plain);
+    // The adaptive buffer of a plain run grows past 4K for the longest rows; a fixed one does not
+    assert_eq!(analyze(&content, &["--profile-columns", "--read-buffer-size", "4K"]), plain);
+}
+
+#[test]
+fn full_size_buffers_are_recycled_across_the_file() {
+    // Three times the two 1 MiB buffers, so each is refilled after being handed back
+    let content = fixture(6 << 20);
+    assert_eq!(analyze(&content, &["--read-ahead"]), analyze(&content, &[]));
+}
+
+#[test]
+fn ranges_read_ahead_from_their_start() {
+    let content = fixture(200 << 10);
+    for range in [["--rows", "40..90"], ["--bytes", "50K..120K"]] {
+        let plain = analyze(&content, &range);
+        let read_ahead = analyze(&content, &[range[0], range[1], "--read-ahead", "--read-buffer-size", "4K"]);
+        assert_eq!(read_ahead, plain, "{:?}", range);
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn read_ahead_is_refused_with_async() {
+    let error = analyze_fixture("feed.csv", &fixture(1 << 10), &["--read-ahead", "--async"]).expect_err("async");
+    assert!(error.contains("--read-ahead applies to the blocking analyzer; it cannot be combined with --async"), "{}", error);
+}
+
+#[test]
+fn parallel_checksums_match_across_buffers_and_batches() {
+    // The reader's checksum is taken over the read buffers, the workers' over their rows;
+    // rows split across 4K buffers and 1 MiB batches must still add up
+    let content = fixture(3 << 20);
+    for range in [&[][..], &["--bytes", "50K..2500K"], &["--rows", "40..900"]] {
+        let plain = analyze(&content, range);
+        for extra in [&["--parallel"][..], &["--parallel", "--read-ahead", "--read-buffer-size", "4K"]] {
+            let args = [range, extra].concat();
+            assert_eq!(analyze(&content, &args), plain, "{:?}", args);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! House rules in the `[recommendations]` section of the config file.
+//!
+//! A rule whose condition holds must show up in the Recommendations section of both
+//! outlier reports with its template filled in from the metrics, after the built-in
+//! rules unless `defaults = false`. A rule over an unknown metric must fail the run.
+
+use std::fs;
+
+use csv_tools_core::test_support::{analyze_fixture, scratch_dir, GoldenReports, TIMESTAMP_PLACEHOLDER};
+
+/// Built-in advice that the fixture's skewed lengths trigger
+const SKEW_ADVICE: &str = "The distribution is heavily skewed right";
+
+/// Twenty short rows and one of 203 characters, the only outlier
+fn fixture() -> Vec<u8> {
+    let mut content = "id,val\n".to_string();
+    for i in 0..20 {
+        content.push_str(&format!("{},{}\n", i, "x".repeat(5 + i % 3)));
+    }
+    content.push_str(&format!("99,{}\n", "y".repeat(200)));
+    content.into_bytes()
+}
+
+/// Analyzes the fixture with `config` as its config file.
+fn analyze_with_config(name: &str, config: &str) -> Result<GoldenReports, String> {
+    let dir = scratch_dir(name);
+    let config_path = dir.join("analyzer.conf");
+    fs::write(&config_path, config).expect("write config");
+    let result = analyze_fixture("data.csv", &fixture(), &["--config", &config_path.to_string_lossy()]);
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+/// The Recommendations section of the text and markdown reports.
+fn recommendations(reports: &GoldenReports) -> (String, String) {
+    let section = |name: &str, extension: &str, heading: &str| {
+        let report = reports.get(&format!("data_{}_report_{}.{}", name, TIMESTAMP_PLACEHOLDER, extension)).expect("outlier report");
+        report[report.find(heading).expect("Recommendations section")..].to_string()
+    };
+    (section("txt_outliers", "txt", "RECOMMENDATIONS"), section("md_outliers", "md", "## Recommendations"))
+}
+
+#[test]
+fn house_rules_follow_the_built_in_advice() {
+    let reports = analyze_with_config("house_rules", "\
+[recommendations]
+rule = max_length > 100 => Rows over 100 chars go to the blob pipeline (largest: {max_length} chars, {outlier_rows} outliers).
+warning = total_rows >= 20 && error_rows == 0 => Big file of {total_rows} rows.
+rule = max_length > upper_threshold * 100 => Never shown.
+").expect("run");
+    let (text, markdown) = recommendations(&reports);
+
+    let rule = "- Rows over 100 chars go to the blob pipeline (largest: 203 chars, 1 outliers).\n";
+    for section in [&text, &markdown] {
+        let skew = section.find(SKEW_ADVICE).expect("built-in advice");
+        let house = section.find(rule).expect("house rule");
+        assert!(skew < house, "{}", section);
+        assert!(!section.contains("Never shown"), "{}", section);
+    }
+    assert!(text.contains("- Warning: Big file of 22 rows.\n"), "{}", text);
+    assert!(markdown.contains("- **Warning**: Big file of 22 rows.\n"), "{}", markdown);
+}
+
+#[test]
+fn defaults_false_keeps_only_the_house_rules() {
+    let reports = analyze_with_config("no_defaults", "\
+[recommendations]
+defaults = false
+rule = outlier_percent > 1 => {outlier_percent}% of rows are outliers.
+").expect("run");
+    let (text, markdown) = recommendations(&reports);
+    for section in [&text, &markdown] {
+        assert!(!section.contains(SKEW_ADVICE), "{}", section);
+        assert!(section.contains("- 4.55% of rows are outliers.\n"), "{}", section);
+    }
+
+    let without_config = analyze_fixture("data.csv", &fixture(), &[]).expect("run");
+    let (text, _) = recommendations(&without_config);
+    assert!(text.contains(SKEW_ADVICE), "{}", text);
+}
+
+#[test]
+fn rules_over_unknown_metrics_fail_the_run() {
+    let error = analyze_with_config("unknown_metric", "[recommendations]\nrule = max_len > 100 => Typo.\n")
+        .expect_err("unknown metric");
+    assert!(error.contains("line 2: unknown metric 'max_len'"), "{}", error);
+
+    let error = analyze_with_config("bad_defaults", "[recommendations]\ndefaults = maybe\n").expect_err("invalid defaults");
+    assert!(error.contains("defaults must be true or false, found 'maybe'"), "{}", error);
+}
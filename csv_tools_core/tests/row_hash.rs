@@ -0,0 +1,87 @@
+//! Known-answer tests of the row hash algorithms against their published vectors.
+//!
+//! SHA-256 is checked against the FIPS 180-2 examples, HMAC-SHA256 against RFC 4231,
+//! XXH64 against the vectors of the reference implementation, and FNV-1a against the
+//! FNV reference values. Every digest goes through [`RowHasher::hash_hex`], so the name
+//! prefixes of the written hashes are checked along the way.
+
+use csv_tools_core::{HashAlgorithm, RowHasher};
+
+fn unsalted(algorithm: HashAlgorithm) -> RowHasher {
+    RowHasher { algorithm, salt: None }
+}
+
+#[test]
+fn sha256_matches_the_fips_examples() {
+    let hasher = unsalted(HashAlgorithm::Sha256);
+    for (input, digest) in [
+        (&b""[..], "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        (b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        // 56 bytes: the padding spills into a second block
+        (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+        (&[b'a'; 1_000_000], "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"),
+    ] {
+        assert_eq!(hasher.hash_hex(input), format!("sha256:{}", digest), "{} bytes", input.len());
+    }
+}
+
+#[test]
+fn hmac_sha256_matches_rfc_4231() {
+    // Test case 5 truncates its output and is left out
+    let long_key = vec![0xaa; 131];
+    let cases: [(&[u8], &[u8], &str); 6] = [
+        (&[0x0b; 20], b"Hi There", "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+        (b"Jefe", b"what do ya want for nothing?", "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+        (&[0xaa; 20], &[0xdd; 50], "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe"),
+        (
+            &(1..=25).collect::<Vec<u8>>(),
+            &[0xcd; 50],
+            "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+        ),
+        (
+            &long_key,
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        ),
+        (
+            &long_key,
+            b"This is a test using a larger than block-size key and a larger than block-size data. \
+              The key needs to be hashed before being used by the HMAC algorithm.",
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+        ),
+    ];
+    for (number, (key, data, digest)) in cases.into_iter().enumerate() {
+        let hasher = RowHasher { algorithm: HashAlgorithm::Sha256, salt: Some(key.to_vec()) };
+        assert_eq!(hasher.hash_hex(data), format!("hmac-sha256:{}", digest), "test case {}", [1, 2, 3, 4, 6, 7][number]);
+    }
+    // A salt given as text keys the HMAC with its UTF-8 bytes
+    assert_eq!(
+        RowHasher::new(HashAlgorithm::Sha256, Some("Jefe")).hash_hex(b"what do ya want for nothing?"),
+        "hmac-sha256:5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+    );
+}
+
+#[test]
+fn xxh64_matches_the_reference_vectors() {
+    let hasher = unsalted(HashAlgorithm::Xxh64);
+    for (input, digest) in [
+        (&b""[..], "ef46db3751d8e999"),
+        (b"a", "d24ec4f1a98c6e5b"),
+        (b"abc", "44bc2cf5ad770999"),
+        (b"xxhash", "32dd38952c4bc720"),
+        // 39 bytes: one 32-byte stripe, then the 8-byte, 4-byte, and single-byte tails
+        (b"Nobody inspects the spammish repetition", "fbcea83c8a378bf1"),
+    ] {
+        assert_eq!(hasher.hash_hex(input), format!("xxh64:{}", digest), "{:?}", String::from_utf8_lossy(input));
+    }
+}
+
+#[test]
+fn fnv1a_matches_the_reference_values_and_is_written_bare() {
+    let hasher = unsalted(HashAlgorithm::Fnv1a);
+    for (input, digest) in [(&b""[..], "cbf29ce484222325"), (b"a", "af63dc4c8601ec8c"), (b"foobar", "85944171f73967e8")] {
+        assert_eq!(hasher.hash_hex(input), digest, "{:?}", String::from_utf8_lossy(input));
+    }
+    // The salt is hashed before the data
+    assert_eq!(RowHasher::new(HashAlgorithm::Fnv1a, Some("foo")).hash_hex(b"bar"), "fnv1a+salt:85944171f73967e8");
+}
@@ -0,0 +1,114 @@
+//! `--timezone` with slim zone files.
+//!
+//! A slim TZif file (zic's default since 2020b) lists transitions only until its footer's
+//! POSIX `TZ` rule takes over. Report times after the last transition must follow that
+//! rule, with daylight saving time switching on the rule's dates in both hemispheres.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use csv_tools_core::test_support::{analyze_fixture_with_clock, scratch_dir, TIMESTAMP_PLACEHOLDER};
+use csv_tools_core::Clock;
+
+/// A TZif version 2 file with a minimal version 1 block, as zic writes slim files: the
+/// transitions (time, type index), the local time types (offset, is DST, abbreviation),
+/// and the footer rule.
+fn slim_tzif(transitions: &[(i64, u8)], types: &[(i32, bool, &str)], rule: &str) -> Vec<u8> {
+    let header = |time_count: usize, type_count: usize, char_count: usize| {
+        let mut header = b"TZif2".to_vec();
+        header.extend_from_slice(&[0; 15]);
+        for count in [0, 0, 0, time_count, type_count, char_count] {
+            header.extend_from_slice(&(count as u32).to_be_bytes());
+        }
+        header
+    };
+    let mut bytes = header(0, 1, 1);
+    bytes.extend_from_slice(&[0; 7]);
+
+    let mut chars = Vec::new();
+    let mut entries = Vec::new();
+    for &(offset, is_dst, abbreviation) in types {
+        entries.extend_from_slice(&offset.to_be_bytes());
+        entries.extend_from_slice(&[is_dst as u8, chars.len() as u8]);
+        chars.extend_from_slice(abbreviation.as_bytes());
+        chars.push(0);
+    }
+    bytes.extend(header(transitions.len(), types.len(), chars.len()));
+    bytes.extend(transitions.iter().flat_map(|(at, _)| at.to_be_bytes()));
+    bytes.extend(transitions.iter().map(|&(_, index)| index));
+    bytes.extend(entries);
+    bytes.extend(chars);
+    bytes.extend(format!("\n{}\n", rule).into_bytes());
+    bytes
+}
+
+/// The zone directory of this test binary, with slim files for a few zones; `$TZDIR`
+/// points at it before any test reads a zone.
+fn zone_dir() -> &'static Path {
+    static ZONE_DIR: OnceLock<PathBuf> = OnceLock::new();
+    ZONE_DIR.get_or_init(|| {
+        let dir = scratch_dir("zones");
+        // The last transition is at 1996-01-01; everything later comes from the rule
+        let zones = [
+            ("Slim/Berlin", slim_tzif(&[(820_454_400, 0)], &[(3600, false, "CET"), (7200, true, "CEST")], "CET-1CEST,M3.5.0,M10.5.0/3")),
+            ("Slim/Sydney", slim_tzif(&[(820_454_400, 0)], &[(36_000, false, "AEST"), (39_600, true, "AEDT")], "AEST-10AEDT,M10.1.0,M4.1.0/3")),
+            ("Slim/Sao_Paulo", slim_tzif(&[(820_454_400, 0)], &[(-7200, true, "-02"), (-10_800, false, "-03")], "<-03>3")),
+            // Daylight saving time all year, from day 0 at midnight to December 31 at 25:00
+            ("Slim/Always", slim_tzif(&[], &[(-18_000, false, "EST")], "EST5EDT,0/0,J365/25")),
+        ];
+        for (name, bytes) in zones {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().expect("zone directory")).expect("create zone directory");
+            fs::write(path, bytes).expect("write zone file");
+        }
+        // SAFETY: every test calls zone_dir() before it reads the environment, and the
+        // others wait on the lock while this one writes it
+        unsafe { env::set_var("TZDIR", &dir) };
+        dir
+    })
+}
+
+/// The "Report generated" time of a run at `unix_seconds` in `zone`.
+fn report_time(zone: &str, unix_seconds: u64) -> String {
+    zone_dir();
+    let reports = analyze_fixture_with_clock("times.csv", b"id\n1\n", &["--timezone", zone], Clock::Fixed(unix_seconds))
+        .unwrap_or_else(|e| panic!("{}: {}", zone, e));
+    let text = reports.get(&format!("times_txt_outliers_report_{}.txt", TIMESTAMP_PLACEHOLDER)).expect("text report");
+    text.lines()
+        .find_map(|line| line.strip_prefix("Report generated: "))
+        .unwrap_or_else(|| panic!("no report time: {}", text))
+        .to_string()
+}
+
+#[test]
+fn footer_rule_switches_to_summer_time_after_the_last_transition() {
+    for (unix_seconds, expected) in [
+        (1_767_225_600, "2026-01-01 01:00:00 CET (UTC+01:00)"),
+        (1_783_000_000, "2026-07-02 15:46:40 CEST (UTC+02:00)"),
+        // Last Sunday of March at 02:00 local, and of October at 03:00 local
+        (1_774_745_999, "2026-03-29 01:59:59 CET (UTC+01:00)"),
+        (1_774_746_000, "2026-03-29 03:00:00 CEST (UTC+02:00)"),
+        (1_792_889_999, "2026-10-25 02:59:59 CEST (UTC+02:00)"),
+        (1_792_890_000, "2026-10-25 02:00:00 CET (UTC+01:00)"),
+    ] {
+        assert_eq!(report_time("Slim/Berlin", unix_seconds), expected, "{}", unix_seconds);
+    }
+    // Before the last transition the file's own types still apply
+    assert_eq!(report_time("Slim/Berlin", 788_918_400), "1995-01-01 01:00:00 CET (UTC+01:00)");
+}
+
+#[test]
+fn southern_rules_span_the_new_year() {
+    assert_eq!(report_time("Slim/Sydney", 1_767_225_600), "2026-01-01 11:00:00 AEDT (UTC+11:00)");
+    assert_eq!(report_time("Slim/Sydney", 1_783_000_000), "2026-07-02 23:46:40 AEST (UTC+10:00)");
+}
+
+#[test]
+fn fixed_and_all_year_rules() {
+    // The last transition's type had daylight saving time; the rule has none any more
+    assert_eq!(report_time("Slim/Sao_Paulo", 1_783_000_000), "2026-07-02 10:46:40 -03 (UTC-03:00)");
+    assert_eq!(report_time("Slim/Always", 1_767_225_600), "2025-12-31 20:00:00 EDT (UTC-04:00)");
+    assert_eq!(report_time("Slim/Always", 1_783_000_000), "2026-07-02 09:46:40 EDT (UTC-04:00)");
+}